@@ -1,10 +1,47 @@
 use pyo3::prelude::*;
 use pyo3::exceptions::PyIndexError;
 use pyo3::exceptions::PyValueError;
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::exceptions::PyKeyError;
+use pyo3::exceptions::PyTypeError;
+use pyo3::types::{PyBytes, PyDict, PySlice, PyString};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+use std::os::raw::c_long;
+use std::path::PathBuf;
 use rust::union_find::UnionFind as RustUnionFind;
 use rust::fenwick_tree::FenwickTree as RustFenwickTree;
 use rust::treap::NodeInfo as RustNodeInfo;
-use rust::{kmp, sparse_table::SparseTable, treap::Treap};
+use rust::{
+    bloom_filter::BloomFilter,
+    count_min::{CountMinSketch, HeavyHitters},
+    byte_map::ByteMap,
+    dot::Highlight,
+    info::{self, Info},
+    fm_index::FmIndex,
+    graph::{self, Graph},
+    indexed_heap::IndexedHeap,
+    kd_tree::KdTree,
+    kmp,
+    math,
+    mergeable_set::MergeableSet,
+    reachability::{self, ReachabilityOracle},
+    monotonic_stack,
+    monoid::{self, Monoid},
+    range_query::{Backend, RangeQueryEngine, WorkloadHints},
+    rolling_hash::StringHasher,
+    segment_tree::IterativeSegmentTree,
+    snapshot::Snapshot,
+    sparse_table::SparseTable,
+    static_dict::StaticDict,
+    strings,
+    suffix_array::SuffixArray,
+    treap::Treap,
+    trie::Trie,
+    DataStructure,
+};
 
 #[pyclass(name="UnionFind")]
 struct PyUnionFind {
@@ -53,6 +90,11 @@ impl PyUnionFind {
         Ok(self.uf.get_parents())
     }
 
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &RustUnionFind::INFO)
+    }
 }
 
 #[pyclass(name = "FenwickTree")]
@@ -106,7 +148,20 @@ impl PyFenwickTree {
     fn internal_tree(&self) -> PyResult<Vec<i64>> {
         Ok(self.ft.get_internal_tree())
     }
-    
+
+    /// `ft[i]` is element i and `ft[a:b]` the sum of that half-open slice, clamped like list slicing.
+    fn __getitem__(&self, key: &Bound<'_, PyAny>) -> PyResult<i64> {
+        Ok(match subscript(key, self.ft.len())? {
+            Subscript::Index(i) => self.ft.sum(i..=i),
+            Subscript::Range(r) => self.ft.sum(r),
+        })
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &RustFenwickTree::<i64>::INFO)
+    }
 }
 
 // --- START: Added KMP Bindings ---
@@ -116,13 +171,49 @@ fn prefix_function(pattern: &str) -> PyResult<Vec<usize>> {
     Ok(kmp::prefix_function(pattern))
 }
 
+/// `ignore_case` folds ASCII letters; `table` is a 256-byte normalization table applied to every byte
+/// of the text and pattern before comparing (after case folding, if both are given).
+#[pyfunction]
+#[pyo3(signature = (text, pattern, ignore_case=false, table=None))]
+fn find_all(text: &str, pattern: &str, ignore_case: bool, table: Option<Vec<u8>>) -> PyResult<Vec<usize>> {
+    let mut map = if ignore_case { ByteMap::ascii_case_insensitive() } else { ByteMap::identity() };
+    if let Some(table) = table {
+        let table: [u8; 256] = table
+            .try_into()
+            .map_err(|t: Vec<u8>| PyValueError::new_err(format!("table must have 256 entries, got {}", t.len())))?;
+        map = map.then(&ByteMap::from_table(table));
+    }
+    Ok(kmp::find_all_with(text.as_bytes(), pattern.as_bytes(), &map))
+}
+
+/// Like find_all, but returns character indices so results can slice Python strings directly.
 #[pyfunction]
-fn find_all(text: &str, pattern: &str) -> PyResult<Vec<usize>> {
-    Ok(kmp::find_all(text, pattern))
+#[pyo3(signature = (text, pattern, ignore_case=false))]
+fn find_all_chars(text: &str, pattern: &str, ignore_case: bool) -> PyResult<Vec<usize>> {
+    if ignore_case {
+        // ASCII folding keeps UTF-8 boundaries, so the byte offsets still map to whole chars
+        Ok(kmp::byte_to_char_indices(text, &kmp::find_all_ignore_ascii_case(text, pattern)))
+    } else {
+        Ok(kmp::find_all_chars(text, pattern))
+    }
 }
 
 // --- END: Added KMP Bindings ---
 
+// --- START: Added Edit Distance Bindings ---
+
+#[pyfunction]
+fn edit_distance(a: &str, b: &str) -> PyResult<usize> {
+    Ok(strings::edit_distance(a, b))
+}
+
+#[pyfunction]
+fn edit_distance_at_most(a: &str, b: &str, k: usize) -> PyResult<Option<usize>> {
+    Ok(strings::distance_at_most(a, b, k))
+}
+
+// --- END: Added Edit Distance Bindings ---
+
 // --- START: Added Sparse Table Binding ---
 #[pyclass(name = "SparseTable")]
 struct PySparseTable {
@@ -143,9 +234,114 @@ impl PySparseTable {
     fn query(&self, l: usize, r: usize) -> Option<i64> {
         self.st.query(l, r)
     }
+
+    /// Write the table to a binary snapshot file.
+    fn save(&self, path: PathBuf) -> PyResult<()> {
+        self.st.save(path).map_err(snapshot_error)
+    }
+
+    /// Load a table written by `save`, without rebuilding it.
+    #[staticmethod]
+    fn load(path: PathBuf) -> PyResult<Self> {
+        Ok(PySparseTable {
+            st: SparseTable::load(path).map_err(snapshot_error)?,
+        })
+    }
+
+    /// `st[i]` is element i and `st[a:b]` the minimum of that half-open slice (None if empty),
+    /// clamped like list slicing.
+    fn __getitem__(&self, key: &Bound<'_, PyAny>) -> PyResult<Option<i64>> {
+        Ok(match subscript(key, self.st.len())? {
+            Subscript::Index(i) => self.st.min(i..=i),
+            Subscript::Range(r) => self.st.min(r),
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.st.len()
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &SparseTable::<i64>::INFO)
+    }
 }
 // --- END: Added Sparse Table Binding ---
 
+// --- START: Added Range Query Engine Binding ---
+#[pyclass(name = "RangeQueryEngine")]
+struct PyRangeQueryEngine {
+    engine: RangeQueryEngine,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyRangeQueryEngine {
+    /// Range-minimum engine; `queries` and `updates` are the expected operation counts used to
+    /// pick the backend. `backend` ("sparse_table", "segment_tree", "sqrt") forces a choice.
+    #[new]
+    #[pyo3(signature = (arr, queries = 0, updates = 0, backend = None))]
+    fn new(arr: Vec<i64>, queries: usize, updates: usize, backend: Option<&str>) -> PyResult<Self> {
+        let engine = match backend {
+            None => RangeQueryEngine::new(
+                &arr,
+                WorkloadHints {
+                    expected_queries: queries,
+                    expected_updates: updates,
+                },
+            ),
+            Some("sparse_table") => RangeQueryEngine::with_backend(&arr, Backend::SparseTable),
+            Some("segment_tree") => RangeQueryEngine::with_backend(&arr, Backend::SegmentTree),
+            Some("sqrt") => RangeQueryEngine::with_backend(&arr, Backend::SqrtDecomposition),
+            Some(other) => return Err(PyValueError::new_err(format!("unknown backend '{}'", other))),
+        };
+        Ok(PyRangeQueryEngine { engine })
+    }
+
+    /// Minimum of arr[l..=r], or None for an empty or out-of-bounds range.
+    fn query(&self, l: usize, r: usize) -> Option<i64> {
+        self.engine.query(l, r)
+    }
+
+    fn update(&mut self, index: usize, value: i64) -> PyResult<()> {
+        if index >= self.engine.len() {
+            return Err(PyIndexError::new_err("index out of range"));
+        }
+        self.engine.update(index, value);
+        Ok(())
+    }
+
+    #[getter]
+    fn backend(&self) -> String {
+        self.engine.backend().to_string()
+    }
+
+    fn explain(&self) -> String {
+        self.engine.explain()
+    }
+
+    fn __len__(&self) -> usize {
+        self.engine.len()
+    }
+
+    /// `engine[i]` is element i and `engine[a:b]` the minimum of that half-open slice (None if
+    /// empty), clamped like list slicing.
+    fn __getitem__(&self, key: &Bound<'_, PyAny>) -> PyResult<Option<i64>> {
+        Ok(match subscript(key, self.engine.len())? {
+            Subscript::Index(i) => self.engine.min(i..=i),
+            Subscript::Range(r) => self.engine.min(r),
+        })
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &RangeQueryEngine::INFO)
+    }
+}
+// --- END: Added Range Query Engine Binding ---
+
 // --- START: Added Treap Binding ---
 #[pyclass(name="TreapNode")]
 struct PyTreapNode {
@@ -175,9 +371,38 @@ fn convert_node<'py>(py: Python<'py>, node: RustNodeInfo) -> PyResult<Py<PyTreap
     })
 }
 
+/// Iteration is lazy and walks the keys in order by rank. Every mutation bumps `generation`,
+/// and a live iterator raises RuntimeError once it notices the treap changed underneath it,
+/// the same way Python's dict iterators do.
 #[pyclass(name = "Treap")]
 struct PyTreap {
     t: Treap,
+    generation: u64,
+}
+
+#[pyclass(name = "TreapIterator")]
+struct PyTreapIterator {
+    treap: Py<PyTreap>,
+    generation: u64,
+    index: usize,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyTreapIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<i64>> {
+        let treap = self.treap.borrow(py);
+        if treap.generation != self.generation {
+            return Err(PyRuntimeError::new_err("Treap changed size during iteration"));
+        }
+        let key = treap.t.kth(self.index);
+        self.index += 1;
+        Ok(key)
+    }
 }
 
 #[allow(non_local_definitions)]
@@ -185,15 +410,20 @@ struct PyTreap {
 impl PyTreap {
     #[new]
     fn new() -> Self {
-        PyTreap { t: Treap::new() }
+        PyTreap { t: Treap::new(), generation: 0 }
     }
 
     fn insert(&mut self, key: i64) {
         self.t.insert(key);
+        self.generation += 1;
     }
 
     fn remove(&mut self, key: i64) {
+        let before = self.t.len();
         self.t.remove(key); 
+        if self.t.len() != before {
+            self.generation += 1;
+        }
     }
 
     fn contains(&self, key: i64) -> bool {
@@ -222,6 +452,15 @@ impl PyTreap {
         self.t.contains(key) 
     }
 
+    fn __iter__(slf: PyRef<'_, Self>) -> PyTreapIterator {
+        let generation = slf.generation;
+        PyTreapIterator {
+            treap: slf.into(),
+            generation,
+            index: 0,
+        }
+    }
+
     #[getter]
     fn root<'py>(&self, py: Python<'py>) -> PyResult<Option<Py<PyTreapNode>>> {
         match self.t.get_structure() {
@@ -230,18 +469,1866 @@ impl PyTreap {
         }
     }
 
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &Treap::INFO)
+    }
 }
 // --- END: Added Treap Binding ---
 
-#[pymodule]
-fn advanced_ds_playground_bindings(_py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
-    // Add existing classes
-    m.add_class::<PyUnionFind>()?;
-    m.add_class::<PyFenwickTree>()?;
+// --- START: Added Trie Binding ---
+#[pyclass(name = "Trie")]
+struct PyTrie {
+    trie: Trie<()>,
+}
 
-    m.add_function(wrap_pyfunction!(prefix_function, m)?)?;
-    m.add_function(wrap_pyfunction!(find_all, m)?)?;
-    m.add_class::<PySparseTable>()?;
-    m.add_class::<PyTreap>()?;
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyTrie {
+    #[new]
+    fn new() -> Self {
+        PyTrie { trie: Trie::new() }
+    }
+
+    /// Insert a word. Returns True if it was not already present.
+    fn insert(&mut self, word: &str) -> bool {
+        self.trie.insert(word, ()).is_none()
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.trie.contains_key(word)
+    }
+
+    /// Number of stored words starting with `prefix`.
+    fn count_prefix(&self, prefix: &str) -> usize {
+        self.trie.count_prefix(prefix)
+    }
+
+    /// Longest stored word that is a prefix of `query`, or None.
+    fn longest_prefix(&self, query: &str) -> Option<String> {
+        self.trie.longest_prefix_match(query).map(|(k, _)| k.to_string())
+    }
+
+    /// Stored words starting with `prefix`, in sorted order.
+    #[pyo3(signature = (prefix = ""))]
+    fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.trie.keys_with_prefix(prefix)
+    }
+
+    fn __len__(&self) -> usize {
+        self.trie.len()
+    }
+
+    fn __contains__(&self, word: &str) -> bool {
+        self.trie.contains_key(word)
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &Trie::<()>::INFO)
+    }
+}
+// --- END: Added Trie Binding ---
+
+// --- START: Added Mergeable Set Binding ---
+#[pyclass(name = "MergeableSet")]
+#[derive(Default)]
+struct PyMergeableSet {
+    set: MergeableSet,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyMergeableSet {
+    #[new]
+    #[pyo3(signature = (values = Vec::new()))]
+    fn new(values: Vec<i64>) -> Self {
+        PyMergeableSet {
+            set: values.into_iter().collect(),
+        }
+    }
+
+    fn insert(&mut self, key: i64) {
+        self.set.insert(key);
+    }
+
+    /// Remove one occurrence of `key`. Returns True if it was present.
+    fn remove(&mut self, key: i64) -> bool {
+        self.set.remove(key)
+    }
+
+    fn min(&self) -> Option<i64> {
+        self.set.min()
+    }
+
+    fn max(&self) -> Option<i64> {
+        self.set.max()
+    }
+
+    fn pop_min(&mut self) -> PyResult<i64> {
+        self.set.pop_min().ok_or_else(|| PyIndexError::new_err("pop from an empty MergeableSet"))
+    }
+
+    fn pop_max(&mut self) -> PyResult<i64> {
+        self.set.pop_max().ok_or_else(|| PyIndexError::new_err("pop from an empty MergeableSet"))
+    }
+
+    fn kth(&self, k: usize) -> PyResult<i64> {
+        self.set.kth(k).ok_or_else(|| PyIndexError::new_err("index out of range"))
+    }
+
+    /// Move every element of `other` into this set, leaving `other` empty.
+    fn merge(slf: &Bound<'_, Self>, other: &Bound<'_, Self>) -> PyResult<()> {
+        if slf.is(other) {
+            return Err(PyValueError::new_err("cannot merge a set into itself"));
+        }
+        let taken = std::mem::take(&mut other.borrow_mut().set);
+        slf.borrow_mut().set.merge(taken);
+        Ok(())
+    }
+
+    /// Split off every element >= `key` into a new set.
+    fn split_off(&mut self, key: i64) -> PyMergeableSet {
+        PyMergeableSet {
+            set: self.set.split_off(key),
+        }
+    }
+
+    fn to_list(&self) -> Vec<i64> {
+        self.set.to_vec()
+    }
+
+    fn __len__(&self) -> usize {
+        self.set.len()
+    }
+
+    fn __contains__(&self, key: i64) -> bool {
+        self.set.contains(key)
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &MergeableSet::INFO)
+    }
+}
+// --- END: Added Mergeable Set Binding ---
+
+// --- START: Added Static Dict Binding ---
+/// Immutable str-keyed mapping backed by a minimal perfect hash. Keeps the insertion order of the
+/// source dict.
+#[pyclass(name = "StaticDict")]
+struct PyStaticDict {
+    dict: StaticDict,
+    values: Vec<PyObject>,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyStaticDict {
+    #[new]
+    fn new(mapping: &Bound<'_, PyDict>) -> PyResult<Self> {
+        let mut keys = Vec::with_capacity(mapping.len());
+        let mut values = Vec::with_capacity(mapping.len());
+        for (k, v) in mapping.iter() {
+            keys.push(k.extract::<String>()?);
+            values.push(v.unbind());
+        }
+        let dict = StaticDict::build(&keys).expect("dict keys are unique");
+        Ok(PyStaticDict { dict, values })
+    }
+
+    fn __getitem__(&self, py: Python<'_>, key: &str) -> PyResult<PyObject> {
+        match self.dict.index(key) {
+            Some(i) => Ok(self.values[i].clone_ref(py)),
+            None => Err(PyKeyError::new_err(key.to_string())),
+        }
+    }
+
+    #[pyo3(signature = (key, default = None))]
+    fn get(&self, py: Python<'_>, key: &str, default: Option<PyObject>) -> Option<PyObject> {
+        match self.dict.index(key) {
+            Some(i) => Some(self.values[i].clone_ref(py)),
+            None => default,
+        }
+    }
+
+    /// Position of `key` in the source mapping, or None.
+    fn index(&self, key: &str) -> Option<usize> {
+        self.dict.index(key)
+    }
+
+    fn keys(&self) -> Vec<String> {
+        self.dict.keys().map(str::to_string).collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.dict.len()
+    }
+
+    fn __contains__(&self, key: &str) -> bool {
+        self.dict.contains(key)
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &StaticDict::INFO)
+    }
+}
+// --- END: Added Static Dict Binding ---
+
+// --- START: Added Snapshot Bindings ---
+/// Corrupt or mismatched snapshots raise ValueError; file system problems keep their OSError type.
+fn snapshot_error(e: std::io::Error) -> PyErr {
+    if e.kind() == std::io::ErrorKind::InvalidData {
+        PyValueError::new_err(e.to_string())
+    } else {
+        e.into()
+    }
+}
+
+/// Suffix array over the UTF-8 bytes of a string. Positions are byte offsets, which equal str
+/// indices for ASCII text.
+#[pyclass(name = "SuffixArray")]
+struct PySuffixArray {
+    sa: SuffixArray,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PySuffixArray {
+    #[new]
+    fn new(text: &str) -> Self {
+        PySuffixArray { sa: SuffixArray::new(text) }
+    }
+
+    fn count(&self, pattern: &str) -> usize {
+        self.sa.count_occurrences(pattern)
+    }
+
+    /// Sorted start offsets of every occurrence of `pattern`.
+    fn find(&self, pattern: &str) -> Vec<usize> {
+        self.sa.find_occurrences(pattern)
+    }
+
+    #[getter]
+    fn sa(&self) -> Vec<usize> {
+        self.sa.sa().to_vec()
+    }
+
+    #[getter]
+    fn lcp(&self) -> Vec<usize> {
+        self.sa.lcp().to_vec()
+    }
+
+    fn save(&self, path: PathBuf) -> PyResult<()> {
+        self.sa.save(path).map_err(snapshot_error)
+    }
+
+    #[staticmethod]
+    fn load(path: PathBuf) -> PyResult<Self> {
+        Ok(PySuffixArray {
+            sa: SuffixArray::load(path).map_err(snapshot_error)?,
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.sa.len()
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &SuffixArray::INFO)
+    }
+}
+
+/// FM-index over the UTF-8 bytes of a string; the text itself is not kept. Positions are byte
+/// offsets, which equal str indices for ASCII text.
+#[pyclass(name = "FMIndex")]
+struct PyFmIndex {
+    fm: FmIndex,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyFmIndex {
+    #[new]
+    #[pyo3(signature = (text, sample_rate = 32))]
+    fn new(text: &str, sample_rate: usize) -> PyResult<Self> {
+        if sample_rate == 0 {
+            return Err(PyValueError::new_err("sample_rate must be positive"));
+        }
+        Ok(PyFmIndex {
+            fm: FmIndex::from_bytes(text.as_bytes(), sample_rate),
+        })
+    }
+
+    fn count(&self, pattern: &str) -> usize {
+        self.fm.count(pattern)
+    }
+
+    /// Sorted start offsets of every occurrence of `pattern`.
+    fn locate(&self, pattern: &str) -> Vec<usize> {
+        self.fm.locate(pattern)
+    }
+
+    fn save(&self, path: PathBuf) -> PyResult<()> {
+        self.fm.save(path).map_err(snapshot_error)
+    }
+
+    #[staticmethod]
+    fn load(path: PathBuf) -> PyResult<Self> {
+        Ok(PyFmIndex {
+            fm: FmIndex::load(path).map_err(snapshot_error)?,
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.fm.len()
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &FmIndex::INFO)
+    }
+}
+// --- END: Added Snapshot Bindings ---
+
+// --- START: Added Range Adapter Bindings ---
+/// A Python subscript resolved against a length: one index (negative counts from the end) or the
+/// half-open range of a step-1 slice, clamped the way list slicing clamps.
+enum Subscript {
+    Index(usize),
+    Range(Range<usize>),
+}
+
+fn subscript(key: &Bound<'_, PyAny>, len: usize) -> PyResult<Subscript> {
+    if let Ok(slice) = key.downcast::<PySlice>() {
+        let indices = slice.indices(len as c_long)?;
+        if indices.step != 1 {
+            return Err(PyValueError::new_err("slice step must be 1"));
+        }
+        let start = indices.start as usize;
+        return Ok(Subscript::Range(start..(indices.stop as usize).max(start)));
+    }
+    let i: isize = key.extract()?;
+    let resolved = if i < 0 { i + len as isize } else { i };
+    if resolved < 0 || resolved as usize >= len {
+        return Err(PyIndexError::new_err("index out of range"));
+    }
+    Ok(Subscript::Index(resolved as usize))
+}
+// --- END: Added Range Adapter Bindings ---
+
+// --- START: Added Info Bindings ---
+fn info_dict<'py>(py: Python<'py>, info: &Info) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("name", info.name)?;
+    dict.set_item("category", info.category)?;
+    dict.set_item("summary", info.summary)?;
+    dict.set_item("space", info.space)?;
+    let mut operations = Vec::with_capacity(info.operations.len());
+    for op in info.operations {
+        let entry = PyDict::new_bound(py);
+        entry.set_item("name", op.name)?;
+        entry.set_item("time", op.time)?;
+        entry.set_item("space", op.space)?;
+        operations.push(entry);
+    }
+    dict.set_item("operations", operations)?;
+    dict.set_item("stable", info.stable)?;
+    dict.set_item("in_place", info.in_place)?;
+    Ok(dict)
+}
+
+/// Metadata for every structure and algorithm in the crate, sorted by category and name.
+#[pyfunction]
+fn catalog(py: Python<'_>) -> PyResult<Vec<Bound<'_, PyDict>>> {
+    info::catalog().iter().map(|i| info_dict(py, i)).collect()
+}
+// --- END: Added Info Bindings ---
+
+// --- START: Added String Toolkit Bindings ---
+/// A `str` or `bytes` argument. Strings are handled as code points, so every index the toolkit takes
+/// or returns is a Python index into the object that was passed in.
+enum Text {
+    Chars(Vec<char>),
+    Bytes(Vec<u8>),
+}
+
+impl<'py> FromPyObject<'py> for Text {
+    fn extract_bound(ob: &Bound<'py, PyAny>) -> PyResult<Self> {
+        if let Ok(s) = ob.downcast::<PyString>() {
+            return Ok(Text::Chars(s.to_cow()?.chars().collect()));
+        }
+        if let Ok(b) = ob.downcast::<PyBytes>() {
+            return Ok(Text::Bytes(b.as_bytes().to_vec()));
+        }
+        Err(PyTypeError::new_err("expected str or bytes"))
+    }
+}
+
+/// Runs a generic slice algorithm on whichever representation the text has.
+macro_rules! with_text {
+    ($text:expr, $s:ident => $body:expr) => {
+        match $text {
+            Text::Chars($s) => $body,
+            Text::Bytes($s) => $body,
+        }
+    };
+}
+
+#[pyfunction]
+fn z_function(s: Text) -> Vec<usize> {
+    with_text!(s, s => strings::z_function(&s))
+}
+
+/// Palindrome radii `(odd, even)` at every centre; see `strings::manacher`.
+#[pyfunction]
+fn manacher(s: Text) -> (Vec<usize>, Vec<usize>) {
+    with_text!(s, s => strings::manacher(&s))
+}
+
+/// Longest palindromic substring, returned as the same type (`str` or `bytes`) as the input.
+#[pyfunction]
+fn longest_palindrome(py: Python<'_>, s: Text) -> PyObject {
+    match s {
+        Text::Chars(s) => {
+            let (start, len) = strings::longest_palindrome(&s);
+            s[start..start + len].iter().collect::<String>().into_py(py)
+        }
+        Text::Bytes(s) => {
+            let (start, len) = strings::longest_palindrome(&s);
+            PyBytes::new_bound(py, &s[start..start + len]).into_py(py)
+        }
+    }
+}
+
+/// Start index of each Lyndon factor.
+#[pyfunction]
+fn lyndon_factorization(s: Text) -> Vec<usize> {
+    with_text!(s, s => strings::lyndon_factorization(&s))
+}
+
+/// Start index of the lexicographically smallest rotation.
+#[pyfunction]
+fn minimal_rotation(s: Text) -> usize {
+    with_text!(s, s => strings::minimal_rotation(&s))
+}
+
+/// Double polynomial hashing for O(1) substring comparison. Ranges are half-open `[l, r)`.
+#[pyclass(name = "StringHasher")]
+struct PyStringHasher {
+    hasher: StringHasher,
+}
+
+impl PyStringHasher {
+    fn check(&self, l: usize, r: usize) -> PyResult<()> {
+        if l > r || r > self.hasher.len() {
+            return Err(PyIndexError::new_err(format!("range {}..{} out of bounds", l, r)));
+        }
+        Ok(())
+    }
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyStringHasher {
+    #[new]
+    fn new(s: Text) -> Self {
+        PyStringHasher { hasher: with_text!(s, s => StringHasher::from_slice(&s)) }
+    }
+
+    fn hash(&self, l: usize, r: usize) -> PyResult<u64> {
+        self.check(l, r)?;
+        Ok(self.hasher.hash(l, r))
+    }
+
+    /// Whether `s[l1:r1] == s[l2:r2]` (up to hash collisions).
+    fn equal(&self, l1: usize, r1: usize, l2: usize, r2: usize) -> PyResult<bool> {
+        self.check(l1, r1)?;
+        self.check(l2, r2)?;
+        Ok(self.hasher.equal(l1, r1, l2, r2))
+    }
+
+    /// Length of the longest common prefix of the suffixes starting at `i` and `j`.
+    fn lcp(&self, i: usize, j: usize) -> PyResult<usize> {
+        self.check(i, self.hasher.len())?;
+        self.check(j, self.hasher.len())?;
+        Ok(self.hasher.lcp(i, j))
+    }
+
+    fn __len__(&self) -> usize {
+        self.hasher.len()
+    }
+
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &StringHasher::INFO)
+    }
+}
+// --- END: Added String Toolkit Bindings ---
+
+// --- START: Added Segment Tree Binding ---
+/// The built-in operations, run natively on i64 values.
+#[derive(Clone, Copy)]
+enum NativeOp {
+    Sum,
+    Min,
+    Max,
+    Gcd,
+}
+
+impl NativeOp {
+    fn parse(name: &str) -> PyResult<Self> {
+        match name {
+            "sum" => Ok(NativeOp::Sum),
+            "min" => Ok(NativeOp::Min),
+            "max" => Ok(NativeOp::Max),
+            "gcd" => Ok(NativeOp::Gcd),
+            _ => Err(PyValueError::new_err(format!(
+                "unknown operation '{}' (expected sum, min, max, gcd or a callable)",
+                name
+            ))),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            NativeOp::Sum => "sum",
+            NativeOp::Min => "min",
+            NativeOp::Max => "max",
+            NativeOp::Gcd => "gcd",
+        }
+    }
+}
+
+impl Monoid<i64> for NativeOp {
+    fn identity(&self) -> i64 {
+        match self {
+            NativeOp::Sum => Monoid::<i64>::identity(&monoid::Sum),
+            NativeOp::Min => Monoid::<i64>::identity(&monoid::Min),
+            NativeOp::Max => Monoid::<i64>::identity(&monoid::Max),
+            NativeOp::Gcd => Monoid::<i64>::identity(&monoid::Gcd),
+        }
+    }
+
+    fn combine(&self, a: &i64, b: &i64) -> i64 {
+        match self {
+            NativeOp::Sum => monoid::Sum.combine(a, b),
+            NativeOp::Min => monoid::Min.combine(a, b),
+            NativeOp::Max => monoid::Max.combine(a, b),
+            NativeOp::Gcd => monoid::Gcd.combine(a, b),
+        }
+    }
+}
+
+/// A Python callable `f(a, b)` plus its identity, used as a monoid. `Monoid::combine` cannot fail,
+/// so the first exception raised by the callable is kept and re-raised when the tree call returns.
+struct PyMonoid {
+    func: PyObject,
+    identity: PyObject,
+    error: RefCell<Option<PyErr>>,
+}
+
+impl PyMonoid {
+    fn take_error(&self) -> PyResult<()> {
+        self.error.take().map_or(Ok(()), Err)
+    }
+}
+
+impl Monoid<PyObject> for PyMonoid {
+    fn identity(&self) -> PyObject {
+        Python::with_gil(|py| self.identity.clone_ref(py))
+    }
+
+    fn combine(&self, a: &PyObject, b: &PyObject) -> PyObject {
+        Python::with_gil(|py| {
+            if self.error.borrow().is_some() {
+                return a.clone_ref(py);
+            }
+            self.func.call1(py, (a, b)).unwrap_or_else(|e| {
+                *self.error.borrow_mut() = Some(e);
+                a.clone_ref(py)
+            })
+        })
+    }
+}
+
+enum SegmentTreeRepr {
+    Native(IterativeSegmentTree<i64, NativeOp>),
+    Custom(IterativeSegmentTree<PyObject, PyMonoid>),
+}
+
+/// Segment tree with point updates and half-open range queries. `op` is "sum", "min", "max" or "gcd"
+/// (native i64 arithmetic), or any callable `op(a, b)` together with its `identity`. A callable is
+/// invoked O(log n) times per operation through the interpreter, which makes queries several times
+/// slower than with a built-in; use it to prototype, then switch to a built-in where one fits.
+#[pyclass(name = "SegmentTree")]
+struct PySegmentTree {
+    repr: SegmentTreeRepr,
+}
+
+impl PySegmentTree {
+    fn len(&self) -> usize {
+        match &self.repr {
+            SegmentTreeRepr::Native(t) => t.len(),
+            SegmentTreeRepr::Custom(t) => t.len(),
+        }
+    }
+
+    fn check_custom(&self) -> PyResult<()> {
+        match &self.repr {
+            SegmentTreeRepr::Native(_) => Ok(()),
+            SegmentTreeRepr::Custom(t) => t.op().take_error(),
+        }
+    }
+
+    /// Aggregate of `range`; min and max of an empty range are None rather than the i64 sentinel.
+    fn fold(&self, py: Python<'_>, range: Range<usize>) -> PyResult<PyObject> {
+        match &self.repr {
+            SegmentTreeRepr::Native(t) => {
+                let empty = range.is_empty() && matches!(t.op(), NativeOp::Min | NativeOp::Max);
+                let value = t.query(range);
+                Ok(if empty { py.None() } else { value.into_py(py) })
+            }
+            SegmentTreeRepr::Custom(t) => {
+                let value = t.query(range);
+                t.op().take_error()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn checked_range(&self, l: usize, r: usize) -> PyResult<Range<usize>> {
+        rust::range::try_resolve(l..r, self.len())
+            .ok_or_else(|| PyIndexError::new_err(format!("range {}..{} out of bounds for length {}", l, r, self.len())))
+    }
+
+    fn descend(
+        &self,
+        py: Python<'_>,
+        pred: &PyObject,
+        search: impl FnOnce(&dyn Fn(PyObject) -> bool) -> usize,
+    ) -> PyResult<usize> {
+        let error: RefCell<Option<PyErr>> = RefCell::new(None);
+        let test = |v: PyObject| -> bool {
+            if error.borrow().is_some() {
+                return false;
+            }
+            pred.call1(py, (v,)).and_then(|r| r.bind(py).is_truthy()).unwrap_or_else(|e| {
+                *error.borrow_mut() = Some(e);
+                false
+            })
+        };
+        let identity = match &self.repr {
+            SegmentTreeRepr::Native(t) => t.op().identity().into_py(py),
+            SegmentTreeRepr::Custom(t) => t.op().identity(),
+        };
+        if !test(identity) {
+            return Err(error
+                .take()
+                .unwrap_or_else(|| PyValueError::new_err("the predicate must hold for the empty range")));
+        }
+        let found = search(&test);
+        self.check_custom()?;
+        error.take().map_or(Ok(found), Err)
+    }
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PySegmentTree {
+    #[new]
+    #[pyo3(signature = (values, op=None, identity=None))]
+    fn new(values: &Bound<'_, PyAny>, op: Option<&Bound<'_, PyAny>>, identity: Option<PyObject>) -> PyResult<Self> {
+        let native = |op: NativeOp| -> PyResult<SegmentTreeRepr> {
+            Ok(SegmentTreeRepr::Native(IterativeSegmentTree::from_slice(&values.extract::<Vec<i64>>()?, op)))
+        };
+        let repr = match op {
+            None => native(NativeOp::Sum)?,
+            Some(op) if op.is_instance_of::<PyString>() => native(NativeOp::parse(&op.extract::<String>()?)?)?,
+            Some(op) if op.is_callable() => {
+                let identity = identity.ok_or_else(|| PyValueError::new_err("a callable op needs an identity"))?;
+                let monoid = PyMonoid {
+                    func: op.clone().unbind(),
+                    identity,
+                    error: RefCell::new(None),
+                };
+                let tree = IterativeSegmentTree::from_slice(&values.extract::<Vec<PyObject>>()?, monoid);
+                tree.op().take_error()?;
+                SegmentTreeRepr::Custom(tree)
+            }
+            Some(_) => return Err(PyTypeError::new_err("op must be a string or a callable")),
+        };
+        Ok(PySegmentTree { repr })
+    }
+
+    /// Name of the built-in operation, or "custom" for a callable.
+    #[getter]
+    fn op(&self) -> &'static str {
+        match &self.repr {
+            SegmentTreeRepr::Native(t) => t.op().name(),
+            SegmentTreeRepr::Custom(_) => "custom",
+        }
+    }
+
+    /// Aggregate of the half-open range [l, r).
+    fn query(&self, py: Python<'_>, l: usize, r: usize) -> PyResult<PyObject> {
+        let range = self.checked_range(l, r)?;
+        self.fold(py, range)
+    }
+
+    /// Aggregate of all values.
+    fn all(&self, py: Python<'_>) -> PyResult<PyObject> {
+        self.fold(py, 0..self.len())
+    }
+
+    /// `st[i]` is element i and `st[a:b]` the aggregate of that slice, clamped like list slicing.
+    fn __getitem__(&self, py: Python<'_>, key: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        match subscript(key, self.len())? {
+            Subscript::Index(i) => Ok(match &self.repr {
+                SegmentTreeRepr::Native(t) => t.get(i).into_py(py),
+                SegmentTreeRepr::Custom(t) => t.get(i).clone_ref(py),
+            }),
+            Subscript::Range(range) => self.fold(py, range),
+        }
+    }
+
+    fn __setitem__(&mut self, index: isize, value: &Bound<'_, PyAny>) -> PyResult<()> {
+        let len = self.len();
+        let i = if index < 0 { index + len as isize } else { index };
+        if i < 0 || i as usize >= len {
+            return Err(PyIndexError::new_err("index out of range"));
+        }
+        match &mut self.repr {
+            SegmentTreeRepr::Native(t) => t.set(i as usize, value.extract()?),
+            SegmentTreeRepr::Custom(t) => t.set(i as usize, value.clone().unbind()),
+        }
+        self.check_custom()
+    }
+
+    fn __len__(&self) -> usize {
+        self.len()
+    }
+
+    /// Largest r such that pred(query(l, r)) is true, for a predicate that holds for the empty range
+    /// and stays false once it fails (e.g. the first index where a prefix sum exceeds x).
+    fn max_right(&self, py: Python<'_>, l: usize, pred: PyObject) -> PyResult<usize> {
+        self.checked_range(l, self.len())?;
+        self.descend(py, &pred, |test| match &self.repr {
+            SegmentTreeRepr::Native(t) => t.max_right(l, |&v| test(v.into_py(py))),
+            SegmentTreeRepr::Custom(t) => t.max_right(l, |v| test(v.clone_ref(py))),
+        })
+    }
+
+    /// Smallest l such that pred(query(l, r)) is true; the mirror image of max_right.
+    fn min_left(&self, py: Python<'_>, r: usize, pred: PyObject) -> PyResult<usize> {
+        self.checked_range(0, r)?;
+        self.descend(py, &pred, |test| match &self.repr {
+            SegmentTreeRepr::Native(t) => t.min_left(r, |&v| test(v.into_py(py))),
+            SegmentTreeRepr::Custom(t) => t.min_left(r, |v| test(v.clone_ref(py))),
+        })
+    }
+
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &IterativeSegmentTree::<i64, monoid::Sum>::INFO)
+    }
+}
+// --- END: Added Segment Tree Binding ---
+
+// --- START: Added Indexed Priority Queue Binding ---
+thread_local! {
+    /// First exception raised by a key's `==` or a priority's `<` while the Rust heap was comparing
+    /// them; those callbacks cannot return errors, so each method checks this slot afterwards.
+    static PENDING_ERROR: RefCell<Option<PyErr>> = const { RefCell::new(None) };
+}
+
+fn record_error(e: PyErr) {
+    PENDING_ERROR.with(|slot| {
+        slot.borrow_mut().get_or_insert(e);
+    });
+}
+
+fn take_pending_error() -> PyResult<()> {
+    PENDING_ERROR.with(|slot| slot.borrow_mut().take()).map_or(Ok(()), Err)
+}
+
+/// A hashable Python object, hashed once on the way in and compared with Python `==`.
+struct PyKey {
+    hash: isize,
+    obj: PyObject,
+}
+
+impl PyKey {
+    fn new(obj: &Bound<'_, PyAny>) -> PyResult<Self> {
+        Ok(PyKey { hash: obj.hash()?, obj: obj.clone().unbind() })
+    }
+}
+
+impl Clone for PyKey {
+    fn clone(&self) -> Self {
+        Python::with_gil(|py| PyKey { hash: self.hash, obj: self.obj.clone_ref(py) })
+    }
+}
+
+impl Hash for PyKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_isize(self.hash);
+    }
+}
+
+impl PartialEq for PyKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash
+            && Python::with_gil(|py| {
+                self.obj.bind(py).eq(other.obj.bind(py)).unwrap_or_else(|e| {
+                    record_error(e);
+                    false
+                })
+            })
+    }
+}
+
+impl Eq for PyKey {}
+
+/// A Python object ordered with its own `<`, as heapq does.
+struct PyPriority(PyObject);
+
+impl PyPriority {
+    fn less(a: &PyPriority, b: &PyPriority) -> bool {
+        Python::with_gil(|py| {
+            a.0.bind(py).lt(b.0.bind(py)).unwrap_or_else(|e| {
+                record_error(e);
+                false
+            })
+        })
+    }
+}
+
+impl PartialEq for PyPriority {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for PyPriority {}
+
+// `<` and `<=` are all the heap uses, so each costs a single Python comparison
+impl PartialOrd for PyPriority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+
+    fn lt(&self, other: &Self) -> bool {
+        PyPriority::less(self, other)
+    }
+
+    fn le(&self, other: &Self) -> bool {
+        !PyPriority::less(other, self)
+    }
+
+    fn gt(&self, other: &Self) -> bool {
+        PyPriority::less(other, self)
+    }
+
+    fn ge(&self, other: &Self) -> bool {
+        !PyPriority::less(self, other)
+    }
+}
+
+impl Ord for PyPriority {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if PyPriority::less(self, other) {
+            Ordering::Less
+        } else if PyPriority::less(other, self) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+}
+
+/// Min-priority queue keyed by any hashable object, with the decrease-key that heapq lacks. Each key
+/// is present at most once; priorities are any mutually comparable objects (numbers, tuples, ...).
+#[pyclass(name = "IndexedPriorityQueue")]
+struct PyIndexedPriorityQueue {
+    heap: IndexedHeap<PyKey, PyPriority>,
+}
+
+impl PyIndexedPriorityQueue {
+    fn entry(py: Python<'_>, key: &PyKey, priority: &PyPriority) -> (PyObject, PyObject) {
+        (key.obj.clone_ref(py), priority.0.clone_ref(py))
+    }
+
+    fn missing(key: &Bound<'_, PyAny>) -> PyErr {
+        PyKeyError::new_err(key.clone().unbind())
+    }
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyIndexedPriorityQueue {
+    /// Optionally seeded with an iterable of `(key, priority)` pairs.
+    #[new]
+    #[pyo3(signature = (items = None))]
+    fn new(items: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
+        let mut queue = PyIndexedPriorityQueue { heap: IndexedHeap::new() };
+        if let Some(items) = items {
+            for item in items.iter()? {
+                let (key, priority): (Bound<'_, PyAny>, Bound<'_, PyAny>) = item?.extract()?;
+                queue.push(&key, &priority)?;
+            }
+        }
+        Ok(queue)
+    }
+
+    /// Insert `key` with `priority`. Raises ValueError if `key` is already queued.
+    fn push(&mut self, key: &Bound<'_, PyAny>, priority: &Bound<'_, PyAny>) -> PyResult<()> {
+        let k = PyKey::new(key)?;
+        let pushed = self.heap.push(k.clone(), PyPriority(priority.clone().unbind()));
+        if let Err(e) = take_pending_error() {
+            // an incomparable priority: take the new entry back out rather than leave it queued
+            if pushed {
+                self.heap.remove(&k);
+                let _ = take_pending_error();
+            }
+            return Err(e);
+        }
+        if !pushed {
+            return Err(PyValueError::new_err(format!(
+                "key {} is already queued; use decrease_key or update",
+                key.repr()?
+            )));
+        }
+        Ok(())
+    }
+
+    /// Lower the priority of `key`. Raises KeyError if it is not queued and ValueError if
+    /// `priority` is greater than its current priority.
+    fn decrease_key(&mut self, key: &Bound<'_, PyAny>, priority: &Bound<'_, PyAny>) -> PyResult<()> {
+        let k = PyKey::new(key)?;
+        if !self.heap.contains(&k) {
+            take_pending_error()?;
+            return Err(Self::missing(key));
+        }
+        let lowered = self.heap.decrease_key(&k, PyPriority(priority.clone().unbind()));
+        take_pending_error()?;
+        if !lowered {
+            return Err(PyValueError::new_err("new priority is greater than the current one"));
+        }
+        Ok(())
+    }
+
+    /// Set the priority of `key` in either direction and return the old one. Raises KeyError if
+    /// `key` is not queued.
+    fn update(&mut self, key: &Bound<'_, PyAny>, priority: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let old = self.heap.change_priority(&PyKey::new(key)?, PyPriority(priority.clone().unbind()));
+        take_pending_error()?;
+        old.map(|p| p.0).ok_or_else(|| Self::missing(key))
+    }
+
+    /// Remove and return the `(key, priority)` pair with the smallest priority.
+    fn pop(&mut self) -> PyResult<(PyObject, PyObject)> {
+        let top = self.heap.pop();
+        take_pending_error()?;
+        top.map(|(k, p)| (k.obj, p.0))
+            .ok_or_else(|| PyIndexError::new_err("pop from an empty IndexedPriorityQueue"))
+    }
+
+    /// The `(key, priority)` pair with the smallest priority, without removing it.
+    fn peek(&self, py: Python<'_>) -> PyResult<(PyObject, PyObject)> {
+        self.heap
+            .peek()
+            .map(|(k, p)| Self::entry(py, k, p))
+            .ok_or_else(|| PyIndexError::new_err("peek into an empty IndexedPriorityQueue"))
+    }
+
+    /// Priority of `key`. Raises KeyError if it is not queued.
+    fn priority(&self, py: Python<'_>, key: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let p = self.heap.priority(&PyKey::new(key)?).map(|p| p.0.clone_ref(py));
+        take_pending_error()?;
+        p.ok_or_else(|| Self::missing(key))
+    }
+
+    /// Remove `key` and return its priority. Raises KeyError if it is not queued.
+    fn remove(&mut self, key: &Bound<'_, PyAny>) -> PyResult<PyObject> {
+        let p = self.heap.remove(&PyKey::new(key)?);
+        take_pending_error()?;
+        p.map(|p| p.0).ok_or_else(|| Self::missing(key))
+    }
+
+    fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    fn __len__(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn __contains__(&self, key: &Bound<'_, PyAny>) -> PyResult<bool> {
+        let found = self.heap.contains(&PyKey::new(key)?);
+        take_pending_error()?;
+        Ok(found)
+    }
+
+    /// Complexity metadata: operations with their time and space costs.
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &IndexedHeap::<(), ()>::INFO)
+    }
+}
+// --- END: Added Indexed Priority Queue Binding ---
+
+// --- START: Added Dijkstra Binding ---
+/// Edge weight for the Python graph functions: a finite, non-negative float, totally ordered.
+#[derive(Clone, Copy, Default)]
+struct Weight(f64);
+
+// shows the bare number, so that DOT edge labels read "2.5" rather than "Weight(2.5)"
+impl std::fmt::Debug for Weight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq for Weight {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Weight {}
+
+impl PartialOrd for Weight {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Weight {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl std::ops::Add for Weight {
+    type Output = Weight;
+
+    fn add(self, other: Weight) -> Weight {
+        Weight(self.0 + other.0)
+    }
+}
+
+/// Builds a `Graph` from `(u, v, weight)` tuples, rejecting out-of-range vertices and weights that
+/// are negative or NaN.
+fn weighted_graph(n: usize, edges: Vec<(usize, usize, f64)>, directed: bool) -> PyResult<Graph<Weight>> {
+    let mut g = if directed { Graph::directed(n) } else { Graph::undirected(n) };
+    for (u, v, w) in edges {
+        add_weighted_edge(&mut g, u, v, w)?;
+    }
+    Ok(g)
+}
+
+fn add_weighted_edge(g: &mut Graph<Weight>, u: usize, v: usize, w: f64) -> PyResult<usize> {
+    let n = g.num_nodes();
+    if u >= n || v >= n {
+        return Err(PyIndexError::new_err(format!("edge ({}, {}) out of range for {} vertices", u, v, n)));
+    }
+    if w.is_nan() || w < 0.0 {
+        return Err(PyValueError::new_err(format!("edge ({}, {}) has invalid weight {}", u, v, w)));
+    }
+    // adding 0.0 turns -0.0, which total_cmp orders below zero, into 0.0
+    Ok(g.add_edge(u, v, Weight(w + 0.0)))
+}
+
+/// Shortest distances from `source` over `n` vertices and `(u, v, weight)` edges. Returns
+/// `(dist, parent)`: `dist[v]` is None when `v` is unreachable, and `parent[v]` is the predecessor
+/// of `v` on a shortest path (None for the source and unreachable vertices).
+#[pyfunction]
+#[pyo3(signature = (n, edges, source, directed=true))]
+#[allow(clippy::type_complexity)]
+fn dijkstra(n: usize, edges: Vec<(usize, usize, f64)>, source: usize, directed: bool) -> PyResult<(Vec<Option<f64>>, Vec<Option<usize>>)> {
+    if source >= n {
+        return Err(PyIndexError::new_err(format!("source {} out of range for {} vertices", source, n)));
+    }
+    let sp = graph::dijkstra(&weighted_graph(n, edges, directed)?, source);
+    Ok((sp.dist.iter().map(|d| d.map(|w| w.0)).collect(), sp.parent))
+}
+
+/// Vertices of a shortest path from `source` to `target`, or None if `target` is unreachable.
+#[pyfunction]
+#[pyo3(signature = (n, edges, source, target, directed=true))]
+fn shortest_path(n: usize, edges: Vec<(usize, usize, f64)>, source: usize, target: usize, directed: bool) -> PyResult<Option<Vec<usize>>> {
+    if source >= n || target >= n {
+        return Err(PyIndexError::new_err(format!("source or target out of range for {} vertices", n)));
+    }
+    Ok(graph::dijkstra(&weighted_graph(n, edges, directed)?, source).path_to(target))
+}
+// --- END: Added Dijkstra Binding ---
+
+// --- START: Added Graph Bindings ---
+/// A vertex index from an edge row: a Python or numpy integer, or an integral float as found in
+/// the first columns of a float array.
+fn vertex_index(item: &Bound<'_, PyAny>) -> PyResult<usize> {
+    if let Ok(v) = item.extract::<usize>() {
+        return Ok(v);
+    }
+    match item.extract::<f64>() {
+        Ok(x) if x >= 0.0 && x.fract() == 0.0 && x < usize::MAX as f64 => Ok(x as usize),
+        _ => Err(PyTypeError::new_err(format!("invalid vertex index {}", item))),
+    }
+}
+
+/// Reads edges from any iterable of `(u, v)` or `(u, v, weight)` rows: a list of tuples or lists,
+/// or a numpy array of shape `(m, 2)` or `(m, 3)`. Rows without a weight get weight 1.
+fn edge_rows(edges: &Bound<'_, PyAny>) -> PyResult<Vec<(usize, usize, f64)>> {
+    let mut rows = Vec::new();
+    for row in edges.iter()? {
+        let row = row?;
+        let len = row.len()?;
+        if len != 2 && len != 3 {
+            return Err(PyValueError::new_err(format!("edge rows need 2 or 3 entries, got {}", len)));
+        }
+        let weight = if len == 3 { row.get_item(2)?.extract::<f64>()? } else { 1.0 };
+        rows.push((vertex_index(&row.get_item(0)?)?, vertex_index(&row.get_item(1)?)?, weight));
+    }
+    Ok(rows)
+}
+
+#[pyclass(name = "Graph", module = "advanced_ds_playground_bindings.graphs")]
+struct PyGraph {
+    g: Graph<Weight>,
+}
+
+impl PyGraph {
+    fn check_vertex(&self, v: usize) -> PyResult<()> {
+        if v >= self.g.num_nodes() {
+            return Err(PyIndexError::new_err(format!("vertex {} out of range for {} vertices", v, self.g.num_nodes())));
+        }
+        Ok(())
+    }
+}
+
+#[pymethods]
+impl PyGraph {
+    /// `n` vertices and optional edges given as `(u, v)` / `(u, v, weight)` rows or a numpy array.
+    #[new]
+    #[pyo3(signature = (n, edges=None, directed=true))]
+    fn new(n: usize, edges: Option<&Bound<'_, PyAny>>, directed: bool) -> PyResult<Self> {
+        let rows = match edges {
+            Some(edges) => edge_rows(edges)?,
+            None => Vec::new(),
+        };
+        Ok(PyGraph { g: weighted_graph(n, rows, directed)? })
+    }
+
+    fn add_node(&mut self) -> usize {
+        self.g.add_node()
+    }
+
+    /// Adds an edge and returns its id; ids follow insertion order.
+    #[pyo3(signature = (u, v, weight=1.0))]
+    fn add_edge(&mut self, u: usize, v: usize, weight: f64) -> PyResult<usize> {
+        add_weighted_edge(&mut self.g, u, v, weight)
+    }
+
+    #[getter]
+    fn num_nodes(&self) -> usize {
+        self.g.num_nodes()
+    }
+
+    #[getter]
+    fn num_edges(&self) -> usize {
+        self.g.num_edges()
+    }
+
+    #[getter]
+    fn directed(&self) -> bool {
+        self.g.is_directed()
+    }
+
+    /// `(u, v, weight)` for every edge, indexed by edge id.
+    fn edges(&self) -> Vec<(usize, usize, f64)> {
+        self.g.edges().map(|(u, v, w)| (u, v, w.0)).collect()
+    }
+
+    fn neighbors(&self, u: usize) -> PyResult<Vec<usize>> {
+        self.check_vertex(u)?;
+        Ok(self.g.neighbors(u).collect())
+    }
+
+    /// Graphviz DOT source, optionally highlighting a path of vertices.
+    #[pyo3(signature = (path=None))]
+    fn to_dot(&self, path: Option<Vec<usize>>) -> String {
+        self.g.to_dot_highlighting(&Highlight::path(&path.unwrap_or_default()))
+    }
+
+    fn __len__(&self) -> usize {
+        self.g.num_nodes()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Graph(n={}, edges={}, directed={})", self.g.num_nodes(), self.g.num_edges(), if self.g.is_directed() { "True" } else { "False" })
+    }
+}
+
+/// Breadth-first search from `source`. Returns `(dist, parent)` with hop counts; both are None for
+/// unreachable vertices, and `parent` is None for the source.
+#[pyfunction]
+#[pyo3(name = "bfs")]
+#[allow(clippy::type_complexity)]
+fn graphs_bfs(graph: PyRef<'_, PyGraph>, source: usize) -> PyResult<(Vec<Option<usize>>, Vec<Option<usize>>)> {
+    graph.check_vertex(source)?;
+    let b = graph::bfs(&graph.g, source);
+    Ok((b.dist, b.parent))
+}
+
+/// Dijkstra from `source` using the edge weights. Returns `(dist, parent)` as the top-level
+/// `dijkstra` does.
+#[pyfunction]
+#[pyo3(name = "dijkstra")]
+#[allow(clippy::type_complexity)]
+fn graphs_dijkstra(graph: PyRef<'_, PyGraph>, source: usize) -> PyResult<(Vec<Option<f64>>, Vec<Option<usize>>)> {
+    graph.check_vertex(source)?;
+    let sp = graph::dijkstra(&graph.g, source);
+    Ok((sp.dist.iter().map(|d| d.map(|w| w.0)).collect(), sp.parent))
+}
+
+/// Vertices of a minimum-weight path from `source` to `target`, or None if unreachable.
+#[pyfunction]
+#[pyo3(name = "shortest_path")]
+fn graphs_shortest_path(graph: PyRef<'_, PyGraph>, source: usize, target: usize) -> PyResult<Option<Vec<usize>>> {
+    graph.check_vertex(source)?;
+    graph.check_vertex(target)?;
+    Ok(graph::dijkstra(&graph.g, source).path_to(target))
+}
+
+/// Minimum spanning forest of an undirected graph by Prim's algorithm. Returns
+/// `(total_weight, edge_ids)` with the ids in increasing order.
+#[pyfunction]
+fn minimum_spanning_tree(graph: PyRef<'_, PyGraph>) -> PyResult<(f64, Vec<usize>)> {
+    if graph.g.is_directed() {
+        return Err(PyValueError::new_err("minimum_spanning_tree needs an undirected graph"));
+    }
+    let tree = graph::prim(&graph.g);
+    Ok((tree.iter().map(|&id| graph.g.weight(id).0).sum(), tree))
+}
+
+/// Strongly connected components as lists of vertices, in topological order of the condensation
+/// (edges between components only lead to later ones).
+#[pyfunction]
+#[pyo3(name = "strongly_connected_components")]
+fn graphs_strongly_connected_components(graph: PyRef<'_, PyGraph>) -> Vec<Vec<usize>> {
+    graph::strongly_connected_components(&graph.g).members()
+}
+
+/// Topological order of a directed acyclic graph. Raises ValueError naming a cycle otherwise.
+#[pyfunction]
+#[pyo3(name = "topological_sort")]
+fn graphs_topological_sort(graph: PyRef<'_, PyGraph>) -> PyResult<Vec<usize>> {
+    if !graph.g.is_directed() {
+        return Err(PyValueError::new_err("topological_sort needs a directed graph"));
+    }
+    graph::topological_sort(&graph.g).map_err(|cycle| PyValueError::new_err(format!("graph has a cycle: {:?}", cycle)))
+}
+
+/// Maximum flow from `source` to `sink` by Dinic's algorithm, with the edge weights as capacities
+/// (which must be whole numbers). Returns `(value, flows)` where `flows[id]` is the flow on edge
+/// `id`; on an undirected graph it is negative when the flow runs from `v` to `u`.
+#[pyfunction]
+#[pyo3(name = "max_flow")]
+fn graphs_max_flow(graph: PyRef<'_, PyGraph>, source: usize, sink: usize) -> PyResult<(i64, Vec<i64>)> {
+    graph.check_vertex(source)?;
+    graph.check_vertex(sink)?;
+    if source == sink {
+        return Err(PyValueError::new_err("source and sink must differ"));
+    }
+    let g = &graph.g;
+    let mut net = graph::MaxFlow::new(g.num_nodes());
+    for (u, v, w) in g.edges() {
+        if w.0.fract() != 0.0 || w.0 > i64::MAX as f64 {
+            return Err(PyValueError::new_err(format!("capacity {} of edge ({}, {}) is not an integer", w.0, u, v)));
+        }
+        net.add_edge(u, v, w.0 as i64);
+        if !g.is_directed() {
+            net.add_edge(v, u, w.0 as i64);
+        }
+    }
+    let value = net.solve(source, sink);
+    let flows = if g.is_directed() {
+        (0..g.num_edges()).map(|id| net.flow(id)).collect()
+    } else {
+        (0..g.num_edges()).map(|id| net.flow(2 * id) - net.flow(2 * id + 1)).collect()
+    };
+    Ok((value, flows))
+}
+// --- END: Added Graph Bindings ---
+
+// --- START: Added KD-Tree Binding ---
+/// Coordinates from any iterable of numbers: a list, a tuple or a numpy row.
+fn coordinates(point: &Bound<'_, PyAny>) -> PyResult<Vec<f64>> {
+    point.iter()?.map(|x| x?.extract::<f64>()).collect()
+}
+
+fn fixed_point<const D: usize>(coords: &[f64]) -> PyResult<[f64; D]> {
+    coords
+        .try_into()
+        .map_err(|_| PyValueError::new_err(format!("expected a point with {} coordinates, got {}", D, coords.len())))
+}
+
+enum KdTreeRepr {
+    Two(KdTree<2>),
+    Three(KdTree<3>),
+}
+
+/// Runs the same expression on whichever dimension the tree has, with `$p` (and `$q`) converted
+/// to fixed-size points of that dimension.
+macro_rules! with_kd_tree {
+    ($repr:expr, $t:ident, [$($p:ident),*] => $body:expr) => {
+        match $repr {
+            KdTreeRepr::Two($t) => {
+                $(let $p = fixed_point::<2>(&coordinates($p)?)?;)*
+                $body
+            }
+            KdTreeRepr::Three($t) => {
+                $(let $p = fixed_point::<3>(&coordinates($p)?)?;)*
+                $body
+            }
+        }
+    };
+}
+
+/// Static k-d tree over 2D or 3D points, given as a list of rows or a numpy array of shape
+/// `(n, 2)` or `(n, 3)`. Points are reported by their row index; distances are euclidean.
+#[pyclass(name = "KdTree")]
+struct PyKdTree {
+    repr: KdTreeRepr,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyKdTree {
+    #[new]
+    fn new(points: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let rows: Vec<Vec<f64>> = points.iter()?.map(|row| coordinates(&row?)).collect::<PyResult<_>>()?;
+        let dims = rows.first().map_or(2, Vec::len);
+        if rows.iter().any(|r| r.iter().any(|x| x.is_nan())) {
+            return Err(PyValueError::new_err("point coordinates must not be NaN"));
+        }
+        let repr = match dims {
+            2 => KdTreeRepr::Two(KdTree::new(&rows.iter().map(|r| fixed_point(r)).collect::<PyResult<Vec<_>>>()?)),
+            3 => KdTreeRepr::Three(KdTree::new(&rows.iter().map(|r| fixed_point(r)).collect::<PyResult<Vec<_>>>()?)),
+            d => return Err(PyValueError::new_err(format!("points must have 2 or 3 coordinates, got {}", d))),
+        };
+        Ok(PyKdTree { repr })
+    }
+
+    #[getter]
+    fn dims(&self) -> usize {
+        match self.repr {
+            KdTreeRepr::Two(_) => 2,
+            KdTreeRepr::Three(_) => 3,
+        }
+    }
+
+    /// `(index, distance)` of the point closest to `point`, or None for an empty tree.
+    fn nearest(&self, point: &Bound<'_, PyAny>) -> PyResult<Option<(usize, f64)>> {
+        Ok(with_kd_tree!(&self.repr, t, [point] => t.nearest(&point)))
+    }
+
+    /// `(index, distance)` of the `k` points closest to `point`, nearest first.
+    fn k_nearest(&self, point: &Bound<'_, PyAny>, k: usize) -> PyResult<Vec<(usize, f64)>> {
+        Ok(with_kd_tree!(&self.repr, t, [point] => t.k_nearest(&point, k)))
+    }
+
+    /// Indices of the points inside the box with corners `lo` and `hi` (bounds included), ascending.
+    fn range(&self, lo: &Bound<'_, PyAny>, hi: &Bound<'_, PyAny>) -> PyResult<Vec<usize>> {
+        Ok(with_kd_tree!(&self.repr, t, [lo, hi] => t.range(&lo, &hi)))
+    }
+
+    fn __len__(&self) -> usize {
+        match &self.repr {
+            KdTreeRepr::Two(t) => t.len(),
+            KdTreeRepr::Three(t) => t.len(),
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("KdTree(n={}, dims={})", self.__len__(), self.dims())
+    }
+
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &KdTree::<2>::INFO)
+    }
+}
+// --- END: Added KD-Tree Binding ---
+
+// --- START: Added Bloom Filter Binding ---
+/// An item of a filter or sketch. Python's own `hash()` is randomized per process for str and
+/// bytes, so items are hashed from their value instead, which keeps saved filters valid.
+#[derive(FromPyObject, Clone, PartialEq, Eq, Hash)]
+enum StableKey {
+    Int(i64),
+    Str(String),
+    Bytes(Vec<u8>),
+}
+
+impl IntoPy<PyObject> for StableKey {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        match self {
+            StableKey::Int(i) => i.into_py(py),
+            StableKey::Str(s) => s.into_py(py),
+            StableKey::Bytes(b) => PyBytes::new_bound(py, &b).into_py(py),
+        }
+    }
+}
+
+/// Bloom filter over ints, strings and bytes: `in` is never wrong for added items, and wrong for
+/// other items with probability about `fp_rate` once `expected_items` items have been added.
+#[pyclass(name = "BloomFilter")]
+#[derive(Clone)]
+struct PyBloomFilter {
+    filter: BloomFilter,
+}
+
+impl PyBloomFilter {
+    fn combine(&self, other: &PyBloomFilter, f: fn(&mut BloomFilter, &BloomFilter)) -> PyResult<PyBloomFilter> {
+        if !self.filter.is_compatible(&other.filter) {
+            return Err(PyValueError::new_err("Bloom filters differ in size or hash count"));
+        }
+        let mut out = self.clone();
+        f(&mut out.filter, &other.filter);
+        Ok(out)
+    }
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyBloomFilter {
+    #[new]
+    #[pyo3(signature = (expected_items, fp_rate = 0.01))]
+    fn new(expected_items: usize, fp_rate: f64) -> PyResult<Self> {
+        if !(fp_rate > 0.0 && fp_rate < 1.0) {
+            return Err(PyValueError::new_err("fp_rate must be between 0 and 1"));
+        }
+        Ok(PyBloomFilter { filter: BloomFilter::new(expected_items, fp_rate) })
+    }
+
+    /// Add `item`. Returns False if it was (probably) there already.
+    fn add(&mut self, item: StableKey) -> bool {
+        self.filter.insert(&item)
+    }
+
+    fn __contains__(&self, item: StableKey) -> bool {
+        self.filter.maybe_contains(&item)
+    }
+
+    /// A filter holding the items of both filters.
+    fn union(&self, other: &PyBloomFilter) -> PyResult<PyBloomFilter> {
+        self.combine(other, BloomFilter::union_with)
+    }
+
+    /// A filter that still holds every item added to both filters.
+    fn intersection(&self, other: &PyBloomFilter) -> PyResult<PyBloomFilter> {
+        self.combine(other, BloomFilter::intersect_with)
+    }
+
+    fn __or__(&self, other: &PyBloomFilter) -> PyResult<PyBloomFilter> {
+        self.union(other)
+    }
+
+    fn __and__(&self, other: &PyBloomFilter) -> PyResult<PyBloomFilter> {
+        self.intersection(other)
+    }
+
+    #[getter]
+    fn num_bits(&self) -> usize {
+        self.filter.num_bits()
+    }
+
+    #[getter]
+    fn num_hashes(&self) -> u32 {
+        self.filter.num_hashes()
+    }
+
+    /// False-positive rate implied by the bits set so far.
+    fn estimated_fp_rate(&self) -> f64 {
+        self.filter.estimated_fp_rate()
+    }
+
+    /// Approximate number of distinct items added.
+    fn estimated_len(&self) -> f64 {
+        self.filter.estimated_len()
+    }
+
+    fn save(&self, path: PathBuf) -> PyResult<()> {
+        self.filter.save(path).map_err(snapshot_error)
+    }
+
+    #[staticmethod]
+    fn load(path: PathBuf) -> PyResult<Self> {
+        Ok(PyBloomFilter { filter: BloomFilter::load(path).map_err(snapshot_error)? })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("BloomFilter(num_bits={}, num_hashes={})", self.filter.num_bits(), self.filter.num_hashes())
+    }
+
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &BloomFilter::INFO)
+    }
+}
+// --- END: Added Bloom Filter Binding ---
+
+// --- START: Added Count-Min Binding ---
+/// Count-Min sketch over ints, strings and bytes: `estimate` never undercounts, and overcounts
+/// by more than `epsilon * total` with probability at most `delta`.
+#[pyclass(name = "CountMinSketch")]
+struct PyCountMinSketch {
+    sketch: CountMinSketch,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyCountMinSketch {
+    #[new]
+    #[pyo3(signature = (epsilon = 0.001, delta = 0.01, conservative = false))]
+    fn new(epsilon: f64, delta: f64, conservative: bool) -> PyResult<Self> {
+        if !(epsilon > 0.0 && epsilon < 1.0 && delta > 0.0 && delta < 1.0) {
+            return Err(PyValueError::new_err("epsilon and delta must be between 0 and 1"));
+        }
+        let sketch = CountMinSketch::new(epsilon, delta);
+        Ok(PyCountMinSketch { sketch: if conservative { sketch.conservative() } else { sketch } })
+    }
+
+    #[pyo3(signature = (item, count = 1))]
+    fn add(&mut self, item: StableKey, count: u64) {
+        self.sketch.add(&item, count);
+    }
+
+    fn estimate(&self, item: StableKey) -> u64 {
+        self.sketch.estimate(&item)
+    }
+
+    fn __getitem__(&self, item: StableKey) -> u64 {
+        self.sketch.estimate(&item)
+    }
+
+    /// Add the counts of `other`, which must have the same epsilon and delta.
+    fn merge(&mut self, other: &PyCountMinSketch) -> PyResult<()> {
+        if !self.sketch.is_compatible(&other.sketch) {
+            return Err(PyValueError::new_err("sketches differ in width or depth"));
+        }
+        self.sketch.merge(&other.sketch);
+        Ok(())
+    }
+
+    #[getter]
+    fn total(&self) -> u64 {
+        self.sketch.total()
+    }
+
+    #[getter]
+    fn width(&self) -> usize {
+        self.sketch.width()
+    }
+
+    #[getter]
+    fn depth(&self) -> usize {
+        self.sketch.depth()
+    }
+
+    /// How far an estimate may exceed the true count, except with probability delta.
+    fn error_bound(&self) -> f64 {
+        self.sketch.error_bound()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("CountMinSketch(width={}, depth={}, total={})", self.sketch.width(), self.sketch.depth(), self.sketch.total())
+    }
+
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &CountMinSketch::INFO)
+    }
+}
+
+/// The items making up at least a `phi` fraction of a stream, e.g. the busiest paths in a log.
+#[pyclass(name = "HeavyHitters")]
+struct PyHeavyHitters {
+    hitters: HeavyHitters<StableKey>,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyHeavyHitters {
+    /// `epsilon` defaults to a tenth of `phi`.
+    #[new]
+    #[pyo3(signature = (phi, epsilon = None, delta = 0.01))]
+    fn new(phi: f64, epsilon: Option<f64>, delta: f64) -> PyResult<Self> {
+        let epsilon = epsilon.unwrap_or(phi / 10.0);
+        if !(0.0 < epsilon && epsilon < phi && phi < 1.0 && delta > 0.0 && delta < 1.0) {
+            return Err(PyValueError::new_err("need 0 < epsilon < phi < 1 and 0 < delta < 1"));
+        }
+        Ok(PyHeavyHitters { hitters: HeavyHitters::new(phi, epsilon, delta) })
+    }
+
+    #[pyo3(signature = (item, count = 1))]
+    fn add(&mut self, item: StableKey, count: u64) {
+        self.hitters.add(&item, count);
+    }
+
+    /// `(item, estimated count)` pairs, most frequent first.
+    fn heavy_hitters(&self) -> Vec<(StableKey, u64)> {
+        self.hitters.heavy_hitters()
+    }
+
+    fn estimate(&self, item: StableKey) -> u64 {
+        self.hitters.sketch().estimate(&item)
+    }
+
+    #[getter]
+    fn total(&self) -> u64 {
+        self.hitters.sketch().total()
+    }
+
+    /// Add the stream counted by `other`, which must have the same epsilon and delta.
+    fn merge(&mut self, other: &PyHeavyHitters) -> PyResult<()> {
+        if !self.hitters.sketch().is_compatible(other.hitters.sketch()) {
+            return Err(PyValueError::new_err("sketches differ in width or depth"));
+        }
+        self.hitters.merge(&other.hitters);
+        Ok(())
+    }
+}
+// --- END: Added Count-Min Binding ---
+
+// --- START: Added Monotonic Stack Bindings ---
+/// A list of ints or of floats; ints are compared exactly, not through floats.
+#[derive(FromPyObject)]
+enum Numbers {
+    Ints(Vec<i64>),
+    Floats(Vec<f64>),
+}
+
+impl Numbers {
+    /// Applies one of the nearest-element helpers, rejecting NaN, which has no order.
+    fn nearest(self, f: fn(&[f64]) -> Vec<Option<usize>>, g: fn(&[i64]) -> Vec<Option<usize>>) -> PyResult<Vec<Option<usize>>> {
+        match self {
+            Numbers::Ints(v) => Ok(g(&v)),
+            Numbers::Floats(v) if v.iter().any(|x| x.is_nan()) => Err(PyValueError::new_err("values must not be NaN")),
+            Numbers::Floats(v) => Ok(f(&v)),
+        }
+    }
+}
+
+/// For each position, the index of the first later value strictly greater, or None.
+#[pyfunction]
+fn next_greater(values: Numbers) -> PyResult<Vec<Option<usize>>> {
+    values.nearest(monotonic_stack::next_greater, monotonic_stack::next_greater)
+}
+
+/// For each position, the index of the last earlier value strictly greater, or None.
+#[pyfunction]
+fn previous_greater(values: Numbers) -> PyResult<Vec<Option<usize>>> {
+    values.nearest(monotonic_stack::previous_greater, monotonic_stack::previous_greater)
+}
+
+/// For each position, the index of the first later value strictly smaller, or None.
+#[pyfunction]
+fn next_smaller(values: Numbers) -> PyResult<Vec<Option<usize>>> {
+    values.nearest(monotonic_stack::next_smaller, monotonic_stack::next_smaller)
+}
+
+/// For each position, the index of the last earlier value strictly smaller, or None.
+#[pyfunction]
+fn previous_smaller(values: Numbers) -> PyResult<Vec<Option<usize>>> {
+    values.nearest(monotonic_stack::previous_smaller, monotonic_stack::previous_smaller)
+}
+
+/// `(area, start, end)` of the largest rectangle under a histogram of non-negative integer bar
+/// heights; the rectangle spans bars `start..end`.
+#[pyfunction]
+fn largest_rectangle(heights: Vec<u64>) -> (u64, usize, usize) {
+    let (area, span) = monotonic_stack::largest_rectangle(&heights);
+    (area, span.start, span.end)
+}
+// --- END: Added Monotonic Stack Bindings ---
+
+// --- START: Added Math Bindings ---
+/// The primes up to and including `n`, by a linear sieve.
+#[pyfunction]
+#[pyo3(name = "primes_up_to")]
+fn math_primes_up_to(n: u32) -> Vec<u32> {
+    math::LinearSieve::new(n as usize).primes().to_vec()
+}
+
+/// The smallest prime factor of every value in `0..=n`, with 0 for 0 and 1.
+#[pyfunction]
+#[pyo3(name = "smallest_prime_factors")]
+fn math_smallest_prime_factors(n: u32) -> Vec<u32> {
+    let sieve = math::LinearSieve::new(n as usize);
+    (0..=n as usize).map(|x| sieve.smallest_prime_factor(x).unwrap_or(0)).collect()
+}
+
+/// The primes in `lo..hi` by a segmented sieve, using memory in the square root of `hi`; `hi` may
+/// be at most 2^40, a little above 10^12.
+#[pyfunction]
+#[pyo3(name = "primes_in_range")]
+fn math_primes_in_range(lo: u64, hi: u64) -> PyResult<Vec<u64>> {
+    if hi > math::SEGMENTED_SIEVE_MAX_END {
+        return Err(PyValueError::new_err(format!("hi must be at most {}, got {}", math::SEGMENTED_SIEVE_MAX_END, hi)));
+    }
+    Ok(math::primes_in_range(lo..hi))
+}
+
+/// `[(prime, exponent), ...]` for `n` by increasing prime, by trial division; empty for 0 and 1.
+#[pyfunction]
+#[pyo3(name = "factorize")]
+fn math_factorize(n: u64) -> Vec<(u64, u32)> {
+    math::factorize(n)
+}
+// --- END: Added Math Bindings ---
+
+// --- START: Added Reachability Binding ---
+/// `(u, v)` pairs from the edge rows, ignoring weights, after checking every vertex is below `n`.
+fn reachability_edges(n: usize, edges: &Bound<'_, PyAny>) -> PyResult<Vec<(usize, usize)>> {
+    let mut pairs = Vec::new();
+    for (u, v, _) in edge_rows(edges)? {
+        if u >= n || v >= n {
+            return Err(PyIndexError::new_err(format!("edge ({}, {}) out of range for {} vertices", u, v, n)));
+        }
+        pairs.push((u, v));
+    }
+    Ok(pairs)
+}
+
+/// Answers "is there a path from u to v" on a fixed directed graph over `0..n`, with or without
+/// cycles. Edges are `(u, v)` rows or a numpy array of shape `(m, 2)`; weights are ignored.
+#[pyclass(name = "ReachabilityOracle")]
+struct PyReachabilityOracle {
+    oracle: ReachabilityOracle,
+    n: usize,
+}
+
+#[allow(non_local_definitions)]
+#[pymethods]
+impl PyReachabilityOracle {
+    #[new]
+    fn new(n: usize, edges: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let edges = reachability_edges(n, edges)?;
+        Ok(PyReachabilityOracle { oracle: ReachabilityOracle::new(n, &edges), n })
+    }
+
+    /// Whether `v` can be reached from `u`; every vertex reaches itself.
+    fn reaches(&self, u: usize, v: usize) -> PyResult<bool> {
+        if u >= self.n || v >= self.n {
+            return Err(PyIndexError::new_err(format!("vertex out of range for {} vertices", self.n)));
+        }
+        Ok(self.oracle.reaches(u, v))
+    }
+
+    /// Total number of label entries, a measure of the index size.
+    #[getter]
+    fn label_size(&self) -> usize {
+        self.oracle.label_size()
+    }
+
+    fn __len__(&self) -> usize {
+        self.n
+    }
+
+    fn __repr__(&self) -> String {
+        format!("ReachabilityOracle(n={}, label_size={})", self.n, self.oracle.label_size())
+    }
+
+    #[staticmethod]
+    fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+        info_dict(py, &ReachabilityOracle::INFO)
+    }
+}
+
+/// For each vertex of a DAG over `0..n`, the sorted vertices it reaches by a path of at least one
+/// edge. Raises ValueError if the graph has a cycle.
+#[pyfunction]
+fn transitive_closure(n: usize, edges: &Bound<'_, PyAny>) -> PyResult<Vec<Vec<usize>>> {
+    let edges = reachability_edges(n, edges)?;
+    let closure = reachability::transitive_closure(n, &edges)
+        .ok_or_else(|| PyValueError::new_err("graph has a cycle"))?;
+    Ok(closure.iter().map(|row| row.iter().collect()).collect())
+}
+// --- END: Added Reachability Binding ---
+
+#[pymodule]
+fn advanced_ds_playground_bindings(py: Python, m: &Bound<'_, PyModule>) -> PyResult<()> {
+    // Add existing classes
+    m.add_class::<PyUnionFind>()?;
+    m.add_class::<PyFenwickTree>()?;
+
+    m.add_function(wrap_pyfunction!(prefix_function, m)?)?;
+    m.add_function(wrap_pyfunction!(find_all, m)?)?;
+    m.add_function(wrap_pyfunction!(find_all_chars, m)?)?;
+    m.add_function(wrap_pyfunction!(edit_distance, m)?)?;
+    m.add_function(wrap_pyfunction!(edit_distance_at_most, m)?)?;
+    m.add_class::<PySparseTable>()?;
+    m.add_class::<PyRangeQueryEngine>()?;
+    m.add_class::<PyTreap>()?;
+    m.add_class::<PyTrie>()?;
+    m.add_class::<PyMergeableSet>()?;
+    m.add_class::<PyStaticDict>()?;
+    m.add_function(wrap_pyfunction!(catalog, m)?)?;
+    m.add_class::<PySuffixArray>()?;
+    m.add_class::<PyFmIndex>()?;
+    m.add_function(wrap_pyfunction!(z_function, m)?)?;
+    m.add_function(wrap_pyfunction!(manacher, m)?)?;
+    m.add_function(wrap_pyfunction!(longest_palindrome, m)?)?;
+    m.add_function(wrap_pyfunction!(lyndon_factorization, m)?)?;
+    m.add_function(wrap_pyfunction!(minimal_rotation, m)?)?;
+    m.add_class::<PyStringHasher>()?;
+    m.add_class::<PySegmentTree>()?;
+    m.add_class::<PyIndexedPriorityQueue>()?;
+    m.add_function(wrap_pyfunction!(dijkstra, m)?)?;
+    m.add_function(wrap_pyfunction!(shortest_path, m)?)?;
+    m.add_class::<PyKdTree>()?;
+    m.add_class::<PyBloomFilter>()?;
+    m.add_class::<PyCountMinSketch>()?;
+    m.add_class::<PyHeavyHitters>()?;
+    m.add_function(wrap_pyfunction!(next_greater, m)?)?;
+    m.add_function(wrap_pyfunction!(previous_greater, m)?)?;
+    m.add_function(wrap_pyfunction!(next_smaller, m)?)?;
+    m.add_function(wrap_pyfunction!(previous_smaller, m)?)?;
+    m.add_function(wrap_pyfunction!(largest_rectangle, m)?)?;
+    m.add_class::<PyReachabilityOracle>()?;
+    m.add_function(wrap_pyfunction!(transitive_closure, m)?)?;
+
+    let graphs = PyModule::new_bound(py, "graphs")?;
+    graphs.add_class::<PyGraph>()?;
+    graphs.add_function(wrap_pyfunction!(graphs_bfs, &graphs)?)?;
+    graphs.add_function(wrap_pyfunction!(graphs_dijkstra, &graphs)?)?;
+    graphs.add_function(wrap_pyfunction!(graphs_shortest_path, &graphs)?)?;
+    graphs.add_function(wrap_pyfunction!(minimum_spanning_tree, &graphs)?)?;
+    graphs.add_function(wrap_pyfunction!(graphs_strongly_connected_components, &graphs)?)?;
+    graphs.add_function(wrap_pyfunction!(graphs_topological_sort, &graphs)?)?;
+    graphs.add_function(wrap_pyfunction!(graphs_max_flow, &graphs)?)?;
+    m.add_submodule(&graphs)?;
+    // lets `from advanced_ds_playground_bindings.graphs import ...` find the submodule
+    py.import_bound("sys")?.getattr("modules")?.set_item("advanced_ds_playground_bindings.graphs", &graphs)?;
+
+    let math = PyModule::new_bound(py, "math")?;
+    math.add_function(wrap_pyfunction!(math_primes_up_to, &math)?)?;
+    math.add_function(wrap_pyfunction!(math_smallest_prime_factors, &math)?)?;
+    math.add_function(wrap_pyfunction!(math_primes_in_range, &math)?)?;
+    math.add_function(wrap_pyfunction!(math_factorize, &math)?)?;
+    m.add_submodule(&math)?;
+    py.import_bound("sys")?.getattr("modules")?.set_item("advanced_ds_playground_bindings.math", &math)?;
     Ok(())
 }
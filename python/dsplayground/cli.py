@@ -16,7 +16,8 @@ from advanced_ds_playground_bindings import (
     prefix_function,  
     find_all,         
     SparseTable,      
-    Treap            
+    Treap,
+    catalog
 )
 
 app = typer.Typer(help="An Interactive Playground for Advanced Data Structures.")
@@ -687,4 +688,52 @@ def treap(
     except (IndexError, ValueError) as e:
         console.print(f"[bold red]Error: {e}[/bold red]")
     except Exception as e:
-        console.print(f"[bold red]Unexpected Error: {e}[/bold red]")
\ No newline at end of file
+        console.print(f"[bold red]Unexpected Error: {e}[/bold red]")
+
+# -------------------------------------------
+# CATALOG
+# -------------------------------------------
+
+@app.command(name="list")
+def list_structures(
+    category: Annotated[str, typer.Option(help='Only show one category, e.g. "strings".')] = "",
+    details: Annotated[bool, typer.Option(help="Show the time and space cost of every operation.")] = False
+):
+    """
+    Lists the available data structures and algorithms with their complexities.
+    """
+    entries = [e for e in catalog() if not category or e["category"] == category]
+    if not entries:
+        categories = sorted({e["category"] for e in catalog()})
+        console.print(f"[bold red]Error: Unknown category '{category}'.[/bold red]")
+        console.print(f"[dim]Categories: {', '.join(categories)}[/dim]")
+        raise typer.Exit(code=1)
+
+    table = Table(title="[bold magenta]Data Structures & Algorithms[/bold magenta]", box=box.ROUNDED)
+    table.add_column("Name", style="bold cyan")
+    table.add_column("Category", style="green")
+    table.add_column("Space", style="yellow")
+    table.add_column("Summary", style="white")
+    for e in entries:
+        table.add_row(e["name"], e["category"], e["space"], e["summary"])
+    console.print(table)
+
+    if not details:
+        console.print("[dim] Tip: pass --details to see the cost of every operation.[/dim]")
+        return
+
+    for e in entries:
+        ops_table = Table(title=f"[bold cyan]{e['name']}[/bold cyan]", box=box.MINIMAL)
+        ops_table.add_column("Operation", style="bold white")
+        ops_table.add_column("Time", style="cyan")
+        ops_table.add_column("Extra Space", style="yellow")
+        for op in e["operations"]:
+            ops_table.add_row(op["name"], op["time"], op["space"])
+        flags = []
+        if e["stable"] is not None:
+            flags.append("stable" if e["stable"] else "not stable")
+        if e["in_place"] is not None:
+            flags.append("in-place" if e["in_place"] else "not in-place")
+        if flags:
+            ops_table.caption = ", ".join(flags)
+        console.print(ops_table)
@@ -0,0 +1,42 @@
+import os
+import tempfile
+
+import pytest
+from advanced_ds_playground_bindings import BloomFilter
+
+def test_membership_and_set_operations():
+    print("\n[TEST] BloomFilter: Membership, union and intersection")
+    a = BloomFilter(1000, 0.01)
+    print(f"[INFO] {a!r}")
+    assert a.add("apple") and not a.add("apple")
+    a.add(b"raw")
+    a.add(7)
+    assert "apple" in a and b"raw" in a and 7 in a and "pear" not in a
+    b = BloomFilter(1000, 0.01)
+    b.add("pear")
+    b.add("apple")
+    both = a | b
+    assert all(x in both for x in ["apple", "pear", b"raw", 7])
+    common = a.intersection(b)
+    assert "apple" in common and "pear" not in common
+    with pytest.raises(ValueError):
+        a.union(BloomFilter(10, 0.01))
+    with pytest.raises(ValueError):
+        BloomFilter(10, 1.5)
+    assert BloomFilter.info()["name"] == "BloomFilter"
+
+def test_false_positives_and_save():
+    print("\n[TEST] BloomFilter: False-positive rate and save/load")
+    f = BloomFilter(2000, 0.02)
+    for i in range(2000):
+        f.add(f"key-{i}")
+    assert all(f"key-{i}" in f for i in range(2000))
+    hits = sum(f"other-{i}" in f for i in range(10000))
+    print(f"[INFO] {hits} false positives out of 10000")
+    assert hits < 300
+    assert abs(f.estimated_len() - 2000) < 100
+    path = os.path.join(tempfile.mkdtemp(), "filter.bin")
+    f.save(path)
+    g = BloomFilter.load(path)
+    assert (g.num_bits, g.num_hashes) == (f.num_bits, f.num_hashes)
+    assert all(f"key-{i}" in g for i in range(2000))
@@ -0,0 +1,52 @@
+import random
+
+import pytest
+from advanced_ds_playground_bindings import CountMinSketch, HeavyHitters
+
+def test_estimates_and_merge():
+    print("\n[TEST] CountMinSketch: Estimates, conservative update and merging")
+    sketch = CountMinSketch(epsilon=0.01, delta=0.01)
+    print(f"[INFO] {sketch!r}")
+    assert (sketch.width, sketch.depth) == (272, 5)
+    sketch.add("GET /", 10)
+    sketch.add(b"\x00raw")
+    sketch.add(404, 3)
+    assert sketch["GET /"] >= 10 and sketch.estimate(b"\x00raw") >= 1 and sketch[404] >= 3
+    other = CountMinSketch(epsilon=0.01, delta=0.01)
+    other.add("GET /", 5)
+    sketch.merge(other)
+    assert sketch.total == 19 and sketch["GET /"] >= 15
+    with pytest.raises(ValueError):
+        sketch.merge(CountMinSketch(epsilon=0.1))
+    with pytest.raises(ValueError):
+        CountMinSketch(epsilon=0)
+
+    rng = random.Random(5)
+    words = [f"w{int(rng.paretovariate(1.2))}" for _ in range(20000)]
+    plain, cons = CountMinSketch(0.002), CountMinSketch(0.002, conservative=True)
+    for w in words:
+        plain.add(w)
+        cons.add(w)
+    for w in set(words):
+        assert words.count(w) <= cons[w] <= plain[w]
+    assert CountMinSketch.info()["name"] == "CountMinSketch"
+
+def test_heavy_hitters():
+    print("\n[TEST] HeavyHitters: Busiest paths in a synthetic log")
+    rng = random.Random(11)
+    log = ["/"] * 3000 + ["/login"] * 1500 + [f"/item/{rng.randrange(5000)}" for _ in range(10000)]
+    rng.shuffle(log)
+    hitters = HeavyHitters(0.05)
+    for path in log[:7000]:
+        hitters.add(path)
+    rest = HeavyHitters(0.05)
+    for path in log[7000:]:
+        rest.add(path)
+    hitters.merge(rest)
+    top = hitters.heavy_hitters()
+    print(f"[INFO] {top}")
+    assert hitters.total == len(log)
+    assert [p for p, _ in top] == ["/", "/login"]
+    assert top[0][1] >= 3000
+    with pytest.raises(ValueError):
+        HeavyHitters(0.05, epsilon=0.1)
@@ -0,0 +1,33 @@
+import math
+import pytest
+from advanced_ds_playground_bindings import dijkstra, shortest_path
+
+EDGES = [(0, 1, 7), (0, 2, 2.5), (2, 1, 3), (1, 3, 1), (2, 3, 8), (3, 4, 2)]
+
+def test_distances_and_parents():
+    print("\n[TEST] dijkstra: Distances and predecessors from an edge list")
+    dist, parent = dijkstra(6, EDGES, 0)
+    print(f"[INFO] dist={dist} parent={parent}")
+    assert dist == [0.0, 5.5, 2.5, 6.5, 8.5, None]
+    assert parent == [None, 2, 0, 1, 3, None]
+    assert shortest_path(6, EDGES, 0, 4) == [0, 2, 1, 3, 4]
+    assert shortest_path(6, EDGES, 0, 5) is None
+
+def test_undirected():
+    print("\n[TEST] dijkstra: Undirected edges are usable both ways")
+    dist, _ = dijkstra(6, EDGES, 4, directed=False)
+    assert dist[0] == 8.5
+    assert shortest_path(6, EDGES, 4, 0, directed=False) == [4, 3, 1, 2, 0]
+    dist, _ = dijkstra(2, [(0, 1, math.inf)], 0)
+    assert dist == [0.0, math.inf]
+
+def test_errors():
+    print("\n[TEST] dijkstra: Invalid input raises")
+    with pytest.raises(ValueError):
+        dijkstra(2, [(0, 1, -1)], 0)
+    with pytest.raises(ValueError):
+        dijkstra(2, [(0, 1, math.nan)], 0)
+    with pytest.raises(IndexError):
+        dijkstra(2, [(0, 2, 1)], 0)
+    with pytest.raises(IndexError):
+        shortest_path(2, [], 0, 3)
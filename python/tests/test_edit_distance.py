@@ -0,0 +1,31 @@
+from advanced_ds_playground_bindings import edit_distance, edit_distance_at_most
+
+def test_edit_distance_basic():
+    print("\n[TEST] Edit distance: basic pairs")
+    cases = [("kitten", "sitting", 3), ("flaw", "lawn", 2), ("", "abc", 3), ("same", "same", 0)]
+    for a, b, expected in cases:
+        got = edit_distance(a, b)
+        print(f"[INFO] '{a}' vs '{b}': got {got}, expected {expected}")
+        assert got == expected
+
+def test_edit_distance_unicode():
+    print("\n[TEST] Edit distance: counted in characters")
+    got = edit_distance("café", "cafe")
+    print(f"[INFO] 'café' vs 'cafe': {got}")
+    assert got == 1
+
+def test_edit_distance_at_most():
+    print("\n[TEST] Edit distance: banded check with threshold")
+    print("[STEP] Distance within the threshold is returned")
+    assert edit_distance_at_most("kitten", "sitting", 3) == 3
+    print("[STEP] Distance above the threshold gives None")
+    assert edit_distance_at_most("kitten", "sitting", 2) is None
+    assert edit_distance_at_most("a", "abcdef", 2) is None
+
+def test_fuzzy_match_candidates():
+    print("\n[TEST] Edit distance: fuzzy matching against a word list")
+    words = ["apple", "apply", "ample", "maple", "banana", "applesauce"]
+    query = "appel"
+    matches = [w for w in words if edit_distance_at_most(query, w, 2) is not None]
+    print(f"[INFO] Query: '{query}', matches within 2: {matches}")
+    assert matches == ["apple", "apply"]
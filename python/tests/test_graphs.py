@@ -0,0 +1,60 @@
+import pytest
+from advanced_ds_playground_bindings import graphs
+from advanced_ds_playground_bindings.graphs import Graph
+
+def test_traversal_and_paths():
+    print("\n[TEST] graphs: BFS, Dijkstra and shortest paths on a Graph")
+    g = Graph(5, [(0, 1, 4), (0, 2, 1), (2, 1, 2), (1, 3, 5)])
+    print(f"[INFO] {g!r}")
+    assert (len(g), g.num_edges, g.directed) == (5, 4, True)
+    dist, parent = graphs.bfs(g, 0)
+    assert dist == [0, 1, 1, 2, None]
+    assert parent[3] == 1 and parent[4] is None
+    dist, parent = graphs.dijkstra(g, 0)
+    assert dist == [0.0, 3.0, 1.0, 8.0, None]
+    assert graphs.shortest_path(g, 0, 3) == [0, 2, 1, 3]
+    assert graphs.shortest_path(g, 0, 4) is None
+    assert g.add_edge(3, 4) == 4
+    assert g.neighbors(3) == [4]
+    assert 'label="1.0"' in g.to_dot(path=[3, 4])
+
+def test_structure_algorithms():
+    print("\n[TEST] graphs: MST, SCC, topological sort and max flow")
+    square = Graph(4, [[0, 1, 1], [1, 2, 2], [2, 3, 1], [3, 0, 3], [0, 2, 5]], directed=False)
+    assert graphs.minimum_spanning_tree(square) == (4.0, [0, 1, 2])
+    cyclic = Graph(4, [(0, 1), (1, 0), (1, 2), (2, 3), (3, 2)])
+    assert graphs.strongly_connected_components(cyclic) == [[0, 1], [2, 3]]
+    with pytest.raises(ValueError):
+        graphs.topological_sort(cyclic)
+    assert graphs.topological_sort(Graph(3, [(2, 0), (0, 1)])) == [2, 0, 1]
+    net = Graph(4, [(0, 1, 3), (0, 2, 2), (1, 2, 1), (1, 3, 2), (2, 3, 3)])
+    value, flows = graphs.max_flow(net, 0, 3)
+    assert value == 5
+    assert flows[0] + flows[1] == 5
+    with pytest.raises(ValueError):
+        graphs.max_flow(Graph(2, [(0, 1, 1.5)]), 0, 1)
+    with pytest.raises(ValueError):
+        graphs.minimum_spanning_tree(net)
+
+def test_numpy_edges():
+    print("\n[TEST] graphs: Edges from a numpy array")
+    try:
+        import numpy as np
+    except ImportError:
+        print("[INFO] numpy not installed, skipping")
+        return
+    g = Graph(3, np.array([[0, 1, 2.5], [1, 2, 0.5]]))
+    assert g.edges() == [(0, 1, 2.5), (1, 2, 0.5)]
+    g = Graph(3, np.array([[0, 1], [1, 2]], dtype=np.int64), directed=False)
+    assert graphs.bfs(g, 2)[0] == [2, 1, 0]
+
+def test_errors():
+    print("\n[TEST] graphs: Invalid input raises")
+    with pytest.raises(IndexError):
+        Graph(2, [(0, 2)])
+    with pytest.raises(ValueError):
+        Graph(2, [(0, 1, 2, 3)])
+    with pytest.raises(TypeError):
+        Graph(2, [(0.5, 1)])
+    with pytest.raises(IndexError):
+        graphs.bfs(Graph(2), 5)
@@ -0,0 +1,63 @@
+import pytest
+from advanced_ds_playground_bindings import IndexedPriorityQueue
+
+def test_push_pop_order():
+    print("\n[TEST] IndexedPriorityQueue: Pop in priority order")
+    pq = IndexedPriorityQueue([("a", 5), ("b", 3)])
+    pq.push("c", 8)
+    pq.push(("tuple", "key"), 1)
+    print(f"[INFO] Peek: {pq.peek()}")
+    assert pq.peek() == (("tuple", "key"), 1)
+    assert len(pq) == 4
+    assert "a" in pq
+    assert "z" not in pq
+    order = [pq.pop() for _ in range(len(pq))]
+    print(f"[INFO] Popped: {order}")
+    assert order == [(("tuple", "key"), 1), ("b", 3), ("a", 5), ("c", 8)]
+    with pytest.raises(IndexError):
+        pq.pop()
+
+def test_decrease_key_and_update():
+    print("\n[TEST] IndexedPriorityQueue: decrease_key, update and remove")
+    pq = IndexedPriorityQueue([(v, 10 * v) for v in range(5)])
+    pq.decrease_key(4, -1)
+    assert pq.peek() == (4, -1)
+    assert pq.priority(4) == -1
+    with pytest.raises(ValueError):
+        pq.decrease_key(1, 100)
+    with pytest.raises(KeyError):
+        pq.decrease_key(99, 0)
+    assert pq.update(0, 25) == 0
+    assert pq.remove(2) == 20
+    with pytest.raises(KeyError):
+        pq.remove(2)
+    assert [pq.pop()[0] for _ in range(len(pq))] == [4, 1, 0, 3]
+
+def test_dijkstra_tuple_priorities():
+    print("\n[TEST] IndexedPriorityQueue: Dijkstra with tie-breaking tuple priorities")
+    graph = {"s": {"a": 4, "b": 1}, "b": {"a": 2, "t": 6}, "a": {"t": 1}, "t": {}}
+    dist = {"s": 0}
+    pq = IndexedPriorityQueue([("s", (0, "s"))])
+    while len(pq):
+        u, (d, _) = pq.pop()
+        for v, w in graph[u].items():
+            if d + w < dist.get(v, float("inf")):
+                dist[v] = d + w
+                if v in pq:
+                    pq.decrease_key(v, (d + w, v))
+                else:
+                    pq.push(v, (d + w, v))
+    print(f"[INFO] Distances: {dist}")
+    assert dist == {"s": 0, "b": 1, "a": 3, "t": 4}
+
+def test_errors():
+    print("\n[TEST] IndexedPriorityQueue: Duplicate, unhashable and incomparable input")
+    pq = IndexedPriorityQueue([("a", 1)])
+    with pytest.raises(ValueError):
+        pq.push("a", 0)
+    with pytest.raises(TypeError):
+        pq.push(["unhashable"], 0)
+    with pytest.raises(TypeError):
+        pq.push("b", "not a number")
+    assert "b" not in pq
+    assert len(pq) == 1
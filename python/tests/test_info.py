@@ -0,0 +1,31 @@
+from advanced_ds_playground_bindings import (
+    UnionFind,
+    FenwickTree,
+    Trie,
+    StaticDict,
+    catalog,
+)
+
+def test_info_on_class_and_instance():
+    print("\n[TEST] info(): Complexity metadata")
+    info = UnionFind.info()
+    print(f"[INFO] {info['name']}: {info['summary']}")
+    assert info["name"] == "UnionFind"
+    assert UnionFind(4).info() == info
+    ops = {op["name"]: op for op in info["operations"]}
+    assert ops["find"]["time"] == "O(α(n)) amortized"
+    assert FenwickTree.info()["category"] == "range queries"
+    assert Trie().info()["name"] == "Trie"
+    assert StaticDict({}).info()["stable"] is None
+
+def test_catalog():
+    print("\n[TEST] catalog(): All structures and algorithms")
+    entries = catalog()
+    print(f"[INFO] {len(entries)} entries")
+    names = [e["name"] for e in entries]
+    assert len(names) == len(set(names))
+    assert "FmIndex" in names and "kmp" in names
+    keys = [(e["category"], e["name"]) for e in entries]
+    assert keys == sorted(keys)
+    kmp = next(e for e in entries if e["name"] == "kmp")
+    assert kmp["in_place"] is False
@@ -0,0 +1,33 @@
+import pytest
+from advanced_ds_playground_bindings import KdTree
+
+def test_nearest_and_range():
+    print("\n[TEST] KdTree: Nearest neighbours and box queries in 2D and 3D")
+    tree = KdTree([(2, 3), (5, 4), (9, 6), (4, 7), (8, 1), (7, 2)])
+    print(f"[INFO] {tree!r}")
+    assert (len(tree), tree.dims) == (6, 2)
+    index, dist = tree.nearest([9, 2])
+    assert index == 4 and abs(dist - 2 ** 0.5) < 1e-12
+    assert [i for i, _ in tree.k_nearest((5, 5), 3)] == [1, 3, 0]
+    assert tree.range([4, 2], [8, 7]) == [1, 3, 5]
+    cube = KdTree([[0, 0, 0], [1, 1, 1], [2, 2, 2]])
+    assert cube.dims == 3 and cube.nearest((1.9, 2, 2))[0] == 2
+    assert KdTree([]).nearest((0, 0)) is None
+    with pytest.raises(ValueError):
+        tree.nearest((1, 2, 3))
+    with pytest.raises(ValueError):
+        KdTree([(1, 2, 3, 4)])
+    assert KdTree.info()["name"] == "KdTree"
+
+def test_numpy_points():
+    print("\n[TEST] KdTree: Points from a numpy array")
+    try:
+        import numpy as np
+    except ImportError:
+        print("[INFO] numpy not installed, skipping")
+        return
+    pts = np.random.default_rng(0).random((200, 3))
+    tree = KdTree(pts)
+    query = np.array([0.5, 0.5, 0.5])
+    expected = np.argsort(((pts - query) ** 2).sum(axis=1), kind="stable")[:4]
+    assert [i for i, _ in tree.k_nearest(query, 4)] == list(expected)
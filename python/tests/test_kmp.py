@@ -1,5 +1,5 @@
 import pytest
-from advanced_ds_playground_bindings import find_all, prefix_function
+from advanced_ds_playground_bindings import find_all, find_all_chars, prefix_function
 
 def test_prefix_function_basic():
     print("\n[TEST] KMP: Prefix function basic")
@@ -51,4 +51,34 @@ def test_pattern_longer_than_text():
     print("\n[TEST] KMP: Pattern longer than text")
     occ = find_all("abc", "abcdef")
     print(f"[INFO] Got occurrences: {occ}")
-    assert occ == []
\ No newline at end of file
+    assert occ == []
+
+def test_find_all_chars_multibyte():
+    print("\n[TEST] KMP: Char indices on multi-byte text")
+    text = "héllo wörld, héllo"
+    pattern = "llo"
+    byte_occ = find_all(text, pattern)
+    char_occ = find_all_chars(text, pattern)
+    print(f"[INFO] Byte offsets: {byte_occ}, Char indices: {char_occ}")
+    assert byte_occ == [3, 18]
+    assert char_occ == [2, 15]
+    for idx in char_occ:
+        assert text[idx:idx + len(pattern)] == pattern
+    assert find_all_chars("日本語の日本", "日本") == [0, 4]
+
+def test_find_all_ignore_case_and_table():
+    print("\n[TEST] KMP: Case-insensitive and normalized search")
+    log = "GET /a 200\nget /A 404\nPost /a_b 200\n"
+    assert find_all(log, "get /a", ignore_case=True) == [0, 11]
+    assert find_all(log, "get /A") == [11]
+    table = bytes(b if b != ord("_") else ord("-") for b in range(256))
+    assert find_all(log, "/A-B", ignore_case=True, table=table) == [27]
+    assert find_all(log, "/a-b", table=table) == [27]
+    with pytest.raises(ValueError):
+        find_all(log, "a", table=b"short")
+    # char indices stay valid next to multi-byte chars
+    text = "café CAFÉ Café"
+    occ = find_all_chars(text, "caf", ignore_case=True)
+    print(f"[INFO] Got char indices: {occ}")
+    assert occ == [0, 5, 10]
+    assert all(text[i:i + 3].lower() == "caf" for i in occ)
@@ -0,0 +1,24 @@
+import pytest
+from advanced_ds_playground_bindings import math
+from advanced_ds_playground_bindings.math import factorize, primes_in_range
+
+def test_sieves():
+    print("\n[TEST] math: Linear and segmented sieves")
+    assert math.primes_up_to(30) == [2, 3, 5, 7, 11, 13, 17, 19, 23, 29]
+    assert math.primes_up_to(1) == []
+    assert math.smallest_prime_factors(10) == [0, 0, 2, 3, 2, 5, 2, 7, 2, 3, 2]
+    assert primes_in_range(0, 30) == math.primes_up_to(29)
+    assert primes_in_range(24, 29) == []
+    assert primes_in_range(10**12, 10**12 + 40) == [10**12 + 39]
+    assert primes_in_range(2**40 - 100, 2**40) == [2**40 - 87]
+    with pytest.raises(ValueError):
+        primes_in_range(2**64 - 1000, 2**64 - 1)
+    with pytest.raises(OverflowError):
+        math.primes_up_to(-1)
+
+def test_factorize():
+    print("\n[TEST] math: Factorization")
+    assert factorize(600851475143) == [(71, 1), (839, 1), (1471, 1), (6857, 1)]
+    assert factorize(10**12) == [(2, 12), (5, 12)]
+    assert factorize(1) == []
+    assert factorize(97) == [(97, 1)]
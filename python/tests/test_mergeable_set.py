@@ -0,0 +1,41 @@
+import pytest
+from advanced_ds_playground_bindings import MergeableSet
+
+def test_pop_both_ends():
+    print("\n[TEST] MergeableSet: Pop min and max")
+    s = MergeableSet([4, 1, 7, 4, 9])
+    print(f"[INFO] Initial: {s.to_list()}")
+    assert len(s) == 5
+    assert s.pop_min() == 1
+    assert s.pop_max() == 9
+    assert s.to_list() == [4, 4, 7]
+    assert 4 in s
+    assert s.remove(4)
+    assert not s.remove(42)
+    print(f"[INFO] After pops and remove: {s.to_list()}")
+    assert s.to_list() == [4, 7]
+
+def test_pop_empty_raises():
+    print("\n[TEST] MergeableSet: Pop from empty raises IndexError")
+    s = MergeableSet()
+    with pytest.raises(IndexError):
+        s.pop_min()
+    with pytest.raises(IndexError):
+        s.pop_max()
+
+def test_merge_and_split():
+    print("\n[TEST] MergeableSet: Merge and split")
+    a = MergeableSet([1, 5, 9])
+    b = MergeableSet([2, 5, 10])
+    print("[STEP] Merging b into a")
+    a.merge(b)
+    print(f"[INFO] a = {a.to_list()}, b = {b.to_list()}")
+    assert a.to_list() == [1, 2, 5, 5, 9, 10]
+    assert len(b) == 0
+    assert a.kth(3) == 5
+    print("[STEP] Splitting off keys >= 5")
+    high = a.split_off(5)
+    assert a.to_list() == [1, 2]
+    assert high.to_list() == [5, 5, 9, 10]
+    with pytest.raises(ValueError):
+        a.merge(a)
@@ -0,0 +1,30 @@
+import pytest
+from advanced_ds_playground_bindings import (
+    largest_rectangle,
+    next_greater,
+    next_smaller,
+    previous_greater,
+    previous_smaller,
+)
+
+def test_nearest_elements():
+    print("\n[TEST] Monotonic stack: Nearest greater and smaller elements")
+    v = [2, 1, 2, 4, 3, 1]
+    assert next_greater(v) == [3, 2, 3, None, None, None]
+    assert previous_greater(v) == [None, 0, None, None, 3, 4]
+    assert next_smaller(v) == [1, None, 5, 4, 5, None]
+    assert previous_smaller(v) == [None, None, 1, 2, 2, None]
+    # large ints stay exact
+    assert next_greater([2**62 + 1, 2**62]) == [None, None]
+    assert next_greater([0.5, 0.25, 1]) == [2, 2, None]
+    assert next_greater([]) == []
+    with pytest.raises(ValueError):
+        next_greater([1.0, float("nan")])
+
+def test_largest_rectangle():
+    print("\n[TEST] Monotonic stack: Largest rectangle in a histogram")
+    assert largest_rectangle([2, 1, 5, 6, 2, 3]) == (10, 2, 4)
+    assert largest_rectangle([3, 3, 3]) == (9, 0, 3)
+    assert largest_rectangle([]) == (0, 0, 0)
+    with pytest.raises(OverflowError):
+        largest_rectangle([-1])
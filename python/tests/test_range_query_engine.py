@@ -0,0 +1,34 @@
+import pytest
+from advanced_ds_playground_bindings import RangeQueryEngine
+
+def test_backend_selection():
+    print("\n[TEST] RangeQueryEngine: Backend chosen from workload")
+    big = [0] * 100_000
+    static = RangeQueryEngine(big, queries=1_000_000, updates=0)
+    mixed = RangeQueryEngine(big, queries=100_000, updates=100_000)
+    small = RangeQueryEngine([0] * 16, queries=100_000, updates=100_000)
+    for name, e in [("static", static), ("mixed", mixed), ("small", small)]:
+        print(f"[INFO] {name}: {e.explain()}")
+    assert static.backend == "sparse table"
+    assert mixed.backend == "segment tree"
+    assert small.backend == "sqrt decomposition"
+
+def test_query_update_all_backends():
+    print("\n[TEST] RangeQueryEngine: Queries agree on every backend")
+    arr = [5, 2, 8, 6, 3, 7, 1, 4]
+    for backend in ["sparse_table", "segment_tree", "sqrt"]:
+        e = RangeQueryEngine(arr, backend=backend)
+        print(f"[STEP] Backend {e.backend}")
+        assert len(e) == 8
+        assert e.query(0, 3) == 2
+        assert e.query(4, 7) == 1
+        e.update(6, 10)
+        assert e.query(4, 7) == 3
+        assert e.query(5, 4) is None
+        with pytest.raises(IndexError):
+            e.update(8, 0)
+
+def test_unknown_backend():
+    print("\n[TEST] RangeQueryEngine: Unknown backend name")
+    with pytest.raises(ValueError):
+        RangeQueryEngine([1, 2, 3], backend="btree")
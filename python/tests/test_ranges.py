@@ -0,0 +1,36 @@
+import pytest
+from advanced_ds_playground_bindings import FenwickTree, SparseTable, RangeQueryEngine
+
+VALUES = [5, -2, 4, 7, 1, 3, 9, 0]
+
+def test_fenwick_slices():
+    print("\n[TEST] Ranges: FenwickTree slicing sums")
+    ft = FenwickTree(VALUES)
+    assert ft[2:5] == sum(VALUES[2:5])
+    assert ft[:] == sum(VALUES)
+    assert ft[-3:] == sum(VALUES[-3:])
+    assert ft[6:100] == sum(VALUES[6:100])
+    assert ft[5:2] == 0
+    assert ft[3] == 7 and ft[-1] == 0
+    print(f"[INFO] ft[2:5] = {ft[2:5]}")
+    with pytest.raises(IndexError):
+        ft[8]
+    with pytest.raises(ValueError):
+        ft[::2]
+
+def test_min_structures_slices():
+    print("\n[TEST] Ranges: SparseTable and RangeQueryEngine slicing minima")
+    structures = [SparseTable(VALUES)] + [
+        RangeQueryEngine(VALUES, backend=b) for b in ["sparse_table", "segment_tree", "sqrt"]
+    ]
+    for s in structures:
+        assert len(s) == len(VALUES)
+        for a in range(-len(VALUES), len(VALUES) + 1):
+            for b in range(-len(VALUES), len(VALUES) + 2):
+                chunk = VALUES[a:b]
+                assert s[a:b] == (min(chunk) if chunk else None)
+        assert s[1] == -2 and s[-2] == 9
+        # the inclusive index-pair API is unchanged
+        assert s.query(1, 4) == s[1:5]
+    with pytest.raises(IndexError):
+        SparseTable([])[0]
@@ -0,0 +1,40 @@
+import pytest
+from advanced_ds_playground_bindings import ReachabilityOracle, transitive_closure
+
+def test_oracle_queries():
+    print("\n[TEST] ReachabilityOracle: Path queries on a graph with a cycle")
+    oracle = ReachabilityOracle(5, [(0, 1), (1, 0), (1, 2), (2, 3)])
+    print(f"[INFO] {oracle!r}")
+    assert len(oracle) == 5 and oracle.label_size > 0
+    print("[STEP] Vertices on the cycle reach each other and everything downstream")
+    assert oracle.reaches(1, 0) and oracle.reaches(0, 3)
+    print("[STEP] Edges are one-way and vertex 4 is isolated")
+    assert not oracle.reaches(3, 0)
+    assert not oracle.reaches(0, 4)
+    assert oracle.reaches(4, 4)
+    print("[STEP] Out-of-range vertices raise IndexError")
+    with pytest.raises(IndexError):
+        oracle.reaches(0, 5)
+    with pytest.raises(IndexError):
+        ReachabilityOracle(2, [(0, 2)])
+    assert ReachabilityOracle.info()["name"] == "ReachabilityOracle"
+
+def test_transitive_closure():
+    print("\n[TEST] transitive_closure: Closure rows of a DAG")
+    closure = transitive_closure(5, [(0, 1), (1, 2), (0, 3), (2, 3), (4, 3)])
+    assert closure == [[1, 2, 3], [2, 3], [3], [], [3]]
+    print("[STEP] A cycle raises ValueError")
+    with pytest.raises(ValueError):
+        transitive_closure(2, [(0, 1), (1, 0)])
+
+def test_matches_closure_on_random_dag():
+    print("\n[TEST] ReachabilityOracle: Agrees with the transitive closure on a random DAG")
+    import random
+    rng = random.Random(7)
+    n = 60
+    edges = [(u, v) for u in range(n) for v in range(u + 1, n) if rng.random() < 0.05]
+    closure = transitive_closure(n, edges)
+    oracle = ReachabilityOracle(n, edges)
+    for u in range(n):
+        for v in range(n):
+            assert oracle.reaches(u, v) == (u == v or v in closure[u])
@@ -0,0 +1,77 @@
+import math
+import pytest
+from advanced_ds_playground_bindings import SegmentTree
+
+VALUES = [5, -2, 4, 7, 1, 3, 9, 0, 6]
+
+def test_builtin_ops():
+    print("\n[TEST] SegmentTree: Built-in operations")
+    reference = {
+        "sum": lambda xs: sum(xs),
+        "min": lambda xs: min(xs) if xs else None,
+        "max": lambda xs: max(xs) if xs else None,
+        "gcd": lambda xs: math.gcd(*xs) if xs else 0,
+    }
+    for op, ref in reference.items():
+        st = SegmentTree(VALUES, op)
+        assert st.op == op and len(st) == len(VALUES)
+        for l in range(len(VALUES) + 1):
+            for r in range(l, len(VALUES) + 1):
+                assert st.query(l, r) == ref(VALUES[l:r]), (op, l, r)
+        assert st[2:5] == ref(VALUES[2:5]) and st[-1] == 6
+        assert st.all() == ref(VALUES)
+    print("[INFO] sum, min, max and gcd agree with Python on every range")
+    assert SegmentTree(VALUES).op == "sum"
+
+def test_updates():
+    print("\n[TEST] SegmentTree: Point updates")
+    values = list(VALUES)
+    st = SegmentTree(values, "min")
+    for i, v in [(3, -10), (-1, -20), (0, 100)]:
+        st[i] = v
+        values[i] = v
+        assert st[:] == min(values)
+    assert st[3] == -10
+    with pytest.raises(IndexError):
+        st[9] = 1
+    with pytest.raises(IndexError):
+        st.query(2, 10)
+
+def test_custom_callable():
+    print("\n[TEST] SegmentTree: Custom callable monoid")
+    words = ["a", "b", "c", "d"]
+    st = SegmentTree(words, lambda a, b: a + b, identity="")
+    assert st.op == "custom"
+    assert st.query(1, 3) == "bc"
+    st[2] = "X"
+    assert st[:] == "abXd"
+    assert st[2:2] == ""
+    with pytest.raises(ValueError):
+        SegmentTree(words, lambda a, b: a + b)
+    with pytest.raises(ValueError):
+        SegmentTree(VALUES, "product")
+    with pytest.raises(TypeError):
+        SegmentTree(VALUES, 3)
+
+    def broken(a, b):
+        raise RuntimeError("boom")
+    with pytest.raises(RuntimeError):
+        SegmentTree([1, 2, 3], broken, identity=0)
+
+def test_descent():
+    print("\n[TEST] SegmentTree: max_right / min_left")
+    values = [3, 1, 4, 1, 5, 9, 2, 6]
+    st = SegmentTree(values)
+    # first index where the prefix sum exceeds 10
+    r = st.max_right(0, lambda s: s <= 10)
+    print(f"[INFO] First index with prefix sum > 10: {r}")
+    assert r == 4
+    assert st.max_right(0, lambda s: s <= 1000) == len(values)
+    assert st.min_left(8, lambda s: s <= 8) == 6
+    custom = SegmentTree(values, lambda a, b: a + b, identity=0)
+    assert custom.max_right(2, lambda s: s < 10) == 4
+    with pytest.raises(ZeroDivisionError):
+        st.max_right(0, lambda s: 1 / 0)
+    assert SegmentTree.info()["name"] == "IterativeSegmentTree"
+    with pytest.raises(ValueError):
+        st.max_right(0, lambda s: s > 0)
@@ -0,0 +1,49 @@
+import os
+import tempfile
+
+import pytest
+from advanced_ds_playground_bindings import SparseTable, SuffixArray, FMIndex
+
+def _path(name):
+    return os.path.join(tempfile.mkdtemp(), name)
+
+def test_sparse_table_snapshot():
+    print("\n[TEST] Snapshot: SparseTable save/load")
+    st = SparseTable([5, 2, 4, 7, 1, 3])
+    path = _path("table.bin")
+    st.save(path)
+    print(f"[INFO] Snapshot size: {os.path.getsize(path)} bytes")
+    loaded = SparseTable.load(path)
+    assert loaded.query(0, 2) == 2
+    assert loaded.query(1, 5) == 1
+    assert loaded.query(3, 3) == 7
+
+def test_string_index_snapshots():
+    print("\n[TEST] Snapshot: SuffixArray and FMIndex save/load")
+    text = "abracadabra " * 50
+    sa_path, fm_path = _path("sa.bin"), _path("fm.bin")
+    sa = SuffixArray(text)
+    fm = FMIndex(text, sample_rate=8)
+    sa.save(sa_path)
+    fm.save(fm_path)
+    sa2 = SuffixArray.load(sa_path)
+    fm2 = FMIndex.load(fm_path)
+    assert len(sa2) == len(fm2) == len(text)
+    assert sa2.find("cad") == fm2.locate("cad") == sa.find("cad")
+    assert fm2.count("abra") == 100
+    assert sa2.sa == sa.sa and sa2.lcp == sa.lcp
+
+def test_bad_snapshots():
+    print("\n[TEST] Snapshot: Wrong kind, corrupt and missing files")
+    path = _path("table.bin")
+    SparseTable([1, 2, 3]).save(path)
+    with pytest.raises(ValueError):
+        FMIndex.load(path)
+    with open(path, "r+b") as f:
+        f.truncate(10)
+    with pytest.raises(ValueError):
+        SparseTable.load(path)
+    with pytest.raises(FileNotFoundError):
+        SuffixArray.load(_path("missing.bin"))
+    with pytest.raises(ValueError):
+        FMIndex("abc", sample_rate=0)
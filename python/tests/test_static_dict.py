@@ -0,0 +1,33 @@
+import pytest
+from advanced_ds_playground_bindings import StaticDict
+
+def test_lookup_and_order():
+    print("\n[TEST] StaticDict: Lookup keeps source order")
+    source = {"fn": 1, "let": 2, "match": 3, "while": 4}
+    d = StaticDict(source)
+    print(f"[INFO] Keys: {d.keys()}")
+    assert len(d) == 4
+    assert d.keys() == ["fn", "let", "match", "while"]
+    for i, (k, v) in enumerate(source.items()):
+        assert d[k] == v
+        assert d.index(k) == i
+    assert "let" in d
+    assert "for" not in d
+
+def test_missing_keys():
+    print("\n[TEST] StaticDict: Missing keys")
+    d = StaticDict({"a": [1, 2], "b": None})
+    assert d["a"] == [1, 2]
+    assert d.get("b", "fallback") is None
+    assert d.get("c") is None
+    assert d.get("c", 0) == 0
+    assert d.index("c") is None
+    with pytest.raises(KeyError):
+        d["c"]
+
+def test_large_table():
+    print("\n[TEST] StaticDict: Large keyword table")
+    source = {f"word{i}": i * i for i in range(2000)}
+    d = StaticDict(source)
+    assert all(d[k] == v for k, v in source.items())
+    print(f"[INFO] {len(d)} keys verified")
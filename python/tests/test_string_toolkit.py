@@ -0,0 +1,53 @@
+import pytest
+from advanced_ds_playground_bindings import (
+    z_function, manacher, longest_palindrome, lyndon_factorization, minimal_rotation, StringHasher,
+)
+
+def test_z_function_str_and_bytes():
+    print("\n[TEST] Strings: Z-function")
+    assert z_function("aabxaab") == [7, 1, 0, 0, 3, 1, 0]
+    assert z_function(b"aabxaab") == [7, 1, 0, 0, 3, 1, 0]
+    # str indices are code points, bytes indices are bytes
+    assert z_function("éaé") == [3, 0, 1]
+    assert len(z_function("éaé".encode())) == 5
+    assert z_function("") == []
+    with pytest.raises(TypeError):
+        z_function(42)
+
+def test_palindromes():
+    print("\n[TEST] Strings: Manacher and longest palindrome")
+    assert longest_palindrome("forgeeksskeegfor") == "geeksskeeg"
+    assert longest_palindrome(b"xabbay") == b"abba"
+    assert longest_palindrome("xyzé€éq") == "é€é"
+    assert longest_palindrome("") == ""
+    s = "abacabad"
+    odd, even = manacher(s)
+    count = sum(1 for l in range(len(s)) for r in range(l + 1, len(s) + 1) if s[l:r] == s[l:r][::-1])
+    print(f"[INFO] {count} palindromic substrings in {s!r}")
+    assert sum(odd) + sum(even) == count
+
+def test_lyndon_and_rotation():
+    print("\n[TEST] Strings: Lyndon factorization and minimal rotation")
+    assert lyndon_factorization("banana") == [0, 1, 3, 5]
+    assert lyndon_factorization(b"banana") == [0, 1, 3, 5]
+    s = "cabbage"
+    k = minimal_rotation(s)
+    assert s[k:] + s[:k] == min(s[i:] + s[:i] for i in range(len(s)))
+
+def test_string_hasher():
+    print("\n[TEST] Strings: StringHasher")
+    for text in ["abcabcab", b"abcabcab", "é€é€x"]:
+        h = StringHasher(text)
+        assert len(h) == len(text)
+        for i in range(len(text) + 1):
+            for j in range(len(text) + 1):
+                common = 0
+                while i + common < len(text) and j + common < len(text) and text[i + common] == text[j + common]:
+                    common += 1
+                assert h.lcp(i, j) == common
+        assert h.equal(0, 2, 2, 4) == (text[0:2] == text[2:4])
+    h = StringHasher("abc")
+    assert h.hash(0, 0) == h.hash(1, 1)
+    with pytest.raises(IndexError):
+        h.hash(2, 4)
+    assert StringHasher.info()["name"] == "StringHasher"
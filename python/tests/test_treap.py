@@ -79,4 +79,39 @@ def test_mass_inserts_removes_stability():
         t.remove(v)
         
     print(f"[INFO] Final empty state: {t.is_empty()}, Length: {t.len()}")
-    assert t.is_empty()
\ No newline at end of file
+    assert t.is_empty()
+
+def test_iteration_in_order():
+    print("\n[TEST] Treap: Iteration yields keys in order")
+    t = Treap()
+    for v in [8, 2, 5, 2, 9]:
+        t.insert(v)
+    keys = list(t)
+    print(f"[INFO] Iterated keys: {keys}")
+    assert keys == [2, 2, 5, 8, 9]
+    assert list(iter(Treap())) == []
+
+def test_iteration_detects_modification():
+    print("\n[TEST] Treap: Mutation during iteration raises RuntimeError")
+    t = Treap()
+    for v in range(5):
+        t.insert(v)
+
+    it = iter(t)
+    assert next(it) == 0
+    print("[STEP] Inserting 42 while an iterator is live")
+    t.insert(42)
+    with pytest.raises(RuntimeError, match="changed size during iteration"):
+        next(it)
+
+    print("[STEP] Removing a missing key does not invalidate iterators")
+    it = iter(t)
+    t.remove(1000)
+    assert next(it) == 0
+
+    print("[STEP] Removing a present key does")
+    t.remove(3)
+    with pytest.raises(RuntimeError):
+        next(it)
+    print(f"[INFO] Fresh iteration after mutations: {list(t)}")
+    assert list(t) == [0, 1, 2, 4, 42]
@@ -0,0 +1,39 @@
+from advanced_ds_playground_bindings import Trie
+
+def test_insert_and_contains():
+    print("\n[TEST] Trie: Insert and contains")
+    t = Trie()
+    print("[STEP] Inserting 'tea', 'ten', 'te', 'tea' (duplicate)")
+    assert t.insert("tea")
+    assert t.insert("ten")
+    assert t.insert("te")
+    assert not t.insert("tea")
+    print(f"[INFO] Length: {len(t)}")
+    assert len(t) == 3
+    assert "tea" in t
+    assert t.contains("te")
+    assert "t" not in t
+    assert "teas" not in t
+
+def test_prefix_queries():
+    print("\n[TEST] Trie: Prefix counting and listing")
+    t = Trie()
+    for w in ["apple", "app", "apply", "banana", "band", "ape"]:
+        t.insert(w)
+    print(f"[INFO] count_prefix('ap') = {t.count_prefix('ap')}")
+    assert t.count_prefix("ap") == 4
+    assert t.count_prefix("c") == 0
+    keys = t.keys_with_prefix("app")
+    print(f"[INFO] keys_with_prefix('app') = {keys}")
+    assert keys == ["app", "apple", "apply"]
+    assert t.keys_with_prefix() == ["ape", "app", "apple", "apply", "banana", "band"]
+
+def test_longest_prefix():
+    print("\n[TEST] Trie: Longest prefix match")
+    t = Trie()
+    for route in ["/", "/usr", "/usr/local"]:
+        t.insert(route)
+    for query, expected in [("/usr/local/bin", "/usr/local"), ("/usr/lib", "/usr"), ("/etc", "/"), ("etc", None)]:
+        got = t.longest_prefix(query)
+        print(f"[INFO] '{query}' -> {got!r}")
+        assert got == expected
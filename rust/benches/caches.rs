@@ -0,0 +1,54 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust::cache::{Cache, LfuCache, LruCache};
+use rust::rng::{Rng, SplitMix64};
+
+const CAPACITY: usize = 1 << 10;
+
+/// A request trace mixing a skewed working set with one-off scans, which LRU admits and LFU
+/// mostly evicts again.
+fn trace() -> Vec<u64> {
+    let mut rng = SplitMix64::new(4242);
+    let mut next = || rng.next_u64() >> 33;
+    let mut scan = 1u64 << 40;
+    (0..1 << 18)
+        .map(|i| {
+            if i % 1000 < 100 {
+                scan += 1;
+                scan
+            } else {
+                // roughly Zipfian: small keys are far more frequent
+                next() % (1 + next() % 20_000)
+            }
+        })
+        .collect()
+}
+
+/// Replays `trace` as read-through lookups and returns the hit count.
+fn replay<C: Cache<u64, u64>>(cache: &mut C, trace: &[u64]) -> usize {
+    let mut hits = 0;
+    for &key in trace {
+        if cache.get(&key).is_some() {
+            hits += 1;
+        } else {
+            cache.put(key, key);
+        }
+    }
+    hits
+}
+
+fn bench_replay(c: &mut Criterion) {
+    let trace = trace();
+    let lru_hits = replay(&mut LruCache::new(CAPACITY), &trace);
+    let lfu_hits = replay(&mut LfuCache::new(CAPACITY), &trace);
+    let rate = |hits: usize| 100.0 * hits as f64 / trace.len() as f64;
+    println!("hit rate: lru_cache {:.1}%, lfu_cache {:.1}%", rate(lru_hits), rate(lfu_hits));
+
+    let mut group = c.benchmark_group("cache_replay");
+    group.sample_size(20);
+    group.bench_function("lru_cache", |b| b.iter(|| black_box(replay(&mut LruCache::new(CAPACITY), &trace))));
+    group.bench_function("lfu_cache", |b| b.iter(|| black_box(replay(&mut LfuCache::new(CAPACITY), &trace))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_replay);
+criterion_main!(benches);
@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust::csr_graph::CsrGraph;
+use std::collections::VecDeque;
+
+const NODES: usize = 100_000;
+const EDGES: usize = 1_000_000;
+
+fn random_adjacency() -> Vec<Vec<usize>> {
+    let mut seed = 0x2545F4914F6CDD1Du64;
+    let mut next = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        seed as usize % NODES
+    };
+    let mut adj = vec![Vec::new(); NODES];
+    for _ in 0..EDGES {
+        let (u, v) = (next(), next());
+        adj[u].push(v);
+    }
+    adj
+}
+
+fn bfs_vec_of_vecs(adj: &[Vec<usize>], src: usize) -> Vec<usize> {
+    let mut dist = vec![usize::MAX; adj.len()];
+    let mut queue = VecDeque::new();
+    dist[src] = 0;
+    queue.push_back(src);
+    while let Some(u) = queue.pop_front() {
+        for &v in &adj[u] {
+            if dist[v] == usize::MAX {
+                dist[v] = dist[u] + 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    dist
+}
+
+fn bench_bfs(c: &mut Criterion) {
+    let adj = random_adjacency();
+    let csr = CsrGraph::from_unweighted_adjacency(&adj);
+
+    let mut group = c.benchmark_group("bfs_1m_edges");
+    group.sample_size(10);
+    group.bench_function("vec_of_vecs", |b| b.iter(|| bfs_vec_of_vecs(black_box(&adj), 0)));
+    group.bench_function("csr", |b| b.iter(|| black_box(&csr).bfs_distances(0)));
+    group.finish();
+}
+
+fn bench_build(c: &mut Criterion) {
+    let adj = random_adjacency();
+    let mut group = c.benchmark_group("csr_build");
+    group.sample_size(10);
+    group.bench_function("from_adjacency", |b| b.iter(|| CsrGraph::from_unweighted_adjacency(black_box(&adj))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_bfs, bench_build);
+criterion_main!(benches);
@@ -0,0 +1,68 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust::bloom_filter::BloomFilter;
+use rust::cuckoo_filter::CuckooFilter;
+use rust::rng::{Rng, SplitMix64};
+use std::collections::HashSet;
+
+// fills 76% of the cuckoo filter's 2^16 slots; 2^16 keys would not fit and need 2^17
+const N: usize = 50_000;
+
+fn keys() -> Vec<u64> {
+    let mut rng = SplitMix64::new(4242);
+    (0..2 * N).map(|_| rng.next_u64() >> 11).collect()
+}
+
+/// Lookups of N present and N (mostly) absent keys, after printing each structure's size per key.
+/// The Bloom filter is sized for a false-positive rate close to the cuckoo filter's.
+fn bench_lookup(c: &mut Criterion) {
+    let keys = keys();
+    let (present, _) = keys.split_at(N);
+    let mut bloom = BloomFilter::new(N, 1e-4);
+    let mut cuckoo = CuckooFilter::new(N);
+    present.iter().for_each(|k| {
+        bloom.insert(k);
+        cuckoo.insert(k);
+    });
+    let set: HashSet<u64> = present.iter().copied().collect();
+    println!("bits per key: bloom_filter {:.1}, cuckoo_filter {:.1}, std_hashset {:.1}",
+        bloom.num_bits() as f64 / N as f64,
+        16.0 * cuckoo.capacity() as f64 / N as f64,
+        8.0 * (set.capacity() * (std::mem::size_of::<u64>() + 1)) as f64 / N as f64);
+
+    let mut group = c.benchmark_group("filter_lookup");
+    group.bench_function("bloom_filter", |b| b.iter(|| black_box(keys.iter().filter(|k| bloom.maybe_contains(k)).count())));
+    group.bench_function("cuckoo_filter", |b| b.iter(|| black_box(keys.iter().filter(|k| cuckoo.maybe_contains(k)).count())));
+    group.bench_function("std_hashset", |b| b.iter(|| black_box(keys.iter().filter(|k| set.contains(k)).count())));
+    group.finish();
+}
+
+/// Building each structure from N keys.
+fn bench_insert(c: &mut Criterion) {
+    let keys = keys();
+    let present = &keys[..N];
+    let mut group = c.benchmark_group("filter_insert");
+    group.sample_size(20);
+    group.bench_function("bloom_filter", |b| {
+        b.iter(|| {
+            let mut filter = BloomFilter::new(N, 1e-4);
+            present.iter().for_each(|k| {
+                filter.insert(k);
+            });
+            black_box(filter.num_bits())
+        })
+    });
+    group.bench_function("cuckoo_filter", |b| {
+        b.iter(|| {
+            let mut filter = CuckooFilter::new(N);
+            present.iter().for_each(|k| {
+                filter.insert(k);
+            });
+            black_box(filter.len())
+        })
+    });
+    group.bench_function("std_hashset", |b| b.iter(|| black_box(present.iter().copied().collect::<HashSet<u64>>().len())));
+    group.finish();
+}
+
+criterion_group!(benches, bench_lookup, bench_insert);
+criterion_main!(benches);
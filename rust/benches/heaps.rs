@@ -0,0 +1,118 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust::dary_heap::DaryHeap;
+use rust::fibonacci_heap::FibonacciHeap;
+use rust::heap::{AddressableHeap, Heap};
+use rust::indexed_heap::IndexedHeap;
+use rust::interval_heap::IntervalHeap;
+use rust::leftist_heap::LeftistHeap;
+use rust::pairing_heap::PairingHeap;
+use rust::rng::{Rng, SplitMix64};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+const N: usize = 1 << 16;
+
+fn keys() -> Vec<u64> {
+    let mut rng = SplitMix64::new(12345);
+    (0..N).map(|_| rng.next_u64() >> 33).collect()
+}
+
+/// Push everything, then pop everything: the pop phase dominates, which is where wider nodes help.
+fn push_pop<H: Heap<Item = u64> + Default>(keys: &[u64]) -> u64 {
+    let mut heap = H::default();
+    keys.iter().for_each(|&k| heap.push(k));
+    std::iter::from_fn(|| heap.pop()).fold(0, u64::wrapping_add)
+}
+
+fn bench_push_pop(c: &mut Criterion) {
+    let keys = keys();
+    let mut group = c.benchmark_group("heap_push_pop");
+    group.sample_size(20);
+    group.bench_function("std_binary_heap", |b| {
+        b.iter(|| {
+            let mut heap: BinaryHeap<Reverse<u64>> = BinaryHeap::with_capacity(keys.len());
+            keys.iter().for_each(|&k| heap.push(Reverse(k)));
+            black_box(std::iter::from_fn(|| heap.pop()).fold(0u64, |acc, Reverse(k)| acc.wrapping_add(k)))
+        })
+    });
+    group.bench_function("dary_2", |b| b.iter(|| black_box(push_pop::<DaryHeap<_, 2>>(&keys))));
+    group.bench_function("dary_4", |b| b.iter(|| black_box(push_pop::<DaryHeap<_, 4>>(&keys))));
+    group.bench_function("dary_8", |b| b.iter(|| black_box(push_pop::<DaryHeap<_, 8>>(&keys))));
+    group.bench_function("interval", |b| b.iter(|| black_box(push_pop::<IntervalHeap<_>>(&keys))));
+    group.bench_function("pairing", |b| b.iter(|| black_box(push_pop::<PairingHeap<_>>(&keys))));
+    group.bench_function("leftist", |b| b.iter(|| black_box(push_pop::<LeftistHeap<_>>(&keys))));
+    group.bench_function("fibonacci", |b| b.iter(|| black_box(push_pop::<FibonacciHeap<_>>(&keys))));
+    group.finish();
+}
+
+/// Complete digraph with pseudo-random weights, stored as a matrix: every relaxation can lower a
+/// tentative distance, which is the workload where decrease-key heaps are supposed to shine.
+fn dense_graph(n: usize) -> Vec<Vec<u64>> {
+    let mut rng = SplitMix64::new(777);
+    (0..n).map(|_| (0..n).map(|_| 1 + rng.next_u64() % 1000).collect()).collect()
+}
+
+/// Dijkstra relaxing with decrease-key, for any addressable heap backend.
+fn dijkstra_decrease_key<H: AddressableHeap<Item = (u64, usize)> + Default>(g: &[Vec<u64>]) -> u64 {
+    let n = g.len();
+    let mut dist = vec![u64::MAX; n];
+    let mut handle: Vec<Option<H::Handle>> = vec![None; n];
+    let mut done = vec![false; n];
+    let mut heap = H::default();
+    dist[0] = 0;
+    handle[0] = Some(heap.push_handle((0, 0)));
+    while let Some((d, u)) = heap.pop() {
+        done[u] = true;
+        for (v, &w) in g[u].iter().enumerate() {
+            let nd = d + w;
+            if done[v] || nd >= dist[v] {
+                continue;
+            }
+            dist[v] = nd;
+            match handle[v] {
+                Some(h) => {
+                    heap.decrease_key(h, (nd, v));
+                }
+                None => handle[v] = Some(heap.push_handle((nd, v))),
+            }
+        }
+    }
+    dist.iter().sum()
+}
+
+/// Dijkstra without decrease-key, for any heap backend: push duplicates and skip stale entries.
+fn dijkstra_lazy<H: Heap<Item = (u64, usize)> + Default>(g: &[Vec<u64>]) -> u64 {
+    let n = g.len();
+    let mut dist = vec![u64::MAX; n];
+    let mut heap = H::default();
+    dist[0] = 0;
+    heap.push((0, 0));
+    while let Some((d, u)) = heap.pop() {
+        if d > dist[u] {
+            continue;
+        }
+        for (v, &w) in g[u].iter().enumerate() {
+            if d + w < dist[v] {
+                dist[v] = d + w;
+                heap.push((d + w, v));
+            }
+        }
+    }
+    dist.iter().sum()
+}
+
+fn bench_dense_dijkstra(c: &mut Criterion) {
+    let g = dense_graph(512);
+    let mut group = c.benchmark_group("dense_dijkstra");
+    group.sample_size(20);
+    group.bench_function("pairing", |b| b.iter(|| black_box(dijkstra_decrease_key::<PairingHeap<_>>(&g))));
+    group.bench_function("fibonacci", |b| b.iter(|| black_box(dijkstra_decrease_key::<FibonacciHeap<_>>(&g))));
+    group.bench_function("indexed", |b| b.iter(|| black_box(dijkstra_decrease_key::<IndexedHeap<_, _>>(&g))));
+    group.bench_function("lazy_pairing", |b| b.iter(|| black_box(dijkstra_lazy::<PairingHeap<_>>(&g))));
+    group.bench_function("lazy_dary_2", |b| b.iter(|| black_box(dijkstra_lazy::<DaryHeap<_, 2>>(&g))));
+    group.bench_function("lazy_dary_4", |b| b.iter(|| black_box(dijkstra_lazy::<DaryHeap<_, 4>>(&g))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_push_pop, bench_dense_dijkstra);
+criterion_main!(benches);
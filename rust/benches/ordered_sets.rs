@@ -0,0 +1,116 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust::avl_tree::AvlTree;
+use rust::rng::{Rng, SplitMix64};
+use rust::skip_list::SkipList;
+use rust::splay_tree::SplayTree;
+use rust::treap::Treap;
+use rust::van_emde_boas::VanEmdeBoas;
+use std::collections::BTreeSet;
+
+const N: usize = 1 << 16;
+
+fn keys() -> Vec<i64> {
+    let mut rng = SplitMix64::new(4242);
+    (0..N).map(|_| (rng.next_u64() >> 33) as i64).collect()
+}
+
+/// Insert every key, probe every key, then remove every key.
+fn bench_insert_contains_remove(c: &mut Criterion) {
+    let keys = keys();
+    let mut group = c.benchmark_group("ordered_set_insert_contains_remove");
+    group.sample_size(20);
+    group.bench_function("std_btreeset", |b| {
+        b.iter(|| {
+            let mut set = BTreeSet::new();
+            keys.iter().for_each(|&k| {
+                set.insert(k);
+            });
+            let hits = keys.iter().filter(|k| set.contains(k)).count();
+            keys.iter().for_each(|k| {
+                set.remove(k);
+            });
+            black_box(hits)
+        })
+    });
+    group.bench_function("skip_list", |b| {
+        b.iter(|| {
+            let mut set = SkipList::new();
+            keys.iter().for_each(|&k| {
+                set.insert(k);
+            });
+            let hits = keys.iter().filter(|k| set.contains(k)).count();
+            keys.iter().for_each(|k| {
+                set.remove(k);
+            });
+            black_box(hits)
+        })
+    });
+    group.bench_function("avl_tree", |b| {
+        b.iter(|| {
+            let mut set = AvlTree::new();
+            keys.iter().for_each(|&k| {
+                set.insert(k);
+            });
+            let hits = keys.iter().filter(|k| set.contains(k)).count();
+            keys.iter().for_each(|k| {
+                set.remove(k);
+            });
+            black_box(hits)
+        })
+    });
+    group.bench_function("splay_tree", |b| {
+        b.iter(|| {
+            let mut set = SplayTree::new();
+            keys.iter().for_each(|&k| {
+                set.insert(k);
+            });
+            let hits = keys.iter().filter(|k| set.contains(k)).count();
+            keys.iter().for_each(|k| {
+                set.remove(k);
+            });
+            black_box(hits)
+        })
+    });
+    group.bench_function("treap", |b| {
+        b.iter(|| {
+            let mut set = Treap::new();
+            keys.iter().for_each(|&k| set.insert(k));
+            let hits = keys.iter().filter(|&&k| set.contains(k)).count();
+            keys.iter().for_each(|&k| set.remove(k));
+            black_box(hits)
+        })
+    });
+    group.finish();
+}
+
+/// Order statistics on a prebuilt set: the Treap's `kth` against `get` on the skip list and AVL tree.
+fn bench_select(c: &mut Criterion) {
+    let keys = keys();
+    let list: SkipList<i64> = keys.iter().copied().collect();
+    let mut treap = Treap::new();
+    keys.iter().for_each(|&k| treap.insert(k));
+    let n = list.len();
+    let mut group = c.benchmark_group("ordered_set_select");
+    group.bench_function("skip_list", |b| b.iter(|| black_box((0..n).step_by(7).filter_map(|i| list.get(i).copied()).fold(0i64, i64::wrapping_add))));
+    let avl: AvlTree<i64> = keys.iter().copied().collect();
+    group.bench_function("avl_tree", |b| b.iter(|| black_box((0..n).step_by(7).filter_map(|i| avl.get(i).copied()).fold(0i64, i64::wrapping_add))));
+    group.bench_function("treap", |b| b.iter(|| black_box((0..n).step_by(7).filter_map(|i| treap.kth(i)).fold(0i64, i64::wrapping_add))));
+    group.finish();
+}
+
+/// Successor queries over 31-bit keys: BTreeSet range lookups against the van Emde Boas tree.
+fn bench_successor(c: &mut Criterion) {
+    let keys = keys();
+    let btree: BTreeSet<i64> = keys.iter().copied().collect();
+    let mut veb = VanEmdeBoas::new(31);
+    keys.iter().for_each(|&k| {
+        veb.insert(k as u64);
+    });
+    let mut group = c.benchmark_group("ordered_set_successor");
+    group.bench_function("std_btreeset", |b| b.iter(|| black_box(keys.iter().filter_map(|&k| btree.range(k + 1..).next().copied()).fold(0i64, i64::wrapping_add))));
+    group.bench_function("van_emde_boas", |b| b.iter(|| black_box(keys.iter().filter_map(|&k| veb.successor(k as u64)).fold(0u64, u64::wrapping_add))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_insert_contains_remove, bench_select, bench_successor);
+criterion_main!(benches);
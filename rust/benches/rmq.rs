@@ -0,0 +1,60 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust::fischer_heun::FischerHeun;
+use rust::rng::{Rng, SplitMix64};
+use rust::sparse_table::SparseTable;
+
+const N: usize = 1 << 20;
+
+fn values() -> Vec<u32> {
+    let mut rng = SplitMix64::new(777);
+    (0..N).map(|_| rng.next_u64() as u32).collect()
+}
+
+/// Random inclusive ranges [l, r], mixing short and long spans.
+fn ranges() -> Vec<(usize, usize)> {
+    let mut rng = SplitMix64::new(99);
+    let mut next = || rng.next_u64() as usize;
+    (0..100_000)
+        .map(|i| {
+            let l = next() % N;
+            let len = if i % 2 == 0 { next() % 64 } else { next() % (N - l) };
+            (l, (l + len).min(N - 1))
+        })
+        .collect()
+}
+
+/// Building each structure over N values, after printing how much memory each index takes.
+fn bench_build(c: &mut Criterion) {
+    let values = values();
+    let fh = FischerHeun::from_slice(&values);
+    // every level of the sparse table is a near-full copy of the array
+    let levels = N.ilog2() as usize + 1;
+    println!("index bytes per element: sparse_table {:.1}, fischer_heun {:.1} (values are {} bytes)",
+        (levels * size_of::<u32>()) as f64,
+        fh.index_bytes() as f64 / N as f64,
+        size_of::<u32>());
+
+    let mut group = c.benchmark_group("rmq_build");
+    group.sample_size(10);
+    group.bench_function("sparse_table", |b| b.iter(|| black_box(SparseTable::from_slice(&values).len())));
+    group.bench_function("fischer_heun", |b| b.iter(|| black_box(FischerHeun::from_slice(&values).len())));
+    group.finish();
+}
+
+fn bench_query(c: &mut Criterion) {
+    let values = values();
+    let ranges = ranges();
+    let table = SparseTable::from_slice(&values);
+    let fh = FischerHeun::from_slice(&values);
+    let mut group = c.benchmark_group("rmq_query");
+    group.bench_function("sparse_table", |b| {
+        b.iter(|| black_box(ranges.iter().map(|&(l, r)| table.query(l, r).unwrap() as u64).sum::<u64>()))
+    });
+    group.bench_function("fischer_heun", |b| {
+        b.iter(|| black_box(ranges.iter().map(|&(l, r)| fh.query(l, r).unwrap() as u64).sum::<u64>()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_build, bench_query);
+criterion_main!(benches);
@@ -0,0 +1,48 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust::fenwick_tree::FenwickTree;
+use rust::monoid::Sum;
+use rust::range::PointUpdate;
+use rust::rng::{Rng, SplitMix64};
+use rust::segment_tree::{IterativeSegmentTree, SegmentTree};
+
+const N: usize = 1 << 17;
+
+/// Deterministic mix of point updates and range queries: (index, value, l, r).
+fn workload() -> Vec<(usize, i64, usize, usize)> {
+    let mut rng = SplitMix64::new(12345);
+    let mut next = || rng.next_u64() as usize;
+    (0..10_000)
+        .map(|_| {
+            let (a, b) = (next() % N, next() % N);
+            (next() % N, (next() % 1000) as i64, a.min(b), a.max(b) + 1)
+        })
+        .collect()
+}
+
+/// The same loop for every backend, written against the `PointUpdate` / `RangeQuery` traits.
+fn replay<Q: PointUpdate<Item = i64, Output = i64>>(q: &mut Q, ops: &[(usize, i64, usize, usize)]) -> i64 {
+    let mut acc = 0i64;
+    for &(i, v, l, r) in ops {
+        q.set(i, v);
+        acc = acc.wrapping_add(q.query_range(l..r));
+    }
+    acc
+}
+
+fn bench_point_update_range_sum(c: &mut Criterion) {
+    let values: Vec<i64> = (0..N as i64).map(|i| i % 97).collect();
+    let ops = workload();
+    let mut group = c.benchmark_group("point_update_range_sum");
+    group.sample_size(20);
+    let mut recursive = SegmentTree::from_slice(&values, Sum);
+    group.bench_function("segment_tree_recursive", |b| b.iter(|| black_box(replay(&mut recursive, &ops))));
+    let mut iterative = IterativeSegmentTree::from_slice(&values, Sum);
+    group.bench_function("segment_tree_iterative", |b| b.iter(|| black_box(replay(&mut iterative, &ops))));
+    // Fenwick trees only add, so `set` costs an extra point query for the old value
+    let mut fenwick = FenwickTree::from_vec(&values);
+    group.bench_function("fenwick", |b| b.iter(|| black_box(replay(&mut fenwick, &ops))));
+    group.finish();
+}
+
+criterion_group!(benches, bench_point_update_range_sum);
+criterion_main!(benches);
@@ -0,0 +1,38 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rust::boyer_moore::{BoyerMoore, Horspool};
+use rust::kmp;
+
+/// Roughly 4 MB of synthetic log lines with the needle planted near the end.
+fn log_text() -> String {
+    let levels = ["INFO", "DEBUG", "WARN", "TRACE"];
+    let mut text = String::new();
+    for i in 0..60_000 {
+        text.push_str(&format!(
+            "2024-01-01T00:00:{:02} {} worker-{} processed request id={} in {}ms\n",
+            i % 60,
+            levels[i % levels.len()],
+            i % 16,
+            i * 7919 % 100_003,
+            i % 250
+        ));
+    }
+    text.push_str("2024-01-01T00:00:00 ERROR worker-3 connection reset by peer while streaming\n");
+    text
+}
+
+fn bench_search(c: &mut Criterion) {
+    let text = log_text();
+    for pattern in ["ERROR", "connection reset by peer while streaming"] {
+        let mut group = c.benchmark_group(format!("search_{}_bytes", pattern.len()));
+        group.sample_size(20);
+        group.bench_function("kmp", |b| b.iter(|| kmp::find_all(black_box(&text), pattern)));
+        let h = Horspool::new(pattern);
+        group.bench_function("horspool", |b| b.iter(|| h.find_all(black_box(&text))));
+        let bm = BoyerMoore::new(pattern);
+        group.bench_function("boyer_moore", |b| b.iter(|| bm.find_all(black_box(&text))));
+        group.finish();
+    }
+}
+
+criterion_group!(benches, bench_search);
+criterion_main!(benches);
@@ -0,0 +1,118 @@
+// rust/src/assignment.rs
+
+/// Hungarian algorithm (Kuhn-Munkres, in the shortest augmenting path form with row and column
+/// potentials) for the assignment problem: given `cost[i][j]` for `n` rows and `m >= n` columns,
+/// assign every row a distinct column minimising the total cost. Costs may be negative.
+/// Returns the total cost and `assignment[i]`, the column of row `i`. For more rows than columns,
+/// solve the transposed matrix. Panics if the matrix is ragged or has more rows than columns.
+///
+/// Complexity: O(n^2 * m)
+pub fn hungarian(cost: &[Vec<i64>]) -> (i64, Vec<usize>) {
+    let n = cost.len();
+    let m = cost.first().map_or(0, Vec::len);
+    assert!(cost.iter().all(|row| row.len() == m), "cost matrix rows must have equal length");
+    assert!(n <= m, "more rows ({}) than columns ({})", n, m);
+    // 1-based with column 0 as a virtual column holding the row being inserted
+    let mut u = vec![0i64; n + 1];
+    let mut v = vec![0i64; m + 1];
+    let mut owner = vec![0usize; m + 1];
+    let mut way = vec![0usize; m + 1];
+    for i in 1..=n {
+        owner[0] = i;
+        let mut j0 = 0;
+        let mut min_slack = vec![i64::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+        // grow the alternating tree until it reaches a free column
+        loop {
+            used[j0] = true;
+            let i0 = owner[j0];
+            let (mut delta, mut j1) = (i64::MAX, 0);
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let slack = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if slack < min_slack[j] {
+                    min_slack[j] = slack;
+                    way[j] = j0;
+                }
+                if min_slack[j] < delta {
+                    delta = min_slack[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[owner[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    min_slack[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if owner[j0] == 0 {
+                break;
+            }
+        }
+        // flip the augmenting path back to the virtual column
+        while j0 != 0 {
+            let j1 = way[j0];
+            owner[j0] = owner[j1];
+            j0 = j1;
+        }
+    }
+    let mut assignment = vec![0; n];
+    for j in 1..=m {
+        if owner[j] != 0 {
+            assignment[owner[j] - 1] = j - 1;
+        }
+    }
+    let total = assignment.iter().enumerate().map(|(i, &j)| cost[i][j]).sum();
+    (total, assignment)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::graph::MinCostFlow;
+
+    #[test]
+    fn test_small_matrices() {
+        let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        assert_eq!(hungarian(&cost), (5, vec![1, 0, 2]));
+        // rectangular: two workers, four jobs
+        let cost = vec![vec![9, 2, 7, 8], vec![6, 4, 3, 7]];
+        assert_eq!(hungarian(&cost), (5, vec![1, 2]));
+        assert_eq!(hungarian(&[]), (0, vec![]));
+        assert_eq!(hungarian(&[vec![-5]]), (-5, vec![0]));
+    }
+
+    #[test]
+    fn test_against_min_cost_flow() {
+        let mut next = test_rng(13);
+        for _ in 0..30 {
+            let (n, m) = (1 + next() as usize % 7, 7);
+            let cost: Vec<Vec<i64>> = (0..n).map(|_| (0..m).map(|_| (next() % 201) as i64 - 100).collect()).collect();
+            let (total, assignment) = hungarian(&cost);
+            let mut cols = assignment.clone();
+            cols.sort_unstable();
+            cols.dedup();
+            assert_eq!(cols.len(), n);
+            assert_eq!(total, assignment.iter().enumerate().map(|(i, &j)| cost[i][j]).sum::<i64>());
+
+            let mut net = MinCostFlow::new(n + m + 2);
+            let (s, t) = (n + m, n + m + 1);
+            for (i, row) in cost.iter().enumerate() {
+                net.add_edge(s, i, 1, 0);
+                for (j, &c) in row.iter().enumerate() {
+                    net.add_edge(i, n + j, 1, c);
+                }
+            }
+            for j in 0..m {
+                net.add_edge(n + j, t, 1, 0);
+            }
+            assert_eq!(net.solve(s, t), (n as i64, total));
+        }
+    }
+}
@@ -0,0 +1,340 @@
+// rust/src/avl_tree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::cmp::Ordering;
+
+type Link<K> = Option<Box<Node<K>>>;
+
+struct Node<K> {
+    key: K,
+    height: usize,
+    size: usize,
+    left: Link<K>,
+    right: Link<K>,
+}
+
+fn height<K>(link: &Link<K>) -> usize {
+    link.as_ref().map_or(0, |n| n.height)
+}
+
+fn size<K>(link: &Link<K>) -> usize {
+    link.as_ref().map_or(0, |n| n.size)
+}
+
+impl<K> Node<K> {
+    fn recalc(&mut self) {
+        self.height = 1 + height(&self.left).max(height(&self.right));
+        self.size = 1 + size(&self.left) + size(&self.right);
+    }
+
+    /// Height of the right subtree minus that of the left.
+    fn balance_factor(&self) -> isize {
+        height(&self.right) as isize - height(&self.left) as isize
+    }
+}
+
+fn rotate_right<K>(mut n: Box<Node<K>>) -> Box<Node<K>> {
+    let mut l = n.left.take().expect("rotate_right needs a left child");
+    n.left = l.right.take();
+    n.recalc();
+    l.right = Some(n);
+    l.recalc();
+    l
+}
+
+fn rotate_left<K>(mut n: Box<Node<K>>) -> Box<Node<K>> {
+    let mut r = n.right.take().expect("rotate_left needs a right child");
+    n.right = r.left.take();
+    n.recalc();
+    r.left = Some(n);
+    r.recalc();
+    r
+}
+
+/// Restores the AVL condition at `n`, whose subtrees are valid AVL trees differing in height by
+/// at most 2, with one single or double rotation.
+fn rebalance<K>(mut n: Box<Node<K>>) -> Box<Node<K>> {
+    n.recalc();
+    match n.balance_factor() {
+        -2 => {
+            if n.left.as_ref().is_some_and(|l| l.balance_factor() > 0) {
+                n.left = n.left.take().map(rotate_left);
+            }
+            rotate_right(n)
+        }
+        2 => {
+            if n.right.as_ref().is_some_and(|r| r.balance_factor() < 0) {
+                n.right = n.right.take().map(rotate_right);
+            }
+            rotate_left(n)
+        }
+        _ => n,
+    }
+}
+
+fn insert<K: Ord>(link: Link<K>, key: K, inserted: &mut bool) -> Box<Node<K>> {
+    let Some(mut n) = link else {
+        *inserted = true;
+        return Box::new(Node { key, height: 1, size: 1, left: None, right: None });
+    };
+    match key.cmp(&n.key) {
+        Ordering::Less => n.left = Some(insert(n.left.take(), key, inserted)),
+        Ordering::Greater => n.right = Some(insert(n.right.take(), key, inserted)),
+        Ordering::Equal => return n,
+    }
+    rebalance(n)
+}
+
+/// Detaches the minimum of the subtree, returning it and the rebalanced rest.
+fn take_min<K>(mut n: Box<Node<K>>) -> (Box<Node<K>>, Link<K>) {
+    match n.left.take() {
+        None => {
+            let rest = n.right.take();
+            (n, rest)
+        }
+        Some(l) => {
+            let (min, rest) = take_min(l);
+            n.left = rest;
+            (min, Some(rebalance(n)))
+        }
+    }
+}
+
+fn remove<K: Ord>(link: Link<K>, key: &K, removed: &mut bool) -> Link<K> {
+    let mut n = link?;
+    match key.cmp(&n.key) {
+        Ordering::Less => n.left = remove(n.left.take(), key, removed),
+        Ordering::Greater => n.right = remove(n.right.take(), key, removed),
+        Ordering::Equal => {
+            *removed = true;
+            // replace the node by its in-order successor, or by its only child
+            let Some(right) = n.right.take() else { return n.left.take() };
+            let (mut successor, rest) = take_min(right);
+            successor.left = n.left.take();
+            successor.right = rest;
+            return Some(rebalance(successor));
+        }
+    }
+    Some(rebalance(n))
+}
+
+/// Ordered set as an AVL tree (Adelson-Velsky and Landis): the subtree heights of every node
+/// differ by at most one, restored after each change by single or double rotations on the way
+/// back up, which bounds the height by about 1.44 log2 n. Nodes also store subtree sizes for
+/// order statistics. `validate` checks all of it, for experiments with the rebalancing rules.
+/// - insert / remove / contains / rank / get: O(log n) worst case
+pub struct AvlTree<K> {
+    root: Link<K>,
+}
+
+impl<K> Default for AvlTree<K> {
+    fn default() -> Self {
+        AvlTree { root: None }
+    }
+}
+
+impl<K: Ord> AvlTree<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        size(&self.root)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Height of the tree: 0 when empty, 1 for a single node.
+    pub fn height(&self) -> usize {
+        height(&self.root)
+    }
+
+    /// Inserts `key`; returns false (keeping the stored key) if it was already present.
+    pub fn insert(&mut self, key: K) -> bool {
+        let mut inserted = false;
+        self.root = Some(insert(self.root.take(), key, &mut inserted));
+        inserted
+    }
+
+    /// Removes `key`; returns whether it was present.
+    pub fn remove(&mut self, key: &K) -> bool {
+        let mut removed = false;
+        self.root = remove(self.root.take(), key, &mut removed);
+        removed
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        let mut cur = &self.root;
+        while let Some(n) = cur {
+            cur = match key.cmp(&n.key) {
+                Ordering::Less => &n.left,
+                Ordering::Greater => &n.right,
+                Ordering::Equal => return true,
+            };
+        }
+        false
+    }
+
+    /// Number of keys smaller than `key`.
+    pub fn rank(&self, key: &K) -> usize {
+        let (mut cur, mut rank) = (&self.root, 0);
+        while let Some(n) = cur {
+            if n.key < *key {
+                rank += size(&n.left) + 1;
+                cur = &n.right;
+            } else {
+                cur = &n.left;
+            }
+        }
+        rank
+    }
+
+    /// The `index`-th smallest key (0-based).
+    pub fn get(&self, mut index: usize) -> Option<&K> {
+        let mut cur = &self.root;
+        while let Some(n) = cur {
+            let left = size(&n.left);
+            match index.cmp(&left) {
+                Ordering::Less => cur = &n.left,
+                Ordering::Equal => return Some(&n.key),
+                Ordering::Greater => {
+                    index -= left + 1;
+                    cur = &n.right;
+                }
+            }
+        }
+        None
+    }
+
+    pub fn first(&self) -> Option<&K> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&K> {
+        self.len().checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &K> + '_ {
+        let mut stack = Vec::new();
+        let mut cur = self.root.as_deref();
+        std::iter::from_fn(move || {
+            while let Some(n) = cur {
+                stack.push(n);
+                cur = n.left.as_deref();
+            }
+            let n = stack.pop()?;
+            cur = n.right.as_deref();
+            Some(&n.key)
+        })
+    }
+
+    /// Checks the search order, the stored heights and sizes, and that every balance factor is
+    /// -1, 0 or 1. Returns a description of the first violation found.
+    pub fn validate(&self) -> Result<(), String> {
+        fn check<K: Ord>(link: &Link<K>, lo: Option<&K>, hi: Option<&K>, path: &mut String) -> Result<(), String> {
+            let Some(n) = link else { return Ok(()) };
+            if lo.is_some_and(|lo| n.key <= *lo) || hi.is_some_and(|hi| n.key >= *hi) {
+                return Err(format!("node at root{} breaks the search order", path));
+            }
+            for (child, step, lo, hi) in [(&n.left, 'L', lo, Some(&n.key)), (&n.right, 'R', Some(&n.key), hi)] {
+                path.push(step);
+                check(child, lo, hi, path)?;
+                path.pop();
+            }
+            if n.height != 1 + height(&n.left).max(height(&n.right)) || n.size != 1 + size(&n.left) + size(&n.right) {
+                return Err(format!("node at root{} has a stale height or size", path));
+            }
+            match n.balance_factor() {
+                -1..=1 => Ok(()),
+                b => Err(format!("node at root{} has balance factor {}", path, b)),
+            }
+        }
+        check(&self.root, None, None, &mut String::new())
+    }
+}
+
+impl<K: Ord> FromIterator<K> for AvlTree<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut tree = AvlTree::new();
+        iter.into_iter().for_each(|k| {
+            tree.insert(k);
+        });
+        tree
+    }
+}
+
+impl<K> DataStructure for AvlTree<K> {
+    const INFO: Info = Info {
+        name: "AvlTree",
+        category: "ordered sets",
+        summary: "Height-balanced BST (balance factors in -1..=1) with order statistics and a validator",
+        space: "O(n)",
+        operations: &[
+            Operation::new("insert", "O(log n)", "O(log n)"),
+            Operation::new("remove", "O(log n)", "O(log n)"),
+            Operation::new("contains", "O(log n)", "O(1)"),
+            Operation::new("rank", "O(log n)", "O(1)"),
+            Operation::new("get", "O(log n)", "O(1)"),
+            Operation::new("validate", "O(n)", "O(log n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_rotations_keep_balance() {
+        // ascending inserts would make a path in a plain BST
+        let mut tree: AvlTree<u32> = (0..1023).collect();
+        assert_eq!(tree.height(), 10);
+        assert_eq!(tree.validate(), Ok(()));
+        assert_eq!((tree.rank(&500), tree.get(500), tree.get(1023)), (500, Some(&500), None));
+        assert!(!tree.insert(7));
+        (0..1023).filter(|k| k % 3 != 0).for_each(|k| assert!(tree.remove(&k)));
+        assert!(!tree.remove(&1));
+        assert_eq!(tree.validate(), Ok(()));
+        assert_eq!((tree.len(), tree.first(), tree.last()), (341, Some(&0), Some(&1020)));
+        assert!(tree.contains(&999) && !tree.contains(&1000));
+
+        // a hand-built lopsided tree is caught
+        let leaf = |key| Some(Box::new(Node { key, height: 1, size: 1, left: None, right: None }));
+        let mut mid = Box::new(Node { key: 2, height: 0, size: 0, left: None, right: leaf(3) });
+        mid.recalc();
+        let mut root = Box::new(Node { key: 1, height: 0, size: 0, left: None, right: Some(mid) });
+        root.recalc();
+        let bad = AvlTree { root: Some(root) };
+        assert_eq!(bad.validate(), Err("node at root has balance factor 2".to_string()));
+    }
+
+    #[test]
+    fn test_against_btreeset() {
+        let mut next = test_rng(29);
+        let mut tree = AvlTree::new();
+        let mut reference = BTreeSet::new();
+        for step in 0..6000 {
+            let key = next() % 500;
+            match next() % 3 {
+                0 => assert_eq!(tree.insert(key), reference.insert(key)),
+                1 => assert_eq!(tree.remove(&key), reference.remove(&key)),
+                _ => {
+                    assert_eq!(tree.contains(&key), reference.contains(&key));
+                    assert_eq!(tree.rank(&key), reference.range(..key).count());
+                }
+            }
+            if step % 500 == 0 {
+                assert_eq!(tree.validate(), Ok(()));
+            }
+        }
+        assert!(tree.iter().eq(reference.iter()));
+        // at most 1.44 log2(n + 2)
+        assert!((tree.height() as f64) < 1.45 * ((tree.len() + 2) as f64).log2());
+    }
+}
@@ -0,0 +1,162 @@
+// rust/src/bitap.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::collections::HashMap;
+
+/// Bitap (shift-or) matcher for patterns of up to 64 chars.
+/// The state is one machine word per allowed mismatch count: bit `j` of `state[d]` is 0 when the
+/// last `j + 1` chars read match the first `j + 1` chars of the pattern with at most `d` mismatches.
+/// Each text char updates every word with a shift, an OR and an AND.
+/// - Build: O(m)
+/// - Search with up to k mismatches (Hamming distance): O(n * (k + 1))
+///
+/// Matching is done on chars, so a multi-byte char counts as one mismatch, but match positions are
+/// byte offsets like `kmp::find_all`.
+pub struct Bitap {
+    // bit j is 0 where pattern[j] == c; chars missing from the map mismatch everywhere
+    masks: HashMap<char, u64>,
+    len: usize,
+    ignore_ascii_case: bool,
+}
+
+impl Bitap {
+    /// Returns None if `pattern` is empty or longer than 64 chars.
+    pub fn new(pattern: &str) -> Option<Self> {
+        Self::build(pattern, false)
+    }
+
+    /// Like `new`, but ASCII letters match regardless of case (the text is folded char by char as it
+    /// is read, never copied).
+    pub fn new_ignore_ascii_case(pattern: &str) -> Option<Self> {
+        Self::build(pattern, true)
+    }
+
+    fn build(pattern: &str, ignore_ascii_case: bool) -> Option<Self> {
+        let len = pattern.chars().count();
+        if len == 0 || len > 64 {
+            return None;
+        }
+        let mut masks = HashMap::new();
+        for (j, c) in pattern.chars().enumerate() {
+            let c = if ignore_ascii_case { c.to_ascii_lowercase() } else { c };
+            *masks.entry(c).or_insert(u64::MAX) &= !(1u64 << j);
+        }
+        Some(Bitap { masks, len, ignore_ascii_case })
+    }
+
+    /// Pattern length in chars.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Start offsets of exact (possibly overlapping) matches.
+    pub fn find_exact(&self, text: &str) -> Vec<usize> {
+        self.find_with_mismatches(text, 0).into_iter().map(|(pos, _)| pos).collect()
+    }
+
+    /// All windows of `text` within Hamming distance `k` of the pattern, as
+    /// `(start byte offset, number of mismatches)`.
+    pub fn find_with_mismatches(&self, text: &str, k: usize) -> Vec<(usize, usize)> {
+        let m = self.len;
+        let done = 1u64 << (m - 1);
+        let mut state = vec![u64::MAX; k.min(m) + 1];
+        // byte offsets of the last m chars, to report where a window starts
+        let mut starts = vec![0usize; m];
+        let mut res = vec![];
+        for (i, (offset, c)) in text.char_indices().enumerate() {
+            starts[i % m] = offset;
+            let c = if self.ignore_ascii_case { c.to_ascii_lowercase() } else { c };
+            let mask = self.masks.get(&c).copied().unwrap_or(u64::MAX);
+            // the previous level's old state, shifted; level 0 cannot spend a mismatch
+            let mut spend = u64::MAX;
+            for s in state.iter_mut() {
+                let old = *s;
+                // extend with a matching char, or spend one more mismatch on this char
+                *s = ((old << 1) | mask) & spend;
+                spend = old << 1;
+            }
+            if i + 1 >= m
+                && let Some(d) = state.iter().position(|s| s & done == 0)
+            {
+                res.push((starts[(i + 1) % m], d));
+            }
+        }
+        res
+    }
+}
+
+impl DataStructure for Bitap {
+    const INFO: Info = Info {
+        name: "Bitap",
+        category: "strings",
+        summary: "Shift-or matcher for patterns of up to 64 chars, exact or with k mismatches",
+        space: "O(σ)",
+        operations: &[
+            Operation::new("new", "O(m)", "O(σ)"),
+            Operation::new("find_exact", "O(n)", "O(1)"),
+            Operation::new("find_with_mismatches", "O(n * (k + 1))", "O(k + m)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::kmp;
+
+    fn hamming_windows(text: &str, pattern: &str, k: usize) -> Vec<(usize, usize)> {
+        let chars: Vec<(usize, char)> = text.char_indices().collect();
+        let p: Vec<char> = pattern.chars().collect();
+        chars
+            .windows(p.len())
+            .filter_map(|w| {
+                let d = w.iter().zip(&p).filter(|((_, a), b)| a != *b).count();
+                (d <= k).then_some((w[0].0, d))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_exact_matches_kmp() {
+        let b = Bitap::new("abab").unwrap();
+        assert_eq!(b.find_exact("ababcabababc"), kmp::find_all("ababcabababc", "abab"));
+        assert!(Bitap::new("").is_none());
+        assert!(Bitap::new(&"x".repeat(65)).is_none());
+        assert!(Bitap::new(&"x".repeat(64)).is_some());
+        assert!(b.find_exact("aba").is_empty());
+    }
+
+    #[test]
+    fn test_mismatches() {
+        let b = Bitap::new("hello").unwrap();
+        assert_eq!(b.find_with_mismatches("say hallo to jello", 1), vec![(4, 1), (13, 1)]);
+        assert_eq!(b.find_with_mismatches("say hallo to jello", 0), vec![]);
+        // a multi-byte char counts as one mismatch and offsets stay byte offsets
+        let b = Bitap::new("cafe").unwrap();
+        assert_eq!(b.find_with_mismatches("un café", 1), vec![(3, 1)]);
+
+        let mut next = test_rng(5);
+        let text: String = (0..500).map(|_| (b'a' + (next() % 4) as u8) as char).collect();
+        for pattern in ["abc", "aabbccdd", "dadadada"] {
+            let b = Bitap::new(pattern).unwrap();
+            for k in 0..4 {
+                assert_eq!(b.find_with_mismatches(&text, k), hamming_windows(&text, pattern, k), "{} k={}", pattern, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ignore_ascii_case() {
+        let b = Bitap::new_ignore_ascii_case("Hello").unwrap();
+        assert_eq!(b.find_with_mismatches("say HALLO to jello", 1), vec![(4, 1), (13, 1)]);
+        assert_eq!(b.find_exact("hElLo HELLO"), kmp::find_all_ignore_ascii_case("hElLo HELLO", "hello"));
+        assert!(Bitap::new("Hello").unwrap().find_exact("hello").is_empty());
+    }
+}
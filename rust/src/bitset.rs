@@ -0,0 +1,217 @@
+// rust/src/bitset.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+/// A fixed-size set of small integers packed 64 per word.
+/// Whole-set operations (union, intersection, counting) process 64 elements per instruction,
+/// which is what makes the O(n^2 / 64) dense graph algorithms possible.
+///
+/// - insert / remove / contains: O(1)
+/// - union_with / intersect_with / difference_with / count_ones: O(n / 64)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitSet {
+    words: Vec<u64>,
+    universe: usize,
+}
+
+impl BitSet {
+    /// Creates an empty set over the universe `0..universe`.
+    pub fn new(universe: usize) -> Self {
+        BitSet {
+            words: vec![0; universe.div_ceil(64)],
+            universe,
+        }
+    }
+
+    /// The exclusive upper bound on elements; `count_ones` is the number of elements.
+    pub fn universe(&self) -> usize {
+        self.universe
+    }
+
+    /// True when no element is set.
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Adds `i`. Returns true if it was not present. Panics if `i >= universe()`.
+    pub fn insert(&mut self, i: usize) -> bool {
+        assert!(i < self.universe, "bit index out of bounds");
+        let (w, b) = (i / 64, 1u64 << (i % 64));
+        let fresh = self.words[w] & b == 0;
+        self.words[w] |= b;
+        fresh
+    }
+
+    /// Removes `i`. Returns true if it was present.
+    pub fn remove(&mut self, i: usize) -> bool {
+        if i >= self.universe {
+            return false;
+        }
+        let (w, b) = (i / 64, 1u64 << (i % 64));
+        let present = self.words[w] & b != 0;
+        self.words[w] &= !b;
+        present
+    }
+
+    pub fn contains(&self, i: usize) -> bool {
+        i < self.universe && self.words[i / 64] & (1u64 << (i % 64)) != 0
+    }
+
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+
+    /// Inserts every element of the universe.
+    pub fn fill(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = u64::MAX);
+        let extra = self.words.len() * 64 - self.universe;
+        if extra > 0
+            && let Some(last) = self.words.last_mut()
+        {
+            *last >>= extra;
+        }
+    }
+
+    /// Number of elements in the set.
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    fn assert_same_universe(&self, other: &BitSet) {
+        assert_eq!(self.universe, other.universe, "bit sets over different universes");
+    }
+
+    /// `self |= other`. Returns true if `self` changed. The binary operations panic if the two
+    /// sets have different universes.
+    pub fn union_with(&mut self, other: &BitSet) -> bool {
+        self.assert_same_universe(other);
+        let mut changed = false;
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            let next = *a | *b;
+            changed |= next != *a;
+            *a = next;
+        }
+        changed
+    }
+
+    /// `self &= other`.
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        self.assert_same_universe(other);
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= *b;
+        }
+    }
+
+    /// `self &= !other`.
+    pub fn difference_with(&mut self, other: &BitSet) {
+        self.assert_same_universe(other);
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= !*b;
+        }
+    }
+
+    /// Size of `self ∩ other` without allocating.
+    pub fn intersection_count(&self, other: &BitSet) -> usize {
+        self.assert_same_universe(other);
+        self.words.iter().zip(&other.words).map(|(a, b)| (a & b).count_ones() as usize).sum()
+    }
+
+    /// Raw 64-bit words, least significant bit first.
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    /// Elements in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(wi, &word)| {
+            let mut w = word;
+            std::iter::from_fn(move || {
+                if w == 0 {
+                    return None;
+                }
+                let bit = w.trailing_zeros() as usize;
+                w &= w - 1;
+                Some(wi * 64 + bit)
+            })
+        })
+    }
+}
+
+impl DataStructure for BitSet {
+    const INFO: Info = Info {
+        name: "BitSet",
+        category: "sets",
+        summary: "Fixed-size set of small integers packed 64 per word",
+        space: "O(n / 64)",
+        operations: &[
+            Operation::new("insert", "O(1)", "O(1)"),
+            Operation::new("contains", "O(1)", "O(1)"),
+            Operation::new("union_with", "O(n / 64)", "O(1)"),
+            Operation::new("intersect_with", "O(n / 64)", "O(1)"),
+            Operation::new("count_ones", "O(n / 64)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove_contains() {
+        let mut s = BitSet::new(130);
+        assert!(s.is_empty());
+        assert!(s.insert(0));
+        assert!(s.insert(64));
+        assert!(s.insert(129));
+        assert!(!s.insert(64));
+        assert!(s.contains(129));
+        assert!(!s.contains(128));
+        assert!(!s.contains(1000));
+        assert_eq!(s.count_ones(), 3);
+        assert!(s.remove(64));
+        assert!(!s.remove(64));
+        assert_eq!(s.iter().collect::<Vec<_>>(), vec![0, 129]);
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let mut a = BitSet::new(100);
+        let mut b = BitSet::new(100);
+        for i in (0..100).step_by(2) {
+            a.insert(i);
+        }
+        for i in (0..100).step_by(3) {
+            b.insert(i);
+        }
+        assert_eq!(a.intersection_count(&b), 17);
+        let mut u = a.clone();
+        assert!(u.union_with(&b));
+        assert!(!u.union_with(&b));
+        assert_eq!(u.count_ones(), 50 + 34 - 17);
+        let mut d = a.clone();
+        d.difference_with(&b);
+        assert_eq!(d.count_ones(), 50 - 17);
+        a.intersect_with(&b);
+        assert_eq!(a.iter().take(3).collect::<Vec<_>>(), vec![0, 6, 12]);
+    }
+
+    #[test]
+    #[should_panic(expected = "different universes")]
+    fn test_mismatched_universes() {
+        // same word count, so zipping the words alone would not notice
+        let mut a = BitSet::new(70);
+        a.union_with(&BitSet::new(100));
+    }
+
+    #[test]
+    fn test_fill_respects_universe() {
+        let mut s = BitSet::new(70);
+        s.fill();
+        assert_eq!((s.count_ones(), s.universe()), (70, 70));
+        assert_eq!(s.iter().last(), Some(69));
+        s.clear();
+        assert!(s.is_empty());
+    }
+}
@@ -0,0 +1,219 @@
+// rust/src/bloom_filter.rs
+use crate::DataStructure;
+use crate::bitset::BitSet;
+use crate::hash::hash_one;
+use crate::info::{Info, Operation};
+use crate::snapshot::{Decoder, Encoder, Snapshot};
+use std::f64::consts::LN_2;
+use std::hash::Hash;
+
+/// Seed of the second hash function, relative to the filter's seed.
+const SECOND_SEED: u64 = 0x9E3779B97F4A7C15;
+
+/// Bloom filter: a set that answers "definitely not present" or "probably present" in a fixed
+/// number of bits. An item sets `k` bits chosen by double hashing (`h1 + i * h2`, Kirsch and
+/// Mitzenmacher), and a lookup reports present when all `k` are set, so there are no false
+/// negatives, and false positives come from other items covering all of an absent item's bits.
+/// `new` sizes the filter for an expected count and false-positive rate (m = -n ln p / ln² 2 bits,
+/// k = m / n ln 2). Filters with the same size, hash count and seed combine with bitwise union
+/// (the filter of both sets) and intersection (an over-approximation of the common items).
+/// Items are hashed with `crate::hash`, so a saved filter stays valid for later processes.
+/// - insert / maybe_contains: O(k)
+/// - union_with / intersect_with: O(m / 64)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    bits: BitSet,
+    hashes: u32,
+    seed: u64,
+}
+
+impl BloomFilter {
+    /// A filter for about `expected_items` items with false-positive rate `fp_rate` once full.
+    /// Panics unless `0 < fp_rate < 1`.
+    pub fn new(expected_items: usize, fp_rate: f64) -> Self {
+        assert!(fp_rate > 0.0 && fp_rate < 1.0, "false-positive rate must be in (0, 1), got {}", fp_rate);
+        let n = expected_items.max(1) as f64;
+        let bits = (-n * fp_rate.ln() / (LN_2 * LN_2)).ceil();
+        let hashes = (bits / n * LN_2).round().max(1.0);
+        Self::with_params(bits as usize, hashes as u32, 0)
+    }
+
+    /// A filter of exactly `bits` bits and `hashes` hash functions. Panics if either is 0.
+    pub fn with_params(bits: usize, hashes: u32, seed: u64) -> Self {
+        assert!(bits > 0 && hashes > 0, "a Bloom filter needs at least one bit and one hash");
+        BloomFilter { bits: BitSet::new(bits), hashes, seed }
+    }
+
+    pub fn num_bits(&self) -> usize {
+        self.bits.universe()
+    }
+
+    pub fn num_hashes(&self) -> u32 {
+        self.hashes
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Whether nothing has been inserted.
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    fn positions<T: Hash + ?Sized>(&self, item: &T) -> impl Iterator<Item = usize> + use<T> {
+        let m = self.bits.universe() as u64;
+        let h1 = hash_one(item, self.seed);
+        // odd, so the probe sequence does not collapse when m is a power of two
+        let h2 = hash_one(item, self.seed ^ SECOND_SEED) | 1;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % m) as usize)
+    }
+
+    /// Adds `item`. Returns false if it was (probably) present already, i.e. no bit changed.
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) -> bool {
+        let mut fresh = false;
+        for p in self.positions(item) {
+            fresh |= self.bits.insert(p);
+        }
+        fresh
+    }
+
+    /// False if `item` was certainly never inserted; true if it probably was.
+    pub fn maybe_contains<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        self.positions(item).all(|p| self.bits.contains(p))
+    }
+
+    /// Whether `other` has the same size, hash count and seed, so the two can be combined.
+    pub fn is_compatible(&self, other: &BloomFilter) -> bool {
+        (self.bits.universe(), self.hashes, self.seed) == (other.bits.universe(), other.hashes, other.seed)
+    }
+
+    /// Turns `self` into the filter of both item sets. Panics if the filters are not compatible.
+    pub fn union_with(&mut self, other: &BloomFilter) {
+        assert!(self.is_compatible(other), "Bloom filters differ in size, hash count or seed");
+        self.bits.union_with(&other.bits);
+    }
+
+    /// Keeps only the bits set in both filters: every common item still tests present, at a
+    /// somewhat higher false-positive rate than a filter built from the common items. Panics if
+    /// the filters are not compatible.
+    pub fn intersect_with(&mut self, other: &BloomFilter) {
+        assert!(self.is_compatible(other), "Bloom filters differ in size, hash count or seed");
+        self.bits.intersect_with(&other.bits);
+    }
+
+    /// Fraction of bits set.
+    pub fn fill_ratio(&self) -> f64 {
+        self.bits.count_ones() as f64 / self.bits.universe() as f64
+    }
+
+    /// Probability that an absent item tests present, given the bits currently set.
+    pub fn estimated_fp_rate(&self) -> f64 {
+        self.fill_ratio().powi(self.hashes as i32)
+    }
+
+    /// Number of distinct items inserted, estimated from the fill ratio (Swamidass and Baldi).
+    pub fn estimated_len(&self) -> f64 {
+        let (m, k) = (self.bits.universe() as f64, self.hashes as f64);
+        -m / k * (1.0 - self.fill_ratio()).ln()
+    }
+}
+
+impl Snapshot for BloomFilter {
+    const KIND: [u8; 4] = *b"BLOM";
+
+    fn encode(&self, out: &mut Encoder) {
+        out.usize(self.bits.universe());
+        out.scalar(self.hashes);
+        out.scalar(self.seed);
+        out.slice(self.bits.words());
+    }
+
+    fn decode(input: &mut Decoder<'_>) -> Option<Self> {
+        let (len, hashes, seed) = (input.usize()?, input.u32()?, input.scalar::<u64>()?);
+        let words: Vec<u64> = input.vec()?;
+        if len == 0 || hashes == 0 || words.len() != len.div_ceil(64) {
+            return None;
+        }
+        let mut bits = BitSet::new(len);
+        for (w, &word) in words.iter().enumerate() {
+            for b in 0..64 {
+                if word >> b & 1 == 1 {
+                    // a bit past the end means the data is corrupt
+                    if 64 * w + b >= len {
+                        return None;
+                    }
+                    bits.insert(64 * w + b);
+                }
+            }
+        }
+        Some(BloomFilter { bits, hashes, seed })
+    }
+}
+
+impl DataStructure for BloomFilter {
+    const INFO: Info = Info {
+        name: "BloomFilter",
+        category: "probabilistic",
+        summary: "Approximate set membership with no false negatives and a tunable false-positive rate (k = hash functions, m = bits)",
+        space: "m bits, about 1.44 n log2(1 / p)",
+        operations: &[
+            Operation::new("insert", "O(k)", "O(1)"),
+            Operation::new("maybe_contains", "O(k)", "O(1)"),
+            Operation::new("union_with / intersect_with", "O(m / 64)", "O(1)"),
+            Operation::new("estimated_len", "O(m / 64)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_membership_union_and_snapshot() {
+        let mut a = BloomFilter::new(100, 0.01);
+        assert_eq!((a.num_bits(), a.num_hashes()), (959, 7));
+        assert!(a.insert("apple") && !a.insert("apple"));
+        a.insert(&42u64);
+        assert!(a.maybe_contains("apple") && a.maybe_contains(&42u64) && !a.maybe_contains("pear"));
+
+        let mut b = BloomFilter::new(100, 0.01);
+        b.insert("pear");
+        b.insert("apple");
+        let mut both = a.clone();
+        both.union_with(&b);
+        assert!(["apple", "pear"].iter().all(|x| both.maybe_contains(x)) && both.maybe_contains(&42u64));
+        a.intersect_with(&b);
+        assert!(a.maybe_contains("apple") && !a.maybe_contains("pear"));
+        assert!(!both.is_compatible(&BloomFilter::with_params(959, 7, 1)));
+
+        let back = BloomFilter::from_snapshot(&both.to_snapshot()).expect("round trip");
+        assert_eq!(back, both);
+        let mut corrupt = both.to_snapshot();
+        let last = corrupt.len() - 1;
+        corrupt[last] = 0xff; // sets bits past the 959th
+        assert!(BloomFilter::from_snapshot(&corrupt).is_err());
+    }
+
+    #[test]
+    fn test_false_positive_rate() {
+        let (n, p) = (5000, 0.02);
+        let mut filter = BloomFilter::new(n, p);
+        (0..n as u64).for_each(|i| {
+            filter.insert(&i);
+        });
+        assert!((0..n as u64).all(|i| filter.maybe_contains(&i)));
+        let false_positives = (n as u64..n as u64 + 20_000).filter(|i| filter.maybe_contains(i)).count();
+        let rate = false_positives as f64 / 20_000.0;
+        assert!(rate < 1.5 * p, "false-positive rate {}", rate);
+        assert!((filter.estimated_fp_rate() - p).abs() < 0.01);
+        assert!((filter.estimated_len() - n as f64).abs() < 0.05 * n as f64);
+    }
+}
@@ -0,0 +1,279 @@
+// rust/src/boyer_moore.rs
+use crate::DataStructure;
+use crate::byte_map::ByteMap;
+use crate::info::{Info, Operation};
+/// Boyer-Moore-Horspool searcher.
+/// Compares the pattern right-to-left and, on a mismatch, skips ahead based on the text byte under
+/// the pattern's last position. On large alphabets and long patterns most windows are rejected after
+/// one comparison and skip almost `m` bytes, giving sublinear average time.
+/// - Build: O(m + σ)
+/// - Search: O(n / m) average, O(n * m) worst case
+///
+/// Works on bytes, so match positions are byte offsets (same as `kmp::find_all`). `with_map` builds a
+/// searcher that compares normalized bytes (see `ByteMap`); skips are then computed on normalized bytes.
+pub struct Horspool {
+    pattern: Vec<u8>,
+    skip: [usize; 256],
+    map: ByteMap,
+}
+
+impl Horspool {
+    pub fn new(pattern: &str) -> Self {
+        Self::with_map(pattern.as_bytes(), ByteMap::identity())
+    }
+
+    pub fn with_map(pattern: &[u8], map: ByteMap) -> Self {
+        let pattern = map.apply(pattern);
+        let m = pattern.len();
+        let mut skip = [m.max(1); 256];
+        for (i, &c) in pattern.iter().enumerate().take(m.saturating_sub(1)) {
+            skip[c as usize] = m - 1 - i;
+        }
+        Horspool { pattern, skip, map }
+    }
+
+    /// Start offsets of all (possibly overlapping) matches. Empty patterns never match.
+    pub fn find_all(&self, text: &str) -> Vec<usize> {
+        self.find_all_bytes(text.as_bytes())
+    }
+
+    /// `find_all` over raw bytes, for input that is not valid UTF-8.
+    pub fn find_all_bytes(&self, text: &[u8]) -> Vec<usize> {
+        let mut res = vec![];
+        self.scan(text, |pos| {
+            res.push(pos);
+            true
+        });
+        res
+    }
+
+    pub fn find_first(&self, text: &str) -> Option<usize> {
+        let mut first = None;
+        self.scan(text.as_bytes(), |pos| {
+            first = Some(pos);
+            false
+        });
+        first
+    }
+
+    fn scan(&self, text: &[u8], mut on_match: impl FnMut(usize) -> bool) {
+        let (n, m) = (text.len(), self.pattern.len());
+        if m == 0 || m > n {
+            return;
+        }
+        let mut s = 0;
+        while s <= n - m {
+            let last = self.map.map(text[s + m - 1]);
+            let window = &text[s..s + m - 1];
+            if last == self.pattern[m - 1]
+                && window.iter().zip(&self.pattern).all(|(&c, &p)| self.map.map(c) == p)
+                && !on_match(s)
+            {
+                return;
+            }
+            s += self.skip[last as usize];
+        }
+    }
+}
+
+/// Full Boyer-Moore searcher combining the bad-character and (strong) good-suffix rules.
+/// The good-suffix rule bounds the number of comparisons to O(n + m) per match-free stretch and
+/// makes long repetitive patterns skip further than Horspool.
+/// - Build: O(m + σ)
+/// - Search: sublinear average, O(n + m) without matches
+///
+/// Like `Horspool`, `with_map` compares bytes after normalizing them with a `ByteMap`.
+pub struct BoyerMoore {
+    pattern: Vec<u8>,
+    last: [isize; 256],
+    good_suffix: Vec<usize>,
+    map: ByteMap,
+}
+
+impl BoyerMoore {
+    pub fn new(pattern: &str) -> Self {
+        Self::with_map(pattern.as_bytes(), ByteMap::identity())
+    }
+
+    pub fn with_map(pattern: &[u8], map: ByteMap) -> Self {
+        let p = map.apply(pattern);
+        let m = p.len();
+        let mut last = [-1isize; 256];
+        for (i, &c) in p.iter().enumerate() {
+            last[c as usize] = i as isize;
+        }
+
+        // shift[j]: how far to move when the mismatch happens just before position j
+        // (i.e. p[j..] matched); bpos[i] is the start of the widest border of p[i..]
+        let mut shift = vec![0usize; m + 1];
+        let mut bpos = vec![0usize; m + 1];
+        let (mut i, mut j) = (m, m + 1);
+        bpos[i] = j;
+        while i > 0 {
+            while j <= m && p[i - 1] != p[j - 1] {
+                if shift[j] == 0 {
+                    shift[j] = j - i;
+                }
+                j = bpos[j];
+            }
+            i -= 1;
+            j -= 1;
+            bpos[i] = j;
+        }
+        j = bpos[0];
+        for (i, s) in shift.iter_mut().enumerate() {
+            if *s == 0 {
+                *s = j;
+            }
+            if i == j {
+                j = bpos[j];
+            }
+        }
+        BoyerMoore {
+            pattern: p,
+            last,
+            good_suffix: shift,
+            map,
+        }
+    }
+
+    /// Start offsets of all (possibly overlapping) matches. Empty patterns never match.
+    pub fn find_all(&self, text: &str) -> Vec<usize> {
+        self.find_all_bytes(text.as_bytes())
+    }
+
+    /// `find_all` over raw bytes, for input that is not valid UTF-8.
+    pub fn find_all_bytes(&self, text: &[u8]) -> Vec<usize> {
+        let (n, m) = (text.len(), self.pattern.len());
+        let mut res = vec![];
+        if m == 0 || m > n {
+            return res;
+        }
+        let mut s = 0;
+        while s <= n - m {
+            let mut j = m;
+            while j > 0 && self.pattern[j - 1] == self.map.map(text[s + j - 1]) {
+                j -= 1;
+            }
+            if j == 0 {
+                res.push(s);
+                s += self.good_suffix[0];
+            } else {
+                let bad_char = j as isize - 1 - self.last[self.map.map(text[s + j - 1]) as usize];
+                s += (self.good_suffix[j] as isize).max(bad_char) as usize;
+            }
+        }
+        res
+    }
+}
+
+/// One-shot Horspool search, equivalent to `kmp::find_all`.
+pub fn horspool_find_all(text: &str, pattern: &str) -> Vec<usize> {
+    Horspool::new(pattern).find_all(text)
+}
+
+impl DataStructure for Horspool {
+    const INFO: Info = Info {
+        name: "Horspool",
+        category: "strings",
+        summary: "Boyer-Moore-Horspool search with the bad-character rule (m = pattern length)",
+        space: "O(m + σ)",
+        operations: &[
+            Operation::new("new", "O(m + σ)", "O(σ)"),
+            Operation::new("find_all", "O(n / m) average, O(n * m) worst", "O(k)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl DataStructure for BoyerMoore {
+    const INFO: Info = Info {
+        name: "BoyerMoore",
+        category: "strings",
+        summary: "Boyer-Moore search with bad-character and strong good-suffix rules (m = pattern length)",
+        space: "O(m + σ)",
+        operations: &[
+            Operation::new("new", "O(m + σ)", "O(m + σ)"),
+            Operation::new("find_all", "sublinear average, O(n + m) without matches", "O(k)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::kmp;
+
+    #[test]
+    fn test_horspool_basic() {
+        let h = Horspool::new("abab");
+        assert_eq!(h.find_all("ababcabababc"), vec![0, 5, 7]);
+        assert_eq!(h.find_first("xxababab"), Some(2));
+        assert!(Horspool::new("").find_all("abc").is_empty());
+        assert!(Horspool::new("abcd").find_all("abc").is_empty());
+        assert_eq!(horspool_find_all("aaaaa", "aa"), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_boyer_moore_basic() {
+        let bm = BoyerMoore::new("abab");
+        assert_eq!(bm.find_all("ababcabababc"), vec![0, 5, 7]);
+        assert_eq!(BoyerMoore::new("aa").find_all("aaaaa"), vec![0, 1, 2, 3]);
+        assert!(BoyerMoore::new("").find_all("abc").is_empty());
+        assert!(BoyerMoore::new("xyz").find_all("hello world").is_empty());
+    }
+
+    #[test]
+    fn test_agree_with_kmp() {
+        let mut next = test_rng(99);
+        let text: String = (0..2000).map(|_| (b'a' + (next() % 3) as u8) as char).collect();
+        for pattern in ["a", "ab", "abc", "aab", "abcab", "bbbb", "cabacab", "abababab"] {
+            let expected = kmp::find_all(&text, pattern);
+            assert_eq!(Horspool::new(pattern).find_all(&text), expected, "horspool {}", pattern);
+            assert_eq!(BoyerMoore::new(pattern).find_all(&text), expected, "boyer-moore {}", pattern);
+        }
+    }
+
+    #[test]
+    fn test_normalized_search() {
+        let log = "GET /a 200\nget /A 404\nPost /a_b 200\n";
+        let ci = ByteMap::ascii_case_insensitive();
+        let expected = kmp::find_all_ignore_ascii_case(log, "get /a");
+        assert_eq!(expected, vec![0, 11]);
+        assert_eq!(Horspool::with_map(b"get /a", ci.clone()).find_all(log), expected);
+        assert_eq!(BoyerMoore::with_map(b"GET /A", ci.clone()).find_all(log), expected);
+
+        let mut table = *ByteMap::identity().table();
+        table[b'_' as usize] = b'-';
+        let map = ci.then(&ByteMap::from_table(table));
+        let expected = kmp::find_all_with(log.as_bytes(), b"/A-B", &map);
+        assert_eq!(expected, vec![27]);
+        assert_eq!(Horspool::with_map(b"/A-B", map.clone()).find_all(log), expected);
+        assert_eq!(BoyerMoore::with_map(b"/A-B", map).find_all(log), expected);
+        // invalid UTF-8 is fine for the byte API
+        assert_eq!(BoyerMoore::with_map(b"ab", ci).find_all_bytes(b"\xffAB\xfeab"), vec![1, 4]);
+    }
+
+    #[test]
+    fn test_case_insensitive_agrees_with_lowercased_kmp() {
+        let mut next = test_rng(7);
+        let text: String = (0..2000)
+            .map(|_| {
+                let c = b'a' + (next() % 3) as u8;
+                (if next() % 2 == 1 { c.to_ascii_uppercase() } else { c }) as char
+            })
+            .collect();
+        let lower = text.to_ascii_lowercase();
+        for pattern in ["a", "aB", "ABC", "aab", "cAbAcAb", "abababab"] {
+            let expected = kmp::find_all(&lower, &pattern.to_ascii_lowercase());
+            let ci = ByteMap::ascii_case_insensitive();
+            assert_eq!(kmp::find_all_ignore_ascii_case(&text, pattern), expected, "kmp {}", pattern);
+            assert_eq!(Horspool::with_map(pattern.as_bytes(), ci.clone()).find_all(&text), expected);
+            assert_eq!(BoyerMoore::with_map(pattern.as_bytes(), ci).find_all(&text), expected);
+        }
+    }
+}
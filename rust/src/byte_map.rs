@@ -0,0 +1,80 @@
+// rust/src/byte_map.rs
+
+/// Byte normalization applied on the fly by the byte searchers (`kmp`, `KmpMatcher`, `Horspool`,
+/// `BoyerMoore`): a text byte `b` matches a pattern byte `p` when `map(b) == map(p)`. Only the
+/// pattern is ever normalized up front, so large inputs are searched in place instead of being copied
+/// and lowercased first.
+///
+/// Composing tables (`then`) gives e.g. case-insensitive matching that also treats `_` as `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ByteMap {
+    table: [u8; 256],
+}
+
+impl ByteMap {
+    /// Every byte maps to itself: plain exact matching.
+    pub fn identity() -> Self {
+        Self::from_fn(|b| b)
+    }
+
+    /// Folds `A-Z` to `a-z`. Non-ASCII bytes are left alone, so UTF-8 sequences keep their boundaries.
+    pub fn ascii_case_insensitive() -> Self {
+        Self::from_fn(|b| b.to_ascii_lowercase())
+    }
+
+    /// A user-supplied normalization table: byte `b` is replaced by `table[b]`.
+    pub fn from_table(table: [u8; 256]) -> Self {
+        ByteMap { table }
+    }
+
+    pub fn from_fn(f: impl Fn(u8) -> u8) -> Self {
+        let mut table = [0u8; 256];
+        for (b, slot) in table.iter_mut().enumerate() {
+            *slot = f(b as u8);
+        }
+        ByteMap { table }
+    }
+
+    /// Applies `self`, then `next`.
+    pub fn then(&self, next: &ByteMap) -> Self {
+        Self::from_fn(|b| next.map(self.map(b)))
+    }
+
+    #[inline]
+    pub fn map(&self, b: u8) -> u8 {
+        self.table[b as usize]
+    }
+
+    pub fn table(&self) -> &[u8; 256] {
+        &self.table
+    }
+
+    /// Normalized copy of `bytes` (used for patterns).
+    pub fn apply(&self, bytes: &[u8]) -> Vec<u8> {
+        bytes.iter().map(|&b| self.map(b)).collect()
+    }
+}
+
+impl Default for ByteMap {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_and_composition() {
+        assert!((0..=255u8).all(|b| ByteMap::identity().map(b) == b));
+        let ci = ByteMap::ascii_case_insensitive();
+        assert_eq!(ci.apply(b"GET /Index.HTML \xc3\x89"), b"get /index.html \xc3\x89".to_vec());
+        let mut table = *ByteMap::identity().table();
+        table[b'_' as usize] = b'-';
+        let both = ci.then(&ByteMap::from_table(table));
+        assert_eq!(both.apply(b"Content_Type"), b"content-type".to_vec());
+        assert_eq!(both.map(b'Q'), b'q');
+        assert_eq!(ByteMap::default(), ByteMap::identity());
+    }
+}
@@ -0,0 +1,370 @@
+// rust/src/cache.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::slab::Handle;
+use crate::slab_list::SlabList;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Common interface of the bounded caches, so one workload can be replayed against each eviction
+/// policy. `get` counts as a use of the entry; `put` on a full cache evicts one entry by the
+/// policy and returns it.
+pub trait Cache<K, V> {
+    fn capacity(&self) -> usize;
+    fn len(&self) -> usize;
+    fn get(&mut self, key: &K) -> Option<&V>;
+    fn put(&mut self, key: K, value: V) -> Option<(K, V)>;
+    fn remove(&mut self, key: &K) -> Option<V>;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Least-recently-used cache: a hash map from key to a handle in a `SlabList` kept in recency
+/// order, most recent at the front. A use moves the entry to the front; eviction pops the back.
+/// - get / put / remove: O(1) expected
+pub struct LruCache<K, V> {
+    map: HashMap<K, Handle>,
+    order: SlabList<(K, V)>,
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "a cache needs room for at least one entry");
+        LruCache { map: HashMap::with_capacity(capacity), order: SlabList::new(), capacity }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// The value for `key`, without counting as a use.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.order.get(*self.map.get(key)?).map(|(_, v)| v)
+    }
+
+    /// The value for `key`, marking it most recently used.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let h = *self.map.get(key)?;
+        self.order.move_to_front(h);
+        self.order.get(h).map(|(_, v)| v)
+    }
+
+    /// Inserts or updates `key` as the most recently used entry. Returns the least recently used
+    /// entry if it had to be evicted to make room.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if let Some(&h) = self.map.get(&key) {
+            self.order.move_to_front(h);
+            if let Some(entry) = self.order.get_mut(h) {
+                entry.1 = value;
+            }
+            return None;
+        }
+        let evicted = if self.map.len() == self.capacity { self.order.pop_back() } else { None };
+        if let Some((old, _)) = &evicted {
+            self.map.remove(old);
+        }
+        let h = self.order.push_front((key.clone(), value));
+        self.map.insert(key, h);
+        evicted
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let h = self.map.remove(key)?;
+        self.order.remove(h).map(|(_, v)| v)
+    }
+
+    /// Entries from most to least recently used.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.order.iter().map(|(_, (k, v))| (k, v))
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Cache<K, V> for LruCache<K, V> {
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        LruCache::get(self, key)
+    }
+
+    fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        LruCache::put(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        LruCache::remove(self, key)
+    }
+}
+
+struct LfuEntry<V> {
+    value: V,
+    frequency: u64,
+    // handle in the bucket of `frequency`
+    handle: Handle,
+}
+
+/// Least-frequently-used cache with O(1) operations (Shah, Mitra and Matani): entries are grouped
+/// in buckets by use count, each a `SlabList` in recency order, and the smallest nonempty count is
+/// tracked. A use moves an entry from its bucket to the front of the next one; eviction takes the
+/// least recently used entry of the smallest bucket, so ties between equally frequent entries go
+/// the LRU way. A new entry starts at count 1, which makes the smallest count 1 again.
+/// - get / put / remove: O(1) expected
+pub struct LfuCache<K, V> {
+    map: HashMap<K, LfuEntry<V>>,
+    buckets: HashMap<u64, SlabList<K>>,
+    min_frequency: u64,
+    capacity: usize,
+}
+
+impl<K: Hash + Eq + Clone, V> LfuCache<K, V> {
+    /// Panics if `capacity` is 0.
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "a cache needs room for at least one entry");
+        LfuCache { map: HashMap::with_capacity(capacity), buckets: HashMap::new(), min_frequency: 0, capacity }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    /// The value for `key`, without counting as a use.
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key).map(|e| &e.value)
+    }
+
+    /// How many times `key` has been used since it was inserted, counting the insertion.
+    pub fn frequency(&self, key: &K) -> Option<u64> {
+        self.map.get(key).map(|e| e.frequency)
+    }
+
+    /// Takes the entry at `handle` out of the bucket of `frequency`, dropping the bucket if it
+    /// empties.
+    fn detach(&mut self, frequency: u64, handle: Handle) -> Option<K> {
+        let bucket = self.buckets.get_mut(&frequency)?;
+        let key = bucket.remove(handle);
+        if bucket.is_empty() {
+            self.buckets.remove(&frequency);
+        }
+        key
+    }
+
+    /// Moves `key` to the next bucket up.
+    fn touch(&mut self, key: &K) {
+        let Some(entry) = self.map.get(key) else { return };
+        let (frequency, handle) = (entry.frequency, entry.handle);
+        let key = self.detach(frequency, handle).expect("every entry is in its bucket");
+        if self.min_frequency == frequency && !self.buckets.contains_key(&frequency) {
+            self.min_frequency += 1;
+        }
+        let handle = self.buckets.entry(frequency + 1).or_default().push_front(key.clone());
+        if let Some(entry) = self.map.get_mut(&key) {
+            entry.frequency += 1;
+            entry.handle = handle;
+        }
+    }
+
+    /// The value for `key`, counting one more use.
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.touch(key);
+        self.map.get(key).map(|e| &e.value)
+    }
+
+    /// Inserts `key`, or updates it and counts one more use. Returns the least frequently used
+    /// entry if it had to be evicted to make room.
+    pub fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        if self.map.contains_key(&key) {
+            self.touch(&key);
+            if let Some(entry) = self.map.get_mut(&key) {
+                entry.value = value;
+            }
+            return None;
+        }
+        let mut evicted = None;
+        if self.map.len() == self.capacity {
+            let bucket = self.buckets.get_mut(&self.min_frequency).expect("a full cache has a smallest bucket");
+            let old = bucket.pop_back().expect("buckets are never empty");
+            if bucket.is_empty() {
+                self.buckets.remove(&self.min_frequency);
+            }
+            let entry = self.map.remove(&old).expect("every bucket key is cached");
+            evicted = Some((old, entry.value));
+        }
+        let handle = self.buckets.entry(1).or_default().push_front(key.clone());
+        self.map.insert(key, LfuEntry { value, frequency: 1, handle });
+        self.min_frequency = 1;
+        evicted
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let entry = self.map.remove(key)?;
+        self.detach(entry.frequency, entry.handle);
+        // the smallest count is only needed for eviction, which happens when the cache is full,
+        // and by then an insertion has reset it to 1
+        Some(entry.value)
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Cache<K, V> for LfuCache<K, V> {
+    fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    fn get(&mut self, key: &K) -> Option<&V> {
+        LfuCache::get(self, key)
+    }
+
+    fn put(&mut self, key: K, value: V) -> Option<(K, V)> {
+        LfuCache::put(self, key, value)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        LfuCache::remove(self, key)
+    }
+}
+
+impl<K, V> DataStructure for LruCache<K, V> {
+    const INFO: Info = Info {
+        name: "LruCache",
+        category: "caches",
+        summary: "Bounded cache evicting the least recently used entry (hash map + slab-backed recency list)",
+        space: "O(capacity)",
+        operations: &[
+            Operation::new("get", "O(1) expected", "O(1)"),
+            Operation::new("put", "O(1) expected", "O(1)"),
+            Operation::new("remove", "O(1) expected", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl<K, V> DataStructure for LfuCache<K, V> {
+    const INFO: Info = Info {
+        name: "LfuCache",
+        category: "caches",
+        summary: "Bounded cache evicting the least frequently used entry, LRU among ties (frequency buckets)",
+        space: "O(capacity)",
+        operations: &[
+            Operation::new("get", "O(1) expected", "O(1)"),
+            Operation::new("put", "O(1) expected", "O(1)"),
+            Operation::new("remove", "O(1) expected", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_eviction_order() {
+        let mut lru = LruCache::new(2);
+        assert_eq!(lru.put("a", 1), None);
+        lru.put("b", 2);
+        assert_eq!(lru.get(&"a"), Some(&1));
+        assert_eq!(lru.put("c", 3), Some(("b", 2)));
+        assert_eq!(lru.peek(&"a"), Some(&1));
+        assert_eq!(lru.put("d", 4), Some(("a", 1)));
+        assert_eq!(lru.iter().map(|(k, _)| *k).collect::<Vec<_>>(), vec!["d", "c"]);
+        assert_eq!(lru.remove(&"c"), Some(3));
+        assert_eq!(lru.len(), 1);
+
+        let mut lfu = LfuCache::new(2);
+        lfu.put("a", 1);
+        lfu.put("b", 2);
+        lfu.get(&"a");
+        lfu.get(&"a");
+        lfu.put("b", 20);
+        assert_eq!((lfu.frequency(&"a"), lfu.frequency(&"b")), (Some(3), Some(2)));
+        assert_eq!(lfu.put("c", 3), Some(("b", 20)));
+        // "c" is the only entry used once
+        assert_eq!(lfu.put("d", 4), Some(("c", 3)));
+        lfu.get(&"d");
+        lfu.get(&"d");
+        // tie at 3 uses: "a" was used least recently
+        assert_eq!(lfu.put("e", 5), Some(("a", 1)));
+        assert_eq!(lfu.remove(&"d"), Some(4));
+        assert!(lfu.contains(&"e") && lfu.len() == 1 && lfu.get(&"x").is_none());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(109);
+        // (key, value, uses, last use)
+        let mut naive: Vec<(u64, u64, u64, u64)> = Vec::new();
+        let mut lfu = LfuCache::new(8);
+        for clock in 0..20_000u64 {
+            let key = next() % if next().is_multiple_of(4) { 40 } else { 10 };
+            match next() % 5 {
+                0 => {
+                    let expected = naive.iter().position(|e| e.0 == key).map(|i| naive.remove(i).1);
+                    assert_eq!(lfu.remove(&key), expected);
+                }
+                1 | 2 => {
+                    let expected = naive.iter_mut().find(|e| e.0 == key).map(|e| {
+                        e.2 += 1;
+                        e.3 = clock;
+                        e.1
+                    });
+                    assert_eq!(lfu.get(&key).copied(), expected);
+                }
+                _ => {
+                    let value = next();
+                    let mut expected = None;
+                    if let Some(e) = naive.iter_mut().find(|e| e.0 == key) {
+                        *e = (key, value, e.2 + 1, clock);
+                    } else {
+                        if naive.len() == 8 {
+                            let victim = (0..8).min_by_key(|&i| (naive[i].2, naive[i].3)).unwrap();
+                            let (k, v, _, _) = naive.remove(victim);
+                            expected = Some((k, v));
+                        }
+                        naive.push((key, value, 1, clock));
+                    }
+                    assert_eq!(lfu.put(key, value), expected);
+                }
+            }
+            assert_eq!(lfu.len(), naive.len());
+        }
+    }
+}
@@ -0,0 +1,182 @@
+// rust/src/circular_list.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::slab::{Handle, Slab};
+#[derive(Debug)]
+struct Slot<T> {
+    value: T,
+    prev: Handle,
+    next: Handle,
+}
+
+/// A circular doubly linked list stored in a `Slab`.
+/// Elements are addressed by handles returned on insertion, which stay valid until that element
+/// is removed, so removal and neighbour lookup are O(1) without searching. Freed slots are reused
+/// by later insertions; a removed element's handle is rejected rather than reaching the new one.
+///
+/// - push_back / insert_after / remove / next / prev: O(1)
+pub struct CircularList<T> {
+    slots: Slab<Slot<T>>,
+    head: Option<Handle>,
+}
+
+impl<T> Default for CircularList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> CircularList<T> {
+    pub fn new() -> Self {
+        CircularList {
+            slots: Slab::new(),
+            head: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    /// Handle of the first element, if any.
+    pub fn head(&self) -> Option<Handle> {
+        self.head
+    }
+
+    /// Appends `value` just before the head, i.e. at the end of the cycle. Returns its handle.
+    pub fn push_back(&mut self, value: T) -> Handle {
+        match self.head {
+            None => {
+                let h = self.slots.insert_with(|h| Slot { value, prev: h, next: h });
+                self.head = Some(h);
+                h
+            }
+            Some(head) => {
+                let tail = self.slots[head].prev;
+                self.insert_after(tail, value).expect("tail handle is live")
+            }
+        }
+    }
+
+    /// Inserts `value` right after the element `h`. Returns None if `h` is not a live handle.
+    pub fn insert_after(&mut self, h: Handle, value: T) -> Option<Handle> {
+        let next = self.slots.get(h)?.next;
+        let new = self.slots.insert(Slot { value, prev: h, next });
+        self.slots[h].next = new;
+        self.slots[next].prev = new;
+        Some(new)
+    }
+
+    /// Unlinks element `h` and returns its value. The handle becomes invalid.
+    pub fn remove(&mut self, h: Handle) -> Option<T> {
+        let Slot { value, prev, next } = self.slots.remove(h)?;
+        if self.slots.is_empty() {
+            self.head = None;
+        } else {
+            self.slots[prev].next = next;
+            self.slots[next].prev = prev;
+            if self.head == Some(h) {
+                self.head = Some(next);
+            }
+        }
+        Some(value)
+    }
+
+    pub fn get(&self, h: Handle) -> Option<&T> {
+        self.slots.get(h).map(|slot| &slot.value)
+    }
+
+    pub fn get_mut(&mut self, h: Handle) -> Option<&mut T> {
+        self.slots.get_mut(h).map(|slot| &mut slot.value)
+    }
+
+    /// Handle of the element after `h`, wrapping around.
+    pub fn next(&self, h: Handle) -> Option<Handle> {
+        self.slots.get(h).map(|slot| slot.next)
+    }
+
+    /// Handle of the element before `h`, wrapping around.
+    pub fn prev(&self, h: Handle) -> Option<Handle> {
+        self.slots.get(h).map(|slot| slot.prev)
+    }
+
+    /// Values in order, starting at the head and going once around.
+    pub fn to_vec(&self) -> Vec<&T> {
+        let mut out = Vec::with_capacity(self.len());
+        if let Some(head) = self.head {
+            let mut cur = head;
+            loop {
+                out.push(&self.slots[cur].value);
+                cur = self.slots[cur].next;
+                if cur == head {
+                    break;
+                }
+            }
+        }
+        out
+    }
+}
+
+impl<T> DataStructure for CircularList<T> {
+    const INFO: Info = Info {
+        name: "CircularList",
+        category: "lists",
+        summary: "Circular doubly linked list in a slab, addressed by stable handles",
+        space: "O(n)",
+        operations: &[
+            Operation::new("push_back", "O(1)", "O(1)"),
+            Operation::new("insert_after", "O(1)", "O(1)"),
+            Operation::new("remove", "O(1)", "O(1)"),
+            Operation::new("next", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_and_wrap() {
+        let mut list = CircularList::new();
+        assert!(list.is_empty());
+        let a = list.push_back('a');
+        let b = list.push_back('b');
+        let c = list.push_back('c');
+        assert_eq!(list.len(), 3);
+        assert_eq!(list.to_vec(), vec![&'a', &'b', &'c']);
+        assert_eq!(list.next(c), Some(a));
+        assert_eq!(list.prev(a), Some(c));
+        assert_eq!(list.next(a), Some(b));
+    }
+
+    #[test]
+    fn test_remove_and_reuse() {
+        let mut list = CircularList::new();
+        let a = list.push_back(1);
+        let b = list.push_back(2);
+        let c = list.push_back(3);
+        assert_eq!(list.remove(a), Some(1));
+        assert_eq!(list.remove(a), None);
+        assert_eq!(list.head(), Some(b));
+        assert_eq!(list.next(c), Some(b));
+        let d = list.insert_after(b, 4).unwrap();
+        assert_eq!(d.index(), a.index()); // slot reused, under a new generation
+        assert_eq!((list.get(a), list.next(a)), (None, None));
+        assert_eq!(list.remove(a), None);
+        assert_eq!(list.to_vec(), vec![&2, &4, &3]);
+        *list.get_mut(c).unwrap() = 30;
+        assert_eq!(list.get(c), Some(&30));
+        list.remove(b);
+        list.remove(c);
+        list.remove(d);
+        assert!(list.is_empty());
+        assert_eq!(list.head(), None);
+    }
+}
@@ -0,0 +1,291 @@
+// rust/src/count_min.rs
+use crate::DataStructure;
+use crate::hash::hash_one;
+use crate::info::{Info, Operation};
+use std::collections::HashMap;
+use std::f64::consts::E;
+use std::hash::Hash;
+
+/// Seed of the second hash function, relative to the sketch's seed.
+const SECOND_SEED: u64 = 0x9E3779B97F4A7C15;
+
+/// Count-Min sketch (Cormode and Muthukrishnan): approximate counts of items in a stream, in
+/// `depth` rows of `width` counters. An item adds its count to one counter per row, chosen by
+/// double hashing, and its estimate is the smallest of those counters. Collisions only add, so an
+/// estimate is never below the true count, and with `width = e / epsilon` and
+/// `depth = ln(1 / delta)` it exceeds it by more than `epsilon * total` with probability at most
+/// `delta`. With conservative update an add raises each counter only as far as the new estimate
+/// needs, which keeps the same guarantee with much less overestimation on skewed streams.
+/// Sketches with the same dimensions and seed merge by adding their counters.
+/// - add / estimate: O(depth)
+/// - merge: O(width * depth)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CountMinSketch {
+    // row-major, `depth` rows of `width` counters
+    counts: Vec<u64>,
+    width: usize,
+    depth: usize,
+    seed: u64,
+    conservative: bool,
+    total: u64,
+}
+
+impl CountMinSketch {
+    /// A sketch whose estimates exceed the true count by at most `epsilon` times the total count,
+    /// except with probability `delta`. Panics unless both lie in (0, 1).
+    pub fn new(epsilon: f64, delta: f64) -> Self {
+        assert!(epsilon > 0.0 && epsilon < 1.0, "epsilon must be in (0, 1), got {}", epsilon);
+        assert!(delta > 0.0 && delta < 1.0, "delta must be in (0, 1), got {}", delta);
+        let width = (E / epsilon).ceil() as usize;
+        let depth = (1.0 / delta).ln().ceil().max(1.0) as usize;
+        Self::with_params(width, depth, 0)
+    }
+
+    /// A sketch of `depth` rows of `width` counters. Panics if either is 0.
+    pub fn with_params(width: usize, depth: usize, seed: u64) -> Self {
+        assert!(width > 0 && depth > 0, "a Count-Min sketch needs at least one row and one column");
+        CountMinSketch { counts: vec![0; width * depth], width, depth, seed, conservative: false, total: 0 }
+    }
+
+    /// Switches the sketch to conservative update.
+    pub fn conservative(mut self) -> Self {
+        self.conservative = true;
+        self
+    }
+
+    pub fn is_conservative(&self) -> bool {
+        self.conservative
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Sum of all counts added.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// How far an estimate may exceed the true count, except with probability `e^-depth`.
+    pub fn error_bound(&self) -> f64 {
+        E / self.width as f64 * self.total as f64
+    }
+
+    pub fn clear(&mut self) {
+        self.counts.iter_mut().for_each(|c| *c = 0);
+        self.total = 0;
+    }
+
+    /// Index into `counts` of `item`'s counter in each row.
+    fn cells<T: Hash + ?Sized>(&self, item: &T) -> impl Iterator<Item = usize> + use<T> {
+        let width = self.width as u64;
+        let h1 = hash_one(item, self.seed);
+        let h2 = hash_one(item, self.seed ^ SECOND_SEED) | 1;
+        (0..self.depth as u64).map(move |r| (r * width + h1.wrapping_add(r.wrapping_mul(h2)) % width) as usize)
+    }
+
+    /// Counts `count` more occurrences of `item`.
+    pub fn add<T: Hash + ?Sized>(&mut self, item: &T, count: u64) {
+        self.total += count;
+        if self.conservative {
+            let target = self.estimate(item) + count;
+            for c in self.cells(item) {
+                self.counts[c] = self.counts[c].max(target);
+            }
+        } else {
+            for c in self.cells(item) {
+                self.counts[c] += count;
+            }
+        }
+    }
+
+    /// Upper bound on the number of occurrences of `item`.
+    pub fn estimate<T: Hash + ?Sized>(&self, item: &T) -> u64 {
+        self.cells(item).map(|c| self.counts[c]).min().unwrap_or(0)
+    }
+
+    /// Whether `other` has the same dimensions and seed, so the two can be merged.
+    pub fn is_compatible(&self, other: &CountMinSketch) -> bool {
+        (self.width, self.depth, self.seed) == (other.width, other.depth, other.seed)
+    }
+
+    /// Adds the counts of `other`, as if its stream had been added to this sketch. Merging a
+    /// conservative sketch gives estimates that are still upper bounds, but not as tight as
+    /// adding the stream would have. Panics if the sketches are not compatible.
+    pub fn merge(&mut self, other: &CountMinSketch) {
+        assert!(self.is_compatible(other), "Count-Min sketches differ in dimensions or seed");
+        self.counts.iter_mut().zip(&other.counts).for_each(|(a, b)| *a += b);
+        self.total += other.total;
+    }
+}
+
+/// Items whose count is at least `phi` times the total, tracked on top of a Count-Min sketch. A
+/// sketch cannot list its items, so this keeps as candidates the items whose estimate reached the
+/// threshold when they were last added, pruning the ones that fell below it as the total grows.
+/// Every item with a true count of at least `phi * total` is reported; items whose count is a
+/// little below can be reported too, as far as their estimates are too high.
+#[derive(Debug, Clone)]
+pub struct HeavyHitters<K> {
+    sketch: CountMinSketch,
+    phi: f64,
+    candidates: HashMap<K, u64>,
+    // candidate count that triggers the next pruning
+    limit: usize,
+}
+
+impl<K: Hash + Eq + Clone> HeavyHitters<K> {
+    /// Tracks items above a `phi` fraction of the stream with a sketch for `epsilon` and `delta`.
+    /// Panics unless `0 < epsilon < phi < 1`.
+    pub fn new(phi: f64, epsilon: f64, delta: f64) -> Self {
+        assert!(epsilon < phi, "epsilon must be below phi, or every item can look heavy");
+        Self::from_sketch(CountMinSketch::new(epsilon, delta), phi)
+    }
+
+    /// Tracks items above a `phi` fraction using `sketch`, which should be empty. Panics unless
+    /// `0 < phi < 1`.
+    pub fn from_sketch(sketch: CountMinSketch, phi: f64) -> Self {
+        assert!(phi > 0.0 && phi < 1.0, "phi must be in (0, 1), got {}", phi);
+        let limit = (2.0 / phi).ceil() as usize;
+        HeavyHitters { sketch, phi, candidates: HashMap::new(), limit }
+    }
+
+    pub fn sketch(&self) -> &CountMinSketch {
+        &self.sketch
+    }
+
+    /// Smallest estimate that counts as heavy right now.
+    pub fn threshold(&self) -> f64 {
+        self.phi * self.sketch.total() as f64
+    }
+
+    /// Counts `count` more occurrences of `item`.
+    pub fn add(&mut self, item: &K, count: u64) {
+        self.sketch.add(item, count);
+        let estimate = self.sketch.estimate(item);
+        if estimate as f64 >= self.threshold() {
+            self.candidates.insert(item.clone(), estimate);
+            if self.candidates.len() > self.limit {
+                self.prune();
+            }
+        }
+    }
+
+    /// Drops the candidates below the threshold, and makes sure the next pruning is far enough off
+    /// to keep adds O(depth) amortized.
+    fn prune(&mut self) {
+        let threshold = self.threshold();
+        self.candidates.retain(|_, estimate| *estimate as f64 >= threshold);
+        self.limit = self.limit.max(2 * self.candidates.len());
+    }
+
+    /// Merges the stream of `other` into this one. Panics if the sketches are not compatible.
+    pub fn merge(&mut self, other: &HeavyHitters<K>) {
+        self.sketch.merge(&other.sketch);
+        for item in other.candidates.keys() {
+            self.candidates.entry(item.clone()).or_default();
+        }
+        // the estimates of both sides' candidates changed with the merge
+        for (item, estimate) in self.candidates.iter_mut() {
+            *estimate = self.sketch.estimate(item);
+        }
+        self.prune();
+    }
+
+    /// The heavy items with their estimated counts, most frequent first.
+    pub fn heavy_hitters(&self) -> Vec<(K, u64)> {
+        let threshold = self.threshold();
+        let mut out: Vec<(K, u64)> = self
+            .candidates
+            .keys()
+            .map(|item| (item.clone(), self.sketch.estimate(item)))
+            .filter(|&(_, estimate)| estimate as f64 >= threshold)
+            .collect();
+        out.sort_by_key(|&(_, estimate)| std::cmp::Reverse(estimate));
+        out
+    }
+}
+
+impl DataStructure for CountMinSketch {
+    const INFO: Info = Info {
+        name: "CountMinSketch",
+        category: "probabilistic",
+        summary: "Approximate stream frequencies with one-sided error, conservative update, merging and heavy hitters (w = width, d = depth)",
+        space: "O(w * d)",
+        operations: &[
+            Operation::new("add", "O(d)", "O(1)"),
+            Operation::new("estimate", "O(d)", "O(1)"),
+            Operation::new("merge", "O(w * d)", "O(1)"),
+            Operation::new("HeavyHitters::add", "O(d) amortized", "O(1 / phi) candidates"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_estimates_and_merge() {
+        let mut sketch = CountMinSketch::with_params(64, 4, 1);
+        sketch.add("GET /", 10);
+        sketch.add("POST /login", 3);
+        sketch.add("GET /", 1);
+        assert_eq!(sketch.total(), 14);
+        assert!(sketch.estimate("GET /") >= 11 && sketch.estimate("POST /login") >= 3);
+        assert!(sketch.estimate("GET /favicon.ico") <= 14);
+        let mut other = CountMinSketch::with_params(64, 4, 1);
+        other.add("GET /", 5);
+        sketch.merge(&other);
+        assert!(sketch.estimate("GET /") >= 16 && sketch.total() == 19);
+        assert!(!sketch.is_compatible(&CountMinSketch::with_params(64, 4, 2)));
+
+        let sized = CountMinSketch::new(0.01, 0.01);
+        assert_eq!((sized.width(), sized.depth()), (272, 5));
+        // a single cell takes everything, so each row only sees its own item or all of them
+        let mut tiny = CountMinSketch::with_params(1, 1, 0).conservative();
+        tiny.add(&1, 4);
+        tiny.add(&2, 3);
+        assert_eq!((tiny.estimate(&1), tiny.total()), (7, 7));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(97);
+        let (epsilon, delta) = (0.005, 0.01);
+        let mut plain = CountMinSketch::new(epsilon, delta);
+        let mut conservative = CountMinSketch::new(epsilon, delta).conservative();
+        let mut hitters = HeavyHitters::new(0.02, epsilon, delta);
+        let mut counts: HashMap<u64, u64> = HashMap::new();
+        for _ in 0..50_000 {
+            // skewed: small keys are far more frequent
+            let key = next() % (1 + next() % 2000);
+            plain.add(&key, 1);
+            conservative.add(&key, 1);
+            hitters.add(&key, 1);
+            *counts.entry(key).or_default() += 1;
+        }
+        let bound = plain.error_bound();
+        let mut misses = 0;
+        for (key, &count) in &counts {
+            let (p, c) = (plain.estimate(key), conservative.estimate(key));
+            assert!(count <= c && c <= p);
+            misses += (p as f64 > count as f64 + bound) as usize;
+        }
+        assert!(misses as f64 <= 2.0 * delta * counts.len() as f64, "{} estimates out of bounds", misses);
+
+        let found: Vec<u64> = hitters.heavy_hitters().into_iter().map(|(k, _)| k).collect();
+        for (key, &count) in &counts {
+            if count as f64 >= 0.02 * 50_000.0 {
+                assert!(found.contains(key), "missed heavy key {}", key);
+            }
+        }
+        assert!(found.iter().all(|k| counts[k] as f64 >= (0.02 - epsilon) * 50_000.0));
+    }
+}
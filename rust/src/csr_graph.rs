@@ -0,0 +1,164 @@
+// rust/src/csr_graph.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+/// A directed graph in compressed sparse row (CSR) form.
+/// All edge targets live in one contiguous array and `offsets[u]..offsets[u + 1]` is the slice of
+/// vertex `u`'s out-edges, so traversals stream through memory instead of chasing one heap
+/// allocation per vertex like `Vec<Vec<_>>` adjacency lists do.
+///
+/// - Build: O(n + m) (counting sort by source)
+/// - neighbors(u): O(1) to get the slice
+///
+/// `W` is the edge weight type; use `()` for unweighted graphs.
+#[derive(Debug, Clone)]
+pub struct CsrGraph<W = ()> {
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    weights: Vec<W>,
+}
+
+impl<W: Clone> CsrGraph<W> {
+    /// Builds from an edge list `(from, to, weight)`. Edges of each vertex keep their input order.
+    /// Panics if an endpoint is `>= n`.
+    pub fn from_edges(n: usize, edges: &[(usize, usize, W)]) -> Self {
+        let mut offsets = vec![0usize; n + 1];
+        for &(u, v, _) in edges {
+            assert!(u < n && v < n, "edge endpoint out of bounds");
+            offsets[u + 1] += 1;
+        }
+        for i in 0..n {
+            offsets[i + 1] += offsets[i];
+        }
+        let mut next = offsets.clone();
+        let mut slots: Vec<Option<(usize, W)>> = vec![None; edges.len()];
+        for (u, v, w) in edges {
+            slots[next[*u]] = Some((*v, w.clone()));
+            next[*u] += 1;
+        }
+        let (targets, weights) = slots.into_iter().map(|e| e.expect("every slot is filled")).unzip();
+        CsrGraph { offsets, targets, weights }
+    }
+
+    /// Converts `Vec<Vec<(to, weight)>>` adjacency lists into CSR form.
+    pub fn from_adjacency(adj: &[Vec<(usize, W)>]) -> Self {
+        let mut offsets = Vec::with_capacity(adj.len() + 1);
+        let total = adj.iter().map(|l| l.len()).sum();
+        let mut targets = Vec::with_capacity(total);
+        let mut weights = Vec::with_capacity(total);
+        offsets.push(0);
+        for list in adj {
+            for (v, w) in list {
+                assert!(*v < adj.len(), "edge endpoint out of bounds");
+                targets.push(*v);
+                weights.push(w.clone());
+            }
+            offsets.push(targets.len());
+        }
+        CsrGraph { offsets, targets, weights }
+    }
+
+    /// Expands back into `Vec<Vec<(to, weight)>>` adjacency lists.
+    pub fn to_adjacency(&self) -> Vec<Vec<(usize, W)>> {
+        (0..self.num_nodes()).map(|u| self.edges(u).map(|(v, w)| (v, w.clone())).collect()).collect()
+    }
+}
+
+impl CsrGraph<()> {
+    /// Converts unweighted `Vec<Vec<to>>` adjacency lists into CSR form.
+    pub fn from_unweighted_adjacency(adj: &[Vec<usize>]) -> Self {
+        let weighted: Vec<Vec<(usize, ())>> = adj.iter().map(|l| l.iter().map(|&v| (v, ())).collect()).collect();
+        Self::from_adjacency(&weighted)
+    }
+}
+
+impl<W> CsrGraph<W> {
+    pub fn num_nodes(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn degree(&self, u: usize) -> usize {
+        self.offsets[u + 1] - self.offsets[u]
+    }
+
+    /// Targets of `u`'s out-edges.
+    pub fn neighbors(&self, u: usize) -> &[usize] {
+        &self.targets[self.offsets[u]..self.offsets[u + 1]]
+    }
+
+    /// Weights of `u`'s out-edges, parallel to `neighbors(u)`.
+    pub fn weights(&self, u: usize) -> &[W] {
+        &self.weights[self.offsets[u]..self.offsets[u + 1]]
+    }
+
+    /// `(to, &weight)` pairs of `u`'s out-edges.
+    pub fn edges(&self, u: usize) -> impl Iterator<Item = (usize, &W)> + '_ {
+        self.neighbors(u).iter().copied().zip(self.weights(u))
+    }
+
+    /// Unweighted BFS distances from `src`; unreachable vertices get `usize::MAX`.
+    pub fn bfs_distances(&self, src: usize) -> Vec<usize> {
+        let mut dist = vec![usize::MAX; self.num_nodes()];
+        let mut queue = std::collections::VecDeque::new();
+        dist[src] = 0;
+        queue.push_back(src);
+        while let Some(u) = queue.pop_front() {
+            for &v in self.neighbors(u) {
+                if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    queue.push_back(v);
+                }
+            }
+        }
+        dist
+    }
+}
+
+impl<W> DataStructure for CsrGraph<W> {
+    const INFO: Info = Info {
+        name: "CsrGraph",
+        category: "graphs",
+        summary: "Directed graph in compressed sparse row form (m = edges)",
+        space: "O(n + m)",
+        operations: &[
+            Operation::new("from_edges", "O(n + m)", "O(n + m)"),
+            Operation::new("neighbors", "O(1)", "O(1)"),
+            Operation::new("bfs_distances", "O(n + m)", "O(n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_edges() {
+        let g = CsrGraph::from_edges(4, &[(0, 1, 5), (2, 3, 1), (0, 2, 7), (3, 0, 2)]);
+        assert_eq!(g.num_nodes(), 4);
+        assert_eq!(g.num_edges(), 4);
+        assert_eq!(g.neighbors(0), &[1, 2]);
+        assert_eq!(g.weights(0), &[5, 7]);
+        assert_eq!(g.degree(1), 0);
+        assert_eq!(g.edges(3).collect::<Vec<_>>(), vec![(0, &2)]);
+    }
+
+    #[test]
+    fn test_adjacency_round_trip() {
+        let adj = vec![vec![(1, 1.5), (2, 0.5)], vec![], vec![(0, 2.0)]];
+        let g = CsrGraph::from_adjacency(&adj);
+        assert_eq!(g.to_adjacency(), adj);
+        assert_eq!(g.neighbors(2), &[0]);
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let g = CsrGraph::from_unweighted_adjacency(&[vec![1, 2], vec![3], vec![3], vec![], vec![0]]);
+        assert_eq!(g.bfs_distances(0), vec![0, 1, 1, 2, usize::MAX]);
+    }
+}
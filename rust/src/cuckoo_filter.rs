@@ -0,0 +1,256 @@
+// rust/src/cuckoo_filter.rs
+use crate::DataStructure;
+use crate::hash::hash_one;
+use crate::info::{Info, Operation};
+use crate::rng::{Rng, SplitMix64};
+use std::hash::Hash;
+
+/// Seed of the fingerprint hash, relative to the filter's seed.
+const FINGERPRINT_SEED: u64 = 0x2545F4914F6CDD1D;
+/// Load factor `new` sizes for; four-slot buckets fill to about 95% before inserts fail.
+const TARGET_LOAD: f64 = 0.9;
+
+/// Cuckoo filter (Fan, Andersen, Kaminsky and Mitzenmacher): approximate membership like a Bloom
+/// filter, but items can be removed again. Each item is reduced to a 16-bit fingerprint that may
+/// live in one of two buckets, `i1 = hash(item)` and `i2 = i1 ^ hash(fingerprint)`; since the
+/// second index only needs the fingerprint, a stored entry can be moved to its other bucket
+/// without the item. An insert into two full buckets evicts a random entry to its alternate
+/// bucket, up to `max_kicks` times; if that chain does not end, the entry left over is kept aside
+/// and the filter refuses further inserts until something is removed. Lookups are wrong with
+/// probability about `2 * bucket_size / 2^16`.
+/// Only remove items that were inserted: removing anything else may delete another item's
+/// fingerprint and cause a false negative. Inserting an item twice stores it twice.
+/// - insert: O(1) amortized, O(max_kicks) worst case
+/// - maybe_contains / remove: O(bucket_size)
+#[derive(Debug, Clone)]
+pub struct CuckooFilter {
+    // `bucket_size` consecutive slots per bucket; 0 marks an empty slot
+    slots: Vec<u16>,
+    bucket_size: usize,
+    // bucket count minus one; the count is a power of two so that `i ^ hash` stays in range
+    mask: usize,
+    max_kicks: usize,
+    len: usize,
+    // an entry that found no slot at the end of an eviction chain, with its bucket
+    victim: Option<(usize, u16)>,
+    seed: u64,
+    rng: SplitMix64,
+}
+
+impl CuckooFilter {
+    /// A filter with four-slot buckets and room for at least `capacity` items.
+    pub fn new(capacity: usize) -> Self {
+        let buckets = (capacity as f64 / (4.0 * TARGET_LOAD)).ceil() as usize;
+        Self::with_params(buckets, 4, 500, 0)
+    }
+
+    /// A filter of `num_buckets` buckets (rounded up to a power of two) of `bucket_size` slots,
+    /// giving up on an insert after `max_kicks` evictions. Panics if `bucket_size` is 0.
+    pub fn with_params(num_buckets: usize, bucket_size: usize, max_kicks: usize, seed: u64) -> Self {
+        assert!(bucket_size > 0, "buckets need at least one slot");
+        let buckets = num_buckets.max(1).next_power_of_two();
+        CuckooFilter { slots: vec![0; buckets * bucket_size], bucket_size, mask: buckets - 1, max_kicks, len: 0, victim: None, seed, rng: SplitMix64::new(seed) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn num_buckets(&self) -> usize {
+        self.mask + 1
+    }
+
+    pub fn bucket_size(&self) -> usize {
+        self.bucket_size
+    }
+
+    /// Total number of slots. Inserts usually start failing a little before this.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Fraction of slots in use.
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.slots.len() as f64
+    }
+
+    /// Whether an insert has failed to place an entry; inserts are refused until a removal.
+    pub fn is_full(&self) -> bool {
+        self.victim.is_some()
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|s| *s = 0);
+        self.len = 0;
+        self.victim = None;
+    }
+
+    /// Fingerprint and first bucket of `item`.
+    fn locate<T: Hash + ?Sized>(&self, item: &T) -> (u16, usize) {
+        let h = hash_one(item, self.seed);
+        // 0 marks an empty slot, so it is not a valid fingerprint
+        let fingerprint = ((h >> 48) as u16).max(1);
+        (fingerprint, h as usize & self.mask)
+    }
+
+    fn alternate(&self, bucket: usize, fingerprint: u16) -> usize {
+        bucket ^ (hash_one(&fingerprint, self.seed ^ FINGERPRINT_SEED) as usize & self.mask)
+    }
+
+    fn bucket(&self, i: usize) -> &[u16] {
+        &self.slots[i * self.bucket_size..(i + 1) * self.bucket_size]
+    }
+
+    /// Puts `fingerprint` in a free slot of bucket `i`, if there is one.
+    fn place(&mut self, i: usize, fingerprint: u16) -> bool {
+        let size = self.bucket_size;
+        match self.slots[i * size..(i + 1) * size].iter_mut().find(|s| **s == 0) {
+            Some(slot) => {
+                *slot = fingerprint;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Adds `item`. Returns false, leaving the filter unchanged, if the filter is full.
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) -> bool {
+        if self.victim.is_some() {
+            return false;
+        }
+        let (mut fingerprint, i1) = self.locate(item);
+        let i2 = self.alternate(i1, fingerprint);
+        self.len += 1;
+        if self.place(i1, fingerprint) || self.place(i2, fingerprint) {
+            return true;
+        }
+        let mut i = if self.rng.next_u64() & 1 == 0 { i1 } else { i2 };
+        for _ in 0..self.max_kicks {
+            let slot = i * self.bucket_size + (self.rng.next_u64() % self.bucket_size as u64) as usize;
+            std::mem::swap(&mut fingerprint, &mut self.slots[slot]);
+            i = self.alternate(i, fingerprint);
+            if self.place(i, fingerprint) {
+                return true;
+            }
+        }
+        // the item itself is stored, but the evicted entry now in hand has nowhere to go
+        self.victim = Some((i, fingerprint));
+        true
+    }
+
+    /// False if `item` is certainly not in the filter; true if it probably is.
+    pub fn maybe_contains<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        let (fingerprint, i1) = self.locate(item);
+        let i2 = self.alternate(i1, fingerprint);
+        self.bucket(i1).contains(&fingerprint)
+            || self.bucket(i2).contains(&fingerprint)
+            || self.victim.is_some_and(|(i, f)| f == fingerprint && (i == i1 || i == i2))
+    }
+
+    /// Removes one copy of `item`. Returns false if it was not found.
+    pub fn remove<T: Hash + ?Sized>(&mut self, item: &T) -> bool {
+        let (fingerprint, i1) = self.locate(item);
+        let i2 = self.alternate(i1, fingerprint);
+        if let Some((i, f)) = self.victim
+            && f == fingerprint
+            && (i == i1 || i == i2)
+        {
+            self.victim = None;
+            self.len -= 1;
+            return true;
+        }
+        for i in [i1, i2] {
+            let size = self.bucket_size;
+            if let Some(slot) = self.slots[i * size..(i + 1) * size].iter_mut().find(|s| **s == fingerprint) {
+                *slot = 0;
+                self.len -= 1;
+                // the freed slot may be where the set-aside entry belongs
+                if let Some((v, f)) = self.victim
+                    && (self.place(v, f) || self.place(self.alternate(v, f), f))
+                {
+                    self.victim = None;
+                }
+                return true;
+            }
+        }
+        false
+    }
+}
+
+impl DataStructure for CuckooFilter {
+    const INFO: Info = Info {
+        name: "CuckooFilter",
+        category: "probabilistic",
+        summary: "Approximate set membership with deletion, storing 16-bit fingerprints in two candidate buckets (b = bucket size)",
+        space: "16 bits per slot, about 17 bits per item at 95% load",
+        operations: &[
+            Operation::new("insert", "O(1) amortized", "O(1)"),
+            Operation::new("maybe_contains", "O(b)", "O(1)"),
+            Operation::new("remove", "O(b)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_fill_and_remove() {
+        let mut filter = CuckooFilter::with_params(16, 4, 100, 7);
+        assert_eq!(filter.capacity(), 64);
+        let mut stored = Vec::new();
+        while filter.insert(&stored.len()) {
+            stored.push(stored.len());
+        }
+        assert!(filter.is_full() && filter.load_factor() > 0.85);
+        assert_eq!(filter.len(), stored.len());
+        assert!(stored.iter().all(|x| filter.maybe_contains(x)));
+        // removals eventually free a slot where the entry set aside belongs
+        let mut removed = 0;
+        while filter.is_full() {
+            assert!(filter.remove(&stored[removed]));
+            removed += 1;
+        }
+        assert_eq!(filter.len(), stored.len() - removed);
+        assert!(filter.insert(&1000usize) && filter.maybe_contains(&1000usize));
+        assert!(stored[removed..].iter().all(|x| filter.maybe_contains(x)));
+
+        let mut small = CuckooFilter::new(100);
+        assert!(small.capacity() >= 100 && small.is_empty());
+        assert!(small.insert("a") && small.insert("a"));
+        assert!(small.remove("a") && small.maybe_contains("a"));
+        assert!(small.remove("a") && !small.maybe_contains("a") && !small.remove("a"));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(89);
+        let mut filter = CuckooFilter::new(3000);
+        let mut counts: HashMap<u64, usize> = HashMap::new();
+        for _ in 0..20_000 {
+            let key = next() % 50_000;
+            if next().is_multiple_of(3) {
+                if let Some(c) = counts.get_mut(&key).filter(|c| **c > 0) {
+                    assert!(filter.remove(&key));
+                    *c -= 1;
+                }
+            } else if counts.values().sum::<usize>() < 3000 {
+                assert!(filter.insert(&key));
+                *counts.entry(key).or_default() += 1;
+            }
+        }
+        assert_eq!(filter.len(), counts.values().sum::<usize>());
+        assert!(counts.iter().filter(|&(_, &c)| c > 0).all(|(k, _)| filter.maybe_contains(k)));
+        let false_positives = (50_000..100_000u64).filter(|k| filter.maybe_contains(k)).count();
+        assert!(false_positives < 50, "{} false positives", false_positives);
+    }
+}
@@ -0,0 +1,331 @@
+// rust/src/cycles.rs
+use std::collections::VecDeque;
+
+/// `(neighbour, edge id)` lists; the edge id lets undirected searches avoid walking back over the same edge.
+fn adjacency(n: usize, edges: &[(usize, usize)], directed: bool) -> Vec<Vec<(usize, usize)>> {
+    let mut adj = vec![Vec::new(); n];
+    for (id, &(u, v)) in edges.iter().enumerate() {
+        adj[u].push((v, id));
+        if !directed && u != v {
+            adj[v].push((u, id));
+        }
+    }
+    adj
+}
+
+/// Cycle detection and enumeration over graphs given as `(from, to)` edge lists on vertices `0..n`.
+/// With `directed == false` each edge can be walked both ways; parallel edges and self-loops count as cycles.
+/// Cycles are returned as vertex sequences without repeating the first vertex at the end.
+///
+/// Returns some cycle of the graph, or None if it is acyclic (a DAG, or a forest when undirected).
+///
+/// Complexity: O(n + m)
+pub fn find_any_cycle(n: usize, edges: &[(usize, usize)], directed: bool) -> Option<Vec<usize>> {
+    let adj = adjacency(n, edges, directed);
+    // 0 = unvisited, 1 = on the DFS stack, 2 = finished
+    let mut state = vec![0u8; n];
+    let mut parent = vec![(usize::MAX, usize::MAX); n];
+    for root in 0..n {
+        if state[root] != 0 {
+            continue;
+        }
+        // iterative DFS: (vertex, next adjacency index)
+        let mut stack = vec![(root, 0usize)];
+        state[root] = 1;
+        while let Some(&mut (u, ref mut next)) = stack.last_mut() {
+            if *next == adj[u].len() {
+                state[u] = 2;
+                stack.pop();
+                continue;
+            }
+            let (v, id) = adj[u][*next];
+            *next += 1;
+            if !directed && id == parent[u].1 {
+                continue;
+            }
+            match state[v] {
+                0 => {
+                    state[v] = 1;
+                    parent[v] = (u, id);
+                    stack.push((v, 0));
+                }
+                1 => {
+                    // back edge u -> v closes a cycle along the stack
+                    let mut cycle = vec![u];
+                    let mut cur = u;
+                    while cur != v {
+                        cur = parent[cur].0;
+                        cycle.push(cur);
+                    }
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+                _ => {}
+            }
+        }
+    }
+    None
+}
+
+/// Returns a shortest cycle (its length is the girth), or None if the graph is acyclic.
+/// Runs a BFS from every vertex.
+///
+/// Complexity: O(n * (n + m))
+pub fn shortest_cycle(n: usize, edges: &[(usize, usize)], directed: bool) -> Option<Vec<usize>> {
+    let adj = adjacency(n, edges, directed);
+    let mut best: Option<Vec<usize>> = None;
+    let mut dist = vec![usize::MAX; n];
+    let mut parent = vec![(usize::MAX, usize::MAX); n];
+    let path_to = |parent: &[(usize, usize)], mut v: usize, root: usize| {
+        let mut path = vec![v];
+        while v != root {
+            v = parent[v].0;
+            path.push(v);
+        }
+        path.reverse();
+        path
+    };
+    for root in 0..n {
+        dist.iter_mut().for_each(|d| *d = usize::MAX);
+        dist[root] = 0;
+        parent[root] = (usize::MAX, usize::MAX);
+        let mut queue = VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            // cycles found from here on are at least this long
+            let lower_bound = if directed { dist[u] + 1 } else { 2 * dist[u] };
+            if best.as_ref().is_some_and(|b| lower_bound >= b.len()) {
+                break;
+            }
+            for &(v, id) in &adj[u] {
+                if directed && v == root {
+                    // edge back to the source closes a cycle through it
+                    let len = dist[u] + 1;
+                    if best.as_ref().is_none_or(|b| len < b.len()) {
+                        best = Some(path_to(&parent, u, root));
+                    }
+                } else if dist[v] == usize::MAX {
+                    dist[v] = dist[u] + 1;
+                    parent[v] = (u, id);
+                    queue.push_back(v);
+                } else if !directed && id != parent[u].1 {
+                    let len = dist[u] + dist[v] + 1;
+                    if best.as_ref().is_none_or(|b| len < b.len()) {
+                        let mut cycle = path_to(&parent, u, root);
+                        let back = path_to(&parent, v, root);
+                        cycle.extend(back.into_iter().skip(1).rev());
+                        best = Some(cycle);
+                    }
+                }
+            }
+        }
+    }
+    best
+}
+
+/// Enumerates up to `max_cycles` simple cycles of a directed graph by Johnson's algorithm.
+/// Each cycle is reported once, starting from its smallest vertex. For every start vertex `s` the
+/// search is confined to the strongly connected component of `s` among the vertices `>= s`, and a
+/// vertex that failed to lead back to `s` stays blocked until one of its successors is unblocked
+/// again, so no dead end is explored twice: the work between two consecutive cycles is O(n + m)
+/// rather than exponential. The count can still be exponential in `n`, hence the bound.
+///
+/// Complexity: O(n * (n + m) + (n + m) * c) for c cycles reported
+pub fn simple_cycles(n: usize, edges: &[(usize, usize)], max_cycles: usize) -> Vec<Vec<usize>> {
+    let mut adj = vec![Vec::new(); n];
+    let mut radj = vec![Vec::new(); n];
+    for &(u, v) in edges {
+        adj[u].push(v);
+        radj[v].push(u);
+    }
+    for list in adj.iter_mut().chain(radj.iter_mut()) {
+        list.sort_unstable();
+        list.dedup();
+    }
+    // vertices >= s reachable from s along `graph` without leaving those vertices
+    let reach = |graph: &[Vec<usize>], s: usize| {
+        let mut seen = vec![false; n];
+        seen[s] = true;
+        let mut stack = vec![s];
+        while let Some(u) = stack.pop() {
+            for &v in &graph[u] {
+                if v > s && !seen[v] {
+                    seen[v] = true;
+                    stack.push(v);
+                }
+            }
+        }
+        seen
+    };
+    let mut cycles = Vec::new();
+    let mut blocked = vec![false; n];
+    // b_lists[w]: blocked vertices to unblock once w is unblocked
+    let mut b_lists: Vec<Vec<usize>> = vec![Vec::new(); n];
+    for start in 0..n {
+        if cycles.len() >= max_cycles {
+            break;
+        }
+        let (forward, backward) = (reach(&adj, start), reach(&radj, start));
+        let in_scc: Vec<bool> = (0..n).map(|v| forward[v] && backward[v]).collect();
+        for v in (start..n).filter(|&v| in_scc[v]) {
+            blocked[v] = false;
+            b_lists[v].clear();
+        }
+        blocked[start] = true;
+        let mut path = vec![start];
+        // per path vertex: (next adjacency index, whether a cycle was found below it)
+        let mut stack = vec![(0usize, false)];
+        while let Some(&mut (ref mut next, ref mut found)) = stack.last_mut() {
+            if cycles.len() >= max_cycles {
+                break;
+            }
+            let u = *path.last().expect("path and stack have equal length");
+            if let Some(&w) = adj[u].get(*next) {
+                *next += 1;
+                if w == start {
+                    cycles.push(path.clone());
+                    *found = true;
+                } else if in_scc[w] && !blocked[w] {
+                    blocked[w] = true;
+                    path.push(w);
+                    stack.push((0, false));
+                }
+                continue;
+            }
+            let found = *found;
+            stack.pop();
+            path.pop();
+            if found {
+                // u may lie on a later cycle: unblock it and, transitively, whatever waited on it
+                blocked[u] = false;
+                let mut todo = vec![u];
+                while let Some(x) = todo.pop() {
+                    for y in std::mem::take(&mut b_lists[x]) {
+                        if blocked[y] {
+                            blocked[y] = false;
+                            todo.push(y);
+                        }
+                    }
+                }
+            } else {
+                // u stays blocked until one of its successors becomes free again
+                for &w in adj[u].iter().filter(|&&w| in_scc[w]) {
+                    if !b_lists[w].contains(&u) {
+                        b_lists[w].push(u);
+                    }
+                }
+            }
+            if let Some(parent) = stack.last_mut() {
+                parent.1 |= found;
+            }
+        }
+    }
+    cycles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    fn is_cycle(cycle: &[usize], edges: &[(usize, usize)], directed: bool) -> bool {
+        let has = |a: usize, b: usize| edges.iter().any(|&(u, v)| (u, v) == (a, b) || (!directed && (v, u) == (a, b)));
+        !cycle.is_empty() && (0..cycle.len()).all(|i| has(cycle[i], cycle[(i + 1) % cycle.len()]))
+    }
+
+    #[test]
+    fn test_find_any_cycle() {
+        let dag = [(0, 1), (1, 2), (0, 2)];
+        assert_eq!(find_any_cycle(3, &dag, true), None);
+        // the same edges undirected form a triangle
+        let c = find_any_cycle(3, &dag, false).unwrap();
+        assert!(is_cycle(&c, &dag, false) && c.len() == 3);
+
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 1)];
+        let c = find_any_cycle(4, &edges, true).unwrap();
+        assert!(is_cycle(&c, &edges, true));
+        assert_eq!(find_any_cycle(4, &[(0, 1), (1, 2), (1, 3)], false), None);
+        assert_eq!(find_any_cycle(1, &[(0, 0)], true), Some(vec![0]));
+    }
+
+    #[test]
+    fn test_shortest_cycle() {
+        // a 6-cycle with a chord creating two 4-cycles
+        let edges = [(0, 1), (1, 2), (2, 3), (3, 4), (4, 5), (5, 0), (0, 3)];
+        let c = shortest_cycle(6, &edges, false).unwrap();
+        assert_eq!(c.len(), 4);
+        assert!(is_cycle(&c, &edges, false));
+
+        let directed = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (3, 2)];
+        let c = shortest_cycle(5, &directed, true).unwrap();
+        assert_eq!(c.len(), 2);
+        assert!(is_cycle(&c, &directed, true));
+        assert_eq!(shortest_cycle(3, &[(0, 1), (1, 2)], false), None);
+    }
+
+    #[test]
+    fn test_simple_cycles() {
+        let edges = [(0, 1), (1, 0), (1, 2), (2, 0), (2, 2)];
+        let mut cycles = simple_cycles(3, &edges, 100);
+        cycles.sort();
+        assert_eq!(cycles, vec![vec![0, 1], vec![0, 1, 2], vec![2]]);
+        assert_eq!(simple_cycles(3, &edges, 2).len(), 2);
+
+        // complete digraph on 4 vertices: 6 two-cycles, 8 three-cycles, 6 four-cycles
+        let mut k4 = vec![];
+        for u in 0..4 {
+            for v in 0..4 {
+                if u != v {
+                    k4.push((u, v));
+                }
+            }
+        }
+        assert_eq!(simple_cycles(4, &k4, usize::MAX).len(), 20);
+
+        // a complete DAG on 40 vertices has 2^38 paths out of vertex 0, but its only cycles come
+        // from one back edge; the component restriction never walks those paths
+        let mut dense: Vec<(usize, usize)> = (0..40).flat_map(|u| (u + 1..40).map(move |v| (u, v))).collect();
+        dense.push((39, 37));
+        let mut cycles = simple_cycles(40, &dense, usize::MAX);
+        cycles.sort();
+        assert_eq!(cycles, vec![vec![37, 38, 39], vec![37, 39]]);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        // plain backtracking over larger vertices, the definition Johnson's algorithm prunes
+        let naive = |n: usize, edges: &[(usize, usize)]| {
+            fn extend(u: usize, path: &mut Vec<usize>, edges: &[(usize, usize)], out: &mut Vec<Vec<usize>>) {
+                let mut next: Vec<usize> = edges.iter().filter(|e| e.0 == u).map(|e| e.1).collect();
+                next.sort_unstable();
+                next.dedup();
+                for v in next {
+                    if v == path[0] {
+                        out.push(path.clone());
+                    } else if v > path[0] && !path.contains(&v) {
+                        path.push(v);
+                        extend(v, path, edges, out);
+                        path.pop();
+                    }
+                }
+            }
+            let mut out = Vec::new();
+            for s in 0..n {
+                extend(s, &mut vec![s], edges, &mut out);
+            }
+            out.sort();
+            out
+        };
+        let mut next = test_rng(165);
+        for _ in 0..200 {
+            let n = 1 + next() as usize % 8;
+            let edges: Vec<(usize, usize)> = (0..next() as usize % 20).map(|_| (next() as usize % n, next() as usize % n)).collect();
+            let mut cycles = simple_cycles(n, &edges, usize::MAX);
+            assert!(cycles.iter().all(|c| is_cycle(c, &edges, true) && c[0] == *c.iter().min().unwrap()));
+            cycles.sort();
+            let expected = naive(n, &edges);
+            assert_eq!(cycles, expected);
+            let cap = next() as usize % (expected.len() + 2);
+            assert_eq!(simple_cycles(n, &edges, cap).len(), cap.min(expected.len()));
+        }
+    }
+}
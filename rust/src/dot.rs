@@ -0,0 +1,189 @@
+// rust/src/dot.rs
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::hash::Hash;
+
+/// Nodes (and the links between consecutive nodes of a path) to emphasise in Graphviz output.
+/// What a node is depends on the structure being rendered: a vertex for `Graph`, a key for `Treap`,
+/// an element for `UnionFind`, an array index for the segment trees.
+#[derive(Debug, Clone)]
+pub struct Highlight<K = usize> {
+    nodes: HashSet<K>,
+    links: HashSet<(K, K)>,
+}
+
+impl<K> Default for Highlight<K> {
+    fn default() -> Self {
+        Highlight { nodes: HashSet::new(), links: HashSet::new() }
+    }
+}
+
+impl<K: Hash + Eq + Clone> Highlight<K> {
+    /// Highlights nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Highlights a set of nodes, e.g. a connected component.
+    pub fn component<I: IntoIterator<Item = K>>(nodes: I) -> Self {
+        Highlight { nodes: nodes.into_iter().collect(), links: HashSet::new() }
+    }
+
+    /// Highlights the nodes of a path and the links between consecutive ones.
+    pub fn path(nodes: &[K]) -> Self {
+        Highlight {
+            nodes: nodes.iter().cloned().collect(),
+            links: nodes.windows(2).map(|w| (w[0].clone(), w[1].clone())).collect(),
+        }
+    }
+
+    pub fn contains(&self, node: &K) -> bool {
+        self.nodes.contains(node)
+    }
+
+    /// Whether the path steps from `a` to `b`, or from `b` to `a` unless `directed`.
+    pub fn links(&self, a: &K, b: &K, directed: bool) -> bool {
+        self.links.contains(&(a.clone(), b.clone())) || (!directed && self.links.contains(&(b.clone(), a.clone())))
+    }
+}
+
+const HIGHLIGHT_NODE: &str = "style=filled, fillcolor=\"#ffd54f\"";
+const HIGHLIGHT_EDGE: &str = "color=\"#e53935\", penwidth=2.5";
+
+/// Builds the text of a DOT graph, quoting labels. Used by the `to_dot` methods of the structures.
+pub(crate) struct DotWriter {
+    out: String,
+    arrow: &'static str,
+}
+
+impl DotWriter {
+    pub(crate) fn new(name: &str, directed: bool) -> Self {
+        let mut out = String::new();
+        let kind = if directed { "digraph" } else { "graph" };
+        writeln!(out, "{} {} {{", kind, quote(name)).expect("writing to a String cannot fail");
+        DotWriter { out, arrow: if directed { "->" } else { "--" } }
+    }
+
+    /// A raw attribute statement such as `node [shape=box]`.
+    pub(crate) fn attr(&mut self, statement: &str) {
+        writeln!(self.out, "  {};", statement).expect("writing to a String cannot fail");
+    }
+
+    pub(crate) fn node(&mut self, id: &str, label: &str, highlighted: bool) {
+        let mut attrs = vec![format!("label={}", quote(label))];
+        if highlighted {
+            attrs.push(HIGHLIGHT_NODE.to_string());
+        }
+        writeln!(self.out, "  {} [{}];", id, attrs.join(", ")).expect("writing to a String cannot fail");
+    }
+
+    /// An invisible placeholder node, to keep a lone child on its side of a binary tree.
+    pub(crate) fn invisible(&mut self, id: &str) {
+        writeln!(self.out, "  {} [label=\"\", style=invis];", id).expect("writing to a String cannot fail");
+    }
+
+    pub(crate) fn edge(&mut self, from: &str, to: &str, label: Option<&str>, highlighted: bool) {
+        let mut attrs = Vec::new();
+        if let Some(label) = label {
+            attrs.push(format!("label={}", quote(label)));
+        }
+        if highlighted {
+            attrs.push(HIGHLIGHT_EDGE.to_string());
+        }
+        let attrs = if attrs.is_empty() { String::new() } else { format!(" [{}]", attrs.join(", ")) };
+        writeln!(self.out, "  {} {} {}{};", from, self.arrow, to, attrs).expect("writing to a String cannot fail");
+    }
+
+    pub(crate) fn invisible_edge(&mut self, from: &str, to: &str) {
+        writeln!(self.out, "  {} {} {} [style=invis];", from, self.arrow, to).expect("writing to a String cannot fail");
+    }
+
+    pub(crate) fn finish(mut self) -> String {
+        self.out.push_str("}\n");
+        self.out
+    }
+}
+
+/// A DOT string literal.
+fn quote(s: &str) -> String {
+    let mut q = String::with_capacity(s.len() + 2);
+    q.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => q.push_str("\\\""),
+            '\\' => q.push_str("\\\\"),
+            '\n' => q.push_str("\\n"),
+            c => q.push(c),
+        }
+    }
+    q.push('"');
+    q
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::monoid::Sum;
+    use crate::segment_tree::{IterativeSegmentTree, SegmentTree};
+    use crate::treap::Treap;
+    use crate::union_find::UnionFind;
+
+    #[test]
+    fn test_writer_and_highlight() {
+        let mut w = DotWriter::new("say \"hi\"", true);
+        w.node("a", "line 1\nline 2", true);
+        w.edge("a", "b", None, true);
+        w.edge("a", "c", Some("5"), false);
+        assert_eq!(
+            w.finish(),
+            "digraph \"say \\\"hi\\\"\" {\n  a [label=\"line 1\\nline 2\", style=filled, fillcolor=\"#ffd54f\"];\n  a -> b [color=\"#e53935\", penwidth=2.5];\n  a -> c [label=\"5\"];\n}\n"
+        );
+        let h = Highlight::path(&[3, 1, 4]);
+        assert!(h.contains(&1) && !h.contains(&2));
+        assert!(h.links(&4, &1, false) && !h.links(&4, &1, true));
+    }
+
+    #[test]
+    fn test_graph_and_union_find() {
+        let g = Graph::from_edges(3, [(0, 1, 2.5), (1, 2, 1.0)], true);
+        let dot = g.to_dot_highlighting(&Highlight::path(&[0, 1]));
+        assert!(dot.starts_with("digraph \"Graph\" {"));
+        assert!(dot.contains("v0 -> v1 [label=\"2.5\", color="));
+        assert!(dot.contains("v1 -> v2 [label=\"1.0\"];"));
+        // unit weights carry no information and get no label
+        let g = Graph::from_unweighted_edges(2, &[(0, 1)], false);
+        assert!(g.to_dot().contains("v0 -- v1;"));
+
+        let mut uf = UnionFind::new(4);
+        uf.union(0, 1);
+        uf.union(2, 1);
+        let dot = uf.to_dot_highlighting(&Highlight::component([0, 1, 2]));
+        assert_eq!(dot.matches("->").count(), 2);
+        assert_eq!(dot.matches("fillcolor").count(), 3);
+    }
+
+    #[test]
+    fn test_trees() {
+        let mut t = Treap::new();
+        [5, 2, 8, 2, 9].into_iter().for_each(|k| t.insert(k));
+        let path = t.search_path(9);
+        assert_eq!(path.last(), Some(&9));
+        let dot = t.to_dot_highlighting(&Highlight::path(&path));
+        assert_eq!(dot.matches("fillcolor").count(), path.len());
+        assert!(dot.contains("label=\"2 (x2)\""));
+
+        let st = SegmentTree::from_slice(&[1, 2, 3], Sum);
+        let dot = st.to_dot_highlighting(&Highlight::component([2]));
+        // 5 nodes; leaf 2 and the root lie on its update path
+        assert_eq!(dot.matches("label=").count(), 5);
+        assert!(dot.contains("label=\"[0, 3)\\n6\", style=filled"));
+        assert!(dot.contains("label=\"[2, 3)\\n3\", style=filled"));
+        let it = IterativeSegmentTree::from_slice(&[1, 2, 3], Sum);
+        let dot = it.to_dot();
+        // the padding leaf past the end is left out
+        assert_eq!(dot.matches("label=").count(), 6);
+        assert!(dot.contains("label=\"[0, 3)\\n6\""));
+        assert!(dot.contains("label=\"[2, 3)\\n3\""));
+    }
+}
@@ -0,0 +1,247 @@
+// rust/src/dynamic_segment_tree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::monoid::Monoid;
+use std::ops::{Bound, Range, RangeBounds};
+
+const NONE: usize = 0;
+
+struct Node<T> {
+    value: T,
+    // child indices into `nodes`; NONE (the root's index) marks a missing child
+    children: [usize; 2],
+}
+
+/// Segment tree over a huge implicit coordinate range such as `0..10^18` or `i64::MIN..i64::MAX`,
+/// for problems with large coordinates where a dense array is impossible and compressing the
+/// coordinates offline is inconvenient. Nodes are allocated only along the paths of updated
+/// positions; missing subtrees hold the monoid identity.
+/// - set / update / query: O(log U) for a coordinate range of size U (at most 64 levels)
+/// - Space: O(k log U) after k updates
+pub struct DynamicSegmentTree<T, Op> {
+    bounds: Range<i64>,
+    nodes: Vec<Node<T>>,
+    op: Op,
+}
+
+fn split(lo: i64, hi: i64) -> i64 {
+    // in i128 so that spans wider than i64::MAX do not overflow
+    lo + ((hi as i128 - lo as i128) / 2) as i64
+}
+
+impl<T: Clone, Op: Monoid<T>> DynamicSegmentTree<T, Op> {
+    /// Tree over the coordinates in `bounds`, all holding the identity. Panics if `bounds` is empty.
+    pub fn new(bounds: Range<i64>, op: Op) -> Self {
+        assert!(bounds.start < bounds.end, "empty coordinate range {:?}", bounds);
+        let root = Node {
+            value: op.identity(),
+            children: [NONE; 2],
+        };
+        DynamicSegmentTree { bounds, nodes: vec![root], op }
+    }
+
+    pub fn bounds(&self) -> Range<i64> {
+        self.bounds.clone()
+    }
+
+    /// Number of allocated nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn op(&self) -> &Op {
+        &self.op
+    }
+
+    fn check(&self, index: i64) {
+        assert!(self.bounds.contains(&index), "index {} out of bounds {:?}", index, self.bounds);
+    }
+
+    /// Replaces the value at `index`. Panics if `index` is outside the bounds.
+    pub fn set(&mut self, index: i64, value: T) {
+        self.update(index, |_| value);
+    }
+
+    /// Replaces the value at `index` with `f(old)`, e.g. `|v| v + 1` to count points.
+    /// Panics if `index` is outside the bounds.
+    pub fn update(&mut self, index: i64, f: impl FnOnce(&T) -> T) {
+        self.check(index);
+        // walk down, creating missing nodes, then recompute the path bottom-up
+        let mut path = Vec::with_capacity(64);
+        let (mut node, mut lo, mut hi) = (0, self.bounds.start, self.bounds.end);
+        while lo < hi - 1 {
+            path.push(node);
+            let mid = split(lo, hi);
+            let side = usize::from(index >= mid);
+            if side == 0 { hi = mid } else { lo = mid }
+            if self.nodes[node].children[side] == NONE {
+                self.nodes.push(Node {
+                    value: self.op.identity(),
+                    children: [NONE; 2],
+                });
+                self.nodes[node].children[side] = self.nodes.len() - 1;
+            }
+            node = self.nodes[node].children[side];
+        }
+        self.nodes[node].value = f(&self.nodes[node].value);
+        for &p in path.iter().rev() {
+            let value = {
+                let child = |side: usize| {
+                    let c = self.nodes[p].children[side];
+                    (c != NONE).then(|| &self.nodes[c].value)
+                };
+                match (child(0), child(1)) {
+                    (Some(a), Some(b)) => self.op.combine(a, b),
+                    (Some(v), None) | (None, Some(v)) => v.clone(),
+                    (None, None) => self.op.identity(),
+                }
+            };
+            self.nodes[p].value = value;
+        }
+    }
+
+    /// Value at `index`. Panics if `index` is outside the bounds.
+    pub fn get(&self, index: i64) -> T {
+        self.check(index);
+        let (mut node, mut lo, mut hi) = (0, self.bounds.start, self.bounds.end);
+        while lo < hi - 1 {
+            let mid = split(lo, hi);
+            let side = usize::from(index >= mid);
+            if side == 0 { hi = mid } else { lo = mid }
+            node = self.nodes[node].children[side];
+            if node == NONE {
+                return self.op.identity();
+            }
+        }
+        self.nodes[node].value.clone()
+    }
+
+    /// Combination of the values at the coordinates in `range` (half-open like slice ranges; `..`
+    /// means the whole bounds). The identity for an empty range. Panics, like `range::resolve`, if
+    /// the range is reversed or reaches outside the bounds.
+    pub fn query<R: RangeBounds<i64>>(&self, range: R) -> T {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => Some(s),
+            Bound::Excluded(&s) => s.checked_add(1),
+            Bound::Unbounded => Some(self.bounds.start),
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e.checked_add(1),
+            Bound::Excluded(&e) => Some(e),
+            Bound::Unbounded => Some(self.bounds.end),
+        };
+        let (l, r) = match (start, end) {
+            (Some(l), Some(r)) if self.bounds.start <= l && l <= r && r <= self.bounds.end => (l, r),
+            _ => panic!("range {:?}..{:?} out of bounds {:?}", start, end, self.bounds),
+        };
+        if l == r {
+            return self.op.identity();
+        }
+        self.query_rec(0, self.bounds.start, self.bounds.end, l, r)
+    }
+
+    fn query_rec(&self, node: usize, lo: i64, hi: i64, l: i64, r: i64) -> T {
+        if l <= lo && hi <= r {
+            return self.nodes[node].value.clone();
+        }
+        let mid = split(lo, hi);
+        let [left, right] = self.nodes[node].children;
+        let left = (l < mid && left != NONE).then(|| self.query_rec(left, lo, mid, l, r));
+        let right = (r > mid && right != NONE).then(|| self.query_rec(right, mid, hi, l, r));
+        match (left, right) {
+            (Some(a), Some(b)) => self.op.combine(&a, &b),
+            (Some(v), None) | (None, Some(v)) => v,
+            (None, None) => self.op.identity(),
+        }
+    }
+
+    /// Combination of all values, in O(1).
+    pub fn all(&self) -> T {
+        self.nodes[0].value.clone()
+    }
+}
+
+impl<T, Op> DataStructure for DynamicSegmentTree<T, Op> {
+    const INFO: Info = Info {
+        name: "DynamicSegmentTree",
+        category: "range queries",
+        summary: "Segment tree over a huge coordinate range with nodes created on demand (U = range size)",
+        space: "O(k log U) after k updates",
+        operations: &[
+            Operation::new("new", "O(1)", "O(1)"),
+            Operation::new("update", "O(log U)", "O(log U)"),
+            Operation::new("query", "O(log U)", "O(log U)"),
+            Operation::new("all", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::monoid::{FnMonoid, Max, Sum};
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_huge_coordinates_match_map() {
+        let mut st = DynamicSegmentTree::new(0..1_000_000_000_000_000_000, Sum);
+        let mut naive: BTreeMap<i64, i64> = BTreeMap::new();
+        let mut next = test_rng(8);
+        let mut keys = vec![];
+        for step in 0..400 {
+            let x = (next() % 1_000_000_000_000_000_000) as i64;
+            keys.push(x);
+            let delta = (step % 7) as i64 - 3;
+            st.update(x, |v| v + delta);
+            *naive.entry(x).or_default() += delta;
+        }
+        assert!(st.node_count() <= 400 * 61);
+        keys.sort();
+        for w in keys.windows(2).step_by(13) {
+            let expected: i64 = naive.range(w[0]..w[1]).map(|(_, v)| v).sum();
+            assert_eq!(st.query(w[0]..w[1]), expected);
+            assert_eq!(st.get(w[0]), naive[&w[0]]);
+        }
+        assert_eq!(st.query(..), naive.values().sum::<i64>());
+        assert_eq!(st.all(), st.query(..));
+        assert_eq!(st.get(1), naive.get(&1).copied().unwrap_or(0));
+    }
+
+    #[test]
+    fn test_full_i64_range_and_order() {
+        let mut st = DynamicSegmentTree::new(i64::MIN..i64::MAX, Max);
+        st.set(i64::MIN, 5);
+        st.set(-1, 9);
+        st.set(i64::MAX - 1, 7);
+        assert_eq!(st.query(..), 9);
+        assert_eq!(st.query(0..), 7);
+        assert_eq!(st.query(..=-2), 5);
+        assert_eq!(st.query(3..3), i64::MIN);
+
+        let concat = FnMonoid::new(String::new(), |a: &String, b: &String| format!("{}{}", a, b));
+        let mut words = DynamicSegmentTree::new(-100..100, concat);
+        for (i, w) in [(50, "c"), (-7, "a"), (0, "b")] {
+            words.set(i, w.to_string());
+        }
+        assert_eq!(words.query(..), "abc");
+        assert_eq!(words.query(-7..50), "ab");
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_out_of_bounds() {
+        DynamicSegmentTree::new(0..10, Sum).set(10, 1i64);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_reversed_range() {
+        let mut st = DynamicSegmentTree::new(0..10, Sum);
+        st.set(4, 1i64);
+        st.query(5..3);
+    }
+}
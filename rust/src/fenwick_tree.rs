@@ -1,26 +1,37 @@
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::range::{self, PointUpdate, RangeQuery};
+use std::ops::{Add, RangeBounds, Sub};
+
 /// A Binary Indexed Tree (or Fenwick Tree) supports point updates and prefix sum queries
 /// in O(log n) time. It is space efficient data structure for these operations.
-pub struct FenwickTree{
-    tree: Vec<i64>,
+///
+/// Generic over any `T: Copy + Default + Add + Sub` (default `i64`), so it also sums floats or
+/// `ModInt`s; `T::default()` is the zero and subtraction must undo addition.
+pub struct FenwickTree<T = i64>{
+    tree: Vec<T>,
 }
 
-impl FenwickTree{
+impl<T> FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
     pub fn new(size: usize) -> Self{
         FenwickTree{
-            tree: vec![0; size + 1],
+            tree: vec![T::default(); size + 1],
         }
     }
 
     /// Creates a new Fenwick Tree from an existing  array of numbers
     /// This is more efficient then creating an empty tree and adding elements one by one
-    pub fn from_vec(values: &[i64]) -> Self{
-        let mut tree = vec![0; values.len() + 1];
+    pub fn from_vec(values: &[T]) -> Self{
+        let mut tree = vec![T::default(); values.len() + 1];
         for (i, &val) in values.iter().enumerate(){
             let idx = i + 1;
-            tree[idx] += val;
+            tree[idx] = tree[idx] + val;
             let parent_idx = idx + (idx & idx.wrapping_neg());
             if parent_idx < tree.len(){
-                tree[parent_idx] += tree[idx];
+                tree[parent_idx] = tree[parent_idx] + tree[idx];
             }
         }
         FenwickTree{tree}
@@ -28,21 +39,21 @@ impl FenwickTree{
 
     /// Adds `delta` to the element at `index`
     /// The index is 0 based for the user
-    pub fn add(&mut self, mut index: usize, delta: i64){
+    pub fn add(&mut self, mut index: usize, delta: T){
         index += 1;
         while index < self.tree.len(){
-            self.tree[index] += delta;
+            self.tree[index] = self.tree[index] + delta;
             index += index & index.wrapping_neg();
         }
     }
 
     /// Queries the cumulative sum from the beginning up to the `index`
     /// The index is 0 based for the user
-    pub fn query(&self, mut index: usize) -> i64{
+    pub fn query(&self, mut index: usize) -> T{
         index += 1;
-        let mut sum = 0;
+        let mut sum = T::default();
         while index > 0{
-            sum += self.tree[index];
+            sum = sum + self.tree[index];
             index -= index & index.wrapping_neg();
         }
         sum
@@ -50,9 +61,9 @@ impl FenwickTree{
 
     /// Queries the sum of the range
     /// Both indicies are 0 based
-    pub fn range_sum(&self, start: usize, end: usize) -> i64{
+    pub fn range_sum(&self, start: usize, end: usize) -> T{
         if start > end{
-            return 0;
+            return T::default();
         }
         if start == 0{
             self.query(end)
@@ -61,17 +72,74 @@ impl FenwickTree{
         }
     }
 
+    /// Sum of the elements in `range`, using the crate's half-open convention (`2..5`, `..=3`, `..`).
+    /// Panics if the range is out of bounds, like slice indexing.
+    pub fn sum<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let r = range::resolve(range, self.len());
+        let prefix = |k: usize| if k == 0 { T::default() } else { self.query(k - 1) };
+        prefix(r.end) - prefix(r.start)
+    }
+
     /// Returns the size of the array the Fenwick Tree represents
     pub fn len(&self) -> usize {
         self.tree.len() - 1
     }
 
-    pub fn get_internal_tree(&self) -> Vec<i64> {
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn get_internal_tree(&self) -> Vec<T> {
         self.tree.clone()
     }
 
 }
 
+impl<T> DataStructure for FenwickTree<T> {
+    const INFO: Info = Info {
+        name: "FenwickTree",
+        category: "range queries",
+        summary: "Binary indexed tree for point updates and prefix sums",
+        space: "O(n)",
+        operations: &[
+            Operation::new("from_vec", "O(n)", "O(n)"),
+            Operation::new("add", "O(log n)", "O(1)"),
+            Operation::new("query", "O(log n)", "O(1)"),
+            Operation::new("range_sum", "O(log n)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl<T> RangeQuery for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    type Item = T;
+    type Output = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> T {
+        self.sum(range)
+    }
+}
+
+/// Assignment through `add` of the difference, so it costs two prefix queries more than `add`.
+impl<T> PointUpdate for FenwickTree<T>
+where
+    T: Copy + Default + Add<Output = T> + Sub<Output = T>,
+{
+    fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len(), "index {} out of bounds for length {}", index, self.len());
+        let current = self.sum(index..=index);
+        self.add(index, value - current);
+    }
+}
+
 #[cfg(test)]
 mod tests{
     use super::*;
@@ -112,8 +180,25 @@ mod tests{
         assert_eq!(ft.range_sum(5, 5), 1);
         assert_eq!(ft.range_sum(7, 6), 0);
     }
-}
-
-
 
+    #[test]
+    fn test_sum_over_ranges() {
+        let values = [3, -1, 4, 1, -5, 9];
+        let ft = FenwickTree::from_vec(&values);
+        for l in 0..=values.len() {
+            for r in l..=values.len() {
+                assert_eq!(ft.sum(l..r), values[l..r].iter().sum::<i64>());
+            }
+        }
+        assert_eq!(ft.sum(1..=3), ft.range_sum(1, 3));
+        assert_eq!(ft.sum(..=2), ft.query(2));
+        assert_eq!(ft.sum(4..), 4);
+        assert_eq!(ft.sum(..), 11);
+    }
 
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_sum_out_of_bounds() {
+        FenwickTree::<i64>::new(3).sum(0..=3);
+    }
+}
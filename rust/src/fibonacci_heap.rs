@@ -0,0 +1,405 @@
+// rust/src/fibonacci_heap.rs
+use crate::DataStructure;
+use crate::heap::{AddressableHeap, Heap, MergeableHeap};
+use crate::info::{Info, Operation};
+use crate::slab::{Handle, Slab};
+
+struct Node<T> {
+    value: T,
+    parent: Option<Handle>,
+    child: Option<Handle>,
+    // neighbours in the circular list of roots or of the parent's children
+    left: Handle,
+    right: Handle,
+    degree: usize,
+    // lost a child since it last became a child itself
+    marked: bool,
+}
+
+/// Fibonacci heap: a lazy forest of heap-ordered trees. Pushes and melds just add roots, and
+/// decrease-key cuts the node out to the root list (cascading through marked ancestors), so both are
+/// O(1) amortized; the deferred work is paid by `pop`, which consolidates roots of equal degree.
+/// This gives Dijkstra its textbook O(m + n log n) bound, though the constant factors usually make a
+/// `PairingHeap` or `DaryHeap` faster in practice; it is here mainly to benchmark that trade-off.
+/// Nodes live in a generational `Slab`, so a handle whose item has been popped or removed stays
+/// dead instead of reaching a later item stored in the same slot.
+/// - push / peek / decrease_key: O(1) amortized
+/// - pop / remove: O(log n) amortized
+/// - meld: O(m), one push per item of the other heap
+pub struct FibonacciHeap<T> {
+    nodes: Slab<Node<T>>,
+    min: Option<Handle>,
+}
+
+impl<T: Ord> Default for FibonacciHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> FibonacciHeap<T> {
+    pub fn new() -> Self {
+        FibonacciHeap { nodes: Slab::new(), min: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn value(&self, h: Handle) -> &T {
+        &self.nodes[h].value
+    }
+
+    /// Unlinks `x` from its circular list, leaving it as a one-element list.
+    fn unlink(&mut self, x: Handle) {
+        let (l, r) = (self.nodes[x].left, self.nodes[x].right);
+        self.nodes[l].right = r;
+        self.nodes[r].left = l;
+        self.nodes[x].left = x;
+        self.nodes[x].right = x;
+    }
+
+    /// Splices the one-element list `x` into the list containing `at`, right after `at`.
+    fn insert_after(&mut self, at: Handle, x: Handle) {
+        let r = self.nodes[at].right;
+        self.nodes[x].left = at;
+        self.nodes[x].right = r;
+        self.nodes[at].right = x;
+        self.nodes[r].left = x;
+    }
+
+    /// Adds the detached tree `x` to the root list and updates the minimum.
+    fn add_root(&mut self, x: Handle) {
+        self.nodes[x].parent = None;
+        self.nodes[x].marked = false;
+        match self.min {
+            Some(m) => {
+                self.insert_after(m, x);
+                if self.value(x) < self.value(m) {
+                    self.min = Some(x);
+                }
+            }
+            None => self.min = Some(x),
+        }
+    }
+
+    /// Members of the circular list containing `start`.
+    fn ring(&self, start: Handle) -> Vec<Handle> {
+        let mut out = vec![start];
+        let mut cur = self.nodes[start].right;
+        while cur != start {
+            out.push(cur);
+            cur = self.nodes[cur].right;
+        }
+        out
+    }
+
+    /// Makes root `y` a child of root `x`.
+    fn link(&mut self, y: Handle, x: Handle) {
+        self.unlink(y);
+        match self.nodes[x].child {
+            Some(c) => self.insert_after(c, y),
+            None => self.nodes[x].child = Some(y),
+        }
+        self.nodes[y].parent = Some(x);
+        self.nodes[y].marked = false;
+        self.nodes[x].degree += 1;
+    }
+
+    /// Links roots of equal degree until all degrees differ, then finds the new minimum.
+    fn consolidate(&mut self, start: Handle) {
+        let mut by_degree: Vec<Option<Handle>> = Vec::new();
+        for mut x in self.ring(start) {
+            let mut d = self.nodes[x].degree;
+            while let Some(y) = by_degree.get_mut(d).and_then(Option::take) {
+                let (parent, child) = if self.value(y) < self.value(x) { (y, x) } else { (x, y) };
+                self.link(child, parent);
+                x = parent;
+                d += 1;
+            }
+            if by_degree.len() <= d {
+                by_degree.resize(d + 1, None);
+            }
+            by_degree[d] = Some(x);
+        }
+        self.min = None;
+        for x in by_degree.into_iter().flatten() {
+            if self.min.is_none_or(|m| self.value(x) < self.value(m)) {
+                self.min = Some(x);
+            }
+        }
+    }
+
+    /// Moves child `x` of `y` to the root list.
+    fn cut(&mut self, x: Handle, y: Handle) {
+        if self.nodes[y].child == Some(x) {
+            let next = self.nodes[x].right;
+            self.nodes[y].child = (next != x).then_some(next);
+        }
+        self.unlink(x);
+        self.nodes[y].degree -= 1;
+        self.add_root(x);
+    }
+
+    /// Cuts marked ancestors of `y` until reaching an unmarked one, which gets marked.
+    fn cascading_cut(&mut self, mut y: Handle) {
+        while let Some(z) = self.nodes[y].parent {
+            if !self.nodes[y].marked {
+                self.nodes[y].marked = true;
+                break;
+            }
+            self.cut(y, z);
+            y = z;
+        }
+    }
+
+    /// Inserts `value` and returns its handle.
+    pub fn push(&mut self, value: T) -> Handle {
+        let h = self.nodes.insert_with(|h| Node {
+            value,
+            parent: None,
+            child: None,
+            left: h,
+            right: h,
+            degree: 0,
+            marked: false,
+        });
+        self.add_root(h);
+        h
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.min.map(|m| self.value(m))
+    }
+
+    /// Handle of the smallest item.
+    pub fn peek_handle(&self) -> Option<Handle> {
+        self.min
+    }
+
+    pub fn get(&self, h: Handle) -> Option<&T> {
+        self.nodes.get(h).map(|n| &n.value)
+    }
+
+    /// Removes and returns the smallest item.
+    pub fn pop(&mut self) -> Option<T> {
+        let z = self.min?;
+        if let Some(c) = self.nodes[z].child.take() {
+            for x in self.ring(c) {
+                self.unlink(x);
+                self.add_root(x);
+            }
+        }
+        let next = self.nodes[z].right;
+        self.unlink(z);
+        if next == z {
+            self.min = None;
+        } else {
+            self.consolidate(next);
+        }
+        self.nodes.remove(z).map(|n| n.value)
+    }
+
+    /// Lowers the item at `h` to `value`. Returns false (leaving the heap unchanged) if `h` is not a
+    /// live handle or `value` is greater than the current item.
+    pub fn decrease_key(&mut self, h: Handle, value: T) -> bool {
+        if self.get(h).is_none_or(|cur| value > *cur) {
+            return false;
+        }
+        self.nodes[h].value = value;
+        match self.nodes[h].parent {
+            Some(p) if self.value(h) < self.value(p) => {
+                self.cut(h, p);
+                self.cascading_cut(p);
+            }
+            _ => {
+                if self.min.is_some_and(|m| self.value(h) < self.value(m)) {
+                    self.min = Some(h);
+                }
+            }
+        }
+        true
+    }
+
+    /// Removes the item at `h` and returns it, or None if `h` is not a live handle.
+    pub fn remove(&mut self, h: Handle) -> Option<T> {
+        if !self.nodes.contains(h) {
+            return None;
+        }
+        // same as decreasing it below everything and popping
+        if let Some(p) = self.nodes[h].parent {
+            self.cut(h, p);
+            self.cascading_cut(p);
+        }
+        self.min = Some(h);
+        self.pop()
+    }
+
+    /// Moves every item of `other` into `self`, one O(1) push each. `other`'s handles do not carry
+    /// over.
+    pub fn meld(&mut self, other: FibonacciHeap<T>) {
+        for node in other.nodes.into_values() {
+            self.push(node.value);
+        }
+    }
+
+    /// Removes every item in ascending order.
+    pub fn drain_sorted(&mut self) -> Vec<T> {
+        std::iter::from_fn(|| self.pop()).collect()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for FibonacciHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = FibonacciHeap::new();
+        iter.into_iter().for_each(|v| {
+            heap.push(v);
+        });
+        heap
+    }
+}
+
+impl<T: Ord> Heap for FibonacciHeap<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+impl<T: Ord> MergeableHeap for FibonacciHeap<T> {
+    fn meld(&mut self, other: Self) {
+        self.meld(other);
+    }
+}
+
+impl<T: Ord> AddressableHeap for FibonacciHeap<T> {
+    type Handle = Handle;
+
+    fn push_handle(&mut self, item: T) -> Handle {
+        self.push(item)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, item: T) -> bool {
+        self.decrease_key(handle, item)
+    }
+}
+
+impl<T: Ord> DataStructure for FibonacciHeap<T> {
+    const INFO: Info = Info {
+        name: "FibonacciHeap",
+        category: "heaps",
+        summary: "Lazy forest min-heap with O(1) amortized push and decrease-key",
+        space: "O(n)",
+        operations: &[
+            Operation::new("push", "O(1)", "O(1)"),
+            Operation::new("peek", "O(1)", "O(1)"),
+            Operation::new("pop", "O(log n) amortized", "O(log n)"),
+            Operation::new("decrease_key", "O(1) amortized", "O(1)"),
+            Operation::new("remove", "O(log n) amortized", "O(log n)"),
+            Operation::new("meld", "O(m)", "O(1)"),
+        ],
+        stable: Some(false),
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_push_pop_sorted() {
+        let values = [7, 3, 9, 1, 4, 1, 8, 2, 6, 5, 0];
+        let mut heap: FibonacciHeap<i32> = values.into_iter().collect();
+        assert_eq!(heap.peek(), Some(&0));
+        let mut expected = values.to_vec();
+        expected.sort();
+        assert_eq!(heap.drain_sorted(), expected);
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_stale_handles() {
+        let mut heap = FibonacciHeap::new();
+        let a = heap.push(10);
+        assert_eq!(heap.pop(), Some(10));
+        // b reuses a's slot, but a must not reach it
+        let b = heap.push(50);
+        assert!(!heap.decrease_key(a, 1));
+        assert_eq!(heap.remove(a), None);
+        assert_eq!(heap.get(a), None);
+        assert_eq!(heap.get(b), Some(&50));
+        assert_eq!(heap.remove(b), Some(50));
+        assert!(heap.is_empty());
+    }
+
+    #[test]
+    fn test_decrease_key_remove_meld() {
+        let mut heap = FibonacciHeap::new();
+        let handles: Vec<Handle> = (10..30).map(|v| heap.push(v)).collect();
+        // pop once so the remaining roots are consolidated into trees
+        assert_eq!(heap.pop(), Some(10));
+        assert!(heap.decrease_key(handles[15], 1));
+        assert!(heap.decrease_key(handles[16], 2));
+        assert!(heap.decrease_key(handles[17], 3));
+        assert!(!heap.decrease_key(handles[0], 0));
+        assert!(!heap.decrease_key(handles[18], 100));
+        assert_eq!(heap.remove(handles[19]), Some(29));
+        assert_eq!(heap.remove(handles[2]), Some(12));
+        assert_eq!(heap.get(handles[3]), Some(&13));
+
+        let other: FibonacciHeap<i32> = [0, 50].into_iter().collect();
+        heap.meld(other);
+        assert_eq!(heap.peek(), Some(&0));
+        assert_eq!(heap.len(), 19);
+        let expected: Vec<i32> = [0, 1, 2, 3, 11].into_iter().chain(13..25).chain([28, 50]).collect();
+        assert_eq!(heap.drain_sorted(), expected);
+    }
+
+    #[test]
+    fn test_against_sorted_reference() {
+        let mut next = test_rng(99);
+        let mut heap = FibonacciHeap::new();
+        let mut live: Vec<(Handle, i64)> = Vec::new();
+        for step in 0..3000 {
+            match step % 5 {
+                0 | 1 => {
+                    let v = (next() % 1000) as i64;
+                    live.push((heap.push(v), v));
+                }
+                2 | 3 if !live.is_empty() => {
+                    let i = next() as usize % live.len();
+                    let v = live[i].1 - (next() % 100) as i64;
+                    assert!(heap.decrease_key(live[i].0, v));
+                    live[i].1 = v;
+                }
+                _ => {
+                    let min = live.iter().map(|&(_, v)| v).min();
+                    if let Some(h) = heap.peek_handle() {
+                        live.retain(|&(lh, _)| lh != h);
+                    }
+                    assert_eq!(heap.pop(), min);
+                }
+            }
+            assert_eq!(heap.len(), live.len());
+        }
+    }
+}
@@ -0,0 +1,558 @@
+// rust/src/finger_tree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::fmt;
+use std::rc::Rc;
+
+type Link<T> = Rc<Node<T>>;
+
+enum Node<T> {
+    Leaf(T),
+    // two or three children one level down, with the number of leaves below
+    Branch(usize, Vec<Link<T>>),
+}
+
+fn weight<T>(node: &Node<T>) -> usize {
+    match node {
+        Node::Leaf(_) => 1,
+        Node::Branch(size, _) => *size,
+    }
+}
+
+fn total<T>(nodes: &[Link<T>]) -> usize {
+    nodes.iter().map(|n| weight(n)).sum()
+}
+
+fn branch<T>(children: Vec<Link<T>>) -> Link<T> {
+    Rc::new(Node::Branch(total(&children), children))
+}
+
+fn children<T>(node: &Node<T>) -> &[Link<T>] {
+    match node {
+        Node::Branch(_, children) => children,
+        Node::Leaf(_) => unreachable!("only the top level holds leaves"),
+    }
+}
+
+fn value<T>(node: &Node<T>) -> &T {
+    match node {
+        Node::Leaf(value) => value,
+        Node::Branch(..) => unreachable!("the top level holds only leaves"),
+    }
+}
+
+/// The node holding leaf `*i` of `nodes`; `*i` becomes the offset inside it.
+fn find<'a, T>(nodes: &'a [Link<T>], i: &mut usize) -> &'a Link<T> {
+    for node in nodes {
+        let w = weight(node);
+        if *i < w {
+            return node;
+        }
+        *i -= w;
+    }
+    unreachable!("index within the nodes' total weight")
+}
+
+/// Splits `nodes` around the node holding leaf `i`.
+fn split_nodes<T>(nodes: &[Link<T>], mut i: usize) -> (&[Link<T>], Link<T>, &[Link<T>]) {
+    for (k, node) in nodes.iter().enumerate() {
+        let w = weight(node);
+        if i < w {
+            return (&nodes[..k], node.clone(), &nodes[k + 1..]);
+        }
+        i -= w;
+    }
+    unreachable!("index within the nodes' total weight")
+}
+
+/// Packs 2 to 12 nodes into branches of three, using twos only at the end.
+fn group<T>(mut nodes: &[Link<T>]) -> Vec<Link<T>> {
+    let mut out = Vec::new();
+    while nodes.len() > 4 {
+        out.push(branch(nodes[..3].to_vec()));
+        nodes = &nodes[3..];
+    }
+    if nodes.len() == 4 {
+        out.extend([branch(nodes[..2].to_vec()), branch(nodes[2..].to_vec())]);
+    } else {
+        out.push(branch(nodes.to_vec()));
+    }
+    out
+}
+
+// One level of the spine. The top level holds leaves, and each `middle` holds branches one level
+// deeper than its parent's, so the types are checked at runtime rather than by nesting generics.
+enum Tree<T> {
+    Empty,
+    Single(Link<T>),
+    // the digits hold one to four nodes each
+    Deep { size: usize, prefix: Vec<Link<T>>, middle: Rc<Tree<T>>, suffix: Vec<Link<T>> },
+}
+
+impl<T> Clone for Tree<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Tree::Empty => Tree::Empty,
+            Tree::Single(node) => Tree::Single(node.clone()),
+            Tree::Deep { size, prefix, middle, suffix } => Tree::Deep {
+                size: *size,
+                prefix: prefix.clone(),
+                middle: middle.clone(),
+                suffix: suffix.clone(),
+            },
+        }
+    }
+}
+
+impl<T> Tree<T> {
+    fn size(&self) -> usize {
+        match self {
+            Tree::Empty => 0,
+            Tree::Single(node) => weight(node),
+            Tree::Deep { size, .. } => *size,
+        }
+    }
+
+    fn deep(prefix: Vec<Link<T>>, middle: Rc<Tree<T>>, suffix: Vec<Link<T>>) -> Tree<T> {
+        let size = total(&prefix) + middle.size() + total(&suffix);
+        Tree::Deep { size, prefix, middle, suffix }
+    }
+
+    fn from_nodes(nodes: &[Link<T>]) -> Tree<T> {
+        nodes.iter().fold(Tree::Empty, |tree, node| tree.push_back(node.clone()))
+    }
+
+    /// A deep tree whose prefix may be empty, refilled from the middle.
+    fn deep_left(prefix: Vec<Link<T>>, middle: Rc<Tree<T>>, suffix: Vec<Link<T>>) -> Tree<T> {
+        if !prefix.is_empty() {
+            return Tree::deep(prefix, middle, suffix);
+        }
+        match middle.pop_front() {
+            None => Tree::from_nodes(&suffix),
+            Some((node, rest)) => Tree::deep(children(&node).to_vec(), Rc::new(rest), suffix),
+        }
+    }
+
+    /// A deep tree whose suffix may be empty, refilled from the middle.
+    fn deep_right(prefix: Vec<Link<T>>, middle: Rc<Tree<T>>, suffix: Vec<Link<T>>) -> Tree<T> {
+        if !suffix.is_empty() {
+            return Tree::deep(prefix, middle, suffix);
+        }
+        match middle.pop_back() {
+            None => Tree::from_nodes(&prefix),
+            Some((node, rest)) => Tree::deep(prefix, Rc::new(rest), children(&node).to_vec()),
+        }
+    }
+
+    fn push_front(&self, node: Link<T>) -> Tree<T> {
+        match self {
+            Tree::Empty => Tree::Single(node),
+            Tree::Single(other) => Tree::deep(vec![node], Rc::new(Tree::Empty), vec![other.clone()]),
+            Tree::Deep { prefix, middle, suffix, .. } if prefix.len() == 4 => {
+                // a full digit keeps one node and pushes the other three down as a branch
+                let middle = middle.push_front(branch(prefix[1..].to_vec()));
+                Tree::deep(vec![node, prefix[0].clone()], Rc::new(middle), suffix.clone())
+            }
+            Tree::Deep { prefix, middle, suffix, .. } => {
+                let prefix = std::iter::once(node).chain(prefix.iter().cloned()).collect();
+                Tree::deep(prefix, middle.clone(), suffix.clone())
+            }
+        }
+    }
+
+    fn push_back(&self, node: Link<T>) -> Tree<T> {
+        match self {
+            Tree::Empty => Tree::Single(node),
+            Tree::Single(other) => Tree::deep(vec![other.clone()], Rc::new(Tree::Empty), vec![node]),
+            Tree::Deep { prefix, middle, suffix, .. } if suffix.len() == 4 => {
+                let middle = middle.push_back(branch(suffix[..3].to_vec()));
+                Tree::deep(prefix.clone(), Rc::new(middle), vec![suffix[3].clone(), node])
+            }
+            Tree::Deep { prefix, middle, suffix, .. } => {
+                let suffix = suffix.iter().cloned().chain(std::iter::once(node)).collect();
+                Tree::deep(prefix.clone(), middle.clone(), suffix)
+            }
+        }
+    }
+
+    fn pop_front(&self) -> Option<(Link<T>, Tree<T>)> {
+        match self {
+            Tree::Empty => None,
+            Tree::Single(node) => Some((node.clone(), Tree::Empty)),
+            Tree::Deep { prefix, middle, suffix, .. } => {
+                let rest = Tree::deep_left(prefix[1..].to_vec(), middle.clone(), suffix.clone());
+                Some((prefix[0].clone(), rest))
+            }
+        }
+    }
+
+    fn pop_back(&self) -> Option<(Link<T>, Tree<T>)> {
+        match self {
+            Tree::Empty => None,
+            Tree::Single(node) => Some((node.clone(), Tree::Empty)),
+            Tree::Deep { prefix, middle, suffix, .. } => {
+                let rest = Tree::deep_right(prefix.clone(), middle.clone(), suffix[..suffix.len() - 1].to_vec());
+                Some((suffix[suffix.len() - 1].clone(), rest))
+            }
+        }
+    }
+
+    /// `left`, then the loose `nodes`, then `right`, all of the same level.
+    fn concat(left: &Tree<T>, nodes: Vec<Link<T>>, right: &Tree<T>) -> Tree<T> {
+        match (left, right) {
+            (Tree::Empty, _) => nodes.into_iter().rev().fold(right.clone(), |tree, node| tree.push_front(node)),
+            (_, Tree::Empty) => nodes.into_iter().fold(left.clone(), |tree, node| tree.push_back(node)),
+            (Tree::Single(node), _) => Tree::concat(&Tree::Empty, nodes, right).push_front(node.clone()),
+            (_, Tree::Single(node)) => Tree::concat(left, nodes, &Tree::Empty).push_back(node.clone()),
+            (
+                Tree::Deep { prefix, middle: left_middle, suffix: inner_left, .. },
+                Tree::Deep { prefix: inner_right, middle: right_middle, suffix, .. },
+            ) => {
+                // the inner digits and the loose nodes, 2 to 12 of them, sink a level as branches
+                let inner: Vec<Link<T>> = inner_left.iter().cloned().chain(nodes).chain(inner_right.iter().cloned()).collect();
+                let middle = Tree::concat(left_middle, group(&inner), right_middle);
+                Tree::deep(prefix.clone(), Rc::new(middle), suffix.clone())
+            }
+        }
+    }
+
+    /// Splits around the node holding leaf `i`, which must be below `size()`.
+    fn split(&self, i: usize) -> (Tree<T>, Link<T>, Tree<T>) {
+        match self {
+            Tree::Empty => unreachable!("split of an empty tree"),
+            Tree::Single(node) => (Tree::Empty, node.clone(), Tree::Empty),
+            Tree::Deep { prefix, middle, suffix, .. } => {
+                let before = total(prefix);
+                if i < before {
+                    let (l, node, r) = split_nodes(prefix, i);
+                    return (Tree::from_nodes(l), node, Tree::deep_left(r.to_vec(), middle.clone(), suffix.clone()));
+                }
+                let i = i - before;
+                if i < middle.size() {
+                    // split the middle around a branch, then the branch around the node
+                    let (ml, node, mr) = middle.split(i);
+                    let (l, node, r) = split_nodes(children(&node), i - ml.size());
+                    let left = Tree::deep_right(prefix.clone(), Rc::new(ml), l.to_vec());
+                    return (left, node, Tree::deep_left(r.to_vec(), Rc::new(mr), suffix.clone()));
+                }
+                let (l, node, r) = split_nodes(suffix, i - middle.size());
+                (Tree::deep_right(prefix.clone(), middle.clone(), l.to_vec()), node, Tree::from_nodes(r))
+            }
+        }
+    }
+}
+
+/// Persistent sequence as a 2-3 finger tree (Hinze and Paterson) annotated with sizes. The tree is a
+/// spine of levels: each keeps one to four nodes at either end (the fingers) and a middle tree of
+/// 2-3 branches one level deeper, so the ends are always a few pointers away. Nodes are immutable
+/// and reference counted: `clone` is an O(1) snapshot, and every update rebuilds only the spine
+/// levels it touches and shares the rest. Pushing onto a full finger moves three nodes down as one
+/// branch, which is why the ends cost O(1) amortized; joining two trees packs the inner fingers into
+/// branches level by level, and the cached sizes guide indexing and splitting.
+/// - push_front / push_back / pop_front / pop_back: O(1) amortized, O(log n) worst case
+/// - front / back / clone: O(1)
+/// - get: O(log n)
+/// - append / concat: O(log min(n, m))
+/// - split_at / split_off: O(log n)
+pub struct FingerTree<T> {
+    tree: Tree<T>,
+}
+
+impl<T> Clone for FingerTree<T> {
+    fn clone(&self) -> Self {
+        FingerTree { tree: self.tree.clone() }
+    }
+}
+
+impl<T> Default for FingerTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> FingerTree<T> {
+    pub fn new() -> Self {
+        FingerTree { tree: Tree::Empty }
+    }
+
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        match &self.tree {
+            Tree::Empty => None,
+            Tree::Single(node) => Some(value(node)),
+            Tree::Deep { prefix, .. } => Some(value(&prefix[0])),
+        }
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        match &self.tree {
+            Tree::Empty => None,
+            Tree::Single(node) => Some(value(node)),
+            Tree::Deep { suffix, .. } => Some(value(&suffix[suffix.len() - 1])),
+        }
+    }
+
+    pub fn get(&self, mut i: usize) -> Option<&T> {
+        if i >= self.len() {
+            return None;
+        }
+        // walk down the spine to the level whose node holds `i`, then down that node
+        let mut tree = &self.tree;
+        let mut node = loop {
+            match tree {
+                Tree::Empty => unreachable!("index below the size"),
+                Tree::Single(node) => break node,
+                Tree::Deep { prefix, middle, suffix, .. } => {
+                    let before = total(prefix);
+                    if i < before {
+                        break find(prefix, &mut i);
+                    }
+                    i -= before;
+                    if i < middle.size() {
+                        tree = middle;
+                        continue;
+                    }
+                    i -= middle.size();
+                    break find(suffix, &mut i);
+                }
+            }
+        };
+        loop {
+            match &**node {
+                Node::Leaf(value) => return Some(value),
+                Node::Branch(_, children) => node = find(children, &mut i),
+            }
+        }
+    }
+
+    pub fn push_front(&mut self, value: T) {
+        self.tree = self.tree.push_front(Rc::new(Node::Leaf(value)));
+    }
+
+    pub fn push_back(&mut self, value: T) {
+        self.tree = self.tree.push_back(Rc::new(Node::Leaf(value)));
+    }
+
+    /// Appends a snapshot of `other`; both trees keep sharing its nodes.
+    pub fn append(&mut self, other: &FingerTree<T>) {
+        self.tree = Tree::concat(&self.tree, Vec::new(), &other.tree);
+    }
+
+    /// Persistent append: a new tree with `self` followed by `other`, leaving both intact.
+    pub fn concat(&self, other: &FingerTree<T>) -> FingerTree<T> {
+        FingerTree { tree: Tree::concat(&self.tree, Vec::new(), &other.tree) }
+    }
+
+    /// The first `at` elements and the rest, as two new trees. Panics if `at > len`.
+    pub fn split_at(&self, at: usize) -> (FingerTree<T>, FingerTree<T>) {
+        assert!(at <= self.len(), "split index {} out of bounds for length {}", at, self.len());
+        if at == self.len() {
+            return (self.clone(), FingerTree::new());
+        }
+        let (left, node, right) = self.tree.split(at);
+        (FingerTree { tree: left }, FingerTree { tree: right.push_front(node) })
+    }
+
+    /// Keeps the first `at` elements and returns the rest. Panics if `at > len`.
+    pub fn split_off(&mut self, at: usize) -> FingerTree<T> {
+        let (left, right) = self.split_at(at);
+        *self = left;
+        right
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        enum Frame<'a, T> {
+            Tree(&'a Tree<T>),
+            Node(&'a Node<T>),
+        }
+        let mut stack = vec![Frame::Tree(&self.tree)];
+        std::iter::from_fn(move || {
+            while let Some(frame) = stack.pop() {
+                match frame {
+                    Frame::Tree(Tree::Empty) => {}
+                    Frame::Tree(Tree::Single(node)) => stack.push(Frame::Node(node)),
+                    Frame::Tree(Tree::Deep { prefix, middle, suffix, .. }) => {
+                        stack.extend(suffix.iter().rev().map(|n| Frame::Node(n)));
+                        stack.push(Frame::Tree(middle));
+                        stack.extend(prefix.iter().rev().map(|n| Frame::Node(n)));
+                    }
+                    Frame::Node(Node::Leaf(value)) => return Some(value),
+                    Frame::Node(Node::Branch(_, children)) => stack.extend(children.iter().rev().map(|n| Frame::Node(n))),
+                }
+            }
+            None
+        })
+    }
+}
+
+impl<T: Clone> FingerTree<T> {
+    /// Removes the first element, cloning it only if another version still shares it.
+    pub fn pop_front(&mut self) -> Option<T> {
+        let (node, rest) = self.tree.pop_front()?;
+        self.tree = rest;
+        Some(Self::take(node))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        let (node, rest) = self.tree.pop_back()?;
+        self.tree = rest;
+        Some(Self::take(node))
+    }
+
+    fn take(node: Link<T>) -> T {
+        match Rc::try_unwrap(node) {
+            Ok(Node::Leaf(value)) => value,
+            Ok(Node::Branch(..)) => unreachable!("the top level holds only leaves"),
+            Err(shared) => value(&shared).clone(),
+        }
+    }
+}
+
+impl<T> FromIterator<T> for FingerTree<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut tree = FingerTree::new();
+        iter.into_iter().for_each(|value| tree.push_back(value));
+        tree
+    }
+}
+
+impl<T: PartialEq> PartialEq for FingerTree<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for FingerTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> DataStructure for FingerTree<T> {
+    const INFO: Info = Info {
+        name: "FingerTree",
+        category: "persistent",
+        summary: "Persistent 2-3 finger tree deque with sizes: O(1) amortized ends, O(log n) concat and split",
+        space: "O(n), plus O(log n) new nodes per update",
+        operations: &[
+            Operation::new("push / pop (either end)", "O(1) amortized, O(log n) worst", "O(1) amortized"),
+            Operation::new("front / back", "O(1)", "O(1)"),
+            Operation::new("get", "O(log n)", "O(1)"),
+            Operation::new("concat", "O(log min(n, m))", "O(log min(n, m))"),
+            Operation::new("split_at", "O(log n)", "O(log n)"),
+            Operation::new("clone", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    // digit sizes, branch arities, cached sizes and uniform leaf depth; returns the leaf count
+    fn check_tree<T>(tree: &Tree<T>, depth: usize) -> usize {
+        fn check_node<T>(node: &Node<T>, depth: usize) -> usize {
+            match node {
+                Node::Leaf(_) => {
+                    assert_eq!(depth, 0);
+                    1
+                }
+                Node::Branch(size, children) => {
+                    assert!((2..=3).contains(&children.len()));
+                    assert_eq!(*size, children.iter().map(|c| check_node(c, depth - 1)).sum::<usize>());
+                    *size
+                }
+            }
+        }
+        match tree {
+            Tree::Empty => 0,
+            Tree::Single(node) => check_node(node, depth),
+            Tree::Deep { size, prefix, middle, suffix } => {
+                assert!((1..=4).contains(&prefix.len()) && (1..=4).contains(&suffix.len()));
+                let digits: usize = prefix.iter().chain(suffix).map(|n| check_node(n, depth)).sum();
+                assert_eq!(*size, digits + check_tree(middle, depth + 1));
+                *size
+            }
+        }
+    }
+
+    #[test]
+    fn test_deque_concat_and_split() {
+        let mut deque = FingerTree::new();
+        for i in 0..10 {
+            deque.push_back(i);
+            deque.push_front(-i - 1);
+        }
+        let snapshot = deque.clone();
+        assert_eq!((deque.front(), deque.back(), deque.len()), (Some(&-10), Some(&9), 20));
+        assert_eq!(deque.pop_front(), Some(-10));
+        assert_eq!(deque.pop_back(), Some(9));
+        assert_eq!(snapshot.iter().copied().collect::<Vec<_>>(), (-10..10).collect::<Vec<_>>());
+        assert_eq!(snapshot.get(12), Some(&2));
+
+        let joined = snapshot.concat(&(100..150).collect());
+        assert_eq!(joined.len(), 70);
+        assert_eq!(joined.get(20), Some(&100));
+        let (left, right) = joined.split_at(25);
+        assert_eq!((left.back(), right.front(), right.len()), (Some(&104), Some(&105), 45));
+        assert_eq!(left.concat(&right), joined);
+        check_tree(&joined.tree, 0);
+
+        let mut empty = FingerTree::<u8>::new();
+        assert_eq!((empty.pop_front(), empty.front(), empty.get(0)), (None, None, None));
+        assert!(empty.split_off(0).is_empty());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(131);
+        let mut versions: Vec<(FingerTree<usize>, Vec<usize>)> = vec![(FingerTree::new(), Vec::new())];
+        for round in 0..3000 {
+            let (tree, naive) = &versions[next() as usize % versions.len()];
+            let (mut tree, mut naive) = (tree.clone(), naive.clone());
+            match next() % 8 {
+                0 | 1 => {
+                    tree.push_back(round);
+                    naive.push(round);
+                }
+                2 | 3 => {
+                    tree.push_front(round);
+                    naive.insert(0, round);
+                }
+                4 => assert_eq!(tree.pop_front(), (!naive.is_empty()).then(|| naive.remove(0))),
+                5 => assert_eq!(tree.pop_back(), naive.pop()),
+                6 => {
+                    let (other, other_naive) = &versions[next() as usize % versions.len()];
+                    tree.append(other);
+                    naive.extend(other_naive);
+                }
+                _ => {
+                    let at = next() as usize % (naive.len() + 1);
+                    let right = tree.split_off(at);
+                    assert_eq!(right.iter().copied().collect::<Vec<_>>(), naive.split_off(at));
+                    check_tree(&right.tree, 0);
+                }
+            }
+            assert_eq!(check_tree(&tree.tree, 0), naive.len());
+            assert_eq!(tree.iter().copied().collect::<Vec<_>>(), naive);
+            if !naive.is_empty() {
+                let i = next() as usize % naive.len();
+                assert_eq!(tree.get(i), Some(&naive[i]));
+            }
+            // keep the versions from growing without bound through repeated appends
+            if naive.len() < 2000 {
+                versions.push((tree, naive));
+            }
+        }
+    }
+}
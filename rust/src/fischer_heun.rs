@@ -0,0 +1,219 @@
+// rust/src/fischer_heun.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::range::{self, RangeQuery};
+use std::collections::HashMap;
+use std::ops::RangeBounds;
+
+/// Range-minimum queries in O(1) after O(n) preprocessing (Fischer and Heun). The array is cut
+/// into blocks of b = log2(n) / 4 elements; a sparse table over the block minima answers the part
+/// of a query spanning whole blocks, and the at most two partial blocks at the ends are answered
+/// by lookup tables. Two blocks have the same in-block argmins exactly when they have the same
+/// Cartesian tree, which the push / pop sequence of a monotonic stack over the block encodes in
+/// 2b bits, so there are at most 4^b = n^(1/2) distinct tables of b^2 entries each, built once per
+/// shape actually seen. The summary table holds n / b log n positions, so the whole index is O(n)
+/// words where `SparseTable` needs n log2 n values. Ties go to the leftmost minimum.
+/// - Build: O(n)
+/// - Query: O(1)
+pub struct FischerHeun<T> {
+    values: Vec<T>,
+    block: usize,
+    // per block, its Cartesian tree shape as an index into `tables`
+    block_type: Vec<usize>,
+    // per shape, the offset of the leftmost minimum of in-block range [l, r] at `l * block + r`
+    tables: Vec<Vec<u8>>,
+    // summary[k][i]: position of the leftmost minimum of blocks i..i + 2^k
+    summary: Vec<Vec<usize>>,
+}
+
+impl<T: Copy + Ord> FischerHeun<T> {
+    pub fn from_slice(arr: &[T]) -> Self {
+        let n = arr.len();
+        let block = ((usize::BITS - n.leading_zeros()) as usize / 4).max(1);
+        let blocks = n.div_ceil(block);
+        let mut shapes: HashMap<u64, usize> = HashMap::new();
+        let mut tables: Vec<Vec<u8>> = Vec::new();
+        let mut block_type = Vec::with_capacity(blocks);
+        let mut minima = Vec::with_capacity(blocks);
+        let mut stack: Vec<T> = Vec::with_capacity(block);
+        for chunk in arr.chunks(block) {
+            // a pop writes 0 and a push writes 1; the leading push keeps shorter codes distinct
+            let mut code = 0u64;
+            stack.clear();
+            for &x in chunk {
+                while let Some(&top) = stack.last()
+                    && top > x
+                {
+                    stack.pop();
+                    code <<= 1;
+                }
+                stack.push(x);
+                code = code << 1 | 1;
+            }
+            let shape = *shapes.entry(code).or_insert_with(|| {
+                tables.push(Self::block_table(chunk, block));
+                tables.len() - 1
+            });
+            minima.push(block_type.len() * block + tables[shape][chunk.len() - 1] as usize);
+            block_type.push(shape);
+        }
+        let mut fh = FischerHeun { values: arr.to_vec(), block, block_type, tables, summary: vec![minima] };
+        let mut k = 1;
+        while 1 << k <= blocks {
+            let prev = &fh.summary[k - 1];
+            let row = (0..=blocks - (1 << k)).map(|i| fh.leftmost_min(prev[i], prev[i + (1 << (k - 1))])).collect();
+            fh.summary.push(row);
+            k += 1;
+        }
+        fh
+    }
+
+    /// Leftmost-minimum offsets for every range inside one block.
+    fn block_table(chunk: &[T], block: usize) -> Vec<u8> {
+        let mut table = vec![0; block * block];
+        for l in 0..chunk.len() {
+            let mut best = l;
+            for r in l..chunk.len() {
+                if chunk[r] < chunk[best] {
+                    best = r;
+                }
+                table[l * block + r] = best as u8;
+            }
+        }
+        table
+    }
+
+    fn leftmost_min(&self, a: usize, b: usize) -> usize {
+        let (va, vb) = (self.values[a], self.values[b]);
+        if vb < va || (vb == va && b < a) { b } else { a }
+    }
+
+    fn in_block(&self, b: usize, lo: usize, hi: usize) -> usize {
+        b * self.block + self.tables[self.block_type[b]][lo * self.block + hi] as usize
+    }
+
+    /// Position of the leftmost minimum of [l, r] inclusive. Returns None if l or r out of bounds
+    /// or l > r.
+    pub fn argmin_inclusive(&self, l: usize, r: usize) -> Option<usize> {
+        if l > r || r >= self.values.len() {
+            return None;
+        }
+        let b = self.block;
+        let (bl, br) = (l / b, r / b);
+        if bl == br {
+            return Some(self.in_block(bl, l % b, r % b));
+        }
+        let mut best = self.in_block(bl, l % b, b - 1);
+        if bl + 1 < br {
+            let (x, y) = (bl + 1, br - 1);
+            let k = (y - x + 1).ilog2() as usize;
+            let whole = self.leftmost_min(self.summary[k][x], self.summary[k][y + 1 - (1 << k)]);
+            best = self.leftmost_min(best, whole);
+        }
+        Some(self.leftmost_min(best, self.in_block(br, 0, r % b)))
+    }
+
+    /// Query range [l, r] inclusive. Returns None if l or r out of bounds or l > r.
+    pub fn query(&self, l: usize, r: usize) -> Option<T> {
+        self.argmin_inclusive(l, r).map(|i| self.values[i])
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Position of the leftmost minimum over `range` (half-open, as in `range::resolve`). Returns
+    /// None for an empty range and panics if the range is out of bounds.
+    pub fn argmin<R: RangeBounds<usize>>(&self, range: R) -> Option<usize> {
+        let r = range::resolve(range, self.len());
+        if r.is_empty() { None } else { self.argmin_inclusive(r.start, r.end - 1) }
+    }
+
+    /// Minimum over `range`, using the crate's half-open convention (`2..5`, `..=3`, `..`).
+    /// Returns None for an empty range and panics if the range is out of bounds.
+    pub fn min<R: RangeBounds<usize>>(&self, range: R) -> Option<T> {
+        self.argmin(range).map(|i| self.values[i])
+    }
+
+    /// Heap bytes of the index (summary table and block tables), not counting the copy of the values.
+    pub fn index_bytes(&self) -> usize {
+        let summary: usize = self.summary.iter().map(|row| row.len() * size_of::<usize>()).sum();
+        let tables: usize = self.tables.iter().map(Vec::len).sum();
+        summary + tables + self.block_type.len() * size_of::<usize>()
+    }
+}
+
+impl<T: Copy + Ord> DataStructure for FischerHeun<T> {
+    const INFO: Info = Info {
+        name: "FischerHeun",
+        category: "range queries",
+        summary: "Static range-minimum in O(1) with linear preprocessing: block summary plus Cartesian-tree lookup tables",
+        space: "O(n)",
+        operations: &[
+            Operation::new("from_slice", "O(n)", "O(n)"),
+            Operation::new("query", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+/// Static, so it implements `RangeQuery` but not `PointUpdate`.
+impl<T: Copy + Ord> RangeQuery for FischerHeun<T> {
+    type Item = T;
+    type Output = Option<T>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> Option<T> {
+        self.min(range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::sparse_table::SparseTable;
+
+    #[test]
+    fn test_queries_and_ties() {
+        let v = [5, 2, 8, 2, 9, 1, 7, 1, 3, 6, 4, 0, 8, 5, 2, 7, 3];
+        let rmq = FischerHeun::from_slice(&v);
+        assert_eq!(rmq.query(0, 4), Some(2));
+        assert_eq!(rmq.argmin(0..5), Some(1));
+        assert_eq!(rmq.argmin(4..11), Some(5));
+        assert_eq!(rmq.min(..), Some(0));
+        assert_eq!(rmq.min(12..), Some(2));
+        assert_eq!((rmq.min(3..3), rmq.query(5, 17), rmq.query(4, 3)), (None, None, None));
+        assert_eq!(rmq.query_range(6..9), Some(1));
+
+        let empty = FischerHeun::<i32>::from_slice(&[]);
+        assert_eq!((empty.len(), empty.min(..)), (0, None));
+        let one = FischerHeun::from_slice(&["only"]);
+        assert_eq!(one.min(0..1), Some("only"));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(151);
+        for n in [1, 2, 3, 7, 16, 17, 100, 1000, 5000] {
+            let v: Vec<u32> = (0..n).map(|_| (next() % 20) as u32).collect();
+            let rmq = FischerHeun::from_slice(&v);
+            let table = SparseTable::from_slice(&v);
+            for _ in 0..500 {
+                let (a, b) = (next() as usize % n, next() as usize % n);
+                let (l, r) = (a.min(b), a.max(b));
+                let expected = (l..=r).min_by_key(|&i| v[i]);
+                assert_eq!(rmq.argmin_inclusive(l, r), expected);
+                assert_eq!(rmq.query(l, r), table.query(l, r));
+            }
+        }
+    }
+}
@@ -0,0 +1,428 @@
+// rust/src/fixed.rs
+//! Fixed-capacity structures whose storage is an inline array sized by a const generic.
+//! They never allocate, so they fit on the stack or in statics and have no allocation cost per
+//! operation. Operations that would exceed the capacity return the rejected item as `Err`.
+
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::range::{self, PointUpdate, RangeQuery};
+use core::ops::RangeBounds;
+
+/// Fenwick tree over exactly `N` elements, with the same 0-based API as `FenwickTree`.
+/// - add / query / range_sum: O(log N)
+pub struct FixedFenwick<const N: usize> {
+    // tree[i - 1] holds the classic 1-based node i
+    tree: [i64; N],
+}
+
+impl<const N: usize> Default for FixedFenwick<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> FixedFenwick<N> {
+    pub const fn new() -> Self {
+        FixedFenwick { tree: [0; N] }
+    }
+
+    /// Builds the tree in O(N) from initial values.
+    pub fn from_array(values: [i64; N]) -> Self {
+        let mut tree = values;
+        for idx in 1..=N {
+            let parent = idx + (idx & idx.wrapping_neg());
+            if parent <= N {
+                tree[parent - 1] += tree[idx - 1];
+            }
+        }
+        FixedFenwick { tree }
+    }
+
+    /// Adds `delta` to the element at `index`. Panics if `index >= N`.
+    pub fn add(&mut self, index: usize, delta: i64) {
+        assert!(index < N, "index out of bounds");
+        let mut idx = index + 1;
+        while idx <= N {
+            self.tree[idx - 1] += delta;
+            idx += idx & idx.wrapping_neg();
+        }
+    }
+
+    /// Sum of elements `0..=index`. Panics if `index >= N`.
+    pub fn query(&self, index: usize) -> i64 {
+        assert!(index < N, "index out of bounds");
+        let mut idx = index + 1;
+        let mut sum = 0;
+        while idx > 0 {
+            sum += self.tree[idx - 1];
+            idx -= idx & idx.wrapping_neg();
+        }
+        sum
+    }
+
+    /// Sum of elements `start..=end`, 0 if `start > end`.
+    pub fn range_sum(&self, start: usize, end: usize) -> i64 {
+        if start > end {
+            return 0;
+        }
+        self.query(end) - if start == 0 { 0 } else { self.query(start - 1) }
+    }
+
+    /// Sum of the elements in `range` (half-open, see `range::resolve`). Panics if out of bounds.
+    pub fn sum<R: RangeBounds<usize>>(&self, range: R) -> i64 {
+        let r = range::resolve(range, N);
+        let prefix = |k: usize| if k == 0 { 0 } else { self.query(k - 1) };
+        prefix(r.end) - prefix(r.start)
+    }
+
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+/// Binary min-heap holding at most `N` items.
+/// - push / pop: O(log N)
+/// - peek: O(1)
+pub struct FixedHeap<T, const N: usize> {
+    data: [Option<T>; N],
+    len: usize,
+}
+
+impl<T: Ord, const N: usize> Default for FixedHeap<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord, const N: usize> FixedHeap<T, N> {
+    pub const fn new() -> Self {
+        FixedHeap {
+            data: [const { None }; N],
+            len: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn at(&self, i: usize) -> &T {
+        self.data[i].as_ref().expect("slots below len are filled")
+    }
+
+    /// Inserts `item`, or hands it back if the heap is full.
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+        let mut i = self.len;
+        self.data[i] = Some(item);
+        self.len += 1;
+        while i > 0 && self.at(i) < self.at((i - 1) / 2) {
+            self.data.swap(i, (i - 1) / 2);
+            i = (i - 1) / 2;
+        }
+        Ok(())
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data[..self.len].first().and_then(Option::as_ref)
+    }
+
+    /// Removes and returns the smallest item.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        self.data.swap(0, self.len);
+        let top = self.data[self.len].take();
+        let mut i = 0;
+        loop {
+            let (l, r) = (2 * i + 1, 2 * i + 2);
+            let mut smallest = i;
+            if l < self.len && self.at(l) < self.at(smallest) {
+                smallest = l;
+            }
+            if r < self.len && self.at(r) < self.at(smallest) {
+                smallest = r;
+            }
+            if smallest == i {
+                break;
+            }
+            self.data.swap(i, smallest);
+            i = smallest;
+        }
+        top
+    }
+}
+
+/// Double-ended ring buffer holding at most `N` items.
+/// - push / pop at either end, get: O(1)
+pub struct FixedRing<T, const N: usize> {
+    data: [Option<T>; N],
+    head: usize,
+    len: usize,
+}
+
+impl<T, const N: usize> Default for FixedRing<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> FixedRing<T, N> {
+    pub const fn new() -> Self {
+        FixedRing {
+            data: [const { None }; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub const fn is_full(&self) -> bool {
+        self.len == N
+    }
+
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn slot(&self, i: usize) -> usize {
+        (self.head + i) % N
+    }
+
+    /// Appends at the back, or hands the item back if the buffer is full.
+    pub fn push_back(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+        let s = self.slot(self.len);
+        self.data[s] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Prepends at the front, or hands the item back if the buffer is full.
+    pub fn push_front(&mut self, item: T) -> Result<(), T> {
+        if self.is_full() {
+            return Err(item);
+        }
+        self.head = (self.head + N - 1) % N;
+        self.data[self.head] = Some(item);
+        self.len += 1;
+        Ok(())
+    }
+
+    /// Appends at the back, evicting and returning the front item when full.
+    /// The usual mode for fixed-size logs and sample windows.
+    pub fn push_overwrite(&mut self, item: T) -> Option<T> {
+        if N == 0 {
+            return Some(item);
+        }
+        let evicted = if self.is_full() { self.pop_front() } else { None };
+        let _ = self.push_back(item);
+        evicted
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let item = self.data[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        item
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        self.len -= 1;
+        let s = self.slot(self.len);
+        self.data[s].take()
+    }
+
+    /// The `i`-th item counting from the front.
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+        self.data[self.slot(i)].as_ref()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Items from front to back.
+    pub fn iter(&self) -> impl Iterator<Item = &T> + '_ {
+        (0..self.len).filter_map(move |i| self.get(i))
+    }
+}
+
+impl<const N: usize> DataStructure for FixedFenwick<N> {
+    const INFO: Info = Info {
+        name: "FixedFenwick",
+        category: "fixed capacity",
+        summary: "Non-allocating Fenwick tree over exactly N elements",
+        space: "O(N), inline",
+        operations: &[
+            Operation::new("add", "O(log N)", "O(1)"),
+            Operation::new("query", "O(log N)", "O(1)"),
+            Operation::new("range_sum", "O(log N)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl<const N: usize> RangeQuery for FixedFenwick<N> {
+    type Item = i64;
+    type Output = i64;
+
+    fn len(&self) -> usize {
+        N
+    }
+
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> i64 {
+        self.sum(range)
+    }
+}
+
+impl<const N: usize> PointUpdate for FixedFenwick<N> {
+    fn set(&mut self, index: usize, value: i64) {
+        let current = self.sum(index..=index);
+        self.add(index, value - current);
+    }
+}
+
+impl<T: Ord, const N: usize> DataStructure for FixedHeap<T, N> {
+    const INFO: Info = Info {
+        name: "FixedHeap",
+        category: "fixed capacity",
+        summary: "Non-allocating binary min-heap with capacity N",
+        space: "O(N), inline",
+        operations: &[
+            Operation::new("push", "O(log N)", "O(1)"),
+            Operation::new("pop", "O(log N)", "O(1)"),
+            Operation::new("peek", "O(1)", "O(1)"),
+        ],
+        stable: Some(false),
+        in_place: None,
+    };
+}
+
+impl<T, const N: usize> DataStructure for FixedRing<T, N> {
+    const INFO: Info = Info {
+        name: "FixedRing",
+        category: "fixed capacity",
+        summary: "Non-allocating double-ended ring buffer with capacity N",
+        space: "O(N), inline",
+        operations: &[
+            Operation::new("push_back", "O(1)", "O(1)"),
+            Operation::new("push_front", "O(1)", "O(1)"),
+            Operation::new("push_overwrite", "O(1)", "O(1)"),
+            Operation::new("pop_front", "O(1)", "O(1)"),
+            Operation::new("pop_back", "O(1)", "O(1)"),
+            Operation::new("get", "O(1)", "O(1)"),
+        ],
+        stable: Some(true),
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fenwick_tree::FenwickTree;
+
+    #[test]
+    fn test_fixed_fenwick_matches_fenwick_tree() {
+        let values = [3, -1, 4, 1, -5, 9, 2, 6];
+        let mut fixed = FixedFenwick::from_array(values);
+        let mut dynamic = FenwickTree::from_vec(&values);
+        fixed.add(3, 10);
+        dynamic.add(3, 10);
+        for i in 0..values.len() {
+            assert_eq!(fixed.query(i), dynamic.query(i));
+            for j in i..values.len() {
+                assert_eq!(fixed.range_sum(i, j), dynamic.range_sum(i, j));
+            }
+        }
+        assert_eq!(fixed.len(), 8);
+        assert_eq!(fixed.sum(2..5), dynamic.sum(2..5));
+        assert_eq!(fixed.sum(..), values.iter().sum::<i64>() + 10);
+        // usable in a static context
+        static EMPTY: FixedFenwick<4> = FixedFenwick::new();
+        assert_eq!(EMPTY.range_sum(0, 3), 0);
+    }
+
+    #[test]
+    fn test_fixed_heap() {
+        let mut heap: FixedHeap<i32, 5> = FixedHeap::new();
+        for v in [5, 2, 8, 1, 9] {
+            assert!(heap.push(v).is_ok());
+        }
+        assert!(heap.is_full());
+        assert_eq!(heap.push(0), Err(0));
+        assert_eq!(heap.peek(), Some(&1));
+        let mut out = [0; 5];
+        for slot in out.iter_mut() {
+            *slot = heap.pop().unwrap();
+        }
+        assert_eq!(out, [1, 2, 5, 8, 9]);
+        assert_eq!(heap.pop(), None);
+        assert!(heap.push(3).is_ok());
+        assert_eq!(heap.peek(), Some(&3));
+    }
+
+    #[test]
+    fn test_fixed_ring() {
+        let mut ring: FixedRing<u8, 3> = FixedRing::new();
+        assert!(ring.push_back(1).is_ok());
+        assert!(ring.push_back(2).is_ok());
+        assert!(ring.push_front(0).is_ok());
+        assert_eq!(ring.push_back(3), Err(3));
+        assert_eq!(ring.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+        assert_eq!(ring.push_overwrite(3), Some(0));
+        assert_eq!(ring.front(), Some(&1));
+        assert_eq!(ring.back(), Some(&3));
+        assert_eq!(ring.pop_back(), Some(3));
+        assert_eq!(ring.pop_front(), Some(1));
+        assert_eq!(ring.get(0), Some(&2));
+        assert_eq!(ring.get(1), None);
+        assert_eq!(ring.pop_front(), Some(2));
+        assert!(ring.is_empty());
+        assert_eq!(ring.pop_back(), None);
+    }
+}
@@ -0,0 +1,371 @@
+// rust/src/fm_index.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::snapshot::{Decoder, Encoder, Snapshot};
+use crate::suffix_array::suffix_array;
+
+/// Burrows–Wheeler transform of `s`, with an implicit end-of-text sentinel that sorts before every byte.
+/// Returns the last column of the sorted rotations of `s` + sentinel with the sentinel itself removed,
+/// together with the row it was removed from (needed by `inverse_bwt`).
+/// - O(n) via the suffix array
+pub fn bwt(s: &[u8]) -> (Vec<u8>, usize) {
+    let sa = suffix_array(s);
+    let mut last = Vec::with_capacity(s.len());
+    // row 0 is the rotation starting at the sentinel; row i + 1 is the suffix sa[i]
+    let mut primary = 0;
+    if let Some(&c) = s.last() {
+        last.push(c);
+    }
+    for (i, &p) in sa.iter().enumerate() {
+        if p == 0 {
+            primary = i + 1;
+        } else {
+            last.push(s[p - 1]);
+        }
+    }
+    (last, primary)
+}
+
+/// Inverts `bwt`. Panics if `primary > last.len()`.
+/// - O(n)
+pub fn inverse_bwt(last: &[u8], primary: usize) -> Vec<u8> {
+    assert!(primary <= last.len(), "primary row out of bounds");
+    let n = last.len();
+    // first[c]: row of the first rotation starting with c (the sentinel row comes first)
+    let mut first = [0usize; 256];
+    let mut counts = [0usize; 256];
+    for &c in last {
+        counts[c as usize] += 1;
+    }
+    let mut acc = 1;
+    for c in 0..256 {
+        first[c] = acc;
+        acc += counts[c];
+    }
+    // lf[j] is the LF mapping of the row that holds last[j]
+    let mut seen = [0usize; 256];
+    let lf: Vec<usize> = last
+        .iter()
+        .map(|&c| {
+            seen[c as usize] += 1;
+            first[c as usize] + seen[c as usize] - 1
+        })
+        .collect();
+    let mut out = vec![0u8; n];
+    let mut row = 0;
+    for k in (0..n).rev() {
+        let j = if row < primary { row } else { row - 1 };
+        out[k] = last[j];
+        row = lf[j];
+    }
+    out
+}
+
+const BLOCK: usize = 64;
+
+/// FM-index: a full-text index over the BWT that answers substring queries without keeping the text.
+/// Occurrence counts are checkpointed every 64 positions for the bytes that actually occur, so
+/// `rank(c, i)` is one table read plus a short scan; `locate` walks LF steps back to the nearest
+/// sampled suffix-array entry.
+/// - Build: O(n)
+/// - count: O(m) rank queries
+/// - locate: O(m + occ * sample_rate) rank queries
+/// - Space: n bytes for the BWT, plus σ counters per 64 bytes and n / sample_rate SA samples
+///
+/// Works on bytes, so positions are byte offsets (same convention as `SuffixArray`).
+pub struct FmIndex {
+    last: Vec<u8>,
+    primary: usize,
+    // first[c]: first row whose rotation starts with byte c
+    first: [usize; 256],
+    // dense symbol id of each byte present in the text, u16::MAX otherwise
+    symbol: [u16; 256],
+    sigma: usize,
+    // checkpoints[b * sigma + s]: occurrences of symbol s in last[..b * BLOCK]
+    checkpoints: Vec<u32>,
+    sample_rate: usize,
+    // rows whose text position is a multiple of sample_rate, with per-word prefix popcounts
+    sampled: Vec<u64>,
+    sampled_rank: Vec<u32>,
+    samples: Vec<usize>,
+}
+
+impl FmIndex {
+    pub fn new(text: &str) -> Self {
+        Self::from_bytes(text.as_bytes(), 32)
+    }
+
+    /// Builds the index, keeping every `sample_rate`-th suffix-array entry for `locate`.
+    /// Larger rates save memory at the cost of slower `locate`. Panics if `sample_rate == 0`.
+    pub fn from_bytes(text: &[u8], sample_rate: usize) -> Self {
+        assert!(sample_rate > 0, "sample rate must be positive");
+        let sa = suffix_array(text);
+        let (last, primary) = bwt(text);
+        let n = text.len();
+        // row 0 is the sentinel suffix (text position n); row i + 1 is sa[i]
+        let rows = n + 1;
+        let mut sampled = vec![0u64; rows.div_ceil(64)];
+        let mut samples = Vec::with_capacity(rows / sample_rate + 1);
+        for (row, pos) in std::iter::once(n).chain(sa.iter().copied()).enumerate() {
+            if pos.is_multiple_of(sample_rate) {
+                sampled[row / 64] |= 1 << (row % 64);
+                samples.push(pos);
+            }
+        }
+        Self::assemble(last, primary, sample_rate, sampled, samples)
+    }
+
+    /// Derives the lookup tables from the BWT and the suffix-array samples.
+    fn assemble(last: Vec<u8>, primary: usize, sample_rate: usize, sampled: Vec<u64>, samples: Vec<usize>) -> Self {
+        let n = last.len();
+        let mut counts = [0usize; 256];
+        for &c in &last {
+            counts[c as usize] += 1;
+        }
+        let mut first = [0usize; 256];
+        let mut symbol = [u16::MAX; 256];
+        let (mut acc, mut sigma) = (1, 0);
+        for c in 0..256 {
+            first[c] = acc;
+            acc += counts[c];
+            if counts[c] > 0 {
+                symbol[c] = sigma as u16;
+                sigma += 1;
+            }
+        }
+
+        let mut checkpoints = Vec::with_capacity((n / BLOCK + 1) * sigma);
+        let mut running = vec![0u32; sigma];
+        for (i, &c) in last.iter().enumerate() {
+            if i.is_multiple_of(BLOCK) {
+                checkpoints.extend_from_slice(&running);
+            }
+            running[symbol[c as usize] as usize] += 1;
+        }
+        if n.is_multiple_of(BLOCK) {
+            checkpoints.extend_from_slice(&running);
+        }
+
+        let mut sampled_rank = Vec::with_capacity(sampled.len());
+        let mut ones = 0;
+        for w in &sampled {
+            sampled_rank.push(ones);
+            ones += w.count_ones();
+        }
+
+        FmIndex {
+            last,
+            primary,
+            first,
+            symbol,
+            sigma,
+            checkpoints,
+            sample_rate,
+            sampled,
+            sampled_rank,
+            samples,
+        }
+    }
+
+    /// Length of the indexed text in bytes.
+    pub fn len(&self) -> usize {
+        self.last.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.last.is_empty()
+    }
+
+    /// The BWT and its sentinel row, as returned by `bwt`.
+    pub fn bwt(&self) -> (&[u8], usize) {
+        (&self.last, self.primary)
+    }
+
+    pub fn sample_rate(&self) -> usize {
+        self.sample_rate
+    }
+
+    /// Occurrences of byte `c` in the BWT rows `[0, row)`, not counting the sentinel.
+    fn rank(&self, c: u8, row: usize) -> usize {
+        let s = self.symbol[c as usize];
+        if s == u16::MAX {
+            return 0;
+        }
+        let i = if row <= self.primary { row } else { row - 1 };
+        let block = i / BLOCK;
+        let base = self.checkpoints[block * self.sigma + s as usize] as usize;
+        base + self.last[block * BLOCK..i].iter().filter(|&&b| b == c).count()
+    }
+
+    /// Row of the rotation obtained by moving the last char of `row` to the front.
+    fn lf(&self, row: usize) -> usize {
+        let c = self.last[if row < self.primary { row } else { row - 1 }];
+        self.first[c as usize] + self.rank(c, row)
+    }
+
+    /// Half-open range of rows whose suffixes start with `pattern` (backward search).
+    fn row_range(&self, pattern: &[u8]) -> (usize, usize) {
+        let (mut lo, mut hi) = (0, self.len() + 1);
+        for &c in pattern.iter().rev() {
+            if self.symbol[c as usize] == u16::MAX {
+                return (0, 0);
+            }
+            lo = self.first[c as usize] + self.rank(c, lo);
+            hi = self.first[c as usize] + self.rank(c, hi);
+            if lo >= hi {
+                return (0, 0);
+            }
+        }
+        (lo, hi)
+    }
+
+    /// Number of (possibly overlapping) occurrences of `pattern`. Returns 0 for an empty pattern.
+    pub fn count(&self, pattern: &str) -> usize {
+        if pattern.is_empty() {
+            return 0;
+        }
+        let (lo, hi) = self.row_range(pattern.as_bytes());
+        hi - lo
+    }
+
+    /// Sorted starting positions of every occurrence of `pattern`.
+    pub fn locate(&self, pattern: &str) -> Vec<usize> {
+        if pattern.is_empty() {
+            return vec![];
+        }
+        let (lo, hi) = self.row_range(pattern.as_bytes());
+        let mut res: Vec<usize> = (lo..hi).map(|row| self.text_position(row)).collect();
+        res.sort_unstable();
+        res
+    }
+
+    /// Text position of the suffix in `row`. Position 0 is always sampled, so the walk never
+    /// steps through the sentinel row.
+    fn text_position(&self, mut row: usize) -> usize {
+        let mut steps = 0;
+        while self.sampled[row / 64] & (1 << (row % 64)) == 0 {
+            row = self.lf(row);
+            steps += 1;
+        }
+        let below = self.sampled[row / 64] & ((1u64 << (row % 64)) - 1);
+        let k = self.sampled_rank[row / 64] as usize + below.count_ones() as usize;
+        self.samples[k] + steps
+    }
+}
+
+impl DataStructure for FmIndex {
+    const INFO: Info = Info {
+        name: "FmIndex",
+        category: "strings",
+        summary: "Compressed full-text index over the Burrows-Wheeler transform (m = pattern length, s = sample rate)",
+        space: "n bytes + O(n σ / 64 + n / s) words",
+        operations: &[
+            Operation::new("new", "O(n)", "O(n)"),
+            Operation::new("count", "O(m)", "O(1)"),
+            Operation::new("locate", "O(m + k * s)", "O(k)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+/// Stores the BWT and the suffix-array samples; the rank tables are rebuilt in O(n) on load.
+impl Snapshot for FmIndex {
+    const KIND: [u8; 4] = *b"FMIX";
+
+    fn encode(&self, out: &mut Encoder) {
+        out.slice(&self.last);
+        out.usize(self.primary);
+        out.usize(self.sample_rate);
+        out.slice(&self.sampled);
+        out.usizes(&self.samples);
+    }
+
+    fn decode(input: &mut Decoder<'_>) -> Option<Self> {
+        let last: Vec<u8> = input.vec()?;
+        let (primary, sample_rate) = (input.usize()?, input.usize()?);
+        let sampled: Vec<u64> = input.vec()?;
+        let samples = input.usizes()?;
+        let rows = last.len() + 1;
+        let stray_bits = sampled.last().is_some_and(|w| !rows.is_multiple_of(64) && w >> (rows % 64) != 0);
+        let consistent = primary <= last.len()
+            && sample_rate > 0
+            && sampled.len() == rows.div_ceil(64)
+            && !stray_bits
+            // the row of text position 0 must be sampled for `locate` to terminate
+            && sampled[primary / 64] & (1 << (primary % 64)) != 0
+            && sampled.iter().map(|w| w.count_ones() as usize).sum::<usize>() == samples.len()
+            && samples.iter().all(|&p| p <= last.len() && p.is_multiple_of(sample_rate));
+        consistent.then(|| Self::assemble(last, primary, sample_rate, sampled, samples))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::suffix_array::SuffixArray;
+
+    #[test]
+    fn test_bwt_round_trip() {
+        assert_eq!(bwt(b"banana"), (b"annbaa".to_vec(), 4));
+        assert_eq!(inverse_bwt(b"annbaa", 4), b"banana".to_vec());
+        assert_eq!(bwt(b""), (vec![], 0));
+        assert_eq!(inverse_bwt(b"", 0), Vec::<u8>::new());
+        let mut next = test_rng(12);
+        for len in [1usize, 2, 17, 300] {
+            let s: Vec<u8> = (0..len).map(|_| (next() % 8) as u8).collect();
+            let (last, primary) = bwt(&s);
+            assert_eq!(inverse_bwt(&last, primary), s);
+        }
+    }
+
+    #[test]
+    fn test_count_and_locate() {
+        let fm = FmIndex::new("mississippi");
+        assert_eq!(fm.len(), 11);
+        assert_eq!(fm.count("ssi"), 2);
+        assert_eq!(fm.locate("ssi"), vec![2, 5]);
+        assert_eq!(fm.locate("i"), vec![1, 4, 7, 10]);
+        assert_eq!(fm.count("mississippi"), 1);
+        assert_eq!(fm.count("x"), 0);
+        assert_eq!(fm.count("ppa"), 0);
+        assert_eq!(fm.count(""), 0);
+        assert!(FmIndex::new("").locate("a").is_empty());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let fm = FmIndex::from_bytes(b"abracadabra abracadabra", 4);
+        let back = FmIndex::from_snapshot(&fm.to_snapshot()).unwrap();
+        assert_eq!(back.bwt(), fm.bwt());
+        assert_eq!(back.locate("abra"), vec![0, 7, 12, 19]);
+        assert_eq!(back.sample_rate(), 4);
+        let empty = FmIndex::from_snapshot(&FmIndex::new("").to_snapshot()).unwrap();
+        assert!(empty.is_empty());
+
+        // a snapshot whose samples cannot be right is rejected
+        let mut bytes = fm.to_snapshot();
+        let len = bytes.len();
+        bytes[len - 8] ^= 1;
+        assert!(FmIndex::from_snapshot(&bytes).is_err());
+    }
+
+    #[test]
+    fn test_matches_suffix_array() {
+        let mut next = test_rng(44);
+        let text: String = (0..2000).map(|_| (b'a' + (next() % 3) as u8) as char).collect();
+        let sa = SuffixArray::new(&text);
+        for rate in [1, 5, 64] {
+            let fm = FmIndex::from_bytes(text.as_bytes(), rate);
+            for start in (0..1990).step_by(97) {
+                for len in [1, 3, 8] {
+                    let p = &text[start..start + len];
+                    assert_eq!(fm.count(p), sa.count_occurrences(p), "{}", p);
+                    assert_eq!(fm.locate(p), sa.find_occurrences(p), "{} rate={}", p, rate);
+                }
+            }
+            assert_eq!(fm.count("abcabcabcabcabc"), sa.count_occurrences("abcabcabcabcabc"));
+        }
+    }
+}
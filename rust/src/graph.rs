@@ -0,0 +1,334 @@
+// rust/src/graph.rs
+use crate::DataStructure;
+use crate::csr_graph::CsrGraph;
+use crate::dot::{DotWriter, Highlight};
+use crate::info::{Info, Operation};
+use std::fmt::Debug;
+use std::ops::Range;
+
+pub mod euler;
+pub mod max_flow;
+pub mod min_cost_flow;
+pub mod prim;
+pub mod scc;
+pub mod shortest_path;
+pub mod topological;
+pub mod traversal;
+
+pub use euler::{EulerianTrail, eulerian_circuit, eulerian_path};
+pub use max_flow::MaxFlow;
+pub use min_cost_flow::MinCostFlow;
+pub use prim::{prim, prim_implicit, prim_with};
+pub use scc::{Scc, strongly_connected_components};
+pub use shortest_path::{ShortestPaths, astar, astar_implicit, dial, dijkstra, dijkstra_with, zero_one_bfs};
+pub use topological::{longest_path_in_dag, topological_sort, topological_sort_dfs};
+pub use traversal::{Bfs, Control, Dfs, Visitor, bfs, bfs_with, dfs, dfs_forest, dfs_with};
+
+/// How a `Graph` stores its adjacency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Storage {
+    /// One `Vec` per vertex: cheap `add_edge`, the right choice while the graph is being built.
+    AdjacencyList,
+    /// All adjacency in one `CsrGraph`: contiguous, faster to traverse, but `add_edge` converts back.
+    Csr,
+}
+
+#[derive(Debug, Clone)]
+enum Adjacency {
+    // (neighbour, edge id) per vertex
+    Lists(Vec<Vec<(usize, usize)>>),
+    // the CSR "weight" of each entry is its edge id
+    Csr(CsrGraph<usize>),
+}
+
+/// Directed or undirected graph on vertices `0..n` with edge weights of type `W` (`()` when
+/// unweighted). Edges are numbered `0..m` in insertion order, and every algorithm in the crate that
+/// picks edges (MST, Euler tours, flows) reports them by these ids. An undirected edge appears in the
+/// adjacency of both endpoints under the same id; a self-loop appears once.
+///
+/// - add_node / add_edge: O(1) amortized with `Storage::AdjacencyList`
+/// - from_edges: O(n + m)
+/// - neighbors / adjacent / out_edges: O(1) to start, O(degree) to iterate
+/// - into_storage: O(n + m)
+#[derive(Debug, Clone)]
+pub struct Graph<W = ()> {
+    directed: bool,
+    edges: Vec<(usize, usize, W)>,
+    adjacency: Adjacency,
+}
+
+impl<W> Graph<W> {
+    fn empty(n: usize, directed: bool) -> Self {
+        Graph {
+            directed,
+            edges: Vec::new(),
+            adjacency: Adjacency::Lists(vec![Vec::new(); n]),
+        }
+    }
+
+    /// Directed graph with `n` vertices and no edges.
+    pub fn directed(n: usize) -> Self {
+        Self::empty(n, true)
+    }
+
+    /// Undirected graph with `n` vertices and no edges.
+    pub fn undirected(n: usize) -> Self {
+        Self::empty(n, false)
+    }
+
+    /// Builds from an edge list `(from, to, weight)`; edge `i` of the list gets id `i`.
+    /// Panics if an endpoint is `>= n`.
+    pub fn from_edges<I>(n: usize, edges: I, directed: bool) -> Self
+    where
+        I: IntoIterator<Item = (usize, usize, W)>,
+    {
+        let mut g = Self::empty(n, directed);
+        edges.into_iter().for_each(|(u, v, w)| {
+            g.add_edge(u, v, w);
+        });
+        g
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        match &self.adjacency {
+            Adjacency::Lists(lists) => lists.len(),
+            Adjacency::Csr(csr) => csr.num_nodes(),
+        }
+    }
+
+    pub fn num_edges(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn nodes(&self) -> Range<usize> {
+        0..self.num_nodes()
+    }
+
+    pub fn storage(&self) -> Storage {
+        match self.adjacency {
+            Adjacency::Lists(_) => Storage::AdjacencyList,
+            Adjacency::Csr(_) => Storage::Csr,
+        }
+    }
+
+    fn to_lists(csr: &CsrGraph<usize>) -> Vec<Vec<(usize, usize)>> {
+        (0..csr.num_nodes()).map(|u| csr.edges(u).map(|(v, &id)| (v, id)).collect()).collect()
+    }
+
+    /// Converts the adjacency to `storage`, keeping edge ids and the order of every vertex's edges.
+    pub fn into_storage(mut self, storage: Storage) -> Self {
+        self.adjacency = match (self.adjacency, storage) {
+            (Adjacency::Lists(lists), Storage::Csr) => {
+                let entries: Vec<(usize, usize, usize)> = lists
+                    .iter()
+                    .enumerate()
+                    .flat_map(|(u, list)| list.iter().map(move |&(v, id)| (u, v, id)))
+                    .collect();
+                Adjacency::Csr(CsrGraph::from_edges(lists.len(), &entries))
+            }
+            (Adjacency::Csr(csr), Storage::AdjacencyList) => Adjacency::Lists(Self::to_lists(&csr)),
+            (same, _) => same,
+        };
+        self
+    }
+
+    fn lists_mut(&mut self) -> &mut Vec<Vec<(usize, usize)>> {
+        if let Adjacency::Csr(csr) = &self.adjacency {
+            self.adjacency = Adjacency::Lists(Self::to_lists(csr));
+        }
+        match &mut self.adjacency {
+            Adjacency::Lists(lists) => lists,
+            Adjacency::Csr(_) => unreachable!("converted to lists above"),
+        }
+    }
+
+    /// Adds an isolated vertex and returns its index.
+    pub fn add_node(&mut self) -> usize {
+        let lists = self.lists_mut();
+        lists.push(Vec::new());
+        lists.len() - 1
+    }
+
+    /// Adds the edge `u -> v` (or `u - v` when undirected) and returns its id. On a CSR graph this
+    /// first converts the adjacency back to lists. Panics if an endpoint is out of bounds.
+    pub fn add_edge(&mut self, u: usize, v: usize, weight: W) -> usize {
+        let n = self.num_nodes();
+        assert!(u < n && v < n, "edge ({}, {}) out of bounds for {} vertices", u, v, n);
+        let id = self.edges.len();
+        let directed = self.directed;
+        let lists = self.lists_mut();
+        lists[u].push((v, id));
+        if !directed && u != v {
+            lists[v].push((u, id));
+        }
+        self.edges.push((u, v, weight));
+        id
+    }
+
+    /// Number of adjacency entries of `u` (out-degree when directed; a self-loop counts once).
+    pub fn degree(&self, u: usize) -> usize {
+        match &self.adjacency {
+            Adjacency::Lists(lists) => lists[u].len(),
+            Adjacency::Csr(csr) => csr.degree(u),
+        }
+    }
+
+    /// `(neighbour, edge id)` pairs of `u`'s out-edges, in insertion order.
+    pub fn adjacent(&self, u: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        // exactly one of the two is Some; chaining them avoids boxing the iterator
+        let (lists, csr) = match &self.adjacency {
+            Adjacency::Lists(lists) => (Some(lists[u].iter().copied()), None),
+            Adjacency::Csr(csr) => (None, Some(csr.edges(u).map(|(v, &id)| (v, id)))),
+        };
+        lists.into_iter().flatten().chain(csr.into_iter().flatten())
+    }
+
+    /// Neighbours of `u`.
+    pub fn neighbors(&self, u: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adjacent(u).map(|(v, _)| v)
+    }
+
+    /// `(neighbour, &weight)` pairs of `u`'s out-edges.
+    pub fn out_edges(&self, u: usize) -> impl Iterator<Item = (usize, &W)> + '_ {
+        self.adjacent(u).map(|(v, id)| (v, &self.edges[id].2))
+    }
+
+    /// Endpoints and weight of edge `id`, as it was added.
+    pub fn edge(&self, id: usize) -> (usize, usize, &W) {
+        let (u, v, ref w) = self.edges[id];
+        (u, v, w)
+    }
+
+    pub fn weight(&self, id: usize) -> &W {
+        &self.edges[id].2
+    }
+
+    /// Every edge as `(from, to, &weight)`, in id order.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, &W)> + '_ {
+        self.edges.iter().map(|(u, v, w)| (*u, *v, w))
+    }
+
+    /// Edge `id` seen from endpoint `u`: the other endpoint (for a directed edge, its head).
+    pub fn opposite(&self, id: usize, u: usize) -> usize {
+        let (a, b, _) = self.edges[id];
+        if a == u { b } else { a }
+    }
+}
+
+impl<W: Clone> Graph<W> {
+    /// The same edge list as `(from, to, weight)` tuples, e.g. for the edge-list based `mst` functions.
+    pub fn edge_list(&self) -> Vec<(usize, usize, W)> {
+        self.edges.clone()
+    }
+
+    /// The graph with every edge reversed, keeping ids. An undirected graph is returned unchanged.
+    pub fn reversed(&self) -> Self {
+        if !self.directed {
+            return self.clone();
+        }
+        let rev = Graph::from_edges(self.num_nodes(), self.edges.iter().map(|(u, v, w)| (*v, *u, w.clone())), true);
+        rev.into_storage(self.storage())
+    }
+}
+
+impl<W: Debug> Graph<W> {
+    /// Graphviz DOT rendering, with each edge labelled by its weight unless the weight type is
+    /// zero-sized (as for unweighted graphs).
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighting(&Highlight::new())
+    }
+
+    /// Like `to_dot`, emphasising the highlighted vertices and the edges that a highlighted path
+    /// walks along (in either direction when undirected).
+    pub fn to_dot_highlighting(&self, highlight: &Highlight) -> String {
+        let mut w = DotWriter::new("Graph", self.directed);
+        w.attr("node [shape=circle]");
+        for v in self.nodes() {
+            w.node(&format!("v{}", v), &v.to_string(), highlight.contains(&v));
+        }
+        let weighted = std::mem::size_of::<W>() > 0;
+        for (u, v, weight) in self.edges() {
+            let label = weighted.then(|| format!("{:?}", weight));
+            w.edge(&format!("v{}", u), &format!("v{}", v), label.as_deref(), highlight.links(&u, &v, self.directed));
+        }
+        w.finish()
+    }
+}
+
+impl Graph<()> {
+    /// Builds an unweighted graph from `(from, to)` pairs.
+    pub fn from_unweighted_edges(n: usize, edges: &[(usize, usize)], directed: bool) -> Self {
+        Self::from_edges(n, edges.iter().map(|&(u, v)| (u, v, ())), directed)
+    }
+}
+
+impl<W> DataStructure for Graph<W> {
+    const INFO: Info = Info {
+        name: "Graph",
+        category: "graphs",
+        summary: "Directed or undirected weighted graph with numbered edges, in adjacency lists or CSR (m = edges)",
+        space: "O(n + m)",
+        operations: &[
+            Operation::new("add_edge", "O(1) amortized", "O(1)"),
+            Operation::new("from_edges", "O(n + m)", "O(n + m)"),
+            Operation::new("adjacent", "O(degree)", "O(1)"),
+            Operation::new("into_storage", "O(n + m)", "O(n + m)"),
+            Operation::new("reversed", "O(n + m)", "O(n + m)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directed_build_and_iterate() {
+        let mut g = Graph::from_edges(3, [(0, 1, 5), (0, 2, 7), (2, 1, 1)], true);
+        assert_eq!(g.num_nodes(), 3);
+        assert_eq!(g.num_edges(), 3);
+        assert_eq!(g.neighbors(0).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(g.out_edges(2).collect::<Vec<_>>(), vec![(1, &1)]);
+        assert_eq!(g.degree(1), 0);
+        let d = g.add_node();
+        assert_eq!(g.add_edge(d, 0, 9), 3);
+        assert_eq!(g.edge(3), (3, 0, &9));
+        assert_eq!(g.edges().map(|(_, _, w)| *w).sum::<i32>(), 22);
+        let rev = g.reversed();
+        assert_eq!(rev.neighbors(1).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(rev.adjacent(0).collect::<Vec<_>>(), vec![(3, 3)]);
+    }
+
+    #[test]
+    fn test_undirected_shares_edge_ids() {
+        let g = Graph::from_unweighted_edges(4, &[(0, 1), (1, 2), (2, 2), (3, 1)], false);
+        assert!(!g.is_directed());
+        assert_eq!(g.adjacent(1).collect::<Vec<_>>(), vec![(0, 0), (2, 1), (3, 3)]);
+        // the self-loop is listed once
+        assert_eq!(g.adjacent(2).collect::<Vec<_>>(), vec![(1, 1), (2, 2)]);
+        assert_eq!(g.opposite(3, 1), 3);
+        assert_eq!(g.opposite(3, 3), 1);
+    }
+
+    #[test]
+    fn test_storage_conversion() {
+        let lists = Graph::from_edges(5, [(0, 1, 'a'), (3, 4, 'b'), (1, 3, 'c'), (0, 4, 'd')], false);
+        let mut csr = lists.clone().into_storage(Storage::Csr);
+        assert_eq!(csr.storage(), Storage::Csr);
+        for u in lists.nodes() {
+            assert_eq!(lists.adjacent(u).collect::<Vec<_>>(), csr.adjacent(u).collect::<Vec<_>>());
+            assert_eq!(lists.degree(u), csr.degree(u));
+        }
+        // adding to a CSR graph converts it back to lists
+        csr.add_edge(2, 0, 'e');
+        assert_eq!(csr.storage(), Storage::AdjacencyList);
+        assert_eq!(csr.out_edges(2).collect::<Vec<_>>(), vec![(0, &'e')]);
+        assert_eq!(csr.neighbors(0).collect::<Vec<_>>(), vec![1, 4, 2]);
+    }
+}
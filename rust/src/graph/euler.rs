@@ -0,0 +1,165 @@
+// rust/src/graph/euler.rs
+use super::Graph;
+
+/// A walk using every edge exactly once: `edges[i]` (an edge id) leads from `vertices[i]` to
+/// `vertices[i + 1]`. A graph without edges gives two empty lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EulerianTrail {
+    pub vertices: Vec<usize>,
+    pub edges: Vec<usize>,
+}
+
+impl EulerianTrail {
+    pub fn is_circuit(&self) -> bool {
+        self.vertices.first() == self.vertices.last()
+    }
+}
+
+/// Where an Eulerian trail has to start, judging by degrees alone: Some(Ok(v)) for a circuit
+/// through `v`, Some(Err(v)) for an open trail from `v`, None if the degrees rule both out.
+fn start_vertex<W>(g: &Graph<W>) -> Option<Result<usize, usize>> {
+    let n = g.num_nodes();
+    let (first, _, _) = g.edges().next()?;
+    if g.is_directed() {
+        // out-degree minus in-degree
+        let mut excess = vec![0i64; n];
+        for (u, v, _) in g.edges() {
+            excess[u] += 1;
+            excess[v] -= 1;
+        }
+        let starts: Vec<usize> = g.nodes().filter(|&v| excess[v] == 1).collect();
+        let unbalanced = g.nodes().filter(|&v| excess[v] != 0).count();
+        match (unbalanced, starts.as_slice()) {
+            (0, _) => Some(Ok(first)),
+            (2, &[s]) => Some(Err(s)),
+            _ => None,
+        }
+    } else {
+        // a self-loop adds 2 to its vertex's degree
+        let mut degree = vec![0usize; n];
+        for (u, v, _) in g.edges() {
+            degree[u] += 1;
+            degree[v] += 1;
+        }
+        let odd: Vec<usize> = g.nodes().filter(|&v| degree[v] % 2 == 1).collect();
+        match odd.as_slice() {
+            [] => Some(Ok(first)),
+            &[s, _] => Some(Err(s)),
+            _ => None,
+        }
+    }
+}
+
+/// Hierholzer's algorithm from `start`; None if the walk strands edges in another component.
+fn hierholzer<W>(g: &Graph<W>, start: usize) -> Option<EulerianTrail> {
+    let mut pending: Vec<_> = g.nodes().map(|u| g.adjacent(u)).collect();
+    let mut used = vec![false; g.num_edges()];
+    let mut trail = EulerianTrail { vertices: Vec::new(), edges: Vec::new() };
+    // (vertex, edge id it was entered by)
+    let mut stack = vec![(start, usize::MAX)];
+    while let Some(&(u, via)) = stack.last() {
+        match pending[u].find(|&(_, id)| !used[id]) {
+            Some((v, id)) => {
+                used[id] = true;
+                stack.push((v, id));
+            }
+            None => {
+                // u is finished: it goes into the trail, which comes out backwards
+                stack.pop();
+                trail.vertices.push(u);
+                if via != usize::MAX {
+                    trail.edges.push(via);
+                }
+            }
+        }
+    }
+    if trail.edges.len() < g.num_edges() {
+        return None;
+    }
+    trail.vertices.reverse();
+    trail.edges.reverse();
+    Some(trail)
+}
+
+/// An Eulerian trail of `g` (directed or undirected): a circuit if every vertex is balanced,
+/// otherwise a path between the two unbalanced vertices (from the one with an extra out-edge when
+/// directed). Returns None if no trail uses every edge. Isolated vertices are ignored.
+/// Complexity: O(n + m)
+pub fn eulerian_path<W>(g: &Graph<W>) -> Option<EulerianTrail> {
+    if g.num_edges() == 0 {
+        return Some(EulerianTrail { vertices: Vec::new(), edges: Vec::new() });
+    }
+    match start_vertex(g)? {
+        Ok(v) | Err(v) => hierholzer(g, v),
+    }
+}
+
+/// An Eulerian circuit of `g`, starting and ending at the tail of edge 0, or None if there is none.
+/// Complexity: O(n + m)
+pub fn eulerian_circuit<W>(g: &Graph<W>) -> Option<EulerianTrail> {
+    if g.num_edges() == 0 {
+        return Some(EulerianTrail { vertices: Vec::new(), edges: Vec::new() });
+    }
+    match start_vertex(g)? {
+        Ok(v) => hierholzer(g, v),
+        Err(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<W>(g: &Graph<W>, trail: &EulerianTrail) {
+        let mut ids = trail.edges.clone();
+        ids.sort_unstable();
+        assert_eq!(ids, (0..g.num_edges()).collect::<Vec<_>>());
+        assert_eq!(trail.vertices.len(), trail.edges.len() + 1);
+        for (i, &id) in trail.edges.iter().enumerate() {
+            let (a, b) = (trail.vertices[i], trail.vertices[i + 1]);
+            let (u, v, _) = g.edge(id);
+            assert!((u, v) == (a, b) || (!g.is_directed() && (v, u) == (a, b)));
+        }
+    }
+
+    #[test]
+    fn test_directed() {
+        // de Bruijn graph B(2, 3): vertices are 2-bit words, edge w -> (w << 1 | b) & 3
+        let g = Graph::from_edges(4, (0..8).map(|x| (x >> 1, x & 3, x)), true);
+        let circuit = eulerian_circuit(&g).unwrap();
+        check(&g, &circuit);
+        assert!(circuit.is_circuit());
+        // the edge labels spell a de Bruijn sequence: every 3-bit word appears once cyclically
+        let bits: Vec<usize> = circuit.edges.iter().map(|&id| *g.weight(id) & 1).collect();
+        let mut words: Vec<usize> = (0..8).map(|i| bits[i] << 2 | bits[(i + 1) % 8] << 1 | bits[(i + 2) % 8]).collect();
+        words.sort_unstable();
+        assert_eq!(words, (0..8).collect::<Vec<_>>());
+
+        let path = Graph::from_unweighted_edges(4, &[(0, 1), (1, 2), (2, 0), (0, 3), (3, 0), (0, 2)], true);
+        let trail = eulerian_path(&path).unwrap();
+        check(&path, &trail);
+        assert_eq!((trail.vertices[0], *trail.vertices.last().unwrap()), (0, 2));
+        assert!(eulerian_circuit(&path).is_none());
+        // balanced but split in two components
+        assert!(eulerian_path(&Graph::from_unweighted_edges(4, &[(0, 1), (1, 0), (2, 3), (3, 2)], true)).is_none());
+        assert!(eulerian_path(&Graph::from_unweighted_edges(3, &[(0, 1), (0, 2)], true)).is_none());
+    }
+
+    #[test]
+    fn test_undirected() {
+        // two triangles sharing vertex 2, a self-loop on 4, and a pendant edge making 0 and 5 odd
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 2), (4, 4), (0, 5)];
+        let g = Graph::from_unweighted_edges(6, &edges, false);
+        let trail = eulerian_path(&g).unwrap();
+        check(&g, &trail);
+        assert!(!trail.is_circuit());
+        assert!(eulerian_circuit(&g).is_none());
+
+        let g = Graph::from_unweighted_edges(6, &edges[..7], false);
+        let circuit = eulerian_circuit(&g).unwrap();
+        check(&g, &circuit);
+        assert!(circuit.is_circuit());
+        assert!(eulerian_path(&Graph::from_unweighted_edges(4, &[(0, 1), (0, 2), (0, 3)], false)).is_none());
+        assert_eq!(eulerian_path(&Graph::<()>::undirected(3)).map(|t| t.edges), Some(vec![]));
+    }
+}
@@ -0,0 +1,231 @@
+// rust/src/graph/min_cost_flow.rs
+use crate::indexed_heap::IndexedHeap;
+
+#[derive(Debug, Clone)]
+struct Arc {
+    to: usize,
+    cap: i64,
+    cost: i64,
+}
+
+/// Minimum-cost flow network by successive shortest paths. Arcs `2 * id` and `2 * id + 1` are edge
+/// `id` and its residual reverse, so edge ids follow insertion order as in `Graph`.
+/// Costs may be negative as long as no cycle of positive capacity has negative total cost: the first
+/// potentials come from Bellman-Ford (queue-based, SPFA), after which every augmenting path is found
+/// by Dijkstra on reduced costs `cost + pot[u] - pot[v]`, which the potentials keep non-negative.
+///
+/// - add_edge: O(1) amortized
+/// - solve: O(n * m) for the initial potentials + O(F * (n + m) log n), F = number of augmentations
+#[derive(Debug, Clone)]
+pub struct MinCostFlow {
+    arcs: Vec<Arc>,
+    adj: Vec<Vec<usize>>,
+}
+
+impl MinCostFlow {
+    pub fn new(n: usize) -> Self {
+        MinCostFlow { arcs: Vec::new(), adj: vec![Vec::new(); n] }
+    }
+
+    pub fn num_nodes(&self) -> usize {
+        self.adj.len()
+    }
+
+    /// Adds the edge `u -> v` with capacity `cap >= 0` and per-unit `cost`, and returns its id.
+    pub fn add_edge(&mut self, u: usize, v: usize, cap: i64, cost: i64) -> usize {
+        let n = self.num_nodes();
+        assert!(u < n && v < n, "edge ({}, {}) out of bounds for {} vertices", u, v, n);
+        assert!(cap >= 0, "negative capacity {}", cap);
+        let id = self.arcs.len() / 2;
+        self.adj[u].push(self.arcs.len());
+        self.arcs.push(Arc { to: v, cap, cost });
+        self.adj[v].push(self.arcs.len());
+        self.arcs.push(Arc { to: u, cap: 0, cost: -cost });
+        id
+    }
+
+    /// Flow currently sent along edge `id`.
+    pub fn flow(&self, id: usize) -> i64 {
+        self.arcs[2 * id + 1].cap
+    }
+
+    /// Residual shortest distances from `s` by SPFA, for the first potentials. Panics if the residual
+    /// network has a negative cycle reachable from `s`.
+    fn bellman_ford(&self, s: usize) -> Vec<i64> {
+        let n = self.num_nodes();
+        let mut dist = vec![i64::MAX; n];
+        let mut queued = vec![false; n];
+        // edges on the current shortest path to each vertex; n of them means the path repeats a vertex
+        let mut hops = vec![0usize; n];
+        let mut queue = std::collections::VecDeque::from([s]);
+        dist[s] = 0;
+        while let Some(u) = queue.pop_front() {
+            queued[u] = false;
+            for &a in &self.adj[u] {
+                let Arc { to, cap, cost } = self.arcs[a];
+                if cap > 0 && dist[u] + cost < dist[to] {
+                    dist[to] = dist[u] + cost;
+                    hops[to] = hops[u] + 1;
+                    assert!(hops[to] < n, "negative-cost cycle in the flow network");
+                    if !queued[to] {
+                        queued[to] = true;
+                        queue.push_back(to);
+                    }
+                }
+            }
+        }
+        // vertices unreachable now stay unreachable, so their potential never matters
+        dist.iter().map(|&d| if d == i64::MAX { 0 } else { d }).collect()
+    }
+
+    /// Dijkstra on reduced costs; fills `dist` and `via` (the arc into each vertex).
+    fn dijkstra(&self, s: usize, pot: &[i64], dist: &mut [i64], via: &mut [usize]) {
+        dist.iter_mut().for_each(|d| *d = i64::MAX);
+        let mut heap = IndexedHeap::new();
+        dist[s] = 0;
+        heap.push(s, 0);
+        while let Some((u, d)) = heap.pop() {
+            for &a in &self.adj[u] {
+                let Arc { to, cap, cost } = self.arcs[a];
+                if cap == 0 {
+                    continue;
+                }
+                let nd = d + cost + pot[u] - pot[to];
+                if nd < dist[to] {
+                    let queued = if dist[to] == i64::MAX { heap.push(to, nd) } else { heap.decrease_key(&to, nd) };
+                    if queued {
+                        dist[to] = nd;
+                        via[to] = a;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Sends as much flow as possible from `s` to `t`, at most `limit`, at minimum total cost.
+    /// Returns `(flow, cost)` for this call; flow already in the network stays and is built on.
+    pub fn solve_limited(&mut self, s: usize, t: usize, limit: i64) -> (i64, i64) {
+        assert_ne!(s, t, "source and sink must differ");
+        let n = self.num_nodes();
+        let mut pot = self.bellman_ford(s);
+        let (mut dist, mut via) = (vec![0; n], vec![usize::MAX; n]);
+        let (mut flow, mut cost) = (0i64, 0i64);
+        while flow < limit {
+            self.dijkstra(s, &pot, &mut dist, &mut via);
+            if dist[t] == i64::MAX {
+                break;
+            }
+            for v in 0..n {
+                if dist[v] != i64::MAX {
+                    pot[v] += dist[v];
+                }
+            }
+            let mut push = limit - flow;
+            let mut v = t;
+            while v != s {
+                push = push.min(self.arcs[via[v]].cap);
+                v = self.arcs[via[v] ^ 1].to;
+            }
+            let mut v = t;
+            while v != s {
+                let a = via[v];
+                self.arcs[a].cap -= push;
+                self.arcs[a ^ 1].cap += push;
+                cost += push * self.arcs[a].cost;
+                v = self.arcs[a ^ 1].to;
+            }
+            flow += push;
+        }
+        (flow, cost)
+    }
+
+    /// Maximum flow from `s` to `t` and the minimum cost among maximum flows.
+    pub fn solve(&mut self, s: usize, t: usize) -> (i64, i64) {
+        self.solve_limited(s, t, i64::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_transportation() {
+        // two warehouses (supply 4 and 3) shipping to two shops (demand 5 and 2)
+        let (s, w1, w2, a, b, t) = (0, 1, 2, 3, 4, 5);
+        let mut net = MinCostFlow::new(6);
+        net.add_edge(s, w1, 4, 0);
+        net.add_edge(s, w2, 3, 0);
+        let routes = [net.add_edge(w1, a, 9, 2), net.add_edge(w1, b, 9, 3), net.add_edge(w2, a, 9, 5), net.add_edge(w2, b, 9, 1)];
+        net.add_edge(a, t, 5, 0);
+        net.add_edge(b, t, 2, 0);
+        // w1 -> a: 4 units at 2, w2 -> a: 1 at 5, w2 -> b: 2 at 1
+        assert_eq!(net.solve(s, t), (7, 15));
+        assert_eq!(routes.map(|id| net.flow(id)), [4, 0, 1, 2]);
+        // nothing left to send
+        assert_eq!(net.solve(s, t), (0, 0));
+
+        let mut net = MinCostFlow::new(3);
+        net.add_edge(0, 1, 10, 1);
+        net.add_edge(1, 2, 10, 1);
+        net.add_edge(0, 2, 3, 5);
+        assert_eq!(net.solve_limited(0, 2, 12), (12, 10 * 2 + 2 * 5));
+    }
+
+    #[test]
+    fn test_negative_costs_without_cycles() {
+        // a chain of hubs, each fanning out to the same leaves: acyclic, but SPFA relaxes the sink
+        // once per leaf per hub, far more than n times
+        let (r, k) = (10, 10);
+        let (s, t) = (0, r + k + 1);
+        let mut net = MinCostFlow::new(r + k + 2);
+        net.add_edge(s, 1, 1, 0);
+        for h in 1..=r {
+            if h < r {
+                net.add_edge(h, h + 1, 1, -100);
+            }
+            for i in 0..k {
+                net.add_edge(h, r + 1 + i, 1, -(i as i64));
+            }
+        }
+        for i in 0..k {
+            net.add_edge(r + 1 + i, t, 1, 0);
+        }
+        assert_eq!(net.solve(s, t), (1, -100 * (r as i64 - 1) - (k as i64 - 1)));
+    }
+
+    fn permutations(n: usize) -> Vec<Vec<usize>> {
+        if n == 0 {
+            return vec![vec![]];
+        }
+        permutations(n - 1)
+            .into_iter()
+            .flat_map(|p| (0..n).map(move |i| {
+                let mut q = p.clone();
+                q.insert(i, n - 1);
+                q
+            }))
+            .collect()
+    }
+
+    #[test]
+    fn test_assignment_with_negative_costs() {
+        let mut next = test_rng(8);
+        let n = 5;
+        for _ in 0..10 {
+            let cost: Vec<Vec<i64>> = (0..n).map(|_| (0..n).map(|_| (next() % 41) as i64 - 20).collect()).collect();
+            let mut net = MinCostFlow::new(2 * n + 2);
+            let (s, t) = (2 * n, 2 * n + 1);
+            for (i, row) in cost.iter().enumerate() {
+                net.add_edge(s, i, 1, 0);
+                net.add_edge(n + i, t, 1, 0);
+                for (j, &c) in row.iter().enumerate() {
+                    net.add_edge(i, n + j, 1, c);
+                }
+            }
+            let best = permutations(n).iter().map(|p| (0..n).map(|i| cost[i][p[i]]).sum::<i64>()).min();
+            assert_eq!(net.solve(s, t), (n as i64, best.unwrap()));
+        }
+    }
+}
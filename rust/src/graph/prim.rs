@@ -0,0 +1,124 @@
+// rust/src/graph/prim.rs
+use super::Graph;
+use crate::heap::AddressableHeap;
+use crate::indexed_heap::IndexedHeap;
+
+/// Grows a spanning tree from every not yet reached vertex in index order. `adjacent(u)` yields
+/// `(v, weight, tag)`; among equal weights the smaller tag wins, and `best[v]` ends up as the
+/// `(u, weight, tag)` of the edge that attached `v`. Heap items are `((weight, tag), v)`.
+fn grow<W, H, I, F>(n: usize, mut adjacent: F) -> Vec<Option<(usize, W, usize)>>
+where
+    W: Copy + Ord,
+    H: AddressableHeap<Item = ((W, usize), usize)> + Default,
+    I: IntoIterator<Item = (usize, W, usize)>,
+    F: FnMut(usize) -> I,
+{
+    let mut best: Vec<Option<(usize, W, usize)>> = vec![None; n];
+    let mut done = vec![false; n];
+    let mut handle = vec![None; n];
+    let mut heap = H::default();
+    for root in 0..n {
+        if done[root] {
+            continue;
+        }
+        done[root] = true;
+        let mut u = root;
+        loop {
+            for (v, w, tag) in adjacent(u) {
+                if done[v] {
+                    continue;
+                }
+                let improved = match (handle[v], best[v]) {
+                    (Some(h), Some((_, bw, btag))) => (w, tag) < (bw, btag) && heap.decrease_key(h, ((w, tag), v)),
+                    _ => {
+                        handle[v] = Some(heap.push_handle(((w, tag), v)));
+                        true
+                    }
+                };
+                if improved {
+                    best[v] = Some((u, w, tag));
+                }
+            }
+            match heap.pop() {
+                Some((_, v)) => {
+                    done[v] = true;
+                    u = v;
+                }
+                None => break,
+            }
+        }
+    }
+    best
+}
+
+/// Prim's minimum spanning forest of an undirected `Graph`, using an `IndexedHeap` keyed by vertex
+/// so each vertex holds one entry that relaxations lower in place. Returns the chosen edge ids in
+/// increasing order; ties are broken by edge id, so the forest is the same one `mst::kruskal` picks
+/// from `g.edge_list()`. Panics if `g` is directed.
+/// Complexity: O((n + m) log n)
+pub fn prim<W: Copy + Ord>(g: &Graph<W>) -> Vec<usize> {
+    prim_with::<W, IndexedHeap<usize, (W, usize)>>(g)
+}
+
+/// `prim` on any addressable priority queue of `((weight, edge id), vertex)` items.
+pub fn prim_with<W, H>(g: &Graph<W>) -> Vec<usize>
+where
+    W: Copy + Ord,
+    H: AddressableHeap<Item = ((W, usize), usize)> + Default,
+{
+    assert!(!g.is_directed(), "prim needs an undirected graph");
+    let best = grow::<W, H, _, _>(g.num_nodes(), |u| g.adjacent(u).map(|(v, id)| (v, *g.weight(id), id)));
+    let mut tree: Vec<usize> = best.into_iter().flatten().map(|(_, _, id)| id).collect();
+    tree.sort_unstable();
+    tree
+}
+
+/// Prim's algorithm on an implicit undirected graph with `n` vertices, for dense or generated graphs
+/// (complete geometric graphs, grids) where materializing and sorting every edge is the expensive
+/// part. `neighbors(u)` yields `(v, weight)` and must be symmetric. Returns, for every vertex, the
+/// tree parent and the weight of the edge to it; roots of the forest get None.
+/// Complexity: O(n log n + m log n), m = total number of pairs `neighbors` yields
+pub fn prim_implicit<W, I, F>(n: usize, mut neighbors: F) -> Vec<Option<(usize, W)>>
+where
+    W: Copy + Ord,
+    I: IntoIterator<Item = (usize, W)>,
+    F: FnMut(usize) -> I,
+{
+    grow::<W, IndexedHeap<usize, (W, usize)>, _, _>(n, |u| neighbors(u).into_iter().map(move |(v, w)| (v, w, u)))
+        .into_iter()
+        .map(|b| b.map(|(u, w, _)| (u, w)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fibonacci_heap::FibonacciHeap;
+    use crate::rng::test_rng;
+    use crate::mst::kruskal;
+
+    #[test]
+    fn test_prim_matches_kruskal() {
+        let mut next = test_rng(5);
+        for _ in 0..20 {
+            let n = 30;
+            // few distinct weights so ties are common, and sometimes a disconnected forest
+            let edges: Vec<(usize, usize, u32)> = (0..next() as usize % 80).map(|_| (next() as usize % n, next() as usize % n, (next() % 5) as u32)).collect();
+            let g = Graph::from_edges(n, edges.iter().copied(), false);
+            assert_eq!(prim(&g), kruskal(n, &edges));
+            assert_eq!(prim_with::<_, FibonacciHeap<_>>(&g), kruskal(n, &edges));
+        }
+    }
+
+    #[test]
+    fn test_prim_implicit_on_points() {
+        // complete graph on points of a line, weight = distance: the MST joins consecutive points
+        let xs = [7i64, 1, 12, 4, 30];
+        let parent = prim_implicit(xs.len(), |u| (0..xs.len()).filter(move |&v| v != u).map(move |v| (v, (xs[u] - xs[v]).abs())));
+        assert_eq!(parent[0], None);
+        let total: i64 = parent.iter().flatten().map(|&(_, w)| w).sum();
+        assert_eq!(total, 29);
+        assert_eq!(parent.iter().filter(|p| p.is_none()).count(), 1);
+        assert_eq!(parent[4], Some((2, 18)));
+    }
+}
@@ -0,0 +1,141 @@
+// rust/src/graph/scc.rs
+use super::Graph;
+
+/// Strongly connected components of a directed graph. Component ids are a topological order of the
+/// condensation: every edge `u -> v` has `component[u] <= component[v]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scc {
+    pub component: Vec<usize>,
+    pub count: usize,
+}
+
+impl Scc {
+    /// Vertices of every component, each list in increasing order.
+    pub fn members(&self) -> Vec<Vec<usize>> {
+        let mut members = vec![Vec::new(); self.count];
+        self.component.iter().enumerate().for_each(|(v, &c)| members[c].push(v));
+        members
+    }
+
+    /// The condensation DAG: one vertex per component and one edge per pair of components joined by
+    /// at least one edge of `g`, weighted with the id of the first such edge. Edges are added in
+    /// increasing `(from, to)` order, so its vertex order is already topological.
+    /// Complexity: O(n + m log m)
+    pub fn condensation<W>(&self, g: &Graph<W>) -> Graph<usize> {
+        let mut edges: Vec<(usize, usize, usize)> = g
+            .edges()
+            .enumerate()
+            .map(|(id, (u, v, _))| (self.component[u], self.component[v], id))
+            .filter(|&(a, b, _)| a != b)
+            .collect();
+        edges.sort_unstable();
+        edges.dedup_by_key(|&mut (a, b, _)| (a, b));
+        Graph::from_edges(self.count, edges, true)
+    }
+}
+
+/// Tarjan's algorithm, iterative so deep graphs cannot overflow the stack. Panics if `g` is
+/// undirected (its components are just the connected components).
+/// Complexity: O(n + m)
+pub fn strongly_connected_components<W>(g: &Graph<W>) -> Scc {
+    assert!(g.is_directed(), "strongly connected components need a directed graph");
+    let n = g.num_nodes();
+    const UNVISITED: usize = usize::MAX;
+    let mut index = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut stack = Vec::new();
+    let mut component = vec![UNVISITED; n];
+    let mut found = 0;
+    let mut counter = 0;
+    for root in g.nodes() {
+        if index[root] != UNVISITED {
+            continue;
+        }
+        index[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        stack.push(root);
+        on_stack[root] = true;
+        let mut calls = vec![(root, g.neighbors(root))];
+        while let Some((u, next)) = calls.last_mut() {
+            let u = *u;
+            if let Some(v) = next.next() {
+                if index[v] == UNVISITED {
+                    index[v] = counter;
+                    low[v] = counter;
+                    counter += 1;
+                    stack.push(v);
+                    on_stack[v] = true;
+                    calls.push((v, g.neighbors(v)));
+                } else if on_stack[v] {
+                    low[u] = low[u].min(index[v]);
+                }
+                continue;
+            }
+            calls.pop();
+            if let Some(&(p, _)) = calls.last() {
+                low[p] = low[p].min(low[u]);
+            }
+            if low[u] == index[u] {
+                // u is the root of a component: everything above it on the stack
+                loop {
+                    let w = stack.pop().expect("u is on the stack");
+                    on_stack[w] = false;
+                    component[w] = found;
+                    if w == u {
+                        break;
+                    }
+                }
+                found += 1;
+            }
+        }
+    }
+    // Tarjan emits sink components first; flip the ids so they follow the edges instead
+    component.iter_mut().for_each(|c| *c = found - 1 - *c);
+    Scc { component, count: found }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::graph::topological_sort;
+
+    #[test]
+    fn test_components_and_condensation() {
+        // {0, 1, 2} -> {3, 4} -> {5}, plus {6} -> {0, 1, 2}
+        let edges = [(0, 1), (1, 2), (2, 0), (2, 3), (3, 4), (4, 3), (1, 4), (4, 5), (6, 0), (5, 5)];
+        let g = Graph::from_unweighted_edges(7, &edges, true);
+        let scc = strongly_connected_components(&g);
+        assert_eq!(scc.count, 4);
+        let mut members = scc.members();
+        members.sort();
+        assert_eq!(members, vec![vec![0, 1, 2], vec![3, 4], vec![5], vec![6]]);
+        assert!(g.edges().all(|(u, v, _)| scc.component[u] <= scc.component[v]));
+
+        let dag = scc.condensation(&g);
+        assert_eq!(dag.num_nodes(), 4);
+        // 6 -> A, A -> B (first via edge 3, also via edge 6), B -> C
+        assert_eq!(dag.num_edges(), 3);
+        let c = |v: usize| scc.component[v];
+        assert!(dag.edges().any(|(a, b, &id)| (a, b, id) == (c(2), c(3), 3)));
+        assert_eq!(topological_sort(&dag), Ok(vec![0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn test_against_reachability() {
+        let mut next = test_rng(21);
+        let n = 25;
+        let edges: Vec<(usize, usize)> = (0..45).map(|_| (next() as usize % n, next() as usize % n)).collect();
+        let g = Graph::from_unweighted_edges(n, &edges, true);
+        let scc = strongly_connected_components(&g);
+        let reach: Vec<Vec<bool>> = g
+            .nodes()
+            .map(|s| crate::graph::bfs(&g, s).dist.iter().map(Option::is_some).collect())
+            .collect();
+        for (u, v) in (0..n).flat_map(|u| (0..n).map(move |v| (u, v))) {
+            assert_eq!(scc.component[u] == scc.component[v], reach[u][v] && reach[v][u]);
+        }
+    }
+}
@@ -0,0 +1,378 @@
+// rust/src/graph/shortest_path.rs
+use super::Graph;
+use super::traversal::path_to;
+use crate::heap::AddressableHeap;
+use crate::indexed_heap::IndexedHeap;
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Single-source shortest path tree. `dist[v]` is None when `v` is unreachable; `parent[v]` is the
+/// predecessor of `v` on a shortest path and `parent_edge[v]` the id of the edge taken from it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<W> {
+    pub source: usize,
+    pub dist: Vec<Option<W>>,
+    pub parent: Vec<Option<usize>>,
+    pub parent_edge: Vec<Option<usize>>,
+}
+
+impl<W> ShortestPaths<W> {
+    /// Vertices of a shortest path from the source to `target`, or None if it is unreachable.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        path_to(&self.parent, |v| self.dist[v].is_some(), target)
+    }
+
+    /// Edge ids of a shortest path from the source to `target`, or None if it is unreachable.
+    pub fn edge_path_to(&self, target: usize) -> Option<Vec<usize>> {
+        self.dist[target].as_ref()?;
+        let mut edges = Vec::new();
+        let mut v = target;
+        while let (Some(p), Some(id)) = (self.parent[v], self.parent_edge[v]) {
+            edges.push(id);
+            v = p;
+        }
+        edges.reverse();
+        Some(edges)
+    }
+}
+
+fn unreached<D: Clone>(src: usize, n: usize) -> ShortestPaths<D> {
+    ShortestPaths {
+        source: src,
+        dist: vec![None; n],
+        parent: vec![None; n],
+        parent_edge: vec![None; n],
+    }
+}
+
+/// Dijkstra's algorithm from `src`, driven by an `IndexedHeap` keyed by vertex so every relaxation
+/// is a decrease-key rather than a duplicate entry. `W::default()` is the zero distance.
+/// Panics on a negative edge weight.
+/// Complexity: O((n + m) log n)
+pub fn dijkstra<W>(g: &Graph<W>, src: usize) -> ShortestPaths<W>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    dijkstra_with::<W, IndexedHeap<usize, W>>(g, src)
+}
+
+/// `dijkstra` on any addressable priority queue of `(distance, vertex)` items, such as
+/// `PairingHeap` or `FibonacciHeap` (whose O(1) decrease-key gives O(m + n log n)).
+pub fn dijkstra_with<W, H>(g: &Graph<W>, src: usize) -> ShortestPaths<W>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+    H: AddressableHeap<Item = (W, usize)> + Default,
+{
+    let n = g.num_nodes();
+    let mut sp = unreached(src, n);
+    let mut done = vec![false; n];
+    let mut handle = vec![None; n];
+    let mut heap = H::default();
+    sp.dist[src] = Some(W::default());
+    handle[src] = Some(heap.push_handle((W::default(), src)));
+    while let Some((d, u)) = heap.pop() {
+        done[u] = true;
+        for (v, id) in g.adjacent(u) {
+            let w = *g.weight(id);
+            assert!(w >= W::default(), "dijkstra requires non-negative weights (edge {})", id);
+            if done[v] {
+                continue;
+            }
+            let nd = d + w;
+            let improved = match (handle[v], sp.dist[v]) {
+                (Some(h), Some(old)) => nd < old && heap.decrease_key(h, (nd, v)),
+                _ => {
+                    handle[v] = Some(heap.push_handle((nd, v)));
+                    true
+                }
+            };
+            if improved {
+                sp.dist[v] = Some(nd);
+                sp.parent[v] = Some(u);
+                sp.parent_edge[v] = Some(id);
+            }
+        }
+    }
+    sp
+}
+
+/// A* search on an implicit graph: `neighbors(n)` yields `(next, edge cost)` and `heuristic(n)`
+/// estimates the remaining cost to the nearest goal. Returns the cost and nodes of a cheapest path
+/// from `start` to a node satisfying `is_goal`, or None if none is reachable. The result is optimal
+/// when the heuristic never overestimates; with a consistent heuristic (`h(n) <= cost + h(next)`)
+/// every node is expanded at most once, otherwise improved nodes are reopened. Costs must be
+/// non-negative, with `W::default()` as zero; a zero heuristic makes this Dijkstra.
+/// Nodes can be any hashable state (grid cells, puzzle positions), discovered lazily.
+/// Complexity: O(m log n) over the nodes actually reached, with a consistent heuristic
+pub fn astar_implicit<N, W, I, F, H, G>(start: N, mut is_goal: G, mut neighbors: F, mut heuristic: H) -> Option<(W, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    W: Copy + Ord + Add<Output = W> + Default,
+    I: IntoIterator<Item = (N, W)>,
+    F: FnMut(&N) -> I,
+    H: FnMut(&N) -> W,
+    G: FnMut(&N) -> bool,
+{
+    // best known cost from start, and the node it was reached from
+    let mut best: HashMap<N, (W, Option<N>)> = HashMap::new();
+    let mut open = IndexedHeap::new();
+    best.insert(start.clone(), (W::default(), None));
+    open.push(start.clone(), heuristic(&start));
+    while let Some((u, _)) = open.pop() {
+        let g = best[&u].0;
+        if is_goal(&u) {
+            let mut path = vec![u];
+            while let Some(p) = best[path.last().expect("path is non-empty")].1.clone() {
+                path.push(p);
+            }
+            path.reverse();
+            return Some((g, path));
+        }
+        for (v, w) in neighbors(&u) {
+            let ng = g + w;
+            if best.get(&v).is_some_and(|&(old, _)| old <= ng) {
+                continue;
+            }
+            let f = ng + heuristic(&v);
+            best.insert(v.clone(), (ng, Some(u.clone())));
+            // lower it if still open, otherwise (re)open it
+            if !open.decrease_key(&v, f) {
+                open.push(v, f);
+            }
+        }
+    }
+    None
+}
+
+/// A* from `src` to `goal` in a `Graph`, with `heuristic(v)` estimating the cost from `v` to `goal`.
+/// Returns the cost and vertices of a cheapest path, or None if `goal` is unreachable; see
+/// `astar_implicit` for the conditions on the heuristic.
+pub fn astar<W, H>(g: &Graph<W>, src: usize, goal: usize, heuristic: H) -> Option<(W, Vec<usize>)>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+    H: FnMut(&usize) -> W,
+{
+    astar_implicit(src, |&v| v == goal, |&u| g.out_edges(u).map(|(v, &w)| (v, w)), heuristic)
+}
+
+/// Weight of edge `id` as a distance increment, for the integer-weight searches below.
+fn small_weight<W: Copy + TryInto<usize>>(g: &Graph<W>, id: usize) -> usize {
+    match (*g.weight(id)).try_into() {
+        Ok(w) => w,
+        Err(_) => panic!("edge {} has a negative or oversized weight", id),
+    }
+}
+
+/// 0-1 BFS from `src` for graphs whose weights are all 0 or 1: a deque stands in for the heap,
+/// with 0-edges relaxed onto the front and 1-edges onto the back, so vertices still leave it in
+/// order of distance. A vertex may be queued twice; the stale copy is skipped.
+/// Panics on any other weight.
+/// Complexity: O(n + m)
+pub fn zero_one_bfs<W: Copy + TryInto<usize>>(g: &Graph<W>, src: usize) -> ShortestPaths<usize> {
+    let mut sp = unreached(src, g.num_nodes());
+    let mut done = vec![false; g.num_nodes()];
+    let mut deque = VecDeque::from([src]);
+    sp.dist[src] = Some(0);
+    while let Some(u) = deque.pop_front() {
+        if std::mem::replace(&mut done[u], true) {
+            continue;
+        }
+        let d = sp.dist[u].expect("queued vertices have a distance");
+        for (v, id) in g.adjacent(u) {
+            let w = small_weight(g, id);
+            assert!(w <= 1, "zero_one_bfs requires weights 0 or 1 (edge {})", id);
+            if sp.dist[v].is_none_or(|old| d + w < old) {
+                sp.dist[v] = Some(d + w);
+                sp.parent[v] = Some(u);
+                sp.parent_edge[v] = Some(id);
+                if w == 0 {
+                    deque.push_front(v);
+                } else {
+                    deque.push_back(v);
+                }
+            }
+        }
+    }
+    sp
+}
+
+/// Dial's algorithm from `src` for small non-negative integer weights: Dijkstra with a bucket queue
+/// in place of the heap. Every pending distance lies within `C` of the current one (`C` the largest
+/// weight), so `C + 1` buckets used circularly suffice and the scan only ever moves forward.
+/// Panics on a negative weight.
+/// Complexity: O(n + m + D), D = the largest finite distance
+pub fn dial<W: Copy + TryInto<usize>>(g: &Graph<W>, src: usize) -> ShortestPaths<usize> {
+    let n = g.num_nodes();
+    let mut sp = unreached(src, n);
+    let max_weight = (0..g.num_edges()).map(|id| small_weight(g, id)).max().unwrap_or(0);
+    let slots = max_weight + 1;
+    let mut buckets = vec![Vec::new(); slots];
+    let mut done = vec![false; n];
+    sp.dist[src] = Some(0);
+    buckets[0].push(src);
+    let (mut d, mut pending) = (0, 1);
+    while pending > 0 {
+        let slot = d % slots;
+        while let Some(u) = buckets[slot].pop() {
+            pending -= 1;
+            // entries superseded by a shorter distance are left behind and skipped here
+            if done[u] || sp.dist[u] != Some(d) {
+                continue;
+            }
+            done[u] = true;
+            for (v, id) in g.adjacent(u) {
+                let nd = d + small_weight(g, id);
+                if !done[v] && sp.dist[v].is_none_or(|old| nd < old) {
+                    sp.dist[v] = Some(nd);
+                    sp.parent[v] = Some(u);
+                    sp.parent_edge[v] = Some(id);
+                    buckets[nd % slots].push(v);
+                    pending += 1;
+                }
+            }
+        }
+        d += 1;
+    }
+    sp
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fibonacci_heap::FibonacciHeap;
+    use crate::pairing_heap::PairingHeap;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_dijkstra_paths() {
+        let g = Graph::from_edges(6, [(0, 1, 7u64), (0, 2, 2), (2, 1, 3), (1, 3, 1), (2, 3, 8), (3, 4, 2), (0, 1, 4)], true);
+        let sp = dijkstra(&g, 0);
+        assert_eq!(sp.dist, vec![Some(0), Some(4), Some(2), Some(5), Some(7), None]);
+        assert_eq!(sp.path_to(4), Some(vec![0, 1, 3, 4]));
+        // the later parallel edge 0 -> 1 of weight 4 beats both 0 -> 1 (7) and 0 -> 2 -> 1 (5)
+        assert_eq!(sp.edge_path_to(4), Some(vec![6, 3, 5]));
+        assert_eq!(sp.path_to(0), Some(vec![0]));
+        assert_eq!(sp.edge_path_to(0), Some(vec![]));
+        assert_eq!(sp.path_to(5), None);
+        assert_eq!(sp.edge_path_to(5), None);
+    }
+
+    #[test]
+    fn test_against_bellman_ford() {
+        let mut next = test_rng(11);
+        for directed in [true, false] {
+            let n = 40;
+            let edges: Vec<(usize, usize, i64)> = (0..150).map(|_| (next() as usize % n, next() as usize % n, (next() % 50) as i64)).collect();
+            let g = Graph::from_edges(n, edges.iter().copied(), directed);
+            let sp = dijkstra(&g, 0);
+            let mut reference = vec![None; n];
+            reference[0] = Some(0i64);
+            for _ in 0..n {
+                for &(u, v, w) in &edges {
+                    let arcs = if directed { vec![(u, v)] } else { vec![(u, v), (v, u)] };
+                    for (a, b) in arcs {
+                        if let Some(da) = reference[a]
+                            && reference[b].is_none_or(|db| da + w < db)
+                        {
+                            reference[b] = Some(da + w);
+                        }
+                    }
+                }
+            }
+            assert_eq!(sp.dist, reference);
+            // every tree edge is tight
+            for v in 0..n {
+                if let (Some(p), Some(id)) = (sp.parent[v], sp.parent_edge[v]) {
+                    assert_eq!(sp.dist[p].unwrap() + *g.weight(id), sp.dist[v].unwrap());
+                    assert_eq!(g.opposite(id, p), v);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_dijkstra_on_other_heaps() {
+        let mut next = test_rng(12);
+        let n = 50;
+        let edges: Vec<(usize, usize, u64)> = (0..200).map(|_| (next() as usize % n, next() as usize % n, next() % 30)).collect();
+        let g = Graph::from_edges(n, edges, true);
+        let dist = dijkstra(&g, 0).dist;
+        assert_eq!(dijkstra_with::<_, PairingHeap<_>>(&g, 0).dist, dist);
+        assert_eq!(dijkstra_with::<_, FibonacciHeap<_>>(&g, 0).dist, dist);
+    }
+
+    #[test]
+    fn test_astar_on_grid() {
+        // '#' is a wall; moves are 4-directional at cost 1
+        let grid = ["......", ".####.", "...#..", ".#.#.#", ".#....", "......"];
+        let cells: Vec<&[u8]> = grid.iter().map(|r| r.as_bytes()).collect();
+        let (h, w) = (cells.len() as i64, cells[0].len() as i64);
+        let open = |r: i64, c: i64| (0..h).contains(&r) && (0..w).contains(&c) && cells[r as usize][c as usize] == b'.';
+        let goal = (2i64, 4i64);
+        let manhattan = |&(r, c): &(i64, i64)| (r - goal.0).abs() + (c - goal.1).abs();
+        let neighbors = |&(r, c): &(i64, i64)| {
+            [(r + 1, c), (r - 1, c), (r, c + 1), (r, c - 1)].into_iter().filter(|&(r, c)| open(r, c)).map(|p| (p, 1i64))
+        };
+        let (cost, path) = astar_implicit((0, 0), |&p| p == goal, neighbors, manhattan).unwrap();
+        assert_eq!(cost, 8);
+        assert_eq!(path.len(), 9);
+        assert!(path.windows(2).all(|s| (s[0].0 - s[1].0).abs() + (s[0].1 - s[1].1).abs() == 1));
+        assert_eq!((path[0], path[8]), ((0, 0), goal));
+        assert!(astar_implicit((0, 0), |&p| p == (3, 0), neighbors, |_| 0).is_some());
+        assert!(astar_implicit((0, 0), |&p| p == (1, 1), neighbors, |_| 0).is_none());
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let mut next = test_rng(41);
+        let n = 50;
+        let edges: Vec<(usize, usize, u64)> = (0..200).map(|_| (next() as usize % n, next() as usize % n, next() % 20)).collect();
+        let g = Graph::from_edges(n, edges.iter().copied(), true);
+        let sp = dijkstra(&g, 0);
+        for goal in 0..n {
+            // the zero heuristic, and the exact distance to goal as a perfect one
+            let to_goal = dijkstra(&g.reversed(), goal);
+            let exact = |v: &usize| to_goal.dist[*v].unwrap_or(0);
+            for result in [astar(&g, 0, goal, |_| 0), astar(&g, 0, goal, exact)] {
+                assert_eq!(result.as_ref().map(|r| r.0), sp.dist[goal]);
+                if let Some((cost, path)) = result {
+                    let walked: u64 = path.windows(2).map(|s| g.out_edges(s[0]).filter(|e| e.0 == s[1]).map(|e| *e.1).min().unwrap()).sum();
+                    assert_eq!((walked, path[0], *path.last().unwrap()), (cost, 0, goal));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_integer_weight_searches_match_dijkstra() {
+        let mut next = test_rng(23);
+        for directed in [true, false] {
+            let n = 60;
+            let arcs: Vec<(usize, usize)> = (0..250).map(|_| (next() as usize % n, next() as usize % n)).collect();
+            let binary = Graph::from_edges(n, arcs.iter().map(|&(u, v)| (u, v, (next() % 2) as u8)), directed);
+            let small = Graph::from_edges(n, arcs.iter().map(|&(u, v)| (u, v, (next() % 7) as u32)), directed);
+            for (g, sp) in [(&binary, zero_one_bfs(&binary, 0)), (&binary, dial(&binary, 0))] {
+                assert_eq!(sp.dist, dijkstra(g, 0).dist.iter().map(|d| d.map(usize::from)).collect::<Vec<_>>());
+                check_tree(&sp, |id| *g.weight(id) as usize);
+            }
+            let sp = dial(&small, 0);
+            assert_eq!(sp.dist, dijkstra(&small, 0).dist.iter().map(|d| d.map(|d| d as usize)).collect::<Vec<_>>());
+            check_tree(&sp, |id| *small.weight(id) as usize);
+        }
+    }
+
+    fn check_tree(sp: &ShortestPaths<usize>, weight: impl Fn(usize) -> usize) {
+        for (v, edge) in sp.parent_edge.iter().enumerate() {
+            if let (Some(p), Some(id)) = (sp.parent[v], *edge) {
+                assert_eq!(sp.dist[p].unwrap() + weight(id), sp.dist[v].unwrap());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "weights 0 or 1")]
+    fn test_zero_one_bfs_rejects_other_weights() {
+        zero_one_bfs(&Graph::from_edges(2, [(0, 1, 2u8)], true), 0);
+    }
+}
@@ -0,0 +1,147 @@
+// rust/src/graph/topological.rs
+use super::Graph;
+use super::traversal::{Visitor, dfs_with};
+use std::collections::VecDeque;
+use std::ops::Add;
+
+/// Remembers the first back edge a DFS reports.
+#[derive(Default)]
+struct FirstBackEdge(Option<(usize, usize)>);
+
+impl Visitor for FirstBackEdge {
+    fn back_edge(&mut self, u: usize, v: usize, _id: usize) {
+        self.0.get_or_insert((u, v));
+    }
+}
+
+/// DFS over the whole graph. Ok(reverse postorder) if it is acyclic, else Err(a cycle), given as
+/// vertices along its edges without repeating the first one at the end.
+fn dfs_order<W>(g: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    let roots: Vec<usize> = g.nodes().collect();
+    let mut back = FirstBackEdge::default();
+    let dfs = dfs_with(g, &roots, &mut back);
+    match back.0 {
+        None => Ok(dfs.postorder.into_iter().rev().collect()),
+        Some((u, v)) => {
+            // v is an ancestor of u, so the tree path v ~> u plus the edge u -> v is a cycle
+            let mut cycle = vec![u];
+            while *cycle.last().expect("cycle is non-empty") != v {
+                cycle.push(dfs.parent[*cycle.last().expect("cycle is non-empty")].expect("v is an ancestor of u"));
+            }
+            cycle.reverse();
+            Err(cycle)
+        }
+    }
+}
+
+/// Kahn's algorithm: repeatedly emits a vertex with no remaining in-edges, smallest index first
+/// among those that became available at the same time (a FIFO queue). Returns the ordering, or
+/// Err(cycle) when some vertices never become free; the cycle is given as vertices along its edges
+/// without repeating the first one at the end. Panics if `g` is undirected.
+/// Complexity: O(n + m)
+pub fn topological_sort<W>(g: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    assert!(g.is_directed(), "topological order needs a directed graph");
+    let mut indegree = vec![0usize; g.num_nodes()];
+    g.edges().for_each(|(_, v, _)| indegree[v] += 1);
+    let mut queue: VecDeque<usize> = g.nodes().filter(|&v| indegree[v] == 0).collect();
+    let mut order = Vec::with_capacity(g.num_nodes());
+    while let Some(u) = queue.pop_front() {
+        order.push(u);
+        for v in g.neighbors(u) {
+            indegree[v] -= 1;
+            if indegree[v] == 0 {
+                queue.push_back(v);
+            }
+        }
+    }
+    if order.len() == g.num_nodes() {
+        return Ok(order);
+    }
+    // the leftover subgraph has no source, so it contains a cycle; let a DFS name one
+    match dfs_order(g) {
+        Err(cycle) => Err(cycle),
+        Ok(_) => unreachable!("Kahn's algorithm stalled on an acyclic graph"),
+    }
+}
+
+/// Topological order as the reverse DFS finishing order, or Err(cycle) as in `topological_sort`.
+/// Panics if `g` is undirected.
+/// Complexity: O(n + m)
+pub fn topological_sort_dfs<W>(g: &Graph<W>) -> Result<Vec<usize>, Vec<usize>> {
+    assert!(g.is_directed(), "topological order needs a directed graph");
+    dfs_order(g)
+}
+
+/// Heaviest path of a DAG, for critical-path scheduling: returns its total weight and vertices
+/// (a single vertex with weight `W::default()` if no path beats that, e.g. when there are no edges,
+/// and an empty path for an empty graph). Weights may be negative. Err(cycle) if `g` is cyclic.
+/// Complexity: O(n + m)
+pub fn longest_path_in_dag<W>(g: &Graph<W>) -> Result<(W, Vec<usize>), Vec<usize>>
+where
+    W: Copy + Ord + Add<Output = W> + Default,
+{
+    let order = topological_sort(g)?;
+    // best[v] = weight of the heaviest path ending at v, and where it came from
+    let mut best = vec![W::default(); g.num_nodes()];
+    let mut parent: Vec<Option<usize>> = vec![None; g.num_nodes()];
+    for &u in &order {
+        for (v, &w) in g.out_edges(u) {
+            if best[u] + w > best[v] {
+                best[v] = best[u] + w;
+                parent[v] = Some(u);
+            }
+        }
+    }
+    let Some(end) = g.nodes().max_by_key(|&v| best[v]) else {
+        return Ok((W::default(), Vec::new()));
+    };
+    let mut path = vec![end];
+    while let Some(p) = parent[*path.last().expect("path is non-empty")] {
+        path.push(p);
+    }
+    path.reverse();
+    Ok((best[end], path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_topological<W>(g: &Graph<W>, order: &[usize]) -> bool {
+        let mut pos = vec![usize::MAX; g.num_nodes()];
+        order.iter().enumerate().for_each(|(i, &v)| pos[v] = i);
+        order.len() == g.num_nodes() && g.edges().all(|(u, v, _)| pos[u] < pos[v])
+    }
+
+    fn is_cycle<W>(g: &Graph<W>, cycle: &[usize]) -> bool {
+        !cycle.is_empty() && (0..cycle.len()).all(|i| g.neighbors(cycle[i]).any(|v| v == cycle[(i + 1) % cycle.len()]))
+    }
+
+    #[test]
+    fn test_orders_and_cycles() {
+        let dag = Graph::from_unweighted_edges(6, &[(5, 2), (5, 0), (4, 0), (4, 1), (2, 3), (3, 1)], true);
+        assert_eq!(topological_sort(&dag), Ok(vec![4, 5, 2, 0, 3, 1]));
+        assert!(is_topological(&dag, &topological_sort_dfs(&dag).unwrap()));
+
+        let cyclic = Graph::from_unweighted_edges(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 2), (5, 0)], true);
+        let cycle = topological_sort(&cyclic).unwrap_err();
+        assert!(is_cycle(&cyclic, &cycle));
+        assert_eq!(cycle.len(), 3);
+        assert!(is_cycle(&cyclic, &topological_sort_dfs(&cyclic).unwrap_err()));
+
+        let self_loop = Graph::from_unweighted_edges(2, &[(0, 1), (1, 1)], true);
+        assert_eq!(topological_sort(&self_loop), Err(vec![1]));
+    }
+
+    #[test]
+    fn test_longest_path() {
+        // task durations on the edges; the critical path is 0 -> 2 -> 3 -> 4
+        let g = Graph::from_edges(6, [(0, 1, 3), (0, 2, 2), (1, 3, 1), (2, 3, 4), (3, 4, 5), (1, 4, 2), (5, 4, -1)], true);
+        assert_eq!(longest_path_in_dag(&g), Ok((11, vec![0, 2, 3, 4])));
+        let g: Graph<i32> = Graph::directed(3);
+        assert_eq!(longest_path_in_dag(&g), Ok((0, vec![2])));
+        assert_eq!(longest_path_in_dag(&Graph::<i32>::directed(0)), Ok((0, vec![])));
+        let g = Graph::from_edges(2, [(0, 1, 1), (1, 0, 1)], true);
+        assert!(longest_path_in_dag(&g).is_err());
+    }
+}
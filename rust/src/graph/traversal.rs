@@ -0,0 +1,274 @@
+// rust/src/graph/traversal.rs
+use super::Graph;
+use std::collections::VecDeque;
+
+/// What a search does after `Visitor::discover` returns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    Continue,
+    /// Do not explore the out-edges of the vertex just discovered.
+    Prune,
+    /// End the whole search now.
+    Stop,
+}
+
+/// Callbacks fired by `bfs_with` / `dfs_with`; every method defaults to doing nothing, so a visitor
+/// implements only the events it cares about. `id` is the edge id in the `Graph`.
+pub trait Visitor {
+    fn discover(&mut self, _u: usize) -> Control {
+        Control::Continue
+    }
+
+    /// All out-edges of `u` have been explored (in DFS, its whole subtree).
+    fn finish(&mut self, _u: usize) {}
+
+    /// `u -> v` discovered `v`.
+    fn tree_edge(&mut self, _u: usize, _v: usize, _id: usize) {}
+
+    /// DFS only: `v` is an ancestor of `u` still on the stack, so the edge closes a cycle.
+    fn back_edge(&mut self, _u: usize, _v: usize, _id: usize) {}
+
+    /// Any other edge to an already discovered vertex: BFS non-tree edges, and DFS forward or cross
+    /// edges (which only occur in directed graphs).
+    fn non_tree_edge(&mut self, _u: usize, _v: usize, _id: usize) {}
+}
+
+impl Visitor for () {}
+
+/// Follows `parent` links back from `target`. Returns the path from its root to `target`, or None if
+/// `target` was not reached (has no parent and is not a root of the search).
+pub fn path_to(parent: &[Option<usize>], reached: impl Fn(usize) -> bool, target: usize) -> Option<Vec<usize>> {
+    if !reached(target) {
+        return None;
+    }
+    let mut path = vec![target];
+    while let Some(p) = parent[*path.last().expect("path is non-empty")] {
+        path.push(p);
+    }
+    path.reverse();
+    Some(path)
+}
+
+/// Result of a breadth-first search. `dist` counts edges from the nearest source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Bfs {
+    pub dist: Vec<Option<usize>>,
+    pub parent: Vec<Option<usize>>,
+    /// Vertices in discovery order.
+    pub order: Vec<usize>,
+}
+
+impl Bfs {
+    /// A shortest path (fewest edges) from a source to `target`, or None if it was not reached.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        path_to(&self.parent, |v| self.dist[v].is_some(), target)
+    }
+}
+
+/// Result of a depth-first search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Dfs {
+    pub parent: Vec<Option<usize>>,
+    /// Vertices in discovery order.
+    pub preorder: Vec<usize>,
+    /// Vertices in finishing order; reversed, it is a topological order of a DAG.
+    pub postorder: Vec<usize>,
+}
+
+impl Dfs {
+    /// The DFS tree path from its root to `target`, or None if it was not reached.
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        let mut reached = vec![false; self.parent.len()];
+        self.preorder.iter().for_each(|&v| reached[v] = true);
+        path_to(&self.parent, |v| reached[v], target)
+    }
+}
+
+// Records `v` at distance `d` and queues it unless the visitor prunes it; false means stop.
+fn bfs_discover<V: Visitor>(result: &mut Bfs, queue: &mut VecDeque<usize>, visitor: &mut V, v: usize, d: usize) -> bool {
+    result.dist[v] = Some(d);
+    result.order.push(v);
+    match visitor.discover(v) {
+        Control::Continue => queue.push_back(v),
+        Control::Prune => {}
+        Control::Stop => return false,
+    }
+    true
+}
+
+/// Breadth-first search from every vertex of `sources` at once, reporting events to `visitor`.
+/// Complexity: O(n + m)
+pub fn bfs_with<W, V: Visitor>(g: &Graph<W>, sources: &[usize], visitor: &mut V) -> Bfs {
+    let n = g.num_nodes();
+    let mut result = Bfs { dist: vec![None; n], parent: vec![None; n], order: Vec::new() };
+    let mut queue = VecDeque::new();
+    for &s in sources {
+        if result.dist[s].is_none() && !bfs_discover(&mut result, &mut queue, visitor, s, 0) {
+            return result;
+        }
+    }
+    while let Some(u) = queue.pop_front() {
+        let d = result.dist[u].expect("queued vertices are discovered");
+        for (v, id) in g.adjacent(u) {
+            if result.dist[v].is_some() {
+                visitor.non_tree_edge(u, v, id);
+                continue;
+            }
+            visitor.tree_edge(u, v, id);
+            result.parent[v] = Some(u);
+            if !bfs_discover(&mut result, &mut queue, visitor, v, d + 1) {
+                return result;
+            }
+        }
+        visitor.finish(u);
+    }
+    result
+}
+
+/// Breadth-first search from `src`.
+pub fn bfs<W>(g: &Graph<W>, src: usize) -> Bfs {
+    bfs_with(g, &[src], &mut ())
+}
+
+/// Iterative depth-first search from each of `roots` in turn (skipping roots already reached),
+/// reporting events to `visitor`. In an undirected graph the edge a vertex was discovered through is
+/// not reported again, and every other non-tree edge is reported once, as a back edge.
+/// Complexity: O(n + m)
+pub fn dfs_with<W, V: Visitor>(g: &Graph<W>, roots: &[usize], visitor: &mut V) -> Dfs {
+    let n = g.num_nodes();
+    let mut result = Dfs { parent: vec![None; n], preorder: Vec::new(), postorder: Vec::new() };
+    // 0 = unvisited, 1 = on the stack, 2 = finished
+    let mut state = vec![0u8; n];
+    let mut via = vec![usize::MAX; n];
+    for &root in roots {
+        if state[root] != 0 {
+            continue;
+        }
+        let mut stack = Vec::new();
+        state[root] = 1;
+        result.preorder.push(root);
+        match visitor.discover(root) {
+            Control::Continue => stack.push((root, g.adjacent(root))),
+            Control::Prune => {
+                state[root] = 2;
+                result.postorder.push(root);
+                visitor.finish(root);
+            }
+            Control::Stop => return result,
+        }
+        while let Some((u, edges)) = stack.last_mut() {
+            let u = *u;
+            let Some((v, id)) = edges.next() else {
+                stack.pop();
+                state[u] = 2;
+                result.postorder.push(u);
+                visitor.finish(u);
+                continue;
+            };
+            match state[v] {
+                0 => {
+                    visitor.tree_edge(u, v, id);
+                    result.parent[v] = Some(u);
+                    via[v] = id;
+                    state[v] = 1;
+                    result.preorder.push(v);
+                    match visitor.discover(v) {
+                        Control::Continue => stack.push((v, g.adjacent(v))),
+                        Control::Prune => {
+                            state[v] = 2;
+                            result.postorder.push(v);
+                            visitor.finish(v);
+                        }
+                        Control::Stop => return result,
+                    }
+                }
+                1 if g.is_directed() || id != via[u] => visitor.back_edge(u, v, id),
+                2 if g.is_directed() => visitor.non_tree_edge(u, v, id),
+                _ => {}
+            }
+        }
+    }
+    result
+}
+
+/// Depth-first search from `src`.
+pub fn dfs<W>(g: &Graph<W>, src: usize) -> Dfs {
+    dfs_with(g, &[src], &mut ())
+}
+
+/// Depth-first search covering the whole graph, starting new trees from vertices in index order.
+pub fn dfs_forest<W>(g: &Graph<W>) -> Dfs {
+    let roots: Vec<usize> = g.nodes().collect();
+    dfs_with(g, &roots, &mut ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bfs_distances_and_paths() {
+        let g = Graph::from_unweighted_edges(6, &[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4)], true);
+        let r = bfs(&g, 0);
+        assert_eq!(r.dist, vec![Some(0), Some(1), Some(1), Some(2), Some(3), None]);
+        assert_eq!(r.order, vec![0, 1, 2, 3, 4]);
+        assert_eq!(r.path_to(4), Some(vec![0, 1, 3, 4]));
+        assert_eq!(r.path_to(5), None);
+        // multi-source
+        let r = bfs_with(&g, &[0, 4], &mut ());
+        assert_eq!(r.dist[4], Some(0));
+        assert_eq!(r.path_to(0), Some(vec![0]));
+    }
+
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<String>,
+        stop_at: Option<usize>,
+    }
+
+    impl Visitor for Recorder {
+        fn discover(&mut self, u: usize) -> Control {
+            self.events.push(format!("d{}", u));
+            if self.stop_at == Some(u) { Control::Stop } else { Control::Continue }
+        }
+        fn finish(&mut self, u: usize) {
+            self.events.push(format!("f{}", u));
+        }
+        fn back_edge(&mut self, u: usize, v: usize, _id: usize) {
+            self.events.push(format!("b{}{}", u, v));
+        }
+        fn non_tree_edge(&mut self, u: usize, v: usize, _id: usize) {
+            self.events.push(format!("x{}{}", u, v));
+        }
+    }
+
+    #[test]
+    fn test_dfs_events() {
+        // 0 -> 1 -> 2 -> 0 is a cycle, 0 -> 2 is a forward edge, 3 -> 1 a cross edge
+        let g = Graph::from_unweighted_edges(4, &[(0, 1), (1, 2), (2, 0), (0, 2), (3, 1)], true);
+        let mut rec = Recorder::default();
+        let r = dfs_with(&g, &[0, 3], &mut rec);
+        assert_eq!(rec.events, ["d0", "d1", "d2", "b20", "f2", "f1", "x02", "f0", "d3", "x31", "f3"]);
+        assert_eq!(r.preorder, vec![0, 1, 2, 3]);
+        assert_eq!(r.postorder, vec![2, 1, 0, 3]);
+        assert_eq!(r.path_to(2), Some(vec![0, 1, 2]));
+
+        let mut rec = Recorder { stop_at: Some(2), ..Default::default() };
+        let r = dfs_with(&g, &[0], &mut rec);
+        assert_eq!(rec.events, ["d0", "d1", "d2"]);
+        assert_eq!(r.postorder, Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_undirected_dfs_reports_each_cycle_edge_once() {
+        // triangle 0-1-2 plus a parallel edge 2-3 / 3-2 and a pendant vertex 4
+        let g = Graph::from_unweighted_edges(5, &[(0, 1), (1, 2), (2, 0), (2, 3), (3, 2), (1, 4)], false);
+        let mut rec = Recorder::default();
+        let r = dfs_with(&g, &[0], &mut rec);
+        let back: Vec<&String> = rec.events.iter().filter(|e| e.starts_with('b')).collect();
+        assert_eq!(back, ["b20", "b32"]);
+        assert!(rec.events.iter().all(|e| !e.starts_with('x')));
+        assert_eq!(dfs_forest(&g).preorder.len(), 5);
+        assert_eq!(r.parent[4], Some(1));
+    }
+}
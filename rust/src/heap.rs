@@ -0,0 +1,118 @@
+// rust/src/heap.rs
+
+/// Common interface of the crate's growable min-heaps (`DaryHeap`, `IntervalHeap`, `PairingHeap`,
+/// `LeftistHeap`, `FibonacciHeap`, `IndexedHeap`), so that graph algorithms and benchmarks can be
+/// written once and run with any priority-queue backend. `FixedHeap` keeps its own API because its `push` can fail.
+/// The inherent methods of each heap remain available and may return more (e.g. handles).
+pub trait Heap {
+    type Item: Ord;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn push(&mut self, item: Self::Item);
+
+    /// The smallest item.
+    fn peek(&self) -> Option<&Self::Item>;
+
+    /// Removes and returns the smallest item.
+    fn pop(&mut self) -> Option<Self::Item>;
+}
+
+/// A `Heap` that can absorb another heap of the same type faster than popping and pushing each item.
+pub trait MergeableHeap: Heap {
+    /// Moves every item of `other` into `self`.
+    fn meld(&mut self, other: Self);
+}
+
+/// A `Heap` whose items can be addressed after insertion, for Dijkstra / Prim style decrease-key.
+/// Handles stay valid until their item is popped or removed.
+pub trait AddressableHeap: Heap {
+    type Handle: Copy;
+
+    /// Inserts `item` and returns its handle.
+    fn push_handle(&mut self, item: Self::Item) -> Self::Handle;
+
+    /// Lowers the item at `handle` to `item`. Returns false (leaving the heap unchanged) if the
+    /// handle is not live or `item` is greater than the current item.
+    fn decrease_key(&mut self, handle: Self::Handle, item: Self::Item) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dary_heap::DaryHeap;
+    use crate::fibonacci_heap::FibonacciHeap;
+    use crate::indexed_heap::IndexedHeap;
+    use crate::interval_heap::IntervalHeap;
+    use crate::leftist_heap::LeftistHeap;
+    use crate::pairing_heap::PairingHeap;
+
+    fn values() -> Vec<i64> {
+        (0..300).map(|i| (i * 7919) % 211 - 50).collect()
+    }
+
+    /// Written once against the traits, run against every backend.
+    fn heapsort<H: Heap<Item = i64> + Default>() -> Vec<i64> {
+        let mut heap = H::default();
+        values().into_iter().for_each(|v| heap.push(v));
+        assert_eq!(heap.len(), 300);
+        assert_eq!(heap.peek().copied(), values().into_iter().min());
+        std::iter::from_fn(|| heap.pop()).collect()
+    }
+
+    fn meld_halves<H: MergeableHeap<Item = i64> + Default>() -> Vec<i64> {
+        let (mut a, mut b) = (H::default(), H::default());
+        for (i, v) in values().into_iter().enumerate() {
+            if i % 2 == 0 { a.push(v) } else { b.push(v) }
+        }
+        a.meld(b);
+        std::iter::from_fn(|| a.pop()).collect()
+    }
+
+    /// Dijkstra on a small weighted digraph, relaxing with decrease-key.
+    fn shortest_paths<H: AddressableHeap<Item = (u64, usize)> + Default>() -> Vec<u64> {
+        let edges: &[&[(usize, u64)]] = &[&[(1, 7), (2, 2)], &[(3, 1)], &[(1, 3), (3, 8)], &[(4, 2)], &[]];
+        let mut dist = vec![u64::MAX; edges.len()];
+        let mut handle = vec![None; edges.len()];
+        let mut heap = H::default();
+        dist[0] = 0;
+        handle[0] = Some(heap.push_handle((0, 0)));
+        while let Some((d, u)) = heap.pop() {
+            for &(v, w) in edges[u] {
+                if d + w < dist[v] {
+                    dist[v] = d + w;
+                    match handle[v] {
+                        Some(h) => assert!(heap.decrease_key(h, (d + w, v))),
+                        None => handle[v] = Some(heap.push_handle((d + w, v))),
+                    }
+                }
+            }
+        }
+        dist
+    }
+
+    #[test]
+    fn test_backends_are_interchangeable() {
+        let mut sorted = values();
+        sorted.sort();
+        assert_eq!(heapsort::<DaryHeap<i64, 2>>(), sorted);
+        assert_eq!(heapsort::<DaryHeap<i64, 4>>(), sorted);
+        assert_eq!(heapsort::<IntervalHeap<i64>>(), sorted);
+        assert_eq!(heapsort::<PairingHeap<i64>>(), sorted);
+        assert_eq!(heapsort::<LeftistHeap<i64>>(), sorted);
+        assert_eq!(heapsort::<FibonacciHeap<i64>>(), sorted);
+
+        assert_eq!(meld_halves::<PairingHeap<i64>>(), sorted);
+        assert_eq!(meld_halves::<LeftistHeap<i64>>(), sorted);
+        assert_eq!(meld_halves::<FibonacciHeap<i64>>(), sorted);
+
+        let expected = vec![0, 5, 2, 6, 8];
+        assert_eq!(shortest_paths::<PairingHeap<_>>(), expected);
+        assert_eq!(shortest_paths::<FibonacciHeap<_>>(), expected);
+        assert_eq!(shortest_paths::<IndexedHeap<_, _>>(), expected);
+    }
+}
@@ -0,0 +1,268 @@
+// rust/src/indexed_heap.rs
+use crate::DataStructure;
+use crate::heap::{AddressableHeap, Heap};
+use crate::info::{Info, Operation};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Binary min-heap of `(key, priority)` entries, addressed by key instead of by handle: a hash map
+/// tracks where each key sits in the array, so a key's priority can be read, lowered, changed or
+/// removed in place. This is the priority queue Dijkstra and Prim are usually written against, and
+/// what `std::collections::BinaryHeap` (or Python's heapq) is missing. Each key appears at most once.
+/// - push / pop / decrease_key / change_priority / remove: O(log n)
+/// - peek / contains / priority: O(1) (expected, for the hash lookups)
+pub struct IndexedHeap<K, P> {
+    // (priority, key), so that the entries are the `Heap` items
+    entries: Vec<(P, K)>,
+    pos: HashMap<K, usize>,
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> Default for IndexedHeap<K, P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq + Clone, P: Ord> IndexedHeap<K, P> {
+    pub fn new() -> Self {
+        IndexedHeap {
+            entries: Vec::new(),
+            pos: HashMap::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.pos.contains_key(key)
+    }
+
+    pub fn priority(&self, key: &K) -> Option<&P> {
+        self.pos.get(key).map(|&i| &self.entries[i].0)
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.entries.swap(i, j);
+        for k in [i, j] {
+            *self.pos.get_mut(&self.entries[k].1).expect("every entry is indexed") = k;
+        }
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 && self.entries[i].0 < self.entries[(i - 1) / 2].0 {
+            self.swap(i, (i - 1) / 2);
+            i = (i - 1) / 2;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        loop {
+            let (l, r) = (2 * i + 1, 2 * i + 2);
+            let mut smallest = i;
+            if l < self.entries.len() && self.entries[l].0 < self.entries[smallest].0 {
+                smallest = l;
+            }
+            if r < self.entries.len() && self.entries[r].0 < self.entries[smallest].0 {
+                smallest = r;
+            }
+            if smallest == i {
+                break;
+            }
+            self.swap(i, smallest);
+            i = smallest;
+        }
+    }
+
+    /// Inserts `key` with `priority`. Returns false (leaving the heap unchanged) if `key` is already
+    /// present; use `decrease_key` or `change_priority` for that.
+    pub fn push(&mut self, key: K, priority: P) -> bool {
+        if self.pos.contains_key(&key) {
+            return false;
+        }
+        self.pos.insert(key.clone(), self.entries.len());
+        self.entries.push((priority, key));
+        self.sift_up(self.entries.len() - 1);
+        true
+    }
+
+    /// The entry with the smallest priority.
+    pub fn peek(&self) -> Option<(&K, &P)> {
+        self.entries.first().map(|(p, k)| (k, p))
+    }
+
+    /// Removes and returns the entry with the smallest priority.
+    pub fn pop(&mut self) -> Option<(K, P)> {
+        self.remove_at(0)
+    }
+
+    fn remove_at(&mut self, i: usize) -> Option<(K, P)> {
+        if i >= self.entries.len() {
+            return None;
+        }
+        let last = self.entries.len() - 1;
+        self.swap(i, last);
+        let (priority, key) = self.entries.pop().expect("heap is non-empty");
+        self.pos.remove(&key);
+        if i < self.entries.len() {
+            // the entry moved into the hole may belong above or below it
+            self.sift_up(i);
+            self.sift_down(i);
+        }
+        Some((key, priority))
+    }
+
+    /// Removes `key` and returns its priority.
+    pub fn remove(&mut self, key: &K) -> Option<P> {
+        let i = *self.pos.get(key)?;
+        self.remove_at(i).map(|(_, p)| p)
+    }
+
+    /// Lowers the priority of `key`. Returns false (leaving the heap unchanged) if `key` is absent
+    /// or `priority` is greater than its current one.
+    pub fn decrease_key(&mut self, key: &K, priority: P) -> bool {
+        match self.pos.get(key) {
+            Some(&i) if priority <= self.entries[i].0 => {
+                self.entries[i].0 = priority;
+                self.sift_up(i);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Sets the priority of `key` in either direction and returns the old one, or None if absent.
+    pub fn change_priority(&mut self, key: &K, priority: P) -> Option<P> {
+        let i = *self.pos.get(key)?;
+        let old = std::mem::replace(&mut self.entries[i].0, priority);
+        self.sift_up(i);
+        self.sift_down(i);
+        Some(old)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.pos.clear();
+    }
+}
+
+/// Items are `(priority, key)` pairs. A key can be in the heap only once, so pushing a key that is
+/// already present panics.
+impl<K: Hash + Eq + Clone + Ord, P: Ord> Heap for IndexedHeap<K, P> {
+    type Item = (P, K);
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, (priority, key): (P, K)) {
+        assert!(self.push(key, priority), "key is already in the heap");
+    }
+
+    fn peek(&self) -> Option<&(P, K)> {
+        self.entries.first()
+    }
+
+    fn pop(&mut self) -> Option<(P, K)> {
+        self.pop().map(|(k, p)| (p, k))
+    }
+}
+
+/// The key is its own handle; `decrease_key` panics if the item's key is not the handle.
+impl<K: Hash + Eq + Copy + Ord, P: Ord> AddressableHeap for IndexedHeap<K, P> {
+    type Handle = K;
+
+    fn push_handle(&mut self, item: (P, K)) -> K {
+        let key = item.1;
+        Heap::push(self, item);
+        key
+    }
+
+    fn decrease_key(&mut self, handle: K, (priority, key): (P, K)) -> bool {
+        assert!(key == handle, "decrease_key cannot change an item's key");
+        self.decrease_key(&handle, priority)
+    }
+}
+
+impl<K, P> DataStructure for IndexedHeap<K, P> {
+    const INFO: Info = Info {
+        name: "IndexedHeap",
+        category: "heaps",
+        summary: "Binary min-heap addressed by key, with decrease-key, priority change and removal",
+        space: "O(n)",
+        operations: &[
+            Operation::new("push", "O(log n)", "O(1) amortized"),
+            Operation::new("pop", "O(log n)", "O(1)"),
+            Operation::new("peek", "O(1)", "O(1)"),
+            Operation::new("decrease_key", "O(log n)", "O(1)"),
+            Operation::new("change_priority", "O(log n)", "O(1)"),
+            Operation::new("remove", "O(log n)", "O(1)"),
+            Operation::new("contains", "O(1) expected", "O(1)"),
+        ],
+        stable: Some(false),
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_keyed_operations() {
+        let mut pq = IndexedHeap::new();
+        for (k, p) in [("a", 5), ("b", 3), ("c", 8), ("d", 1)] {
+            assert!(pq.push(k, p));
+        }
+        assert!(!pq.push("a", 0));
+        assert_eq!(pq.peek(), Some((&"d", &1)));
+        assert!(pq.decrease_key(&"c", 0));
+        assert!(!pq.decrease_key(&"b", 10));
+        assert!(!pq.decrease_key(&"z", 0));
+        assert_eq!(pq.priority(&"c"), Some(&0));
+        assert_eq!(pq.change_priority(&"d", 9), Some(1));
+        assert_eq!(pq.remove(&"b"), Some(3));
+        assert!(!pq.contains(&"b"));
+        assert_eq!(pq.len(), 3);
+        let order: Vec<(&str, i32)> = std::iter::from_fn(|| pq.pop()).collect();
+        assert_eq!(order, vec![("c", 0), ("a", 5), ("d", 9)]);
+        assert!(pq.is_empty());
+    }
+
+    #[test]
+    fn test_against_reference() {
+        let mut next = test_rng(3);
+        let mut pq = IndexedHeap::new();
+        let mut reference: HashMap<i64, i64> = HashMap::new();
+        for _ in 0..3000 {
+            let key = (next() % 64) as i64;
+            let p = (next() % 1000) as i64;
+            match next() % 4 {
+                0 => {
+                    let fresh = !reference.contains_key(&key);
+                    assert_eq!(pq.push(key, p), fresh);
+                    if fresh {
+                        reference.insert(key, p);
+                    }
+                }
+                1 => assert_eq!(pq.change_priority(&key, p), reference.get_mut(&key).map(|q| std::mem::replace(q, p))),
+                2 => assert_eq!(pq.remove(&key), reference.remove(&key)),
+                _ => {
+                    let expected = reference.values().min().copied();
+                    let got = pq.pop();
+                    assert_eq!(got.map(|(_, p)| p), expected);
+                    if let Some((k, _)) = got {
+                        reference.remove(&k);
+                    }
+                }
+            }
+            assert_eq!(pq.len(), reference.len());
+        }
+    }
+}
@@ -0,0 +1,507 @@
+// rust/src/info.rs
+use crate::DataStructure;
+
+/// Cost of one operation in Big-O notation. `space` is the extra space the operation needs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Operation {
+    pub name: &'static str,
+    pub time: &'static str,
+    pub space: &'static str,
+}
+
+impl Operation {
+    pub const fn new(name: &'static str, time: &'static str, space: &'static str) -> Self {
+        Operation { name, time, space }
+    }
+}
+
+/// Machine-readable description of a data structure or algorithm, for documentation and teaching tools.
+/// n is the number of elements (or the text length, or the number of vertices) and m the pattern length
+/// or number of edges, as the summary states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Info {
+    pub name: &'static str,
+    pub category: &'static str,
+    pub summary: &'static str,
+    /// Space of the whole structure, or the extra space of an algorithm.
+    pub space: &'static str,
+    pub operations: &'static [Operation],
+    /// Whether equal items keep their relative order; None where that does not apply.
+    pub stable: Option<bool>,
+    /// Whether an algorithm works inside its input with O(1) extra space; None where that does not apply.
+    pub in_place: Option<bool>,
+}
+
+impl Info {
+    pub fn operation(&self, name: &str) -> Option<&'static Operation> {
+        self.operations.iter().find(|op| op.name == name)
+    }
+}
+
+/// Algorithms that are plain functions rather than structures, so they have no `DataStructure` impl.
+const ALGORITHMS: &[Info] = &[
+    Info {
+        name: "kmp",
+        category: "strings",
+        summary: "Knuth-Morris-Pratt search via the prefix function (n = text, m = pattern)",
+        space: "O(m)",
+        operations: &[
+            Operation::new("prefix_function", "O(m)", "O(m)"),
+            Operation::new("find_all", "O(n + m)", "O(m)"),
+            Operation::new("find_all_with", "O(n + m)", "O(m)"),
+            Operation::new("build_automaton", "O(m * σ)", "O(m * σ)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "edit_distance",
+        category: "strings",
+        summary: "Levenshtein distance; bit-parallel (Myers) when the shorter string fits in a word",
+        space: "O(min(n, m))",
+        operations: &[
+            Operation::new("edit_distance", "O(n) for m <= 64, else O(n * m)", "O(min(n, m))"),
+            Operation::new("distance_at_most", "O(min(n, m) * k)", "O(min(n, m))"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "lyndon_factorization",
+        category: "strings",
+        summary: "Duval's algorithm for Lyndon factorization and the minimal rotation",
+        space: "O(1)",
+        operations: &[
+            Operation::new("lyndon_factorization", "O(n)", "O(1)"),
+            Operation::new("minimal_rotation", "O(n)", "O(1)"),
+        ],
+        stable: None,
+        in_place: Some(true),
+    },
+    Info {
+        name: "z_function",
+        category: "strings",
+        summary: "Z-function: longest common prefix of the string with each of its suffixes",
+        space: "O(n)",
+        operations: &[Operation::new("z_function", "O(n)", "O(n)")],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "manacher",
+        category: "strings",
+        summary: "Manacher's algorithm: palindrome radii at every centre and the longest palindrome",
+        space: "O(n)",
+        operations: &[
+            Operation::new("manacher", "O(n)", "O(n)"),
+            Operation::new("longest_palindrome", "O(n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "longest_repeated_substring",
+        category: "strings",
+        summary: "Longest substring occurring twice, from the suffix array and LCP array",
+        space: "O(n)",
+        operations: &[
+            Operation::new("longest_repeated_substring", "O(n log σ)", "O(n)"),
+            Operation::new("longest_repeated_substring_non_overlapping", "O(n log n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "bwt",
+        category: "strings",
+        summary: "Burrows-Wheeler transform and its inverse",
+        space: "O(n)",
+        operations: &[
+            Operation::new("bwt", "O(n)", "O(n)"),
+            Operation::new("inverse_bwt", "O(n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "max_flow",
+        category: "graphs",
+        summary: "Maximum flow and minimum cut by Dinic's algorithm (level graphs and blocking flows)",
+        space: "O(n + m)",
+        operations: &[
+            Operation::new("add_edge", "O(1) amortized", "O(1)"),
+            Operation::new("solve", "O(n^2 * m)", "O(n)"),
+            Operation::new("min_cut", "O(n + m)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "min_cost_flow",
+        category: "graphs",
+        summary: "Min-cost max-flow by successive shortest paths with potentials; negative costs allowed (F = augmentations)",
+        space: "O(n + m)",
+        operations: &[
+            Operation::new("add_edge", "O(1) amortized", "O(1)"),
+            Operation::new("solve", "O(n * m + F * (n + m) log n)", "O(n)"),
+            Operation::new("solve_limited", "O(n * m + F * (n + m) log n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "mst",
+        category: "graphs",
+        summary: "Minimum spanning forests (Kruskal, Borůvka, Prim) plus verification and sensitivity (m = edges)",
+        space: "O(n + m)",
+        operations: &[
+            Operation::new("kruskal", "O(m log m)", "O(n + m)"),
+            Operation::new("boruvka", "O(m log n)", "O(n)"),
+            Operation::new("verify_mst", "O((n + m) log n)", "O(n log n)"),
+            Operation::new("second_best_mst", "O((n + m) log n)", "O(n log n)"),
+            Operation::new("mst_sensitivity", "O((n + m) log n)", "O(n log n)"),
+            Operation::new("graph::prim", "O((n + m) log n)", "O(n)"),
+            Operation::new("graph::prim_implicit", "O((n + m) log n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "eulerian_path",
+        category: "graphs",
+        summary: "Eulerian paths and circuits of directed or undirected Graphs by Hierholzer's algorithm",
+        space: "O(n + m)",
+        operations: &[
+            Operation::new("eulerian_path", "O(n + m)", "O(n + m)"),
+            Operation::new("eulerian_circuit", "O(n + m)", "O(n + m)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "hungarian",
+        category: "graphs",
+        summary: "Optimal assignment of n rows to m >= n columns of a cost matrix (Kuhn-Munkres with potentials)",
+        space: "O(n + m)",
+        operations: &[Operation::new("hungarian", "O(n^2 * m)", "O(n + m)")],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "cycles",
+        category: "graphs",
+        summary: "Finding any cycle, a shortest cycle, or enumerating simple cycles (m = edges)",
+        space: "O(n + m)",
+        operations: &[
+            Operation::new("find_any_cycle", "O(n + m)", "O(n + m)"),
+            Operation::new("shortest_cycle", "O(n * (n + m))", "O(n + m)"),
+            Operation::new("simple_cycles", "O(n * (n + m) + (n + m) * c) for c cycles", "O(n + m)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "dijkstra",
+        category: "graphs",
+        summary: "Shortest paths with non-negative weights: Dijkstra, A* over Graph or implicit graphs, 0-1 BFS and Dial's buckets for small integer weights (D = max distance)",
+        space: "O(n)",
+        operations: &[
+            Operation::new("dijkstra", "O((n + m) log n)", "O(n)"),
+            Operation::new("zero_one_bfs", "O(n + m)", "O(n)"),
+            Operation::new("dial", "O(n + m + D)", "O(n + max weight)"),
+            Operation::new("path_to", "O(path length)", "O(path length)"),
+            Operation::new("astar / astar_implicit", "O(m log n) over reached nodes", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "traversal",
+        category: "graphs",
+        summary: "BFS and DFS over Graph with visitor callbacks, returning distances, parents and orders",
+        space: "O(n)",
+        operations: &[
+            Operation::new("bfs / bfs_with", "O(n + m)", "O(n)"),
+            Operation::new("dfs / dfs_with / dfs_forest", "O(n + m)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "strongly_connected_components",
+        category: "graphs",
+        summary: "Tarjan's SCC over a directed Graph, with topologically numbered ids and the condensation DAG",
+        space: "O(n)",
+        operations: &[
+            Operation::new("strongly_connected_components", "O(n + m)", "O(n)"),
+            Operation::new("condensation", "O(n + m log m)", "O(n + m)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "topological_sort",
+        category: "graphs",
+        summary: "Topological order of a directed Graph (Kahn or DFS) or a cycle, and DAG longest paths",
+        space: "O(n)",
+        operations: &[
+            Operation::new("topological_sort", "O(n + m)", "O(n)"),
+            Operation::new("topological_sort_dfs", "O(n + m)", "O(n)"),
+            Operation::new("longest_path_in_dag", "O(n + m)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "transitive_closure",
+        category: "graphs",
+        summary: "Reachability matrix of a DAG as bitsets (m = edges)",
+        space: "O(n^2 / 64)",
+        operations: &[Operation::new("transitive_closure", "O(n + m * n / 64)", "O(n^2 / 64)")],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "josephus",
+        category: "simulation",
+        summary: "Josephus problem: survivor and elimination order",
+        space: "O(1)",
+        operations: &[
+            Operation::new("josephus", "O(n)", "O(1)"),
+            Operation::new("josephus_fast", "O(min(n, k log n))", "O(min(n, k log n))"),
+            Operation::new("josephus_order", "O(n * min(n, k))", "O(n)"),
+        ],
+        stable: None,
+        in_place: None,
+    },
+    Info {
+        name: "sweep_line",
+        category: "geometry",
+        summary: "Skyline and union area/perimeter of rectangles by sweeping",
+        space: "O(n)",
+        operations: &[
+            Operation::new("skyline", "O(n log n)", "O(n)"),
+            Operation::new("rectangles_union_area", "O(n log n)", "O(n)"),
+            Operation::new("rectangles_union_perimeter", "O(n log n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "mo",
+        category: "range queries",
+        summary: "Mo's algorithm: offline range queries by sliding one window in block or Hilbert order",
+        space: "O(q)",
+        operations: &[Operation::new("mo", "O(q log q + n √q) add / remove calls", "O(q)")],
+        stable: None,
+        in_place: None,
+    },
+    Info {
+        name: "monotonic_stack",
+        category: "arrays",
+        summary: "Nearest greater / smaller element on either side and the largest histogram rectangle",
+        space: "O(n)",
+        operations: &[
+            Operation::new("next_greater / previous_greater / next_smaller / previous_smaller", "O(n)", "O(n)"),
+            Operation::new("largest_rectangle", "O(n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: Some(false),
+    },
+    Info {
+        name: "sieves",
+        category: "math",
+        summary: "Linear sieve with smallest prime factors, segmented sieve for wide ranges, trial-division factorization",
+        space: "O(n) linear; O(√end + window) segmented",
+        operations: &[
+            Operation::new("LinearSieve::new", "O(n)", "O(n)"),
+            Operation::new("LinearSieve::factorize", "O(log x)", "O(log x)"),
+            Operation::new("primes_in_range", "O((end - start) log log end + √end)", "O(√end + window)"),
+            Operation::new("factorize", "O(√n)", "O(log n)"),
+        ],
+        stable: None,
+        in_place: None,
+    },
+];
+
+/// Every structure implementing `DataStructure` and every standalone algorithm, sorted by category
+/// and then by name.
+pub fn catalog() -> Vec<Info> {
+    use crate::{
+        avl_tree::AvlTree,
+        bitap::Bitap,
+        bit_vector::BitVector,
+        bitset::BitSet,
+        bloom_filter::BloomFilter,
+        boyer_moore::*,
+        cache::{LfuCache, LruCache},
+        circular_list::CircularList,
+        count_min::CountMinSketch,
+        csr_graph::CsrGraph,
+        cuckoo_filter::CuckooFilter,
+        dary_heap::DaryHeap,
+        dense_graph::DenseGraph,
+        dlx::ExactCover,
+        dynamic_segment_tree::DynamicSegmentTree,
+        fenwick_tree::FenwickTree,
+        finger_tree::FingerTree,
+        fischer_heun::FischerHeun,
+        fibonacci_heap::FibonacciHeap,
+        fixed::*,
+        fm_index::FmIndex,
+        graph::Graph,
+        hyperloglog::HyperLogLog,
+        indexed_heap::IndexedHeap,
+        interval_heap::IntervalHeap,
+        interval_set::IntervalSet,
+        interval_tree::IntervalTree,
+        kd_tree::KdTree,
+        kmp::KmpMatcher,
+        lca::EulerTourLca,
+        lazy_segment_tree::{LazySegmentTree, RangeSum},
+        leftist_heap::LeftistHeap,
+        li_chao::LiChaoTree,
+        link_cut_tree::LinkCutTree,
+        mergeable_set::MergeableSet,
+        modint::ModInt,
+        monoid::Sum,
+        pairing_heap::{PairingForest, PairingHeap},
+        persistent_array::PersistentArray,
+        quadtree::Quadtree,
+        radix_trie::RadixTrie,
+        range_query::RangeQueryEngine,
+        range_tree::RangeTree2D,
+        reachability::ReachabilityOracle,
+        rolling_hash::StringHasher,
+        rope::Rope,
+        segment_tree::{IterativeSegmentTree, SegmentTree},
+        segment_tree_2d::SegmentTree2D,
+        segment_tree_beats::SegmentTreeBeats,
+        skip_list::SkipList,
+        slab::Slab,
+        splay_tree::SplayTree,
+        slab_list::SlabList,
+        sparse_table::SparseTable,
+        spatial_hash::SpatialHash,
+        sparse_set::SparseSet,
+        sqrt_decomposition::SqrtDecomposition,
+        static_dict::StaticDict,
+        streaming::{ReservoirSampler, RunningStats, WeightedReservoir},
+        suffix_array::{GeneralizedSuffixArray, SuffixArray},
+        suffix_automaton::SuffixAutomaton,
+        treap::Treap,
+        trie::Trie,
+        union_find::UnionFind,
+        van_emde_boas::VanEmdeBoas,
+        wavelet_tree::WaveletTree,
+        xor_trie::XorTrie,
+    };
+    let mut all = vec![
+        UnionFind::INFO,
+        FenwickTree::<i64>::INFO,
+        FixedFenwick::<0>::INFO,
+        FixedHeap::<(), 0>::INFO,
+        FixedRing::<(), 0>::INFO,
+        Treap::INFO,
+        SkipList::<()>::INFO,
+        SplayTree::<()>::INFO,
+        AvlTree::<()>::INFO,
+        MergeableSet::INFO,
+        PairingHeap::<()>::INFO,
+        PairingForest::<()>::INFO,
+        DaryHeap::<(), 4>::INFO,
+        IntervalHeap::<()>::INFO,
+        LeftistHeap::<()>::INFO,
+        FibonacciHeap::<()>::INFO,
+        IndexedHeap::<(), ()>::INFO,
+        SparseTable::<i64>::INFO,
+        FischerHeun::<i64>::INFO,
+        WaveletTree::INFO,
+        EulerTourLca::INFO,
+        LinkCutTree::<(), ()>::INFO,
+        RangeQueryEngine::INFO,
+        SegmentTree::<i64, Sum>::INFO,
+        IterativeSegmentTree::<i64, Sum>::INFO,
+        LazySegmentTree::<RangeSum>::INFO,
+        SqrtDecomposition::<RangeSum>::INFO,
+        IntervalTree::<i64>::INFO,
+        DynamicSegmentTree::<i64, Sum>::INFO,
+        SegmentTreeBeats::INFO,
+        SegmentTree2D::<i64, Sum>::INFO,
+        RangeTree2D::INFO,
+        LiChaoTree::INFO,
+        KmpMatcher::INFO,
+        Horspool::INFO,
+        BoyerMoore::INFO,
+        Bitap::INFO,
+        StringHasher::INFO,
+        SpatialHash::INFO,
+        KdTree::<2>::INFO,
+        Quadtree::INFO,
+        SuffixArray::INFO,
+        GeneralizedSuffixArray::INFO,
+        FmIndex::INFO,
+        SuffixAutomaton::INFO,
+        Rope::INFO,
+        PersistentArray::<()>::INFO,
+        FingerTree::<()>::INFO,
+        Slab::<()>::INFO,
+        CircularList::<()>::INFO,
+        SlabList::<()>::INFO,
+        CsrGraph::<()>::INFO,
+        Graph::<()>::INFO,
+        BitSet::INFO,
+        SparseSet::INFO,
+        BitVector::INFO,
+        VanEmdeBoas::INFO,
+        BloomFilter::INFO,
+        IntervalSet::<i64>::INFO,
+        CuckooFilter::INFO,
+        CountMinSketch::INFO,
+        HyperLogLog::INFO,
+        ReservoirSampler::<()>::INFO,
+        WeightedReservoir::<()>::INFO,
+        RunningStats::INFO,
+        LruCache::<(), ()>::INFO,
+        LfuCache::<(), ()>::INFO,
+        ExactCover::INFO,
+        ModInt::<1_000_000_007>::INFO,
+        DenseGraph::INFO,
+        ReachabilityOracle::INFO,
+        Trie::<()>::INFO,
+        RadixTrie::<()>::INFO,
+        XorTrie::INFO,
+        StaticDict::INFO,
+    ];
+    all.extend_from_slice(ALGORITHMS);
+    all.sort_by_key(|info| (info.category, info.name));
+    all
+}
+
+/// Looks up a catalog entry by name, ignoring ASCII case.
+pub fn lookup(name: &str) -> Option<Info> {
+    catalog().into_iter().find(|info| info.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::union_find::UnionFind;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_catalog() {
+        let all = catalog();
+        let names: HashSet<&str> = all.iter().map(|i| i.name).collect();
+        assert_eq!(names.len(), all.len(), "names are unique");
+        assert!(all.iter().all(|i| !i.operations.is_empty() && !i.summary.is_empty()));
+        assert!(all.windows(2).all(|w| (w[0].category, w[0].name) <= (w[1].category, w[1].name)));
+
+        let uf = UnionFind::new(3);
+        assert_eq!(uf.name(), "UnionFind");
+        assert_eq!(uf.info().operation("find").map(|op| op.time), Some("O(α(n)) amortized"));
+        assert_eq!(lookup("unionfind"), Some(UnionFind::INFO));
+        assert_eq!(lookup("kmp").and_then(|i| i.in_place), Some(false));
+        assert!(lookup("nope").is_none());
+    }
+}
@@ -0,0 +1,281 @@
+// rust/src/interval_heap.rs
+use crate::DataStructure;
+use crate::heap::Heap;
+use crate::info::{Info, Operation};
+
+/// Interval heap: a double-ended priority queue in one implicit array. Node `k` holds the pair
+/// `data[2k] <= data[2k + 1]`, and each node's interval contains its children's, so the low ends form
+/// a min-heap, the high ends a max-heap, and the root holds both the minimum and the maximum.
+/// Suited to sliding quantiles and to bounded searches (beam search, A* with pruning) that expand
+/// the best item and evict the worst. Unlike `MergeableSet` it is array-backed and generic over `T`.
+/// - push / pop_min / pop_max: O(log n)
+/// - peek_min / peek_max: O(1)
+pub struct IntervalHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T: Ord> Default for IntervalHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> IntervalHeap<T> {
+    pub fn new() -> Self {
+        IntervalHeap { data: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        IntervalHeap { data: Vec::with_capacity(capacity) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        // a single item is both ends
+        self.data.get(1).or(self.data.first())
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.data.push(item);
+        let i = self.data.len() - 1;
+        if i % 2 == 1 {
+            // completes a pair: order it, then only one end can violate its parent
+            if self.data[i] < self.data[i - 1] {
+                self.data.swap(i, i - 1);
+            }
+            self.sift_up_min(i - 1);
+            self.sift_up_max(i);
+        } else if i > 0 {
+            let parent = (i / 2 - 1) / 2;
+            if self.data[i] < self.data[2 * parent] {
+                self.sift_up_min(i);
+            } else if self.data[i] > self.data[2 * parent + 1] {
+                self.sift_up_max(i);
+            }
+        }
+    }
+
+    /// Removes and returns the smallest item.
+    pub fn pop_min(&mut self) -> Option<T> {
+        if self.data.len() <= 2 {
+            return (!self.data.is_empty()).then(|| self.data.remove(0));
+        }
+        let top = self.data.swap_remove(0);
+        self.sift_down_min();
+        Some(top)
+    }
+
+    /// Removes and returns the largest item.
+    pub fn pop_max(&mut self) -> Option<T> {
+        if self.data.len() <= 2 {
+            return self.data.pop();
+        }
+        let top = self.data.swap_remove(1);
+        self.sift_down_max();
+        Some(top)
+    }
+
+    /// Moves the low end at index `i` up through the parents' low ends.
+    fn sift_up_min(&mut self, mut i: usize) {
+        while i >= 2 {
+            let p = 2 * ((i / 2 - 1) / 2);
+            if self.data[i] >= self.data[p] {
+                break;
+            }
+            self.data.swap(i, p);
+            i = p;
+        }
+    }
+
+    /// Moves the high end at index `i` up through the parents' high ends.
+    fn sift_up_max(&mut self, mut i: usize) {
+        while i >= 2 {
+            let p = 2 * ((i / 2 - 1) / 2) + 1;
+            if self.data[i] <= self.data[p] {
+                break;
+            }
+            self.data.swap(i, p);
+            i = p;
+        }
+    }
+
+    /// Orders the pair of node `k`, if it has two items.
+    fn fix_pair(&mut self, k: usize) {
+        if 2 * k + 1 < self.data.len() && self.data[2 * k] > self.data[2 * k + 1] {
+            self.data.swap(2 * k, 2 * k + 1);
+        }
+    }
+
+    fn sift_down_min(&mut self) {
+        let n = self.data.len();
+        let mut k = 0;
+        loop {
+            self.fix_pair(k);
+            let Some(c) = [2 * k + 1, 2 * k + 2]
+                .into_iter()
+                .filter(|&c| 2 * c < n)
+                .min_by(|&a, &b| self.data[2 * a].cmp(&self.data[2 * b]))
+            else {
+                break;
+            };
+            if self.data[2 * c] >= self.data[2 * k] {
+                break;
+            }
+            self.data.swap(2 * k, 2 * c);
+            k = c;
+        }
+    }
+
+    fn sift_down_max(&mut self) {
+        let n = self.data.len();
+        // the high end of a node holding a single item is that item
+        let high = |c: usize| (2 * c + 1).min(n - 1);
+        let mut k = 0;
+        loop {
+            self.fix_pair(k);
+            let Some(c) = [2 * k + 1, 2 * k + 2]
+                .into_iter()
+                .filter(|&c| 2 * c < n)
+                .max_by(|&a, &b| self.data[high(a)].cmp(&self.data[high(b)]))
+            else {
+                break;
+            };
+            if self.data[high(c)] <= self.data[2 * k + 1] {
+                break;
+            }
+            self.data.swap(2 * k + 1, high(c));
+            k = c;
+        }
+    }
+
+    /// Items in ascending order.
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut out = Vec::with_capacity(self.data.len());
+        while let Some(x) = self.pop_min() {
+            out.push(x);
+        }
+        out
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+}
+
+impl<T: Ord> FromIterator<T> for IntervalHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = IntervalHeap::new();
+        iter.into_iter().for_each(|x| heap.push(x));
+        heap
+    }
+}
+
+/// As a `Heap` it is a min-heap; `peek_max` / `pop_max` stay inherent.
+impl<T: Ord> Heap for IntervalHeap<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek_min()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_min()
+    }
+}
+
+impl<T> DataStructure for IntervalHeap<T> {
+    const INFO: Info = Info {
+        name: "IntervalHeap",
+        category: "heaps",
+        summary: "Array-backed double-ended priority queue with min and max at the root",
+        space: "O(n)",
+        operations: &[
+            Operation::new("push", "O(log n)", "O(1) amortized"),
+            Operation::new("pop_min", "O(log n)", "O(1)"),
+            Operation::new("pop_max", "O(log n)", "O(1)"),
+            Operation::new("peek_min", "O(1)", "O(1)"),
+            Operation::new("peek_max", "O(1)", "O(1)"),
+        ],
+        stable: Some(false),
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_both_ends() {
+        let mut heap: IntervalHeap<i32> = [5, 1, 9, 3, 7, 3].into_iter().collect();
+        assert_eq!(heap.peek_min(), Some(&1));
+        assert_eq!(heap.peek_max(), Some(&9));
+        assert_eq!(heap.pop_max(), Some(9));
+        assert_eq!(heap.pop_min(), Some(1));
+        assert_eq!(heap.pop_max(), Some(7));
+        assert_eq!(heap.len(), 3);
+        assert_eq!(heap.into_sorted_vec(), vec![3, 3, 5]);
+
+        let mut single = IntervalHeap::new();
+        single.push('x');
+        assert_eq!(single.peek_min(), single.peek_max());
+        assert_eq!(single.pop_max(), Some('x'));
+        assert_eq!(single.pop_min(), None);
+        assert_eq!(single.pop_max(), None);
+    }
+
+    #[test]
+    fn test_against_multiset() {
+        let mut next = test_rng(7);
+        let mut heap = IntervalHeap::new();
+        let mut reference: BTreeMap<i64, usize> = BTreeMap::new();
+        for _ in 0..3000 {
+            match next() % 5 {
+                0..=2 => {
+                    let v = (next() % 200) as i64;
+                    heap.push(v);
+                    *reference.entry(v).or_default() += 1;
+                }
+                op => {
+                    let expected = if op == 3 {
+                        reference.keys().next().copied()
+                    } else {
+                        reference.keys().next_back().copied()
+                    };
+                    let got = if op == 3 { heap.pop_min() } else { heap.pop_max() };
+                    assert_eq!(got, expected);
+                    if let Some(v) = got {
+                        let count = reference.get_mut(&v).unwrap();
+                        *count -= 1;
+                        if *count == 0 {
+                            reference.remove(&v);
+                        }
+                    }
+                }
+            }
+            assert_eq!(heap.peek_min(), reference.keys().next());
+            assert_eq!(heap.peek_max(), reference.keys().next_back());
+        }
+    }
+}
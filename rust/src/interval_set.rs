@@ -0,0 +1,301 @@
+// rust/src/interval_set.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::ops::{Add, Range, Sub};
+
+/// Set of points stored as disjoint half-open intervals `[start, end)`, kept sorted in a vector.
+/// Inserting coalesces every interval the new one overlaps or touches, so `[1, 3)` and `[3, 5)`
+/// become `[1, 5)`; removing a range trims or splits the intervals it cuts. Bounds only need
+/// `PartialOrd`, so integer and float intervals both work; NaN bounds are rejected. Lookups
+/// binary search, and the set operations merge two sorted lists in one pass.
+/// - contains: O(log n)
+/// - insert / remove: O(log n + k) to find the k affected intervals, plus moving the tail
+/// - union / intersection / difference / complement: O(n + m)
+#[derive(Debug, Clone, PartialEq)]
+pub struct IntervalSet<T> {
+    intervals: Vec<(T, T)>,
+}
+
+impl<T> Default for IntervalSet<T> {
+    fn default() -> Self {
+        IntervalSet { intervals: Vec::new() }
+    }
+}
+
+/// Appends `[s, e)` to sorted, coalesced `out`, merging it into the last interval if they meet.
+fn push_coalesced<T: PartialOrd + Copy>(out: &mut Vec<(T, T)>, (s, e): (T, T)) {
+    match out.last_mut() {
+        Some(last) if s <= last.1 => {
+            if e > last.1 {
+                last.1 = e;
+            }
+        }
+        _ => out.push((s, e)),
+    }
+}
+
+impl<T: PartialOrd + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of disjoint intervals.
+    pub fn len(&self) -> usize {
+        self.intervals.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The intervals in increasing order.
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.intervals.iter().map(|&(s, e)| s..e)
+    }
+
+    fn check(range: &Range<T>) {
+        assert!(range.start <= range.end, "interval bounds must satisfy start <= end and not be NaN");
+    }
+
+    /// Adds every point of `range`. Empty ranges change nothing. Panics if `start > end`.
+    pub fn insert(&mut self, range: Range<T>) {
+        Self::check(&range);
+        let (mut s, mut e) = (range.start, range.end);
+        if s == e {
+            return;
+        }
+        // intervals ending before `s` and starting after `e` stay; the ones between merge
+        let lo = self.intervals.partition_point(|iv| iv.1 < s);
+        let hi = self.intervals.partition_point(|iv| iv.0 <= e);
+        if lo < hi {
+            if self.intervals[lo].0 < s {
+                s = self.intervals[lo].0;
+            }
+            if self.intervals[hi - 1].1 > e {
+                e = self.intervals[hi - 1].1;
+            }
+        }
+        self.intervals.splice(lo..hi, [(s, e)]);
+    }
+
+    /// Removes every point of `range`. Panics if `start > end`.
+    pub fn remove(&mut self, range: Range<T>) {
+        Self::check(&range);
+        let (s, e) = (range.start, range.end);
+        if s == e {
+            return;
+        }
+        let lo = self.intervals.partition_point(|iv| iv.1 <= s);
+        let hi = self.intervals.partition_point(|iv| iv.0 < e);
+        if lo >= hi {
+            return;
+        }
+        let (first, last) = (self.intervals[lo].0, self.intervals[hi - 1].1);
+        let left = (first < s).then_some((first, s));
+        let right = (e < last).then_some((e, last));
+        self.intervals.splice(lo..hi, left.into_iter().chain(right));
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        let i = self.intervals.partition_point(|iv| iv.1 <= point);
+        self.intervals.get(i).is_some_and(|iv| iv.0 <= point)
+    }
+
+    /// The interval containing `point`, if any.
+    pub fn interval_at(&self, point: T) -> Option<Range<T>> {
+        let i = self.intervals.partition_point(|iv| iv.1 <= point);
+        self.intervals.get(i).filter(|iv| iv.0 <= point).map(|&(s, e)| s..e)
+    }
+
+    /// Points in either set.
+    pub fn union(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let (mut i, mut j) = (0, 0);
+        let mut out = Vec::with_capacity(a.len() + b.len());
+        while i < a.len() || j < b.len() {
+            let next = if j == b.len() || (i < a.len() && a[i].0 <= b[j].0) {
+                i += 1;
+                a[i - 1]
+            } else {
+                j += 1;
+                b[j - 1]
+            };
+            push_coalesced(&mut out, next);
+        }
+        IntervalSet { intervals: out }
+    }
+
+    /// Points in both sets.
+    pub fn intersection(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let (a, b) = (&self.intervals, &other.intervals);
+        let (mut i, mut j) = (0, 0);
+        let mut out = Vec::new();
+        while i < a.len() && j < b.len() {
+            let s = if a[i].0 > b[j].0 { a[i].0 } else { b[j].0 };
+            let e = if a[i].1 < b[j].1 { a[i].1 } else { b[j].1 };
+            if s < e {
+                out.push((s, e));
+            }
+            // the interval ending first cannot meet anything further on the other side
+            if a[i].1 < b[j].1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals: out }
+    }
+
+    /// Points in `self` but not in `other`.
+    pub fn difference(&self, other: &IntervalSet<T>) -> IntervalSet<T> {
+        let b = &other.intervals;
+        let mut j = 0;
+        let mut out = Vec::new();
+        for &(s, e) in &self.intervals {
+            while j < b.len() && b[j].1 <= s {
+                j += 1;
+            }
+            // `cur` is where the uncovered part of [s, e) resumes
+            let mut cur = s;
+            while j < b.len() && b[j].0 < e {
+                if cur < b[j].0 {
+                    out.push((cur, b[j].0));
+                }
+                if b[j].1 > cur {
+                    cur = b[j].1;
+                }
+                if cur >= e {
+                    break;
+                }
+                j += 1;
+            }
+            if cur < e {
+                out.push((cur, e));
+            }
+        }
+        IntervalSet { intervals: out }
+    }
+
+    /// Points of `within` that are not in the set. Panics if `start > end`.
+    pub fn complement(&self, within: Range<T>) -> IntervalSet<T> {
+        let mut bounds = IntervalSet::new();
+        bounds.insert(within);
+        bounds.difference(self)
+    }
+}
+
+impl<T: PartialOrd + Copy + Default + Add<Output = T> + Sub<Output = T>> IntervalSet<T> {
+    /// Total length of the intervals.
+    pub fn covered_length(&self) -> T {
+        self.intervals.iter().fold(T::default(), |total, &(s, e)| total + (e - s))
+    }
+}
+
+impl<T: PartialOrd + Copy> FromIterator<Range<T>> for IntervalSet<T> {
+    /// Sorts the ranges once and coalesces them in a single pass.
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut ranges: Vec<(T, T)> = iter
+            .into_iter()
+            .inspect(Self::check)
+            .filter(|r| r.start < r.end)
+            .map(|r| (r.start, r.end))
+            .collect();
+        ranges.sort_by(|a, b| a.0.partial_cmp(&b.0).expect("bounds are not NaN"));
+        let mut intervals = Vec::with_capacity(ranges.len());
+        ranges.into_iter().for_each(|r| push_coalesced(&mut intervals, r));
+        IntervalSet { intervals }
+    }
+}
+
+impl<T> DataStructure for IntervalSet<T> {
+    const INFO: Info = Info {
+        name: "IntervalSet",
+        category: "sets",
+        summary: "Disjoint half-open intervals in a sorted vector, coalescing on insert, with set operations",
+        space: "O(n)",
+        operations: &[
+            Operation::new("contains", "O(log n)", "O(1)"),
+            Operation::new("insert / remove", "O(log n + k), plus moving the tail", "O(1)"),
+            Operation::new("union / intersection / difference", "O(n + m)", "O(n + m)"),
+            Operation::new("complement", "O(n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_coalescing_and_set_operations() {
+        let mut set = IntervalSet::new();
+        set.insert(1..3);
+        set.insert(5..8);
+        set.insert(3..4);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![1..4, 5..8]);
+        set.insert(0..6);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..8]);
+        set.remove(2..3);
+        set.remove(7..20);
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![0..2, 3..7]);
+        assert!(set.contains(0) && !set.contains(2) && set.contains(6) && !set.contains(7));
+        assert_eq!(set.interval_at(4), Some(3..7));
+        assert_eq!(set.covered_length(), 6);
+        assert_eq!(set.complement(-1..10).iter().collect::<Vec<_>>(), vec![-1..0, 2..3, 7..10]);
+
+        let hours: IntervalSet<f64> = [9.0..12.5, 13.0..17.0, 12.0..12.75].into_iter().collect();
+        let meetings: IntervalSet<f64> = [10.0..11.0, 16.5..18.0].into_iter().collect();
+        let free = hours.difference(&meetings);
+        assert_eq!(free.iter().collect::<Vec<_>>(), vec![9.0..10.0, 11.0..12.75, 13.0..16.5]);
+        assert_eq!(free.covered_length(), 6.25);
+        assert_eq!(hours.intersection(&meetings).covered_length(), 1.5);
+        assert_eq!(hours.union(&meetings).iter().collect::<Vec<_>>(), vec![9.0..12.75, 13.0..18.0]);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = test_rng(113);
+        let mut next = || (rng() % 60) as i64;
+        let covered = |set: &IntervalSet<i64>| (0..60).map(|x| set.contains(x)).collect::<Vec<_>>();
+        let canonical = |set: &IntervalSet<i64>| set.intervals.windows(2).all(|w| w[0].1 < w[1].0) && set.intervals.iter().all(|iv| iv.0 < iv.1);
+        let mut set = IntervalSet::new();
+        let mut naive = [false; 60];
+        for round in 0..2000 {
+            let (a, b) = (next(), next());
+            let (s, e) = (a.min(b), a.max(b));
+            let keep = round % 3 != 0;
+            if keep {
+                set.insert(s..e);
+            } else {
+                set.remove(s..e);
+            }
+            naive[s as usize..e as usize].iter_mut().for_each(|x| *x = keep);
+            assert!(canonical(&set));
+            assert_eq!(covered(&set), naive.to_vec());
+            assert_eq!(set.covered_length(), naive.iter().filter(|&&x| x).count() as i64);
+
+            if round % 50 == 0 {
+                let other: IntervalSet<i64> = (0..5)
+                    .map(|_| {
+                        let s = next();
+                        s..s + next() % 8
+                    })
+                    .collect();
+                let theirs = covered(&other);
+                let mine = covered(&set);
+                for (result, f) in [
+                    (set.union(&other), (|a, b| a || b) as fn(bool, bool) -> bool),
+                    (set.intersection(&other), |a, b| a && b),
+                    (set.difference(&other), |a, b| a && !b),
+                ] {
+                    assert!(canonical(&result));
+                    assert_eq!(covered(&result), (0..60).map(|x| f(mine[x], theirs[x])).collect::<Vec<_>>());
+                }
+                assert_eq!(covered(&set.complement(0..60)), mine.iter().map(|&x| !x).collect::<Vec<_>>());
+            }
+        }
+    }
+}
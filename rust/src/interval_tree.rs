@@ -0,0 +1,167 @@
+// rust/src/interval_tree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+
+struct Node<T> {
+    center: T,
+    // the node's intervals are `by_start[first..first + count]` and `by_end[first..first + count]`
+    first: usize,
+    count: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+}
+
+/// Static centered interval tree over closed intervals `[start, end]`. Each node picks a center
+/// point (the start of the median interval) and keeps the intervals containing it twice, sorted by
+/// start ascending and by end descending; intervals wholly left or right of the center go to the
+/// subtrees. A point left of the center then only needs the node's prefix of starts at or below
+/// it, and one right of the center the prefix of ends at or above it, so every scanned interval
+/// is a hit. Everything lives in three flat arrays built once, with no per-interval allocation.
+/// Intervals are reported by their index in the input slice, in no particular order.
+/// - Build: O(n log n)
+/// - stabbing / overlapping: O(log n + k)
+pub struct IntervalTree<T> {
+    nodes: Vec<Node<T>>,
+    by_start: Vec<(T, usize)>,
+    by_end: Vec<(T, usize)>,
+    root: Option<usize>,
+}
+
+impl<T: Ord + Copy> IntervalTree<T> {
+    /// Panics if some interval has `start > end`.
+    pub fn new(intervals: &[(T, T)]) -> Self {
+        assert!(intervals.iter().all(|&(s, e)| s <= e), "intervals need start <= end");
+        let mut tree = IntervalTree { nodes: Vec::new(), by_start: Vec::with_capacity(intervals.len()), by_end: Vec::with_capacity(intervals.len()), root: None };
+        let mut ids: Vec<usize> = (0..intervals.len()).collect();
+        ids.sort_by_key(|&i| intervals[i]);
+        tree.root = tree.build(intervals, ids);
+        tree
+    }
+
+    /// Builds the subtree over `ids` (sorted by start) and returns its node.
+    fn build(&mut self, intervals: &[(T, T)], ids: Vec<usize>) -> Option<usize> {
+        if ids.is_empty() {
+            return None;
+        }
+        let center = intervals[ids[ids.len() / 2]].0;
+        let (mut left, mut right, mut here) = (Vec::new(), Vec::new(), Vec::new());
+        for i in ids {
+            let (s, e) = intervals[i];
+            if e < center {
+                left.push(i);
+            } else if s > center {
+                right.push(i);
+            } else {
+                here.push(i);
+            }
+        }
+        let first = self.by_start.len();
+        self.by_start.extend(here.iter().map(|&i| (intervals[i].0, i)));
+        here.sort_by_key(|&i| std::cmp::Reverse(intervals[i].1));
+        self.by_end.extend(here.iter().map(|&i| (intervals[i].1, i)));
+        let node = self.nodes.len();
+        self.nodes.push(Node { center, first, count: here.len(), left: None, right: None });
+        self.nodes[node].left = self.build(intervals, left);
+        self.nodes[node].right = self.build(intervals, right);
+        Some(node)
+    }
+
+    pub fn len(&self) -> usize {
+        self.by_start.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_start.is_empty()
+    }
+
+    /// Indices of the intervals containing `x`.
+    pub fn stabbing(&self, x: T) -> Vec<usize> {
+        self.overlapping(x, x)
+    }
+
+    /// Indices of the intervals sharing at least one point with `[lo, hi]`. Panics if `lo > hi`.
+    pub fn overlapping(&self, lo: T, hi: T) -> Vec<usize> {
+        assert!(lo <= hi, "query needs lo <= hi");
+        let mut out = Vec::new();
+        let mut stack: Vec<usize> = self.root.into_iter().collect();
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            let span = node.first..node.first + node.count;
+            if hi < node.center {
+                // every interval here ends at or after the center, so only the start matters
+                out.extend(self.by_start[span].iter().take_while(|&&(s, _)| s <= hi).map(|&(_, i)| i));
+                stack.extend(node.left);
+            } else if lo > node.center {
+                out.extend(self.by_end[span].iter().take_while(|&&(e, _)| e >= lo).map(|&(_, i)| i));
+                stack.extend(node.right);
+            } else {
+                // the query covers the center, which every interval here contains
+                out.extend(self.by_start[span].iter().map(|&(_, i)| i));
+                stack.extend(node.left);
+                stack.extend(node.right);
+            }
+        }
+        out
+    }
+}
+
+impl<T> DataStructure for IntervalTree<T> {
+    const INFO: Info = Info {
+        name: "IntervalTree",
+        category: "range queries",
+        summary: "Static centered interval tree in flat arrays for stabbing and overlap queries",
+        space: "O(n)",
+        operations: &[
+            Operation::new("new", "O(n log n)", "O(n)"),
+            Operation::new("stabbing", "O(log n + k)", "O(log n)"),
+            Operation::new("overlapping", "O(log n + k)", "O(log n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    fn sorted(mut ids: Vec<usize>) -> Vec<usize> {
+        ids.sort_unstable();
+        ids
+    }
+
+    #[test]
+    fn test_stabbing_and_overlap() {
+        let intervals = [(1, 5), (3, 8), (10, 12), (6, 6), (0, 20), (13, 15)];
+        let tree = IntervalTree::new(&intervals);
+        assert_eq!(tree.len(), 6);
+        assert_eq!(sorted(tree.stabbing(6)), vec![1, 3, 4]);
+        assert_eq!(sorted(tree.stabbing(5)), vec![0, 1, 4]);
+        assert_eq!(sorted(tree.stabbing(21)), vec![]);
+        assert_eq!(sorted(tree.overlapping(9, 10)), vec![2, 4]);
+        assert_eq!(sorted(tree.overlapping(12, 13)), vec![2, 4, 5]);
+        assert_eq!(sorted(tree.overlapping(-5, 0)), vec![4]);
+        assert!(IntervalTree::<i32>::new(&[]).stabbing(0).is_empty());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(79);
+        let intervals: Vec<(i64, i64)> = (0..500)
+            .map(|_| {
+                let s = (next() % 1000) as i64;
+                (s, s + (next() % if next().is_multiple_of(10) { 400 } else { 30 }) as i64)
+            })
+            .collect();
+        let tree = IntervalTree::new(&intervals);
+        for _ in 0..500 {
+            let lo = (next() % 1100) as i64 - 50;
+            let hi = lo + (next() % 40) as i64;
+            let expected: Vec<usize> = (0..intervals.len()).filter(|&i| intervals[i].0 <= hi && lo <= intervals[i].1).collect();
+            assert_eq!(sorted(tree.overlapping(lo, hi)), expected);
+            let hits = tree.stabbing(lo);
+            assert_eq!(hits.len(), intervals.iter().filter(|&&(s, e)| s <= lo && lo <= e).count());
+        }
+    }
+}
@@ -0,0 +1,108 @@
+// rust/src/josephus.rs
+use crate::circular_list::CircularList;
+
+/// Josephus problem: `n` people stand in a circle and every `k`-th one is eliminated.
+/// Returns the 0-based position of the survivor, using the recurrence J(i) = (J(i - 1) + k) mod i.
+///
+/// Complexity: O(n). Panics if `n` or `k` is 0.
+pub fn josephus(n: usize, k: usize) -> usize {
+    assert!(n > 0 && k > 0, "n and k must be positive");
+    let mut res = 0;
+    for i in 2..=n {
+        // reduce k first so that `res + k` cannot overflow
+        res = (res + k % i) % i;
+    }
+    res
+}
+
+/// Same answer as `josephus`, but eliminates `n / k` people per round, which is much faster for small `k`.
+/// Each round shrinks the circle to `n - n / k`, so there are O(k log n) rounds while `k < n`; once the
+/// circle is smaller than `k` it finishes with the plain recurrence.
+///
+/// Complexity: O(k log n) for k < n, O(n) otherwise. Panics if `n` or `k` is 0.
+pub fn josephus_fast(n: usize, k: usize) -> usize {
+    assert!(n > 0 && k > 0, "n and k must be positive");
+    if k == 1 {
+        return n - 1;
+    }
+    // circle sizes of the rounds, largest first
+    let mut sizes = Vec::new();
+    let mut m = n;
+    while m >= k {
+        sizes.push(m);
+        m -= m / k;
+    }
+    let mut res = josephus(m, k);
+    for &size in sizes.iter().rev() {
+        // res is the survivor's position in the circle left after removing every k-th of `size`
+        res = match res.checked_sub(size % k) {
+            None => res + size - size % k,
+            Some(r) => r + r / (k - 1),
+        };
+    }
+    res
+}
+
+/// Full elimination order (0-based positions), simulated on a `CircularList` so each removal is O(1).
+/// Each step walks `(k - 1) mod m` links around a circle of `m` people, so a huge `k` costs no more
+/// than `k = n`.
+///
+/// Complexity: O(n * min(n, k))
+pub fn josephus_order(n: usize, k: usize) -> Vec<usize> {
+    assert!(k > 0, "k must be positive");
+    let mut circle = CircularList::new();
+    for i in 0..n {
+        circle.push_back(i);
+    }
+    let mut order = Vec::with_capacity(n);
+    let mut cur = circle.head();
+    while let Some(mut h) = cur {
+        for _ in 0..(k - 1) % circle.len() {
+            h = circle.next(h).expect("live handle");
+        }
+        cur = if circle.len() > 1 { circle.next(h) } else { None };
+        order.push(circle.remove(h).expect("live handle"));
+    }
+    order
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_values() {
+        assert_eq!(josephus(1, 3), 0);
+        assert_eq!(josephus(7, 3), 3);
+        assert_eq!(josephus(41, 3), 30);
+        assert_eq!(josephus(5, 2), 2);
+    }
+
+    #[test]
+    fn test_fast_matches_linear() {
+        for n in 1..60 {
+            for k in 1..12 {
+                assert_eq!(josephus_fast(n, k), josephus(n, k), "n={} k={}", n, k);
+            }
+        }
+        // closed form for k = 2: 2 * (n - 2^floor(log2 n))
+        assert_eq!(josephus_fast(1_000_000_000, 2), 926_258_176);
+
+        // k >= n, where the round-based recursion used to go n levels deep, and k near usize::MAX
+        assert_eq!(josephus_fast(1_000_000, 2_000_000), josephus(1_000_000, 2_000_000));
+        // every step only sees k mod i, and lcm(1..=10) = 2520
+        let k = usize::MAX % 2520;
+        assert_eq!(josephus_fast(10, usize::MAX), *josephus_order(10, k).last().unwrap());
+        assert_eq!(josephus(10, usize::MAX), josephus(10, k));
+    }
+
+    #[test]
+    fn test_elimination_order() {
+        assert_eq!(josephus_order(7, 3), vec![2, 5, 1, 6, 4, 0, 3]);
+        assert_eq!(*josephus_order(41, 3).last().unwrap(), josephus(41, 3));
+        assert!(josephus_order(0, 2).is_empty());
+        // only k mod (circle size) matters at each step, so a huge k must not walk k links
+        assert_eq!(josephus_order(5, usize::MAX), josephus_order(5, usize::MAX % 60));
+        assert_eq!(*josephus_order(1000, usize::MAX).last().unwrap(), josephus(1000, usize::MAX));
+    }
+}
@@ -0,0 +1,189 @@
+// rust/src/kd_tree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+/// A candidate neighbour: squared distance, then point index. Ordered so that a `BinaryHeap`
+/// keeps the farthest candidate on top.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Candidate(f64, usize);
+
+impl Eq for Candidate {}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn dist2<const D: usize>(a: &[f64; D], b: &[f64; D]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+/// Static k-d tree over points in `D` dimensions, stored implicitly: the points are permuted so
+/// that each subrange's median (along axis `depth % D`) sits in its middle, with the smaller
+/// coordinates to its left and the larger to its right. Queries descend into the side of the
+/// splitting plane holding the query first and only cross the plane when the best distance found
+/// so far reaches over it. Points are reported by their index in the input slice.
+/// - Build: O(n log n)
+/// - nearest / k_nearest: O(log n) expected for well-spread points, O(n) worst case
+/// - range: O(n^(1 - 1/D) + k)
+pub struct KdTree<const D: usize> {
+    points: Vec<[f64; D]>,
+    // input index of each stored point
+    ids: Vec<usize>,
+}
+
+impl<const D: usize> KdTree<D> {
+    /// Panics if `D` is 0 or a coordinate is NaN.
+    pub fn new(points: &[[f64; D]]) -> Self {
+        assert!(D > 0, "a k-d tree needs at least one dimension");
+        assert!(points.iter().flatten().all(|x| !x.is_nan()), "point coordinates must not be NaN");
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        Self::build(points, &mut order, 0);
+        KdTree { points: order.iter().map(|&i| points[i]).collect(), ids: order }
+    }
+
+    fn build(points: &[[f64; D]], order: &mut [usize], depth: usize) {
+        if order.len() <= 1 {
+            return;
+        }
+        let (axis, mid) = (depth % D, order.len() / 2);
+        order.select_nth_unstable_by(mid, |&a, &b| points[a][axis].total_cmp(&points[b][axis]));
+        let (left, right) = order.split_at_mut(mid);
+        Self::build(points, left, depth + 1);
+        Self::build(points, &mut right[1..], depth + 1);
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    /// Index and distance of the point closest to `query`, ties going to the smaller index.
+    pub fn nearest(&self, query: &[f64; D]) -> Option<(usize, f64)> {
+        self.k_nearest(query, 1).pop()
+    }
+
+    /// Indices and distances of the `k` points closest to `query`, nearest first (ties by index).
+    pub fn k_nearest(&self, query: &[f64; D], k: usize) -> Vec<(usize, f64)> {
+        let mut best = BinaryHeap::with_capacity(k + 1);
+        if k > 0 {
+            self.search(0..self.len(), 0, query, k, &mut best);
+        }
+        best.into_sorted_vec().into_iter().map(|Candidate(d2, i)| (i, d2.sqrt())).collect()
+    }
+
+    fn search(&self, span: std::ops::Range<usize>, depth: usize, query: &[f64; D], k: usize, best: &mut BinaryHeap<Candidate>) {
+        if span.is_empty() {
+            return;
+        }
+        let mid = span.start + span.len() / 2;
+        let p = &self.points[mid];
+        best.push(Candidate(dist2(p, query), self.ids[mid]));
+        if best.len() > k {
+            best.pop();
+        }
+        let diff = query[depth % D] - p[depth % D];
+        let (near, far) = if diff < 0.0 { (span.start..mid, mid + 1..span.end) } else { (mid + 1..span.end, span.start..mid) };
+        self.search(near, depth + 1, query, k, best);
+        // `<=` so that points on the plane still compete for ties
+        if best.len() < k || best.peek().is_some_and(|w| diff * diff <= w.0) {
+            self.search(far, depth + 1, query, k, best);
+        }
+    }
+
+    /// Indices of the points inside the axis-aligned box `lo..=hi` (bounds included), ascending.
+    pub fn range(&self, lo: &[f64; D], hi: &[f64; D]) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack = vec![(0..self.len(), 0)];
+        while let Some((span, depth)) = stack.pop() {
+            if span.is_empty() {
+                continue;
+            }
+            let mid = span.start + span.len() / 2;
+            let (p, axis) = (&self.points[mid], depth % D);
+            if (0..D).all(|a| lo[a] <= p[a] && p[a] <= hi[a]) {
+                out.push(self.ids[mid]);
+            }
+            // equal coordinates may sit on either side of the median
+            if lo[axis] <= p[axis] {
+                stack.push((span.start..mid, depth + 1));
+            }
+            if p[axis] <= hi[axis] {
+                stack.push((mid + 1..span.end, depth + 1));
+            }
+        }
+        out.sort_unstable();
+        out
+    }
+}
+
+impl<const D: usize> DataStructure for KdTree<D> {
+    const INFO: Info = Info {
+        name: "KdTree",
+        category: "spatial",
+        summary: "Static k-d tree for nearest neighbour, k nearest and box queries over D-dimensional points",
+        space: "O(n)",
+        operations: &[
+            Operation::new("new", "O(n log n)", "O(n)"),
+            Operation::new("nearest", "O(log n) expected, O(n) worst", "O(log n)"),
+            Operation::new("k_nearest", "O(k log k + log n) expected", "O(k + log n)"),
+            Operation::new("range", "O(n^(1 - 1/D) + k)", "O(log n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_queries_2d() {
+        let points = [[2.0, 3.0], [5.0, 4.0], [9.0, 6.0], [4.0, 7.0], [8.0, 1.0], [7.0, 2.0], [4.0, 7.0]];
+        let tree = KdTree::new(&points);
+        assert_eq!(tree.nearest(&[9.0, 2.0]), Some((4, 2f64.sqrt())));
+        // the duplicate [4, 7] ties; the smaller index wins
+        assert_eq!(tree.nearest(&[4.0, 8.0]), Some((3, 1.0)));
+        let knn: Vec<usize> = tree.k_nearest(&[5.0, 5.0], 3).into_iter().map(|(i, _)| i).collect();
+        assert_eq!(knn, vec![1, 3, 6]);
+        assert_eq!(tree.k_nearest(&[0.0, 0.0], 10).len(), 7);
+        assert_eq!(tree.range(&[4.0, 2.0], &[8.0, 7.0]), vec![1, 3, 5, 6]);
+        assert!(tree.range(&[10.0, 0.0], &[11.0, 9.0]).is_empty());
+
+        let empty = KdTree::<3>::new(&[]);
+        assert_eq!((empty.nearest(&[0.0; 3]), empty.range(&[0.0; 3], &[1.0; 3])), (None, vec![]));
+    }
+
+    #[test]
+    fn test_against_brute_force_3d() {
+        let mut rng = test_rng(71);
+        // a coarse grid, so that ties and equal coordinates are common
+        let mut next = || (rng() % 20) as f64;
+        let points: Vec<[f64; 3]> = (0..400).map(|_| [next(), next(), next()]).collect();
+        let tree = KdTree::new(&points);
+        for _ in 0..200 {
+            let q = [next() + 0.5, next(), next() - 0.25];
+            let mut expected: Vec<(f64, usize)> = points.iter().enumerate().map(|(i, p)| (dist2(p, &q), i)).collect();
+            expected.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+            let got: Vec<usize> = tree.k_nearest(&q, 5).into_iter().map(|(i, _)| i).collect();
+            assert_eq!(got, expected[..5].iter().map(|&(_, i)| i).collect::<Vec<_>>());
+            let (lo, hi) = ([next(), next(), next()], [next(), next(), next()]);
+            let inside: Vec<usize> = (0..points.len()).filter(|&i| (0..3).all(|a| lo[a] <= points[i][a] && points[i][a] <= hi[a])).collect();
+            assert_eq!(tree.range(&lo, &hi), inside);
+        }
+    }
+}
@@ -1,11 +1,18 @@
 // rust/src/kmp.rs
+use crate::DataStructure;
+use crate::byte_map::ByteMap;
+use crate::info::{Info, Operation};
 /// Knuth-Morris-Pratt (KMP) algorithm implementation for substring search.
 /// - prefix_function computes the longest proper prefix which is also suffix for each prefix.
 /// - find_all returns start indices where pattern matches text. Works on bytes (UTF-8).
 ///
 /// Complexity: O(n + m)
 pub fn prefix_function(pattern: &str) -> Vec<usize> {
-    let s = pattern.as_bytes();
+    prefix_function_slice(pattern.as_bytes())
+}
+
+/// Prefix function over any slice of comparable tokens (bytes, ints, words, ...).
+pub fn prefix_function_slice<T: Eq>(s: &[T]) -> Vec<usize> {
     let n = s.len();
     let mut pi = vec![0usize; n];
     for i in 1..n {
@@ -25,31 +32,271 @@ pub fn prefix_function(pattern: &str) -> Vec<usize> {
 /// Find all occurrences of `pattern` in `text`. Returns vector of starting indices.
 /// Returns empty vec if pattern is empty or longer than text.
 pub fn find_all(text: &str, pattern: &str) -> Vec<usize> {
-    let n = text.len();
+    find_all_slice(text.as_bytes(), pattern.as_bytes())
+}
+
+/// Find all occurrences of a token `pattern` inside a token `text`. Returns starting indices.
+/// Returns empty vec if pattern is empty or longer than text.
+pub fn find_all_slice<T: Eq>(text: &[T], pattern: &[T]) -> Vec<usize> {
+    let mut res = vec![];
+    scan(text, pattern, true, |pos| {
+        res.push(pos);
+        true
+    });
+    res
+}
+
+/// Like `find_all`, but bytes are compared after normalizing them with `map` (for example
+/// `ByteMap::ascii_case_insensitive()`). Only the pattern is normalized up front; the text is read in
+/// place and need not be valid UTF-8.
+pub fn find_all_with(text: &[u8], pattern: &[u8], map: &ByteMap) -> Vec<usize> {
+    let pattern = map.apply(pattern);
+    let mut res = vec![];
+    scan_by(text, &pattern, true, |&c, &p| map.map(c) == p, |pos| {
+        res.push(pos);
+        true
+    });
+    res
+}
+
+/// ASCII case-insensitive `find_all`; non-ASCII bytes must match exactly.
+pub fn find_all_ignore_ascii_case(text: &str, pattern: &str) -> Vec<usize> {
+    find_all_with(text.as_bytes(), pattern.as_bytes(), &ByteMap::ascii_case_insensitive())
+}
+
+/// Like `find_all`, but returns char (Unicode scalar) indices instead of byte offsets,
+/// so results can be used to slice the text the way Python does.
+/// A UTF-8 pattern can only match starting at a char boundary, so every result maps to a whole char.
+pub fn find_all_chars(text: &str, pattern: &str) -> Vec<usize> {
+    byte_to_char_indices(text, &find_all(text, pattern))
+}
+
+/// Converts sorted byte offsets that lie on char boundaries of `text` into char indices, in one pass.
+pub fn byte_to_char_indices(text: &str, byte_offsets: &[usize]) -> Vec<usize> {
+    let mut res = Vec::with_capacity(byte_offsets.len());
+    let mut chars = text.char_indices().map(|(b, _)| b).enumerate().peekable();
+    for &offset in byte_offsets {
+        debug_assert!(text.is_char_boundary(offset));
+        while let Some(&(_, b)) = chars.peek() {
+            if b >= offset {
+                break;
+            }
+            chars.next();
+        }
+        res.push(chars.peek().map(|&(ci, _)| ci).unwrap_or_else(|| text.chars().count()));
+    }
+    res
+}
+
+/// Runs KMP and calls `on_match` with each start index until it returns false.
+/// With `overlapping == false` the search restarts after each match, giving leftmost non-overlapping matches.
+fn scan<T: Eq>(text: &[T], pattern: &[T], overlapping: bool, on_match: impl FnMut(usize) -> bool) {
+    scan_by(text, pattern, overlapping, |a, b| a == b, on_match)
+}
+
+/// `scan` with a custom text-to-pattern comparison; the prefix function still uses `==` on the pattern.
+fn scan_by<T: Eq>(
+    text: &[T],
+    pattern: &[T],
+    overlapping: bool,
+    eq: impl Fn(&T, &T) -> bool,
+    mut on_match: impl FnMut(usize) -> bool,
+) {
     let m = pattern.len();
-    if m == 0 || m > n {
-        return vec![];
+    if m == 0 || m > text.len() {
+        return;
     }
-    let mut res = vec![];
-    let pi = prefix_function(pattern);
-    let t_bytes = text.as_bytes();
-    let p_bytes = pattern.as_bytes();
+    let pi = prefix_function_slice(pattern);
     let mut j = 0;
-    for i in 0..n {
-        while j > 0 && t_bytes[i] != p_bytes[j] {
+    for (i, c) in text.iter().enumerate() {
+        while j > 0 && !eq(c, &pattern[j]) {
             j = pi[j - 1];
         }
-        if t_bytes[i] == p_bytes[j] {
+        if eq(c, &pattern[j]) {
             j += 1;
         }
         if j == m {
-            res.push(i + 1 - m);
-            j = pi[j - 1];
+            if !on_match(i + 1 - m) {
+                return;
+            }
+            j = if overlapping { pi[j - 1] } else { 0 };
         }
     }
-    res
 }
 
+/// Index of the first occurrence of `pattern`, stopping the scan as soon as it is found.
+pub fn find_first(text: &str, pattern: &str) -> Option<usize> {
+    let mut first = None;
+    scan(text.as_bytes(), pattern.as_bytes(), true, |pos| {
+        first = Some(pos);
+        false
+    });
+    first
+}
+
+/// Returns true if `pattern` occurs in `text`. Like `find_all`, an empty pattern never matches.
+pub fn contains(text: &str, pattern: &str) -> bool {
+    find_first(text, pattern).is_some()
+}
+
+/// Number of occurrences of `pattern`, overlapping ones included ("aa" occurs 3 times in "aaaa").
+pub fn count_occurrences(text: &str, pattern: &str) -> usize {
+    let mut count = 0;
+    scan(text.as_bytes(), pattern.as_bytes(), true, |_| {
+        count += 1;
+        true
+    });
+    count
+}
+
+/// Number of leftmost non-overlapping occurrences of `pattern` ("aa" occurs 2 times in "aaaa").
+pub fn count_non_overlapping(text: &str, pattern: &str) -> usize {
+    let mut count = 0;
+    scan(text.as_bytes(), pattern.as_bytes(), false, |_| {
+        count += 1;
+        true
+    });
+    count
+}
+
+/// Splits `text` on non-overlapping occurrences of `pattern`, like `str::split`.
+/// An empty pattern returns the whole text as a single piece.
+pub fn split_by_pattern<'a>(text: &'a str, pattern: &str) -> Vec<&'a str> {
+    let mut pieces = vec![];
+    let mut start = 0;
+    scan(text.as_bytes(), pattern.as_bytes(), false, |pos| {
+        pieces.push(&text[start..pos]);
+        start = pos + pattern.len();
+        true
+    });
+    pieces.push(&text[start..]);
+    pieces
+}
+
+
+/// Full KMP automaton: `table[state][c]` is the state reached from `state` on the `c`-th char of
+/// `alphabet`, where state `j` means the last `j` chars read equal the first `j` chars of the pattern.
+/// State `pattern.len()` is the match state; its transitions continue as if the match overlapped.
+/// Useful inside DP over strings, e.g. counting strings of length n that avoid the pattern.
+/// Chars of the pattern missing from `alphabet` can never be matched.
+///
+/// Complexity: O(m * σ)
+pub fn build_automaton(pattern: &str, alphabet: &str) -> Vec<Vec<usize>> {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let alphabet: Vec<char> = alphabet.chars().collect();
+    build_automaton_slice(&pattern, &alphabet)
+}
+
+/// KMP automaton over any slice of comparable tokens; see `build_automaton`.
+pub fn build_automaton_slice<T: Eq>(pattern: &[T], alphabet: &[T]) -> Vec<Vec<usize>> {
+    let m = pattern.len();
+    let pi = prefix_function_slice(pattern);
+    let mut table: Vec<Vec<usize>> = Vec::with_capacity(m + 1);
+    for state in 0..=m {
+        let row = alphabet
+            .iter()
+            .enumerate()
+            .map(|(ci, c)| {
+                if state < m && pattern[state] == *c {
+                    state + 1
+                } else if state == 0 {
+                    0
+                } else {
+                    // same move as from the longest border, which is an earlier (complete) row
+                    table[pi[state - 1]][ci]
+                }
+            })
+            .collect();
+        table.push(row);
+    }
+    table
+}
+
+/// Incremental KMP matcher for text that arrives in pieces (sockets, file readers, ...).
+/// Only the pattern and its prefix function are kept, never the text.
+/// Match positions are byte offsets from the start of the stream.
+pub struct KmpMatcher {
+    pattern: Vec<u8>,
+    pi: Vec<usize>,
+    map: ByteMap,
+    state: usize,
+    consumed: usize,
+}
+
+impl KmpMatcher {
+    pub fn new(pattern: &str) -> Self {
+        Self::with_map(pattern.as_bytes(), ByteMap::identity())
+    }
+
+    /// Matcher that compares bytes after normalizing them with `map`, e.g. to grep a log stream
+    /// case-insensitively without lowercasing each chunk first.
+    pub fn with_map(pattern: &[u8], map: ByteMap) -> Self {
+        let pattern = map.apply(pattern);
+        KmpMatcher {
+            pi: prefix_function_slice(&pattern),
+            pattern,
+            map,
+            state: 0,
+            consumed: 0,
+        }
+    }
+
+    /// Feeds one byte. Returns the start offset of a match that ends at this byte, if any.
+    /// An empty pattern never matches.
+    pub fn push(&mut self, byte: u8) -> Option<usize> {
+        self.consumed += 1;
+        let byte = self.map.map(byte);
+        let m = self.pattern.len();
+        if m == 0 {
+            return None;
+        }
+        while self.state > 0 && byte != self.pattern[self.state] {
+            self.state = self.pi[self.state - 1];
+        }
+        if byte == self.pattern[self.state] {
+            self.state += 1;
+        }
+        if self.state == m {
+            self.state = self.pi[m - 1];
+            return Some(self.consumed - m);
+        }
+        None
+    }
+
+    /// Feeds a chunk of bytes and returns the start offsets of all matches completed inside it.
+    /// Matches spanning chunk boundaries are found as well.
+    pub fn push_chunk(&mut self, chunk: &[u8]) -> Vec<usize> {
+        chunk.iter().filter_map(|&b| self.push(b)).collect()
+    }
+
+    /// Number of bytes consumed so far.
+    pub fn position(&self) -> usize {
+        self.consumed
+    }
+
+    /// Forgets all consumed input, as if the matcher was just created.
+    pub fn reset(&mut self) {
+        self.state = 0;
+        self.consumed = 0;
+    }
+}
+
+
+impl DataStructure for KmpMatcher {
+    const INFO: Info = Info {
+        name: "KmpMatcher",
+        category: "strings",
+        summary: "Streaming KMP matcher fed one byte or chunk at a time (m = pattern length)",
+        space: "O(m)",
+        operations: &[
+            Operation::new("new", "O(m)", "O(m)"),
+            Operation::new("push", "O(1) amortized", "O(1)"),
+            Operation::new("push_chunk", "O(chunk) amortized", "O(matches)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
 
 #[cfg(test)]
 mod tests {
@@ -89,4 +336,136 @@ mod tests {
     fn test_empty_pattern() {
         assert!(find_all("anytext", "").is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_find_first_and_contains() {
+        assert_eq!(find_first("ababcabababc", "abab"), Some(0));
+        assert_eq!(find_first("xxabcabc", "abc"), Some(2));
+        assert_eq!(find_first("hello", "xyz"), None);
+        assert!(contains("hello world", "o w"));
+        assert!(!contains("hello", ""));
+    }
+
+    #[test]
+    fn test_find_all_chars_multibyte() {
+        let text = "héllo wörld, héllo";
+        assert_eq!(find_all(text, "llo"), vec![3, 18]);
+        assert_eq!(find_all_chars(text, "llo"), vec![2, 15]);
+        let chars: Vec<char> = text.chars().collect();
+        for idx in find_all_chars(text, "héllo") {
+            assert_eq!(chars[idx..idx + 5].iter().collect::<String>(), "héllo");
+        }
+        assert_eq!(find_all_chars("日本語の日本", "日本"), vec![0, 4]);
+        assert_eq!(find_all_chars("ascii only", "only"), vec![6]);
+        assert!(find_all_chars("abc", "").is_empty());
+    }
+
+    #[test]
+    fn test_counts() {
+        assert_eq!(count_occurrences("aaaa", "aa"), 3);
+        assert_eq!(count_non_overlapping("aaaa", "aa"), 2);
+        assert_eq!(count_non_overlapping("aaaaa", "aa"), 2);
+        assert_eq!(count_occurrences("abc", ""), 0);
+    }
+
+    #[test]
+    fn test_split_by_pattern() {
+        assert_eq!(split_by_pattern("a, b, c", ", "), vec!["a", "b", "c"]);
+        assert_eq!(split_by_pattern(", a, ", ", "), vec!["", "a", ""]);
+        assert_eq!(split_by_pattern("aaaa", "aa"), vec!["", "", ""]);
+        assert_eq!(split_by_pattern("no match", "xyz"), vec!["no match"]);
+        assert_eq!(split_by_pattern("abc", ""), vec!["abc"]);
+        // splitting must agree with the standard library
+        let text = "xxyxxyyxxx";
+        assert_eq!(split_by_pattern(text, "xx"), text.split("xx").collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_generic_token_slices() {
+        let text = [1, 2, 1, 2, 1, 2, 3];
+        assert_eq!(prefix_function_slice(&[1, 2, 1, 2]), vec![0, 0, 1, 2]);
+        assert_eq!(find_all_slice(&text, &[1, 2, 1]), vec![0, 2]);
+        assert!(find_all_slice(&text, &[4]).is_empty());
+
+        let words = ["to", "be", "or", "not", "to", "be"];
+        assert_eq!(find_all_slice(&words, &["to", "be"]), vec![0, 4]);
+        let empty: [&str; 0] = [];
+        assert!(find_all_slice(&words, &empty).is_empty());
+    }
+
+    #[test]
+    fn test_streaming_matcher_across_chunks() {
+        let text = "ababcabababc";
+        let mut m = KmpMatcher::new("abab");
+        let mut occ = m.push_chunk(b"aba");
+        occ.extend(m.push_chunk(b"bcab"));
+        occ.extend(m.push_chunk(b"ababc"));
+        assert_eq!(occ, find_all(text, "abab"));
+        assert_eq!(m.position(), text.len());
+
+        m.reset();
+        assert_eq!(m.push_chunk(b"abab"), vec![0]);
+    }
+
+    #[test]
+    fn test_streaming_matcher_single_bytes() {
+        let mut m = KmpMatcher::new("aa");
+        let occ: Vec<Option<usize>> = b"aaa".iter().map(|&b| m.push(b)).collect();
+        assert_eq!(occ, vec![None, Some(0), Some(1)]);
+
+        let mut empty = KmpMatcher::new("");
+        assert!(empty.push_chunk(b"abc").is_empty());
+    }
+
+    #[test]
+    fn test_build_automaton() {
+        let table = build_automaton("abab", "ab");
+        assert_eq!(table, vec![vec![1, 0], vec![1, 2], vec![3, 0], vec![1, 4], vec![3, 0]]);
+
+        // walking the automaton finds the same matches as find_all
+        let text = "abababbabab";
+        let mut state = 0;
+        let mut ends = vec![];
+        for (i, c) in text.bytes().enumerate() {
+            state = table[state][(c - b'a') as usize];
+            if state == 4 {
+                ends.push(i + 1 - 4);
+            }
+        }
+        assert_eq!(ends, find_all(text, "abab"));
+    }
+
+    #[test]
+    fn test_automaton_counts_avoiding_strings() {
+        // number of strings over {a, b} of length n that do not contain "aba", by DP over states
+        let table = build_automaton("aba", "ab");
+        let mut ways = vec![0u64; 4];
+        ways[0] = 1;
+        for n in 1..=12 {
+            let mut next = vec![0u64; 4];
+            for (state, &w) in ways.iter().enumerate().take(3) {
+                for &to in &table[state] {
+                    next[to] += w;
+                }
+            }
+            ways = next;
+            let brute = (0..1u32 << n)
+                .filter(|mask| {
+                    let s: String = (0..n).map(|i| if mask >> i & 1 == 1 { 'b' } else { 'a' }).collect();
+                    !contains(&s, "aba")
+                })
+                .count() as u64;
+            assert_eq!(ways[..3].iter().sum::<u64>(), brute, "n = {}", n);
+        }
+    }
+
+    #[test]
+    fn test_streaming_matcher_ignore_case() {
+        let mut m = KmpMatcher::with_map(b"error", ByteMap::ascii_case_insensitive());
+        let mut hits = m.push_chunk(b"ok\nERR");
+        hits.extend(m.push_chunk(b"OR: disk\nError again"));
+        assert_eq!(hits, vec![3, 15]);
+        assert_eq!(find_all_ignore_ascii_case("Ababab", "ABAB"), vec![0, 2]);
+        assert_eq!(find_all_with(b"\xffaA", b"a", &ByteMap::ascii_case_insensitive()), vec![1, 2]);
+    }
+}
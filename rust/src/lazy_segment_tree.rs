@@ -0,0 +1,369 @@
+// rust/src/lazy_segment_tree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::range;
+use std::ops::RangeBounds;
+
+/// A (value, lazy) pair for `LazySegmentTree`: a monoid of values (as in `Monoid`) plus a monoid of
+/// pending updates acting on them. An update must distribute over `combine`, i.e.
+/// `apply(f, combine(a, b)) == combine(apply(f, a), apply(f, b))` (with the lengths adding up),
+/// which is what lets a whole node be updated without touching its children.
+pub trait LazyOp {
+    type Value: Clone;
+    type Lazy: Clone;
+
+    fn identity(&self) -> Self::Value;
+    fn combine(&self, a: &Self::Value, b: &Self::Value) -> Self::Value;
+    /// The update that changes nothing.
+    fn no_update(&self) -> Self::Lazy;
+    /// Applies `f` to the aggregate `v` of `len` consecutive elements.
+    fn apply(&self, f: &Self::Lazy, v: &Self::Value, len: usize) -> Self::Value;
+    /// The single update equivalent to `earlier` followed by `later`.
+    fn compose(&self, later: &Self::Lazy, earlier: &Self::Lazy) -> Self::Lazy;
+}
+
+/// Update used by the built-in ops: assign every element (if `assign` is set), then add `add`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Update {
+    pub assign: Option<i64>,
+    pub add: i64,
+}
+
+impl Update {
+    pub fn add(delta: i64) -> Self {
+        Update { assign: None, add: delta }
+    }
+
+    pub fn assign(value: i64) -> Self {
+        Update { assign: Some(value), add: 0 }
+    }
+
+    fn then(self, later: Update) -> Update {
+        match later.assign {
+            Some(_) => later,
+            None => Update {
+                assign: self.assign,
+                add: self.add + later.add,
+            },
+        }
+    }
+
+    fn on(self, v: i64) -> i64 {
+        self.assign.unwrap_or(v) + self.add
+    }
+}
+
+/// Range sum over `i64` under `Update` (range add / range assign).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RangeSum;
+
+/// Range minimum over `i64` under `Update`; `i64::MAX` for an empty range.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RangeMin;
+
+/// Range maximum over `i64` under `Update`; `i64::MIN` for an empty range.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RangeMax;
+
+impl LazyOp for RangeSum {
+    type Value = i64;
+    type Lazy = Update;
+
+    fn identity(&self) -> i64 {
+        0
+    }
+
+    fn combine(&self, a: &i64, b: &i64) -> i64 {
+        a + b
+    }
+
+    fn no_update(&self) -> Update {
+        Update::default()
+    }
+
+    fn apply(&self, f: &Update, v: &i64, len: usize) -> i64 {
+        let len = len as i64;
+        f.assign.map_or(*v, |a| a * len) + f.add * len
+    }
+
+    fn compose(&self, later: &Update, earlier: &Update) -> Update {
+        earlier.then(*later)
+    }
+}
+
+macro_rules! impl_extremum_op {
+    ($op:ty, $identity:expr, $pick:ident) => {
+        impl LazyOp for $op {
+            type Value = i64;
+            type Lazy = Update;
+
+            fn identity(&self) -> i64 {
+                $identity
+            }
+
+            fn combine(&self, a: &i64, b: &i64) -> i64 {
+                (*a).$pick(*b)
+            }
+
+            fn no_update(&self) -> Update {
+                Update::default()
+            }
+
+            // assigning or adding a constant moves every element the same way, so it moves the extremum too
+            fn apply(&self, f: &Update, v: &i64, _len: usize) -> i64 {
+                f.on(*v)
+            }
+
+            fn compose(&self, later: &Update, earlier: &Update) -> Update {
+                earlier.then(*later)
+            }
+        }
+    };
+}
+
+impl_extremum_op!(RangeMin, i64::MAX, min);
+impl_extremum_op!(RangeMax, i64::MIN, max);
+
+/// Segment tree with lazy propagation: updates and aggregate queries over whole ranges. Each node
+/// keeps its aggregate plus one pending update for its subtree, pushed to the children only when a
+/// later operation needs to go below it.
+/// - Build: O(n)
+/// - update / query: O(log n)
+///
+/// The built-in `RangeSum`, `RangeMin` and `RangeMax` take `Update::add` / `Update::assign`; other
+/// combinations (affine maps, range flip over bit counts, ...) are a `LazyOp` impl away.
+pub struct LazySegmentTree<Op: LazyOp> {
+    len: usize,
+    tree: Vec<Op::Value>,
+    lazy: Vec<Op::Lazy>,
+    op: Op,
+}
+
+impl<Op: LazyOp> LazySegmentTree<Op> {
+    /// `len` copies of the identity.
+    pub fn new(len: usize, op: Op) -> Self {
+        let size = 4 * len.max(1);
+        LazySegmentTree {
+            len,
+            tree: vec![op.identity(); size],
+            lazy: vec![op.no_update(); size],
+            op,
+        }
+    }
+
+    pub fn from_slice(values: &[Op::Value], op: Op) -> Self {
+        let mut st = Self::new(values.len(), op);
+        if !values.is_empty() {
+            st.build(1, 0, values.len(), values);
+        }
+        st
+    }
+
+    fn build(&mut self, node: usize, lo: usize, hi: usize, values: &[Op::Value]) {
+        if hi - lo == 1 {
+            self.tree[node] = values[lo].clone();
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.build(2 * node, lo, mid, values);
+        self.build(2 * node + 1, mid, hi, values);
+        self.pull(node);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn pull(&mut self, node: usize) {
+        self.tree[node] = self.op.combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    fn apply_node(&mut self, node: usize, f: &Op::Lazy, len: usize) {
+        self.tree[node] = self.op.apply(f, &self.tree[node], len);
+        self.lazy[node] = self.op.compose(f, &self.lazy[node]);
+    }
+
+    fn push(&mut self, node: usize, lo: usize, mid: usize, hi: usize) {
+        let f = std::mem::replace(&mut self.lazy[node], self.op.no_update());
+        self.apply_node(2 * node, &f, mid - lo);
+        self.apply_node(2 * node + 1, &f, hi - mid);
+    }
+
+    /// Applies `f` to every element in `range` (half-open convention, see `crate::range`).
+    /// Panics if the range is out of bounds.
+    pub fn update<R: RangeBounds<usize>>(&mut self, range: R, f: Op::Lazy) {
+        let r = range::resolve(range, self.len);
+        if !r.is_empty() {
+            self.update_rec(1, 0, self.len, r.start, r.end, &f);
+        }
+    }
+
+    fn update_rec(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize, f: &Op::Lazy) {
+        if l <= lo && hi <= r {
+            self.apply_node(node, f, hi - lo);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(node, lo, mid, hi);
+        if l < mid {
+            self.update_rec(2 * node, lo, mid, l, r, f);
+        }
+        if r > mid {
+            self.update_rec(2 * node + 1, mid, hi, l, r, f);
+        }
+        self.pull(node);
+    }
+
+    /// Aggregate of `range`; the identity for an empty range. Takes `&mut self` because pending
+    /// updates are pushed down on the way. Panics if the range is out of bounds.
+    pub fn query<R: RangeBounds<usize>>(&mut self, range: R) -> Op::Value {
+        let r = range::resolve(range, self.len);
+        if r.is_empty() {
+            return self.op.identity();
+        }
+        self.query_rec(1, 0, self.len, r.start, r.end)
+    }
+
+    fn query_rec(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize) -> Op::Value {
+        if l <= lo && hi <= r {
+            return self.tree[node].clone();
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(node, lo, mid, hi);
+        if r <= mid {
+            self.query_rec(2 * node, lo, mid, l, r)
+        } else if l >= mid {
+            self.query_rec(2 * node + 1, mid, hi, l, r)
+        } else {
+            let left = self.query_rec(2 * node, lo, mid, l, r);
+            let right = self.query_rec(2 * node + 1, mid, hi, l, r);
+            self.op.combine(&left, &right)
+        }
+    }
+
+    /// Value at `index`. Panics if out of bounds.
+    pub fn get(&mut self, index: usize) -> Op::Value {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        self.query(index..=index)
+    }
+
+    /// Aggregate of all elements, in O(1).
+    pub fn all(&self) -> Op::Value {
+        if self.is_empty() { self.op.identity() } else { self.tree[1].clone() }
+    }
+
+    /// Current values of all elements (pushes every pending update), in O(n).
+    pub fn to_vec(&mut self) -> Vec<Op::Value> {
+        (0..self.len).map(|i| self.get(i)).collect()
+    }
+}
+
+impl<Op: LazyOp> DataStructure for LazySegmentTree<Op> {
+    const INFO: Info = Info {
+        name: "LazySegmentTree",
+        category: "range queries",
+        summary: "Segment tree with lazy propagation: range updates combined with range aggregates",
+        space: "O(n)",
+        operations: &[
+            Operation::new("from_slice", "O(n)", "O(n)"),
+            Operation::new("update", "O(log n)", "O(log n)"),
+            Operation::new("query", "O(log n)", "O(log n)"),
+            Operation::new("all", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_add_and_assign_match_naive() {
+        let mut next = test_rng(21);
+        let n = 41;
+        let mut values: Vec<i64> = (0..n).map(|_| (next() % 100) as i64).collect();
+        let mut sum = LazySegmentTree::from_slice(&values, RangeSum);
+        let mut min = LazySegmentTree::from_slice(&values, RangeMin);
+        let mut max = LazySegmentTree::from_slice(&values, RangeMax);
+        for _ in 0..300 {
+            let a = next() as usize % (n + 1);
+            let b = next() as usize % (n + 1);
+            let (l, r) = (a.min(b), a.max(b));
+            let x = (next() % 50) as i64 - 25;
+            match next() % 3 {
+                0 => {
+                    let f = if next() & 1 == 0 { Update::add(x) } else { Update::assign(x) };
+                    for v in &mut values[l..r] {
+                        *v = f.on(*v);
+                    }
+                    sum.update(l..r, f);
+                    min.update(l..r, f);
+                    max.update(l..r, f);
+                }
+                _ => {
+                    let chunk = &values[l..r];
+                    assert_eq!(sum.query(l..r), chunk.iter().sum::<i64>());
+                    assert_eq!(min.query(l..r), chunk.iter().copied().min().unwrap_or(i64::MAX));
+                    assert_eq!(max.query(l..r), chunk.iter().copied().max().unwrap_or(i64::MIN));
+                }
+            }
+        }
+        assert_eq!(sum.to_vec(), values);
+        assert_eq!(sum.all(), values.iter().sum::<i64>());
+    }
+
+    #[test]
+    fn test_update_composition() {
+        let mut st = LazySegmentTree::new(6, RangeSum);
+        st.update(.., Update::add(2));
+        st.update(1..5, Update::assign(10));
+        st.update(3.., Update::add(-1));
+        assert_eq!(st.to_vec(), vec![2, 10, 10, 9, 9, 1]);
+        assert_eq!(st.query(2..=3), 19);
+        assert_eq!(st.query(4..4), 0);
+    }
+
+    /// Affine maps `x -> a * x + b` acting on sums: a user-defined (value, lazy) pair.
+    struct AffineSum;
+
+    impl LazyOp for AffineSum {
+        type Value = i64;
+        type Lazy = (i64, i64);
+
+        fn identity(&self) -> i64 {
+            0
+        }
+
+        fn combine(&self, a: &i64, b: &i64) -> i64 {
+            a + b
+        }
+
+        fn no_update(&self) -> (i64, i64) {
+            (1, 0)
+        }
+
+        fn apply(&self, &(a, b): &(i64, i64), v: &i64, len: usize) -> i64 {
+            a * v + b * len as i64
+        }
+
+        fn compose(&self, &(a2, b2): &(i64, i64), &(a1, b1): &(i64, i64)) -> (i64, i64) {
+            (a2 * a1, a2 * b1 + b2)
+        }
+    }
+
+    #[test]
+    fn test_custom_lazy_op() {
+        let mut st = LazySegmentTree::from_slice(&[1, 2, 3, 4], AffineSum);
+        st.update(0..3, (2, 1));
+        st.update(1.., (3, 0));
+        assert_eq!(st.to_vec(), vec![3, 15, 21, 12]);
+        assert_eq!(st.query(..), 51);
+    }
+}
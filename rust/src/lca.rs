@@ -0,0 +1,215 @@
+// rust/src/lca.rs
+use crate::DataStructure;
+use crate::graph::Graph;
+use crate::info::{Info, Operation};
+use crate::sparse_table::SparseTable;
+use std::collections::VecDeque;
+
+/// Lowest common ancestors in a static rooted forest with O(1) queries. The Euler tour lists every
+/// vertex each time the DFS passes through it, so the LCA of `u` and `v` is the shallowest vertex
+/// of the tour between their first visits. The tour is stored as `(depth, vertex)` pairs in a
+/// `SparseTable`, whose `min` then is an argmin by depth that returns the vertex alongside.
+/// - Build: O(n log n)
+/// - lca / distance / is_ancestor: O(1)
+pub struct EulerTourLca {
+    depth: Vec<usize>,
+    first: Vec<usize>,
+    root: Vec<usize>,
+    parent: Vec<Option<usize>>,
+    tour: SparseTable<(usize, usize)>,
+}
+
+impl EulerTourLca {
+    /// Builds from a parent array; roots have no parent. Children are visited in index order.
+    /// Panics if the parent links contain a cycle.
+    pub fn from_parents(parent: &[Option<usize>]) -> Self {
+        let n = parent.len();
+        let mut children = vec![Vec::new(); n];
+        for (v, p) in parent.iter().enumerate() {
+            if let Some(p) = *p {
+                children[p].push(v);
+            }
+        }
+        let mut depth = vec![0; n];
+        let mut first = vec![usize::MAX; n];
+        let mut root = vec![usize::MAX; n];
+        let mut tour = Vec::with_capacity(2 * n);
+        for r in (0..n).filter(|&v| parent[v].is_none()) {
+            first[r] = tour.len();
+            root[r] = r;
+            tour.push((0, r));
+            let mut stack = vec![(r, 0usize)];
+            while let Some(&mut (u, ref mut next)) = stack.last_mut() {
+                if let Some(&v) = children[u].get(*next) {
+                    *next += 1;
+                    depth[v] = depth[u] + 1;
+                    first[v] = tour.len();
+                    root[v] = r;
+                    tour.push((depth[v], v));
+                    stack.push((v, 0));
+                } else {
+                    stack.pop();
+                    if let Some(&(p, _)) = stack.last() {
+                        tour.push((depth[p], p));
+                    }
+                }
+            }
+        }
+        assert!(first.iter().all(|&f| f != usize::MAX), "parent links contain a cycle");
+        EulerTourLca {
+            depth,
+            first,
+            root,
+            parent: parent.to_vec(),
+            tour: SparseTable::from_slice(&tour),
+        }
+    }
+
+    /// Builds from the undirected edges of a forest on `0..n`, rooting each tree at its smallest
+    /// vertex. Panics if the edges contain a cycle.
+    pub fn from_edges(n: usize, edges: &[(usize, usize)]) -> Self {
+        let mut adj = vec![Vec::new(); n];
+        for &(u, v) in edges {
+            adj[u].push(v);
+            adj[v].push(u);
+        }
+        let mut parent = vec![None; n];
+        let mut seen = vec![false; n];
+        let mut reached_edges = 0;
+        for r in 0..n {
+            if seen[r] {
+                continue;
+            }
+            seen[r] = true;
+            let mut queue = VecDeque::from([r]);
+            while let Some(u) = queue.pop_front() {
+                for &v in &adj[u] {
+                    if !seen[v] {
+                        seen[v] = true;
+                        parent[v] = Some(u);
+                        reached_edges += 1;
+                        queue.push_back(v);
+                    }
+                }
+            }
+        }
+        assert_eq!(reached_edges, edges.len(), "edges contain a cycle");
+        Self::from_parents(&parent)
+    }
+
+    /// Builds from an undirected `Graph` that is a forest, as `from_edges`.
+    pub fn from_graph<W>(g: &Graph<W>) -> Self {
+        let edges: Vec<(usize, usize)> = g.edges().map(|(u, v, _)| (u, v)).collect();
+        Self::from_edges(g.num_nodes(), &edges)
+    }
+
+    pub fn len(&self) -> usize {
+        self.depth.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.depth.is_empty()
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        self.parent[v]
+    }
+
+    /// Root of the tree containing `v`.
+    pub fn root(&self, v: usize) -> usize {
+        self.root[v]
+    }
+
+    /// Lowest common ancestor, or None if `u` and `v` are in different trees.
+    pub fn lca(&self, u: usize, v: usize) -> Option<usize> {
+        if self.root[u] != self.root[v] {
+            return None;
+        }
+        let (l, r) = (self.first[u].min(self.first[v]), self.first[u].max(self.first[v]));
+        self.tour.query(l, r).map(|(_, w)| w)
+    }
+
+    /// Number of edges between `u` and `v`, or None if they are in different trees.
+    pub fn distance(&self, u: usize, v: usize) -> Option<usize> {
+        self.lca(u, v).map(|w| self.depth[u] + self.depth[v] - 2 * self.depth[w])
+    }
+
+    /// Whether `a` is `v` or one of its ancestors.
+    pub fn is_ancestor(&self, a: usize, v: usize) -> bool {
+        self.lca(a, v) == Some(a)
+    }
+}
+
+impl DataStructure for EulerTourLca {
+    const INFO: Info = Info {
+        name: "EulerTourLca",
+        category: "trees",
+        summary: "Lowest common ancestors of a static forest via an Euler tour and a sparse table argmin",
+        space: "O(n log n)",
+        operations: &[
+            Operation::new("build", "O(n log n)", "O(n log n)"),
+            Operation::new("lca", "O(1)", "O(1)"),
+            Operation::new("distance", "O(1)", "O(1)"),
+            Operation::new("is_ancestor", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_queries() {
+        //        0          5
+        //      / | \        |
+        //     1  2  3       6
+        //    / \     \
+        //   4   7     8
+        let parent = [None, Some(0), Some(0), Some(0), Some(1), None, Some(5), Some(1), Some(3)];
+        let lca = EulerTourLca::from_parents(&parent);
+        assert_eq!(lca.lca(4, 7), Some(1));
+        assert_eq!(lca.lca(4, 8), Some(0));
+        assert_eq!(lca.lca(2, 2), Some(2));
+        assert_eq!(lca.lca(1, 4), Some(1));
+        assert_eq!(lca.lca(4, 6), None);
+        assert_eq!(lca.distance(7, 8), Some(4));
+        assert!(lca.is_ancestor(0, 8));
+        assert!(!lca.is_ancestor(8, 0));
+        assert_eq!((lca.root(6), lca.depth(8), lca.parent(8)), (5, 2, Some(3)));
+    }
+
+    #[test]
+    fn test_against_naive_climbing() {
+        let mut next = test_rng(17);
+        let n = 200;
+        // random forest: vertex v > 0 hangs below a smaller vertex, or starts a new tree
+        let edges: Vec<(usize, usize)> = (1..n).map(|v| (next() as usize % v, v, next() as usize % 10)).filter(|e| e.2 != 0).map(|(u, v, _)| (u, v)).collect();
+        let g = Graph::from_unweighted_edges(n, &edges, false);
+        let lca = EulerTourLca::from_graph(&g);
+        let naive = |mut u: usize, mut v: usize| {
+            while lca.depth(u) > lca.depth(v) {
+                u = lca.parent(u)?;
+            }
+            while lca.depth(v) > lca.depth(u) {
+                v = lca.parent(v)?;
+            }
+            while u != v {
+                u = lca.parent(u)?;
+                v = lca.parent(v)?;
+            }
+            Some(u)
+        };
+        for _ in 0..2000 {
+            let (u, v) = (next() as usize % n, next() as usize % n);
+            assert_eq!(lca.lca(u, v), naive(u, v));
+        }
+    }
+}
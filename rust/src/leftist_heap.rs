@@ -0,0 +1,241 @@
+// rust/src/leftist_heap.rs
+use crate::DataStructure;
+use crate::heap::{Heap, MergeableHeap};
+use crate::info::{Info, Operation};
+use std::rc::Rc;
+
+type Link<T> = Option<Rc<Node<T>>>;
+
+struct Node<T> {
+    value: T,
+    // length of the right spine; a left child's rank is never smaller than the right child's
+    rank: usize,
+    left: Link<T>,
+    right: Link<T>,
+}
+
+fn rank<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |n| n.rank)
+}
+
+/// Merges two heaps along their right spines, copying only the O(log n) spine nodes it rewrites;
+/// every other subtree is shared with the inputs.
+fn merge<T: Ord + Clone>(a: &Link<T>, b: &Link<T>) -> Link<T> {
+    let (a, b) = match (a, b) {
+        (None, x) | (x, None) => return x.clone(),
+        (Some(a), Some(b)) => if b.value < a.value { (b, a) } else { (a, b) },
+    };
+    let merged = merge(&a.right, &Some(b.clone()));
+    let (left, right) = if rank(&a.left) >= rank(&merged) {
+        (a.left.clone(), merged)
+    } else {
+        (merged, a.left.clone())
+    };
+    Some(Rc::new(Node {
+        value: a.value.clone(),
+        rank: rank(&right) + 1,
+        left,
+        right,
+    }))
+}
+
+/// Persistent leftist min-heap. Nodes are immutable and reference counted, so `clone` is an O(1)
+/// snapshot and `meld` leaves both inputs intact: every update copies only the short right spine it
+/// walks and shares the rest. The mergeable heap to reach for when old versions must stay usable
+/// (backtracking search, k-shortest-path style branching); `PairingHeap` is faster when they need not.
+/// - push / pop / meld: O(log n), allocating O(log n) new nodes
+/// - peek / clone: O(1)
+pub struct LeftistHeap<T> {
+    root: Link<T>,
+    len: usize,
+}
+
+impl<T> Clone for LeftistHeap<T> {
+    fn clone(&self) -> Self {
+        LeftistHeap {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Drop for LeftistHeap<T> {
+    // the left spine can be O(n) long (e.g. after descending pushes), so unlink nodes iteratively
+    // rather than letting the recursive drop overflow the stack; shared nodes are left to their owners
+    fn drop(&mut self) {
+        let mut stack: Vec<Rc<Node<T>>> = self.root.take().into_iter().collect();
+        while let Some(rc) = stack.pop() {
+            if let Ok(mut node) = Rc::try_unwrap(rc) {
+                stack.extend(node.left.take());
+                stack.extend(node.right.take());
+            }
+        }
+    }
+}
+
+impl<T: Ord + Clone> Default for LeftistHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> LeftistHeap<T> {
+    pub fn new() -> Self {
+        LeftistHeap { root: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.as_ref().map(|n| &n.value)
+    }
+
+    pub fn push(&mut self, value: T) {
+        let single = Some(Rc::new(Node { value, rank: 1, left: None, right: None }));
+        self.root = merge(&self.root, &single);
+        self.len += 1;
+    }
+
+    /// Removes and returns the smallest item. The value is cloned, since snapshots may still share it.
+    pub fn pop(&mut self) -> Option<T> {
+        let root = self.root.take()?;
+        self.root = merge(&root.left, &root.right);
+        self.len -= 1;
+        Some(root.value.clone())
+    }
+
+    /// Adds every item of `other` to `self`; `other` is left unchanged and shares nodes with `self`.
+    pub fn meld(&mut self, other: &LeftistHeap<T>) {
+        self.root = merge(&self.root, &other.root);
+        self.len += other.len;
+    }
+
+    /// A new heap holding the items of both, leaving the inputs untouched.
+    pub fn melded(&self, other: &LeftistHeap<T>) -> LeftistHeap<T> {
+        let mut heap = self.clone();
+        heap.meld(other);
+        heap
+    }
+
+    /// Items in ascending order, without modifying the heap.
+    pub fn to_sorted_vec(&self) -> Vec<T> {
+        let mut copy = self.clone();
+        std::iter::from_fn(|| copy.pop()).collect()
+    }
+}
+
+impl<T: Ord + Clone> FromIterator<T> for LeftistHeap<T> {
+    /// Builds the heap by melding singletons pairwise in rounds, O(n) instead of O(n log n) pushes.
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut level: Vec<Link<T>> = iter
+            .into_iter()
+            .map(|value| Some(Rc::new(Node { value, rank: 1, left: None, right: None })))
+            .collect();
+        let len = level.len();
+        while level.len() > 1 {
+            level = level.chunks(2).map(|pair| merge(&pair[0], pair.get(1).unwrap_or(&None))).collect();
+        }
+        LeftistHeap { root: level.pop().flatten(), len }
+    }
+}
+
+impl<T: Ord + Clone> Heap for LeftistHeap<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+impl<T: Ord + Clone> MergeableHeap for LeftistHeap<T> {
+    fn meld(&mut self, other: Self) {
+        self.meld(&other);
+    }
+}
+
+impl<T> DataStructure for LeftistHeap<T> {
+    const INFO: Info = Info {
+        name: "LeftistHeap",
+        category: "heaps",
+        summary: "Persistent leftist min-heap: O(log n) meld, O(1) snapshots through shared nodes",
+        space: "O(n), plus O(log n) new nodes per update",
+        operations: &[
+            Operation::new("push", "O(log n)", "O(log n)"),
+            Operation::new("pop", "O(log n)", "O(log n)"),
+            Operation::new("meld", "O(log n)", "O(log n)"),
+            Operation::new("peek", "O(1)", "O(1)"),
+            Operation::new("clone", "O(1)", "O(1)"),
+            Operation::new("from_iter", "O(n)", "O(n)"),
+        ],
+        stable: Some(false),
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_pop_and_build() {
+        let values = [9, 4, 7, 1, 8, 2, 2, 6, 0, 5];
+        let mut expected = values.to_vec();
+        expected.sort();
+        let built: LeftistHeap<i32> = values.into_iter().collect();
+        assert_eq!(built.len(), 10);
+        assert_eq!(built.to_sorted_vec(), expected);
+
+        let mut pushed = LeftistHeap::new();
+        values.into_iter().for_each(|v| pushed.push(v));
+        assert_eq!(pushed.peek(), Some(&0));
+        let popped: Vec<i32> = std::iter::from_fn(|| pushed.pop()).collect();
+        assert_eq!(popped, expected);
+        assert!(pushed.is_empty());
+    }
+
+    #[test]
+    fn test_persistence() {
+        let mut heap: LeftistHeap<i32> = (0..20).rev().collect();
+        let snapshot = heap.clone();
+        assert_eq!(heap.pop(), Some(0));
+        heap.push(-5);
+        assert_eq!(snapshot.peek(), Some(&0));
+        assert_eq!(snapshot.len(), 20);
+        assert_eq!(heap.peek(), Some(&-5));
+
+        let odds: LeftistHeap<i32> = (100..110).filter(|v| v % 2 == 1).collect();
+        let both = snapshot.melded(&odds);
+        assert_eq!(both.len(), 25);
+        assert_eq!(odds.to_sorted_vec(), vec![101, 103, 105, 107, 109]);
+        assert_eq!(snapshot.to_sorted_vec(), (0..20).collect::<Vec<_>>());
+        heap.meld(&odds);
+        assert_eq!(heap.to_sorted_vec().last(), Some(&109));
+        assert_eq!(heap.len(), 25);
+    }
+
+    #[test]
+    fn test_long_left_spine_drops() {
+        // descending pushes make every new root the parent of a one-sided chain
+        let mut heap = LeftistHeap::new();
+        (0..200_000).rev().for_each(|v| heap.push(v));
+        assert_eq!(heap.peek(), Some(&0));
+    }
+}
@@ -0,0 +1,262 @@
+// rust/src/li_chao.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::ops::Range;
+
+const NONE: usize = 0;
+
+/// A line `y = slope * x + intercept`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line {
+    pub slope: i64,
+    pub intercept: i64,
+}
+
+impl Line {
+    pub fn new(slope: i64, intercept: i64) -> Self {
+        Line { slope, intercept }
+    }
+
+    pub fn eval(&self, x: i64) -> i64 {
+        self.slope * x + self.intercept
+    }
+}
+
+struct Node {
+    line: Option<Line>,
+    // NONE (the root's index) marks a missing child
+    children: [usize; 2],
+}
+
+/// Li Chao tree: a set of lines (or line segments) over integer x in `bounds`, answering "lowest
+/// (or highest) line at x" queries. The staple container for convex-hull-trick DP optimizations,
+/// without the monotonic slopes or queries the deque version needs.
+/// Each node over an x interval keeps the line that is best at its midpoint; the line that loses can
+/// only win on one side, so it moves down into that half only. Nodes are created on demand, so the
+/// bounds may be as wide as `i64` (values `slope * x + intercept` must fit in an i64).
+/// - add_line / query: O(log U) for a range of U x values
+/// - add_segment: O(log^2 U)
+pub struct LiChaoTree {
+    bounds: Range<i64>,
+    nodes: Vec<Node>,
+    maximize: bool,
+    lines: usize,
+}
+
+fn midpoint(lo: i64, hi: i64) -> i64 {
+    // floor of the midpoint of the closed interval [lo, hi], computed without overflow
+    ((lo as i128 + hi as i128).div_euclid(2)) as i64
+}
+
+impl LiChaoTree {
+    /// Container answering minimum queries. Panics if `bounds` is empty.
+    pub fn new_min(bounds: Range<i64>) -> Self {
+        Self::with_mode(bounds, false)
+    }
+
+    /// Container answering maximum queries. Panics if `bounds` is empty.
+    pub fn new_max(bounds: Range<i64>) -> Self {
+        Self::with_mode(bounds, true)
+    }
+
+    fn with_mode(bounds: Range<i64>, maximize: bool) -> Self {
+        assert!(bounds.start < bounds.end, "empty x range {:?}", bounds);
+        let root = Node {
+            line: None,
+            children: [NONE; 2],
+        };
+        LiChaoTree {
+            bounds,
+            nodes: vec![root],
+            maximize,
+            lines: 0,
+        }
+    }
+
+    pub fn bounds(&self) -> Range<i64> {
+        self.bounds.clone()
+    }
+
+    /// Number of lines and segments added.
+    pub fn len(&self) -> usize {
+        self.lines
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines == 0
+    }
+
+    // maximum queries are minimum queries over the negated lines
+    fn stored(&self, line: Line) -> Line {
+        if self.maximize { Line::new(-line.slope, -line.intercept) } else { line }
+    }
+
+    fn child(&mut self, node: usize, side: usize) -> usize {
+        if self.nodes[node].children[side] == NONE {
+            self.nodes.push(Node {
+                line: None,
+                children: [NONE; 2],
+            });
+            self.nodes[node].children[side] = self.nodes.len() - 1;
+        }
+        self.nodes[node].children[side]
+    }
+
+    /// Adds the line `y = slope * x + intercept` over the whole x range.
+    pub fn add_line(&mut self, slope: i64, intercept: i64) {
+        let line = self.stored(Line::new(slope, intercept));
+        self.lines += 1;
+        self.insert(0, self.bounds.start, self.bounds.end - 1, line);
+    }
+
+    /// Adds the line only for x in `xs` (half-open); the part outside the bounds is ignored.
+    pub fn add_segment(&mut self, slope: i64, intercept: i64, xs: Range<i64>) {
+        let line = self.stored(Line::new(slope, intercept));
+        let (l, r) = (xs.start.max(self.bounds.start), xs.end.min(self.bounds.end));
+        self.lines += 1;
+        if l < r {
+            self.insert_segment(0, self.bounds.start, self.bounds.end - 1, l, r - 1, line);
+        }
+    }
+
+    /// Inserts `line` at `node`, which covers the closed interval [lo, hi].
+    fn insert(&mut self, mut node: usize, mut lo: i64, mut hi: i64, mut line: Line) {
+        loop {
+            let Some(current) = self.nodes[node].line else {
+                self.nodes[node].line = Some(line);
+                return;
+            };
+            let mid = midpoint(lo, hi);
+            let better_left = line.eval(lo) < current.eval(lo);
+            let better_mid = line.eval(mid) < current.eval(mid);
+            if better_mid {
+                self.nodes[node].line = Some(line);
+                line = current;
+            }
+            if lo == hi {
+                return;
+            }
+            // two lines cross at most once: the loser can only win on the side where they cross
+            if better_left != better_mid {
+                node = self.child(node, 0);
+                hi = mid;
+            } else {
+                node = self.child(node, 1);
+                lo = mid + 1;
+            }
+        }
+    }
+
+    fn insert_segment(&mut self, node: usize, lo: i64, hi: i64, l: i64, r: i64, line: Line) {
+        if l <= lo && hi <= r {
+            self.insert(node, lo, hi, line);
+            return;
+        }
+        let mid = midpoint(lo, hi);
+        if l <= mid {
+            let left = self.child(node, 0);
+            self.insert_segment(left, lo, mid, l, r, line);
+        }
+        if r > mid {
+            let right = self.child(node, 1);
+            self.insert_segment(right, mid + 1, hi, l, r, line);
+        }
+    }
+
+    /// Lowest (or, for `new_max`, highest) value at `x` over the lines covering `x`; None if none does.
+    /// Panics if `x` is outside the bounds.
+    pub fn query(&self, x: i64) -> Option<i64> {
+        assert!(self.bounds.contains(&x), "x = {} out of bounds {:?}", x, self.bounds);
+        let (mut node, mut lo, mut hi) = (0, self.bounds.start, self.bounds.end - 1);
+        let mut best: Option<i64> = None;
+        loop {
+            if let Some(line) = self.nodes[node].line {
+                let y = line.eval(x);
+                best = Some(best.map_or(y, |b| b.min(y)));
+            }
+            if lo == hi {
+                break;
+            }
+            let mid = midpoint(lo, hi);
+            let side = usize::from(x > mid);
+            if side == 0 { hi = mid } else { lo = mid + 1 }
+            node = self.nodes[node].children[side];
+            if node == NONE {
+                break;
+            }
+        }
+        if self.maximize { best.map(|y| -y) } else { best }
+    }
+}
+
+impl DataStructure for LiChaoTree {
+    const INFO: Info = Info {
+        name: "LiChaoTree",
+        category: "geometry",
+        summary: "Li Chao tree: insert lines or segments, query the lowest/highest one at x (U = x range)",
+        space: "O(k log U) for k lines",
+        operations: &[
+            Operation::new("add_line", "O(log U)", "O(log U)"),
+            Operation::new("add_segment", "O(log^2 U)", "O(log^2 U)"),
+            Operation::new("query", "O(log U)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_lines_match_brute_force() {
+        let mut next = test_rng(4);
+        let mut min = LiChaoTree::new_min(-50..50);
+        let mut max = LiChaoTree::new_max(-50..50);
+        let mut lines = vec![];
+        assert_eq!(min.query(0), None);
+        for _ in 0..60 {
+            let line = Line::new((next() % 21) as i64 - 10, (next() % 201) as i64 - 100);
+            min.add_line(line.slope, line.intercept);
+            max.add_line(line.slope, line.intercept);
+            lines.push(line);
+            for x in -50..50 {
+                assert_eq!(min.query(x), lines.iter().map(|l| l.eval(x)).min());
+                assert_eq!(max.query(x), lines.iter().map(|l| l.eval(x)).max());
+            }
+        }
+        assert_eq!(min.len(), 60);
+    }
+
+    #[test]
+    fn test_segments_match_brute_force() {
+        let mut next = test_rng(10);
+        let mut tree = LiChaoTree::new_min(0..64);
+        let mut segments = vec![];
+        for _ in 0..80 {
+            let line = Line::new((next() % 11) as i64 - 5, (next() % 101) as i64 - 50);
+            let (a, b) = ((next() % 70) as i64 - 3, (next() % 70) as i64 - 3);
+            let xs = a.min(b)..a.max(b);
+            tree.add_segment(line.slope, line.intercept, xs.clone());
+            segments.push((line, xs));
+            for x in 0..64 {
+                let expected = segments.iter().filter(|(_, xs)| xs.contains(&x)).map(|(l, _)| l.eval(x)).min();
+                assert_eq!(tree.query(x), expected, "x = {}", x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_wide_bounds() {
+        let mut tree = LiChaoTree::new_max(-1_000_000_000..1_000_000_000);
+        tree.add_line(3, 0);
+        tree.add_line(-2, 5);
+        tree.add_line(0, 100);
+        assert_eq!(tree.query(999_999_999), Some(2_999_999_997));
+        assert_eq!(tree.query(-1_000_000_000), Some(2_000_000_005));
+        assert_eq!(tree.query(10), Some(100));
+        assert!(tree.nodes.len() < 100);
+    }
+}
@@ -2,9 +2,100 @@
 
 pub mod union_find;
 pub mod fenwick_tree;
+pub mod fixed;
+pub mod range;
 pub mod treap;
+pub mod skip_list;
+pub mod splay_tree;
+pub mod avl_tree;
+pub mod mergeable_set;
 pub mod sparse_table;
+pub mod fischer_heun;
+pub mod wavelet_tree;
+pub mod lca;
+pub mod link_cut_tree;
+pub mod range_query;
+pub mod monoid;
+pub mod modint;
+pub mod math;
+pub mod segment_tree;
+pub mod lazy_segment_tree;
+pub mod sqrt_decomposition;
+pub mod mo;
+pub mod monotonic_stack;
+pub mod interval_tree;
+pub mod interval_set;
+pub mod dynamic_segment_tree;
+pub mod segment_tree_beats;
+pub mod segment_tree_2d;
+pub mod range_tree;
+pub mod li_chao;
+pub mod heap;
+pub mod indexed_heap;
+pub mod dary_heap;
+pub mod fibonacci_heap;
+pub mod interval_heap;
+pub mod leftist_heap;
+pub mod pairing_heap;
+pub mod byte_map;
 pub mod kmp;
+pub mod boyer_moore;
+pub mod bitap;
+pub mod strings;
+pub mod rolling_hash;
+pub mod spatial_hash;
+pub mod kd_tree;
+pub mod quadtree;
+pub mod suffix_array;
+pub mod fm_index;
+pub mod suffix_automaton;
+pub mod rope;
+pub mod persistent_array;
+pub mod finger_tree;
+pub mod rectangle_union;
+pub mod skyline;
+pub mod slab;
+pub mod circular_list;
+pub mod josephus;
+pub mod slab_list;
+pub mod cache;
+pub mod csr_graph;
+pub mod graph;
+pub mod bitset;
+pub mod sparse_set;
+pub mod bit_vector;
+pub mod van_emde_boas;
+pub mod bloom_filter;
+pub mod cuckoo_filter;
+pub mod count_min;
+pub mod hyperloglog;
+pub mod streaming;
+pub mod dlx;
+pub mod dense_graph;
+pub mod reachability;
+pub mod cycles;
+pub mod mst;
+pub mod assignment;
+pub mod trie;
+pub mod radix_trie;
+pub mod xor_trie;
+pub mod static_dict;
+pub mod info;
+pub mod dot;
+pub mod rng;
+pub mod hash;
+pub mod snapshot;
+/// Common interface of the crate's data structures: static metadata describing what each one is for
+/// and what its operations cost. `info::catalog()` lists every implementor together with the
+/// standalone algorithms.
 pub trait DataStructure {
-    fn name(&self) -> &'static str;
+    const INFO: info::Info;
+
+    fn name(&self) -> &'static str {
+        Self::INFO.name
+    }
+
+    fn info(&self) -> &'static info::Info {
+        &Self::INFO
+    }
 }
\ No newline at end of file
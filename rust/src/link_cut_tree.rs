@@ -0,0 +1,360 @@
+// rust/src/link_cut_tree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::monoid::Monoid;
+
+const NIL: usize = usize::MAX;
+
+struct Node<T> {
+    // children in the splay tree of the node's preferred path, ordered by depth
+    ch: [usize; 2],
+    // splay parent, or for the root of a splay tree the path-parent pointer
+    parent: usize,
+    // the subtree's children still have to be swapped (lazy reversal)
+    flipped: bool,
+    value: T,
+    // aggregate of the subtree in path order, and in reverse order
+    sum: T,
+    rev_sum: T,
+}
+
+/// Link-cut tree (Sleator-Tarjan) over vertices `0..n` carrying values of a monoid: a forest that
+/// supports adding and removing edges while answering connectivity and path aggregate queries.
+/// The dynamic counterpart of `EulerTourLca`. Each preferred path is a splay tree keyed by depth;
+/// rerooting reverses a path lazily, and the aggregate is kept in both directions so that
+/// non-commutative operations come out in path order.
+/// - link / cut / connected / path / lca / set: O(log n) amortized
+pub struct LinkCutTree<T, Op> {
+    nodes: Vec<Node<T>>,
+    op: Op,
+}
+
+impl<T: Clone, Op: Monoid<T>> LinkCutTree<T, Op> {
+    /// `len` isolated vertices holding the identity.
+    pub fn new(len: usize, op: Op) -> Self {
+        Self::from_slice(&vec![op.identity(); len], op)
+    }
+
+    /// Isolated vertices with the given values.
+    pub fn from_slice(values: &[T], op: Op) -> Self {
+        let nodes = values
+            .iter()
+            .map(|v| Node {
+                ch: [NIL; 2],
+                parent: NIL,
+                flipped: false,
+                value: v.clone(),
+                sum: v.clone(),
+                rev_sum: v.clone(),
+            })
+            .collect();
+        LinkCutTree { nodes, op }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn op(&self) -> &Op {
+        &self.op
+    }
+
+    /// Whether `x` is the root of its splay tree (its parent pointer, if any, is a path-parent).
+    fn is_splay_root(&self, x: usize) -> bool {
+        let p = self.nodes[x].parent;
+        p == NIL || !self.nodes[p].ch.contains(&x)
+    }
+
+    fn flip(&mut self, x: usize) {
+        if x == NIL {
+            return;
+        }
+        let node = &mut self.nodes[x];
+        node.ch.swap(0, 1);
+        std::mem::swap(&mut node.sum, &mut node.rev_sum);
+        node.flipped ^= true;
+    }
+
+    fn push(&mut self, x: usize) {
+        if self.nodes[x].flipped {
+            let [l, r] = self.nodes[x].ch;
+            self.flip(l);
+            self.flip(r);
+            self.nodes[x].flipped = false;
+        }
+    }
+
+    fn update(&mut self, x: usize) {
+        let [l, r] = self.nodes[x].ch;
+        let (mut sum, mut rev_sum) = (self.nodes[x].value.clone(), self.nodes[x].value.clone());
+        if l != NIL {
+            sum = self.op.combine(&self.nodes[l].sum, &sum);
+            rev_sum = self.op.combine(&rev_sum, &self.nodes[l].rev_sum);
+        }
+        if r != NIL {
+            sum = self.op.combine(&sum, &self.nodes[r].sum);
+            rev_sum = self.op.combine(&self.nodes[r].rev_sum, &rev_sum);
+        }
+        self.nodes[x].sum = sum;
+        self.nodes[x].rev_sum = rev_sum;
+    }
+
+    fn rotate(&mut self, x: usize) {
+        let y = self.nodes[x].parent;
+        let z = self.nodes[y].parent;
+        let dir = usize::from(self.nodes[y].ch[1] == x);
+        if !self.is_splay_root(y) {
+            let zd = usize::from(self.nodes[z].ch[1] == y);
+            self.nodes[z].ch[zd] = x;
+        }
+        self.nodes[x].parent = z;
+        let b = self.nodes[x].ch[dir ^ 1];
+        self.nodes[y].ch[dir] = b;
+        if b != NIL {
+            self.nodes[b].parent = y;
+        }
+        self.nodes[x].ch[dir ^ 1] = y;
+        self.nodes[y].parent = x;
+        self.update(y);
+        self.update(x);
+    }
+
+    fn splay(&mut self, x: usize) {
+        // pending reversals on the way down from the splay root must be applied first
+        let mut path = vec![x];
+        while !self.is_splay_root(*path.last().expect("path is non-empty")) {
+            path.push(self.nodes[*path.last().expect("path is non-empty")].parent);
+        }
+        path.iter().rev().for_each(|&y| self.push(y));
+        while !self.is_splay_root(x) {
+            let y = self.nodes[x].parent;
+            if !self.is_splay_root(y) {
+                let z = self.nodes[y].parent;
+                let zig_zig = (self.nodes[y].ch[0] == x) == (self.nodes[z].ch[0] == y);
+                self.rotate(if zig_zig { y } else { x });
+            }
+            self.rotate(x);
+        }
+    }
+
+    /// Makes the root-to-`x` path preferred and splays `x` to the top of it. Returns the last
+    /// vertex where the walk joined a new path, which is the LCA of `x` and the previously
+    /// accessed vertex.
+    fn access(&mut self, x: usize) -> usize {
+        let mut last = NIL;
+        let mut y = x;
+        while y != NIL {
+            self.splay(y);
+            self.nodes[y].ch[1] = last;
+            self.update(y);
+            last = y;
+            y = self.nodes[y].parent;
+        }
+        self.splay(x);
+        last
+    }
+
+    /// Makes `x` the root of its tree.
+    fn make_root(&mut self, x: usize) {
+        self.access(x);
+        self.flip(x);
+    }
+
+    /// Root of the tree containing `x` (the last vertex rerooted, or an original root).
+    pub fn find_root(&mut self, x: usize) -> usize {
+        self.access(x);
+        let mut r = x;
+        loop {
+            self.push(r);
+            match self.nodes[r].ch[0] {
+                NIL => break,
+                l => r = l,
+            }
+        }
+        self.splay(r);
+        r
+    }
+
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        u == v || self.find_root(u) == self.find_root(v)
+    }
+
+    /// Adds the edge `u - v`. Returns false (changing nothing) if they are already connected.
+    pub fn link(&mut self, u: usize, v: usize) -> bool {
+        if self.connected(u, v) {
+            return false;
+        }
+        self.make_root(u);
+        self.nodes[u].parent = v;
+        true
+    }
+
+    /// Removes the edge `u - v`. Returns false (changing nothing) if there is no such edge.
+    pub fn cut(&mut self, u: usize, v: usize) -> bool {
+        if u == v || !self.connected(u, v) {
+            return false;
+        }
+        self.make_root(u);
+        self.access(v);
+        // the path u..v is exactly the edge iff u is v's only predecessor on it
+        if self.nodes[v].ch[0] != u || self.nodes[u].ch[1] != NIL {
+            return false;
+        }
+        self.nodes[v].ch[0] = NIL;
+        self.nodes[u].parent = NIL;
+        self.update(v);
+        true
+    }
+
+    /// Aggregate of the values on the path from `u` to `v`, combined in that order, or None if they
+    /// are in different trees.
+    pub fn path(&mut self, u: usize, v: usize) -> Option<T> {
+        if !self.connected(u, v) {
+            return None;
+        }
+        self.make_root(u);
+        self.access(v);
+        Some(self.nodes[v].sum.clone())
+    }
+
+    /// Lowest common ancestor of `u` and `v` when their tree is rooted at `root`, or None if the
+    /// three are not all connected. Reroots the tree at `root`.
+    pub fn lca(&mut self, root: usize, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(root, u) || !self.connected(root, v) {
+            return None;
+        }
+        self.make_root(root);
+        self.access(u);
+        Some(self.access(v))
+    }
+
+    pub fn get(&self, x: usize) -> &T {
+        &self.nodes[x].value
+    }
+
+    pub fn set(&mut self, x: usize, value: T) {
+        // with x at the top of its splay tree only its own aggregates change
+        self.access(x);
+        self.nodes[x].value = value;
+        self.update(x);
+    }
+}
+
+impl<T, Op> DataStructure for LinkCutTree<T, Op> {
+    const INFO: Info = Info {
+        name: "LinkCutTree",
+        category: "trees",
+        summary: "Dynamic forest with link, cut, connectivity, LCA and monoid path aggregates",
+        space: "O(n)",
+        operations: &[
+            Operation::new("link", "O(log n) amortized", "O(1)"),
+            Operation::new("cut", "O(log n) amortized", "O(1)"),
+            Operation::new("connected", "O(log n) amortized", "O(1)"),
+            Operation::new("path", "O(log n) amortized", "O(1)"),
+            Operation::new("lca", "O(log n) amortized", "O(1)"),
+            Operation::new("set", "O(log n) amortized", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::monoid::{FnMonoid, Sum};
+    use std::collections::VecDeque;
+
+    #[test]
+    fn test_link_cut_and_sums() {
+        let mut lct = LinkCutTree::from_slice(&[1i64, 2, 3, 4, 5], Sum);
+        assert!(lct.link(0, 1));
+        assert!(lct.link(1, 2));
+        assert!(lct.link(3, 1));
+        assert!(!lct.link(0, 3));
+        assert!(!lct.connected(0, 4));
+        assert_eq!(lct.path(0, 3), Some(1 + 2 + 4));
+        assert_eq!(lct.path(2, 2), Some(3));
+        assert_eq!(lct.path(0, 4), None);
+        assert_eq!(lct.lca(0, 2, 3), Some(1));
+        assert_eq!(lct.lca(2, 2, 0), Some(2));
+        assert!(!lct.cut(0, 2));
+        assert!(lct.cut(1, 2));
+        assert!(!lct.connected(0, 2));
+        lct.set(1, 20);
+        assert_eq!(lct.path(3, 0), Some(4 + 20 + 1));
+        assert_eq!(*lct.get(1), 20);
+        assert!(lct.link(2, 4));
+        assert_eq!(lct.path(4, 2), Some(8));
+    }
+
+    /// Vertices on the path from u to v in an undirected forest, by BFS.
+    fn naive_path(adj: &[Vec<usize>], u: usize, v: usize) -> Option<Vec<usize>> {
+        let mut prev = vec![usize::MAX; adj.len()];
+        prev[u] = u;
+        let mut queue = VecDeque::from([u]);
+        while let Some(x) = queue.pop_front() {
+            for &y in &adj[x] {
+                if prev[y] == usize::MAX {
+                    prev[y] = x;
+                    queue.push_back(y);
+                }
+            }
+        }
+        if prev[v] == usize::MAX {
+            return None;
+        }
+        let mut path = vec![v];
+        while *path.last().unwrap() != u {
+            path.push(prev[*path.last().unwrap()]);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    #[test]
+    fn test_ordered_paths_against_naive() {
+        let mut next = test_rng(31);
+        let n = 30;
+        // string concatenation is not commutative, so it checks that paths come out in order
+        let concat = FnMonoid::new(String::new(), |a: &String, b: &String| format!("{}{}", a, b));
+        let names: Vec<String> = (0..n).map(|i| ((b'a' + (i % 26) as u8) as char).to_string()).collect();
+        let mut lct = LinkCutTree::from_slice(&names, concat);
+        let mut values = names.clone();
+        let mut adj: Vec<Vec<usize>> = vec![Vec::new(); n];
+        for _ in 0..3000 {
+            let (u, v) = (next() as usize % n, next() as usize % n);
+            match next() % 4 {
+                0 => {
+                    let linked = naive_path(&adj, u, v).is_none();
+                    assert_eq!(lct.link(u, v), linked);
+                    if linked {
+                        adj[u].push(v);
+                        adj[v].push(u);
+                    }
+                }
+                1 => {
+                    let is_edge = adj[u].contains(&v);
+                    assert_eq!(lct.cut(u, v), is_edge);
+                    if is_edge {
+                        adj[u].retain(|&x| x != v);
+                        adj[v].retain(|&x| x != u);
+                    }
+                }
+                2 => {
+                    values[u] = format!("{}", next() as usize % 10);
+                    lct.set(u, values[u].clone());
+                }
+                _ => {
+                    let expected = naive_path(&adj, u, v).map(|p| p.iter().map(|&x| values[x].clone()).collect::<String>());
+                    assert_eq!(lct.path(u, v), expected);
+                }
+            }
+        }
+    }
+}
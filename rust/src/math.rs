@@ -0,0 +1,262 @@
+// rust/src/math.rs
+use std::ops::Range;
+
+/// Linear sieve (Euler's sieve) over `0..=n`: every composite is crossed off exactly once, by its
+/// smallest prime factor, so building takes O(n) rather than the O(n log log n) of Eratosthenes,
+/// and the smallest prime factor table it leaves behind factorizes any `x <= n` in O(log x) by
+/// repeated division.
+/// - Build: O(n)
+/// - is_prime / smallest_prime_factor: O(1)
+/// - factorize: O(log x)
+pub struct LinearSieve {
+    // smallest prime factor of every value, 0 for 0 and 1
+    spf: Vec<u32>,
+    primes: Vec<u32>,
+}
+
+impl LinearSieve {
+    /// Sieves `0..=n`. Panics if `n` does not fit in a u32.
+    pub fn new(n: usize) -> Self {
+        assert!(n <= u32::MAX as usize, "sieve limit {} does not fit in u32", n);
+        let mut spf = vec![0u32; n + 1];
+        let mut primes = Vec::new();
+        for i in 2..=n {
+            if spf[i] == 0 {
+                spf[i] = i as u32;
+                primes.push(i as u32);
+            }
+            // i * p has smallest prime factor p for every prime p up to spf(i)
+            for &p in &primes {
+                let m = i * p as usize;
+                if p > spf[i] || m > n {
+                    break;
+                }
+                spf[m] = p;
+            }
+        }
+        LinearSieve { spf, primes }
+    }
+
+    /// The largest value covered.
+    pub fn limit(&self) -> usize {
+        self.spf.len() - 1
+    }
+
+    /// The primes up to `limit()`, increasing.
+    pub fn primes(&self) -> &[u32] {
+        &self.primes
+    }
+
+    /// Panics if `x > limit()`.
+    pub fn is_prime(&self, x: usize) -> bool {
+        x >= 2 && self.spf[x] as usize == x
+    }
+
+    /// None for 0 and 1. Panics if `x > limit()`.
+    pub fn smallest_prime_factor(&self, x: usize) -> Option<u32> {
+        (x >= 2).then(|| self.spf[x])
+    }
+
+    /// `(prime, exponent)` pairs of `x` by increasing prime; empty for 0 and 1. Panics if
+    /// `x > limit()`.
+    pub fn factorize(&self, mut x: usize) -> Vec<(u64, u32)> {
+        let mut factors: Vec<(u64, u32)> = Vec::new();
+        while x >= 2 {
+            let p = self.spf[x];
+            match factors.last_mut() {
+                Some(last) if last.0 == p as u64 => last.1 += 1,
+                _ => factors.push((p as u64, 1)),
+            }
+            x /= p as usize;
+        }
+        factors
+    }
+}
+
+/// Primes in `range`, increasing, by a segmented sieve of Eratosthenes: the primes up to
+/// sqrt(end) are sieved once, then they cross off their multiples in one fixed-size window of the
+/// range at a time, so memory stays O(sqrt(end) + window) however wide the range is. Ranges ending
+/// around 10^12 need only the base primes below 10^6; `range.end` may be at most
+/// `SEGMENTED_SIEVE_MAX_END` = 2^40, which keeps the base sieve to 2^20 values.
+/// - next: O(1) amortized over a window; O((end - start) log log end + sqrt(end)) in total
+pub struct SegmentedSieve {
+    base: Vec<u64>,
+    range: Range<u64>,
+    // the current window starts at `window_start`; `composite[i]` marks `window_start + i`
+    window_start: u64,
+    composite: Vec<bool>,
+    pos: usize,
+}
+
+const WINDOW: u64 = 1 << 16;
+
+/// The largest `range.end` a `SegmentedSieve` accepts, a little above 10^12.
+pub const SEGMENTED_SIEVE_MAX_END: u64 = 1 << 40;
+
+impl SegmentedSieve {
+    /// Panics if `range.end > SEGMENTED_SIEVE_MAX_END`.
+    pub fn new(range: Range<u64>) -> Self {
+        assert!(
+            range.end <= SEGMENTED_SIEVE_MAX_END,
+            "range end {} above the segmented sieve limit {}",
+            range.end,
+            SEGMENTED_SIEVE_MAX_END
+        );
+        let root = range.end.saturating_sub(1).isqrt();
+        let base = LinearSieve::new(root as usize).primes().iter().map(|&p| p as u64).collect();
+        let mut sieve = SegmentedSieve { base, window_start: range.start, range, composite: Vec::new(), pos: 0 };
+        sieve.fill();
+        sieve
+    }
+
+    /// Sieves the window starting at `window_start`.
+    fn fill(&mut self) {
+        let (lo, hi) = (self.window_start, self.range.end.min(self.window_start.saturating_add(WINDOW)));
+        self.composite.clear();
+        self.composite.resize(hi.saturating_sub(lo) as usize, false);
+        self.pos = 0;
+        for &p in &self.base {
+            let square = match p.checked_mul(p) {
+                Some(square) if square < hi => square,
+                _ => break,
+            };
+            // the first multiple of p in the window, but never p itself; none if it overflows
+            let Some(first) = lo.div_ceil(p).checked_mul(p) else { continue };
+            let mut m = square.max(first);
+            while m < hi {
+                self.composite[(m - lo) as usize] = true;
+                match m.checked_add(p) {
+                    Some(next) => m = next,
+                    None => break,
+                }
+            }
+        }
+        for x in lo..hi.min(2) {
+            self.composite[(x - lo) as usize] = true;
+        }
+    }
+}
+
+impl Iterator for SegmentedSieve {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        loop {
+            while self.pos < self.composite.len() {
+                self.pos += 1;
+                if !self.composite[self.pos - 1] {
+                    return Some(self.window_start + self.pos as u64 - 1);
+                }
+            }
+            self.window_start += self.composite.len() as u64;
+            if self.window_start >= self.range.end {
+                return None;
+            }
+            self.fill();
+        }
+    }
+}
+
+/// The primes in `range`, increasing; see `SegmentedSieve`. Panics if
+/// `range.end > SEGMENTED_SIEVE_MAX_END`.
+pub fn primes_in_range(range: Range<u64>) -> Vec<u64> {
+    SegmentedSieve::new(range).collect()
+}
+
+/// `(prime, exponent)` pairs of `n` by increasing prime, by trial division with 2, 3 and then the
+/// numbers 6k ± 1 up to sqrt(n); empty for 0 and 1. Fast up to about 10^12 (a million candidate
+/// divisors at worst); use `LinearSieve::factorize` for many small values.
+///
+/// Complexity: O(sqrt(n))
+pub fn factorize(mut n: u64) -> Vec<(u64, u32)> {
+    let mut factors = Vec::new();
+    let mut divide_out = |n: &mut u64, d: u64| {
+        let mut e = 0;
+        while n.is_multiple_of(d) {
+            *n /= d;
+            e += 1;
+        }
+        if e > 0 {
+            factors.push((d, e));
+        }
+    };
+    if n < 2 {
+        return Vec::new();
+    }
+    divide_out(&mut n, 2);
+    divide_out(&mut n, 3);
+    let mut d = 5;
+    while d <= n / d {
+        divide_out(&mut n, d);
+        divide_out(&mut n, d + 2);
+        d += 6;
+    }
+    if n > 1 {
+        factors.push((n, 1));
+    }
+    factors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_sieves_and_factorization() {
+        let sieve = LinearSieve::new(30);
+        assert_eq!(sieve.primes(), &[2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert_eq!((sieve.smallest_prime_factor(1), sieve.smallest_prime_factor(21)), (None, Some(3)));
+        assert!(sieve.is_prime(29) && !sieve.is_prime(1) && !sieve.is_prime(0));
+        assert_eq!(sieve.factorize(24), vec![(2, 3), (3, 1)]);
+        assert!(sieve.factorize(1).is_empty());
+        assert_eq!(LinearSieve::new(0).limit(), 0);
+
+        assert_eq!(primes_in_range(0..30), vec![2, 3, 5, 7, 11, 13, 17, 19, 23, 29]);
+        assert!(primes_in_range(24..29).is_empty() && primes_in_range(5..5).is_empty());
+        // the first primes above 10^12
+        let big: Vec<u64> = SegmentedSieve::new(1_000_000_000_000..1_000_000_000_100).collect();
+        assert_eq!(big[0], 1_000_000_000_039);
+        assert!(big.iter().all(|&p| factorize(p) == vec![(p, 1)]));
+        assert_eq!(factorize(1_000_000_000_000), vec![(2, 12), (5, 12)]);
+        assert_eq!(factorize(600_851_475_143), vec![(71, 1), (839, 1), (1471, 1), (6857, 1)]);
+        assert_eq!(factorize(u64::MAX), vec![(3, 1), (5, 1), (17, 1), (257, 1), (641, 1), (65537, 1), (6_700_417, 1)]);
+
+        // right at the limit: 2^40 - 87 is the largest prime below 2^40
+        let top = primes_in_range(SEGMENTED_SIEVE_MAX_END - 100..SEGMENTED_SIEVE_MAX_END);
+        assert_eq!(top.last(), Some(&(SEGMENTED_SIEVE_MAX_END - 87)));
+        assert!(top.iter().all(|&p| factorize(p) == vec![(p, 1)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "above the segmented sieve limit")]
+    fn test_range_end_above_limit() {
+        primes_in_range(u64::MAX - 1000..u64::MAX);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let is_prime = |x: u64| x >= 2 && (2..x).take_while(|d| d * d <= x).all(|d| !x.is_multiple_of(d));
+        let sieve = LinearSieve::new(5000);
+        for x in 0..=5000 {
+            assert_eq!(sieve.is_prime(x), is_prime(x as u64));
+            let product: u64 = sieve.factorize(x).iter().map(|&(p, e)| p.pow(e)).product();
+            if x >= 1 {
+                assert_eq!(product, x as u64);
+                assert_eq!(sieve.factorize(x), factorize(x as u64));
+            }
+            if let Some(p) = sieve.smallest_prime_factor(x) {
+                assert_eq!(p as usize, (2..=x).find(|&d| x.is_multiple_of(d)).unwrap());
+            }
+        }
+
+        let mut next = test_rng(163);
+        for _ in 0..10 {
+            // some ranges span several windows
+            let lo = next() % 2_000_000;
+            let hi = lo + next() % 150_000;
+            let expected: Vec<u64> = (lo..hi).filter(|&x| is_prime(x)).collect();
+            assert_eq!(primes_in_range(lo..hi), expected);
+        }
+    }
+}
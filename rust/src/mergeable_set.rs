@@ -0,0 +1,148 @@
+// rust/src/mergeable_set.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::treap::Treap;
+
+/// A multiset of i64 with heap-like and ordered-set operations in one structure.
+/// Thin facade over `Treap`: both ends can be popped (a double-ended priority queue), rank queries
+/// stay available, and whole sets can be merged or split by key without re-inserting elements.
+/// - insert / remove / pop_min / pop_max / kth: O(log n) expected
+/// - merge: O(m log(n / m)) expected, O(log n) when the key ranges don't interleave
+/// - split_off: O(log n) expected
+#[derive(Default)]
+pub struct MergeableSet {
+    treap: Treap,
+}
+
+impl MergeableSet {
+    pub fn new() -> Self {
+        MergeableSet { treap: Treap::new() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.treap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.treap.is_empty()
+    }
+
+    pub fn insert(&mut self, key: i64) {
+        self.treap.insert(key);
+    }
+
+    /// Removes one occurrence of `key`. Returns true if it was present.
+    pub fn remove(&mut self, key: i64) -> bool {
+        let before = self.treap.len();
+        self.treap.remove(key);
+        self.treap.len() != before
+    }
+
+    pub fn contains(&self, key: i64) -> bool {
+        self.treap.contains(key)
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.treap.min()
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.treap.max()
+    }
+
+    pub fn pop_min(&mut self) -> Option<i64> {
+        self.treap.pop_min()
+    }
+
+    pub fn pop_max(&mut self) -> Option<i64> {
+        self.treap.pop_max()
+    }
+
+    /// The `k`-th smallest element (0-based, duplicates counted).
+    pub fn kth(&self, k: usize) -> Option<i64> {
+        self.treap.kth(k)
+    }
+
+    /// Moves every element of `other` into `self`.
+    pub fn merge(&mut self, other: MergeableSet) {
+        self.treap.union(other.treap);
+    }
+
+    /// Splits off every element `>= key` into a new set.
+    pub fn split_off(&mut self, key: i64) -> MergeableSet {
+        MergeableSet {
+            treap: self.treap.split_off(key),
+        }
+    }
+
+    /// Elements in sorted order.
+    pub fn to_vec(&self) -> Vec<i64> {
+        self.treap.inorder_vec()
+    }
+}
+
+impl FromIterator<i64> for MergeableSet {
+    fn from_iter<I: IntoIterator<Item = i64>>(iter: I) -> Self {
+        let mut set = MergeableSet::new();
+        iter.into_iter().for_each(|k| set.insert(k));
+        set
+    }
+}
+
+impl DataStructure for MergeableSet {
+    const INFO: Info = Info {
+        name: "MergeableSet",
+        category: "ordered sets",
+        summary: "Multiset of i64 with double-ended pops, rank queries, merge and split, backed by a treap",
+        space: "O(n)",
+        operations: &[
+            Operation::new("insert", "O(log n) expected", "O(1)"),
+            Operation::new("pop_min", "O(log n) expected", "O(1)"),
+            Operation::new("pop_max", "O(log n) expected", "O(1)"),
+            Operation::new("kth", "O(log n) expected", "O(1)"),
+            Operation::new("merge", "O(m log(n / m)) expected", "O(log n)"),
+            Operation::new("split_off", "O(log n) expected", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_double_ended_queue() {
+        let mut s: MergeableSet = [4, 1, 7, 4, 9].into_iter().collect();
+        assert_eq!(s.len(), 5);
+        assert_eq!(s.pop_min(), Some(1));
+        assert_eq!(s.pop_max(), Some(9));
+        assert_eq!(s.min(), Some(4));
+        assert!(s.remove(4));
+        assert!(s.contains(4));
+        assert!(!s.remove(100));
+        assert_eq!(s.to_vec(), vec![4, 7]);
+    }
+
+    #[test]
+    fn test_merge_and_split() {
+        let mut low: MergeableSet = (0..50).collect();
+        let high: MergeableSet = (50..100).collect();
+        low.merge(high);
+        assert_eq!(low.len(), 100);
+        assert_eq!(low.kth(73), Some(73));
+
+        // interleaving ranges with duplicates
+        let evens: MergeableSet = (0..100).step_by(2).collect();
+        low.merge(evens);
+        assert_eq!(low.len(), 150);
+        assert_eq!(low.kth(1), Some(0));
+        assert_eq!(low.kth(2), Some(1));
+
+        let top = low.split_off(90);
+        assert_eq!(top.len(), 15);
+        assert_eq!(low.max(), Some(89));
+        assert_eq!(top.min(), Some(90));
+    }
+}
@@ -0,0 +1,159 @@
+// rust/src/mo.rs
+use std::ops::Range;
+
+/// Order in which `mo` visits the queries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MoOrder {
+    /// Sort by the block of the left end, then by the right end, alternating direction between
+    /// blocks so the right pointer sweeps back and forth instead of jumping back each block.
+    #[default]
+    Blocks,
+    /// Sort by position along a Hilbert curve through the (left, right) plane. Consecutive queries
+    /// stay close in both coordinates, often cutting the pointer moves noticeably.
+    Hilbert,
+}
+
+/// Distance of `(x, y)` along the Hilbert curve filling the `2^order` square.
+fn hilbert_index(mut x: u64, mut y: u64, order: u32) -> u64 {
+    let side = 1u64 << order;
+    let mut d = 0;
+    let mut s = side >> 1;
+    while s > 0 {
+        let (rx, ry) = ((x & s != 0) as u64, (y & s != 0) as u64);
+        d += s * s * ((3 * rx) ^ ry);
+        // rotate the quadrant so the sub-curve starts and ends where the next level expects
+        if ry == 0 {
+            if rx == 1 {
+                (x, y) = (side - 1 - x, side - 1 - y);
+            }
+            (x, y) = (y, x);
+        }
+        s >>= 1;
+    }
+    d
+}
+
+/// Mo's algorithm: answers offline queries over half-open index ranges by sliding one window
+/// `[l, r)` across all of them, in an order that keeps the total movement small. `add(state, i)`
+/// and `remove(state, i)` put element `i` into or take it out of the window, and `answer(state)`
+/// reads off the result for the current window, so any aggregate that can be maintained under
+/// single insertions and deletions (distinct counts, mode frequencies, inversion counts, ...) gets
+/// range queries without a tree. Elements may be added and removed at either end, in any order.
+/// Answers are returned in the order of `queries`.
+///
+/// Complexity: O(q log q) to sort plus O(n √q) calls to `add` / `remove`, where `n` is the largest
+/// query end. Panics if a query range is reversed.
+pub fn mo<S, A>(
+    state: &mut S,
+    queries: &[Range<usize>],
+    order: MoOrder,
+    mut add: impl FnMut(&mut S, usize),
+    mut remove: impl FnMut(&mut S, usize),
+    mut answer: impl FnMut(&S) -> A,
+) -> Vec<A> {
+    for q in queries {
+        assert!(q.start <= q.end, "query range {}..{} is reversed", q.start, q.end);
+    }
+    let n = queries.iter().map(|q| q.end).max().unwrap_or(0);
+    let mut visit: Vec<usize> = (0..queries.len()).collect();
+    match order {
+        MoOrder::Blocks => {
+            let block = (n as f64 / (queries.len() as f64).sqrt()).ceil().max(1.0) as usize;
+            visit.sort_by_key(|&i| {
+                let (b, r) = (queries[i].start / block, queries[i].end);
+                (b, if b % 2 == 0 { r } else { n - r })
+            });
+        }
+        MoOrder::Hilbert => {
+            let bits = usize::BITS - n.leading_zeros();
+            visit.sort_by_cached_key(|&i| hilbert_index(queries[i].start as u64, queries[i].end as u64, bits));
+        }
+    }
+
+    let mut answers: Vec<Option<A>> = (0..queries.len()).map(|_| None).collect();
+    let (mut l, mut r) = (0, 0);
+    for i in visit {
+        let q = &queries[i];
+        // grow before shrinking, so the window never turns inside out
+        while r < q.end {
+            add(state, r);
+            r += 1;
+        }
+        while l > q.start {
+            l -= 1;
+            add(state, l);
+        }
+        while r > q.end {
+            r -= 1;
+            remove(state, r);
+        }
+        while l < q.start {
+            remove(state, l);
+            l += 1;
+        }
+        answers[i] = Some(answer(state));
+    }
+    answers.into_iter().map(|a| a.expect("every query is visited")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    /// Distinct values in each range, the classic Mo's workload.
+    fn distinct_counts(values: &[usize], queries: &[Range<usize>], order: MoOrder) -> Vec<usize> {
+        let mut state = (vec![0usize; 64], 0usize);
+        mo(
+            &mut state,
+            queries,
+            order,
+            |(counts, distinct), i| {
+                counts[values[i]] += 1;
+                *distinct += (counts[values[i]] == 1) as usize;
+            },
+            |(counts, distinct), i| {
+                counts[values[i]] -= 1;
+                *distinct -= (counts[values[i]] == 0) as usize;
+            },
+            |&(_, distinct)| distinct,
+        )
+    }
+
+    #[test]
+    fn test_distinct_counts() {
+        let values = [1, 2, 1, 3, 2, 2, 4];
+        let queries = [0..3, 2..7, 4..6, 3..3, 0..7, 1..2];
+        for order in [MoOrder::Blocks, MoOrder::Hilbert] {
+            assert_eq!(distinct_counts(&values, &queries, order), vec![2, 4, 1, 0, 4, 1]);
+        }
+        assert!(distinct_counts(&values, &[], MoOrder::Hilbert).is_empty());
+        // the first curve cells: (0,0), (0,1), (1,1), (1,0)
+        assert_eq!([(0, 0), (0, 1), (1, 1), (1, 0)].map(|(x, y)| hilbert_index(x, y, 1)), [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(67);
+        let n = 500;
+        let values: Vec<usize> = (0..n).map(|_| next() as usize % 64).collect();
+        let queries: Vec<Range<usize>> = (0..300)
+            .map(|_| {
+                let (a, b) = (next() as usize % (n + 1), next() as usize % (n + 1));
+                a.min(b)..a.max(b)
+            })
+            .collect();
+        let expected: Vec<usize> = queries
+            .iter()
+            .map(|q| {
+                let mut seen = values[q.clone()].to_vec();
+                seen.sort_unstable();
+                seen.dedup();
+                seen.len()
+            })
+            .collect();
+        for order in [MoOrder::Blocks, MoOrder::Hilbert] {
+            assert_eq!(distinct_counts(&values, &queries, order), expected);
+        }
+    }
+}
@@ -0,0 +1,452 @@
+// rust/src/modint.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Inverse of `a` modulo `m` by the extended Euclidean algorithm, if `gcd(a, m) == 1`.
+fn inverse(a: u64, m: u64) -> Option<u64> {
+    // invariant: old_s * a == old_r and s * a == r (mod m)
+    let (mut old_r, mut r) = (a as i128, m as i128);
+    let (mut old_s, mut s) = (1i128, 0i128);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_s, s) = (s, old_s - q * s);
+    }
+    (old_r == 1 || m == 1).then(|| old_s.rem_euclid(m as i128) as u64)
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (a as u128 * b as u128 % m as u128) as u64
+}
+
+fn pow_mod(mut base: u64, mut exp: u64, m: u64) -> u64 {
+    let mut acc = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = mul_mod(acc, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exp >>= 1;
+    }
+    acc
+}
+
+/// Integer modulo the compile-time constant `M`, always kept reduced to `0..M`. The arithmetic
+/// operators wrap around `M` (products go through `u128`, so any `M` up to 2^63 works), which
+/// removes the `% MOD` after every step of DP and counting code. Division multiplies by the
+/// inverse, which exists when the divisor is coprime to `M` (always, for nonzero divisors and a
+/// prime `M`) and panics otherwise; `inv` is the non-panicking form. `ModInt` is `Copy + Default`
+/// with `Add` and `Sub`, so it works as the element of `FenwickTree` and of the segment trees with
+/// the `Sum` monoid. `DynModInt` is the same with the modulus chosen at runtime.
+/// - add / sub / mul / neg: O(1)
+/// - inv / div: O(log M)
+/// - pow: O(log exp)
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ModInt<const M: u64> {
+    value: u64,
+}
+
+/// The NTT-friendly prime 998244353 = 119 * 2^23 + 1.
+pub type ModInt998244353 = ModInt<998_244_353>;
+/// The prime 10^9 + 7.
+pub type ModInt1000000007 = ModInt<1_000_000_007>;
+
+impl<const M: u64> ModInt<M> {
+    pub const MODULUS: u64 = M;
+
+    /// `value` reduced modulo `M`.
+    pub fn new(value: u64) -> Self {
+        const { assert!(M >= 1 && M <= 1 << 63, "the modulus must be in 1..=2^63") };
+        ModInt { value: value % M }
+    }
+
+    /// The representative in `0..M`.
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    pub fn pow(self, exp: u64) -> Self {
+        ModInt { value: pow_mod(self.value, exp, M) }
+    }
+
+    /// The multiplicative inverse, if `value` is coprime to `M`.
+    pub fn inv(self) -> Option<Self> {
+        inverse(self.value, M).map(|value| ModInt { value })
+    }
+}
+
+impl<const M: u64> From<u64> for ModInt<M> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const M: u64> From<i64> for ModInt<M> {
+    /// Negative values map to their representative in `0..M`, so `-1` becomes `M - 1`.
+    fn from(value: i64) -> Self {
+        Self::new((value as i128).rem_euclid(M as i128) as u64)
+    }
+}
+
+impl<const M: u64> From<u32> for ModInt<M> {
+    fn from(value: u32) -> Self {
+        Self::new(value as u64)
+    }
+}
+
+impl<const M: u64> From<usize> for ModInt<M> {
+    fn from(value: usize) -> Self {
+        Self::new(value as u64)
+    }
+}
+
+impl<const M: u64> Add for ModInt<M> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let sum = self.value + rhs.value;
+        ModInt { value: if sum >= M { sum - M } else { sum } }
+    }
+}
+
+impl<const M: u64> Sub for ModInt<M> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let value = if self.value >= rhs.value { self.value - rhs.value } else { self.value + M - rhs.value };
+        ModInt { value }
+    }
+}
+
+impl<const M: u64> Mul for ModInt<M> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        ModInt { value: mul_mod(self.value, rhs.value, M) }
+    }
+}
+
+impl<const M: u64> Div for ModInt<M> {
+    type Output = Self;
+
+    /// Panics if `rhs` has no inverse modulo `M`.
+    fn div(self, rhs: Self) -> Self {
+        let inv = rhs.inv().unwrap_or_else(|| panic!("{} has no inverse modulo {}", rhs.value, M));
+        self.mul(inv)
+    }
+}
+
+impl<const M: u64> Neg for ModInt<M> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ModInt::default() - self
+    }
+}
+
+impl<const M: u64> AddAssign for ModInt<M> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const M: u64> SubAssign for ModInt<M> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const M: u64> MulAssign for ModInt<M> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<const M: u64> DivAssign for ModInt<M> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<const M: u64> Sum for ModInt<M> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt::default(), Add::add)
+    }
+}
+
+impl<const M: u64> Product for ModInt<M> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(ModInt::new(1), Mul::mul)
+    }
+}
+
+impl<const M: u64> fmt::Display for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const M: u64> fmt::Debug for ModInt<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, M)
+    }
+}
+
+/// Integer modulo a modulus chosen at runtime, carried in every value. Mixing two values with
+/// different moduli panics. `DynModInt::default()` is a zero with no modulus yet (shown as modulus
+/// 0) that takes on the modulus of whatever it is combined with, which is what lets `FenwickTree`
+/// and the `Sum` monoid start from `T::default()`.
+/// - add / sub / mul / neg: O(1)
+/// - inv / div: O(log m)
+/// - pow: O(log exp)
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct DynModInt {
+    value: u64,
+    // 0 only for the unbound zero
+    modulus: u64,
+}
+
+impl DynModInt {
+    /// `value` reduced modulo `modulus`. Panics unless `1 <= modulus <= 2^63`.
+    pub fn new(value: u64, modulus: u64) -> Self {
+        assert!((1..=1 << 63).contains(&modulus), "the modulus must be in 1..=2^63, got {}", modulus);
+        DynModInt { value: value % modulus, modulus }
+    }
+
+    /// `value` modulo `modulus`, with negatives mapped into `0..modulus`.
+    pub fn from_signed(value: i64, modulus: u64) -> Self {
+        let reduced = (value as i128).rem_euclid(modulus.max(1) as i128) as u64;
+        Self::new(reduced, modulus)
+    }
+
+    pub fn value(self) -> u64 {
+        self.value
+    }
+
+    /// The modulus, or 0 for the unbound zero from `default()`.
+    pub fn modulus(self) -> u64 {
+        self.modulus
+    }
+
+    /// The common modulus of two operands.
+    fn modulus_with(self, rhs: Self) -> u64 {
+        match (self.modulus, rhs.modulus) {
+            (0, m) | (m, 0) => m,
+            (a, b) => {
+                assert_eq!(a, b, "mixed moduli {} and {}", a, b);
+                a
+            }
+        }
+    }
+
+    /// Panics on the unbound zero, since `x^0 = 1` needs a modulus to be reduced by.
+    pub fn pow(self, exp: u64) -> Self {
+        assert!(self.modulus != 0, "pow needs a value with a modulus");
+        DynModInt { value: pow_mod(self.value, exp, self.modulus), modulus: self.modulus }
+    }
+
+    /// The multiplicative inverse, if `value` is coprime to the modulus; None for the unbound zero.
+    pub fn inv(self) -> Option<Self> {
+        if self.modulus == 0 {
+            return None;
+        }
+        inverse(self.value, self.modulus).map(|value| DynModInt { value, modulus: self.modulus })
+    }
+}
+
+impl Add for DynModInt {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        let m = self.modulus_with(rhs);
+        let sum = self.value + rhs.value;
+        DynModInt { value: if m != 0 && sum >= m { sum - m } else { sum }, modulus: m }
+    }
+}
+
+impl Sub for DynModInt {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        let m = self.modulus_with(rhs);
+        let value = if self.value >= rhs.value { self.value - rhs.value } else { self.value + m - rhs.value };
+        DynModInt { value, modulus: m }
+    }
+}
+
+impl Mul for DynModInt {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        let m = self.modulus_with(rhs);
+        // with the unbound zero on either side the product is zero
+        let value = if m == 0 { 0 } else { mul_mod(self.value, rhs.value, m) };
+        DynModInt { value, modulus: m }
+    }
+}
+
+impl Div for DynModInt {
+    type Output = Self;
+
+    /// Panics if `rhs` has no inverse modulo the common modulus.
+    fn div(self, rhs: Self) -> Self {
+        let m = self.modulus_with(rhs);
+        let rhs = DynModInt { modulus: m, ..rhs };
+        let inv = rhs.inv().unwrap_or_else(|| panic!("{} has no inverse modulo {}", rhs.value, m));
+        self.mul(inv)
+    }
+}
+
+impl Neg for DynModInt {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        DynModInt::default() - self
+    }
+}
+
+impl AddAssign for DynModInt {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for DynModInt {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for DynModInt {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for DynModInt {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl Sum for DynModInt {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(DynModInt::default(), Add::add)
+    }
+}
+
+impl fmt::Display for DynModInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl fmt::Debug for DynModInt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.value, self.modulus)
+    }
+}
+
+impl<const M: u64> DataStructure for ModInt<M> {
+    const INFO: Info = Info {
+        name: "ModInt",
+        category: "math",
+        summary: "Integers modulo a compile-time (ModInt) or runtime (DynModInt) modulus with operator arithmetic",
+        space: "O(1)",
+        operations: &[
+            Operation::new("add / sub / mul / neg", "O(1)", "O(1)"),
+            Operation::new("inv / div", "O(log M)", "O(1)"),
+            Operation::new("pow", "O(log exp)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::fenwick_tree::FenwickTree;
+    use crate::monoid::Sum as SumOp;
+    use crate::segment_tree::SegmentTree;
+
+    type Mint = ModInt1000000007;
+
+    #[test]
+    fn test_arithmetic_and_trees() {
+        let (a, b) = (Mint::new(1_000_000_000), Mint::from(10u64));
+        assert_eq!((a + b).value(), 3);
+        assert_eq!((b - a).value(), 17);
+        assert_eq!((-Mint::new(1)).value(), 1_000_000_006);
+        assert_eq!(Mint::from(-1i64), -Mint::new(1));
+        assert_eq!((a * a).value(), 49);
+        assert_eq!(Mint::new(2).pow(1_000_000_006), Mint::new(1));
+        assert_eq!(Mint::new(3) / Mint::new(3), Mint::new(1));
+        assert_eq!((Mint::new(1) / Mint::new(2)).value(), 500_000_004);
+        assert_eq!(Mint::new(0).inv(), None);
+        assert_eq!(ModInt::<12>::new(4).inv(), None);
+        assert_eq!(ModInt::<12>::new(5).inv(), Some(ModInt::new(5)));
+        assert_eq!(format!("{} {:?}", b, ModInt::<7>::new(9)), "10 2 (mod 7)");
+
+        // C(50, 25) mod p through factorials and an inverse
+        let fact = |n: u64| (1..=n).map(Mint::new).product::<Mint>();
+        assert_eq!(fact(50) / (fact(25) * fact(25)), Mint::new(126_410_606_437_752 % 1_000_000_007));
+        // the largest allowed modulus, where sums would overflow without reduction
+        let big = ModInt::<{ 1 << 63 }>::new((1 << 63) - 1);
+        assert_eq!((big + big).value(), (1 << 63) - 2);
+        assert_eq!(ModInt::<{ 1 << 63 }>::from(-1i64), big);
+
+        let values: Vec<Mint> = (0..10u64).map(|i| Mint::new(i * 300_000_000)).collect();
+        let mut fenwick = FenwickTree::from_vec(&values);
+        let mut tree = SegmentTree::from_slice(&values, SumOp);
+        fenwick.add(3, Mint::new(1_000_000_000));
+        tree.set(3, values[3] + Mint::new(1_000_000_000));
+        let expected: Mint = values.iter().copied().sum::<Mint>() + Mint::new(1_000_000_000);
+        assert_eq!((fenwick.sum(..), tree.query(0..10)), (expected, expected));
+
+        let m = |v| DynModInt::new(v, 13);
+        assert_eq!((m(7) * m(8)).value(), 4);
+        assert_eq!(m(1) / m(4), m(10));
+        assert_eq!(DynModInt::from_signed(-3, 13), m(10));
+        assert_eq!((DynModInt::default() + m(5), DynModInt::default() - m(5)), (m(5), m(8)));
+        let dynamic = FenwickTree::from_vec(&[m(12), m(5), m(9)]);
+        assert_eq!(dynamic.sum(..), m(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "mixed moduli")]
+    fn test_mixed_moduli() {
+        let _ = DynModInt::new(1, 7) + DynModInt::new(1, 11);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(157);
+        const P: u64 = 998_244_353;
+        for _ in 0..2000 {
+            let (x, y, e) = (next(), next(), next() % 100);
+            let (a, b) = (ModInt998244353::new(x), ModInt998244353::new(y));
+            let (xr, yr) = ((x % P) as u128, (y % P) as u128);
+            let p = P as u128;
+            assert_eq!((a + b).value() as u128, (xr + yr) % p);
+            assert_eq!((a - b).value() as u128, (xr + p - yr) % p);
+            assert_eq!((a * b).value() as u128, xr * yr % p);
+            assert_eq!(a.pow(e).value() as u128, (0..e).fold(1, |acc, _| acc * xr % p));
+            if yr != 0 {
+                assert_eq!(a / b * b, a);
+            }
+            // a composite runtime modulus: inverses exist exactly for coprime values
+            let modulus = 2 + next() % 1000;
+            let d = DynModInt::new(y, modulus);
+            let gcd = (1..=modulus).rev().find(|&g| modulus.is_multiple_of(g) && d.value().is_multiple_of(g)).unwrap();
+            match d.inv() {
+                Some(inv) => assert!(gcd == 1 && (d * inv).value() == 1),
+                None => assert_ne!(gcd, 1),
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+// rust/src/monoid.rs
+use std::ops::Add;
+
+/// An associative operation with an identity element, the building block of the segment trees:
+/// `combine(a, combine(b, c)) == combine(combine(a, b), c)` and `combine(identity, x) == x == combine(x, identity)`.
+/// Commutativity is not required; trees always combine values in index order.
+///
+/// Methods take `&self` so an operation can carry state (a modulus, a callback, ...).
+pub trait Monoid<T> {
+    fn identity(&self) -> T;
+    fn combine(&self, a: &T, b: &T) -> T;
+}
+
+/// Addition with identity `T::default()` (zero for the numeric types).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sum;
+
+impl<T: Copy + Default + Add<Output = T>> Monoid<T> for Sum {
+    fn identity(&self) -> T {
+        T::default()
+    }
+
+    fn combine(&self, a: &T, b: &T) -> T {
+        *a + *b
+    }
+}
+
+/// Minimum, with the type's largest value as identity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Min;
+
+/// Maximum, with the type's smallest value as identity.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Max;
+
+macro_rules! impl_min_max {
+    ($($t:ty),*) => {$(
+        impl Monoid<$t> for Min {
+            fn identity(&self) -> $t {
+                <$t>::MAX
+            }
+
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                *a.min(b)
+            }
+        }
+
+        impl Monoid<$t> for Max {
+            fn identity(&self) -> $t {
+                <$t>::MIN
+            }
+
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                *a.max(b)
+            }
+        }
+    )*};
+}
+
+impl_min_max!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Greatest common divisor (of absolute values), with identity 0. Signed values are reduced as
+/// their unsigned magnitudes, so `MIN` works too; the one gcd that does not fit back, `|MIN|` itself
+/// (from `MIN` with 0 or `MIN`), panics.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Gcd;
+
+macro_rules! impl_gcd {
+    ($($t:ty),*) => {$(
+        impl Monoid<$t> for Gcd {
+            fn identity(&self) -> $t {
+                0
+            }
+
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                let (mut a, mut b) = (*a, *b);
+                while b != 0 {
+                    (a, b) = (b, a % b);
+                }
+                a
+            }
+        }
+    )*};
+    ($($t:ty => $u:ty),*) => {$(
+        impl Monoid<$t> for Gcd {
+            fn identity(&self) -> $t {
+                0
+            }
+
+            fn combine(&self, a: &$t, b: &$t) -> $t {
+                let g: $u = Gcd.combine(&a.unsigned_abs(), &b.unsigned_abs());
+                <$t>::try_from(g).expect("gcd of MIN with 0 or MIN does not fit the type")
+            }
+        }
+    )*};
+}
+
+impl_gcd!(u8, u16, u32, u64, u128, usize);
+impl_gcd!(i8 => u8, i16 => u16, i32 => u32, i64 => u64, i128 => u128, isize => usize);
+
+/// A monoid made from an identity value and a closure, for one-off operations (gcd, matrix product,
+/// string concatenation, ...).
+#[derive(Clone)]
+pub struct FnMonoid<T, F> {
+    identity: T,
+    combine: F,
+}
+
+impl<T, F> FnMonoid<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    pub fn new(identity: T, combine: F) -> Self {
+        FnMonoid { identity, combine }
+    }
+}
+
+impl<T, F> Monoid<T> for FnMonoid<T, F>
+where
+    T: Clone,
+    F: Fn(&T, &T) -> T,
+{
+    fn identity(&self) -> T {
+        self.identity.clone()
+    }
+
+    fn combine(&self, a: &T, b: &T) -> T {
+        (self.combine)(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_monoids() {
+        assert_eq!(Monoid::<i64>::combine(&Sum, &3, &4), 7);
+        assert_eq!(Monoid::<u8>::identity(&Min), u8::MAX);
+        assert_eq!(Monoid::<i32>::combine(&Max, &-3, &-4), -3);
+        assert_eq!(Gcd.combine(&12i64, &-18), 6);
+        assert_eq!(Gcd.combine(&0u32, &7), 7);
+        assert_eq!(Monoid::<i64>::identity(&Gcd), 0);
+        assert_eq!(Gcd.combine(&i64::MIN, &-6), 2);
+        assert_eq!(Gcd.combine(&i8::MIN, &-1), 1);
+        let product = FnMonoid::new(1u64, |a: &u64, b: &u64| a * b);
+        assert_eq!(product.combine(&product.identity(), &5), 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit")]
+    fn test_gcd_of_min_alone() {
+        Gcd.combine(&i32::MIN, &0);
+    }
+}
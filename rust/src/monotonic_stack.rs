@@ -0,0 +1,134 @@
+// rust/src/monotonic_stack.rs
+use std::ops::Range;
+
+/// Nearest-element queries answered with a monotonic stack: scanning in one direction, the stack
+/// holds the indices that can still be the answer for some later position, and each index is
+/// pushed and popped at most once. All comparisons are strict, and positions without an answer
+/// get None. Values only need `PartialOrd`, so floats work, as long as none of them is NaN.
+///
+/// For every position, the index of the nearest element in the scan direction that `beats` it.
+fn nearest<T>(values: &[T], forward: bool, beats: impl Fn(&T, &T) -> bool) -> Vec<Option<usize>> {
+    let mut out = vec![None; values.len()];
+    let mut stack: Vec<usize> = Vec::new();
+    let n = values.len();
+    for k in 0..n {
+        let i = if forward { k } else { n - 1 - k };
+        // an index beaten by `values[i]` is hidden behind it for every later position
+        while let Some(&top) = stack.last() {
+            if beats(&values[top], &values[i]) {
+                break;
+            }
+            stack.pop();
+        }
+        out[i] = stack.last().copied();
+        stack.push(i);
+    }
+    out
+}
+
+/// Index of the first later element strictly greater than each element.
+///
+/// Complexity: O(n)
+pub fn next_greater<T: PartialOrd>(values: &[T]) -> Vec<Option<usize>> {
+    nearest(values, false, |candidate, x| candidate > x)
+}
+
+/// Index of the last earlier element strictly greater than each element.
+///
+/// Complexity: O(n)
+pub fn previous_greater<T: PartialOrd>(values: &[T]) -> Vec<Option<usize>> {
+    nearest(values, true, |candidate, x| candidate > x)
+}
+
+/// Index of the first later element strictly smaller than each element.
+///
+/// Complexity: O(n)
+pub fn next_smaller<T: PartialOrd>(values: &[T]) -> Vec<Option<usize>> {
+    nearest(values, false, |candidate, x| candidate < x)
+}
+
+/// Index of the last earlier element strictly smaller than each element.
+///
+/// Complexity: O(n)
+pub fn previous_smaller<T: PartialOrd>(values: &[T]) -> Vec<Option<usize>> {
+    nearest(values, true, |candidate, x| candidate < x)
+}
+
+/// Largest rectangle in a histogram of unit-width bars: returns its area and the bars it spans.
+/// Every bar extends left and right up to the nearest strictly lower bar; one stack pass finds
+/// both ends, as a bar's right end is fixed when a lower bar pops it. Returns `(0, 0..0)` when no
+/// bar has positive height; on ties the leftmost rectangle wins.
+///
+/// Complexity: O(n)
+pub fn largest_rectangle(heights: &[u64]) -> (u64, Range<usize>) {
+    let mut best = (0, 0..0);
+    let mut stack: Vec<usize> = Vec::new();
+    for i in 0..=heights.len() {
+        // a virtual bar of height 0 at the end pops everything
+        let h = heights.get(i).copied().unwrap_or(0);
+        while let Some(&top) = stack.last()
+            && heights[top] >= h
+        {
+            stack.pop();
+            let start = stack.last().map_or(0, |&s| s + 1);
+            let area = heights[top] * (i - start) as u64;
+            // when an equal bar pops `top` this rectangle is cut short, but that bar's covers it
+            if area > best.0 || (area == best.0 && area > 0 && start < best.1.start) {
+                best = (area, start..i);
+            }
+        }
+        stack.push(i);
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_nearest_and_rectangle() {
+        let v = [2, 1, 2, 4, 3, 1];
+        assert_eq!(next_greater(&v), vec![Some(3), Some(2), Some(3), None, None, None]);
+        assert_eq!(previous_greater(&v), vec![None, Some(0), None, None, Some(3), Some(4)]);
+        assert_eq!(next_smaller(&v), vec![Some(1), None, Some(5), Some(4), Some(5), None]);
+        assert_eq!(previous_smaller(&v), vec![None, None, Some(1), Some(2), Some(2), None]);
+        assert_eq!(next_greater(&[1.5, 0.5, 2.0]), vec![Some(2), Some(2), None]);
+        assert!(next_greater::<i32>(&[]).is_empty());
+
+        assert_eq!(largest_rectangle(&[2, 1, 5, 6, 2, 3]), (10, 2..4));
+        assert_eq!(largest_rectangle(&[3, 3, 3]), (9, 0..3));
+        assert_eq!(largest_rectangle(&[4, 0, 2, 2]), (4, 0..1));
+        assert_eq!(largest_rectangle(&[0, 0]), (0, 0..0));
+        assert_eq!(largest_rectangle(&[]), (0, 0..0));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(107);
+        for n in [1, 2, 5, 40, 200] {
+            let v: Vec<u64> = (0..n).map(|_| next() % 8).collect();
+            let later = |i: usize, f: &dyn Fn(u64) -> bool| (i + 1..n).find(|&j| f(v[j]));
+            let earlier = |i: usize, f: &dyn Fn(u64) -> bool| (0..i).rev().find(|&j| f(v[j]));
+            assert_eq!(next_greater(&v), (0..n).map(|i| later(i, &|x| x > v[i])).collect::<Vec<_>>());
+            assert_eq!(next_smaller(&v), (0..n).map(|i| later(i, &|x| x < v[i])).collect::<Vec<_>>());
+            assert_eq!(previous_greater(&v), (0..n).map(|i| earlier(i, &|x| x > v[i])).collect::<Vec<_>>());
+            assert_eq!(previous_smaller(&v), (0..n).map(|i| earlier(i, &|x| x < v[i])).collect::<Vec<_>>());
+
+            // the best area over every span
+            let mut best = (0, 0..0);
+            for l in 0..n {
+                for r in l + 1..=n {
+                    let area = v[l..r].iter().min().unwrap() * (r - l) as u64;
+                    if area > best.0 {
+                        best = (area, l..r);
+                    }
+                }
+            }
+            let (area, span) = largest_rectangle(&v);
+            assert_eq!(area, best.0);
+            assert_eq!(area, v[span.clone()].iter().min().map_or(0, |&m| m * span.len() as u64));
+        }
+    }
+}
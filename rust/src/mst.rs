@@ -0,0 +1,399 @@
+// rust/src/mst.rs
+use crate::lca::EulerTourLca;
+use crate::union_find::UnionFind;
+use std::ops::Sub;
+
+/// Minimum spanning forests over undirected edge lists `(u, v, weight)` on vertices `0..n`.
+/// Both algorithms return the indices of the chosen edges in increasing order. Ties are broken by
+/// edge index, so all of them pick the same forest for the same input.
+///
+/// Kruskal: sort edges and add those that join two components.
+/// Complexity: O(m log m)
+pub fn kruskal<W: Copy + Ord>(n: usize, edges: &[(usize, usize, W)]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..edges.len()).collect();
+    order.sort_by_key(|&i| (edges[i].2, i));
+    let mut uf = UnionFind::new(n);
+    let mut tree: Vec<usize> = order.into_iter().filter(|&i| uf.union(edges[i].0, edges[i].1)).collect();
+    tree.sort_unstable();
+    tree
+}
+
+/// Borůvka: in every round each component picks its cheapest outgoing edge and all of them are
+/// added at once, so the number of components at least halves per round.
+/// The per-component minimum search in a round only reads shared state, which is what makes the
+/// algorithm the usual choice for parallel MST; this version runs it sequentially.
+/// Complexity: O(m log n)
+pub fn boruvka<W: Copy + Ord>(n: usize, edges: &[(usize, usize, W)]) -> Vec<usize> {
+    let mut uf = UnionFind::new(n);
+    let mut tree = Vec::new();
+    let mut cheapest: Vec<Option<usize>> = vec![None; n];
+    loop {
+        cheapest.iter_mut().for_each(|c| *c = None);
+        for (i, &(u, v, w)) in edges.iter().enumerate() {
+            let (ru, rv) = (uf.find(u), uf.find(v));
+            if ru == rv {
+                continue;
+            }
+            for r in [ru, rv] {
+                if cheapest[r].is_none_or(|j| (w, i) < (edges[j].2, j)) {
+                    cheapest[r] = Some(i);
+                }
+            }
+        }
+        let mut merged = false;
+        for i in cheapest.iter().flatten() {
+            // an edge can be the cheapest of both its endpoints' components
+            if uf.union(edges[*i].0, edges[*i].1) {
+                tree.push(*i);
+                merged = true;
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+    tree.sort_unstable();
+    tree
+}
+
+/// Checks that `tree` (edge indices into `edges`) is a minimum spanning forest of the graph.
+/// The tree must be acyclic and connect every pair of vertices the full graph connects, and every
+/// non-tree edge `(u, v, w)` must weigh at least the heaviest edge on the tree path from `u` to `v`
+/// (the cycle property). Path maxima are answered with an Euler-tour LCA over a sparse table and
+/// binary-lifted maxima towards the LCA.
+///
+/// Complexity: O((n + m) log n)
+pub fn verify_mst<W: Copy + Ord>(n: usize, edges: &[(usize, usize, W)], tree: &[usize]) -> bool {
+    if tree.iter().any(|&i| i >= edges.len()) {
+        return false;
+    }
+    let Some((mut uf, paths)) = PathMax::from_tree(n, edges, tree) else {
+        return false;
+    };
+    let mut in_tree = vec![false; edges.len()];
+    tree.iter().for_each(|&i| in_tree[i] = true);
+    edges.iter().zip(&in_tree).filter(|&(_, &t)| !t).all(|(&(u, v, w), _)| {
+        // an edge between two tree components means the forest is not spanning
+        uf.connected(u, v) && paths.query(u, v).is_none_or(|(heaviest, _)| heaviest <= w)
+    })
+}
+
+/// The cheapest spanning forest that differs from the minimum one (it may tie with it).
+/// Some second-best forest always differs from the MST by a single swap: add a non-tree edge and drop
+/// the heaviest edge on the cycle it closes, so only m candidates need checking.
+/// Returns None when the MST is the only spanning forest.
+///
+/// Complexity: O((n + m) log n)
+pub fn second_best_mst<W>(n: usize, edges: &[(usize, usize, W)]) -> Option<Vec<usize>>
+where
+    W: Copy + Ord + Sub<Output = W>,
+{
+    let tree = kruskal(n, edges);
+    let (_, paths) = PathMax::from_tree(n, edges, &tree).expect("kruskal returns a forest");
+    let mut in_tree = vec![false; edges.len()];
+    tree.iter().for_each(|&i| in_tree[i] = true);
+    let (_, added, removed) = edges
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| !in_tree[i])
+        .filter_map(|(i, &(u, v, w))| paths.query(u, v).map(|(heaviest, j)| (w - heaviest, i, j)))
+        .min()?;
+    let mut swapped: Vec<usize> = tree.into_iter().filter(|&i| i != removed).collect();
+    swapped.push(added);
+    swapped.sort_unstable();
+    Some(swapped)
+}
+
+/// Inclusive range of weights an edge can take while the forest returned by `kruskal` stays minimum.
+/// `None` on either side means the range is unbounded in that direction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WeightRange<W> {
+    pub min: Option<W>,
+    pub max: Option<W>,
+}
+
+/// Sensitivity analysis of the minimum spanning forest.
+/// A non-tree edge can get cheaper down to the heaviest edge on the tree path between its endpoints.
+/// A tree edge can get more expensive up to the cheapest non-tree edge whose tree path covers it
+/// (unbounded for bridges). Covering weights are assigned by walking the non-tree edges in increasing
+/// weight order and skipping already covered tree edges with a path-compressed jump pointer.
+///
+/// Complexity: O(m log m + n log n)
+pub fn mst_sensitivity<W: Copy + Ord>(n: usize, edges: &[(usize, usize, W)]) -> Vec<WeightRange<W>> {
+    let tree = kruskal(n, edges);
+    let (_, paths) = PathMax::from_tree(n, edges, &tree).expect("kruskal returns a forest");
+    let mut in_tree = vec![false; edges.len()];
+    tree.iter().for_each(|&i| in_tree[i] = true);
+    let mut ranges: Vec<WeightRange<W>> = edges
+        .iter()
+        .enumerate()
+        .map(|(i, &(u, v, _))| WeightRange {
+            min: if in_tree[i] { None } else { paths.query(u, v).map(|(heaviest, _)| heaviest) },
+            max: None,
+        })
+        .collect();
+
+    let mut non_tree: Vec<usize> = (0..edges.len()).filter(|&i| !in_tree[i]).collect();
+    non_tree.sort_by_key(|&i| (edges[i].2, i));
+    // jump[v]: the nearest ancestor-or-self of v whose parent edge has not been covered yet
+    let mut jump: Vec<usize> = (0..n).collect();
+    fn find(jump: &mut [usize], v: usize) -> usize {
+        let mut root = v;
+        while jump[root] != root {
+            root = jump[root];
+        }
+        let mut cur = v;
+        while jump[cur] != root {
+            let next = jump[cur];
+            jump[cur] = root;
+            cur = next;
+        }
+        root
+    }
+    for i in non_tree {
+        let (u, v, w) = edges[i];
+        if u == v {
+            continue;
+        }
+        let top = paths.lca_depth(u, v);
+        for end in [u, v] {
+            let mut x = find(&mut jump, end);
+            while paths.lca.depth(x) > top {
+                let (parent, edge) = paths.up[0][x];
+                let (_, id) = edge.expect("non-root vertices have a parent edge");
+                ranges[id].max = Some(w);
+                jump[x] = parent;
+                x = find(&mut jump, parent);
+            }
+        }
+    }
+    ranges
+}
+
+/// Heaviest tree edge as `(weight, edge id)`, None for an empty path.
+type Heaviest<W> = Option<(W, usize)>;
+
+/// Maximum edge weight on tree paths of a forest.
+struct PathMax<W> {
+    lca: EulerTourLca,
+    // up[j][v]: 2^j-th ancestor of v and the heaviest (weight, edge id) on the way there
+    up: Vec<Vec<(usize, Heaviest<W>)>>,
+}
+
+impl<W: Copy + Ord> PathMax<W> {
+    /// Builds the structure over the given tree edges, also returning the union-find of the forest.
+    /// Returns None if the edges contain a cycle.
+    fn from_tree(n: usize, edges: &[(usize, usize, W)], tree: &[usize]) -> Option<(UnionFind, Self)> {
+        let mut uf = UnionFind::new(n);
+        let mut adj = vec![Vec::new(); n];
+        for &i in tree {
+            let (u, v, w) = edges[i];
+            if !uf.union(u, v) {
+                return None;
+            }
+            adj[u].push((v, (w, i)));
+            adj[v].push((u, (w, i)));
+        }
+        Some((uf, PathMax::new(&adj)))
+    }
+
+    fn new(adj: &[Vec<(usize, (W, usize))>]) -> Self {
+        let n = adj.len();
+        let mut seen = vec![false; n];
+        let mut parent: Vec<(usize, Heaviest<W>)> = (0..n).map(|v| (v, None)).collect();
+        for root in 0..n {
+            if seen[root] {
+                continue;
+            }
+            seen[root] = true;
+            let mut stack = vec![root];
+            while let Some(u) = stack.pop() {
+                for &(v, w) in &adj[u] {
+                    if !seen[v] {
+                        seen[v] = true;
+                        parent[v] = (u, Some(w));
+                        stack.push(v);
+                    }
+                }
+            }
+        }
+        let lca = EulerTourLca::from_parents(&parent.iter().map(|&(p, w)| w.map(|_| p)).collect::<Vec<_>>());
+        let mut up = vec![parent];
+        for j in 1..usize::BITS as usize - n.leading_zeros() as usize {
+            let prev = &up[j - 1];
+            let level = (0..n)
+                .map(|v| {
+                    let (mid, a) = prev[v];
+                    let (top, b) = prev[mid];
+                    (top, a.max(b))
+                })
+                .collect();
+            up.push(level);
+        }
+        PathMax { lca, up }
+    }
+
+    /// Heaviest edge climbing from `v` to its ancestor at depth `target`.
+    fn climb(&self, mut v: usize, target: usize) -> Heaviest<W> {
+        let mut best = None;
+        let mut steps = self.lca.depth(v) - target;
+        let mut j = 0;
+        while steps > 0 {
+            if steps & 1 == 1 {
+                let (next, w) = self.up[j][v];
+                best = best.max(w);
+                v = next;
+            }
+            steps >>= 1;
+            j += 1;
+        }
+        best
+    }
+
+    fn lca_depth(&self, u: usize, v: usize) -> usize {
+        self.lca.depth(self.lca.lca(u, v).expect("vertices are in the same tree"))
+    }
+
+    /// Heaviest `(weight, edge id)` on the path between two vertices of the same tree
+    /// (None when `u == v`).
+    fn query(&self, u: usize, v: usize) -> Heaviest<W> {
+        let top = self.lca_depth(u, v);
+        self.climb(u, top).max(self.climb(v, top))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    fn weight(edges: &[(usize, usize, i64)], tree: &[usize]) -> i64 {
+        tree.iter().map(|&i| edges[i].2).sum()
+    }
+
+    fn random_graph(n: usize, m: usize, seed: u64) -> Vec<(usize, usize, i64)> {
+        let mut next = test_rng(seed);
+        (0..m).map(|_| (next() as usize % n, next() as usize % n, (next() % 20) as i64)).collect()
+    }
+
+    #[test]
+    fn test_small_mst() {
+        let edges = [(0, 1, 4), (0, 2, 1), (1, 2, 2), (1, 3, 5), (2, 3, 8), (3, 4, 3)];
+        let k = kruskal(5, &edges);
+        assert_eq!(k, vec![1, 2, 3, 5]);
+        assert_eq!(boruvka(5, &edges), k);
+        assert_eq!(weight(&edges, &k), 11);
+        assert!(verify_mst(5, &edges, &k));
+        // swapping the 5 for the 8 keeps a spanning tree but not a minimum one
+        assert!(!verify_mst(5, &edges, &[1, 2, 4, 5]));
+        // missing an edge leaves vertex 4 disconnected
+        assert!(!verify_mst(5, &edges, &[1, 2, 3]));
+        // a cycle is not a tree
+        assert!(!verify_mst(5, &edges, &[0, 1, 2, 3, 5]));
+    }
+
+    #[test]
+    fn test_boruvka_matches_kruskal() {
+        for seed in 0..20 {
+            let n = 40;
+            let edges = random_graph(n, 90, seed);
+            let k = kruskal(n, &edges);
+            let b = boruvka(n, &edges);
+            assert_eq!(weight(&edges, &b), weight(&edges, &k), "seed {}", seed);
+            assert!(verify_mst(n, &edges, &b));
+            assert!(verify_mst(n, &edges, &k));
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_heavier_forest() {
+        let n = 30;
+        let edges = random_graph(n, 80, 7);
+        let tree = kruskal(n, &edges);
+        // replace one tree edge by a strictly heavier non-tree edge that reconnects the cut
+        for &removed in &tree {
+            let rest: Vec<usize> = tree.iter().copied().filter(|&i| i != removed).collect();
+            let mut uf = UnionFind::new(n);
+            rest.iter().for_each(|&i| {
+                uf.union(edges[i].0, edges[i].1);
+            });
+            let swap = (0..edges.len()).find(|&i| {
+                let (u, v, w) = edges[i];
+                !tree.contains(&i) && !uf.connected(u, v) && w > edges[removed].2
+            });
+            if let Some(i) = swap {
+                let mut other = rest.clone();
+                other.push(i);
+                assert!(!verify_mst(n, &edges, &other));
+            }
+        }
+    }
+
+    /// Weights of all spanning forests (same size as the MST), by brute force over edge subsets.
+    fn all_forest_weights(n: usize, edges: &[(usize, usize, i64)]) -> Vec<(i64, Vec<usize>)> {
+        let size = kruskal(n, edges).len();
+        let mut res = vec![];
+        for mask in 0u32..1 << edges.len() {
+            if mask.count_ones() as usize != size {
+                continue;
+            }
+            let chosen: Vec<usize> = (0..edges.len()).filter(|&i| mask >> i & 1 == 1).collect();
+            let mut uf = UnionFind::new(n);
+            if chosen.iter().all(|&i| uf.union(edges[i].0, edges[i].1)) {
+                res.push((weight(edges, &chosen), chosen));
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn test_second_best_mst() {
+        for seed in 0..15 {
+            let edges = random_graph(6, 11, seed);
+            let mst = kruskal(6, &edges);
+            let mut others: Vec<i64> =
+                all_forest_weights(6, &edges).into_iter().filter(|(_, t)| *t != mst).map(|(w, _)| w).collect();
+            others.sort_unstable();
+            let second = second_best_mst(6, &edges);
+            assert_eq!(second.as_ref().map(|t| weight(&edges, t)), others.first().copied(), "seed {}", seed);
+            if let Some(t) = second {
+                assert_ne!(t, mst);
+            }
+        }
+        // a tree has no alternative
+        assert_eq!(second_best_mst(3, &[(0, 1, 1), (1, 2, 1)]), None);
+    }
+
+    #[test]
+    fn test_mst_sensitivity() {
+        let edges = [(0, 1, 4), (0, 2, 1), (1, 2, 2), (1, 3, 5), (2, 3, 8), (3, 4, 3)];
+        let ranges = mst_sensitivity(5, &edges);
+        assert_eq!(ranges[0], WeightRange { min: Some(2), max: None });
+        assert_eq!(ranges[2], WeightRange { min: None, max: Some(4) });
+        assert_eq!(ranges[4], WeightRange { min: Some(5), max: None });
+        // (3, 4) is a bridge
+        assert_eq!(ranges[5], WeightRange { min: None, max: None });
+
+        // every bound is tight: the kruskal forest stays minimum at the bound and not one step past it
+        for seed in 0..10 {
+            let n = 12;
+            let edges = random_graph(n, 30, seed);
+            let tree = kruskal(n, &edges);
+            for (i, range) in mst_sensitivity(n, &edges).into_iter().enumerate() {
+                let mut changed = edges.clone();
+                if let Some(hi) = range.max {
+                    changed[i].2 = hi;
+                    assert!(verify_mst(n, &changed, &tree));
+                    changed[i].2 = hi + 1;
+                    assert!(!verify_mst(n, &changed, &tree));
+                }
+                if let Some(lo) = range.min {
+                    changed[i].2 = lo;
+                    assert!(verify_mst(n, &changed, &tree));
+                    changed[i].2 = lo - 1;
+                    assert!(!verify_mst(n, &changed, &tree));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,538 @@
+// rust/src/pairing_heap.rs
+use crate::DataStructure;
+use crate::heap::{AddressableHeap, Heap, MergeableHeap};
+use crate::info::{Info, Operation};
+use crate::slab::{Handle, Slab};
+
+struct Node<T> {
+    value: T,
+    // the heap the item was pushed into; later melds are followed through `PairingForest::find`
+    home: usize,
+    child: Option<Handle>,
+    sibling: Option<Handle>,
+    // parent for a leftmost child, left sibling otherwise; None for a root
+    prev: Option<Handle>,
+}
+
+struct HeapEntry {
+    // union-find over heap ids; `root` and `len` are only meaningful at a representative
+    parent: usize,
+    ids: usize,
+    root: Option<Handle>,
+    len: usize,
+}
+
+/// Identifies one heap of a `PairingForest`. After a meld both ids name the combined heap.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct HeapId(usize);
+
+/// Any number of pairing heaps sharing one arena, so that melding two of them only links their
+/// roots. Every item records the heap it was pushed into, and melds are tracked by a union-find
+/// over heap ids, which is how `decrease_key` and `remove` find the root of an item's current heap
+/// without being told. Handles come from a generational `Slab`, so a handle whose item has been
+/// popped or removed is rejected even after its slot is reused.
+/// - new_heap / push / peek / meld: O(1) (union-find lookups add an inverse-Ackermann factor)
+/// - pop / remove: O(log n) amortized
+/// - decrease_key: o(log n) amortized (O(1) in practice)
+pub struct PairingForest<T> {
+    nodes: Slab<Node<T>>,
+    heaps: Vec<HeapEntry>,
+}
+
+impl<T: Ord> Default for PairingForest<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PairingForest<T> {
+    pub fn new() -> Self {
+        PairingForest { nodes: Slab::new(), heaps: Vec::new() }
+    }
+
+    /// Adds an empty heap.
+    pub fn new_heap(&mut self) -> HeapId {
+        let id = self.heaps.len();
+        self.heaps.push(HeapEntry { parent: id, ids: 1, root: None, len: 0 });
+        HeapId(id)
+    }
+
+    /// Representative of `id`'s heap. Union by size keeps the chains O(log k) long for k heaps.
+    fn find(&self, mut id: usize) -> usize {
+        while self.heaps[id].parent != id {
+            id = self.heaps[id].parent;
+        }
+        id
+    }
+
+    /// `find` with path halving, for the mutating operations.
+    fn find_mut(&mut self, mut id: usize) -> usize {
+        while self.heaps[id].parent != id {
+            let grandparent = self.heaps[self.heaps[id].parent].parent;
+            self.heaps[id].parent = grandparent;
+            id = grandparent;
+        }
+        id
+    }
+
+    /// Number of items in `heap`. Panics if `heap` is from another forest.
+    pub fn len(&self, heap: HeapId) -> usize {
+        self.heaps[self.find(heap.0)].len
+    }
+
+    pub fn is_empty(&self, heap: HeapId) -> bool {
+        self.len(heap) == 0
+    }
+
+    /// Whether `a` and `b` name the same heap, i.e. have been melded together.
+    pub fn same_heap(&self, a: HeapId, b: HeapId) -> bool {
+        self.find(a.0) == self.find(b.0)
+    }
+
+    /// The heap currently holding the item at `h`, or None if `h` is not a live handle.
+    pub fn heap_of(&self, h: Handle) -> Option<HeapId> {
+        Some(HeapId(self.find(self.nodes.get(h)?.home)))
+    }
+
+    /// Links two detached roots; the larger one becomes the leftmost child of the smaller.
+    fn link(&mut self, a: Handle, b: Handle) -> Handle {
+        let (parent, child) = if self.nodes[b].value < self.nodes[a].value { (b, a) } else { (a, b) };
+        let first = self.nodes[parent].child;
+        self.nodes[child].sibling = first;
+        self.nodes[child].prev = Some(parent);
+        if let Some(f) = first {
+            self.nodes[f].prev = Some(child);
+        }
+        self.nodes[parent].child = Some(child);
+        parent
+    }
+
+    /// Links the detached subtree `h` to the root of the heap with representative `rep`.
+    fn link_root(&mut self, rep: usize, h: Handle) {
+        self.heaps[rep].root = Some(match self.heaps[rep].root {
+            Some(r) => self.link(r, h),
+            None => h,
+        });
+    }
+
+    /// Detaches the subtree rooted at the non-root node `h` from its parent and siblings.
+    fn cut(&mut self, h: Handle) {
+        let prev = self.nodes[h].prev.expect("non-root node has a prev");
+        let next = self.nodes[h].sibling;
+        if self.nodes[prev].child == Some(h) {
+            self.nodes[prev].child = next;
+        } else {
+            self.nodes[prev].sibling = next;
+        }
+        if let Some(n) = next {
+            self.nodes[n].prev = Some(prev);
+        }
+        self.nodes[h].prev = None;
+        self.nodes[h].sibling = None;
+    }
+
+    /// Two-pass pairing of the children of `h`: link them in pairs left to right, then fold the
+    /// pairs right to left. Returns the new root of the combined subtree.
+    fn merge_children(&mut self, h: Handle) -> Option<Handle> {
+        let mut roots = Vec::new();
+        let mut cur = self.nodes[h].child.take();
+        while let Some(a) = cur {
+            let b = self.nodes[a].sibling;
+            cur = b.and_then(|b| self.nodes[b].sibling);
+            for x in [Some(a), b].into_iter().flatten() {
+                self.nodes[x].sibling = None;
+                self.nodes[x].prev = None;
+            }
+            roots.push(match b {
+                Some(b) => self.link(a, b),
+                None => a,
+            });
+        }
+        roots.into_iter().rev().reduce(|acc, r| self.link(r, acc))
+    }
+
+    /// Inserts `value` into `heap` and returns its handle. Panics if `heap` is from another forest.
+    pub fn push(&mut self, heap: HeapId, value: T) -> Handle {
+        let rep = self.find_mut(heap.0);
+        let h = self.nodes.insert(Node { value, home: rep, child: None, sibling: None, prev: None });
+        self.link_root(rep, h);
+        self.heaps[rep].len += 1;
+        h
+    }
+
+    pub fn peek(&self, heap: HeapId) -> Option<&T> {
+        self.peek_handle(heap).map(|r| &self.nodes[r].value)
+    }
+
+    /// Handle of the smallest item of `heap`.
+    pub fn peek_handle(&self, heap: HeapId) -> Option<Handle> {
+        self.heaps[self.find(heap.0)].root
+    }
+
+    /// Removes and returns the smallest item of `heap`.
+    pub fn pop(&mut self, heap: HeapId) -> Option<T> {
+        let rep = self.find_mut(heap.0);
+        let r = self.heaps[rep].root?;
+        self.heaps[rep].root = self.merge_children(r);
+        self.heaps[rep].len -= 1;
+        self.nodes.remove(r).map(|n| n.value)
+    }
+
+    pub fn get(&self, h: Handle) -> Option<&T> {
+        self.nodes.get(h).map(|n| &n.value)
+    }
+
+    /// Lowers the item at `h` to `value`. Returns false (leaving the forest unchanged) if `h` is not
+    /// a live handle or `value` is greater than the current item.
+    pub fn decrease_key(&mut self, h: Handle, value: T) -> bool {
+        let Some(node) = self.nodes.get_mut(h) else { return false };
+        if value > node.value {
+            return false;
+        }
+        node.value = value;
+        let home = node.home;
+        let rep = self.find_mut(home);
+        if self.heaps[rep].root != Some(h) {
+            self.cut(h);
+            self.link_root(rep, h);
+        }
+        true
+    }
+
+    /// Removes the item at `h` and returns it, or None if `h` is not a live handle.
+    pub fn remove(&mut self, h: Handle) -> Option<T> {
+        let rep = self.find_mut(self.nodes.get(h)?.home);
+        if self.heaps[rep].root == Some(h) {
+            return self.pop(HeapId(rep));
+        }
+        self.cut(h);
+        if let Some(sub) = self.merge_children(h) {
+            self.link_root(rep, sub);
+        }
+        self.heaps[rep].len -= 1;
+        self.nodes.remove(h).map(|n| n.value)
+    }
+
+    /// Melds `b` into `a` by linking their roots; afterwards both ids name the combined heap, and
+    /// every handle keeps addressing its item. Melding a heap with itself does nothing.
+    pub fn meld(&mut self, a: HeapId, b: HeapId) {
+        let (ra, rb) = (self.find_mut(a.0), self.find_mut(b.0));
+        if ra == rb {
+            return;
+        }
+        let (big, small) = if self.heaps[ra].ids >= self.heaps[rb].ids { (ra, rb) } else { (rb, ra) };
+        let roots = (self.heaps[ra].root.take(), self.heaps[rb].root.take());
+        self.heaps[small].parent = big;
+        self.heaps[big].ids += self.heaps[small].ids;
+        self.heaps[big].len += self.heaps[small].len;
+        self.heaps[big].root = match roots {
+            (Some(x), Some(y)) => Some(self.link(x, y)),
+            (x, y) => x.or(y),
+        };
+    }
+}
+
+/// Pairing heap: a min-heap ordered multiway tree whose roots are linked in O(1), so melding two
+/// heaps is cheap and decrease-key is just "cut the subtree and link it to the root". In practice it
+/// usually beats a Fibonacci heap on the same workloads thanks to its much smaller constant factors.
+/// This is a single heap owning its arena (a one-heap `PairingForest`); `push` returns a handle for
+/// `decrease_key` / `remove` that is rejected once its item leaves the heap. Two separate heaps do
+/// not share an arena, so `meld` has to move the other heap's items over; heaps that are melded
+/// together repeatedly belong in one `PairingForest`, where `meld` only links two roots.
+/// - push / peek: O(1)
+/// - pop / remove: O(log n) amortized
+/// - decrease_key: o(log n) amortized (O(1) in practice)
+/// - meld: O(m) for another heap of m items; O(1) within a `PairingForest`
+pub struct PairingHeap<T> {
+    forest: PairingForest<T>,
+    heap: HeapId,
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        let mut forest = PairingForest::new();
+        let heap = forest.new_heap();
+        PairingHeap { forest, heap }
+    }
+
+    pub fn len(&self) -> usize {
+        self.forest.len(self.heap)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts `value` and returns its handle.
+    pub fn push(&mut self, value: T) -> Handle {
+        self.forest.push(self.heap, value)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.forest.peek(self.heap)
+    }
+
+    /// Handle of the smallest item.
+    pub fn peek_handle(&self) -> Option<Handle> {
+        self.forest.peek_handle(self.heap)
+    }
+
+    /// Removes and returns the smallest item.
+    pub fn pop(&mut self) -> Option<T> {
+        self.forest.pop(self.heap)
+    }
+
+    pub fn get(&self, h: Handle) -> Option<&T> {
+        self.forest.get(h)
+    }
+
+    /// Lowers the item at `h` to `value`. Returns false (leaving the heap unchanged) if `h` is not a
+    /// live handle or `value` is greater than the current item.
+    pub fn decrease_key(&mut self, h: Handle, value: T) -> bool {
+        self.forest.decrease_key(h, value)
+    }
+
+    /// Removes the item at `h` and returns it, or None if `h` is not a live handle.
+    pub fn remove(&mut self, h: Handle) -> Option<T> {
+        self.forest.remove(h)
+    }
+
+    /// Moves every item of `other` into `self`, one O(1) push each. `other`'s handles do not carry
+    /// over; use a `PairingForest` to meld heaps in O(1) and keep their handles.
+    pub fn meld(&mut self, other: PairingHeap<T>) {
+        for node in other.forest.nodes.into_values() {
+            self.push(node.value);
+        }
+    }
+
+    /// Removes every item in ascending order.
+    pub fn drain_sorted(&mut self) -> Vec<T> {
+        std::iter::from_fn(|| self.pop()).collect()
+    }
+}
+
+impl<T: Ord> FromIterator<T> for PairingHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut heap = PairingHeap::new();
+        iter.into_iter().for_each(|v| {
+            heap.push(v);
+        });
+        heap
+    }
+}
+
+impl<T: Ord> Heap for PairingHeap<T> {
+    type Item = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn push(&mut self, item: T) {
+        self.push(item);
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+impl<T: Ord> MergeableHeap for PairingHeap<T> {
+    fn meld(&mut self, other: Self) {
+        self.meld(other);
+    }
+}
+
+impl<T: Ord> AddressableHeap for PairingHeap<T> {
+    type Handle = Handle;
+
+    fn push_handle(&mut self, item: T) -> Handle {
+        self.push(item)
+    }
+
+    fn decrease_key(&mut self, handle: Handle, item: T) -> bool {
+        self.decrease_key(handle, item)
+    }
+}
+
+impl<T: Ord> DataStructure for PairingHeap<T> {
+    const INFO: Info = Info {
+        name: "PairingHeap",
+        category: "heaps",
+        summary: "Min-heap with cheap linking and handle-based decrease-key, a practical Fibonacci heap alternative",
+        space: "O(n)",
+        operations: &[
+            Operation::new("push", "O(1)", "O(1)"),
+            Operation::new("peek", "O(1)", "O(1)"),
+            Operation::new("pop", "O(log n) amortized", "O(log n)"),
+            Operation::new("decrease_key", "o(log n) amortized", "O(1)"),
+            Operation::new("remove", "O(log n) amortized", "O(log n)"),
+            Operation::new("meld", "O(m) to move the other heap's items", "O(1)"),
+        ],
+        stable: Some(false),
+        in_place: None,
+    };
+}
+
+impl<T: Ord> DataStructure for PairingForest<T> {
+    const INFO: Info = Info {
+        name: "PairingForest",
+        category: "heaps",
+        summary: "Pairing heaps sharing one arena, with O(1) meld that keeps every handle valid",
+        space: "O(n + heaps)",
+        operations: &[
+            Operation::new("push", "O(1)", "O(1)"),
+            Operation::new("pop", "O(log n) amortized", "O(log n)"),
+            Operation::new("decrease_key", "o(log n) amortized", "O(1)"),
+            Operation::new("meld", "O(1)", "O(1)"),
+        ],
+        stable: Some(false),
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_push_pop_sorted() {
+        let values = [7, 3, 9, 1, 4, 1, 8, 2, 6, 5, 0];
+        let mut heap: PairingHeap<i32> = values.into_iter().collect();
+        assert_eq!(heap.len(), 11);
+        assert_eq!(heap.peek(), Some(&0));
+        let mut expected = values.to_vec();
+        expected.sort();
+        assert_eq!(heap.drain_sorted(), expected);
+        assert!(heap.is_empty());
+        assert_eq!(heap.pop(), None);
+    }
+
+    #[test]
+    fn test_decrease_key_and_remove() {
+        let mut heap = PairingHeap::new();
+        let handles: Vec<Handle> = (10..20).map(|v| heap.push(v)).collect();
+        assert!(heap.decrease_key(handles[7], 5));
+        assert_eq!(heap.peek(), Some(&5));
+        assert_eq!(heap.peek_handle(), Some(handles[7]));
+        assert!(!heap.decrease_key(handles[3], 100));
+        assert_eq!(heap.remove(handles[4]), Some(14));
+        assert_eq!(heap.remove(handles[4]), None);
+        assert!(!heap.decrease_key(handles[4], 0));
+
+        // the freed slot goes to the next push, but the removed item's handle does not reach it
+        let reused = heap.push(30);
+        assert_eq!(reused.index(), handles[4].index());
+        assert!(!heap.decrease_key(handles[4], 0) && heap.remove(handles[4]).is_none());
+        assert_eq!(heap.get(reused), Some(&30));
+        assert_eq!(heap.get(handles[9]), Some(&19));
+        assert_eq!(heap.drain_sorted(), vec![5, 10, 11, 12, 13, 15, 16, 18, 19, 30]);
+    }
+
+    #[test]
+    fn test_forest_meld_keeps_handles() {
+        let mut forest = PairingForest::new();
+        let (a, b, c) = (forest.new_heap(), forest.new_heap(), forest.new_heap());
+        let ha: Vec<Handle> = [8, 4, 6].into_iter().map(|v| forest.push(a, v)).collect();
+        let hb: Vec<Handle> = [7, 5, 9].into_iter().map(|v| forest.push(b, v)).collect();
+        forest.push(c, 0);
+        assert_eq!(forest.pop(b), Some(5));
+
+        forest.meld(a, b);
+        assert!(forest.same_heap(a, b) && !forest.same_heap(a, c));
+        assert_eq!((forest.len(a), forest.len(b), forest.peek(b)), (5, 5, Some(&4)));
+        assert!(forest.decrease_key(hb[2], 1));
+        assert_eq!(forest.heap_of(hb[2]), forest.heap_of(ha[0]));
+        assert_eq!(forest.pop(a), Some(1));
+        assert_eq!(forest.remove(ha[2]), Some(6));
+        assert!(forest.get(hb[1]).is_none() && !forest.decrease_key(hb[2], 0));
+
+        // either id pushes into the combined heap; melding it again is a no-op
+        forest.push(b, 3);
+        forest.meld(b, a);
+        forest.meld(c, a);
+        assert_eq!(forest.len(c), 5);
+        assert_eq!(std::iter::from_fn(|| forest.pop(a)).collect::<Vec<_>>(), vec![0, 3, 4, 7, 8]);
+        assert!(forest.is_empty(b) && forest.heap_of(ha[0]).is_none());
+
+        let mut x: PairingHeap<i32> = [8, 4].into_iter().collect();
+        x.meld([7, 1].into_iter().collect());
+        assert_eq!(x.drain_sorted(), vec![1, 4, 7, 8]);
+    }
+
+    #[test]
+    fn test_against_sorted_reference() {
+        let mut next = test_rng(42);
+        let mut heap = PairingHeap::new();
+        let mut live: Vec<(Handle, i64)> = Vec::new();
+        for step in 0..2000 {
+            match step % 4 {
+                0 | 1 => {
+                    let v = (next() % 1000) as i64;
+                    live.push((heap.push(v), v));
+                }
+                2 if !live.is_empty() => {
+                    let i = next() as usize % live.len();
+                    let v = live[i].1 - (next() % 50) as i64;
+                    assert!(heap.decrease_key(live[i].0, v));
+                    live[i].1 = v;
+                }
+                _ => {
+                    let min = live.iter().map(|&(_, v)| v).min();
+                    if let Some(h) = heap.peek_handle() {
+                        live.retain(|&(lh, _)| lh != h);
+                    }
+                    assert_eq!(heap.pop(), min);
+                }
+            }
+            assert_eq!(heap.len(), live.len());
+        }
+
+        // random melds across a forest, against one sorted list per group of melded heaps
+        let mut forest = PairingForest::new();
+        let ids: Vec<HeapId> = (0..8).map(|_| forest.new_heap()).collect();
+        let mut group: Vec<usize> = (0..8).collect();
+        let mut items: Vec<(Handle, usize, i64)> = Vec::new();
+        for _ in 0..2000 {
+            let (i, j) = (next() as usize % 8, next() as usize % 8);
+            match next() % 5 {
+                0 | 1 => {
+                    let v = (next() % 1000) as i64;
+                    items.push((forest.push(ids[i], v), group[i], v));
+                }
+                2 => {
+                    forest.meld(ids[i], ids[j]);
+                    let (gi, gj) = (group[i], group[j]);
+                    group.iter_mut().filter(|g| **g == gj).for_each(|g| *g = gi);
+                    items.iter_mut().filter(|it| it.1 == gj).for_each(|it| it.1 = gi);
+                }
+                3 if !items.is_empty() => {
+                    let k = next() as usize % items.len();
+                    let v = items[k].2 - (next() % 50) as i64;
+                    assert!(forest.decrease_key(items[k].0, v));
+                    items[k].2 = v;
+                }
+                _ => {
+                    let min = items.iter().filter(|it| it.1 == group[i]).map(|it| it.2).min();
+                    assert_eq!(forest.peek(ids[i]).copied(), min);
+                    if let Some(h) = forest.peek_handle(ids[i]) {
+                        items.retain(|it| it.0 != h);
+                    }
+                    assert_eq!(forest.pop(ids[i]), min);
+                }
+            }
+            assert_eq!(forest.len(ids[i]), items.iter().filter(|it| it.1 == group[i]).count());
+        }
+    }
+}
@@ -0,0 +1,245 @@
+// rust/src/persistent_array.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::fmt;
+use std::rc::Rc;
+
+const BITS: usize = 5;
+const FANOUT: usize = 1 << BITS;
+const MASK: usize = FANOUT - 1;
+
+#[derive(Clone)]
+enum Node<T> {
+    Branch(Vec<Rc<Node<T>>>),
+    // up to FANOUT values
+    Leaf(Vec<T>),
+}
+
+/// Persistent array: a fixed-length array where `set` returns a new version and leaves the old one
+/// intact. Elements sit in the leaves of a 32-way trie indexed by the bits of the position, five
+/// at a time from the top; nodes are immutable and reference counted, so a `set` copies the one
+/// path it changes (a leaf and a spine of at most log32 n branches) and shares everything else.
+/// `clone` is an O(1) snapshot. `set_mut` updates in place wherever the path is not shared, which
+/// makes building or editing one version cheap. The building block for persistent union-find and
+/// other structures whose old versions must stay queryable.
+/// - get: O(log32 n)
+/// - set / set_mut: O(32 log32 n), allocating O(log32 n) nodes (none for unshared paths)
+/// - clone: O(1)
+pub struct PersistentArray<T> {
+    root: Option<Rc<Node<T>>>,
+    len: usize,
+    // bit offset of the root's child index; 0 when the root is a leaf
+    shift: usize,
+}
+
+impl<T> Clone for PersistentArray<T> {
+    fn clone(&self) -> Self {
+        PersistentArray { root: self.root.clone(), len: self.len, shift: self.shift }
+    }
+}
+
+impl<T: Clone> PersistentArray<T> {
+    /// An array of `len` copies of `value`.
+    pub fn new(len: usize, value: T) -> Self {
+        Self::from_vec(vec![value; len])
+    }
+
+    pub fn from_vec(values: Vec<T>) -> Self {
+        let len = values.len();
+        let mut values = values.into_iter();
+        let mut level: Vec<Rc<Node<T>>> = Vec::with_capacity(len.div_ceil(FANOUT));
+        while values.len() > 0 {
+            level.push(Rc::new(Node::Leaf(values.by_ref().take(FANOUT).collect())));
+        }
+        let mut shift = 0;
+        while level.len() > 1 {
+            let mut children = level.into_iter();
+            level = Vec::with_capacity(children.len().div_ceil(FANOUT));
+            while children.len() > 0 {
+                level.push(Rc::new(Node::Branch(children.by_ref().take(FANOUT).collect())));
+            }
+            shift += BITS;
+        }
+        PersistentArray { root: level.pop(), len, shift }
+    }
+
+    /// A new version with position `i` set to `value`. Panics if `i` is out of bounds.
+    pub fn set(&self, i: usize, value: T) -> Self {
+        let mut next = self.clone();
+        next.set_mut(i, value);
+        next
+    }
+
+    /// Sets position `i` of this version, copying only the nodes other versions share. Panics if
+    /// `i` is out of bounds.
+    pub fn set_mut(&mut self, i: usize, value: T) {
+        assert!(i < self.len, "index {} out of bounds for length {}", i, self.len);
+        let mut node = Rc::make_mut(self.root.as_mut().expect("a nonempty array has a root"));
+        let mut shift = self.shift;
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    node = Rc::make_mut(&mut children[(i >> shift) & MASK]);
+                    shift -= BITS;
+                }
+                Node::Leaf(values) => {
+                    values[i & MASK] = value;
+                    return;
+                }
+            }
+        }
+    }
+
+    /// A new version with `f` applied to position `i`. Panics if `i` is out of bounds.
+    pub fn update(&self, i: usize, f: impl FnOnce(&T) -> T) -> Self {
+        assert!(i < self.len, "index {} out of bounds for length {}", i, self.len);
+        self.set(i, f(&self[i]))
+    }
+
+    pub fn to_vec(&self) -> Vec<T> {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T> PersistentArray<T> {
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn get(&self, i: usize) -> Option<&T> {
+        if i >= self.len {
+            return None;
+        }
+        let mut node = self.root.as_deref()?;
+        let mut shift = self.shift;
+        loop {
+            match node {
+                Node::Branch(children) => {
+                    node = &children[(i >> shift) & MASK];
+                    shift -= BITS;
+                }
+                Node::Leaf(values) => return values.get(i & MASK),
+            }
+        }
+    }
+
+    /// The elements in order, walking the leaves left to right.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let mut stack: Vec<&Node<T>> = self.root.as_deref().into_iter().collect();
+        std::iter::from_fn(move || {
+            while let Some(node) = stack.pop() {
+                match node {
+                    Node::Branch(children) => stack.extend(children.iter().rev().map(|c| &**c)),
+                    Node::Leaf(values) => return Some(values.iter()),
+                }
+            }
+            None
+        })
+        .flatten()
+    }
+}
+
+impl<T> std::ops::Index<usize> for PersistentArray<T> {
+    type Output = T;
+
+    fn index(&self, i: usize) -> &T {
+        match self.get(i) {
+            Some(value) => value,
+            None => panic!("index {} out of bounds for length {}", i, self.len),
+        }
+    }
+}
+
+impl<T: Clone> FromIterator<T> for PersistentArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for PersistentArray<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl<T> DataStructure for PersistentArray<T> {
+    const INFO: Info = Info {
+        name: "PersistentArray",
+        category: "persistent",
+        summary: "Fixed-length array with versioned updates by path copying in a 32-way trie",
+        space: "O(n), plus O(log n) per retained version",
+        operations: &[
+            Operation::new("get", "O(log32 n)", "O(1)"),
+            Operation::new("set", "O(log32 n)", "O(log32 n)"),
+            Operation::new("clone", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_versions_and_union_find() {
+        let v0 = PersistentArray::new(3, 'a');
+        let v1 = v0.set(1, 'b');
+        let v2 = v1.update(2, |c| c.to_ascii_uppercase());
+        assert_eq!((v0.to_vec(), v1.to_vec(), v2.to_vec()), (vec!['a'; 3], vec!['a', 'b', 'a'], vec!['a', 'b', 'A']));
+        assert_eq!((v2.get(3), v2[1]), (None, 'b'));
+        assert!(PersistentArray::<u8>::from_vec(Vec::new()).iter().next().is_none());
+
+        // persistent union-find without path compression, keeping every version
+        fn find(parent: &PersistentArray<usize>, mut x: usize) -> usize {
+            while parent[x] != x {
+                x = parent[x];
+            }
+            x
+        }
+        let mut versions = vec![(0..8).collect::<PersistentArray<usize>>()];
+        for (a, b) in [(0, 1), (2, 3), (1, 3), (5, 6)] {
+            let parent = versions.last().unwrap();
+            let (ra, rb) = (find(parent, a), find(parent, b));
+            versions.push(parent.set(ra, rb));
+        }
+        assert_ne!(find(&versions[2], 0), find(&versions[2], 3));
+        assert_eq!(find(&versions[3], 0), find(&versions[3], 3));
+        assert_eq!(find(&versions[0], 5), 5);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(127);
+        for n in [1, 31, 32, 33, 1024, 1025, 5000] {
+            let mut versions: Vec<(PersistentArray<usize>, Vec<usize>)> = vec![((0..n).collect(), (0..n).collect())];
+            for _ in 0..300 {
+                let base = next() as usize % versions.len();
+                let (i, value) = (next() as usize % n, next() as usize);
+                let (array, naive) = &versions[base];
+                let mut naive = naive.clone();
+                naive[i] = value;
+                let array = if next().is_multiple_of(2) {
+                    array.set(i, value)
+                } else {
+                    let mut copy = array.clone();
+                    copy.set_mut(i, value);
+                    copy
+                };
+                versions.push((array, naive));
+            }
+            for (array, naive) in &versions {
+                assert_eq!(array.len(), n);
+                assert_eq!(&array.to_vec(), naive);
+                let i = next() as usize % n;
+                assert_eq!(array.get(i), Some(&naive[i]));
+            }
+        }
+    }
+}
@@ -0,0 +1,314 @@
+// rust/src/quadtree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Subdivision stops at this depth, so that many points at one spot cannot recurse forever.
+const MAX_DEPTH: usize = 24;
+
+/// An axis-aligned rectangle `min..=max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub min: (f64, f64),
+    pub max: (f64, f64),
+}
+
+impl Rect {
+    pub fn new(min: (f64, f64), max: (f64, f64)) -> Self {
+        Rect { min, max }
+    }
+
+    pub fn contains(&self, p: (f64, f64)) -> bool {
+        self.min.0 <= p.0 && p.0 <= self.max.0 && self.min.1 <= p.1 && p.1 <= self.max.1
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.0 <= other.max.0 && other.min.0 <= self.max.0 && self.min.1 <= other.max.1 && other.min.1 <= self.max.1
+    }
+
+    /// Squared distance from `p` to the closest point of the rectangle (0 inside).
+    fn dist2(&self, p: (f64, f64)) -> f64 {
+        let dx = (self.min.0 - p.0).max(p.0 - self.max.0).max(0.0);
+        let dy = (self.min.1 - p.1).max(p.1 - self.max.1).max(0.0);
+        dx * dx + dy * dy
+    }
+
+    fn center(&self) -> (f64, f64) {
+        ((self.min.0 + self.max.0) / 2.0, (self.min.1 + self.max.1) / 2.0)
+    }
+
+    /// Quadrant `q` (bit 0: east, bit 1: north).
+    fn quadrant(&self, q: usize) -> Rect {
+        let c = self.center();
+        let (x0, x1) = if q & 1 == 0 { (self.min.0, c.0) } else { (c.0, self.max.0) };
+        let (y0, y1) = if q & 2 == 0 { (self.min.1, c.1) } else { (c.1, self.max.1) };
+        Rect::new((x0, y0), (x1, y1))
+    }
+}
+
+struct Node {
+    bounds: Rect,
+    depth: usize,
+    // index of the first of four consecutive children, once split
+    children: Option<usize>,
+    ids: Vec<usize>,
+}
+
+/// A key with a float priority, for the best-first search heaps.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct ByDist(f64, usize);
+
+impl Eq for ByDist {}
+
+impl Ord for ByDist {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+impl PartialOrd for ByDist {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Point region quadtree over a fixed bounding rectangle. Each leaf holds up to `capacity`
+/// points; one more splits it into four equal quadrants (the point on a dividing line goes
+/// east / north). Unlike a uniform grid it adapts to clustered data, which is what games and
+/// particle simulations tend to produce. Points get sequential ids; removing one does not merge
+/// leaves back, so a tree that shrinks a lot is best rebuilt.
+/// - insert / remove: O(depth) (depth is O(log n) for spread-out points)
+/// - query: O(depth + nodes overlapping the rectangle + k)
+/// - nearest: best-first over the nodes; `nearest_approx` stops once no node can be closer than
+///   the best found by a factor of `1 + eps`, for cheaper answers at most that far off.
+pub struct Quadtree {
+    nodes: Vec<Node>,
+    points: Vec<Option<(f64, f64)>>,
+    capacity: usize,
+    len: usize,
+}
+
+impl Quadtree {
+    /// An empty tree over `bounds` with leaves of up to 8 points.
+    pub fn new(bounds: Rect) -> Self {
+        Self::with_capacity(bounds, 8)
+    }
+
+    /// Panics if `capacity` is 0 or `bounds` is empty or not finite.
+    pub fn with_capacity(bounds: Rect, capacity: usize) -> Self {
+        assert!(capacity > 0, "leaf capacity must be positive");
+        let finite = [bounds.min.0, bounds.min.1, bounds.max.0, bounds.max.1].iter().all(|x| x.is_finite());
+        assert!(finite && bounds.min.0 <= bounds.max.0 && bounds.min.1 <= bounds.max.1, "invalid bounds {:?}", bounds);
+        Quadtree {
+            nodes: vec![Node { bounds, depth: 0, children: None, ids: Vec::new() }],
+            points: Vec::new(),
+            capacity,
+            len: 0,
+        }
+    }
+
+    pub fn bounds(&self) -> Rect {
+        self.nodes[0].bounds
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the coordinates of a live point.
+    pub fn get(&self, id: usize) -> Option<(f64, f64)> {
+        self.points.get(id).copied().flatten()
+    }
+
+    /// The leaf whose quadrant holds `p`.
+    fn leaf(&self, p: (f64, f64)) -> usize {
+        let mut n = 0;
+        while let Some(first) = self.nodes[n].children {
+            let c = self.nodes[n].bounds.center();
+            n = first + (p.0 >= c.0) as usize + 2 * (p.1 >= c.1) as usize;
+        }
+        n
+    }
+
+    /// Inserts a point and returns its id, or None if it lies outside the bounds. Ids are never reused.
+    pub fn insert(&mut self, p: (f64, f64)) -> Option<usize> {
+        if !self.bounds().contains(p) {
+            return None;
+        }
+        let id = self.points.len();
+        self.points.push(Some(p));
+        self.len += 1;
+        let mut n = self.leaf(p);
+        self.nodes[n].ids.push(id);
+        while self.nodes[n].ids.len() > self.capacity && self.nodes[n].depth < MAX_DEPTH {
+            self.split(n);
+            // everything may have landed in one quadrant; keep splitting that one
+            n = self.leaf(p);
+        }
+        Some(id)
+    }
+
+    fn split(&mut self, n: usize) {
+        let first = self.nodes.len();
+        let (bounds, depth) = (self.nodes[n].bounds, self.nodes[n].depth);
+        for q in 0..4 {
+            self.nodes.push(Node { bounds: bounds.quadrant(q), depth: depth + 1, children: None, ids: Vec::new() });
+        }
+        self.nodes[n].children = Some(first);
+        for id in std::mem::take(&mut self.nodes[n].ids) {
+            let leaf = self.leaf(self.points[id].expect("stored ids are live"));
+            self.nodes[leaf].ids.push(id);
+        }
+    }
+
+    /// Removes the point with the given id. Returns false if the id is unknown or already removed.
+    pub fn remove(&mut self, id: usize) -> bool {
+        let Some(p) = self.points.get_mut(id).and_then(|p| p.take()) else { return false };
+        let leaf = self.leaf(p);
+        let ids = &mut self.nodes[leaf].ids;
+        let pos = ids.iter().position(|&x| x == id).expect("a point lives in the leaf holding it");
+        ids.swap_remove(pos);
+        self.len -= 1;
+        true
+    }
+
+    /// Ids of the points inside `rect` (bounds included), ascending.
+    pub fn query(&self, rect: Rect) -> Vec<usize> {
+        let mut out = Vec::new();
+        let mut stack = vec![0];
+        while let Some(n) = stack.pop() {
+            let node = &self.nodes[n];
+            if !node.bounds.intersects(&rect) {
+                continue;
+            }
+            match node.children {
+                Some(first) => stack.extend(first..first + 4),
+                None => out.extend(node.ids.iter().filter(|&&id| rect.contains(self.points[id].expect("stored ids are live")))),
+            }
+        }
+        out.sort_unstable();
+        out
+    }
+
+    /// Id and distance of the point closest to `p`, ties going to the smaller id.
+    pub fn nearest(&self, p: (f64, f64)) -> Option<(usize, f64)> {
+        self.nearest_approx(p, 0.0)
+    }
+
+    /// A point whose distance to `p` is at most `1 + eps` times the nearest one's, with its id.
+    /// Panics if `eps` is negative.
+    pub fn nearest_approx(&self, p: (f64, f64), eps: f64) -> Option<(usize, f64)> {
+        assert!(eps >= 0.0, "eps must be non-negative");
+        let slack = (1.0 + eps) * (1.0 + eps);
+        let mut best: Option<ByDist> = None;
+        // nodes by their distance from p, nearest first
+        let mut frontier = BinaryHeap::from([Reverse(ByDist(self.nodes[0].bounds.dist2(p), 0))]);
+        while let Some(Reverse(ByDist(d2, n))) = frontier.pop() {
+            // nothing left can beat the best found by more than the allowed factor (equal distances
+            // are still explored, for the smaller id)
+            if best.is_some_and(|b| d2 * slack > b.0) {
+                break;
+            }
+            let node = &self.nodes[n];
+            match node.children {
+                Some(first) => frontier.extend((first..first + 4).map(|c| Reverse(ByDist(self.nodes[c].bounds.dist2(p), c)))),
+                None => {
+                    for &id in &node.ids {
+                        let q = self.points[id].expect("stored ids are live");
+                        let cand = ByDist((q.0 - p.0).powi(2) + (q.1 - p.1).powi(2), id);
+                        if best.is_none_or(|b| cand < b) {
+                            best = Some(cand);
+                        }
+                    }
+                }
+            }
+        }
+        best.map(|ByDist(d2, id)| (id, d2.sqrt()))
+    }
+}
+
+impl DataStructure for Quadtree {
+    const INFO: Info = Info {
+        name: "Quadtree",
+        category: "spatial",
+        summary: "Point region quadtree with rectangle queries and exact or (1 + eps)-approximate nearest neighbour",
+        space: "O(n)",
+        operations: &[
+            Operation::new("insert", "O(depth)", "O(1)"),
+            Operation::new("remove", "O(depth)", "O(1)"),
+            Operation::new("query", "O(depth + nodes overlapped + k)", "O(depth)"),
+            Operation::new("nearest / nearest_approx", "O(depth) typical, O(n) worst", "O(nodes visited)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_insert_query_remove() {
+        let mut tree = Quadtree::with_capacity(Rect::new((0.0, 0.0), (10.0, 10.0)), 2);
+        let ids: Vec<usize> = [(1.0, 1.0), (9.0, 9.0), (5.0, 5.0), (2.0, 8.0), (1.5, 1.0), (1.0, 1.5)]
+            .into_iter()
+            .map(|p| tree.insert(p).expect("inside the bounds"))
+            .collect();
+        assert_eq!(tree.insert((10.5, 0.0)), None);
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.query(Rect::new((0.0, 0.0), (5.0, 5.0))), vec![ids[0], ids[2], ids[4], ids[5]]);
+        assert_eq!(tree.query(Rect::new((6.0, 0.0), (10.0, 4.0))), vec![]);
+        assert_eq!(tree.nearest((8.0, 7.0)), Some((ids[1], 5f64.sqrt())));
+        assert!(tree.remove(ids[1]) && !tree.remove(ids[1]) && !tree.remove(99));
+        assert_eq!(tree.nearest((8.0, 7.0)).map(|(id, _)| id), Some(ids[2]));
+        assert_eq!(tree.get(ids[1]), None);
+
+        // a pile of identical points stops splitting at the depth limit
+        let mut pile = Quadtree::with_capacity(Rect::new((0.0, 0.0), (1.0, 1.0)), 1);
+        (0..50).for_each(|_| {
+            pile.insert((0.25, 0.25));
+        });
+        assert_eq!((pile.query(Rect::new((0.2, 0.2), (0.3, 0.3))).len(), pile.nearest((1.0, 1.0)).map(|(id, _)| id)), (50, Some(0)));
+        assert_eq!(Quadtree::new(Rect::new((0.0, 0.0), (1.0, 1.0))).nearest((0.5, 0.5)), None);
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = test_rng(73);
+        let mut next = || (rng() >> 11) as f64 / (1u64 << 53) as f64 * 100.0;
+        let mut tree = Quadtree::new(Rect::new((0.0, 0.0), (100.0, 100.0)));
+        let mut pts = Vec::new();
+        // two dense clusters plus uniform noise
+        for i in 0..600 {
+            let p = match i % 3 {
+                0 => (next() / 50.0 + 10.0, next() / 50.0 + 10.0),
+                1 => (next() / 20.0 + 70.0, next() / 20.0 + 30.0),
+                _ => (next(), next()),
+            };
+            pts.push(p);
+            tree.insert(p);
+        }
+        for id in (0..600).step_by(7) {
+            tree.remove(id);
+        }
+        let live: Vec<usize> = (0..600).filter(|id| id % 7 != 0).collect();
+        let dist = |id: usize, p: (f64, f64)| ((pts[id].0 - p.0).powi(2) + (pts[id].1 - p.1).powi(2)).sqrt();
+        for _ in 0..200 {
+            let p = (next(), next());
+            let exact = live.iter().map(|&id| (dist(id, p), id)).min_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+            assert_eq!(tree.nearest(p), exact.map(|(d, id)| (id, d)));
+            let (_, approx) = tree.nearest_approx(p, 0.5).expect("tree is not empty");
+            assert!(approx <= 1.5 * exact.expect("tree is not empty").0 + 1e-9);
+            let (a, b) = ((next(), next()), (next(), next()));
+            let rect = Rect::new((a.0.min(b.0), a.1.min(b.1)), (a.0.max(b.0), a.1.max(b.1)));
+            assert_eq!(tree.query(rect), live.iter().copied().filter(|&id| rect.contains(pts[id])).collect::<Vec<_>>());
+        }
+    }
+}
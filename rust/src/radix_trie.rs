@@ -0,0 +1,439 @@
+// rust/src/radix_trie.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::collections::BTreeMap;
+use std::ops::Bound::{Excluded, Unbounded};
+
+struct Node<V> {
+    // bytes on the edge from the parent into this node (empty only for the root)
+    label: Vec<u8>,
+    // keyed by the first byte of the child's label
+    children: BTreeMap<u8, usize>,
+    value: Option<V>,
+    // number of keys stored in this subtree
+    count: usize,
+}
+
+impl<V> Node<V> {
+    fn new(label: Vec<u8>) -> Self {
+        Node {
+            label,
+            children: BTreeMap::new(),
+            value: None,
+            count: 0,
+        }
+    }
+}
+
+/// Where a key ends in the tree: after the first `matched` bytes of the edge label into `node`.
+/// The key ends exactly at `node` when the whole label is matched.
+struct Location {
+    node: usize,
+    matched: usize,
+}
+
+/// A path-compressed (radix) trie mapping string keys to values.
+/// Chains of single-child nodes are merged into one edge labelled with a byte string, so the tree has
+/// at most 2k nodes for k keys no matter how long the shared prefixes are. It offers the same API as
+/// `Trie` plus ordered neighbour queries (`successor` / `predecessor`).
+/// - insert / get / count_prefix: O(|key| + depth * log σ)
+/// - successor / predecessor: O(|key| + depth * log σ)
+///
+/// Nodes live in an arena (`Vec`) and are addressed by index.
+pub struct RadixTrie<V> {
+    nodes: Vec<Node<V>>,
+}
+
+impl<V> Default for RadixTrie<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> RadixTrie<V> {
+    pub fn new() -> Self {
+        RadixTrie {
+            nodes: vec![Node::new(Vec::new())],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of allocated nodes, including the root.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn locate(&self, key: &[u8]) -> Option<Location> {
+        let mut cur = 0;
+        let mut i = 0;
+        while i < key.len() {
+            let child = *self.nodes[cur].children.get(&key[i])?;
+            let label = &self.nodes[child].label;
+            let rest = &key[i..];
+            if rest.len() < label.len() {
+                return label.starts_with(rest).then_some(Location { node: child, matched: rest.len() });
+            }
+            if !rest.starts_with(label) {
+                return None;
+            }
+            i += label.len();
+            cur = child;
+        }
+        Some(Location {
+            node: cur,
+            matched: self.nodes[cur].label.len(),
+        })
+    }
+
+    /// The node `key` ends at exactly, if any.
+    fn exact_node(&self, key: &[u8]) -> Option<usize> {
+        let loc = self.locate(key)?;
+        (loc.matched == self.nodes[loc.node].label.len()).then_some(loc.node)
+    }
+
+    /// Inserts `key`, returning the previous value if the key was already present.
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        if let Some(old) = self.get_mut(key) {
+            return Some(std::mem::replace(old, value));
+        }
+        let key = key.as_bytes();
+        let mut cur = 0;
+        let mut i = 0;
+        self.nodes[0].count += 1;
+        while i < key.len() {
+            let Some(&child) = self.nodes[cur].children.get(&key[i]) else {
+                let mut leaf = Node::new(key[i..].to_vec());
+                leaf.value = Some(value);
+                leaf.count = 1;
+                self.nodes.push(leaf);
+                let leaf = self.nodes.len() - 1;
+                self.nodes[cur].children.insert(key[i], leaf);
+                return None;
+            };
+            let label = &self.nodes[child].label;
+            let common = label.iter().zip(&key[i..]).take_while(|(a, b)| a == b).count();
+            if common == label.len() {
+                self.nodes[child].count += 1;
+                cur = child;
+            } else {
+                // split the edge: cur -> mid (shared part) -> child (remainder)
+                let mut mid = Node::new(label[..common].to_vec());
+                let tail = self.nodes[child].label.split_off(common);
+                mid.children.insert(tail[0], child);
+                mid.count = self.nodes[child].count + 1;
+                self.nodes[child].label = tail;
+                self.nodes.push(mid);
+                let mid = self.nodes.len() - 1;
+                self.nodes[cur].children.insert(key[i], mid);
+                cur = mid;
+            }
+            i += common;
+        }
+        self.nodes[cur].value = Some(value);
+        None
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.nodes[self.exact_node(key.as_bytes())?].value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        let node = self.exact_node(key.as_bytes())?;
+        self.nodes[node].value.as_mut()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Number of stored keys starting with `prefix` (including `prefix` itself).
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        self.locate(prefix.as_bytes()).map_or(0, |loc| self.nodes[loc.node].count)
+    }
+
+    /// The longest stored key that is a prefix of `query`, returned as a slice of `query`.
+    pub fn longest_prefix_match<'q>(&self, query: &'q str) -> Option<(&'q str, &V)> {
+        let bytes = query.as_bytes();
+        let mut cur = 0;
+        let mut i = 0;
+        let mut best = self.nodes[0].value.as_ref().map(|v| (0, v));
+        while let Some(&child) = bytes.get(i).and_then(|b| self.nodes[cur].children.get(b)) {
+            let label = &self.nodes[child].label;
+            if !bytes[i..].starts_with(label) {
+                break;
+            }
+            i += label.len();
+            cur = child;
+            if let Some(v) = self.nodes[cur].value.as_ref() {
+                best = Some((i, v));
+            }
+        }
+        // stored keys are valid UTF-8, so a key that is a prefix of `query` ends on a char boundary
+        best.map(|(len, v)| (&query[..len], v))
+    }
+
+    /// Keys starting with `prefix` and their values, in sorted order.
+    pub fn iter_prefix(&self, prefix: &str) -> Iter<'_, V> {
+        let mut key = prefix.as_bytes().to_vec();
+        let stack = match self.locate(prefix.as_bytes()) {
+            Some(loc) => {
+                // complete the part of the edge label the prefix stopped in
+                key.extend_from_slice(&self.nodes[loc.node].label[loc.matched..]);
+                vec![(loc.node, key.len(), false)]
+            }
+            None => vec![],
+        };
+        Iter { trie: self, stack, key }
+    }
+
+    /// All entries in sorted key order.
+    pub fn iter(&self) -> Iter<'_, V> {
+        self.iter_prefix("")
+    }
+
+    /// Keys starting with `prefix`, in sorted order.
+    pub fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.iter_prefix(prefix).map(|(k, _)| k).collect()
+    }
+
+    /// Smallest key strictly greater than `key`.
+    pub fn successor(&self, key: &str) -> Option<(String, &V)> {
+        let target = key.as_bytes();
+        // best candidate so far: the minimum of a subtree hanging below target[..depth]
+        let mut candidate: Option<(usize, usize)> = None;
+        let mut cur = 0;
+        let mut i = 0;
+        loop {
+            if i == target.len() {
+                // every key below extends the target
+                if let Some(&first) = self.nodes[cur].children.values().next() {
+                    return Some(self.min_entry(target, first));
+                }
+                break;
+            }
+            let b = target[i];
+            if let Some((_, &c)) = self.nodes[cur].children.range((Excluded(b), Unbounded)).next() {
+                candidate = Some((c, i));
+            }
+            let Some(&child) = self.nodes[cur].children.get(&b) else {
+                break;
+            };
+            let label = &self.nodes[child].label;
+            let rest = &target[i..];
+            let common = label.iter().zip(rest).take_while(|(a, b)| a == b).count();
+            if common == label.len() {
+                i += common;
+                cur = child;
+                continue;
+            }
+            if common == rest.len() || label[common] > rest[common] {
+                // the whole subtree sorts after the target
+                return Some(self.min_entry(&target[..i], child));
+            }
+            break;
+        }
+        candidate.map(|(c, d)| self.min_entry(&target[..d], c))
+    }
+
+    /// Largest key strictly smaller than `key`.
+    pub fn predecessor(&self, key: &str) -> Option<(String, &V)> {
+        let target = key.as_bytes();
+        // best candidate so far: (node, depth, whole subtree?) - either the maximum of a subtree
+        // hanging below target[..depth], or the node whose own key is target[..depth]
+        let mut candidate: Option<(usize, usize, bool)> = None;
+        let mut cur = 0;
+        let mut i = 0;
+        while i < target.len() {
+            let b = target[i];
+            if let Some((_, &c)) = self.nodes[cur].children.range(..b).next_back() {
+                candidate = Some((c, i, true));
+            } else if self.nodes[cur].value.is_some() {
+                candidate = Some((cur, i, false));
+            }
+            let Some(&child) = self.nodes[cur].children.get(&b) else {
+                break;
+            };
+            let label = &self.nodes[child].label;
+            let rest = &target[i..];
+            let common = label.iter().zip(rest).take_while(|(a, b)| a == b).count();
+            if common == label.len() {
+                i += common;
+                cur = child;
+                continue;
+            }
+            if common < rest.len() && label[common] < rest[common] {
+                // the whole subtree sorts before the target
+                return Some(self.max_entry(&target[..i], child));
+            }
+            break;
+        }
+        candidate.map(|(node, depth, subtree)| {
+            if subtree {
+                self.max_entry(&target[..depth], node)
+            } else {
+                let key = String::from_utf8(target[..depth].to_vec()).expect("prefix of a stored key");
+                (key, self.nodes[node].value.as_ref().expect("candidate holds a value"))
+            }
+        })
+    }
+
+    /// Smallest entry in the subtree of `node`, whose parent's key is `prefix`.
+    fn min_entry(&self, prefix: &[u8], mut node: usize) -> (String, &V) {
+        let mut key = prefix.to_vec();
+        loop {
+            key.extend_from_slice(&self.nodes[node].label);
+            if let Some(v) = self.nodes[node].value.as_ref() {
+                return (String::from_utf8(key).expect("keys are inserted as str"), v);
+            }
+            node = *self.nodes[node].children.values().next().expect("valueless nodes have children");
+        }
+    }
+
+    /// Largest entry in the subtree of `node`, whose parent's key is `prefix`.
+    fn max_entry(&self, prefix: &[u8], mut node: usize) -> (String, &V) {
+        let mut key = prefix.to_vec();
+        loop {
+            key.extend_from_slice(&self.nodes[node].label);
+            match self.nodes[node].children.values().next_back() {
+                Some(&child) => node = child,
+                None => {
+                    let v = self.nodes[node].value.as_ref().expect("leaves hold values");
+                    return (String::from_utf8(key).expect("keys are inserted as str"), v);
+                }
+            }
+        }
+    }
+}
+
+/// Pre-order walk of a subtree, which visits keys in sorted order.
+pub struct Iter<'a, V> {
+    trie: &'a RadixTrie<V>,
+    // (node, key length before the node's label, whether to append the label)
+    stack: Vec<(usize, usize, bool)>,
+    key: Vec<u8>,
+}
+
+impl<'a, V> Iterator for Iter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, depth, append)) = self.stack.pop() {
+            let n = &self.trie.nodes[node];
+            self.key.truncate(depth);
+            if append {
+                self.key.extend_from_slice(&n.label);
+            }
+            let child_depth = self.key.len();
+            self.stack.extend(n.children.values().rev().map(|&c| (c, child_depth, true)));
+            if let Some(v) = n.value.as_ref() {
+                let key = String::from_utf8(self.key.clone()).expect("keys are inserted as str");
+                return Some((key, v));
+            }
+        }
+        None
+    }
+}
+
+impl<V> DataStructure for RadixTrie<V> {
+    const INFO: Info = Info {
+        name: "RadixTrie",
+        category: "strings",
+        summary: "Path-compressed trie with ordered successor and predecessor queries",
+        space: "O(k) nodes for k keys",
+        operations: &[
+            Operation::new("insert", "O(|key| + depth * log σ)", "O(|key|)"),
+            Operation::new("get", "O(|key| + depth * log σ)", "O(1)"),
+            Operation::new("count_prefix", "O(|key| + depth * log σ)", "O(1)"),
+            Operation::new("successor", "O(|key| + depth * log σ)", "O(|key|)"),
+            Operation::new("predecessor", "O(|key| + depth * log σ)", "O(|key|)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::trie::Trie;
+    use std::collections::BTreeMap;
+
+    fn random_keys(count: usize, seed: u64) -> Vec<String> {
+        let mut next = test_rng(seed);
+        (0..count)
+            .map(|_| {
+                let len = next() % 16;
+                (0..len).map(|_| (b'a' + (next() % 4) as u8) as char).collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_split_and_lookup() {
+        let mut t = RadixTrie::new();
+        assert_eq!(t.insert("romane", 1), None);
+        assert_eq!(t.insert("romanus", 2), None);
+        assert_eq!(t.insert("romulus", 3), None);
+        assert_eq!(t.insert("rom", 4), None);
+        assert_eq!(t.insert("romane", 5), Some(1));
+        assert_eq!(t.len(), 4);
+        assert_eq!(t.get("romane"), Some(&5));
+        assert_eq!(t.get("roma"), None);
+        assert_eq!(t.get("romanes"), None);
+        assert_eq!(t.count_prefix("roma"), 2);
+        assert_eq!(t.count_prefix("ro"), 4);
+        assert_eq!(t.keys_with_prefix("roma"), vec!["romane", "romanus"]);
+        assert_eq!(t.keys_with_prefix("romu"), vec!["romulus"]);
+        assert_eq!(t.longest_prefix_match("romanesque"), Some(("romane", &5)));
+        assert_eq!(t.longest_prefix_match("romantic"), Some(("rom", &4)));
+    }
+
+    #[test]
+    fn test_matches_btreemap() {
+        let keys = random_keys(300, 3);
+        let mut t = RadixTrie::new();
+        let mut reference = BTreeMap::new();
+        for (i, k) in keys.iter().enumerate() {
+            assert_eq!(t.insert(k, i), reference.insert(k.clone(), i));
+        }
+        assert_eq!(t.len(), reference.len());
+        let all: Vec<(String, usize)> = t.iter().map(|(k, &v)| (k, v)).collect();
+        let expected: Vec<(String, usize)> = reference.iter().map(|(k, &v)| (k.clone(), v)).collect();
+        assert_eq!(all, expected);
+
+        for q in random_keys(200, 8) {
+            let succ = reference.range::<String, _>((Excluded(&q), Unbounded)).next();
+            assert_eq!(t.successor(&q).map(|(k, &v)| (k, v)), succ.map(|(k, &v)| (k.clone(), v)), "succ {:?}", q);
+            let pred = reference.range::<String, _>(..&q).next_back();
+            assert_eq!(t.predecessor(&q).map(|(k, &v)| (k, v)), pred.map(|(k, &v)| (k.clone(), v)), "pred {:?}", q);
+            let count = reference.keys().filter(|k| k.starts_with(&q)).count();
+            assert_eq!(t.count_prefix(&q), count);
+            let listed: Vec<&String> = reference.keys().filter(|k| k.starts_with(&q)).collect();
+            assert_eq!(t.keys_with_prefix(&q).iter().collect::<Vec<_>>(), listed, "prefix {:?}", q);
+        }
+    }
+
+    #[test]
+    fn test_compression_saves_nodes() {
+        let mut radix = RadixTrie::new();
+        let mut plain = Trie::new();
+        for i in 0..500 {
+            let key = format!("https://example.com/api/v1/resources/{}", i);
+            radix.insert(&key, i);
+            plain.insert(&key, i);
+        }
+        assert_eq!(radix.len(), plain.len());
+        assert!(radix.node_count() <= 2 * radix.len() + 1);
+        assert_eq!(radix.keys_with_prefix("https://example.com/api/v1/resources/49").len(), 11);
+        let next = radix.successor("https://example.com/api/v1/resources/499").map(|(k, _)| k);
+        assert_eq!(next.as_deref(), Some("https://example.com/api/v1/resources/5"));
+    }
+}
@@ -0,0 +1,135 @@
+// rust/src/range.rs
+use core::ops::{Bound, Range, RangeBounds};
+
+/// Resolves any std range (`l..r`, `l..=r`, `..r`, `l..`, `..`) against a structure of length `len`,
+/// giving the crate-wide convention: 0-based, half-open `start..end`, exactly like slice indexing.
+/// Returns None if the range is reversed or reaches past `len`. Empty ranges such as `3..3` are valid.
+///
+/// Range-taking methods (`FenwickTree::sum`, `SparseTable::min`, `RangeQueryEngine::min`, ...) use
+/// `resolve`, so they accept and reject exactly what `&slice[range]` would. The older index-pair
+/// methods (`range_sum(l, r)`, `query(l, r)`) are inclusive `[l, r]` and are kept for compatibility.
+pub fn try_resolve<R: RangeBounds<usize>>(range: R, len: usize) -> Option<Range<usize>> {
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s.checked_add(1)?,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e.checked_add(1)?,
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+    (start <= end && end <= len).then_some(start..end)
+}
+
+/// Like `try_resolve`, but panics on an invalid range, with the same kind of message as slice indexing.
+pub fn resolve<R: RangeBounds<usize>>(range: R, len: usize) -> Range<usize> {
+    let (start, end) = (range.start_bound().cloned(), range.end_bound().cloned());
+    try_resolve((start, end), len)
+        .unwrap_or_else(|| panic!("range {:?}..{:?} out of bounds for length {}", start, end, len))
+}
+
+/// Common interface of the structures answering aggregate queries over index ranges (`FenwickTree`,
+/// `FixedFenwick`, `SparseTable`, `FischerHeun`, `SegmentTree`, `IterativeSegmentTree`,
+/// `SqrtDecomposition`, `RangeQueryEngine`), so that generic code and benchmarks can swap one
+/// backend for another.
+/// Structures whose queries need `&mut self` (the lazy trees) keep their own API.
+pub trait RangeQuery {
+    /// Element type.
+    type Item;
+    /// Result of a query: a sum, a minimum (`Option` where an empty range has none), ...
+    type Output;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Aggregate over `range`, half-open as in `resolve`. Panics if the range is out of bounds.
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> Self::Output;
+}
+
+/// A `RangeQuery` structure whose elements can be replaced one at a time.
+pub trait PointUpdate: RangeQuery {
+    /// Sets element `index` to `value`. Panics if `index` is out of bounds.
+    fn set(&mut self, index: usize, value: Self::Item);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fenwick_tree::FenwickTree;
+    use crate::monoid::{Min, Sum};
+    use crate::range_query::{Backend, RangeQueryEngine};
+    use crate::segment_tree::{IterativeSegmentTree, SegmentTree};
+    use crate::sparse_table::SparseTable;
+
+    /// Written once against the traits, run against every backend.
+    fn replay<Q: PointUpdate<Item = i64>>(q: &mut Q, values: &mut [i64], check: impl Fn(&[i64]) -> Q::Output)
+    where
+        Q::Output: PartialEq + std::fmt::Debug,
+    {
+        for step in 0..values.len() {
+            let v = (step as i64 * 37) % 11 - 5;
+            q.set(step, v);
+            values[step] = v;
+            for l in 0..=values.len() {
+                for r in l..=values.len() {
+                    assert_eq!(q.query_range(l..r), check(&values[l..r]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_backends_are_interchangeable() {
+        let values = vec![5, -2, 4, 7, 1, 3, 9, 0];
+        let sum = |chunk: &[i64]| chunk.iter().sum::<i64>();
+        replay(&mut FenwickTree::from_vec(&values), &mut values.clone(), sum);
+        replay(&mut SegmentTree::from_slice(&values, Sum), &mut values.clone(), sum);
+        replay(&mut IterativeSegmentTree::from_slice(&values, Sum), &mut values.clone(), sum);
+        let min = |chunk: &[i64]| chunk.iter().copied().min();
+        for backend in [Backend::SparseTable, Backend::SegmentTree, Backend::SqrtDecomposition] {
+            replay(&mut RangeQueryEngine::with_backend(&values, backend), &mut values.clone(), min);
+        }
+        let min_or_identity = |chunk: &[i64]| chunk.iter().copied().min().unwrap_or(i64::MAX);
+        replay(&mut IterativeSegmentTree::from_slice(&values, Min), &mut values.clone(), min_or_identity);
+
+        let st = SparseTable::from_slice(&values);
+        assert_eq!(RangeQuery::query_range(&st, 2..5), Some(1));
+        assert_eq!(RangeQuery::len(&st), values.len());
+    }
+
+    #[test]
+    fn test_all_range_forms() {
+        assert_eq!(try_resolve(2..5, 10), Some(2..5));
+        assert_eq!(try_resolve(2..=5, 10), Some(2..6));
+        assert_eq!(try_resolve(..5, 10), Some(0..5));
+        assert_eq!(try_resolve(..=5, 10), Some(0..6));
+        assert_eq!(try_resolve(7.., 10), Some(7..10));
+        assert_eq!(try_resolve(.., 10), Some(0..10));
+        assert_eq!(try_resolve((Bound::Excluded(1), Bound::Excluded(4)), 10), Some(2..4));
+        assert_eq!(try_resolve(3..3, 10), Some(3..3));
+        assert_eq!(try_resolve(10.., 10), Some(10..10));
+        assert_eq!(try_resolve(.., 0), Some(0..0));
+    }
+
+    #[test]
+    fn test_invalid_ranges() {
+        assert_eq!(try_resolve(5..11, 10), None);
+        assert_eq!(try_resolve(0..=10, 10), None);
+        assert_eq!(try_resolve(11.., 10), None);
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = try_resolve(5..3, 10);
+        assert_eq!(reversed, None);
+        assert_eq!(try_resolve(0..=usize::MAX, usize::MAX), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds for length 10")]
+    fn test_resolve_panics_like_slicing() {
+        resolve(2..=10, 10);
+    }
+}
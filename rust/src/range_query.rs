@@ -0,0 +1,302 @@
+// rust/src/range_query.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::monoid::Min;
+use crate::range::{self, PointUpdate, RangeQuery};
+use crate::segment_tree::IterativeSegmentTree;
+use crate::sparse_table::SparseTable;
+use std::fmt;
+use std::ops::RangeBounds;
+
+/// Expected workload of a `RangeQueryEngine`, used only to pick a backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkloadHints {
+    pub expected_queries: usize,
+    pub expected_updates: usize,
+}
+
+/// Data structures a `RangeQueryEngine` can run on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    SparseTable,
+    SegmentTree,
+    SqrtDecomposition,
+}
+
+impl Backend {
+    pub const ALL: [Backend; 3] = [Backend::SparseTable, Backend::SegmentTree, Backend::SqrtDecomposition];
+
+    /// Rough operation counts for building the structure over `n` elements and running the workload.
+    /// The constants reflect typical relative speeds rather than exact instruction counts.
+    fn estimated_cost(self, n: usize, hints: WorkloadHints) -> f64 {
+        let n = n.max(1) as f64;
+        let log = n.log2().max(1.0);
+        let (q, u) = (hints.expected_queries as f64, hints.expected_updates as f64);
+        match self {
+            // static: every update rebuilds the table
+            Backend::SparseTable => n * log * (1.0 + u) + 2.0 * q,
+            Backend::SegmentTree => 2.0 * n + 4.0 * log * q + 2.0 * log * u,
+            Backend::SqrtDecomposition => n + 2.0 * n.sqrt() * q + n.sqrt() * u,
+        }
+    }
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Backend::SparseTable => "sparse table",
+            Backend::SegmentTree => "segment tree",
+            Backend::SqrtDecomposition => "sqrt decomposition",
+        })
+    }
+}
+
+/// Range-minimum engine that chooses its data structure from the declared workload.
+/// - Sparse table: O(1) queries, but every update rebuilds it in O(n log n). Best for static data.
+/// - Segment tree: O(log n) queries and updates. Best for mixed workloads on large arrays.
+/// - Sqrt decomposition: O(√n) queries and updates with very small constants. Competitive on small arrays.
+///
+/// The choice minimizes a simple cost model; `explain()` reports the estimates behind it.
+/// Ranges are inclusive `[l, r]`, as in `SparseTable::query`.
+pub struct RangeQueryEngine {
+    repr: Repr,
+    hints: WorkloadHints,
+}
+
+enum Repr {
+    Sparse { values: Vec<i64>, table: SparseTable<i64> },
+    Segment(IterativeSegmentTree<i64, Min>),
+    Sqrt(MinBlocks),
+}
+
+impl RangeQueryEngine {
+    pub fn new(arr: &[i64], hints: WorkloadHints) -> Self {
+        let backend = Backend::ALL
+            .into_iter()
+            .min_by(|a, b| a.estimated_cost(arr.len(), hints).total_cmp(&b.estimated_cost(arr.len(), hints)))
+            .expect("at least one backend");
+        let mut engine = Self::with_backend(arr, backend);
+        engine.hints = hints;
+        engine
+    }
+
+    /// Builds the engine on a specific backend, bypassing the cost model.
+    pub fn with_backend(arr: &[i64], backend: Backend) -> Self {
+        let repr = match backend {
+            Backend::SparseTable => Repr::Sparse {
+                values: arr.to_vec(),
+                table: SparseTable::from_slice(arr),
+            },
+            Backend::SegmentTree => Repr::Segment(IterativeSegmentTree::from_slice(arr, Min)),
+            Backend::SqrtDecomposition => Repr::Sqrt(MinBlocks::new(arr)),
+        };
+        RangeQueryEngine {
+            repr,
+            hints: WorkloadHints::default(),
+        }
+    }
+
+    pub fn backend(&self) -> Backend {
+        match self.repr {
+            Repr::Sparse { .. } => Backend::SparseTable,
+            Repr::Segment(_) => Backend::SegmentTree,
+            Repr::Sqrt(_) => Backend::SqrtDecomposition,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.repr {
+            Repr::Sparse { values, .. } => values.len(),
+            Repr::Segment(t) => t.len(),
+            Repr::Sqrt(b) => b.values.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Minimum of `arr[l..=r]`. Returns None if the range is empty or out of bounds.
+    pub fn query(&self, l: usize, r: usize) -> Option<i64> {
+        if l > r || r >= self.len() {
+            return None;
+        }
+        match &self.repr {
+            Repr::Sparse { table, .. } => table.query(l, r),
+            Repr::Segment(t) => Some(t.query(l..=r)),
+            Repr::Sqrt(b) => Some(b.query(l, r)),
+        }
+    }
+
+    /// Minimum over `range`, using the crate's half-open convention (`2..5`, `..=3`, `..`).
+    /// Returns None for an empty range and panics if the range is out of bounds, like slice indexing.
+    pub fn min<R: RangeBounds<usize>>(&self, range: R) -> Option<i64> {
+        let r = range::resolve(range, self.len());
+        if r.is_empty() { None } else { self.query(r.start, r.end - 1) }
+    }
+
+    /// Sets `arr[i] = value`. Panics if `i` is out of bounds.
+    pub fn update(&mut self, i: usize, value: i64) {
+        assert!(i < self.len(), "index out of bounds");
+        match &mut self.repr {
+            Repr::Sparse { values, table } => {
+                values[i] = value;
+                *table = SparseTable::from_slice(values);
+            }
+            Repr::Segment(t) => t.set(i, value),
+            Repr::Sqrt(b) => b.update(i, value),
+        }
+    }
+
+    /// Human-readable account of the backend choice and the cost estimates behind it.
+    pub fn explain(&self) -> String {
+        let n = self.len();
+        let costs: Vec<String> = Backend::ALL
+            .iter()
+            .map(|b| format!("{} ~{:.0}", b, b.estimated_cost(n, self.hints)))
+            .collect();
+        let why = match self.backend() {
+            Backend::SparseTable => "no updates to pay for, so O(1) queries win",
+            Backend::SegmentTree => "updates and queries both stay O(log n)",
+            Backend::SqrtDecomposition => "the array is small enough that O(√n) block scans beat tree overhead",
+        };
+        format!(
+            "n = {}, expected {} queries and {} updates: using {} ({}). Estimated costs: {}",
+            n,
+            self.hints.expected_queries,
+            self.hints.expected_updates,
+            self.backend(),
+            why,
+            costs.join(", ")
+        )
+    }
+}
+
+/// Sqrt decomposition for range minimum: the array split into blocks of about √n with cached minima.
+struct MinBlocks {
+    values: Vec<i64>,
+    block: usize,
+    mins: Vec<i64>,
+}
+
+impl MinBlocks {
+    fn new(arr: &[i64]) -> Self {
+        let block = (arr.len() as f64).sqrt().ceil().max(1.0) as usize;
+        let mins = arr.chunks(block).map(|c| *c.iter().min().expect("chunks are non-empty")).collect();
+        MinBlocks {
+            values: arr.to_vec(),
+            block,
+            mins,
+        }
+    }
+
+    fn update(&mut self, i: usize, value: i64) {
+        self.values[i] = value;
+        let b = i / self.block;
+        let end = ((b + 1) * self.block).min(self.values.len());
+        self.mins[b] = *self.values[b * self.block..end].iter().min().expect("block is non-empty");
+    }
+
+    fn query(&self, l: usize, r: usize) -> i64 {
+        let (bl, br) = (l / self.block, r / self.block);
+        if bl == br {
+            return *self.values[l..=r].iter().min().expect("range is non-empty");
+        }
+        let head = self.values[l..(bl + 1) * self.block].iter().min();
+        let middle = self.mins[bl + 1..br].iter().min();
+        let tail = self.values[br * self.block..=r].iter().min();
+        *[head, middle, tail].into_iter().flatten().min().expect("range is non-empty")
+    }
+}
+
+impl DataStructure for RangeQueryEngine {
+    const INFO: Info = Info {
+        name: "RangeQueryEngine",
+        category: "range queries",
+        summary: "Range minimum over a backend chosen from the expected workload",
+        space: "O(n) to O(n log n), by backend",
+        operations: &[
+            Operation::new("new", "O(n) to O(n log n), by backend", "O(n log n)"),
+            Operation::new("query", "O(1), O(log n) or O(√n), by backend", "O(1)"),
+            Operation::new("update", "O(n log n), O(log n) or O(√n), by backend", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl RangeQuery for RangeQueryEngine {
+    type Item = i64;
+    type Output = Option<i64>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> Option<i64> {
+        self.min(range)
+    }
+}
+
+impl PointUpdate for RangeQueryEngine {
+    fn set(&mut self, index: usize, value: i64) {
+        self.update(index, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    fn sample(n: usize, seed: u64) -> Vec<i64> {
+        let mut next = test_rng(seed);
+        (0..n).map(|_| (next() % 1000) as i64).collect()
+    }
+
+    #[test]
+    fn test_backend_choice() {
+        let big = vec![0; 100_000];
+        let static_load = WorkloadHints { expected_queries: 1_000_000, expected_updates: 0 };
+        assert_eq!(RangeQueryEngine::new(&big, static_load).backend(), Backend::SparseTable);
+        let mixed = WorkloadHints { expected_queries: 100_000, expected_updates: 100_000 };
+        assert_eq!(RangeQueryEngine::new(&big, mixed).backend(), Backend::SegmentTree);
+        let small = vec![0; 16];
+        assert_eq!(RangeQueryEngine::new(&small, mixed).backend(), Backend::SqrtDecomposition);
+        let explanation = RangeQueryEngine::new(&big, mixed).explain();
+        assert!(explanation.contains("using segment tree"), "{}", explanation);
+    }
+
+    #[test]
+    fn test_backends_agree() {
+        let mut arr = sample(137, 3);
+        let mut engines: Vec<RangeQueryEngine> =
+            Backend::ALL.iter().map(|&b| RangeQueryEngine::with_backend(&arr, b)).collect();
+        let mut next = test_rng(9);
+        for step in 0..400 {
+            let a = next() as usize % arr.len();
+            let b = next() as usize % arr.len();
+            if step % 4 == 0 {
+                let v = (next() % 1000) as i64 - 500;
+                arr[a] = v;
+                engines.iter_mut().for_each(|e| e.update(a, v));
+            }
+            let (l, r) = (a.min(b), a.max(b));
+            let expected = arr[l..=r].iter().min().copied();
+            for e in &engines {
+                assert_eq!(e.query(l, r), expected, "{} on [{}, {}]", e.backend(), l, r);
+            }
+        }
+        assert!(engines.iter().all(|e| e.query(5, 4).is_none() && e.query(0, arr.len()).is_none()));
+        for e in &engines {
+            assert_eq!(e.min(10..20), arr[10..20].iter().min().copied());
+            assert_eq!(e.min(..=0), Some(arr[0]));
+            assert_eq!(e.min(..), arr.iter().min().copied());
+            assert_eq!(e.min(7..7), None);
+        }
+        for b in Backend::ALL {
+            let empty = RangeQueryEngine::with_backend(&[], b);
+            assert!(empty.is_empty() && empty.query(0, 0).is_none());
+        }
+    }
+}
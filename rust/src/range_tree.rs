@@ -0,0 +1,184 @@
+// rust/src/range_tree.rs
+use crate::DataStructure;
+use crate::bit_vector::BitVector;
+use crate::info::{Info, Operation};
+use std::ops::{Bound, Range, RangeBounds};
+
+/// Positions in `sorted` of the values inside `bounds`.
+fn positions<V: RangeBounds<i64>>(sorted: &[i64], bounds: V) -> Range<usize> {
+    let lo = match bounds.start_bound() {
+        Bound::Included(v) => sorted.partition_point(|x| x < v),
+        Bound::Excluded(v) => sorted.partition_point(|x| x <= v),
+        Bound::Unbounded => 0,
+    };
+    let hi = match bounds.end_bound() {
+        Bound::Included(v) => sorted.partition_point(|x| x <= v),
+        Bound::Excluded(v) => sorted.partition_point(|x| x < v),
+        Bound::Unbounded => sorted.len(),
+    };
+    lo..hi.max(lo)
+}
+
+/// One level of the tree: every node's points in y order, side by side.
+struct Level {
+    // input index of the point at each position
+    ids: Vec<usize>,
+    // whether the point goes to the right child; None on the last level
+    right: Option<BitVector>,
+}
+
+/// Static 2D range tree with fractional cascading, laid out level by level. The points are sorted
+/// by x and split in halves recursively, as in a segment tree; every node keeps its points sorted
+/// by y. Instead of binary searching each node's list, a query locates its y bounds once in the
+/// root list and carries those ranks down: a bit per point records which child it went to, and a
+/// `BitVector` rank turns a rank in a node into the rank in either child in O(1). Points are
+/// reported by their index in the input slice.
+/// - Build: O(n log n), O(n log n) words of space
+/// - count: O(log n)
+/// - report: O(log n + k)
+pub struct RangeTree2D {
+    xs: Vec<i64>,
+    ys: Vec<i64>,
+    levels: Vec<Level>,
+}
+
+impl RangeTree2D {
+    pub fn new(points: &[(i64, i64)]) -> Self {
+        let n = points.len();
+        let mut by_x: Vec<usize> = (0..n).collect();
+        by_x.sort_by_key(|&i| points[i]);
+        // x rank of each input point
+        let mut x_rank = vec![0; n];
+        by_x.iter().enumerate().for_each(|(r, &i)| x_rank[i] = r);
+        let mut ids: Vec<usize> = (0..n).collect();
+        ids.sort_by_key(|&i| (points[i].1, x_rank[i]));
+        let ys = ids.iter().map(|&i| points[i].1).collect();
+
+        let mut levels = Vec::new();
+        let mut nodes: Vec<Range<usize>> = std::iter::once(0..n).collect();
+        loop {
+            if nodes.iter().all(|r| r.len() <= 1) {
+                levels.push(Level { ids, right: None });
+                break;
+            }
+            let mut bits = Vec::with_capacity(n);
+            let mut next_ids = Vec::with_capacity(n);
+            let mut children = Vec::with_capacity(2 * nodes.len());
+            for node in &nodes {
+                let mid = node.start + node.len() / 2;
+                let goes_right = |&i: &usize| x_rank[i] >= mid;
+                bits.extend(ids[node.clone()].iter().map(goes_right));
+                // a stable partition keeps both halves in y order
+                next_ids.extend(ids[node.clone()].iter().filter(|i| !goes_right(i)));
+                next_ids.extend(ids[node.clone()].iter().filter(|i| goes_right(i)));
+                children.extend([node.start..mid, mid..node.end]);
+            }
+            levels.push(Level { ids: std::mem::replace(&mut ids, next_ids), right: Some(bits.into_iter().collect()) });
+            nodes = children;
+        }
+        RangeTree2D { xs: by_x.iter().map(|&i| points[i].0).collect(), ys, levels }
+    }
+
+    pub fn len(&self) -> usize {
+        self.xs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.xs.is_empty()
+    }
+
+    /// Calls `f(level, positions)` for the canonical nodes covering x ranks `want`, with the
+    /// positions of their points whose y rank (within the node) lies in `ranks`.
+    fn visit(&self, d: usize, node: Range<usize>, want: &Range<usize>, ranks: Range<usize>, f: &mut impl FnMut(usize, Range<usize>)) {
+        if ranks.is_empty() || node.end <= want.start || want.end <= node.start {
+            return;
+        }
+        if want.start <= node.start && node.end <= want.end {
+            f(d, node.start + ranks.start..node.start + ranks.end);
+            return;
+        }
+        let bits = self.levels[d].right.as_ref().expect("a partly covered node has children");
+        let mid = node.start + node.len() / 2;
+        // ranks among the points before `node.start + r` that went left
+        let left = |r: usize| bits.rank0(node.start + r) - bits.rank0(node.start);
+        let (l0, l1) = (left(ranks.start), left(ranks.end));
+        self.visit(d + 1, node.start..mid, want, l0..l1, f);
+        self.visit(d + 1, mid..node.end, want, ranks.start - l0..ranks.end - l1, f);
+    }
+
+    fn query<X: RangeBounds<i64>, Y: RangeBounds<i64>>(&self, x: X, y: Y, mut f: impl FnMut(usize, Range<usize>)) {
+        let (want, ranks) = (positions(&self.xs, x), positions(&self.ys, y));
+        self.visit(0, 0..self.len(), &want, ranks, &mut f);
+    }
+
+    /// Number of points with x in `x` and y in `y`.
+    pub fn count<X: RangeBounds<i64>, Y: RangeBounds<i64>>(&self, x: X, y: Y) -> usize {
+        let mut count = 0;
+        self.query(x, y, |_, r| count += r.len());
+        count
+    }
+
+    /// Indices of the points with x in `x` and y in `y`, in no particular order.
+    pub fn report<X: RangeBounds<i64>, Y: RangeBounds<i64>>(&self, x: X, y: Y) -> Vec<usize> {
+        let mut out = Vec::new();
+        self.query(x, y, |d, r| out.extend_from_slice(&self.levels[d].ids[r]));
+        out
+    }
+}
+
+impl DataStructure for RangeTree2D {
+    const INFO: Info = Info {
+        name: "RangeTree2D",
+        category: "range queries",
+        summary: "Static 2D range tree with fractional cascading for orthogonal range counting and reporting",
+        space: "O(n log n)",
+        operations: &[
+            Operation::new("new", "O(n log n)", "O(n log n)"),
+            Operation::new("count", "O(log n)", "O(log n)"),
+            Operation::new("report", "O(log n + k)", "O(log n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_count_and_report() {
+        let points = [(1, 1), (2, 5), (3, 3), (3, 7), (5, 2), (6, 6), (8, 4), (3, 3)];
+        let tree = RangeTree2D::new(&points);
+        assert_eq!(tree.count(2..=6, 2..=6), 5);
+        let mut hits = tree.report(2..=6, 2..=6);
+        hits.sort_unstable();
+        assert_eq!(hits, vec![1, 2, 4, 5, 7]);
+        assert_eq!(tree.count(3..4, ..), 3);
+        assert_eq!(tree.count(.., 7..), 1);
+        assert_eq!(tree.count(.., ..), 8);
+        assert_eq!(tree.count(9.., ..), 0);
+        assert_eq!(tree.count(4..=4, ..), 0);
+        assert!(RangeTree2D::new(&[]).report(.., ..).is_empty());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut rng = test_rng(83);
+        let mut next = || (rng() % 100) as i64;
+        for n in [1, 2, 7, 300] {
+            let points: Vec<(i64, i64)> = (0..n).map(|_| (next(), next())).collect();
+            let tree = RangeTree2D::new(&points);
+            for _ in 0..200 {
+                let (x1, x2, y1, y2) = (next(), next(), next(), next());
+                let inside = |&(x, y): &(i64, i64)| x1 <= x && x < x2 && y1 <= y && y <= y2;
+                let expected: Vec<usize> = (0..n).filter(|&i| inside(&points[i])).collect();
+                assert_eq!(tree.count(x1..x2, y1..=y2), expected.len());
+                let mut got = tree.report(x1..x2, y1..=y2);
+                got.sort_unstable();
+                assert_eq!(got, expected);
+            }
+        }
+    }
+}
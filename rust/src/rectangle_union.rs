@@ -0,0 +1,170 @@
+// rust/src/rectangle_union.rs
+/// Segment tree over y slabs that counts how many rectangles fully cover each node.
+struct CoverageTree {
+    ys: Vec<i64>,
+    count: Vec<u32>,
+    // widened so that a span of the whole i64 range still fits
+    covered: Vec<i128>,
+}
+
+impl CoverageTree {
+    /// `ys` are the sorted, deduplicated y coordinates; leaf `i` is the slab [ys[i], ys[i + 1]).
+    fn new(ys: Vec<i64>) -> Self {
+        let slabs = ys.len().saturating_sub(1).max(1);
+        CoverageTree {
+            ys,
+            count: vec![0; 4 * slabs],
+            covered: vec![0; 4 * slabs],
+        }
+    }
+
+    fn total(&self) -> i128 {
+        self.covered[1]
+    }
+
+    /// Adds `delta` coverage to slabs in [l, r).
+    fn update(&mut self, l: usize, r: usize, delta: i32) {
+        let slabs = self.ys.len().saturating_sub(1);
+        if l < r && slabs > 0 {
+            self.update_rec(1, 0, slabs, l, r, delta);
+        }
+    }
+
+    fn update_rec(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize, delta: i32) {
+        if r <= lo || hi <= l {
+            return;
+        }
+        if l <= lo && hi <= r {
+            self.count[node] = (self.count[node] as i32 + delta) as u32;
+        } else {
+            let mid = (lo + hi) / 2;
+            self.update_rec(2 * node, lo, mid, l, r, delta);
+            self.update_rec(2 * node + 1, mid, hi, l, r, delta);
+        }
+        // a fully covered node counts its whole span, otherwise defer to the children
+        self.covered[node] = if self.count[node] > 0 {
+            self.ys[hi] as i128 - self.ys[lo] as i128
+        } else if hi - lo == 1 {
+            0
+        } else {
+            self.covered[2 * node] + self.covered[2 * node + 1]
+        };
+    }
+}
+
+fn normalize(rects: &[(i64, i64, i64, i64)]) -> Vec<(i64, i64, i64, i64)> {
+    rects
+        .iter()
+        .map(|&(x1, y1, x2, y2)| (x1.min(x2), y1.min(y2), x1.max(x2), y1.max(y2)))
+        .filter(|&(x1, y1, x2, y2)| x1 < x2 && y1 < y2)
+        .collect()
+}
+
+/// Sweeps along x. Returns (area, sum of |change in covered length|), the latter being
+/// the length of the union's boundary edges perpendicular to the sweep direction.
+fn sweep(rects: &[(i64, i64, i64, i64)]) -> (i128, i128) {
+    if rects.is_empty() {
+        return (0, 0);
+    }
+    let mut ys: Vec<i64> = rects.iter().flat_map(|r| [r.1, r.3]).collect();
+    ys.sort_unstable();
+    ys.dedup();
+    let index = |y: i64| ys.binary_search(&y).expect("y coordinate was compressed");
+
+    // (x, delta, y1 index, y2 index); openings sort before closings at the same x
+    let mut events: Vec<(i64, i32, usize, usize)> = Vec::with_capacity(2 * rects.len());
+    for &(x1, y1, x2, y2) in rects {
+        events.push((x1, -1, index(y1), index(y2)));
+        events.push((x2, 1, index(y1), index(y2)));
+    }
+    events.sort_unstable();
+
+    let mut tree = CoverageTree::new(ys.clone());
+    let (mut area, mut edges) = (0i128, 0i128);
+    let mut prev_x = events[0].0;
+    for (x, delta, l, r) in events {
+        area += tree.total() * (x as i128 - prev_x as i128);
+        let before = tree.total();
+        tree.update(l, r, -delta);
+        edges += (tree.total() - before).abs();
+        prev_x = x;
+    }
+    (area, edges)
+}
+
+/// Total area covered by a union of axis-aligned rectangles.
+/// A vertical line sweeps over the x coordinates while a coverage-counting segment tree
+/// over the compressed y coordinates keeps track of how much of the line lies inside some rectangle.
+///
+/// Rectangles are `(x1, y1, x2, y2)` with opposite corners; empty rectangles are ignored.
+/// The area is accumulated in i128, which holds it exactly unless the union's bounding box spans
+/// more than 2^63 along both axes.
+/// Complexity: O(n log n)
+pub fn rectangles_union_area(rects: &[(i64, i64, i64, i64)]) -> i128 {
+    sweep(&normalize(rects)).0
+}
+
+/// Perimeter of the union of rectangles (including the boundaries of holes).
+/// Runs the same sweep once along each axis.
+pub fn rectangles_union_perimeter(rects: &[(i64, i64, i64, i64)]) -> i128 {
+    let rects = normalize(rects);
+    let vertical = sweep(&rects).1;
+    let transposed: Vec<_> = rects.iter().map(|&(x1, y1, x2, y2)| (y1, x1, y2, x2)).collect();
+    let horizontal = sweep(&transposed).1;
+    vertical + horizontal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_area() {
+        assert_eq!(rectangles_union_area(&[]), 0);
+        assert_eq!(rectangles_union_area(&[(0, 0, 2, 2)]), 4);
+        // two overlapping squares share a 1x1 corner
+        assert_eq!(rectangles_union_area(&[(0, 0, 2, 2), (1, 1, 3, 3)]), 7);
+        // nested rectangle adds nothing
+        assert_eq!(rectangles_union_area(&[(0, 0, 10, 10), (2, 2, 3, 3)]), 100);
+        // corners given in any order, degenerate rectangles ignored
+        assert_eq!(rectangles_union_area(&[(2, 2, 0, 0), (5, 5, 5, 9)]), 4);
+    }
+
+    #[test]
+    fn test_union_perimeter() {
+        assert_eq!(rectangles_union_perimeter(&[(0, 0, 2, 2)]), 8);
+        assert_eq!(rectangles_union_perimeter(&[(0, 0, 2, 2), (1, 1, 3, 3)]), 12);
+        // touching rectangles merge into a 4x2 block
+        assert_eq!(rectangles_union_perimeter(&[(0, 0, 2, 2), (2, 0, 4, 2)]), 12);
+        // a frame of four bars around a 1x1 hole: outer 12, inner 4
+        let frame = [(0, 0, 3, 1), (0, 2, 3, 3), (0, 1, 1, 2), (2, 1, 3, 2)];
+        assert_eq!(rectangles_union_area(&frame), 8);
+        assert_eq!(rectangles_union_perimeter(&frame), 16);
+    }
+
+    #[test]
+    fn test_matches_grid_count() {
+        let rects = [(0, 0, 4, 3), (2, 1, 6, 5), (5, 0, 7, 2), (1, 4, 3, 7)];
+        let mut cells = 0;
+        for x in 0..8 {
+            for y in 0..8 {
+                if rects.iter().any(|&(x1, y1, x2, y2)| x1 <= x && x < x2 && y1 <= y && y < y2) {
+                    cells += 1;
+                }
+            }
+        }
+        assert_eq!(rectangles_union_area(&rects), cells);
+    }
+
+    #[test]
+    fn test_extreme_coordinates() {
+        let m = 1i64 << 62;
+        // 2^63 by 2^63, far past i64
+        assert_eq!(rectangles_union_area(&[(-m, -m, m, m)]), 1i128 << 126);
+        assert_eq!(rectangles_union_perimeter(&[(-m, -m, m, m)]), 1i128 << 65);
+        // the full i64 range along one axis
+        let wide = [(i64::MIN, 0, i64::MAX, 4), (0, 2, 1, 6)];
+        assert_eq!(rectangles_union_area(&wide), 4 * (u64::MAX as i128) + 2);
+        assert_eq!(rectangles_union_perimeter(&wide), 2 * (u64::MAX as i128) + 8 + 4);
+    }
+}
@@ -0,0 +1,68 @@
+// rust/src/rng.rs
+
+/// Source of random bits for the randomized structures, so that tests and benchmarks can inject a
+/// seeded or scripted generator. Any `FnMut() -> u64` closure is one.
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> Rng for F {
+    fn next_u64(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// SplitMix64 (Steele, Lea and Flood): one add and two multiply-xorshifts per output. Not
+/// cryptographic, but plenty for balancing randomized structures.
+#[derive(Debug, Clone)]
+pub struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    pub fn new(seed: u64) -> Self {
+        SplitMix64 { state: seed }
+    }
+}
+
+impl Default for SplitMix64 {
+    fn default() -> Self {
+        SplitMix64::new(0x9E3779B97F4A7C15)
+    }
+}
+
+impl Rng for SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+}
+
+/// Seeded SplitMix64 stream for the randomized tests. A closure, so it also serves as an `Rng`.
+#[cfg(test)]
+pub(crate) fn test_rng(seed: u64) -> impl FnMut() -> u64 {
+    let mut rng = SplitMix64::new(seed);
+    move || rng.next_u64()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_splitmix_and_closures() {
+        // reference outputs for seed 0
+        let mut rng = SplitMix64::new(0);
+        assert_eq!(rng.next_u64(), 0xE220A8397B1DCDAF);
+        assert_eq!(rng.next_u64(), 0x6E789E6AA1B965F4);
+        let mut counter = 0;
+        let mut scripted = || {
+            counter += 1;
+            counter
+        };
+        assert_eq!((scripted.next_u64(), scripted.next_u64()), (1, 2));
+    }
+}
@@ -0,0 +1,156 @@
+// rust/src/rolling_hash.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+const MOD1: u64 = 1_000_000_007;
+const MOD2: u64 = 998_244_353;
+const BASE1: u64 = 911_382_323;
+const BASE2: u64 = 972_663_749;
+
+/// Polynomial prefix hashes of a byte string under two moduli, for O(1) substring comparison.
+/// Ranges are half-open byte ranges `[l, r)`, like slice indexing. Two different substrings collide
+/// with probability about 1e-18, so `equal` is a Monte Carlo test; the tiny error rate is the usual
+/// trade for constant-time comparisons.
+/// - Build: O(n)
+/// - hash / equal: O(1)
+/// - lcp: O(log n)
+pub struct StringHasher {
+    prefix: Vec<(u64, u64)>,
+    power: Vec<(u64, u64)>,
+}
+
+impl StringHasher {
+    pub fn new(s: &str) -> Self {
+        Self::from_bytes(s.as_bytes())
+    }
+
+    pub fn from_bytes(s: &[u8]) -> Self {
+        Self::from_slice(s)
+    }
+
+    /// Hashes any sequence of symbols, e.g. `&[char]` so that positions count chars instead of bytes.
+    pub fn from_slice<T: Copy + Into<u64>>(s: &[T]) -> Self {
+        let mut prefix = Vec::with_capacity(s.len() + 1);
+        let mut power = Vec::with_capacity(s.len() + 1);
+        prefix.push((0, 0));
+        power.push((1, 1));
+        for (i, &c) in s.iter().enumerate() {
+            let (h1, h2) = prefix[i];
+            let (p1, p2) = power[i];
+            // +1 so that a zero byte still changes the hash
+            let c = c.into() % MOD2 + 1;
+            prefix.push(((h1 * BASE1 + c) % MOD1, (h2 * BASE2 + c) % MOD2));
+            power.push((p1 * BASE1 % MOD1, p2 * BASE2 % MOD2));
+        }
+        StringHasher { prefix, power }
+    }
+
+    pub fn len(&self) -> usize {
+        self.prefix.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Hash of `s[l..r]`, both moduli packed into one u64. Panics if the range is out of bounds.
+    pub fn hash(&self, l: usize, r: usize) -> u64 {
+        assert!(l <= r && r <= self.len(), "range {}..{} out of bounds", l, r);
+        let (a1, a2) = self.prefix[l];
+        let (b1, b2) = self.prefix[r];
+        let (p1, p2) = self.power[r - l];
+        let h1 = (b1 + MOD1 * MOD1 - a1 * p1) % MOD1;
+        let h2 = (b2 + MOD2 * MOD2 - a2 * p2) % MOD2;
+        (h1 << 32) | h2
+    }
+
+    /// Whether `s[l1..r1] == s[l2..r2]` (up to hash collisions).
+    pub fn equal(&self, l1: usize, r1: usize, l2: usize, r2: usize) -> bool {
+        self.hash(l1, r1) == self.hash(l2, r2) && r1 - l1 == r2 - l2
+    }
+
+    /// Length of the longest common prefix of the suffixes starting at `i` and `j`.
+    pub fn lcp(&self, i: usize, j: usize) -> usize {
+        let n = self.len();
+        assert!(i <= n && j <= n, "suffix start out of bounds");
+        // largest len with equal prefixes, by binary search on [0, max]
+        let (mut lo, mut hi) = (0, n - i.max(j));
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if self.hash(i, i + mid) == self.hash(j, j + mid) {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+        lo
+    }
+}
+
+impl DataStructure for StringHasher {
+    const INFO: Info = Info {
+        name: "StringHasher",
+        category: "strings",
+        summary: "Double-modulus polynomial prefix hashes for constant-time substring comparison",
+        space: "O(n)",
+        operations: &[
+            Operation::new("new", "O(n)", "O(n)"),
+            Operation::new("hash", "O(1)", "O(1)"),
+            Operation::new("equal", "O(1)", "O(1)"),
+            Operation::new("lcp", "O(log n)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_equal_substrings() {
+        let h = StringHasher::new("abracadabra");
+        assert_eq!(h.len(), 11);
+        assert!(h.equal(0, 4, 7, 11));
+        assert!(!h.equal(0, 4, 1, 5));
+        assert!(!h.equal(0, 4, 7, 10));
+        assert!(h.equal(3, 3, 5, 5));
+        assert_eq!(h.hash(0, 1), h.hash(3, 4));
+        assert_eq!(h.lcp(0, 7), 4);
+        assert_eq!(h.lcp(0, 3), 1);
+        assert_eq!(h.lcp(2, 2), 9);
+        assert_eq!(h.lcp(1, 11), 0);
+    }
+
+    #[test]
+    fn test_lcp_matches_brute_force() {
+        let mut next = test_rng(31);
+        let s: Vec<u8> = (0..300).map(|_| b'a' + (next() % 2) as u8).collect();
+        let h = StringHasher::from_bytes(&s);
+        for i in (0..s.len()).step_by(7) {
+            for j in (0..s.len()).step_by(5) {
+                let brute = s[i..].iter().zip(&s[j..]).take_while(|(a, b)| a == b).count();
+                assert_eq!(h.lcp(i, j), brute, "{} {}", i, j);
+            }
+        }
+    }
+
+    #[test]
+    fn test_zero_bytes() {
+        let h = StringHasher::from_bytes(&[0, 0, 1]);
+        assert!(!h.equal(0, 1, 0, 2));
+        assert_ne!(h.hash(0, 1), h.hash(0, 0));
+    }
+
+    #[test]
+    fn test_char_positions() {
+        let chars: Vec<char> = "é€é€x".chars().collect();
+        let h = StringHasher::from_slice(&chars);
+        assert_eq!(h.len(), 5);
+        assert!(h.equal(0, 2, 2, 4));
+        assert_eq!(h.lcp(0, 2), 2);
+        // bytes hash the same through either constructor
+        assert_eq!(StringHasher::from_slice(b"abc").hash(0, 3), StringHasher::new("abc").hash(0, 3));
+    }
+}
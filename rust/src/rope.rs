@@ -0,0 +1,373 @@
+// rust/src/rope.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+use std::rc::Rc;
+
+/// Leaves are cut at this many bytes when building, and neighbouring leaves merge up to it.
+const MAX_LEAF: usize = 1024;
+
+struct Node {
+    chars: usize,
+    bytes: usize,
+    height: usize,
+    kind: Kind,
+}
+
+enum Kind {
+    Leaf(String),
+    Branch(Rc<Node>, Rc<Node>),
+}
+
+fn leaf(text: String) -> Rc<Node> {
+    Rc::new(Node { chars: text.chars().count(), bytes: text.len(), height: 1, kind: Kind::Leaf(text) })
+}
+
+fn branch(left: Rc<Node>, right: Rc<Node>) -> Rc<Node> {
+    Rc::new(Node {
+        chars: left.chars + right.chars,
+        bytes: left.bytes + right.bytes,
+        height: 1 + left.height.max(right.height),
+        kind: Kind::Branch(left, right),
+    })
+}
+
+fn children(n: &Rc<Node>) -> (&Rc<Node>, &Rc<Node>) {
+    match &n.kind {
+        Kind::Branch(l, r) => (l, r),
+        Kind::Leaf(_) => unreachable!("only branches are taller than 1"),
+    }
+}
+
+/// `branch(left, right)` with an AVL rotation if their heights differ by 2.
+fn balanced(left: Rc<Node>, right: Rc<Node>) -> Rc<Node> {
+    if left.height > right.height + 1 {
+        let (ll, lr) = children(&left);
+        if ll.height >= lr.height {
+            branch(ll.clone(), branch(lr.clone(), right))
+        } else {
+            let (lrl, lrr) = children(lr);
+            branch(branch(ll.clone(), lrl.clone()), branch(lrr.clone(), right))
+        }
+    } else if right.height > left.height + 1 {
+        let (rl, rr) = children(&right);
+        if rr.height >= rl.height {
+            branch(branch(left, rl.clone()), rr.clone())
+        } else {
+            let (rll, rlr) = children(rl);
+            branch(branch(left, rll.clone()), branch(rlr.clone(), rr.clone()))
+        }
+    } else {
+        branch(left, right)
+    }
+}
+
+/// Concatenation: descends the taller tree's inner spine to the height of the shorter one and
+/// rebalances on the way back, in O(difference in heights). Two small leaves merge into one.
+fn join(a: Option<Rc<Node>>, b: Option<Rc<Node>>) -> Option<Rc<Node>> {
+    let (a, b) = match (a, b) {
+        (None, x) | (x, None) => return x,
+        (Some(a), Some(b)) => (a, b),
+    };
+    if let (Kind::Leaf(x), Kind::Leaf(y)) = (&a.kind, &b.kind)
+        && x.len() + y.len() <= MAX_LEAF
+    {
+        return Some(leaf(format!("{}{}", x, y)));
+    }
+    Some(if a.height > b.height + 1 {
+        let (l, r) = children(&a);
+        balanced(l.clone(), join(Some(r.clone()), Some(b)).expect("joining non-empty trees"))
+    } else if b.height > a.height + 1 {
+        let (l, r) = children(&b);
+        balanced(join(Some(a), Some(l.clone())).expect("joining non-empty trees"), r.clone())
+    } else {
+        branch(a, b)
+    })
+}
+
+/// Splits into the first `at` characters and the rest, rejoining the pieces cut off along the
+/// search path; the joins telescope to O(log n) in total.
+fn split(n: Rc<Node>, at: usize) -> (Option<Rc<Node>>, Option<Rc<Node>>) {
+    if at == 0 {
+        return (None, Some(n));
+    }
+    if at >= n.chars {
+        return (Some(n), None);
+    }
+    match &n.kind {
+        Kind::Leaf(text) => {
+            let byte = text.char_indices().nth(at).map_or(text.len(), |(b, _)| b);
+            (Some(leaf(text[..byte].to_string())), Some(leaf(text[byte..].to_string())))
+        }
+        Kind::Branch(l, r) => {
+            if at <= l.chars {
+                let (ll, lr) = split(l.clone(), at);
+                (ll, join(lr, Some(r.clone())))
+            } else {
+                let (rl, rr) = split(r.clone(), at - l.chars);
+                (join(Some(l.clone()), rl), rr)
+            }
+        }
+    }
+}
+
+/// Balanced tree of leaves built bottom-up by pairing neighbours.
+fn build(mut level: Vec<Rc<Node>>) -> Option<Rc<Node>> {
+    while level.len() > 1 {
+        let mut next = Vec::with_capacity(level.len().div_ceil(2));
+        let mut pairs = level.into_iter();
+        while let Some(a) = pairs.next() {
+            next.push(match pairs.next() {
+                Some(b) => branch(a, b),
+                None => a,
+            });
+        }
+        level = next;
+    }
+    level.pop()
+}
+
+/// Rope: text held as a height-balanced binary tree whose leaves are string chunks of at most
+/// `MAX_LEAF` bytes and whose inner nodes cache character counts, so positions are found by
+/// descending. Everything is expressed through split and concatenate, both O(log n). Nodes are
+/// immutable and shared through `Rc`, so cloning a rope (e.g. for an undo stack) is O(1) and
+/// edits copy only a path. Positions count `char`s, not bytes.
+/// - insert / remove / slice / split_off / append / char_at: O(log n), plus the inserted text
+#[derive(Clone, Default)]
+pub struct Rope {
+    root: Option<Rc<Node>>,
+}
+
+impl Rope {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of characters.
+    pub fn len_chars(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.chars)
+    }
+
+    pub fn len_bytes(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.bytes)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// Height of the tree: 0 when empty, 1 for a single leaf.
+    pub fn height(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.height)
+    }
+
+    fn check(&self, at: usize) {
+        assert!(at <= self.len_chars(), "position {} out of bounds for {} chars", at, self.len_chars());
+    }
+
+    /// Character range `[start, end)` of `range`, checked against the length.
+    fn bounds<R: RangeBounds<usize>>(&self, range: R) -> (usize, usize) {
+        let start = match range.start_bound() {
+            Bound::Included(&s) => s,
+            Bound::Excluded(&s) => s + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&e) => e + 1,
+            Bound::Excluded(&e) => e,
+            Bound::Unbounded => self.len_chars(),
+        };
+        assert!(start <= end, "range starts at {} but ends at {}", start, end);
+        self.check(end);
+        (start, end)
+    }
+
+    /// Moves the characters from `at` on into a new rope and returns it.
+    pub fn split_off(&mut self, at: usize) -> Rope {
+        self.check(at);
+        let Some(root) = self.root.take() else { return Rope::new() };
+        let (left, right) = split(root, at);
+        self.root = left;
+        Rope { root: right }
+    }
+
+    /// Appends `other` at the end.
+    pub fn append(&mut self, other: Rope) {
+        self.root = join(self.root.take(), other.root);
+    }
+
+    /// Inserts `text` before the character at position `at`.
+    pub fn insert(&mut self, at: usize, text: &str) {
+        let tail = self.split_off(at);
+        self.append(Rope::from(text));
+        self.append(tail);
+    }
+
+    /// Deletes the characters in `range`.
+    pub fn remove<R: RangeBounds<usize>>(&mut self, range: R) {
+        let (start, end) = self.bounds(range);
+        let mut tail = self.split_off(start);
+        let rest = tail.split_off(end - start);
+        self.append(rest);
+    }
+
+    /// The characters in `range` as a rope sharing this one's nodes.
+    pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Rope {
+        let (start, end) = self.bounds(range);
+        let mut middle = self.clone().split_off(start);
+        middle.split_off(end - start);
+        middle
+    }
+
+    pub fn char_at(&self, mut at: usize) -> Option<char> {
+        let mut cur = self.root.as_ref()?;
+        if at >= cur.chars {
+            return None;
+        }
+        loop {
+            match &cur.kind {
+                Kind::Leaf(text) => return text.chars().nth(at),
+                Kind::Branch(l, r) => {
+                    if at < l.chars {
+                        cur = l;
+                    } else {
+                        at -= l.chars;
+                        cur = r;
+                    }
+                }
+            }
+        }
+    }
+
+    /// The leaf strings from left to right.
+    pub fn chunks(&self) -> impl Iterator<Item = &str> + '_ {
+        let mut stack: Vec<&Node> = self.root.as_deref().into_iter().collect();
+        std::iter::from_fn(move || {
+            while let Some(n) = stack.pop() {
+                match &n.kind {
+                    Kind::Leaf(text) => return Some(text.as_str()),
+                    Kind::Branch(l, r) => {
+                        stack.push(r);
+                        stack.push(l);
+                    }
+                }
+            }
+            None
+        })
+    }
+
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.chunks().flat_map(str::chars)
+    }
+}
+
+impl From<&str> for Rope {
+    fn from(text: &str) -> Self {
+        let mut leaves = Vec::with_capacity(text.len() / MAX_LEAF + 1);
+        let mut rest = text;
+        while !rest.is_empty() {
+            let mut cut = rest.len().min(MAX_LEAF);
+            while !rest.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            leaves.push(leaf(rest[..cut].to_string()));
+            rest = &rest[cut..];
+        }
+        Rope { root: build(leaves) }
+    }
+}
+
+impl fmt::Display for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.chunks().try_for_each(|chunk| f.write_str(chunk))
+    }
+}
+
+impl fmt::Debug for Rope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Rope({:?})", self.to_string())
+    }
+}
+
+impl DataStructure for Rope {
+    const INFO: Info = Info {
+        name: "Rope",
+        category: "strings",
+        summary: "Balanced tree of shared string chunks for editing large texts by character position",
+        space: "O(n)",
+        operations: &[
+            Operation::new("insert", "O(log n + k)", "O(log n + k)"),
+            Operation::new("remove", "O(log n)", "O(log n)"),
+            Operation::new("append", "O(log n)", "O(log n)"),
+            Operation::new("split_off / slice", "O(log n)", "O(log n)"),
+            Operation::new("char_at", "O(log n)", "O(1)"),
+            Operation::new("clone", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_editing() {
+        let mut rope = Rope::from("hello world");
+        rope.insert(5, ", dear");
+        assert_eq!(rope.to_string(), "hello, dear world");
+        rope.remove(5..11);
+        assert_eq!(rope.to_string(), "hello world");
+        let snapshot = rope.clone();
+        rope.insert(0, "¡");
+        rope.insert(rope.len_chars(), "!");
+        assert_eq!(rope.to_string(), "¡hello world!");
+        assert_eq!((rope.len_chars(), rope.len_bytes()), (13, 14));
+        assert_eq!(rope.char_at(0), Some('¡'));
+        assert_eq!(rope.char_at(13), None);
+        assert_eq!(rope.slice(1..=5).to_string(), "hello");
+        // the clone is unaffected by later edits
+        assert_eq!(snapshot.to_string(), "hello world");
+
+        let mut tail = rope.split_off(7);
+        assert_eq!((rope.to_string(), tail.to_string()), ("¡hello ".to_string(), "world!".to_string()));
+        tail.append(Rope::new());
+        rope.append(tail);
+        assert_eq!(rope.to_string(), "¡hello world!");
+        rope.remove(..);
+        assert!(rope.is_empty());
+    }
+
+    #[test]
+    fn test_large_text_against_string() {
+        let mut next = test_rng(37);
+        let base: String = (0..200_000).map(|i| ['a', 'β', 'c', '\n', '€'][i % 5]).collect();
+        let mut rope = Rope::from(base.as_str());
+        let mut reference: Vec<char> = base.chars().collect();
+        for _ in 0..2000 {
+            let at = next() as usize % (reference.len() + 1);
+            if next().is_multiple_of(2) {
+                let text: String = (0..next() as usize % 20).map(|i| ['x', 'ü', 'y'][i % 3]).collect();
+                rope.insert(at, &text);
+                reference.splice(at..at, text.chars());
+            } else {
+                let end = (at + next() as usize % 50).min(reference.len());
+                rope.remove(at..end);
+                reference.drain(at..end);
+            }
+        }
+        assert_eq!(rope.len_chars(), reference.len());
+        assert!(rope.chars().eq(reference.iter().copied()));
+        for _ in 0..100 {
+            let at = next() as usize % reference.len();
+            assert_eq!(rope.char_at(at), Some(reference[at]));
+            let end = (at + next() as usize % 100).min(reference.len());
+            assert!(rope.slice(at..end).chars().eq(reference[at..end].iter().copied()));
+        }
+        // still logarithmic after all the edits
+        assert!(rope.height() <= 2 * (rope.len_bytes() / MAX_LEAF + 2).ilog2() as usize + 4);
+    }
+}
@@ -0,0 +1,618 @@
+// rust/src/segment_tree.rs
+use crate::DataStructure;
+use crate::dot::{DotWriter, Highlight};
+use crate::info::{Info, Operation};
+use crate::monoid::Monoid;
+use crate::range::{self, PointUpdate, RangeQuery};
+use std::fmt::Debug;
+use std::ops::RangeBounds;
+
+/// Segment tree over any monoid (`Sum`, `Min`, `Max`, `FnMonoid`, or a user type implementing
+/// `Monoid<T>`): point updates and aggregate queries over arbitrary ranges. Sits between the static
+/// `SparseTable` (idempotent operations only, no updates) and the sum-only `FenwickTree`.
+/// Values are combined in index order, so non-commutative operations work too.
+/// - Build: O(n)
+/// - set / query: O(log n)
+///
+/// Recursive and top-down over `[lo, hi)` node ranges, with node `k`'s children at `2k` and `2k + 1`.
+pub struct SegmentTree<T, Op> {
+    len: usize,
+    tree: Vec<T>,
+    op: Op,
+}
+
+impl<T: Clone, Op: Monoid<T>> SegmentTree<T, Op> {
+    /// `len` copies of the identity.
+    pub fn new(len: usize, op: Op) -> Self {
+        let tree = vec![op.identity(); 4 * len.max(1)];
+        SegmentTree { len, tree, op }
+    }
+
+    pub fn from_slice(values: &[T], op: Op) -> Self {
+        let mut st = Self::new(values.len(), op);
+        if !values.is_empty() {
+            st.build(1, 0, values.len(), values);
+        }
+        st
+    }
+
+    fn build(&mut self, node: usize, lo: usize, hi: usize, values: &[T]) {
+        if hi - lo == 1 {
+            self.tree[node] = values[lo].clone();
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.build(2 * node, lo, mid, values);
+        self.build(2 * node + 1, mid, hi, values);
+        self.tree[node] = self.op.combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn op(&self) -> &Op {
+        &self.op
+    }
+
+    /// Value at `index`. Panics if out of bounds.
+    pub fn get(&self, index: usize) -> T {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        self.query(index..=index)
+    }
+
+    /// Replaces the value at `index`. Panics if out of bounds.
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        self.set_rec(1, 0, self.len, index, value);
+    }
+
+    fn set_rec(&mut self, node: usize, lo: usize, hi: usize, index: usize, value: T) {
+        if hi - lo == 1 {
+            self.tree[node] = value;
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        if index < mid {
+            self.set_rec(2 * node, lo, mid, index, value);
+        } else {
+            self.set_rec(2 * node + 1, mid, hi, index, value);
+        }
+        self.tree[node] = self.op.combine(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    /// Combination of the values in `range` (half-open convention, see `crate::range`); the identity
+    /// for an empty range. Panics if the range is out of bounds, like slice indexing.
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let r = range::resolve(range, self.len);
+        if r.is_empty() {
+            return self.op.identity();
+        }
+        self.query_rec(1, 0, self.len, r.start, r.end)
+    }
+
+    /// Combination of all values, in O(1).
+    pub fn all(&self) -> T {
+        if self.is_empty() { self.op.identity() } else { self.tree[1].clone() }
+    }
+
+    /// Binary search on the tree: the largest `r` such that `pred(&query(l..r))` holds, for a
+    /// predicate that holds for the identity and, once false, stays false as `r` grows (like
+    /// "sum <= x" over non-negative values). For example "the first index where the prefix sum
+    /// exceeds x" is `max_right(0, |&s| s <= x)`. Panics if `l > len`.
+    ///
+    /// Complexity: O(log n) predicate calls
+    pub fn max_right(&self, l: usize, pred: impl Fn(&T) -> bool) -> usize {
+        assert!(l <= self.len, "index {} out of bounds for length {}", l, self.len);
+        debug_assert!(pred(&self.op.identity()), "the predicate must hold for the identity");
+        let mut acc = self.op.identity();
+        if l == self.len {
+            return l;
+        }
+        self.max_right_rec(1, 0, self.len, l, &pred, &mut acc).unwrap_or(self.len)
+    }
+
+    /// Returns where the predicate first fails inside `[lo, hi)`, or None (with `acc` extended) if it
+    /// holds through `hi`.
+    fn max_right_rec(
+        &self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        l: usize,
+        pred: &impl Fn(&T) -> bool,
+        acc: &mut T,
+    ) -> Option<usize> {
+        if hi <= l {
+            return None;
+        }
+        if l <= lo {
+            let extended = self.op.combine(acc, &self.tree[node]);
+            if pred(&extended) {
+                *acc = extended;
+                return None;
+            }
+            if hi - lo == 1 {
+                return Some(lo);
+            }
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.max_right_rec(2 * node, lo, mid, l, pred, acc)
+            .or_else(|| self.max_right_rec(2 * node + 1, mid, hi, l, pred, acc))
+    }
+
+    /// Mirror image of `max_right`: the smallest `l` such that `pred(&query(l..r))` holds, for a
+    /// predicate that holds for the identity and stays false once false as `l` decreases.
+    /// Panics if `r > len`.
+    ///
+    /// Complexity: O(log n) predicate calls
+    pub fn min_left(&self, r: usize, pred: impl Fn(&T) -> bool) -> usize {
+        assert!(r <= self.len, "index {} out of bounds for length {}", r, self.len);
+        debug_assert!(pred(&self.op.identity()), "the predicate must hold for the identity");
+        let mut acc = self.op.identity();
+        if r == 0 {
+            return 0;
+        }
+        self.min_left_rec(1, 0, self.len, r, &pred, &mut acc).unwrap_or(0)
+    }
+
+    fn min_left_rec(
+        &self,
+        node: usize,
+        lo: usize,
+        hi: usize,
+        r: usize,
+        pred: &impl Fn(&T) -> bool,
+        acc: &mut T,
+    ) -> Option<usize> {
+        if lo >= r {
+            return None;
+        }
+        if hi <= r {
+            let extended = self.op.combine(&self.tree[node], acc);
+            if pred(&extended) {
+                *acc = extended;
+                return None;
+            }
+            if hi - lo == 1 {
+                return Some(hi);
+            }
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.min_left_rec(2 * node + 1, mid, hi, r, pred, acc)
+            .or_else(|| self.min_left_rec(2 * node, lo, mid, r, pred, acc))
+    }
+
+    fn query_rec(&self, node: usize, lo: usize, hi: usize, l: usize, r: usize) -> T {
+        if l <= lo && hi <= r {
+            return self.tree[node].clone();
+        }
+        let mid = lo + (hi - lo) / 2;
+        if r <= mid {
+            self.query_rec(2 * node, lo, mid, l, r)
+        } else if l >= mid {
+            self.query_rec(2 * node + 1, mid, hi, l, r)
+        } else {
+            let left = self.query_rec(2 * node, lo, mid, l, r);
+            let right = self.query_rec(2 * node + 1, mid, hi, l, r);
+            self.op.combine(&left, &right)
+        }
+    }
+}
+
+impl<T, Op> DataStructure for SegmentTree<T, Op> {
+    const INFO: Info = Info {
+        name: "SegmentTree",
+        category: "range queries",
+        summary: "Segment tree over any monoid: point updates and range aggregates",
+        space: "O(n)",
+        operations: &[
+            Operation::new("from_slice", "O(n)", "O(n)"),
+            Operation::new("set", "O(log n)", "O(log n)"),
+            Operation::new("query", "O(log n)", "O(log n)"),
+            Operation::new("all", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl<T: Debug, Op> SegmentTree<T, Op> {
+    /// Graphviz DOT rendering: every node with the range it covers and its aggregate.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighting(&Highlight::new())
+    }
+
+    /// Like `to_dot`, emphasising the nodes covering a highlighted array index, i.e. the
+    /// leaf-to-root paths a `set` at those indices updates.
+    pub fn to_dot_highlighting(&self, highlight: &Highlight) -> String {
+        let mut w = DotWriter::new("SegmentTree", true);
+        w.attr("node [shape=box]");
+        if self.len > 0 {
+            self.dot_rec(&mut w, highlight, 1, 0, self.len);
+        }
+        w.finish()
+    }
+
+    fn dot_rec(&self, w: &mut DotWriter, highlight: &Highlight, node: usize, lo: usize, hi: usize) {
+        let marked = |lo: usize, hi: usize| (lo..hi).any(|i| highlight.contains(&i));
+        let label = format!("[{}, {})\n{:?}", lo, hi, self.tree[node]);
+        w.node(&format!("s{}", node), &label, marked(lo, hi));
+        if hi - lo == 1 {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        for (child, clo, chi) in [(2 * node, lo, mid), (2 * node + 1, mid, hi)] {
+            self.dot_rec(w, highlight, child, clo, chi);
+            w.edge(&format!("s{}", node), &format!("s{}", child), None, marked(clo, chi));
+        }
+    }
+}
+
+impl<T: Clone, Op: Monoid<T>> RangeQuery for SegmentTree<T, Op> {
+    type Item = T;
+    type Output = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> T {
+        self.query(range)
+    }
+}
+
+impl<T: Clone, Op: Monoid<T>> PointUpdate for SegmentTree<T, Op> {
+    fn set(&mut self, index: usize, value: T) {
+        self.set(index, value);
+    }
+}
+
+/// Non-recursive segment tree over a monoid, the fast alternative to `SegmentTree` with the same
+/// interface. Leaves sit at `tree[size..size + len]` (`size` the next power of two) and each internal
+/// node `k` covers its children `2k` and `2k + 1`, so updates and queries are tight loops over a flat
+/// array walking from the leaves up, without recursion or range bookkeeping.
+/// - Build: O(n)
+/// - set / query: O(log n)
+pub struct IterativeSegmentTree<T, Op> {
+    len: usize,
+    size: usize,
+    tree: Vec<T>,
+    op: Op,
+}
+
+impl<T: Clone, Op: Monoid<T>> IterativeSegmentTree<T, Op> {
+    /// `len` copies of the identity.
+    pub fn new(len: usize, op: Op) -> Self {
+        let size = len.next_power_of_two();
+        let tree = vec![op.identity(); 2 * size];
+        IterativeSegmentTree { len, size, tree, op }
+    }
+
+    pub fn from_slice(values: &[T], op: Op) -> Self {
+        let mut st = Self::new(values.len(), op);
+        st.tree[st.size..st.size + values.len()].clone_from_slice(values);
+        for k in (1..st.size).rev() {
+            st.tree[k] = st.op.combine(&st.tree[2 * k], &st.tree[2 * k + 1]);
+        }
+        st
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn op(&self) -> &Op {
+        &self.op
+    }
+
+    /// Value at `index`, in O(1). Panics if out of bounds.
+    pub fn get(&self, index: usize) -> &T {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        &self.tree[self.size + index]
+    }
+
+    /// Replaces the value at `index`. Panics if out of bounds.
+    pub fn set(&mut self, index: usize, value: T) {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        let mut k = self.size + index;
+        self.tree[k] = value;
+        while k > 1 {
+            k /= 2;
+            self.tree[k] = self.op.combine(&self.tree[2 * k], &self.tree[2 * k + 1]);
+        }
+    }
+
+    /// Combination of the values in `range` (half-open convention, see `crate::range`); the identity
+    /// for an empty range. Panics if the range is out of bounds, like slice indexing.
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let r = range::resolve(range, self.len);
+        let (mut l, mut r) = (r.start + self.size, r.end + self.size);
+        // left and right partial results are kept apart so non-commutative operations stay ordered
+        let (mut left, mut right) = (self.op.identity(), self.op.identity());
+        while l < r {
+            if l & 1 == 1 {
+                left = self.op.combine(&left, &self.tree[l]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                right = self.op.combine(&self.tree[r], &right);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        self.op.combine(&left, &right)
+    }
+
+    /// Combination of all values, in O(1).
+    pub fn all(&self) -> T {
+        self.tree[1].clone()
+    }
+
+    /// Binary search on the tree: the largest `r` such that `pred(&query(l..r))` holds, for a
+    /// predicate that holds for the identity and, once false, stays false as `r` grows. See
+    /// `SegmentTree::max_right`. Panics if `l > len`.
+    ///
+    /// Complexity: O(log n) predicate calls
+    pub fn max_right(&self, l: usize, pred: impl Fn(&T) -> bool) -> usize {
+        assert!(l <= self.len, "index {} out of bounds for length {}", l, self.len);
+        debug_assert!(pred(&self.op.identity()), "the predicate must hold for the identity");
+        if l == self.len {
+            return l;
+        }
+        let mut k = l + self.size;
+        let mut acc = self.op.identity();
+        loop {
+            // climb while k is a left child: its parent starts at the same index
+            while k.is_multiple_of(2) {
+                k /= 2;
+            }
+            let extended = self.op.combine(&acc, &self.tree[k]);
+            if !pred(&extended) {
+                // the answer lies inside node k: descend, taking left children while they fit
+                while k < self.size {
+                    k *= 2;
+                    let extended = self.op.combine(&acc, &self.tree[k]);
+                    if pred(&extended) {
+                        acc = extended;
+                        k += 1;
+                    }
+                }
+                return k - self.size;
+            }
+            acc = extended;
+            k += 1;
+            // stop after a node ending at the right edge of the tree
+            if k.is_power_of_two() {
+                return self.len;
+            }
+        }
+    }
+
+    /// Mirror image of `max_right`: the smallest `l` such that `pred(&query(l..r))` holds.
+    /// Panics if `r > len`.
+    ///
+    /// Complexity: O(log n) predicate calls
+    pub fn min_left(&self, r: usize, pred: impl Fn(&T) -> bool) -> usize {
+        assert!(r <= self.len, "index {} out of bounds for length {}", r, self.len);
+        debug_assert!(pred(&self.op.identity()), "the predicate must hold for the identity");
+        if r == 0 {
+            return 0;
+        }
+        let mut k = r + self.size;
+        let mut acc = self.op.identity();
+        loop {
+            k -= 1;
+            while k > 1 && k % 2 == 1 {
+                k /= 2;
+            }
+            let extended = self.op.combine(&self.tree[k], &acc);
+            if !pred(&extended) {
+                while k < self.size {
+                    k = 2 * k + 1;
+                    let extended = self.op.combine(&self.tree[k], &acc);
+                    if pred(&extended) {
+                        acc = extended;
+                        k -= 1;
+                    }
+                }
+                return k + 1 - self.size;
+            }
+            acc = extended;
+            if k.is_power_of_two() {
+                return 0;
+            }
+        }
+    }
+}
+
+impl<T, Op> DataStructure for IterativeSegmentTree<T, Op> {
+    const INFO: Info = Info {
+        name: "IterativeSegmentTree",
+        category: "range queries",
+        summary: "Non-recursive bottom-up segment tree over any monoid: point updates and range aggregates",
+        space: "O(n)",
+        operations: &[
+            Operation::new("from_slice", "O(n)", "O(n)"),
+            Operation::new("get", "O(1)", "O(1)"),
+            Operation::new("set", "O(log n)", "O(1)"),
+            Operation::new("query", "O(log n)", "O(1)"),
+            Operation::new("all", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl<T: Debug, Op> IterativeSegmentTree<T, Op> {
+    /// Graphviz DOT rendering: every node with the range it covers (clipped to `len`) and its
+    /// aggregate. Padding nodes lying entirely past the end are left out.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighting(&Highlight::new())
+    }
+
+    /// Like `to_dot`, emphasising the nodes covering a highlighted array index, i.e. the
+    /// leaf-to-root paths a `set` at those indices updates.
+    pub fn to_dot_highlighting(&self, highlight: &Highlight) -> String {
+        let mut w = DotWriter::new("IterativeSegmentTree", true);
+        w.attr("node [shape=box]");
+        // node k at depth d = log2(k) covers size >> d positions starting at (k - 2^d) * (size >> d)
+        let range = |k: usize| {
+            let depth = k.ilog2();
+            let span = self.size >> depth;
+            let lo = (k - (1 << depth)) * span;
+            (lo, (lo + span).min(self.len))
+        };
+        let nodes = if self.len == 0 { 0..0 } else { 1..2 * self.size };
+        for k in nodes.filter(|&k| range(k).0 < self.len) {
+            let (lo, hi) = range(k);
+            let marked = (lo..hi).any(|i| highlight.contains(&i));
+            w.node(&format!("s{}", k), &format!("[{}, {})\n{:?}", lo, hi, self.tree[k]), marked);
+            if k > 1 {
+                w.edge(&format!("s{}", k / 2), &format!("s{}", k), None, marked);
+            }
+        }
+        w.finish()
+    }
+}
+
+impl<T: Clone, Op: Monoid<T>> RangeQuery for IterativeSegmentTree<T, Op> {
+    type Item = T;
+    type Output = T;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> T {
+        self.query(range)
+    }
+}
+
+impl<T: Clone, Op: Monoid<T>> PointUpdate for IterativeSegmentTree<T, Op> {
+    fn set(&mut self, index: usize, value: T) {
+        self.set(index, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::monoid::{FnMonoid, Max, Min, Sum};
+
+    fn sample(n: usize, seed: u64) -> Vec<i64> {
+        let mut next = test_rng(seed);
+        (0..n).map(|_| (next() % 1000) as i64 - 500).collect()
+    }
+
+    #[test]
+    fn test_matches_naive_with_updates() {
+        let mut values = sample(37, 3);
+        let mut sum = SegmentTree::from_slice(&values, Sum);
+        let mut min = SegmentTree::from_slice(&values, Min);
+        let mut max = SegmentTree::from_slice(&values, Max);
+        let updates = sample(20, 11);
+        for (step, &v) in updates.iter().enumerate() {
+            let i = (step * 7) % values.len();
+            values[i] = v;
+            sum.set(i, v);
+            min.set(i, v);
+            max.set(i, v);
+            for l in 0..=values.len() {
+                for r in l..=values.len() {
+                    let chunk = &values[l..r];
+                    assert_eq!(sum.query(l..r), chunk.iter().sum::<i64>());
+                    assert_eq!(min.query(l..r), chunk.iter().copied().min().unwrap_or(i64::MAX));
+                    assert_eq!(max.query(l..r), chunk.iter().copied().max().unwrap_or(i64::MIN));
+                }
+            }
+        }
+        assert_eq!(sum.all(), values.iter().sum::<i64>());
+        assert_eq!(min.get(5), values[5]);
+    }
+
+    #[test]
+    fn test_non_commutative_and_empty() {
+        let words = ["a", "b", "c", "d", "e"].map(String::from);
+        let concat = FnMonoid::new(String::new(), |a: &String, b: &String| format!("{}{}", a, b));
+        let mut st = SegmentTree::from_slice(&words, concat);
+        assert_eq!(st.query(1..4), "bcd");
+        st.set(2, "X".to_string());
+        assert_eq!(st.query(..), "abXde");
+        assert_eq!(st.query(3..3), "");
+
+        let empty = SegmentTree::<i64, _>::new(0, Sum);
+        assert!(empty.is_empty());
+        assert_eq!(empty.query(..), 0);
+        assert_eq!(empty.all(), 0);
+        let zeros = SegmentTree::<u32, _>::new(4, Min);
+        assert_eq!(zeros.query(..), u32::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of bounds")]
+    fn test_query_out_of_bounds() {
+        SegmentTree::from_slice(&[1, 2, 3], Sum).query(1..4);
+    }
+
+    #[test]
+    fn test_iterative_matches_recursive() {
+        for n in [0, 1, 2, 5, 16, 33] {
+            let values = sample(n, n as u64 + 1);
+            let mut fast = IterativeSegmentTree::from_slice(&values, Min);
+            let mut slow = SegmentTree::from_slice(&values, Min);
+            for (step, v) in sample(n, 77).into_iter().enumerate() {
+                fast.set(step, v);
+                slow.set(step, v);
+                for l in 0..=n {
+                    for r in l..=n {
+                        assert_eq!(fast.query(l..r), slow.query(l..r));
+                    }
+                }
+            }
+            assert_eq!(fast.all(), slow.all());
+        }
+        let words = ["a", "b", "c", "d", "e"].map(String::from);
+        let concat = FnMonoid::new(String::new(), |a: &String, b: &String| format!("{}{}", a, b));
+        let mut st = IterativeSegmentTree::from_slice(&words, concat);
+        st.set(2, "X".to_string());
+        assert_eq!(st.query(1..5), "bXde");
+        assert_eq!(st.get(2), "X");
+    }
+
+    #[test]
+    fn test_descent_matches_linear_scan() {
+        for n in [0, 1, 2, 7, 16, 29] {
+            let values: Vec<i64> = sample(n, 40 + n as u64).iter().map(|v| v.abs() % 10).collect();
+            let slow = SegmentTree::from_slice(&values, Sum);
+            let fast = IterativeSegmentTree::from_slice(&values, Sum);
+            for l in 0..=n {
+                for x in [0, 3, 10, 25, 1000] {
+                    let expected = (l..=n).rev().find(|&r| values[l..r].iter().sum::<i64>() <= x).unwrap();
+                    assert_eq!(slow.max_right(l, |&s| s <= x), expected, "l = {}, x = {}", l, x);
+                    assert_eq!(fast.max_right(l, |&s| s <= x), expected, "l = {}, x = {}", l, x);
+                    let r = l;
+                    let expected = (0..=r).find(|&l| values[l..r].iter().sum::<i64>() <= x).unwrap();
+                    assert_eq!(slow.min_left(r, |&s| s <= x), expected, "r = {}, x = {}", r, x);
+                    assert_eq!(fast.min_left(r, |&s| s <= x), expected, "r = {}, x = {}", r, x);
+                }
+            }
+        }
+        // first index whose prefix sum exceeds 10: prefix sums are 3, 4, 8, 9, 14, ...
+        let st = IterativeSegmentTree::from_slice(&[3, 1, 4, 1, 5, 9, 2, 6], Sum);
+        assert_eq!(st.max_right(0, |&s| s <= 10), 4);
+        // first index from the left whose value is below 2, via min
+        let mins = SegmentTree::from_slice(&[3, 4, 2, 5, 1, 6], Min);
+        assert_eq!(mins.max_right(0, |&m| m >= 2), 4);
+    }
+}
@@ -0,0 +1,234 @@
+// rust/src/segment_tree_2d.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::monoid::Monoid;
+use crate::range;
+use std::ops::RangeBounds;
+
+/// Segment tree of segment trees over a mutable `rows x cols` grid: point updates and aggregates over
+/// rectangles. The outer tree runs over rows and every outer node holds a full column tree of its row
+/// band, all stored bottom-up in one flat array (see `IterativeSegmentTree`).
+/// The monoid must be commutative (sum, min, max, xor, gcd, ...), since a rectangle is assembled
+/// from row bands and column blocks in no particular order.
+/// - Build: O(rows * cols)
+/// - set / query: O(log rows * log cols)
+/// - Space: 4 * rows * cols values (after rounding both sides up to powers of two)
+pub struct SegmentTree2D<T, Op> {
+    rows: usize,
+    cols: usize,
+    // power-of-two sizes of the row and column trees
+    row_size: usize,
+    col_size: usize,
+    tree: Vec<T>,
+    op: Op,
+}
+
+impl<T: Clone, Op: Monoid<T>> SegmentTree2D<T, Op> {
+    /// A grid filled with the identity.
+    pub fn new(rows: usize, cols: usize, op: Op) -> Self {
+        let (row_size, col_size) = (rows.next_power_of_two(), cols.next_power_of_two());
+        let tree = vec![op.identity(); 4 * row_size * col_size];
+        SegmentTree2D {
+            rows,
+            cols,
+            row_size,
+            col_size,
+            tree,
+            op,
+        }
+    }
+
+    /// Builds the tree from a grid given row by row. Returns None if the rows have different lengths.
+    pub fn from_rows(grid: &[Vec<T>], op: Op) -> Option<Self> {
+        let cols = grid.first().map_or(0, Vec::len);
+        if grid.iter().any(|row| row.len() != cols) {
+            return None;
+        }
+        let mut st = Self::new(grid.len(), cols, op);
+        let (rs, cs) = (st.row_size, st.col_size);
+        for (r, row) in grid.iter().enumerate() {
+            let base = st.idx(rs + r, cs);
+            st.tree[base..base + cols].clone_from_slice(row);
+        }
+        for i in rs..2 * rs {
+            for j in (1..cs).rev() {
+                st.pull(st.idx(i, j), st.idx(i, 2 * j), st.idx(i, 2 * j + 1));
+            }
+        }
+        for i in (1..rs).rev() {
+            for j in 1..2 * cs {
+                st.pull(st.idx(i, j), st.idx(2 * i, j), st.idx(2 * i + 1, j));
+            }
+        }
+        Some(st)
+    }
+
+    /// Flat index of column node `j` in the column tree of row node `i`.
+    fn idx(&self, i: usize, j: usize) -> usize {
+        i * 2 * self.col_size + j
+    }
+
+    fn pull(&mut self, dst: usize, a: usize, b: usize) {
+        self.tree[dst] = self.op.combine(&self.tree[a], &self.tree[b]);
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn op(&self) -> &Op {
+        &self.op
+    }
+
+    fn check(&self, r: usize, c: usize) {
+        assert!(
+            r < self.rows && c < self.cols,
+            "cell ({}, {}) out of bounds for a {}x{} grid",
+            r,
+            c,
+            self.rows,
+            self.cols
+        );
+    }
+
+    /// Value of cell `(r, c)`, in O(1). Panics if out of bounds.
+    pub fn get(&self, r: usize, c: usize) -> &T {
+        self.check(r, c);
+        &self.tree[self.idx(self.row_size + r, self.col_size + c)]
+    }
+
+    /// Replaces the value of cell `(r, c)`. Panics if out of bounds.
+    pub fn set(&mut self, r: usize, c: usize, value: T) {
+        self.check(r, c);
+        let mut i = self.row_size + r;
+        let leaf = self.idx(i, self.col_size + c);
+        self.tree[leaf] = value;
+        // the column tree of the leaf row
+        let mut j = (self.col_size + c) / 2;
+        while j >= 1 {
+            self.pull(self.idx(i, j), self.idx(i, 2 * j), self.idx(i, 2 * j + 1));
+            j /= 2;
+        }
+        // then, for every row band above it, the column nodes on the same path
+        while i > 1 {
+            i /= 2;
+            let mut j = self.col_size + c;
+            while j >= 1 {
+                self.pull(self.idx(i, j), self.idx(2 * i, j), self.idx(2 * i + 1, j));
+                j /= 2;
+            }
+        }
+    }
+
+    fn query_cols(&self, i: usize, mut l: usize, mut r: usize, acc: &mut T) {
+        while l < r {
+            if l & 1 == 1 {
+                *acc = self.op.combine(acc, &self.tree[self.idx(i, l)]);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                *acc = self.op.combine(acc, &self.tree[self.idx(i, r)]);
+            }
+            l /= 2;
+            r /= 2;
+        }
+    }
+
+    /// Combination of the cells in the rectangle `rows x cols` (half-open ranges, see `crate::range`);
+    /// the identity if the rectangle is empty. Panics if either range is out of bounds.
+    pub fn query<R: RangeBounds<usize>, C: RangeBounds<usize>>(&self, rows: R, cols: C) -> T {
+        let rr = range::resolve(rows, self.rows);
+        let cc = range::resolve(cols, self.cols);
+        let mut acc = self.op.identity();
+        if cc.is_empty() {
+            return acc;
+        }
+        let (cl, cr) = (cc.start + self.col_size, cc.end + self.col_size);
+        let (mut l, mut r) = (rr.start + self.row_size, rr.end + self.row_size);
+        while l < r {
+            if l & 1 == 1 {
+                self.query_cols(l, cl, cr, &mut acc);
+                l += 1;
+            }
+            if r & 1 == 1 {
+                r -= 1;
+                self.query_cols(r, cl, cr, &mut acc);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        acc
+    }
+}
+
+impl<T, Op> DataStructure for SegmentTree2D<T, Op> {
+    const INFO: Info = Info {
+        name: "SegmentTree2D",
+        category: "range queries",
+        summary: "Segment tree of segment trees: point updates and rectangle aggregates on a grid (n x m)",
+        space: "O(n * m)",
+        operations: &[
+            Operation::new("from_rows", "O(n * m)", "O(n * m)"),
+            Operation::new("set", "O(log n * log m)", "O(1)"),
+            Operation::new("query", "O(log n * log m)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::monoid::{Max, Sum};
+
+    #[test]
+    fn test_rectangles_match_naive() {
+        let mut next = test_rng(2);
+        for (rows, cols) in [(1, 1), (3, 5), (8, 8), (6, 1), (7, 11)] {
+            let mut grid: Vec<Vec<i64>> = (0..rows).map(|_| (0..cols).map(|_| (next() % 100) as i64 - 50).collect()).collect();
+            let mut sum = SegmentTree2D::from_rows(&grid, Sum).unwrap();
+            let mut max = SegmentTree2D::from_rows(&grid, Max).unwrap();
+            for step in 0..20 {
+                let (r, c, v) = (next() as usize % rows, next() as usize % cols, (next() % 100) as i64);
+                grid[r][c] = v;
+                sum.set(r, c, v);
+                max.set(r, c, v);
+                assert_eq!(*sum.get(r, c), v);
+                if step % 5 != 0 {
+                    continue;
+                }
+                for r0 in 0..=rows {
+                    for r1 in r0..=rows {
+                        for c0 in 0..=cols {
+                            for c1 in c0..=cols {
+                                let cells = || grid[r0..r1].iter().flat_map(|row| row[c0..c1].iter().copied());
+                                assert_eq!(sum.query(r0..r1, c0..c1), cells().sum::<i64>());
+                                assert_eq!(max.query(r0..r1, c0..c1), cells().max().unwrap_or(i64::MIN));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shapes() {
+        assert!(SegmentTree2D::from_rows(&[vec![1, 2], vec![3]], Sum).is_none());
+        let empty = SegmentTree2D::<i64, _>::from_rows(&[], Sum).unwrap();
+        assert_eq!((empty.rows(), empty.cols()), (0, 0));
+        assert_eq!(empty.query(.., ..), 0);
+        let mut grid = SegmentTree2D::new(2, 3, Sum);
+        grid.set(1, 2, 5i64);
+        grid.set(0, 0, 1);
+        assert_eq!(grid.query(.., ..), 6);
+        assert_eq!(grid.query(1.., ..2), 0);
+    }
+}
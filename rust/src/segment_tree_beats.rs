@@ -0,0 +1,364 @@
+// rust/src/segment_tree_beats.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::range;
+use std::ops::RangeBounds;
+
+#[derive(Debug, Clone, Copy)]
+struct Node {
+    sum: i64,
+    // largest value, strict second largest (i64::MIN if none) and how many elements hold the largest
+    max1: i64,
+    max2: i64,
+    max_count: i64,
+    min1: i64,
+    min2: i64,
+    min_count: i64,
+    // pending add for the children
+    add: i64,
+}
+
+impl Node {
+    fn leaf(v: i64) -> Self {
+        Node {
+            sum: v,
+            max1: v,
+            max2: i64::MIN,
+            max_count: 1,
+            min1: v,
+            min2: i64::MAX,
+            min_count: 1,
+            add: 0,
+        }
+    }
+
+    fn merge(a: &Node, b: &Node) -> Node {
+        let (max1, max2, max_count) = match a.max1.cmp(&b.max1) {
+            std::cmp::Ordering::Equal => (a.max1, a.max2.max(b.max2), a.max_count + b.max_count),
+            std::cmp::Ordering::Greater => (a.max1, a.max2.max(b.max1), a.max_count),
+            std::cmp::Ordering::Less => (b.max1, a.max1.max(b.max2), b.max_count),
+        };
+        let (min1, min2, min_count) = match a.min1.cmp(&b.min1) {
+            std::cmp::Ordering::Equal => (a.min1, a.min2.min(b.min2), a.min_count + b.min_count),
+            std::cmp::Ordering::Less => (a.min1, a.min2.min(b.min1), a.min_count),
+            std::cmp::Ordering::Greater => (b.min1, a.min1.min(b.min2), b.min_count),
+        };
+        Node {
+            sum: a.sum + b.sum,
+            max1,
+            max2,
+            max_count,
+            min1,
+            min2,
+            min_count,
+            add: 0,
+        }
+    }
+
+    fn apply_add(&mut self, x: i64, len: usize) {
+        self.sum += x * len as i64;
+        self.max1 += x;
+        if self.max2 != i64::MIN {
+            self.max2 += x;
+        }
+        self.min1 += x;
+        if self.min2 != i64::MAX {
+            self.min2 += x;
+        }
+        self.add += x;
+    }
+
+    /// Lowers the maximum to `x`; requires `max2 < x < max1`, so only the maximal elements change.
+    fn apply_chmin(&mut self, x: i64) {
+        self.sum += (x - self.max1) * self.max_count;
+        if self.min1 == self.max1 {
+            self.min1 = x;
+        } else if self.min2 == self.max1 {
+            self.min2 = x;
+        }
+        self.max1 = x;
+    }
+
+    /// Raises the minimum to `x`; requires `min1 < x < min2`.
+    fn apply_chmax(&mut self, x: i64) {
+        self.sum += (x - self.min1) * self.min_count;
+        if self.max1 == self.min1 {
+            self.max1 = x;
+        } else if self.max2 == self.min1 {
+            self.max2 = x;
+        }
+        self.min1 = x;
+    }
+}
+
+/// Segment tree beats (Ji's segment tree) over `i64`: range chmin (`a[i] = min(a[i], x)`), range
+/// chmax and range add, with range sum, min and max queries. Ordinary lazy propagation cannot do
+/// chmin with sums because the effect on a node's sum depends on how many elements exceed `x`.
+/// Here each node also keeps its largest and second-largest values (and the smallest two); an update
+/// stops at a node when only its extreme elements change, and otherwise recurses. A potential argument
+/// bounds the extra recursion.
+/// - Build: O(n)
+/// - chmin / chmax / sum / min / max: O(log n) amortized (O(log^2 n) amortized once add is mixed in)
+pub struct SegmentTreeBeats {
+    len: usize,
+    tree: Vec<Node>,
+}
+
+impl SegmentTreeBeats {
+    pub fn from_slice(values: &[i64]) -> Self {
+        let mut st = SegmentTreeBeats {
+            len: values.len(),
+            tree: vec![Node::leaf(0); 4 * values.len().max(1)],
+        };
+        if !values.is_empty() {
+            st.build(1, 0, values.len(), values);
+        }
+        st
+    }
+
+    fn build(&mut self, node: usize, lo: usize, hi: usize, values: &[i64]) {
+        if hi - lo == 1 {
+            self.tree[node] = Node::leaf(values[lo]);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.build(2 * node, lo, mid, values);
+        self.build(2 * node + 1, mid, hi, values);
+        self.pull(node);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn pull(&mut self, node: usize) {
+        self.tree[node] = Node::merge(&self.tree[2 * node], &self.tree[2 * node + 1]);
+    }
+
+    fn push(&mut self, node: usize, lo: usize, mid: usize, hi: usize) {
+        let parent = self.tree[node];
+        for (child, len) in [(2 * node, mid - lo), (2 * node + 1, hi - mid)] {
+            let c = &mut self.tree[child];
+            if parent.add != 0 {
+                c.apply_add(parent.add, len);
+            }
+            // a child whose extreme exceeds the parent's missed a chmin / chmax applied to the parent
+            if c.max1 > parent.max1 {
+                c.apply_chmin(parent.max1);
+            }
+            if c.min1 < parent.min1 {
+                c.apply_chmax(parent.min1);
+            }
+        }
+        self.tree[node].add = 0;
+    }
+
+    /// Sets every element in `range` to `min(element, x)`. Panics if the range is out of bounds.
+    pub fn chmin<R: RangeBounds<usize>>(&mut self, range: R, x: i64) {
+        let r = range::resolve(range, self.len);
+        if !r.is_empty() {
+            self.chmin_rec(1, 0, self.len, r.start, r.end, x);
+        }
+    }
+
+    fn chmin_rec(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize, x: i64) {
+        if r <= lo || hi <= l || self.tree[node].max1 <= x {
+            return;
+        }
+        if l <= lo && hi <= r && self.tree[node].max2 < x {
+            self.tree[node].apply_chmin(x);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(node, lo, mid, hi);
+        self.chmin_rec(2 * node, lo, mid, l, r, x);
+        self.chmin_rec(2 * node + 1, mid, hi, l, r, x);
+        self.pull(node);
+    }
+
+    /// Sets every element in `range` to `max(element, x)`. Panics if the range is out of bounds.
+    pub fn chmax<R: RangeBounds<usize>>(&mut self, range: R, x: i64) {
+        let r = range::resolve(range, self.len);
+        if !r.is_empty() {
+            self.chmax_rec(1, 0, self.len, r.start, r.end, x);
+        }
+    }
+
+    fn chmax_rec(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize, x: i64) {
+        if r <= lo || hi <= l || self.tree[node].min1 >= x {
+            return;
+        }
+        if l <= lo && hi <= r && self.tree[node].min2 > x {
+            self.tree[node].apply_chmax(x);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(node, lo, mid, hi);
+        self.chmax_rec(2 * node, lo, mid, l, r, x);
+        self.chmax_rec(2 * node + 1, mid, hi, l, r, x);
+        self.pull(node);
+    }
+
+    /// Adds `x` to every element in `range`. Panics if the range is out of bounds.
+    pub fn add<R: RangeBounds<usize>>(&mut self, range: R, x: i64) {
+        let r = range::resolve(range, self.len);
+        if !r.is_empty() {
+            self.add_rec(1, 0, self.len, r.start, r.end, x);
+        }
+    }
+
+    fn add_rec(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize, x: i64) {
+        if r <= lo || hi <= l {
+            return;
+        }
+        if l <= lo && hi <= r {
+            self.tree[node].apply_add(x, hi - lo);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(node, lo, mid, hi);
+        self.add_rec(2 * node, lo, mid, l, r, x);
+        self.add_rec(2 * node + 1, mid, hi, l, r, x);
+        self.pull(node);
+    }
+
+    /// Node summary of `range`, which must be non-empty and in bounds.
+    fn fold(&mut self, node: usize, lo: usize, hi: usize, l: usize, r: usize) -> Node {
+        if l <= lo && hi <= r {
+            return self.tree[node];
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(node, lo, mid, hi);
+        if r <= mid {
+            self.fold(2 * node, lo, mid, l, r)
+        } else if l >= mid {
+            self.fold(2 * node + 1, mid, hi, l, r)
+        } else {
+            let left = self.fold(2 * node, lo, mid, l, r);
+            let right = self.fold(2 * node + 1, mid, hi, l, r);
+            Node::merge(&left, &right)
+        }
+    }
+
+    fn summary<R: RangeBounds<usize>>(&mut self, range: R) -> Option<Node> {
+        let r = range::resolve(range, self.len);
+        (!r.is_empty()).then(|| self.fold(1, 0, self.len, r.start, r.end))
+    }
+
+    /// Sum over `range`; 0 for an empty range. Takes `&mut self` because pending updates are pushed
+    /// down on the way. Panics if the range is out of bounds.
+    pub fn sum<R: RangeBounds<usize>>(&mut self, range: R) -> i64 {
+        self.summary(range).map_or(0, |n| n.sum)
+    }
+
+    /// Minimum over `range`; None for an empty range.
+    pub fn min<R: RangeBounds<usize>>(&mut self, range: R) -> Option<i64> {
+        self.summary(range).map(|n| n.min1)
+    }
+
+    /// Maximum over `range`; None for an empty range.
+    pub fn max<R: RangeBounds<usize>>(&mut self, range: R) -> Option<i64> {
+        self.summary(range).map(|n| n.max1)
+    }
+
+    /// Current values of all elements, in O(n): one traversal pushes every pending tag down to the
+    /// leaves.
+    pub fn to_vec(&mut self) -> Vec<i64> {
+        let mut out = Vec::with_capacity(self.len);
+        if self.len > 0 {
+            self.collect(1, 0, self.len, &mut out);
+        }
+        out
+    }
+
+    fn collect(&mut self, node: usize, lo: usize, hi: usize, out: &mut Vec<i64>) {
+        if hi - lo == 1 {
+            out.push(self.tree[node].sum);
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        self.push(node, lo, mid, hi);
+        self.collect(2 * node, lo, mid, out);
+        self.collect(2 * node + 1, mid, hi, out);
+    }
+}
+
+impl DataStructure for SegmentTreeBeats {
+    const INFO: Info = Info {
+        name: "SegmentTreeBeats",
+        category: "range queries",
+        summary: "Segment tree beats: range chmin/chmax/add with range sum, min and max",
+        space: "O(n)",
+        operations: &[
+            Operation::new("from_slice", "O(n)", "O(n)"),
+            Operation::new("chmin", "O(log n) amortized", "O(log n)"),
+            Operation::new("chmax", "O(log n) amortized", "O(log n)"),
+            Operation::new("add", "O(log^2 n) amortized", "O(log n)"),
+            Operation::new("sum", "O(log n)", "O(log n)"),
+            Operation::new("to_vec", "O(n)", "O(n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_matches_naive() {
+        let mut next = test_rng(31);
+        for n in [1, 2, 9, 64] {
+            let mut values: Vec<i64> = (0..n).map(|_| (next() % 100) as i64 - 50).collect();
+            let mut st = SegmentTreeBeats::from_slice(&values);
+            for _ in 0..2000 {
+                let (a, b) = (next() as usize % (n + 1), next() as usize % (n + 1));
+                let (l, r) = (a.min(b), a.max(b));
+                let x = (next() % 100) as i64 - 50;
+                match next() % 6 {
+                    0 => {
+                        st.chmin(l..r, x);
+                        values[l..r].iter_mut().for_each(|v| *v = (*v).min(x));
+                    }
+                    1 => {
+                        st.chmax(l..r, x);
+                        values[l..r].iter_mut().for_each(|v| *v = (*v).max(x));
+                    }
+                    2 => {
+                        st.add(l..r, x / 5);
+                        values[l..r].iter_mut().for_each(|v| *v += x / 5);
+                    }
+                    _ => {
+                        let chunk = &values[l..r];
+                        assert_eq!(st.sum(l..r), chunk.iter().sum::<i64>());
+                        assert_eq!(st.min(l..r), chunk.iter().copied().min());
+                        assert_eq!(st.max(l..r), chunk.iter().copied().max());
+                        // to_vec pushes tags down in place; later updates must still see them
+                        if x % 10 == 0 {
+                            assert_eq!(st.to_vec(), values);
+                        }
+                    }
+                }
+            }
+            assert_eq!(st.to_vec(), values);
+        }
+    }
+
+    #[test]
+    fn test_clamp_example() {
+        let mut st = SegmentTreeBeats::from_slice(&[5, 1, 9, 3, 7]);
+        st.chmin(.., 6);
+        assert_eq!(st.to_vec(), vec![5, 1, 6, 3, 6]);
+        st.chmax(1..4, 4);
+        assert_eq!(st.to_vec(), vec![5, 4, 6, 4, 6]);
+        assert_eq!(st.sum(..), 25);
+        assert_eq!(st.max(3..3), None);
+        assert!(SegmentTreeBeats::from_slice(&[]).is_empty());
+    }
+}
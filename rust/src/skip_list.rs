@@ -0,0 +1,312 @@
+// rust/src/skip_list.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::rng::{Rng, SplitMix64};
+use std::ops::{Bound, RangeBounds};
+
+const NIL: usize = usize::MAX;
+const HEAD: usize = 0;
+const MAX_LEVEL: usize = 32;
+
+struct Node<K> {
+    // None only for the head sentinel and for freed slots
+    key: Option<K>,
+    // per level: the next node, and how many positions ahead it is (NIL counts as position len + 1)
+    links: Vec<(usize, usize)>,
+}
+
+/// Ordered set as a skip list (Pugh): a sorted linked list where each node also joins the lists of
+/// the levels above with probability 1/2, so searches drop down from sparse express lanes.
+/// Every link records its width (the number of positions it skips), which makes `rank` and `get`
+/// logarithmic too. Nodes live in an arena with a free list; the coin flips come from an
+/// injectable `Rng`, so a scripted generator can force any shape.
+/// - insert / remove / contains / rank / get: O(log n) expected
+/// - range: O(log n + k) expected for k keys
+pub struct SkipList<K, R = SplitMix64> {
+    nodes: Vec<Node<K>>,
+    free: Vec<usize>,
+    len: usize,
+    rng: R,
+}
+
+impl<K: Ord> SkipList<K> {
+    pub fn new() -> Self {
+        Self::with_rng(SplitMix64::default())
+    }
+}
+
+impl<K: Ord> Default for SkipList<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Ord, R: Rng> SkipList<K, R> {
+    pub fn with_rng(rng: R) -> Self {
+        let head = Node { key: None, links: vec![(NIL, 1)] };
+        SkipList { nodes: vec![head], free: Vec::new(), len: 0, rng }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of levels the head currently spans.
+    pub fn height(&self) -> usize {
+        self.nodes[HEAD].links.len()
+    }
+
+    fn key(&self, x: usize) -> &K {
+        self.nodes[x].key.as_ref().expect("only the head and free slots have no key")
+    }
+
+    /// 1 + the number of heads before the first tail, capped at MAX_LEVEL.
+    fn random_level(&mut self) -> usize {
+        (1 + self.rng.next_u64().trailing_ones() as usize).min(MAX_LEVEL)
+    }
+
+    /// The last node on each level whose key is below `key` (or for which `before` holds), with its
+    /// position (the head is 0).
+    fn predecessors(&self, before: impl Fn(&K) -> bool) -> Vec<(usize, usize)> {
+        let mut preds = vec![(HEAD, 0); self.height()];
+        let (mut x, mut pos) = (HEAD, 0);
+        for level in (0..self.height()).rev() {
+            loop {
+                let (next, width) = self.nodes[x].links[level];
+                if next == NIL || !before(self.key(next)) {
+                    break;
+                }
+                x = next;
+                pos += width;
+            }
+            preds[level] = (x, pos);
+        }
+        preds
+    }
+
+    fn lower_bound(&self, bound: Bound<&K>) -> usize {
+        let preds = match bound {
+            Bound::Included(k) => self.predecessors(|x| x < k),
+            Bound::Excluded(k) => self.predecessors(|x| x <= k),
+            Bound::Unbounded => return self.nodes[HEAD].links[0].0,
+        };
+        self.nodes[preds[0].0].links[0].0
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        let next = self.lower_bound(Bound::Included(key));
+        next != NIL && self.key(next) == key
+    }
+
+    /// Inserts `key`; returns false (keeping the stored key) if it was already present.
+    pub fn insert(&mut self, key: K) -> bool {
+        let mut preds = self.predecessors(|x| *x < key);
+        let next = self.nodes[preds[0].0].links[0].0;
+        if next != NIL && *self.key(next) == key {
+            return false;
+        }
+        let height = self.random_level();
+        while self.height() < height {
+            // a new top lane from the head straight to the end
+            self.nodes[HEAD].links.push((NIL, self.len + 1));
+            preds.push((HEAD, 0));
+        }
+        let pos = preds[0].1 + 1;
+        let mut links = Vec::with_capacity(height);
+        for (level, &(p, p_pos)) in preds.iter().enumerate() {
+            let (next, width) = self.nodes[p].links[level];
+            if level < height {
+                links.push((next, p_pos + width + 1 - pos));
+                self.nodes[p].links[level].1 = pos - p_pos;
+            } else {
+                self.nodes[p].links[level].1 = width + 1;
+            }
+        }
+        let node = Node { key: Some(key), links };
+        let id = match self.free.pop() {
+            Some(id) => {
+                self.nodes[id] = node;
+                id
+            }
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            }
+        };
+        for (level, &(p, _)) in preds.iter().enumerate().take(height) {
+            self.nodes[p].links[level].0 = id;
+        }
+        self.len += 1;
+        true
+    }
+
+    /// Removes `key`; returns whether it was present.
+    pub fn remove(&mut self, key: &K) -> bool {
+        let preds = self.predecessors(|x| x < key);
+        let target = self.nodes[preds[0].0].links[0].0;
+        if target == NIL || self.key(target) != key {
+            return false;
+        }
+        for (level, &(p, _)) in preds.iter().enumerate() {
+            let (next, width) = self.nodes[p].links[level];
+            self.nodes[p].links[level] = if next == target {
+                let (after, skipped) = self.nodes[target].links[level];
+                (after, width + skipped - 1)
+            } else {
+                (next, width - 1)
+            };
+        }
+        self.nodes[target] = Node { key: None, links: Vec::new() };
+        self.free.push(target);
+        self.len -= 1;
+        // drop lanes that became empty, keeping the bottom one
+        while self.height() > 1 && self.nodes[HEAD].links[self.height() - 1].0 == NIL {
+            self.nodes[HEAD].links.pop();
+        }
+        true
+    }
+
+    /// Number of keys smaller than `key`.
+    pub fn rank(&self, key: &K) -> usize {
+        self.predecessors(|x| x < key)[0].1
+    }
+
+    /// The `index`-th smallest key (0-based).
+    pub fn get(&self, index: usize) -> Option<&K> {
+        if index >= self.len {
+            return None;
+        }
+        let (mut x, mut pos) = (HEAD, 0);
+        for level in (0..self.height()).rev() {
+            loop {
+                let (next, width) = self.nodes[x].links[level];
+                if next == NIL || pos + width > index + 1 {
+                    break;
+                }
+                x = next;
+                pos += width;
+            }
+        }
+        Some(self.key(x))
+    }
+
+    pub fn first(&self) -> Option<&K> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&K> {
+        self.len.checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    /// Keys within `range`, in increasing order.
+    pub fn range<T: RangeBounds<K>>(&self, range: T) -> impl Iterator<Item = &K> + '_ {
+        let mut x = self.lower_bound(range.start_bound());
+        // the walk stops at the first node past the end
+        let stop = match range.end_bound() {
+            Bound::Included(end) => self.lower_bound(Bound::Excluded(end)),
+            Bound::Excluded(end) => self.lower_bound(Bound::Included(end)),
+            Bound::Unbounded => NIL,
+        };
+        if x != NIL && stop != NIL && self.key(x) >= self.key(stop) {
+            x = stop;
+        }
+        std::iter::from_fn(move || {
+            if x == stop {
+                return None;
+            }
+            let key = self.key(x);
+            x = self.nodes[x].links[0].0;
+            Some(key)
+        })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &K> + '_ {
+        self.range(..)
+    }
+}
+
+impl<K: Ord> FromIterator<K> for SkipList<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut list = SkipList::new();
+        iter.into_iter().for_each(|k| {
+            list.insert(k);
+        });
+        list
+    }
+}
+
+impl<K, R> DataStructure for SkipList<K, R> {
+    const INFO: Info = Info {
+        name: "SkipList",
+        category: "ordered sets",
+        summary: "Probabilistic ordered set of linked express lanes with link widths for rank and select",
+        space: "O(n) expected",
+        operations: &[
+            Operation::new("insert", "O(log n) expected", "O(log n)"),
+            Operation::new("remove", "O(log n) expected", "O(log n)"),
+            Operation::new("contains", "O(log n) expected", "O(1)"),
+            Operation::new("rank", "O(log n) expected", "O(log n)"),
+            Operation::new("get", "O(log n) expected", "O(1)"),
+            Operation::new("range", "O(log n + k) expected", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_set_operations() {
+        let mut list: SkipList<i32> = [5, 1, 9, 3, 7].into_iter().collect();
+        assert!(!list.insert(3));
+        assert_eq!(list.len(), 5);
+        assert!(list.contains(&7) && !list.contains(&4));
+        assert_eq!(list.iter().copied().collect::<Vec<_>>(), vec![1, 3, 5, 7, 9]);
+        assert_eq!(list.range(3..=7).copied().collect::<Vec<_>>(), vec![3, 5, 7]);
+        assert_eq!(list.range(4..9).copied().collect::<Vec<_>>(), vec![5, 7]);
+        assert_eq!((list.rank(&6), list.rank(&0), list.rank(&100)), (3, 0, 5));
+        assert_eq!((list.get(1), list.get(5)), (Some(&3), None));
+        assert!(list.remove(&1) && !list.remove(&1));
+        assert_eq!((list.first(), list.last()), (Some(&3), Some(&9)));
+
+        // a scripted generator that never flips heads degenerates into a plain sorted list
+        let mut flat = SkipList::with_rng(|| 0u64);
+        (0..10).rev().for_each(|k| {
+            flat.insert(k);
+        });
+        assert_eq!(flat.height(), 1);
+        assert_eq!(flat.get(4), Some(&4));
+    }
+
+    #[test]
+    fn test_against_btreeset() {
+        let mut next = test_rng(3);
+        let mut list = SkipList::with_rng(SplitMix64::new(99));
+        let mut reference = BTreeSet::new();
+        for _ in 0..5000 {
+            let key = next() % 300;
+            match next() % 3 {
+                0 => assert_eq!(list.insert(key), reference.insert(key)),
+                1 => assert_eq!(list.remove(&key), reference.remove(&key)),
+                _ => {
+                    assert_eq!(list.contains(&key), reference.contains(&key));
+                    assert_eq!(list.rank(&key), reference.range(..key).count());
+                    assert_eq!(list.get(key as usize % 50), reference.iter().nth(key as usize % 50));
+                    let hi = key + next() % 40;
+                    assert!(list.range(key..hi).eq(reference.range(key..hi)));
+                }
+            }
+            assert_eq!(list.len(), reference.len());
+        }
+        assert!(list.iter().eq(reference.iter()));
+    }
+}
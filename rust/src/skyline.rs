@@ -0,0 +1,70 @@
+// rust/src/skyline.rs
+use crate::treap::Treap;
+
+/// Computes the skyline (outline) formed by a set of buildings.
+/// Each building is `(left, right, height)` and covers x in [left, right).
+/// Returns the key points `(x, height)` where the outline height changes, ending with a point at height 0.
+///
+/// A sweep over building edges keeps the heights of the active buildings in a `Treap` multiset,
+/// so the current outline height is its maximum.
+/// Complexity: O(n log n)
+pub fn skyline(buildings: &[(i64, i64, i64)]) -> Vec<(i64, i64)> {
+    // (x, is_end, height); starts come before ends at the same x
+    let mut events: Vec<(i64, bool, i64)> = Vec::with_capacity(2 * buildings.len());
+    for &(left, right, height) in buildings {
+        if left < right && height > 0 {
+            events.push((left, false, height));
+            events.push((right, true, height));
+        }
+    }
+    events.sort_unstable();
+
+    let mut active = Treap::new();
+    let mut res: Vec<(i64, i64)> = Vec::new();
+    let mut i = 0;
+    while i < events.len() {
+        let x = events[i].0;
+        while i < events.len() && events[i].0 == x {
+            let (_, is_end, height) = events[i];
+            if is_end {
+                active.remove(height);
+            } else {
+                active.insert(height);
+            }
+            i += 1;
+        }
+        let height = active.max().unwrap_or(0);
+        if res.last().map(|&(_, h)| h) != Some(height) {
+            res.push((x, height));
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classic_example() {
+        let buildings = [(2, 9, 10), (3, 7, 15), (5, 12, 12), (15, 20, 10), (19, 24, 8)];
+        assert_eq!(
+            skyline(&buildings),
+            vec![(2, 10), (3, 15), (7, 12), (12, 0), (15, 10), (20, 8), (24, 0)]
+        );
+    }
+
+    #[test]
+    fn test_adjacent_and_equal_heights() {
+        // same height buildings touching merge into one flat segment
+        assert_eq!(skyline(&[(0, 2, 3), (2, 5, 3)]), vec![(0, 3), (5, 0)]);
+        // identical buildings
+        assert_eq!(skyline(&[(1, 4, 2), (1, 4, 2)]), vec![(1, 2), (4, 0)]);
+    }
+
+    #[test]
+    fn test_empty() {
+        assert!(skyline(&[]).is_empty());
+        assert!(skyline(&[(3, 3, 5), (1, 2, 0)]).is_empty());
+    }
+}
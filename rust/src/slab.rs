@@ -0,0 +1,206 @@
+// rust/src/slab.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::ops::{Index, IndexMut};
+
+/// Reference to an entry of a `Slab`: the slot index together with the slot's generation when the
+/// entry was inserted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Handle {
+    index: usize,
+    generation: u64,
+}
+
+impl Handle {
+    /// The slot index, dense in `0..capacity`, for side tables indexed alongside the slab.
+    pub fn index(self) -> usize {
+        self.index
+    }
+}
+
+#[derive(Debug)]
+struct Entry<T> {
+    value: Option<T>,
+    generation: u64,
+}
+
+/// Vector of values addressed by generational handles, the arena behind the crate's linked
+/// structures (`SlabList`, `CircularList`, `PairingHeap`, `FibonacciHeap`) and the point store of
+/// `SpatialHash`. Removing an entry frees its slot for the next insertion and bumps the slot's
+/// generation, so a handle to a removed entry never matches again, even once its slot holds
+/// something else: lookups through it return None instead of silently reaching the new entry.
+/// - insert / remove / get: O(1)
+/// - iter: O(capacity)
+#[derive(Debug)]
+pub struct Slab<T> {
+    entries: Vec<Entry<T>>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<T> Default for Slab<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Slab<T> {
+    pub fn new() -> Self {
+        Slab { entries: Vec::new(), free: Vec::new(), len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of slots, live or free; every `Handle::index` is below it.
+    pub fn capacity(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Stores `value` and returns its handle.
+    pub fn insert(&mut self, value: T) -> Handle {
+        self.insert_with(|_| value)
+    }
+
+    /// Stores the value `make` builds from the handle it is about to get, for entries that refer
+    /// to themselves (such as a one-element cycle).
+    pub fn insert_with(&mut self, make: impl FnOnce(Handle) -> T) -> Handle {
+        let index = self.free.pop().unwrap_or_else(|| {
+            self.entries.push(Entry { value: None, generation: 0 });
+            self.entries.len() - 1
+        });
+        let h = Handle { index, generation: self.entries[index].generation };
+        self.entries[index].value = Some(make(h));
+        self.len += 1;
+        h
+    }
+
+    /// Removes the entry at `h` and returns it, or None if `h` is stale.
+    pub fn remove(&mut self, h: Handle) -> Option<T> {
+        if !self.contains(h) {
+            return None;
+        }
+        let entry = &mut self.entries[h.index];
+        entry.generation += 1;
+        self.free.push(h.index);
+        self.len -= 1;
+        entry.value.take()
+    }
+
+    /// Whether `h` refers to a live entry.
+    pub fn contains(&self, h: Handle) -> bool {
+        self.get(h).is_some()
+    }
+
+    pub fn get(&self, h: Handle) -> Option<&T> {
+        self.entries.get(h.index).filter(|e| e.generation == h.generation)?.value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, h: Handle) -> Option<&mut T> {
+        self.entries.get_mut(h.index).filter(|e| e.generation == h.generation)?.value.as_mut()
+    }
+
+    /// Iterates `(handle, &value)` over the live entries by slot index.
+    pub fn iter(&self) -> impl Iterator<Item = (Handle, &T)> + '_ {
+        self.entries.iter().enumerate().filter_map(|(index, e)| {
+            e.value.as_ref().map(|v| (Handle { index, generation: e.generation }, v))
+        })
+    }
+
+    /// The live values by slot index, consuming the slab.
+    pub fn into_values(self) -> impl Iterator<Item = T> {
+        self.entries.into_iter().filter_map(|e| e.value)
+    }
+}
+
+/// Panics if `h` is stale; for handles the caller knows are live, such as the links of a list.
+impl<T> Index<Handle> for Slab<T> {
+    type Output = T;
+
+    fn index(&self, h: Handle) -> &T {
+        self.get(h).expect("stale slab handle")
+    }
+}
+
+impl<T> IndexMut<Handle> for Slab<T> {
+    fn index_mut(&mut self, h: Handle) -> &mut T {
+        self.get_mut(h).expect("stale slab handle")
+    }
+}
+
+impl<T> DataStructure for Slab<T> {
+    const INFO: Info = Info {
+        name: "Slab",
+        category: "lists",
+        summary: "Arena of values addressed by generational handles that detect use after removal",
+        space: "O(capacity)",
+        operations: &[
+            Operation::new("insert", "O(1) amortized", "O(1)"),
+            Operation::new("remove", "O(1)", "O(1)"),
+            Operation::new("get", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_stale_handles_after_reuse() {
+        let mut slab = Slab::new();
+        let a = slab.insert("a");
+        let b = slab.insert("b");
+        assert_eq!((slab.len(), slab[a], slab.get(b)), (2, "a", Some(&"b")));
+        assert_eq!(slab.remove(a), Some("a"));
+        assert_eq!(slab.remove(a), None);
+
+        // the slot comes back under a new generation; the old handle does not reach it
+        let c = slab.insert("c");
+        assert_eq!((c.index(), slab.capacity()), (a.index(), 2));
+        assert_ne!(c, a);
+        assert!(!slab.contains(a) && slab.get(a).is_none() && slab.get_mut(a).is_none());
+        assert_eq!(slab.remove(a), None);
+        assert_eq!(slab[c], "c");
+
+        let mut handles = Slab::new();
+        let d = handles.insert_with(|h| h);
+        assert_eq!(handles[d], d);
+        let live: Vec<Handle> = slab.iter().map(|(h, _)| h).collect();
+        assert_eq!(live.len(), 2);
+        assert!(live.contains(&b) && live.contains(&c) && !live.contains(&a));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(164);
+        let mut slab = Slab::new();
+        // every handle ever issued, with the value it holds while live
+        let mut issued: Vec<(Handle, Option<usize>)> = Vec::new();
+        for step in 0..3000 {
+            if issued.is_empty() || !next().is_multiple_of(3) {
+                issued.push((slab.insert(step), Some(step)));
+            } else {
+                let i = next() as usize % issued.len();
+                let (h, expected) = issued[i];
+                assert_eq!(slab.remove(h), expected);
+                issued[i].1 = None;
+            }
+            let (h, expected) = issued[next() as usize % issued.len()];
+            assert_eq!(slab.get(h).copied(), expected);
+            assert_eq!(slab.len(), issued.iter().filter(|(_, v)| v.is_some()).count());
+        }
+        let mut values: Vec<usize> = slab.into_values().collect();
+        values.sort_unstable();
+        let expected: Vec<usize> = issued.iter().filter_map(|&(_, v)| v).collect();
+        assert_eq!(values, expected);
+    }
+}
@@ -0,0 +1,260 @@
+// rust/src/slab_list.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::slab::{Handle, Slab};
+#[derive(Debug)]
+struct Slot<T> {
+    value: T,
+    prev: Option<Handle>,
+    next: Option<Handle>,
+}
+
+/// A doubly linked list stored in a `Slab` (arena), addressed by stable handles.
+/// A handle stays valid until its element is removed, so LRU caches, order-maintenance
+/// and simulations can unlink or splice elements in O(1) without searching.
+/// Freed slots are reused by later insertions, but the slab's generations keep a removed
+/// element's handle from reaching whatever takes its slot.
+///
+/// - push_front / push_back / insert_after / insert_before / remove / move_to_front: O(1)
+/// - iter: O(n)
+pub struct SlabList<T> {
+    slots: Slab<Slot<T>>,
+    head: Option<Handle>,
+    tail: Option<Handle>,
+}
+
+impl<T> Default for SlabList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SlabList<T> {
+    pub fn new() -> Self {
+        SlabList {
+            slots: Slab::new(),
+            head: None,
+            tail: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.slots.is_empty()
+    }
+
+    pub fn front(&self) -> Option<Handle> {
+        self.head
+    }
+
+    pub fn back(&self) -> Option<Handle> {
+        self.tail
+    }
+
+    /// Links the detached slot `h` between `prev` and `next`.
+    fn link(&mut self, h: Handle, prev: Option<Handle>, next: Option<Handle>) {
+        self.slots[h].prev = prev;
+        self.slots[h].next = next;
+        match prev {
+            Some(p) => self.slots[p].next = Some(h),
+            None => self.head = Some(h),
+        }
+        match next {
+            Some(n) => self.slots[n].prev = Some(h),
+            None => self.tail = Some(h),
+        }
+    }
+
+    /// Detaches slot `h` from its neighbours without freeing it.
+    fn unlink(&mut self, h: Handle) {
+        let (prev, next) = (self.slots[h].prev, self.slots[h].next);
+        match prev {
+            Some(p) => self.slots[p].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(n) => self.slots[n].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    /// Stores `value` and links it between `prev` and `next`.
+    fn insert_between(&mut self, value: T, prev: Option<Handle>, next: Option<Handle>) -> Handle {
+        let h = self.slots.insert(Slot { value, prev: None, next: None });
+        self.link(h, prev, next);
+        h
+    }
+
+    pub fn push_front(&mut self, value: T) -> Handle {
+        self.insert_between(value, None, self.head)
+    }
+
+    pub fn push_back(&mut self, value: T) -> Handle {
+        self.insert_between(value, self.tail, None)
+    }
+
+    /// Inserts `value` right after `h`. Returns None if `h` is not a live handle.
+    pub fn insert_after(&mut self, h: Handle, value: T) -> Option<Handle> {
+        let next = self.slots.get(h)?.next;
+        Some(self.insert_between(value, Some(h), next))
+    }
+
+    /// Inserts `value` right before `h`. Returns None if `h` is not a live handle.
+    pub fn insert_before(&mut self, h: Handle, value: T) -> Option<Handle> {
+        let prev = self.slots.get(h)?.prev;
+        Some(self.insert_between(value, prev, Some(h)))
+    }
+
+    /// Unlinks element `h` and returns its value. The handle becomes invalid.
+    pub fn remove(&mut self, h: Handle) -> Option<T> {
+        if !self.slots.contains(h) {
+            return None;
+        }
+        self.unlink(h);
+        self.slots.remove(h).map(|slot| slot.value)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.and_then(|h| self.remove(h))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.and_then(|h| self.remove(h))
+    }
+
+    /// Moves element `h` to the front, keeping its handle. Returns false if `h` is not live.
+    pub fn move_to_front(&mut self, h: Handle) -> bool {
+        if !self.slots.contains(h) {
+            return false;
+        }
+        if self.head != Some(h) {
+            self.unlink(h);
+            self.link(h, None, self.head);
+        }
+        true
+    }
+
+    pub fn get(&self, h: Handle) -> Option<&T> {
+        self.slots.get(h).map(|slot| &slot.value)
+    }
+
+    pub fn get_mut(&mut self, h: Handle) -> Option<&mut T> {
+        self.slots.get_mut(h).map(|slot| &mut slot.value)
+    }
+
+    /// Handle of the element after `h`; None at the back or if `h` is not live.
+    pub fn next(&self, h: Handle) -> Option<Handle> {
+        self.slots.get(h)?.next
+    }
+
+    /// Handle of the element before `h`; None at the front or if `h` is not live.
+    pub fn prev(&self, h: Handle) -> Option<Handle> {
+        self.slots.get(h)?.prev
+    }
+
+    /// Iterates `(handle, &value)` pairs from front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { list: self, cur: self.head }
+    }
+}
+
+pub struct Iter<'a, T> {
+    list: &'a SlabList<T>,
+    cur: Option<Handle>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (Handle, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let h = self.cur?;
+        let slot = &self.list.slots[h];
+        self.cur = slot.next;
+        Some((h, &slot.value))
+    }
+}
+
+impl<T> DataStructure for SlabList<T> {
+    const INFO: Info = Info {
+        name: "SlabList",
+        category: "lists",
+        summary: "Doubly linked list in a slab, addressed by stable handles",
+        space: "O(n)",
+        operations: &[
+            Operation::new("push_front", "O(1)", "O(1)"),
+            Operation::new("push_back", "O(1)", "O(1)"),
+            Operation::new("insert_after", "O(1)", "O(1)"),
+            Operation::new("remove", "O(1)", "O(1)"),
+            Operation::new("move_to_front", "O(1)", "O(1)"),
+            Operation::new("iter", "O(n)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values<T: Copy>(list: &SlabList<T>) -> Vec<T> {
+        list.iter().map(|(_, &v)| v).collect()
+    }
+
+    #[test]
+    fn test_push_insert_iterate() {
+        let mut list = SlabList::new();
+        let b = list.push_back(2);
+        let a = list.push_front(1);
+        let d = list.push_back(4);
+        let c = list.insert_after(b, 3).unwrap();
+        list.insert_before(a, 0).unwrap();
+        assert_eq!(values(&list), vec![0, 1, 2, 3, 4]);
+        assert_eq!(list.len(), 5);
+        assert_eq!(list.next(b), Some(c));
+        assert_eq!(list.prev(c), Some(b));
+        assert_eq!(list.next(d), None);
+        assert_eq!(list.back(), Some(d));
+    }
+
+    #[test]
+    fn test_remove_and_stable_handles() {
+        let mut list = SlabList::new();
+        let handles: Vec<Handle> = (0..5).map(|v| list.push_back(v)).collect();
+        assert_eq!(list.remove(handles[2]), Some(2));
+        assert_eq!(list.remove(handles[2]), None);
+        // remaining handles still address the same values
+        assert_eq!(list.get(handles[3]), Some(&3));
+        assert_eq!(list.next(handles[1]), Some(handles[3]));
+        assert_eq!(list.pop_front(), Some(0));
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(values(&list), vec![1, 3]);
+        // the freed slot gets reused, but the removed element's handle does not reach its new value
+        let h = list.push_back(9);
+        assert!(handles.iter().any(|old| old.index() == h.index()) && !handles.contains(&h));
+        assert_eq!((list.get(handles[2]), list.next(handles[2])), (None, None));
+        assert_eq!(list.remove(handles[4]), None);
+        assert!(list.insert_after(handles[0], 7).is_none() && !list.move_to_front(handles[0]));
+        assert_eq!(values(&list), vec![1, 3, 9]);
+    }
+
+    #[test]
+    fn test_move_to_front() {
+        let mut list = SlabList::new();
+        let a = list.push_back('a');
+        let b = list.push_back('b');
+        let c = list.push_back('c');
+        assert!(list.move_to_front(c));
+        assert_eq!(values(&list), vec!['c', 'a', 'b']);
+        assert!(list.move_to_front(b));
+        assert_eq!(values(&list), vec!['b', 'c', 'a']);
+        assert_eq!(list.back(), Some(a));
+        assert!(list.move_to_front(b));
+        assert_eq!(values(&list), vec!['b', 'c', 'a']);
+        list.remove(a);
+        assert!(!list.move_to_front(a));
+    }
+}
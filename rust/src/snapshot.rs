@@ -0,0 +1,257 @@
+// rust/src/snapshot.rs
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const MAGIC: [u8; 4] = *b"DSAS";
+/// Bumped whenever the encoding of any structure changes; older files are then rejected.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// Compact binary snapshots, so that indexes which are expensive to build can be saved once and
+/// loaded quickly later.
+///
+/// File layout (all integers little-endian):
+/// - magic `b"DSAS"`, format version (u32), structure kind (4 bytes), body length (u64)
+/// - body: the structure's fields as written by `encode`; vectors are a u64 length followed by
+///   fixed-width elements, so large arrays are stored contiguously
+///
+/// Loading checks the header, that the body has exactly the declared length, and that the decoded
+/// fields are consistent with each other. Anything else fails with `io::ErrorKind::InvalidData`.
+pub trait Snapshot: Sized {
+    /// Identifies the structure in the header, so loading a file as the wrong type fails cleanly.
+    const KIND: [u8; 4];
+
+    fn encode(&self, out: &mut Encoder);
+
+    /// Reads back what `encode` wrote. Returns None on truncated or inconsistent data.
+    fn decode(input: &mut Decoder<'_>) -> Option<Self>;
+
+    fn to_snapshot(&self) -> Vec<u8> {
+        let mut body = Encoder::default();
+        self.encode(&mut body);
+        let mut out = Vec::with_capacity(20 + body.buf.len());
+        out.extend_from_slice(&MAGIC);
+        out.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        out.extend_from_slice(&Self::KIND);
+        out.extend_from_slice(&(body.buf.len() as u64).to_le_bytes());
+        out.extend_from_slice(&body.buf);
+        out
+    }
+
+    fn from_snapshot(bytes: &[u8]) -> io::Result<Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+        let mut header = Decoder::new(bytes);
+        if header.array::<4>() != Some(MAGIC) {
+            return Err(invalid("not a snapshot file"));
+        }
+        match header.u32() {
+            Some(FORMAT_VERSION) => {}
+            Some(v) => return Err(invalid(&format!("unsupported snapshot version {}", v))),
+            None => return Err(invalid("truncated header")),
+        }
+        match header.array::<4>() {
+            Some(kind) if kind == Self::KIND => {}
+            Some(kind) => {
+                return Err(invalid(&format!(
+                    "snapshot holds {}, expected {}",
+                    String::from_utf8_lossy(&kind),
+                    String::from_utf8_lossy(&Self::KIND)
+                )));
+            }
+            None => return Err(invalid("truncated header")),
+        }
+        let len = header.usize().ok_or_else(|| invalid("truncated header"))?;
+        if header.remaining() != len {
+            return Err(invalid("snapshot body has the wrong length"));
+        }
+        let mut body = Decoder::new(&bytes[bytes.len() - len..]);
+        match Self::decode(&mut body) {
+            Some(value) if body.remaining() == 0 => Ok(value),
+            _ => Err(invalid("corrupt snapshot body")),
+        }
+    }
+
+    fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_snapshot())
+    }
+
+    fn load<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_snapshot(&fs::read(path)?)
+    }
+}
+
+/// Fixed-width little-endian values that can be stored in bulk.
+pub trait Scalar: Copy {
+    const WIDTH: usize;
+    fn put(self, out: &mut Vec<u8>);
+    fn get(bytes: &[u8]) -> Self;
+}
+
+macro_rules! impl_scalar {
+    ($($t:ty),*) => {$(
+        impl Scalar for $t {
+            const WIDTH: usize = std::mem::size_of::<$t>();
+            fn put(self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+            fn get(bytes: &[u8]) -> Self {
+                <$t>::from_le_bytes(bytes.try_into().expect("slice has WIDTH bytes"))
+            }
+        }
+    )*};
+}
+
+impl_scalar!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+/// Body writer handed to `Snapshot::encode`. `usize` is always stored as u64.
+#[derive(Default)]
+pub struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn scalar<T: Scalar>(&mut self, v: T) {
+        v.put(&mut self.buf);
+    }
+
+    pub fn usize(&mut self, v: usize) {
+        self.scalar(v as u64);
+    }
+
+    pub fn slice<T: Scalar>(&mut self, values: &[T]) {
+        self.usize(values.len());
+        self.buf.reserve(values.len() * T::WIDTH);
+        for &v in values {
+            v.put(&mut self.buf);
+        }
+    }
+
+    pub fn usizes(&mut self, values: &[usize]) {
+        self.usize(values.len());
+        for &v in values {
+            self.scalar(v as u64);
+        }
+    }
+}
+
+/// Body reader handed to `Snapshot::decode`. Every read returns None once the input runs out.
+pub struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Decoder { data, pos: 0 }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.data.len() - self.pos
+    }
+
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        let end = self.pos.checked_add(n).filter(|&end| end <= self.data.len())?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Some(bytes)
+    }
+
+    pub fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    pub fn scalar<T: Scalar>(&mut self) -> Option<T> {
+        Some(T::get(self.take(T::WIDTH)?))
+    }
+
+    pub fn u32(&mut self) -> Option<u32> {
+        self.scalar()
+    }
+
+    pub fn usize(&mut self) -> Option<usize> {
+        usize::try_from(self.scalar::<u64>()?).ok()
+    }
+
+    fn len_for(&mut self, width: usize) -> Option<usize> {
+        let len = self.usize()?;
+        // reject lengths the remaining input cannot hold before allocating anything
+        (len.checked_mul(width)? <= self.remaining()).then_some(len)
+    }
+
+    pub fn vec<T: Scalar>(&mut self) -> Option<Vec<T>> {
+        let len = self.len_for(T::WIDTH)?;
+        let bytes = self.take(len * T::WIDTH)?;
+        Some(bytes.chunks_exact(T::WIDTH).map(T::get).collect())
+    }
+
+    pub fn usizes(&mut self) -> Option<Vec<usize>> {
+        let len = self.len_for(8)?;
+        (0..len).map(|_| self.usize()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Pair {
+        name: Vec<u8>,
+        ids: Vec<usize>,
+    }
+
+    impl Snapshot for Pair {
+        const KIND: [u8; 4] = *b"PAIR";
+
+        fn encode(&self, out: &mut Encoder) {
+            out.slice(&self.name);
+            out.usizes(&self.ids);
+        }
+
+        fn decode(input: &mut Decoder<'_>) -> Option<Self> {
+            Some(Pair {
+                name: input.vec()?,
+                ids: input.usizes()?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_round_trip_and_corruption() {
+        let p = Pair {
+            name: b"abc".to_vec(),
+            ids: vec![1, 2, 300],
+        };
+        let bytes = p.to_snapshot();
+        let back = Pair::from_snapshot(&bytes).unwrap();
+        assert_eq!((back.name, back.ids), (p.name, p.ids));
+
+        let kind = |r: io::Result<Pair>| r.err().map(|e| e.kind());
+        assert_eq!(kind(Pair::from_snapshot(&bytes[..bytes.len() - 1])), Some(io::ErrorKind::InvalidData));
+        assert_eq!(kind(Pair::from_snapshot(b"nope")), Some(io::ErrorKind::InvalidData));
+        let mut wrong_version = bytes.clone();
+        wrong_version[4] = 9;
+        assert_eq!(kind(Pair::from_snapshot(&wrong_version)), Some(io::ErrorKind::InvalidData));
+        let mut wrong_kind = bytes.clone();
+        wrong_kind[8..12].copy_from_slice(b"XXXX");
+        let err = Pair::from_snapshot(&wrong_kind).err().unwrap();
+        assert!(err.to_string().contains("expected PAIR"), "{}", err);
+        // a vector length larger than the file is rejected without allocating
+        let mut huge = bytes.clone();
+        huge[20..28].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(kind(Pair::from_snapshot(&huge)), Some(io::ErrorKind::InvalidData));
+    }
+
+    #[test]
+    fn test_save_and_load_file() {
+        let path = std::env::temp_dir().join(format!("dsa-snapshot-{}.bin", std::process::id()));
+        let p = Pair {
+            name: vec![],
+            ids: vec![7],
+        };
+        p.save(&path).unwrap();
+        let back = Pair::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(back.ids, vec![7]);
+        assert_eq!(Pair::load(&path).err().map(|e| e.kind()), Some(io::ErrorKind::NotFound));
+    }
+}
@@ -0,0 +1,170 @@
+// rust/src/sparse_set.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+
+/// Set of integers from `0..universe` as a pair of arrays (Briggs and Torczon): `dense` lists the
+/// members in its first `len` slots, and `sparse[x]` is where `x` sits in `dense`. A member is
+/// recognised by the two pointing at each other, so stale entries in `sparse` are harmless and
+/// `clear` only forgets the length. That makes it the visited set for searches repeated many
+/// times over one graph: no O(universe) reset between runs, and iteration costs O(len), not
+/// O(universe). Removal swaps the last member into the hole, so iteration order is insertion order
+/// only until the first removal.
+/// - insert / remove / contains: O(1)
+/// - clear: O(1)
+/// - iter: O(len)
+#[derive(Debug, Clone)]
+pub struct SparseSet {
+    dense: Vec<usize>,
+    sparse: Vec<usize>,
+}
+
+impl SparseSet {
+    /// An empty set for values in `0..universe`.
+    pub fn new(universe: usize) -> Self {
+        SparseSet { dense: Vec::with_capacity(universe), sparse: vec![0; universe] }
+    }
+
+    /// The exclusive upper bound on values.
+    pub fn universe(&self) -> usize {
+        self.sparse.len()
+    }
+
+    pub fn len(&self) -> usize {
+        self.dense.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dense.is_empty()
+    }
+
+    /// False for values outside the universe.
+    pub fn contains(&self, value: usize) -> bool {
+        self.sparse.get(value).is_some_and(|&i| self.dense.get(i) == Some(&value))
+    }
+
+    /// Adds `value`; returns false if it was already present. Panics if it is outside the universe.
+    pub fn insert(&mut self, value: usize) -> bool {
+        assert!(value < self.universe(), "value {} outside the universe 0..{}", value, self.universe());
+        if self.contains(value) {
+            return false;
+        }
+        self.sparse[value] = self.dense.len();
+        self.dense.push(value);
+        true
+    }
+
+    /// Removes `value`; returns false if it was absent.
+    pub fn remove(&mut self, value: usize) -> bool {
+        if !self.contains(value) {
+            return false;
+        }
+        let i = self.sparse[value];
+        let last = self.dense.pop().expect("a member exists");
+        if last != value {
+            self.dense[i] = last;
+            self.sparse[last] = i;
+        }
+        true
+    }
+
+    /// Empties the set in O(1).
+    pub fn clear(&mut self) {
+        self.dense.clear();
+    }
+
+    /// The members, in insertion order until the first removal.
+    pub fn as_slice(&self) -> &[usize] {
+        &self.dense
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.dense.iter().copied()
+    }
+}
+
+impl Extend<usize> for SparseSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| {
+            self.insert(value);
+        });
+    }
+}
+
+impl DataStructure for SparseSet {
+    const INFO: Info = Info {
+        name: "SparseSet",
+        category: "sets",
+        summary: "Bounded integer set with O(1) insert, remove, contains and clear, and O(len) iteration",
+        space: "O(universe)",
+        operations: &[
+            Operation::new("insert / remove / contains", "O(1)", "O(1)"),
+            Operation::new("clear", "O(1)", "O(1)"),
+            Operation::new("iter", "O(len)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_insert_remove_clear() {
+        let mut set = SparseSet::new(10);
+        assert!(set.insert(7) && set.insert(2) && set.insert(9) && !set.insert(2));
+        assert_eq!(set.as_slice(), &[7, 2, 9]);
+        assert!(set.remove(7) && !set.remove(7) && !set.remove(42));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![9, 2]);
+        assert!(set.contains(9) && !set.contains(7) && !set.contains(10));
+
+        // stale `sparse` entries left by clear must not make anything look present
+        set.clear();
+        assert!(set.is_empty() && !set.contains(9) && !set.contains(2));
+        set.extend([2, 4, 2]);
+        assert_eq!((set.len(), set.contains(2), set.contains(9)), (2, true, false));
+
+        // per-source BFS over one graph, reusing the visited set without resetting it
+        let adj = [vec![1], vec![2], vec![0], vec![4], vec![]];
+        let mut visited = SparseSet::new(adj.len());
+        let reach: Vec<usize> = (0..adj.len())
+            .map(|s| {
+                visited.clear();
+                visited.insert(s);
+                let mut i = 0;
+                while let Some(&u) = visited.as_slice().get(i) {
+                    for &v in &adj[u] {
+                        visited.insert(v);
+                    }
+                    i += 1;
+                }
+                visited.len()
+            })
+            .collect();
+        assert_eq!(reach, vec![3, 3, 3, 2, 1]);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(149);
+        let mut set = SparseSet::new(64);
+        let mut naive = [false; 64];
+        for _ in 0..5000 {
+            let v = next() as usize % 64;
+            match next() % 20 {
+                0 => {
+                    set.clear();
+                    naive = [false; 64];
+                }
+                1..=9 => assert_eq!(set.insert(v), !std::mem::replace(&mut naive[v], true)),
+                _ => assert_eq!(set.remove(v), std::mem::replace(&mut naive[v], false)),
+            }
+            let mut members: Vec<usize> = set.iter().collect();
+            members.sort_unstable();
+            assert_eq!(members, (0..64).filter(|&x| naive[x]).collect::<Vec<_>>());
+            assert!((0..66).all(|x| set.contains(x) == naive.get(x).copied().unwrap_or(false)));
+        }
+    }
+}
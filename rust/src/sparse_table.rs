@@ -1,3 +1,9 @@
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::range::{self, RangeQuery};
+use crate::snapshot::{Decoder, Encoder, Scalar, Snapshot};
+use std::ops::RangeBounds;
+
 /// Sparse Table for immutable array queries where the operation is idempotent (like min, gcd).
 /// - Build: O(n log n)
 /// - Query: O(1)
@@ -55,6 +61,83 @@ where
         let right = self.table[k][r + 1 - (1 << k)];
         Some(std::cmp::min(left, right))
     }
+
+    pub fn len(&self) -> usize {
+        self.table.first().map_or(0, Vec::len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Minimum over `range`, using the crate's half-open convention (`2..5`, `..=3`, `..`).
+    /// Returns None for an empty range and panics if the range is out of bounds, like slice indexing.
+    pub fn min<R: RangeBounds<usize>>(&self, range: R) -> Option<T> {
+        let r = range::resolve(range, self.len());
+        if r.is_empty() { None } else { self.query(r.start, r.end - 1) }
+    }
+}
+
+impl<T: Copy + Ord> DataStructure for SparseTable<T> {
+    const INFO: Info = Info {
+        name: "SparseTable",
+        category: "range queries",
+        summary: "Static range-minimum table with constant-time queries",
+        space: "O(n log n)",
+        operations: &[
+            Operation::new("from_slice", "O(n log n)", "O(n log n)"),
+            Operation::new("query", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+/// Static, so it implements `RangeQuery` but not `PointUpdate`.
+impl<T: Copy + Ord> RangeQuery for SparseTable<T> {
+    type Item = T;
+    type Output = Option<T>;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> Option<T> {
+        self.min(range)
+    }
+}
+
+/// Stores the table rows; the log lookup is rebuilt on load.
+impl<T> Snapshot for SparseTable<T>
+where
+    T: Copy + Ord + Scalar,
+{
+    const KIND: [u8; 4] = *b"SPTB";
+
+    fn encode(&self, out: &mut Encoder) {
+        out.usize(self.table.len());
+        for row in &self.table {
+            out.slice(row);
+        }
+    }
+
+    fn decode(input: &mut Decoder<'_>) -> Option<Self> {
+        let levels = input.usize()?;
+        let mut table: Vec<Vec<T>> = Vec::new();
+        for _ in 0..levels {
+            table.push(input.vec()?);
+        }
+        let n = table.first().map_or(0, Vec::len);
+        let mut log = vec![0usize; n + 1];
+        for i in 2..=n {
+            log[i] = log[i / 2] + 1;
+        }
+        // row k holds the minima of the n - 2^k + 1 windows of length 2^k
+        let expected_levels = if n == 0 { 0 } else { log[n] + 1 };
+        let shaped =
+            levels == expected_levels && table.iter().enumerate().all(|(k, row)| row.len() == n + 1 - (1 << k));
+        shaped.then_some(SparseTable { table, log })
+    }
 }
 
 #[cfg(test)]
@@ -83,4 +166,37 @@ mod tests {
         assert_eq!(st2.query(2, 1), None);
         assert_eq!(st2.query(0, 10), None);
     }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let arr: Vec<i64> = vec![5, -2, 4, 7, 1, 3, 9, 0, 6];
+        let st = SparseTable::from_slice(&arr);
+        let back = SparseTable::<i64>::from_snapshot(&st.to_snapshot()).unwrap();
+        for l in 0..arr.len() {
+            for r in l..arr.len() {
+                assert_eq!(back.query(l, r), st.query(l, r));
+            }
+        }
+        let empty = SparseTable::<i64>::from_snapshot(&SparseTable::<i64>::from_slice(&[]).to_snapshot()).unwrap();
+        assert_eq!(empty.query(0, 0), None);
+        // element types are not interchangeable, and neither are structure kinds
+        assert!(SparseTable::<i32>::from_snapshot(&st.to_snapshot()).is_err());
+        assert!(crate::suffix_array::SuffixArray::from_snapshot(&st.to_snapshot()).is_err());
+    }
+
+    #[test]
+    fn test_min_over_ranges() {
+        let arr = vec![5, 2, 4, 7, 1, 3];
+        let st = SparseTable::from_slice(&arr);
+        assert_eq!(st.len(), 6);
+        for l in 0..=arr.len() {
+            for r in l..=arr.len() {
+                assert_eq!(st.min(l..r), arr[l..r].iter().min().copied());
+            }
+        }
+        assert_eq!(st.min(1..=4), st.query(1, 4));
+        assert_eq!(st.min(..), Some(1));
+        assert_eq!(st.min(3..3), None);
+        assert!(SparseTable::<i64>::from_slice(&[]).min(..).is_none());
+    }
 }
@@ -0,0 +1,205 @@
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::slab::{Handle, Slab};
+use std::collections::HashMap;
+
+/// A uniform-grid spatial hash over 2D points.
+/// Space is cut into square cells of side `cell_size`, and each point is bucketed by the cell it falls in.
+/// A radius query only has to look at the cells overlapping the query's bounding box, so for
+/// radii close to the cell size it touches a constant number of buckets. When the box covers more
+/// cells than are occupied, it walks the occupied cells instead, so a huge radius costs O(n).
+/// Points are kept in a generational `Slab`: removed slots are reused, and a removed point's
+/// handle never matches the point that takes over its slot.
+///
+/// - insert / remove: O(1) expected
+/// - neighbors_within(p, r): O(min((r / cell_size + 1)^2, occupied cells) + k)
+pub struct SpatialHash {
+    cell_size: f64,
+    cells: HashMap<(i64, i64), Vec<Handle>>,
+    points: Slab<(f64, f64)>,
+}
+
+impl SpatialHash {
+    /// Creates an empty grid. `cell_size` should be about the typical query radius.
+    /// Panics if `cell_size` is not a positive finite number.
+    pub fn new(cell_size: f64) -> Self {
+        assert!(cell_size.is_finite() && cell_size > 0.0, "cell_size must be positive");
+        SpatialHash {
+            cell_size,
+            cells: HashMap::new(),
+            points: Slab::new(),
+        }
+    }
+
+    fn cell_of(&self, p: (f64, f64)) -> (i64, i64) {
+        ((p.0 / self.cell_size).floor() as i64, (p.1 / self.cell_size).floor() as i64)
+    }
+
+    /// Inserts a point and returns its handle.
+    pub fn insert(&mut self, point: (f64, f64)) -> Handle {
+        let id = self.points.insert(point);
+        let cell = self.cell_of(point);
+        self.cells.entry(cell).or_default().push(id);
+        id
+    }
+
+    /// Removes the point with the given handle. Returns false if it was already removed.
+    pub fn remove(&mut self, id: Handle) -> bool {
+        let point = match self.points.remove(id) {
+            Some(p) => p,
+            None => return false,
+        };
+        let cell = self.cell_of(point);
+        if let Some(bucket) = self.cells.get_mut(&cell) {
+            if let Some(pos) = bucket.iter().position(|&x| x == id) {
+                bucket.swap_remove(pos);
+            }
+            if bucket.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+        true
+    }
+
+    /// Returns the coordinates of a live point.
+    pub fn get(&self, id: Handle) -> Option<(f64, f64)> {
+        self.points.get(id).copied()
+    }
+
+    /// Returns the handles of all points at euclidean distance <= `r` from `p`, sorted by slot
+    /// index.
+    /// Panics if `r` is negative, infinite or NaN.
+    pub fn neighbors_within(&self, p: (f64, f64), r: f64) -> Vec<Handle> {
+        assert!(r.is_finite() && r >= 0.0, "radius must be a non-negative finite number, got {}", r);
+        let (min_x, min_y) = self.cell_of((p.0 - r, p.1 - r));
+        let (max_x, max_y) = self.cell_of((p.0 + r, p.1 + r));
+        let r2 = r * r;
+        let mut out = Vec::new();
+        let mut scan = |bucket: &Vec<Handle>| {
+            for &id in bucket {
+                let q = self.points[id];
+                let (dx, dy) = (q.0 - p.0, q.1 - p.1);
+                if dx * dx + dy * dy <= r2 {
+                    out.push(id);
+                }
+            }
+        };
+        let box_cells = (max_x as i128 - min_x as i128 + 1) * (max_y as i128 - min_y as i128 + 1);
+        if box_cells > self.cells.len() as i128 {
+            // cheaper to test every occupied cell than to probe the empty ones in the box
+            let in_box = |&(cx, cy): &(i64, i64)| (min_x..=max_x).contains(&cx) && (min_y..=max_y).contains(&cy);
+            self.cells.iter().filter(|(cell, _)| in_box(cell)).for_each(|(_, bucket)| scan(bucket));
+        } else {
+            for cx in min_x..=max_x {
+                for cy in min_y..=max_y {
+                    if let Some(bucket) = self.cells.get(&(cx, cy)) {
+                        scan(bucket);
+                    }
+                }
+            }
+        }
+        out.sort_unstable_by_key(|id| id.index());
+        out
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+}
+
+impl DataStructure for SpatialHash {
+    const INFO: Info = Info {
+        name: "SpatialHash",
+        category: "spatial",
+        summary: "Uniform grid over 2D points for radius queries",
+        space: "O(n)",
+        operations: &[
+            Operation::new("insert", "O(1) expected", "O(1)"),
+            Operation::new("remove", "O(1) expected", "O(1)"),
+            Operation::new("neighbors_within", "O(min((r / cell_size + 1)^2, cells) + k)", "O(k)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_insert_and_neighbors() {
+        let mut grid = SpatialHash::new(1.0);
+        let a = grid.insert((0.0, 0.0));
+        let b = grid.insert((0.5, 0.5));
+        let c = grid.insert((3.0, 3.0));
+        let d = grid.insert((-0.9, 0.1));
+        assert_eq!(grid.len(), 4);
+
+        assert_eq!(grid.neighbors_within((0.0, 0.0), 1.0), vec![a, b, d]);
+        assert_eq!(grid.neighbors_within((3.0, 3.0), 0.1), vec![c]);
+        assert!(grid.neighbors_within((10.0, 10.0), 1.0).is_empty());
+        // radius spanning many cells
+        assert_eq!(grid.neighbors_within((0.0, 0.0), 5.0), vec![a, b, c, d]);
+        // a box of ~10^24 cells is answered from the 3 occupied ones
+        assert_eq!(grid.neighbors_within((0.0, 0.0), 1e12), vec![a, b, c, d]);
+        assert_eq!(grid.neighbors_within((2e11, 0.0), 3e11), vec![a, b, c, d]);
+        assert_eq!(grid.neighbors_within((3.0, 3.0), 0.0), vec![c]);
+    }
+
+    #[test]
+    #[should_panic(expected = "non-negative finite")]
+    fn test_rejects_nan_radius() {
+        SpatialHash::new(1.0).neighbors_within((0.0, 0.0), f64::NAN);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut grid = SpatialHash::new(2.0);
+        let a = grid.insert((1.0, 1.0));
+        let b = grid.insert((1.5, 1.0));
+        assert!(grid.remove(a));
+        assert!(!grid.remove(a));
+        assert_eq!(grid.get(a), None);
+        assert_eq!(grid.get(b), Some((1.5, 1.0)));
+        assert_eq!(grid.neighbors_within((1.0, 1.0), 1.0), vec![b]);
+        // c takes over a's slot, but a stays dead
+        let c = grid.insert((1.0, 1.0));
+        assert_eq!(c.index(), a.index());
+        assert_eq!(grid.get(a), None);
+        assert!(!grid.remove(a));
+        assert_eq!(grid.neighbors_within((1.0, 1.0), 1.0), vec![c, b]);
+        assert!(grid.remove(b));
+        assert!(grid.remove(c));
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_matches_brute_force() {
+        let mut grid = SpatialHash::new(0.7);
+        let mut pts = Vec::new();
+        let mut rng = test_rng(7);
+        let mut next = || (rng() >> 11) as f64 / (1u64 << 53) as f64 * 10.0 - 5.0;
+        for _ in 0..200 {
+            let (x, y) = (next(), next());
+            pts.push((x, y));
+            grid.insert((x, y));
+        }
+        for &(qx, qy, r) in &[(0.0, 0.0, 1.0), (-4.5, 4.5, 2.3), (2.0, -1.0, 0.3)] {
+            let expected: Vec<usize> = pts
+                .iter()
+                .enumerate()
+                .filter(|(_, p)| (p.0 - qx).powi(2) + (p.1 - qy).powi(2) <= r * r)
+                .map(|(i, _)| i)
+                .collect();
+            // nothing is removed, so slots follow insertion order
+            let found: Vec<usize> = grid.neighbors_within((qx, qy), r).into_iter().map(Handle::index).collect();
+            assert_eq!(found, expected);
+        }
+    }
+}
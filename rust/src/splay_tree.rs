@@ -0,0 +1,334 @@
+// rust/src/splay_tree.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::cmp::Ordering;
+
+type Link<K> = Option<Box<Node<K>>>;
+
+struct Node<K> {
+    key: K,
+    size: usize,
+    left: Link<K>,
+    right: Link<K>,
+}
+
+impl<K> Node<K> {
+    fn new(key: K) -> Box<Self> {
+        Box::new(Node { key, size: 1, left: None, right: None })
+    }
+
+    fn recalc(&mut self) {
+        self.size = 1 + self.left.as_ref().map_or(0, |n| n.size) + self.right.as_ref().map_or(0, |n| n.size);
+    }
+}
+
+/// Top-down splay (Sleator and Tarjan): walks from the root towards the node `target` points at,
+/// rotating at zig-zig steps, and hangs the nodes passed on the way into a left tree (everything
+/// smaller) and a right tree (everything larger). Those are kept as spines of detached nodes and
+/// reassembled under the node where the walk ended, which becomes the new root. `target(k)` says
+/// whether the wanted node is before, at or after key `k`.
+fn splay<K>(mut t: Box<Node<K>>, target: impl Fn(&K) -> Ordering) -> Box<Node<K>> {
+    // left spine nodes are missing their right child, right spine nodes their left child
+    let mut left_spine: Vec<Box<Node<K>>> = Vec::new();
+    let mut right_spine: Vec<Box<Node<K>>> = Vec::new();
+    loop {
+        match target(&t.key) {
+            Ordering::Less => {
+                let Some(mut c) = t.left.take() else { break };
+                if target(&c.key) == Ordering::Less {
+                    // zig-zig: rotate right before linking
+                    t.left = c.right.take();
+                    t.recalc();
+                    c.right = Some(t);
+                    t = c;
+                    let Some(next) = t.left.take() else { break };
+                    right_spine.push(t);
+                    t = next;
+                } else {
+                    right_spine.push(t);
+                    t = c;
+                }
+            }
+            Ordering::Greater => {
+                let Some(mut c) = t.right.take() else { break };
+                if target(&c.key) == Ordering::Greater {
+                    // zag-zag: rotate left before linking
+                    t.right = c.left.take();
+                    t.recalc();
+                    c.left = Some(t);
+                    t = c;
+                    let Some(next) = t.right.take() else { break };
+                    left_spine.push(t);
+                    t = next;
+                } else {
+                    left_spine.push(t);
+                    t = c;
+                }
+            }
+            Ordering::Equal => break,
+        }
+    }
+    // the deepest spine nodes take the new root's subtrees, and each spine node the one below it
+    let mut left = t.left.take();
+    while let Some(mut n) = left_spine.pop() {
+        n.right = left;
+        n.recalc();
+        left = Some(n);
+    }
+    let mut right = t.right.take();
+    while let Some(mut n) = right_spine.pop() {
+        n.left = right;
+        n.recalc();
+        right = Some(n);
+    }
+    t.left = left;
+    t.right = right;
+    t.recalc();
+    t
+}
+
+/// Ordered set as a splay tree: every access rotates the touched node to the root, so recently and
+/// frequently used keys stay near the top with no balance information stored. A single operation
+/// may take O(n) (ascending inserts build a path), but any sequence of m operations costs
+/// O(m log n). `contains` therefore needs `&mut self`. Splitting and joining at the root are
+/// natural here, which is also how the splay trees inside `LinkCutTree` cut and link paths.
+/// - insert / remove / contains / split_off / join: O(log n) amortized
+pub struct SplayTree<K> {
+    root: Link<K>,
+}
+
+impl<K> Default for SplayTree<K> {
+    fn default() -> Self {
+        SplayTree { root: None }
+    }
+}
+
+impl<K: Ord> SplayTree<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.root.as_ref().map_or(0, |n| n.size)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.root.is_none()
+    }
+
+    /// The key at the root: the one most recently accessed.
+    pub fn root(&self) -> Option<&K> {
+        self.root.as_ref().map(|n| &n.key)
+    }
+
+    /// Splays the node `target` leads to (or the last node on the way) to the root.
+    fn splay_by(&mut self, target: impl Fn(&K) -> Ordering) {
+        if let Some(root) = self.root.take() {
+            self.root = Some(splay(root, target));
+        }
+    }
+
+    pub fn contains(&mut self, key: &K) -> bool {
+        self.splay_by(|k| key.cmp(k));
+        self.root() == Some(key)
+    }
+
+    /// Inserts `key`; returns false (keeping the stored key) if it was already present.
+    pub fn insert(&mut self, key: K) -> bool {
+        self.splay_by(|k| key.cmp(k));
+        let Some(mut root) = self.root.take() else {
+            self.root = Some(Node::new(key));
+            return true;
+        };
+        let mut node = Node::new(key);
+        match node.key.cmp(&root.key) {
+            Ordering::Equal => {
+                self.root = Some(root);
+                return false;
+            }
+            Ordering::Less => {
+                node.left = root.left.take();
+                root.recalc();
+                node.right = Some(root);
+            }
+            Ordering::Greater => {
+                node.right = root.right.take();
+                root.recalc();
+                node.left = Some(root);
+            }
+        }
+        node.recalc();
+        self.root = Some(node);
+        true
+    }
+
+    /// Removes `key`; returns whether it was present.
+    pub fn remove(&mut self, key: &K) -> bool {
+        if !self.contains(key) {
+            return false;
+        }
+        let mut root = self.root.take().expect("contains splayed the key to the root");
+        let right = SplayTree { root: root.right.take() };
+        self.root = root.left.take();
+        self.join(right);
+        true
+    }
+
+    pub fn first(&mut self) -> Option<&K> {
+        self.splay_by(|_| Ordering::Less);
+        self.root()
+    }
+
+    pub fn last(&mut self) -> Option<&K> {
+        self.splay_by(|_| Ordering::Greater);
+        self.root()
+    }
+
+    /// Moves the keys `>= key` into a new tree and returns it.
+    pub fn split_off(&mut self, key: &K) -> SplayTree<K> {
+        self.splay_by(|k| key.cmp(k));
+        let Some(mut root) = self.root.take() else {
+            return SplayTree::new();
+        };
+        if root.key >= *key {
+            self.root = root.left.take();
+            root.recalc();
+            SplayTree { root: Some(root) }
+        } else {
+            let right = root.right.take();
+            root.recalc();
+            self.root = Some(root);
+            SplayTree { root: right }
+        }
+    }
+
+    /// Appends `other`, whose keys must all be greater than those in `self`: the maximum of `self`
+    /// is splayed to the root, where `other` hangs as its right subtree. Panics if the key ranges
+    /// overlap.
+    pub fn join(&mut self, mut other: SplayTree<K>) {
+        if other.is_empty() {
+            return;
+        }
+        if self.is_empty() {
+            *self = other;
+            return;
+        }
+        self.splay_by(|_| Ordering::Greater);
+        let mut root = self.root.take().expect("self is non-empty");
+        assert!(Some(&root.key) < other.first(), "join needs every key of self below those of other");
+        root.right = other.root.take();
+        root.recalc();
+        self.root = Some(root);
+    }
+
+    /// Keys in increasing order, without splaying.
+    pub fn iter(&self) -> impl Iterator<Item = &K> + '_ {
+        let mut stack = Vec::new();
+        let mut cur = self.root.as_deref();
+        std::iter::from_fn(move || {
+            while let Some(n) = cur {
+                stack.push(n);
+                cur = n.left.as_deref();
+            }
+            let n = stack.pop()?;
+            cur = n.right.as_deref();
+            Some(&n.key)
+        })
+    }
+}
+
+impl<K> Drop for SplayTree<K> {
+    // splay trees can be paths, so the default recursive drop could overflow the stack
+    fn drop(&mut self) {
+        let mut stack: Vec<Box<Node<K>>> = self.root.take().into_iter().collect();
+        while let Some(mut n) = stack.pop() {
+            stack.extend(n.left.take());
+            stack.extend(n.right.take());
+        }
+    }
+}
+
+impl<K: Ord> FromIterator<K> for SplayTree<K> {
+    fn from_iter<I: IntoIterator<Item = K>>(iter: I) -> Self {
+        let mut tree = SplayTree::new();
+        iter.into_iter().for_each(|k| {
+            tree.insert(k);
+        });
+        tree
+    }
+}
+
+impl<K> DataStructure for SplayTree<K> {
+    const INFO: Info = Info {
+        name: "SplayTree",
+        category: "ordered sets",
+        summary: "Self-adjusting BST that splays every accessed key to the root, with split and join",
+        space: "O(n)",
+        operations: &[
+            Operation::new("insert", "O(log n) amortized", "O(log n)"),
+            Operation::new("remove", "O(log n) amortized", "O(log n)"),
+            Operation::new("contains", "O(log n) amortized", "O(log n)"),
+            Operation::new("split_off", "O(log n) amortized", "O(log n)"),
+            Operation::new("join", "O(log n) amortized", "O(log n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_set_operations_and_self_adjustment() {
+        let mut tree: SplayTree<i32> = [50, 20, 80, 10, 30, 70, 90].into_iter().collect();
+        assert!(!tree.insert(30));
+        assert!(tree.contains(&10));
+        assert_eq!(tree.root(), Some(&10));
+        assert!(!tree.contains(&60));
+        // an unsuccessful search still splays the last node it saw
+        assert!(matches!(tree.root(), Some(&50) | Some(&70)));
+        assert_eq!((tree.first().copied(), tree.last().copied()), (Some(10), Some(90)));
+        assert!(tree.remove(&50) && !tree.remove(&50));
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30, 70, 80, 90]);
+
+        let mut high = tree.split_off(&70);
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30]);
+        assert_eq!((high.len(), high.first().copied()), (3, Some(70)));
+        tree.join(high.split_off(&85));
+        assert_eq!(tree.iter().copied().collect::<Vec<_>>(), vec![10, 20, 30, 90]);
+        tree.join(SplayTree::new());
+        assert_eq!(tree.len(), 4);
+
+        // ascending inserts leave a path; searching its far end halves the depth along the way
+        let mut path: SplayTree<u32> = (0..100_000).collect();
+        assert!(path.contains(&0) && path.contains(&99_999));
+    }
+
+    #[test]
+    fn test_against_btreeset() {
+        let mut next = test_rng(19);
+        let mut tree = SplayTree::new();
+        let mut reference = BTreeSet::new();
+        for _ in 0..5000 {
+            let key = next() % 400;
+            match next() % 5 {
+                0 | 1 => assert_eq!(tree.insert(key), reference.insert(key)),
+                2 => assert_eq!(tree.remove(&key), reference.remove(&key)),
+                3 => {
+                    // split and immediately rejoin
+                    let high = tree.split_off(&key);
+                    assert!(tree.iter().eq(reference.range(..key)));
+                    assert!(high.iter().eq(reference.range(key..)));
+                    tree.join(high);
+                }
+                _ => assert_eq!(tree.contains(&key), reference.contains(&key)),
+            }
+            assert_eq!(tree.len(), reference.len());
+        }
+        assert!(tree.iter().eq(reference.iter()));
+    }
+}
@@ -0,0 +1,260 @@
+// rust/src/sqrt_decomposition.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::lazy_segment_tree::LazyOp;
+use crate::range::{self, PointUpdate, RangeQuery};
+use std::ops::{Range, RangeBounds};
+
+/// Sqrt decomposition: the array cut into blocks of `b` elements (about √n by default), each with
+/// its aggregate and one pending update, driven by the same `LazyOp` as `LazySegmentTree`. A range
+/// operation touches at most two partial blocks element by element and every whole block in
+/// between in O(1), so it costs O(b + n / b). Unlike a segment tree the block size is a plain knob:
+/// `with_block_size` lets an offline workload trade update cost against query cost.
+/// - Build: O(n)
+/// - update / query / set: O(b + n / b), O(√n) for the default block size
+pub struct SqrtDecomposition<Op: LazyOp> {
+    values: Vec<Op::Value>,
+    block: usize,
+    aggregates: Vec<Op::Value>,
+    // update pending on every element of the block, not yet applied to `values`
+    lazy: Vec<Op::Lazy>,
+    op: Op,
+}
+
+impl<Op: LazyOp> SqrtDecomposition<Op> {
+    /// `len` copies of the identity.
+    pub fn new(len: usize, op: Op) -> Self {
+        let values = vec![op.identity(); len];
+        Self::from_vec(values, op)
+    }
+
+    pub fn from_slice(values: &[Op::Value], op: Op) -> Self {
+        Self::from_vec(values.to_vec(), op)
+    }
+
+    fn from_vec(values: Vec<Op::Value>, op: Op) -> Self {
+        let block = (values.len() as f64).sqrt().ceil().max(1.0) as usize;
+        Self::build(values, block, op)
+    }
+
+    /// Uses blocks of `block` elements instead of √n: smaller blocks favour point work, larger
+    /// ones favour long ranges. Panics if `block` is 0.
+    pub fn with_block_size(values: &[Op::Value], block: usize, op: Op) -> Self {
+        assert!(block > 0, "block size must be positive");
+        Self::build(values.to_vec(), block, op)
+    }
+
+    fn build(values: Vec<Op::Value>, block: usize, op: Op) -> Self {
+        let blocks = values.len().div_ceil(block);
+        let mut sd = SqrtDecomposition { values, block, aggregates: vec![op.identity(); blocks], lazy: vec![op.no_update(); blocks], op };
+        (0..blocks).for_each(|b| sd.pull(b));
+        sd
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn block_size(&self) -> usize {
+        self.block
+    }
+
+    pub fn op(&self) -> &Op {
+        &self.op
+    }
+
+    fn span(&self, b: usize) -> Range<usize> {
+        b * self.block..((b + 1) * self.block).min(self.values.len())
+    }
+
+    /// Recomputes the aggregate of block `b` from its elements (and pending update).
+    fn pull(&mut self, b: usize) {
+        let span = self.span(b);
+        let len = span.len();
+        let folded = self.values[span].iter().fold(self.op.identity(), |acc, v| self.op.combine(&acc, v));
+        self.aggregates[b] = self.op.apply(&self.lazy[b], &folded, len);
+    }
+
+    /// Applies the pending update of block `b` to its elements.
+    fn push(&mut self, b: usize) {
+        let f = std::mem::replace(&mut self.lazy[b], self.op.no_update());
+        for i in self.span(b) {
+            self.values[i] = self.op.apply(&f, &self.values[i], 1);
+        }
+    }
+
+    /// Applies `f` to every element in `range` (half-open convention, see `crate::range`).
+    /// Panics if the range is out of bounds.
+    pub fn update<R: RangeBounds<usize>>(&mut self, range: R, f: Op::Lazy) {
+        let r = range::resolve(range, self.len());
+        if r.is_empty() {
+            return;
+        }
+        let (first, last) = (r.start / self.block, (r.end - 1) / self.block);
+        for b in first..=last {
+            let span = self.span(b);
+            if r.start <= span.start && span.end <= r.end {
+                self.aggregates[b] = self.op.apply(&f, &self.aggregates[b], span.len());
+                self.lazy[b] = self.op.compose(&f, &self.lazy[b]);
+            } else {
+                self.push(b);
+                for i in span.start.max(r.start)..span.end.min(r.end) {
+                    self.values[i] = self.op.apply(&f, &self.values[i], 1);
+                }
+                self.pull(b);
+            }
+        }
+    }
+
+    /// Aggregate of `range`; the identity for an empty range. Panics if the range is out of bounds.
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> Op::Value {
+        let r = range::resolve(range, self.len());
+        let mut acc = self.op.identity();
+        if r.is_empty() {
+            return acc;
+        }
+        let (first, last) = (r.start / self.block, (r.end - 1) / self.block);
+        for b in first..=last {
+            let span = self.span(b);
+            if r.start <= span.start && span.end <= r.end {
+                acc = self.op.combine(&acc, &self.aggregates[b]);
+            } else {
+                // the block's pending update distributes over the partial aggregate
+                let part = span.start.max(r.start)..span.end.min(r.end);
+                let len = part.len();
+                let folded = self.values[part].iter().fold(self.op.identity(), |acc, v| self.op.combine(&acc, v));
+                acc = self.op.combine(&acc, &self.op.apply(&self.lazy[b], &folded, len));
+            }
+        }
+        acc
+    }
+
+    /// Value at `index`. Panics if out of bounds.
+    pub fn get(&self, index: usize) -> Op::Value {
+        assert!(index < self.len(), "index {} out of bounds for length {}", index, self.len());
+        self.op.apply(&self.lazy[index / self.block], &self.values[index], 1)
+    }
+
+    /// Sets the element at `index` to `value`. Panics if out of bounds.
+    pub fn set(&mut self, index: usize, value: Op::Value) {
+        assert!(index < self.len(), "index {} out of bounds for length {}", index, self.len());
+        let b = index / self.block;
+        self.push(b);
+        self.values[index] = value;
+        self.pull(b);
+    }
+
+    /// Aggregate of all elements, in O(n / b).
+    pub fn all(&self) -> Op::Value {
+        self.aggregates.iter().fold(self.op.identity(), |acc, a| self.op.combine(&acc, a))
+    }
+
+    /// Current values of all elements, in O(n).
+    pub fn to_vec(&self) -> Vec<Op::Value> {
+        (0..self.len()).map(|i| self.get(i)).collect()
+    }
+}
+
+impl<Op: LazyOp> DataStructure for SqrtDecomposition<Op> {
+    const INFO: Info = Info {
+        name: "SqrtDecomposition",
+        category: "range queries",
+        summary: "Blocks of about √n elements with cached aggregates and pending updates; block size is tunable",
+        space: "O(n)",
+        operations: &[
+            Operation::new("from_slice", "O(n)", "O(n)"),
+            Operation::new("update", "O(√n)", "O(1)"),
+            Operation::new("query", "O(√n)", "O(1)"),
+            Operation::new("set", "O(√n)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl<Op: LazyOp> RangeQuery for SqrtDecomposition<Op> {
+    type Item = Op::Value;
+    type Output = Op::Value;
+
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn query_range<R: RangeBounds<usize>>(&self, range: R) -> Op::Value {
+        self.query(range)
+    }
+}
+
+impl<Op: LazyOp> PointUpdate for SqrtDecomposition<Op> {
+    fn set(&mut self, index: usize, value: Op::Value) {
+        self.set(index, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use crate::lazy_segment_tree::{RangeMax, RangeMin, RangeSum, Update};
+
+    #[test]
+    fn test_partial_and_whole_blocks() {
+        let mut sd = SqrtDecomposition::with_block_size(&[1, 2, 3, 4, 5, 6, 7], 3, RangeSum);
+        assert_eq!((sd.block_size(), sd.all()), (3, 28));
+        sd.update(1..6, Update::add(10));
+        assert_eq!(sd.to_vec(), vec![1, 12, 13, 14, 15, 16, 7]);
+        // assign over whole block 1, then a partial block touches its pending update
+        sd.update(3..6, Update::assign(0));
+        sd.set(4, 9);
+        assert_eq!(sd.to_vec(), vec![1, 12, 13, 0, 9, 0, 7]);
+        assert_eq!((sd.query(2..5), sd.query(..), sd.query(6..6)), (22, 42, 0));
+        assert_eq!(sd.get(6), 7);
+
+        let empty = SqrtDecomposition::new(0, RangeMin);
+        assert_eq!((empty.is_empty(), empty.query(..)), (true, i64::MAX));
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(61);
+        for block in [1, 4, 7, 100] {
+            let n = 53;
+            let mut values: Vec<i64> = (0..n).map(|_| (next() % 100) as i64).collect();
+            let mut sum = SqrtDecomposition::with_block_size(&values, block, RangeSum);
+            let mut min = SqrtDecomposition::with_block_size(&values, block, RangeMin);
+            let mut max = SqrtDecomposition::from_slice(&values, RangeMax);
+            for _ in 0..400 {
+                let (a, b) = (next() as usize % (n + 1), next() as usize % (n + 1));
+                let (l, r) = (a.min(b), a.max(b));
+                let x = (next() % 50) as i64 - 25;
+                match next() % 4 {
+                    0 => {
+                        let f = if next().is_multiple_of(2) { Update::add(x) } else { Update::assign(x) };
+                        values[l..r].iter_mut().for_each(|v| *v = f.assign.unwrap_or(*v) + f.add);
+                        sum.update(l..r, f);
+                        min.update(l..r, f);
+                        max.update(l..r, f);
+                    }
+                    1 if l < n => {
+                        values[l] = x;
+                        sum.set(l, x);
+                        min.set(l, x);
+                        max.set(l, x);
+                    }
+                    _ => {
+                        let chunk = &values[l..r];
+                        assert_eq!(sum.query(l..r), chunk.iter().sum::<i64>());
+                        assert_eq!(min.query(l..r), chunk.iter().copied().min().unwrap_or(i64::MAX));
+                        assert_eq!(max.query_range(l..r), chunk.iter().copied().max().unwrap_or(i64::MIN));
+                    }
+                }
+            }
+            assert_eq!(sum.to_vec(), values);
+            assert_eq!(min.all(), *values.iter().min().expect("non-empty"));
+        }
+    }
+}
@@ -0,0 +1,181 @@
+// rust/src/static_dict.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::union_find::UnionFind;
+use std::collections::HashSet;
+
+fn hash_bytes(bytes: &[u8], seed: u64) -> u64 {
+    // FNV-1a over the bytes, then a splitmix finalizer to spread the bits
+    let mut h = 0xcbf29ce484222325u64 ^ seed;
+    for &b in bytes {
+        h = (h ^ b as u64).wrapping_mul(0x100000001b3);
+    }
+    h = (h ^ (h >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    h = (h ^ (h >> 27)).wrapping_mul(0x94D049BB133111EB);
+    h ^ (h >> 31)
+}
+
+/// A read-only string set with an order-preserving minimal perfect hash (CHM algorithm).
+/// Every key is an edge between two of about 2.1n vertices chosen by two hash functions; seeds are
+/// retried until that graph is acyclic, and then each vertex gets a value `g` so that for the key at
+/// input position `i`, `(g[u] + g[v]) % n == i`. A lookup is two hashes, two array reads and one
+/// string comparison, and `index` returns the position the key had in the input.
+/// - build: O(n) expected (about 5 seed attempts on average)
+/// - index / contains: O(|key|)
+/// - Space: 2.1n u32s plus the keys stored back to back in a single buffer
+pub struct StaticDict {
+    seeds: (u64, u64),
+    g: Vec<u32>,
+    text: String,
+    // offsets[i]..offsets[i + 1] is the i-th key in `text`
+    offsets: Vec<usize>,
+}
+
+impl StaticDict {
+    /// Builds the dictionary. Returns None if `keys` contains duplicates.
+    pub fn build<S: AsRef<str>>(keys: &[S]) -> Option<Self> {
+        let mut seen = HashSet::with_capacity(keys.len());
+        if !keys.iter().all(|k| seen.insert(k.as_ref())) {
+            return None;
+        }
+        let n = keys.len();
+        let m = n * 21 / 10 + 2;
+        let mut text = String::new();
+        let mut offsets = vec![0];
+        for k in keys {
+            text.push_str(k.as_ref());
+            offsets.push(text.len());
+        }
+        let mut attempt = 0u64;
+        loop {
+            let seeds = (attempt.wrapping_mul(0x9E3779B97F4A7C15), attempt.wrapping_mul(0xC2B2AE3D27D4EB4F) ^ 1);
+            if let Some(g) = Self::assign(keys, m, seeds) {
+                return Some(StaticDict { seeds, g, text, offsets });
+            }
+            attempt += 1;
+        }
+    }
+
+    fn vertices(key: &[u8], m: usize, seeds: (u64, u64)) -> (usize, usize) {
+        (
+            (hash_bytes(key, seeds.0) % m as u64) as usize,
+            (hash_bytes(key, seeds.1) % m as u64) as usize,
+        )
+    }
+
+    /// Values `g` for one seed pair, or None if the key graph has a self-loop or a cycle.
+    fn assign<S: AsRef<str>>(keys: &[S], m: usize, seeds: (u64, u64)) -> Option<Vec<u32>> {
+        let n = keys.len();
+        let mut uf = UnionFind::new(m);
+        let mut adj = vec![Vec::new(); m];
+        for (i, k) in keys.iter().enumerate() {
+            let (u, v) = Self::vertices(k.as_ref().as_bytes(), m, seeds);
+            if !uf.union(u, v) {
+                return None;
+            }
+            adj[u].push((v, i));
+            adj[v].push((u, i));
+        }
+        // walk each tree from an arbitrary root with g = 0 and solve every edge for its far end
+        let mut g = vec![u32::MAX; m];
+        for root in 0..m {
+            if g[root] != u32::MAX {
+                continue;
+            }
+            g[root] = 0;
+            let mut stack = vec![root];
+            while let Some(u) = stack.pop() {
+                for &(v, i) in &adj[u] {
+                    if g[v] == u32::MAX {
+                        g[v] = ((i + n - g[u] as usize) % n) as u32;
+                        stack.push(v);
+                    }
+                }
+            }
+        }
+        Some(g)
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len() - 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The `i`-th key in input order.
+    pub fn key(&self, i: usize) -> Option<&str> {
+        Some(&self.text[*self.offsets.get(i)?..*self.offsets.get(i + 1)?])
+    }
+
+    /// Keys in input order.
+    pub fn keys(&self) -> impl Iterator<Item = &str> + '_ {
+        self.offsets.windows(2).map(|w| &self.text[w[0]..w[1]])
+    }
+
+    /// Position of `key` in the input the dictionary was built from.
+    pub fn index(&self, key: &str) -> Option<usize> {
+        let n = self.len();
+        if n == 0 {
+            return None;
+        }
+        let (u, v) = Self::vertices(key.as_bytes(), self.g.len(), self.seeds);
+        let i = (self.g[u] as usize + self.g[v] as usize) % n;
+        (self.key(i) == Some(key)).then_some(i)
+    }
+
+    pub fn contains(&self, key: &str) -> bool {
+        self.index(key).is_some()
+    }
+}
+
+impl DataStructure for StaticDict {
+    const INFO: Info = Info {
+        name: "StaticDict",
+        category: "hashing",
+        summary: "Read-only string set with an order-preserving minimal perfect hash (CHM)",
+        space: "2.1n u32 + key bytes",
+        operations: &[
+            Operation::new("build", "O(n) expected", "O(n)"),
+            Operation::new("index", "O(|key|)", "O(1)"),
+            Operation::new("contains", "O(|key|)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keyword_table() {
+        let keywords = ["fn", "let", "mut", "match", "if", "else", "while", "loop", "return", "struct"];
+        let dict = StaticDict::build(&keywords).unwrap();
+        assert_eq!(dict.len(), keywords.len());
+        for (i, k) in keywords.iter().enumerate() {
+            assert_eq!(dict.index(k), Some(i));
+        }
+        assert_eq!(dict.index("for"), None);
+        assert_eq!(dict.index(""), None);
+        assert!(!dict.contains("matc"));
+        assert_eq!(dict.keys().collect::<Vec<_>>(), keywords.to_vec());
+    }
+
+    #[test]
+    fn test_large_and_edge_cases() {
+        let keys: Vec<String> = (0..5000).map(|i| format!("key-{}", i * 7)).collect();
+        let dict = StaticDict::build(&keys).unwrap();
+        assert!(keys.iter().enumerate().all(|(i, k)| dict.index(k) == Some(i)));
+        assert!((0..5000).all(|i| !dict.contains(&format!("key-{}", i * 7 + 1))));
+
+        assert!(StaticDict::build(&["a", "b", "a"]).is_none());
+        let empty = StaticDict::build::<&str>(&[]).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(empty.index("x"), None);
+        let single = StaticDict::build(&[""]).unwrap();
+        assert_eq!(single.index(""), Some(0));
+    }
+}
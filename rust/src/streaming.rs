@@ -0,0 +1,406 @@
+// rust/src/streaming.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::rng::{Rng, SplitMix64};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// Uniform sample from (0, 1], so its logarithm is finite.
+fn unit(rng: &mut impl Rng) -> f64 {
+    1.0 - (rng.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+}
+
+/// Uniform sample of `k` items from a stream of unknown length, with Li's Algorithm L. Once the
+/// reservoir is full, instead of drawing a random number per item it draws how many items to skip
+/// before the next replacement, so a stream of n items costs O(k (1 + log(n / k))) random draws.
+/// Every item seen so far is in the sample with the same probability `k / seen`.
+/// - push: O(1)
+pub struct ReservoirSampler<T, R = SplitMix64> {
+    sample: Vec<T>,
+    capacity: usize,
+    seen: u64,
+    // Algorithm L's threshold: an item gets in when its uniform random key falls below it
+    w: f64,
+    // items still to pass over before the next replacement
+    skip: u64,
+    rng: R,
+}
+
+impl<T> ReservoirSampler<T> {
+    pub fn new(k: usize) -> Self {
+        Self::with_rng(k, SplitMix64::default())
+    }
+}
+
+impl<T, R: Rng> ReservoirSampler<T, R> {
+    pub fn with_rng(k: usize, rng: R) -> Self {
+        ReservoirSampler { sample: Vec::with_capacity(k), capacity: k, seen: 0, w: 1.0, skip: 0, rng }
+    }
+
+    /// Size of a full sample.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of items pushed so far.
+    pub fn seen(&self) -> u64 {
+        self.seen
+    }
+
+    /// The current sample, in no particular order.
+    pub fn sample(&self) -> &[T] {
+        &self.sample
+    }
+
+    pub fn into_sample(self) -> Vec<T> {
+        self.sample
+    }
+
+    /// Draws the next gap between replacements, after lowering `w`.
+    fn next_skip(&mut self) {
+        let k = self.capacity as f64;
+        self.w *= (unit(&mut self.rng).ln() / k).exp();
+        self.skip = (unit(&mut self.rng).ln() / (-self.w).ln_1p()).floor() as u64;
+    }
+
+    pub fn push(&mut self, item: T) {
+        self.seen += 1;
+        if self.sample.len() < self.capacity {
+            self.sample.push(item);
+            if self.sample.len() == self.capacity {
+                self.next_skip();
+            }
+        } else if self.capacity > 0 {
+            if self.skip > 0 {
+                self.skip -= 1;
+                return;
+            }
+            let slot = (self.rng.next_u64() % self.capacity as u64) as usize;
+            self.sample[slot] = item;
+            self.next_skip();
+        }
+    }
+}
+
+impl<T, R: Rng> Extend<T> for ReservoirSampler<T, R> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|item| self.push(item));
+    }
+}
+
+/// A sampled item with its key, ordered by key alone.
+struct Keyed<T> {
+    key: f64,
+    item: T,
+}
+
+impl<T> PartialEq for Keyed<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key.total_cmp(&other.key) == Ordering::Equal
+    }
+}
+
+impl<T> Eq for Keyed<T> {}
+
+impl<T> PartialOrd for Keyed<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for Keyed<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.total_cmp(&other.key)
+    }
+}
+
+/// Weighted sample of `k` items without replacement, with Efraimidis and Spirakis' A-ES: each item
+/// gets the key `u^(1 / weight)` for a uniform `u`, and the sample is the `k` largest keys, kept in
+/// a min-heap. This draws items in proportion to their weight, one after another without
+/// replacement. Keys are compared as `ln(u) / weight`, which orders the same way without
+/// underflowing for small weights.
+/// - push: O(log k)
+pub struct WeightedReservoir<T, R = SplitMix64> {
+    heap: BinaryHeap<Reverse<Keyed<T>>>,
+    capacity: usize,
+    total_weight: f64,
+    rng: R,
+}
+
+impl<T> WeightedReservoir<T> {
+    pub fn new(k: usize) -> Self {
+        Self::with_rng(k, SplitMix64::default())
+    }
+}
+
+impl<T, R: Rng> WeightedReservoir<T, R> {
+    pub fn with_rng(k: usize, rng: R) -> Self {
+        WeightedReservoir { heap: BinaryHeap::with_capacity(k), capacity: k, total_weight: 0.0, rng }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of items in the sample.
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Sum of the weights pushed so far.
+    pub fn total_weight(&self) -> f64 {
+        self.total_weight
+    }
+
+    /// Offers `item` with `weight`; items of weight 0 are never sampled. Panics if the weight is
+    /// negative, infinite or NaN.
+    pub fn push(&mut self, item: T, weight: f64) {
+        assert!(weight.is_finite() && weight >= 0.0, "weights must be finite and non-negative, got {}", weight);
+        self.total_weight += weight;
+        if weight == 0.0 || self.capacity == 0 {
+            return;
+        }
+        let key = unit(&mut self.rng).ln() / weight;
+        if self.heap.len() < self.capacity {
+            self.heap.push(Reverse(Keyed { key, item }));
+        } else if let Some(mut smallest) = self.heap.peek_mut()
+            && key > smallest.0.key
+        {
+            *smallest = Reverse(Keyed { key, item });
+        }
+    }
+
+    /// The sampled items, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.heap.iter().map(|entry| &entry.0.item)
+    }
+
+    /// The sampled items in the order A-ES drew them: the first is a weighted draw from the whole
+    /// stream, the second from the rest, and so on.
+    pub fn into_sample(self) -> Vec<T> {
+        self.heap.into_sorted_vec().into_iter().map(|entry| entry.0.item).collect()
+    }
+}
+
+/// Count, mean, variance, minimum and maximum of a stream of numbers in O(1) space, with Welford's
+/// update, which avoids the cancellation of the textbook sum-of-squares formula. Two trackers
+/// merge with Chan et al.'s pairwise formula, so a stream can be summarized in parallel pieces.
+/// - push / merge: O(1)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunningStats {
+    count: u64,
+    mean: f64,
+    // sum of squared differences from the current mean
+    m2: f64,
+    min: f64,
+    max: f64,
+}
+
+impl Default for RunningStats {
+    fn default() -> Self {
+        RunningStats { count: 0, mean: 0.0, m2: 0.0, min: f64::INFINITY, max: f64::NEG_INFINITY }
+    }
+}
+
+impl RunningStats {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, x: f64) {
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+        self.min = self.min.min(x);
+        self.max = self.max.max(x);
+    }
+
+    /// Combines `other`'s numbers into this tracker.
+    pub fn merge(&mut self, other: &RunningStats) {
+        if other.count == 0 {
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        let share = other.count as f64 / count as f64;
+        self.m2 += other.m2 + delta * delta * self.count as f64 * share;
+        self.mean += delta * share;
+        self.count = count;
+        self.min = self.min.min(other.min);
+        self.max = self.max.max(other.max);
+    }
+
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// Population variance, dividing by the count.
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.m2 / self.count as f64)
+    }
+
+    /// Unbiased sample variance, dividing by the count minus one. None below two numbers.
+    pub fn sample_variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.m2 / (self.count - 1) as f64)
+    }
+
+    /// Sample standard deviation.
+    pub fn std_dev(&self) -> Option<f64> {
+        self.sample_variance().map(f64::sqrt)
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+}
+
+impl Extend<f64> for RunningStats {
+    fn extend<I: IntoIterator<Item = f64>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|x| self.push(x));
+    }
+}
+
+impl FromIterator<f64> for RunningStats {
+    fn from_iter<I: IntoIterator<Item = f64>>(iter: I) -> Self {
+        let mut stats = RunningStats::new();
+        stats.extend(iter);
+        stats
+    }
+}
+
+impl<T, R> DataStructure for ReservoirSampler<T, R> {
+    const INFO: Info = Info {
+        name: "ReservoirSampler",
+        category: "streaming",
+        summary: "Uniform sample of k items from a stream of unknown length (Algorithm L, skipping ahead between replacements)",
+        space: "O(k)",
+        operations: &[
+            Operation::new("push", "O(1)", "O(1)"),
+            Operation::new("stream of n items", "O(n), O(k log(n / k)) random draws", "O(k)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl<T, R> DataStructure for WeightedReservoir<T, R> {
+    const INFO: Info = Info {
+        name: "WeightedReservoir",
+        category: "streaming",
+        summary: "Weighted sample of k items without replacement from a stream (A-ES keys in a min-heap)",
+        space: "O(k)",
+        operations: &[
+            Operation::new("push", "O(log k)", "O(1)"),
+            Operation::new("into_sample", "O(k log k)", "O(k)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+impl DataStructure for RunningStats {
+    const INFO: Info = Info {
+        name: "RunningStats",
+        category: "streaming",
+        summary: "Running count, mean, variance, min and max with Welford updates and mergeable partial results",
+        space: "O(1)",
+        operations: &[
+            Operation::new("push", "O(1)", "O(1)"),
+            Operation::new("merge", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_small_streams() {
+        let mut all = ReservoirSampler::new(10);
+        all.extend(0..4);
+        assert_eq!((all.sample(), all.seen()), (&[0, 1, 2, 3][..], 4));
+        let mut none = ReservoirSampler::new(0);
+        none.extend(0..4);
+        assert!(none.sample().is_empty());
+
+        let mut weighted = WeightedReservoir::new(2);
+        weighted.push("never", 0.0);
+        weighted.push("a", 1.0);
+        assert_eq!(weighted.len(), 1);
+        weighted.push("b", 2.0);
+        weighted.push("c", 3.0);
+        assert_eq!((weighted.len(), weighted.total_weight()), (2, 6.0));
+        assert!(!weighted.iter().any(|&x| x == "never"));
+
+        let stats: RunningStats = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0].into_iter().collect();
+        assert_eq!((stats.count(), stats.mean(), stats.variance()), (8, Some(5.0), Some(4.0)));
+        assert_eq!((stats.min(), stats.max()), (Some(2.0), Some(9.0)));
+        assert!((stats.sample_variance().unwrap() - 32.0 / 7.0).abs() < 1e-12);
+        assert_eq!(RunningStats::new().mean(), None);
+        assert_eq!([1.0].into_iter().collect::<RunningStats>().std_dev(), None);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(103);
+        // every item of a 40-item stream should land in a 5-item sample 1/8 of the time
+        let trials = 8000;
+        let mut hits = [0u32; 40];
+        let mut sampler_seed = 0;
+        for _ in 0..trials {
+            sampler_seed += 1;
+            let mut sampler = ReservoirSampler::with_rng(5, SplitMix64::new(sampler_seed));
+            sampler.extend(0..40);
+            sampler.sample().iter().for_each(|&i| hits[i] += 1);
+        }
+        let expected = trials as f64 * 5.0 / 40.0;
+        assert!(hits.iter().all(|&h| (h as f64 - expected).abs() < 0.15 * expected), "{:?}", hits);
+
+        // with one slot, A-ES picks each item with probability proportional to its weight
+        let weights = [1.0, 2.0, 3.0, 4.0];
+        let mut picks = [0u32; 4];
+        for t in 0..trials {
+            let mut reservoir = WeightedReservoir::with_rng(1, SplitMix64::new(t));
+            (0..4).for_each(|i| reservoir.push(i, weights[i]));
+            picks[reservoir.into_sample()[0]] += 1;
+        }
+        for (i, &p) in picks.iter().enumerate() {
+            let expected = trials as f64 * weights[i] / 10.0;
+            assert!((p as f64 - expected).abs() < 0.1 * expected, "{:?}", picks);
+        }
+
+        let values: Vec<f64> = (0..1000).map(|_| 1e6 + (next() % 1000) as f64 / 7.0).collect();
+        let mean = values.iter().sum::<f64>() / 1000.0;
+        let variance = values.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / 1000.0;
+        let mut left: RunningStats = values[..300].iter().copied().collect();
+        let right: RunningStats = values[300..].iter().copied().collect();
+        left.merge(&right);
+        let whole: RunningStats = values.iter().copied().collect();
+        for stats in [left, whole] {
+            assert_eq!(stats.count(), 1000);
+            assert!((stats.mean().unwrap() - mean).abs() < 1e-6);
+            assert!((stats.variance().unwrap() - variance).abs() < 1e-6 * variance);
+        }
+    }
+}
@@ -0,0 +1,415 @@
+// rust/src/strings.rs
+//! Assorted string algorithms that work on any slice of ordered tokens.
+//! The `_str` variants operate on chars (not bytes) so results never split a UTF-8 sequence.
+
+use std::collections::HashMap;
+
+/// Lyndon factorization (Duval's algorithm).
+/// Splits `s` into Lyndon words w1 >= w2 >= ... >= wk, where a Lyndon word is strictly smaller than
+/// all of its proper rotations. Returns the start index of each factor.
+///
+/// Complexity: O(n) time, O(1) extra space
+pub fn lyndon_factorization<T: Ord>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut starts = vec![];
+    let mut i = 0;
+    while i < n {
+        let (mut j, mut k) = (i + 1, i);
+        while j < n && s[k] <= s[j] {
+            if s[k] < s[j] {
+                k = i;
+            } else {
+                k += 1;
+            }
+            j += 1;
+        }
+        while i <= k {
+            starts.push(i);
+            i += j - k;
+        }
+    }
+    starts
+}
+
+/// Lyndon factors of a string, compared char by char.
+pub fn lyndon_factorization_str(s: &str) -> Vec<&str> {
+    let chars: Vec<char> = s.chars().collect();
+    let byte_offsets: Vec<usize> = s.char_indices().map(|(b, _)| b).chain(std::iter::once(s.len())).collect();
+    let starts = lyndon_factorization(&chars);
+    starts
+        .iter()
+        .enumerate()
+        .map(|(f, &start)| {
+            let end = starts.get(f + 1).copied().unwrap_or(chars.len());
+            &s[byte_offsets[start]..byte_offsets[end]]
+        })
+        .collect()
+}
+
+/// Lexicographically minimal rotation (Booth's algorithm).
+/// Returns the start index `k` such that `s[k..] + s[..k]` is the smallest rotation; the smallest such
+/// index when several rotations tie. Returns 0 for empty input.
+///
+/// Complexity: O(n)
+pub fn minimal_rotation<T: Ord>(s: &[T]) -> usize {
+    let n = s.len();
+    if n == 0 {
+        return 0;
+    }
+    // failure function of the candidate rotation starting at k, over the doubled string
+    let mut f = vec![-1isize; 2 * n];
+    let mut k = 0usize;
+    for j in 1..2 * n {
+        let sj = &s[j % n];
+        let mut i = f[j - k - 1];
+        while i != -1 && *sj != s[(k + i as usize + 1) % n] {
+            if *sj < s[(k + i as usize + 1) % n] {
+                k = j - i as usize - 1;
+            }
+            i = f[i as usize];
+        }
+        if i == -1 && *sj != s[k % n] {
+            if *sj < s[k % n] {
+                k = j;
+            }
+            f[j - k] = -1;
+        } else {
+            f[j - k] = i + 1;
+        }
+    }
+    k % n
+}
+
+/// The minimal rotation of a string, rotating by chars.
+pub fn minimal_rotation_str(s: &str) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let k = minimal_rotation(&chars);
+    chars[k..].iter().chain(&chars[..k]).collect()
+}
+
+/// Z-function: `z[i]` is the length of the longest common prefix of `s` and `s[i..]`, with `z[0] = n`.
+/// Searching for `p` in `t` is a scan of the Z-function of `p + separator + t` for values `>= |p|`.
+///
+/// Complexity: O(n)
+pub fn z_function<T: Eq>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut z = vec![0; n];
+    if n == 0 {
+        return z;
+    }
+    z[0] = n;
+    // [l, r) is the rightmost window known to match a prefix of s
+    let (mut l, mut r) = (0, 0);
+    for i in 1..n {
+        let mut k = if i < r { z[i - l].min(r - i) } else { 0 };
+        while i + k < n && s[k] == s[i + k] {
+            k += 1;
+        }
+        z[i] = k;
+        if i + k > r {
+            (l, r) = (i, i + k);
+        }
+    }
+    z
+}
+
+/// Palindrome radii (Manacher's algorithm). Returns `(odd, even)` where
+/// - `odd[i]` is the largest `k` such that `s[i + 1 - k..i + k]` is a palindrome (length `2k - 1`)
+/// - `even[i]` is the largest `k` such that `s[i - k..i + k]` is a palindrome (length `2k`, centred
+///   between `i - 1` and `i`)
+///
+/// Their sum is the number of palindromic substrings (counted by position).
+///
+/// Complexity: O(n)
+pub fn manacher<T: Eq>(s: &[T]) -> (Vec<usize>, Vec<usize>) {
+    let n = s.len();
+    let (mut odd, mut even) = (vec![0; n], vec![0; n]);
+    // [l, r) is the rightmost palindrome found so far
+    let (mut l, mut r) = (0, 0);
+    for i in 0..n {
+        let mut k = if i < r { odd[l + r - 1 - i].min(r - i) } else { 1 };
+        while k <= i && i + k < n && s[i - k] == s[i + k] {
+            k += 1;
+        }
+        odd[i] = k;
+        if i + k > r {
+            (l, r) = (i + 1 - k, i + k);
+        }
+    }
+    (l, r) = (0, 0);
+    for i in 0..n {
+        let mut k = if i < r { even[l + r - i].min(r - i) } else { 0 };
+        while k < i && i + k < n && s[i - k - 1] == s[i + k] {
+            k += 1;
+        }
+        even[i] = k;
+        if i + k > r {
+            (l, r) = (i - k, i + k);
+        }
+    }
+    (odd, even)
+}
+
+/// Longest palindromic substring as `(start, len)`; the leftmost one if several are longest.
+/// Returns `(0, 0)` for empty input.
+///
+/// Complexity: O(n)
+pub fn longest_palindrome<T: Eq>(s: &[T]) -> (usize, usize) {
+    let (odd, even) = manacher(s);
+    let mut best = (0, 0);
+    for i in 0..s.len() {
+        let candidates = [(i + 1 - odd[i], 2 * odd[i] - 1), (i - even[i], 2 * even[i])];
+        for (start, len) in candidates {
+            if len > best.1 || (len == best.1 && start < best.0) {
+                best = (start, len);
+            }
+        }
+    }
+    best
+}
+
+/// Longest palindromic substring, compared char by char.
+pub fn longest_palindrome_str(s: &str) -> &str {
+    let chars: Vec<char> = s.chars().collect();
+    let (start, len) = longest_palindrome(&chars);
+    let byte_offsets: Vec<usize> = s.char_indices().map(|(b, _)| b).chain(std::iter::once(s.len())).collect();
+    &s[byte_offsets[start]..byte_offsets[start + len]]
+}
+
+/// Levenshtein distance (unit-cost insert, delete, substitute) by the classic DP over two rows.
+///
+/// Complexity: O(n * m) time, O(m) space
+pub fn levenshtein<T: Eq>(a: &[T], b: &[T]) -> usize {
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, x) in a.iter().enumerate() {
+        cur[0] = i + 1;
+        for (j, y) in b.iter().enumerate() {
+            let sub = prev[j] + usize::from(x != y);
+            cur[j + 1] = sub.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Myers/Hyyrö bit-parallel Levenshtein for a pattern of at most 64 tokens.
+/// Each column of the DP is kept as vertical +1/-1 delta bitvectors, so one text token costs O(1) word ops.
+fn myers_distance(pattern: &[char], text: &[char]) -> usize {
+    let m = pattern.len();
+    debug_assert!(m <= 64);
+    if m == 0 {
+        return text.len();
+    }
+    let mut peq: HashMap<char, u64> = HashMap::new();
+    for (i, &c) in pattern.iter().enumerate() {
+        *peq.entry(c).or_insert(0) |= 1 << i;
+    }
+    let last = 1u64 << (m - 1);
+    let (mut pv, mut mv) = (u64::MAX, 0u64);
+    let mut score = m;
+    for c in text {
+        let eq = peq.get(c).copied().unwrap_or(0);
+        let xv = eq | mv;
+        let xh = ((eq & pv).wrapping_add(pv) ^ pv) | eq;
+        let mut ph = mv | !(xh | pv);
+        let mut mh = pv & xh;
+        if ph & last != 0 {
+            score += 1;
+        } else if mh & last != 0 {
+            score -= 1;
+        }
+        // row 0 of the DP grows by one per text token
+        ph = (ph << 1) | 1;
+        mh <<= 1;
+        pv = mh | !(xv | ph);
+        mv = ph & xv;
+    }
+    score
+}
+
+/// Levenshtein distance between two strings, counted in chars.
+/// Uses the bit-parallel Myers algorithm when the shorter string has at most 64 chars and falls back
+/// to the plain DP otherwise.
+///
+/// Complexity: O(n) for short patterns, O(n * m) otherwise
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (short, long) = if a.len() <= b.len() { (&a, &b) } else { (&b, &a) };
+    if short.len() <= 64 {
+        myers_distance(short, long)
+    } else {
+        levenshtein(short, long)
+    }
+}
+
+/// Returns the edit distance if it is at most `k`, otherwise None.
+/// Only the diagonal band of width 2k + 1 is computed and the scan stops as soon as a whole row
+/// exceeds `k`, which makes rejecting distant candidates cheap when fuzzy matching against a list.
+///
+/// Complexity: O(min(n, m) * k)
+pub fn distance_at_most(a: &str, b: &str, k: usize) -> Option<usize> {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let (n, m) = (a.len(), b.len());
+    if n.abs_diff(m) > k {
+        return None;
+    }
+    // every value above k is clamped to k + 1, which also stands in for cells outside the band
+    let cap = k + 1;
+    let mut prev: Vec<usize> = (0..=m).map(|j| j.min(cap)).collect();
+    let mut cur = vec![cap; m + 1];
+    for i in 1..=n {
+        let lo = i.saturating_sub(k).max(1);
+        let hi = (i + k).min(m);
+        cur[0] = i.min(cap);
+        cur[lo - 1] = if lo == 1 { cur[0] } else { cap };
+        let mut row_min = cur[lo - 1];
+        for j in lo..=hi {
+            let sub = prev[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            let v = sub.min(prev[j] + 1).min(cur[j - 1] + 1).min(cap);
+            cur[j] = v;
+            row_min = row_min.min(v);
+        }
+        if hi < m {
+            cur[hi + 1] = cap;
+        }
+        if row_min > k {
+            return None;
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    Some(prev[m]).filter(|&d| d <= k)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    fn is_lyndon(w: &[u8]) -> bool {
+        (1..w.len()).all(|r| {
+            let rotated: Vec<u8> = w[r..].iter().chain(&w[..r]).copied().collect();
+            w < &rotated[..]
+        })
+    }
+
+    #[test]
+    fn test_lyndon_factorization() {
+        assert_eq!(lyndon_factorization_str("banana"), vec!["b", "an", "an", "a"]);
+        assert_eq!(lyndon_factorization_str("abcab"), vec!["abc", "ab"]);
+        assert_eq!(lyndon_factorization_str("aaa"), vec!["a", "a", "a"]);
+        assert!(lyndon_factorization_str("").is_empty());
+        // multi-byte chars are never split
+        assert_eq!(lyndon_factorization_str("éa"), vec!["é", "a"]);
+    }
+
+    #[test]
+    fn test_lyndon_properties() {
+        let s = b"cabacbbabcbaabcab";
+        let starts = lyndon_factorization(s);
+        let mut factors = vec![];
+        for (f, &st) in starts.iter().enumerate() {
+            let end = starts.get(f + 1).copied().unwrap_or(s.len());
+            factors.push(&s[st..end]);
+        }
+        assert!(factors.iter().all(|w| is_lyndon(w)));
+        assert!(factors.windows(2).all(|p| p[0] >= p[1]));
+        assert_eq!(factors.concat(), s.to_vec());
+    }
+
+    #[test]
+    fn test_minimal_rotation() {
+        assert_eq!(minimal_rotation_str("bbaaccaadd"), "aaccaaddbb");
+        assert_eq!(minimal_rotation_str("cba"), "acb");
+        assert_eq!(minimal_rotation(b"abab"), 0);
+        assert_eq!(minimal_rotation::<u8>(&[]), 0);
+        let mut next = test_rng(5);
+        for len in 1..40 {
+            let s: Vec<u8> = (0..len).map(|_| (next() % 4) as u8).collect();
+            let brute = (0..len)
+                .min_by_key(|&r| s[r..].iter().chain(&s[..r]).copied().collect::<Vec<u8>>())
+                .unwrap();
+            assert_eq!(minimal_rotation(&s), brute, "{:?}", s);
+        }
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("", "abc"), 3);
+        assert_eq!(edit_distance("flaw", "lawn"), 2);
+        assert_eq!(edit_distance("same", "same"), 0);
+        // counted in chars, not bytes
+        assert_eq!(edit_distance("café", "cafe"), 1);
+        let long_a = "ab".repeat(50);
+        let long_b = "ba".repeat(50);
+        assert_eq!(edit_distance(&long_a, &long_b), 2);
+    }
+
+    #[test]
+    fn test_edit_distance_matches_dp() {
+        let mut next = test_rng(17);
+        let mut random = |len: usize| -> String { (0..len).map(|_| (b'a' + (next() % 4) as u8) as char).collect() };
+        for (la, lb) in [(0, 5), (10, 12), (63, 70), (64, 64), (65, 90), (30, 8)] {
+            let (a, b) = (random(la), random(lb));
+            let expected = levenshtein(a.as_bytes(), b.as_bytes());
+            assert_eq!(edit_distance(&a, &b), expected, "{} / {}", a, b);
+            for k in 0..expected + 3 {
+                let banded = distance_at_most(&a, &b, k);
+                assert_eq!(banded, (expected <= k).then_some(expected), "k = {}", k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_z_function() {
+        assert_eq!(z_function(b"aabxaab"), vec![7, 1, 0, 0, 3, 1, 0]);
+        assert_eq!(z_function(b"aaaaa"), vec![5, 4, 3, 2, 1]);
+        assert!(z_function::<u8>(&[]).is_empty());
+        let mut next = test_rng(5);
+        let s: Vec<u8> = (0..300).map(|_| b'a' + (next() % 2) as u8).collect();
+        let z = z_function(&s);
+        for i in 0..s.len() {
+            let naive = s.iter().zip(&s[i..]).take_while(|(a, b)| a == b).count();
+            assert_eq!(z[i], naive, "i = {}", i);
+        }
+    }
+
+    #[test]
+    fn test_manacher_matches_naive() {
+        let is_palindrome = |w: &[u8]| w.iter().eq(w.iter().rev());
+        let mut next = test_rng(9);
+        for n in [0, 1, 2, 7, 40, 120] {
+            let s: Vec<u8> = (0..n).map(|_| b'a' + (next() % 3) as u8).collect();
+            let (odd, even) = manacher(&s);
+            let mut count = 0;
+            let mut best = (0, 0);
+            for l in 0..n {
+                for r in l + 1..=n {
+                    if is_palindrome(&s[l..r]) {
+                        count += 1;
+                        if r - l > best.1 {
+                            best = (l, r - l);
+                        }
+                    }
+                }
+            }
+            for i in 0..n {
+                assert!(is_palindrome(&s[i + 1 - odd[i]..i + odd[i]]));
+                assert!(is_palindrome(&s[i - even[i]..i + even[i]]));
+            }
+            assert_eq!(odd.iter().sum::<usize>() + even.iter().sum::<usize>(), count);
+            assert_eq!(longest_palindrome(&s), best);
+        }
+    }
+
+    #[test]
+    fn test_longest_palindrome_str() {
+        assert_eq!(longest_palindrome_str("forgeeksskeegfor"), "geeksskeeg");
+        assert_eq!(longest_palindrome_str("abacdfgdcaba"), "aba");
+        assert_eq!(longest_palindrome_str("xyzé€éq"), "é€é");
+        assert_eq!(longest_palindrome_str(""), "");
+    }
+}
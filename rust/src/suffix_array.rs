@@ -0,0 +1,701 @@
+// rust/src/suffix_array.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use crate::snapshot::{Decoder, Encoder, Snapshot};
+/// Suffix array with LCP, built with SA-IS (induced sorting) and Kasai's algorithm.
+/// - Build: O(n) for the suffix array, O(n) for the LCP array
+/// - count_occurrences / find_occurrences: O(m log n) by binary searching the suffix array
+///
+/// Works on bytes, so indices are byte offsets for UTF-8 input (same convention as `kmp`).
+pub struct SuffixArray {
+    text: Vec<u8>,
+    sa: Vec<usize>,
+    rank: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl SuffixArray {
+    pub fn new(text: &str) -> Self {
+        Self::from_bytes(text.as_bytes())
+    }
+
+    pub fn from_bytes(text: &[u8]) -> Self {
+        let sa = suffix_array(text);
+        let mut rank = vec![0usize; sa.len()];
+        for (i, &p) in sa.iter().enumerate() {
+            rank[p] = i;
+        }
+        let lcp = lcp_array(text, &sa);
+        SuffixArray {
+            text: text.to_vec(),
+            sa,
+            rank,
+            lcp,
+        }
+    }
+
+    /// Starting positions of the suffixes in lexicographic order.
+    pub fn sa(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// `rank()[i]` is the position of suffix `i` inside `sa()`.
+    pub fn rank(&self) -> &[usize] {
+        &self.rank
+    }
+
+    /// `lcp()[i]` is the longest common prefix of suffixes `sa()[i - 1]` and `sa()[i]`; `lcp()[0]` is 0.
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    pub fn text(&self) -> &[u8] {
+        &self.text
+    }
+
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sa.is_empty()
+    }
+
+    /// Returns the half-open range of `sa()` whose suffixes start with `pattern`.
+    pub fn pattern_range(&self, pattern: &[u8]) -> (usize, usize) {
+        let s = &self.text;
+        let m = pattern.len();
+        let lo = self.sa.partition_point(|&i| &s[i..] < pattern);
+        let hi = self.sa.partition_point(|&i| &s[i..(i + m).min(s.len())] <= pattern);
+        (lo, hi.max(lo))
+    }
+
+    /// Number of (possibly overlapping) occurrences of `pattern`. Returns 0 for an empty pattern.
+    pub fn count_occurrences(&self, pattern: &str) -> usize {
+        if pattern.is_empty() {
+            return 0;
+        }
+        let (lo, hi) = self.pattern_range(pattern.as_bytes());
+        hi - lo
+    }
+
+    /// Sorted starting positions of every occurrence of `pattern`.
+    pub fn find_occurrences(&self, pattern: &str) -> Vec<usize> {
+        if pattern.is_empty() {
+            return vec![];
+        }
+        let (lo, hi) = self.pattern_range(pattern.as_bytes());
+        let mut res = self.sa[lo..hi].to_vec();
+        res.sort_unstable();
+        res
+    }
+}
+
+/// Suffix array with LCP over several strings at once, for queries across documents.
+/// The strings are concatenated with a distinct sentinel after each one, smaller than every byte, so
+/// no common prefix runs across a string boundary.
+/// - Build: O(N) for total length N
+/// - longest_common_substring(k): O(N log k)
+/// - find_occurrences: O(m log N)
+///
+/// Works on bytes; positions are `(string index, byte offset)` pairs.
+pub struct GeneralizedSuffixArray {
+    // string i's bytes b become k + b, followed by the sentinel i
+    text: Vec<usize>,
+    // starts[i] is where string i begins in `text`
+    starts: Vec<usize>,
+    sa: Vec<usize>,
+    lcp: Vec<usize>,
+}
+
+impl GeneralizedSuffixArray {
+    pub fn new<S: AsRef<str>>(strings: &[S]) -> Self {
+        let bytes: Vec<&[u8]> = strings.iter().map(|s| s.as_ref().as_bytes()).collect();
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn from_bytes<S: AsRef<[u8]>>(strings: &[S]) -> Self {
+        let k = strings.len();
+        let mut text = Vec::with_capacity(strings.iter().map(|s| s.as_ref().len() + 1).sum());
+        let mut starts = Vec::with_capacity(k);
+        for (i, s) in strings.iter().enumerate() {
+            starts.push(text.len());
+            text.extend(s.as_ref().iter().map(|&b| k + b as usize));
+            text.push(i);
+        }
+        let sa = sa_is(&text, k + 255);
+        let lcp = lcp_array(&text, &sa);
+        GeneralizedSuffixArray { text, starts, sa, lcp }
+    }
+
+    pub fn num_strings(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Suffix array of the concatenation. The first `num_strings()` entries are the sentinel suffixes.
+    pub fn sa(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// `lcp()[i]` is the longest common prefix of suffixes `sa()[i - 1]` and `sa()[i]`, never crossing
+    /// a string boundary.
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    /// `(string index, byte offset)` of a position in the concatenation.
+    pub fn locate(&self, pos: usize) -> (usize, usize) {
+        let doc = self.starts.partition_point(|&s| s <= pos) - 1;
+        (doc, pos - self.starts[doc])
+    }
+
+    /// Every occurrence of `pattern` in any of the strings, sorted. Empty for an empty pattern.
+    pub fn find_occurrences(&self, pattern: &str) -> Vec<(usize, usize)> {
+        if pattern.is_empty() {
+            return vec![];
+        }
+        let k = self.num_strings();
+        let p: Vec<usize> = pattern.bytes().map(|b| k + b as usize).collect();
+        let s = &self.text;
+        let m = p.len();
+        let lo = self.sa.partition_point(|&i| s[i..] < p[..]);
+        let hi = self.sa.partition_point(|&i| s[i..(i + m).min(s.len())] <= p[..]);
+        let mut res: Vec<(usize, usize)> = self.sa[lo..hi.max(lo)].iter().map(|&i| self.locate(i)).collect();
+        res.sort_unstable();
+        res
+    }
+
+    /// Longest substring that occurs in at least `k` of the strings, as its length and the first
+    /// occurrence `(string index, byte offset)` in every string that contains it, sorted by string.
+    /// Among equally long candidates the lexicographically smallest wins. Returns None if
+    /// `k == 0`, `k > num_strings()` or no non-empty substring is shared by `k` strings.
+    ///
+    /// A window of consecutive suffixes slides over the suffix array, kept minimal while it covers
+    /// `k` different strings; the shared prefix of the window is the minimum LCP inside it.
+    pub fn longest_common_substring(&self, k: usize) -> Option<(usize, Vec<(usize, usize)>)> {
+        let d = self.num_strings();
+        if k == 0 || k > d {
+            return None;
+        }
+        let doc = |rank: usize| self.locate(self.sa[rank]).0;
+        // (length, rank where the shared prefix is witnessed)
+        let mut best = (0, 0);
+        if k == 1 {
+            // the longest string itself
+            let len = |i: usize| self.starts.get(i + 1).unwrap_or(&self.text.len()) - self.starts[i] - 1;
+            let longest = (0..d).max_by_key(|&i| (len(i), std::cmp::Reverse(i))).expect("k <= d");
+            let rank = self.sa.iter().position(|&p| p == self.starts[longest]).expect("suffix exists");
+            best = (len(longest), rank);
+        } else {
+            let mut count = vec![0usize; d];
+            let mut covered = 0;
+            // ranks i in (lo, hi] with increasing lcp[i], for the window minimum
+            let mut mins = std::collections::VecDeque::new();
+            let mut lo = d;
+            for hi in d..self.sa.len() {
+                count[doc(hi)] += 1;
+                if count[doc(hi)] == 1 {
+                    covered += 1;
+                }
+                if hi > lo {
+                    while mins.back().is_some_and(|&i| self.lcp[i] >= self.lcp[hi]) {
+                        mins.pop_back();
+                    }
+                    mins.push_back(hi);
+                }
+                while count[doc(lo)] > 1 || (covered > k && lo < hi) {
+                    count[doc(lo)] -= 1;
+                    if count[doc(lo)] == 0 {
+                        covered -= 1;
+                    }
+                    lo += 1;
+                    while mins.front().is_some_and(|&i| i <= lo) {
+                        mins.pop_front();
+                    }
+                }
+                if covered >= k
+                    && let Some(&i) = mins.front()
+                    && self.lcp[i] > best.0
+                {
+                    best = (self.lcp[i], i);
+                }
+            }
+        }
+        let (len, at) = best;
+        if len == 0 {
+            return None;
+        }
+        // every suffix in the run around `at` sharing the first `len` symbols
+        let mut first = at;
+        while first > 0 && self.lcp[first] >= len {
+            first -= 1;
+        }
+        let mut last = at;
+        while last + 1 < self.sa.len() && self.lcp[last + 1] >= len {
+            last += 1;
+        }
+        let mut occurrences: Vec<(usize, usize)> = self.sa[first..=last].iter().map(|&p| self.locate(p)).collect();
+        occurrences.sort_unstable();
+        occurrences.dedup_by_key(|&mut (doc, _)| doc);
+        Some((len, occurrences))
+    }
+}
+
+/// Builds the suffix array of a byte string.
+pub fn suffix_array(s: &[u8]) -> Vec<usize> {
+    let s: Vec<usize> = s.iter().map(|&b| b as usize).collect();
+    sa_is(&s, 255)
+}
+
+/// Kasai's algorithm. `lcp[i]` is the LCP of suffixes `sa[i - 1]` and `sa[i]`, with `lcp[0] = 0`.
+pub fn lcp_array<T: Eq>(s: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    let mut rank = vec![0usize; n];
+    for (i, &p) in sa.iter().enumerate() {
+        rank[p] = i;
+    }
+    let mut lcp = vec![0usize; n];
+    let mut k = 0;
+    for i in 0..n {
+        if rank[i] == 0 {
+            k = 0;
+            continue;
+        }
+        let j = sa[rank[i] - 1];
+        while i + k < n && j + k < n && s[i + k] == s[j + k] {
+            k += 1;
+        }
+        lcp[rank[i]] = k;
+        k = k.saturating_sub(1);
+    }
+    lcp
+}
+
+/// Suffix array and LCP over the chars of `s`, plus the byte offset of every char (and of the end).
+fn char_suffix_array(s: &str) -> (Vec<usize>, Vec<usize>, Vec<usize>) {
+    let chars: Vec<char> = s.chars().collect();
+    let mut alphabet = chars.clone();
+    alphabet.sort_unstable();
+    alphabet.dedup();
+    let ranks: Vec<usize> = chars.iter().map(|c| alphabet.binary_search(c).expect("char is in alphabet")).collect();
+    let sa = sa_is(&ranks, alphabet.len().saturating_sub(1));
+    let lcp = lcp_array(&chars, &sa);
+    let offsets = s.char_indices().map(|(b, _)| b).chain(std::iter::once(s.len())).collect();
+    (sa, lcp, offsets)
+}
+
+/// Longest substring occurring at least twice (occurrences may overlap), with the sorted byte offsets
+/// of all its occurrences. Ties go to the lexicographically smallest substring.
+/// Works on chars, so the result is always a valid `&str`. Returns None if no char repeats.
+///
+/// Complexity: O(n log σ)
+pub fn longest_repeated_substring(s: &str) -> Option<(&str, Vec<usize>)> {
+    let (sa, lcp, offsets) = char_suffix_array(s);
+    let (best, len) = lcp.iter().copied().enumerate().max_by_key(|&(i, l)| (l, std::cmp::Reverse(i)))?;
+    if len == 0 {
+        return None;
+    }
+    Some(occurrences(s, &sa, &lcp, &offsets, best, len))
+}
+
+/// Like `longest_repeated_substring`, but two of the occurrences must not overlap
+/// ("aaaa" gives "aa" instead of "aaa").
+/// Binary search on the length; a length is feasible if some run of suffixes sharing that prefix
+/// contains two starts at least that far apart.
+///
+/// Complexity: O(n log n)
+pub fn longest_repeated_substring_non_overlapping(s: &str) -> Option<(&str, Vec<usize>)> {
+    let (sa, lcp, offsets) = char_suffix_array(s);
+    // first position in the suffix array of a run witnessing `len`, if any
+    let witness = |len: usize| -> Option<usize> {
+        let (mut lo, mut hi) = (sa.first().copied()?, sa[0]);
+        for i in 1..sa.len() {
+            if lcp[i] >= len {
+                lo = lo.min(sa[i]);
+                hi = hi.max(sa[i]);
+                if hi - lo >= len {
+                    return Some(i);
+                }
+            } else {
+                (lo, hi) = (sa[i], sa[i]);
+            }
+        }
+        None
+    };
+    let (mut lo, mut hi) = (0, sa.len() / 2);
+    while lo < hi {
+        let mid = (lo + hi).div_ceil(2);
+        if witness(mid).is_some() {
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    if lo == 0 {
+        return None;
+    }
+    let at = witness(lo).expect("feasible length");
+    Some(occurrences(s, &sa, &lcp, &offsets, at, lo))
+}
+
+/// The substring of length `len` (in chars) shared by suffixes `sa[at - 1]` and `sa[at]`, and the
+/// byte offsets of every suffix in the surrounding run with LCP >= `len`.
+fn occurrences<'a>(
+    s: &'a str,
+    sa: &[usize],
+    lcp: &[usize],
+    offsets: &[usize],
+    at: usize,
+    len: usize,
+) -> (&'a str, Vec<usize>) {
+    let mut first = at;
+    while first > 0 && lcp[first] >= len {
+        first -= 1;
+    }
+    let mut last = at;
+    while last + 1 < sa.len() && lcp[last + 1] >= len {
+        last += 1;
+    }
+    let mut positions: Vec<usize> = sa[first..=last].iter().map(|&p| offsets[p]).collect();
+    positions.sort_unstable();
+    let start = sa[at];
+    (&s[offsets[start]..offsets[start + len]], positions)
+}
+
+const NONE: usize = usize::MAX;
+
+/// SA-IS over an integer alphabet `0..=upper`.
+pub(crate) fn sa_is(s: &[usize], upper: usize) -> Vec<usize> {
+    let n = s.len();
+    match n {
+        0 => return vec![],
+        1 => return vec![0],
+        2 => return if s[0] < s[1] { vec![0, 1] } else { vec![1, 0] },
+        _ => {}
+    }
+
+    // ls[i] is true when suffix i is S-type (smaller than suffix i + 1)
+    let mut ls = vec![false; n];
+    for i in (0..n - 1).rev() {
+        ls[i] = if s[i] == s[i + 1] { ls[i + 1] } else { s[i] < s[i + 1] };
+    }
+
+    // bucket boundaries: sum_l[c] is the start of bucket c, sum_s[c] the start of its S part
+    let mut sum_l = vec![0usize; upper + 1];
+    let mut sum_s = vec![0usize; upper + 1];
+    for i in 0..n {
+        if !ls[i] {
+            sum_s[s[i]] += 1;
+        } else {
+            sum_l[s[i] + 1] += 1;
+        }
+    }
+    for i in 0..=upper {
+        sum_s[i] += sum_l[i];
+        if i < upper {
+            sum_l[i + 1] += sum_s[i];
+        }
+    }
+
+    let mut sa = vec![NONE; n];
+    let induce = |lms: &[usize], sa: &mut Vec<usize>| {
+        sa.iter_mut().for_each(|x| *x = NONE);
+        let mut buf = sum_s.clone();
+        for &d in lms {
+            if d == n {
+                continue;
+            }
+            sa[buf[s[d]]] = d;
+            buf[s[d]] += 1;
+        }
+        buf.copy_from_slice(&sum_l);
+        sa[buf[s[n - 1]]] = n - 1;
+        buf[s[n - 1]] += 1;
+        for i in 0..n {
+            let v = sa[i];
+            if v != NONE && v >= 1 && !ls[v - 1] {
+                sa[buf[s[v - 1]]] = v - 1;
+                buf[s[v - 1]] += 1;
+            }
+        }
+        buf.copy_from_slice(&sum_l);
+        for i in (0..n).rev() {
+            let v = sa[i];
+            if v != NONE && v >= 1 && ls[v - 1] {
+                buf[s[v - 1] + 1] -= 1;
+                sa[buf[s[v - 1] + 1]] = v - 1;
+            }
+        }
+    };
+
+    // LMS positions: S-type suffixes preceded by an L-type suffix
+    let mut lms_map = vec![NONE; n + 1];
+    let mut lms = Vec::new();
+    for i in 1..n {
+        if !ls[i - 1] && ls[i] {
+            lms_map[i] = lms.len();
+            lms.push(i);
+        }
+    }
+    let m = lms.len();
+    induce(&lms, &mut sa);
+
+    if m > 0 {
+        let mut sorted_lms = Vec::with_capacity(m);
+        for &v in &sa {
+            if lms_map[v] != NONE {
+                sorted_lms.push(v);
+            }
+        }
+        // name LMS substrings, equal substrings share a name
+        let mut rec_s = vec![0usize; m];
+        let mut rec_upper = 0;
+        rec_s[lms_map[sorted_lms[0]]] = 0;
+        for i in 1..m {
+            let mut l = sorted_lms[i - 1];
+            let mut r = sorted_lms[i];
+            let end_l = if lms_map[l] + 1 < m { lms[lms_map[l] + 1] } else { n };
+            let end_r = if lms_map[r] + 1 < m { lms[lms_map[r] + 1] } else { n };
+            let mut same = true;
+            if end_l - l != end_r - r {
+                same = false;
+            } else {
+                while l < end_l && s[l] == s[r] {
+                    l += 1;
+                    r += 1;
+                }
+                if l == n || s[l] != s[r] {
+                    same = false;
+                }
+            }
+            if !same {
+                rec_upper += 1;
+            }
+            rec_s[lms_map[sorted_lms[i]]] = rec_upper;
+        }
+        let rec_sa = sa_is(&rec_s, rec_upper);
+        for i in 0..m {
+            sorted_lms[i] = lms[rec_sa[i]];
+        }
+        induce(&sorted_lms, &mut sa);
+    }
+    sa
+}
+
+impl DataStructure for SuffixArray {
+    const INFO: Info = Info {
+        name: "SuffixArray",
+        category: "strings",
+        summary: "Suffix array with LCP via SA-IS and Kasai (m = pattern length)",
+        space: "O(n)",
+        operations: &[
+            Operation::new("new", "O(n)", "O(n)"),
+            Operation::new("count_occurrences", "O(m log n)", "O(1)"),
+            Operation::new("find_occurrences", "O(m log n + k log k)", "O(k)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+/// Stores the text, suffix array and LCP array; the rank array is rebuilt on load.
+impl Snapshot for SuffixArray {
+    const KIND: [u8; 4] = *b"SUFA";
+
+    fn encode(&self, out: &mut Encoder) {
+        out.slice(&self.text);
+        out.usizes(&self.sa);
+        out.usizes(&self.lcp);
+    }
+
+    fn decode(input: &mut Decoder<'_>) -> Option<Self> {
+        let text: Vec<u8> = input.vec()?;
+        let sa = input.usizes()?;
+        let lcp = input.usizes()?;
+        let n = text.len();
+        if sa.len() != n || lcp.len() != n {
+            return None;
+        }
+        let mut rank = vec![usize::MAX; n];
+        for (i, &p) in sa.iter().enumerate() {
+            // each suffix must appear exactly once
+            if p >= n || rank[p] != usize::MAX {
+                return None;
+            }
+            rank[p] = i;
+        }
+        Some(SuffixArray { text, sa, rank, lcp })
+    }
+}
+
+impl DataStructure for GeneralizedSuffixArray {
+    const INFO: Info = Info {
+        name: "GeneralizedSuffixArray",
+        category: "strings",
+        summary: "Suffix array with LCP over several strings joined by distinct sentinels (N = total length)",
+        space: "O(N)",
+        operations: &[
+            Operation::new("new", "O(N)", "O(N)"),
+            Operation::new("find_occurrences", "O(m log N)", "O(k)"),
+            Operation::new("longest_common_substring", "O(N log k)", "O(N)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    fn naive_sa(s: &[u8]) -> Vec<usize> {
+        let mut sa: Vec<usize> = (0..s.len()).collect();
+        sa.sort_by(|&a, &b| s[a..].cmp(&s[b..]));
+        sa
+    }
+
+    #[test]
+    fn test_banana() {
+        let sa = SuffixArray::new("banana");
+        assert_eq!(sa.sa(), &[5, 3, 1, 0, 4, 2]);
+        assert_eq!(sa.lcp(), &[0, 1, 3, 0, 0, 2]);
+        assert_eq!(sa.rank()[0], 3);
+    }
+
+    #[test]
+    fn test_matches_naive() {
+        let mut next = test_rng(42);
+        for len in [0usize, 1, 2, 3, 7, 50, 300] {
+            let s: Vec<u8> = (0..len).map(|_| b'a' + (next() % 3) as u8).collect();
+            assert_eq!(suffix_array(&s), naive_sa(&s));
+        }
+        let repeated = vec![b'a'; 100];
+        assert_eq!(suffix_array(&repeated), naive_sa(&repeated));
+    }
+
+    #[test]
+    fn test_count_and_find() {
+        let sa = SuffixArray::new("abracadabra");
+        assert_eq!(sa.count_occurrences("abra"), 2);
+        assert_eq!(sa.count_occurrences("a"), 5);
+        assert_eq!(sa.count_occurrences("cad"), 1);
+        assert_eq!(sa.count_occurrences("xyz"), 0);
+        assert_eq!(sa.count_occurrences(""), 0);
+        assert_eq!(sa.count_occurrences("abracadabrax"), 0);
+        assert_eq!(sa.find_occurrences("a"), vec![0, 3, 5, 7, 10]);
+        assert_eq!(sa.find_occurrences("bra"), vec![1, 8]);
+    }
+
+    #[test]
+    fn test_empty_text() {
+        let sa = SuffixArray::new("");
+        assert!(sa.is_empty());
+        assert_eq!(sa.count_occurrences("a"), 0);
+        assert!(sa.lcp().is_empty());
+    }
+
+    #[test]
+    fn test_longest_repeated_substring() {
+        assert_eq!(longest_repeated_substring("banana"), Some(("ana", vec![1, 3])));
+        assert_eq!(longest_repeated_substring("abcd"), None);
+        assert_eq!(longest_repeated_substring(""), None);
+        assert_eq!(longest_repeated_substring("aaaa"), Some(("aaa", vec![0, 1])));
+        // ties go to the smaller substring, and multi-byte chars are never split
+        assert_eq!(longest_repeated_substring("xyxy-abab"), Some(("ab", vec![5, 7])));
+        assert_eq!(longest_repeated_substring("éaèbéc"), Some(("é", vec![0, 6])));
+
+        assert_eq!(longest_repeated_substring_non_overlapping("aaaa"), Some(("aa", vec![0, 1, 2])));
+        assert_eq!(longest_repeated_substring_non_overlapping("banana"), Some(("an", vec![1, 3])));
+        assert_eq!(longest_repeated_substring_non_overlapping("aa"), Some(("a", vec![0, 1])));
+        assert_eq!(longest_repeated_substring_non_overlapping("abc"), None);
+    }
+
+    #[test]
+    fn test_longest_repeated_matches_brute_force() {
+        let mut next = test_rng(8);
+        for len in [5usize, 20, 60] {
+            let s: String = (0..len).map(|_| (b'a' + (next() % 2) as u8) as char).collect();
+            let b = s.as_bytes();
+            let longest = |overlap: bool| {
+                (1..len)
+                    .rev()
+                    .find(|&l| {
+                        (0..=len - l).any(|i| {
+                            (i + 1..=len - l).any(|j| (overlap || j - i >= l) && b[i..i + l] == b[j..j + l])
+                        })
+                    })
+                    .unwrap_or(0)
+            };
+            assert_eq!(longest_repeated_substring(&s).map_or(0, |(t, _)| t.len()), longest(true), "{}", s);
+            let (t, pos) = longest_repeated_substring_non_overlapping(&s).unwrap();
+            assert_eq!(t.len(), longest(false), "{}", s);
+            assert_eq!(pos, crate::kmp::find_all(&s, t));
+        }
+    }
+
+    #[test]
+    fn test_generalized_suffix_array() {
+        let docs = ["the quick brown fox", "a quick brown dog", "quick brown foxes", "lazy dog"];
+        let gsa = GeneralizedSuffixArray::new(&docs);
+        assert_eq!(gsa.num_strings(), 4);
+        assert_eq!(gsa.longest_common_substring(3), Some((12, vec![(0, 4), (1, 2), (2, 0)])));
+        assert_eq!(gsa.longest_common_substring(2), Some((15, vec![(0, 4), (2, 0)])));
+        assert_eq!(gsa.longest_common_substring(4), Some((1, vec![(0, 3), (1, 1), (2, 5), (3, 4)])));
+        assert_eq!(gsa.longest_common_substring(1), Some((19, vec![(0, 0)])));
+        assert_eq!(gsa.longest_common_substring(5), None);
+        assert_eq!(gsa.longest_common_substring(0), None);
+        assert_eq!(gsa.find_occurrences("dog"), vec![(1, 14), (3, 5)]);
+        assert_eq!(gsa.find_occurrences("cat"), vec![]);
+
+        // matches never run across string boundaries
+        let gsa = GeneralizedSuffixArray::new(&["ab", "ab", "c"]);
+        assert_eq!(gsa.longest_common_substring(2), Some((2, vec![(0, 0), (1, 0)])));
+        assert_eq!(GeneralizedSuffixArray::new(&["abc", "xyz"]).longest_common_substring(2), None);
+        assert_eq!(GeneralizedSuffixArray::new::<&str>(&[]).longest_common_substring(1), None);
+    }
+
+    #[test]
+    fn test_longest_common_substring_brute_force() {
+        let mut rng = test_rng(91);
+        let mut next = |len: usize| -> String { (0..len).map(|_| (b'a' + (rng() % 3) as u8) as char).collect() };
+        for round in 0..20 {
+            let docs: Vec<String> = (0..2 + round % 4).map(|i| next(5 + 7 * i)).collect();
+            let gsa = GeneralizedSuffixArray::new(&docs);
+            for k in 2..=docs.len() {
+                let shared = |sub: &str| docs.iter().filter(|d| d.contains(sub)).count() >= k;
+                let expected = docs
+                    .iter()
+                    .flat_map(|d| (0..d.len()).flat_map(move |i| (i + 1..=d.len()).map(move |j| &d[i..j])))
+                    .filter(|sub| shared(sub))
+                    .map(str::len)
+                    .max()
+                    .unwrap_or(0);
+                let got = gsa.longest_common_substring(k);
+                assert_eq!(got.as_ref().map_or(0, |(len, _)| *len), expected, "{:?} k={}", docs, k);
+                if let Some((len, occ)) = got {
+                    let (d0, o0) = occ[0];
+                    let sub = &docs[d0][o0..o0 + len];
+                    let expected_occ: Vec<(usize, usize)> =
+                        docs.iter().enumerate().filter_map(|(i, d)| d.find(sub).map(|o| (i, o))).collect();
+                    assert_eq!(occ, expected_occ);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let sa = SuffixArray::new("mississippi");
+        let back = SuffixArray::from_snapshot(&sa.to_snapshot()).unwrap();
+        assert_eq!((back.sa(), back.rank(), back.lcp(), back.text()), (sa.sa(), sa.rank(), sa.lcp(), sa.text()));
+        assert_eq!(back.find_occurrences("ssi"), vec![2, 5]);
+
+        // the suffix array must be a permutation
+        let mut bytes = sa.to_snapshot();
+        let first_sa_entry = 20 + 8 + 11 + 8;
+        bytes[first_sa_entry..first_sa_entry + 8].copy_from_slice(&1u64.to_le_bytes());
+        bytes[first_sa_entry + 8..first_sa_entry + 16].copy_from_slice(&1u64.to_le_bytes());
+        assert!(SuffixArray::from_snapshot(&bytes).is_err());
+    }
+}
@@ -0,0 +1,229 @@
+// rust/src/suffix_automaton.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+struct State {
+    len: usize,
+    link: Option<usize>,
+    next: BTreeMap<u8, usize>,
+    // number of end positions of the substrings in this state
+    occ: usize,
+}
+
+/// A suffix automaton (SAM): the minimal DFA accepting every substring of a text.
+/// It has at most 2n - 1 states and 3n - 4 transitions, which makes it a compact alternative to suffix trees.
+/// - Build: O(n log σ)
+/// - contains / count_occurrences: O(m log σ)
+/// - distinct_substrings: O(states)
+///
+/// Works on bytes, like the rest of the string modules.
+pub struct SuffixAutomaton {
+    states: Vec<State>,
+    last: usize,
+}
+
+impl SuffixAutomaton {
+    pub fn new(text: &str) -> Self {
+        Self::from_bytes(text.as_bytes())
+    }
+
+    pub fn from_bytes(text: &[u8]) -> Self {
+        let mut sam = SuffixAutomaton {
+            states: vec![State { len: 0, link: None, next: BTreeMap::new(), occ: 0 }],
+            last: 0,
+        };
+        for &c in text {
+            sam.extend(c);
+        }
+        sam.count_end_positions();
+        sam
+    }
+
+    fn extend(&mut self, c: u8) {
+        let cur = self.states.len();
+        self.states.push(State {
+            len: self.states[self.last].len + 1,
+            link: None,
+            next: BTreeMap::new(),
+            occ: 1,
+        });
+        let mut p = Some(self.last);
+        while let Some(v) = p {
+            if self.states[v].next.contains_key(&c) {
+                break;
+            }
+            self.states[v].next.insert(c, cur);
+            p = self.states[v].link;
+        }
+        match p {
+            None => self.states[cur].link = Some(0),
+            Some(v) => {
+                let q = self.states[v].next[&c];
+                if self.states[v].len + 1 == self.states[q].len {
+                    self.states[cur].link = Some(q);
+                } else {
+                    // split q by cloning it with a shorter length
+                    let clone = self.states.len();
+                    let mut cloned = self.states[q].clone();
+                    cloned.len = self.states[v].len + 1;
+                    cloned.occ = 0;
+                    self.states.push(cloned);
+                    let mut p = Some(v);
+                    while let Some(u) = p {
+                        if self.states[u].next.get(&c) != Some(&q) {
+                            break;
+                        }
+                        self.states[u].next.insert(c, clone);
+                        p = self.states[u].link;
+                    }
+                    self.states[q].link = Some(clone);
+                    self.states[cur].link = Some(clone);
+                }
+            }
+        }
+        self.last = cur;
+    }
+
+    /// Propagates end-position counts up the suffix links, longest states first.
+    fn count_end_positions(&mut self) {
+        let max_len = self.states[self.last].len;
+        let mut buckets = vec![0usize; max_len + 1];
+        for s in &self.states {
+            buckets[s.len] += 1;
+        }
+        for i in 1..=max_len {
+            buckets[i] += buckets[i - 1];
+        }
+        let mut order = vec![0usize; self.states.len()];
+        for (i, s) in self.states.iter().enumerate().rev() {
+            buckets[s.len] -= 1;
+            order[buckets[s.len]] = i;
+        }
+        for &v in order.iter().rev() {
+            if let Some(link) = self.states[v].link {
+                self.states[link].occ += self.states[v].occ;
+            }
+        }
+    }
+
+    fn walk(&self, pattern: &[u8]) -> Option<usize> {
+        let mut v = 0;
+        for c in pattern {
+            v = *self.states[v].next.get(c)?;
+        }
+        Some(v)
+    }
+
+    pub fn num_states(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Returns true if `pattern` is a substring of the text. The empty pattern is always contained.
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.walk(pattern.as_bytes()).is_some()
+    }
+
+    /// Number of (possibly overlapping) occurrences of `pattern`. Returns 0 for an empty pattern.
+    pub fn count_occurrences(&self, pattern: &str) -> usize {
+        if pattern.is_empty() {
+            return 0;
+        }
+        self.walk(pattern.as_bytes()).map(|v| self.states[v].occ).unwrap_or(0)
+    }
+
+    /// Number of distinct non-empty substrings of the text.
+    pub fn distinct_substrings(&self) -> u64 {
+        self.states
+            .iter()
+            .skip(1)
+            .map(|s| (s.len - self.states[s.link.unwrap_or(0)].len) as u64)
+            .sum()
+    }
+
+    /// Longest substring shared by the text and `other`.
+    /// Returns `(start, len)` where `start` is the byte offset of the match inside `other`.
+    pub fn longest_common_substring(&self, other: &str) -> (usize, usize) {
+        let (mut v, mut l) = (0usize, 0usize);
+        let (mut best, mut best_end) = (0usize, 0usize);
+        for (i, c) in other.bytes().enumerate() {
+            while v != 0 && !self.states[v].next.contains_key(&c) {
+                v = self.states[v].link.unwrap_or(0);
+                l = self.states[v].len;
+            }
+            if let Some(&nv) = self.states[v].next.get(&c) {
+                v = nv;
+                l += 1;
+            }
+            if l > best {
+                best = l;
+                best_end = i + 1;
+            }
+        }
+        (best_end - best, best)
+    }
+}
+
+impl DataStructure for SuffixAutomaton {
+    const INFO: Info = Info {
+        name: "SuffixAutomaton",
+        category: "strings",
+        summary: "Minimal DFA of all substrings of a text (m = pattern length)",
+        space: "O(n)",
+        operations: &[
+            Operation::new("new", "O(n log σ)", "O(n)"),
+            Operation::new("contains", "O(m log σ)", "O(1)"),
+            Operation::new("count_occurrences", "O(m log σ)", "O(1)"),
+            Operation::new("distinct_substrings", "O(n)", "O(1)"),
+            Operation::new("longest_common_substring", "O(m log σ)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_contains_and_occurrences() {
+        let sam = SuffixAutomaton::new("abracadabra");
+        assert!(sam.contains("cad"));
+        assert!(sam.contains("abracadabra"));
+        assert!(!sam.contains("abc"));
+        assert_eq!(sam.count_occurrences("abra"), 2);
+        assert_eq!(sam.count_occurrences("a"), 5);
+        assert_eq!(sam.count_occurrences("z"), 0);
+        assert_eq!(sam.count_occurrences(""), 0);
+        assert!(sam.num_states() < 2 * 11);
+    }
+
+    #[test]
+    fn test_distinct_substrings() {
+        for text in ["", "a", "aaaa", "abab", "abracadabra", "mississippi"] {
+            let bytes = text.as_bytes();
+            let mut set = HashSet::new();
+            for i in 0..bytes.len() {
+                for j in i + 1..=bytes.len() {
+                    set.insert(&bytes[i..j]);
+                }
+            }
+            let sam = SuffixAutomaton::new(text);
+            assert_eq!(sam.distinct_substrings(), set.len() as u64, "text {}", text);
+        }
+    }
+
+    #[test]
+    fn test_longest_common_substring() {
+        let sam = SuffixAutomaton::new("xabcdey");
+        let other = "zzbcdzz";
+        let (start, len) = sam.longest_common_substring(other);
+        assert_eq!(&other[start..start + len], "bcd");
+
+        let sam = SuffixAutomaton::new("abc");
+        assert_eq!(sam.longest_common_substring("xyz"), (0, 0));
+    }
+}
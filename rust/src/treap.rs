@@ -1,15 +1,11 @@
+use crate::DataStructure;
+use crate::dot::{DotWriter, Highlight};
+use crate::info::{Info, Operation};
+use crate::rng::{Rng, SplitMix64};
 use std::sync::atomic::{AtomicU64, Ordering};
 
-static SPLITMIX64_SEED: AtomicU64 = AtomicU64::new(0x9E3779B97F4A7C15);
-
-#[inline]
-fn splitmix64(x: &mut u64) -> u64 {
-    *x = x.wrapping_add(0x9E3779B97F4A7C15);
-    let mut z = *x;
-    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
-    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
-    z ^ (z >> 31)
-}
+/// Bumped by every `Treap::new()`, so that unseeded treaps draw independent priority streams.
+static NEXT_SEED: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug)]
 struct Node {
@@ -33,13 +29,7 @@ pub struct NodeInfo {
 }
 
 impl Node {
-    fn new(key: i64) -> Self {
-        // Get the current seed and generate a new priority
-        let mut seed = SPLITMIX64_SEED.load(Ordering::Relaxed);
-        let priority = splitmix64(&mut seed);
-        // Store the updated seed
-        SPLITMIX64_SEED.store(seed, Ordering::Relaxed);
-
+    fn new(key: i64, priority: u64) -> Self {
         Self {
             key,
             priority, // The new random priority
@@ -69,14 +59,30 @@ impl Node {
     }
 }
 
-/// A Treap (Cartesian tree) keyed by i64 with randomized priorities.
+/// A Treap (Cartesian tree) keyed by i64 with randomized priorities, drawn from a seeded
+/// `SplitMix64` so that a given seed always builds the same shape.
 pub struct Treap {
     root: Option<Box<Node>>,
+    rng: SplitMix64,
+}
+
+impl Default for Treap {
+    fn default() -> Self {
+        Treap::new()
+    }
 }
 
 impl Treap {
+    /// Empty treap with a fresh seed. Treaps that get split or unioned together must not share
+    /// a priority stream, so each call mixes a process-wide counter into the default seed.
     pub fn new() -> Self {
-        Treap { root: None }
+        let n = NEXT_SEED.fetch_add(1, Ordering::Relaxed);
+        Treap::with_seed(SplitMix64::new(n).next_u64())
+    }
+
+    /// Empty treap whose priorities come from `SplitMix64::new(seed)`.
+    pub fn with_seed(seed: u64) -> Self {
+        Treap { root: None, rng: SplitMix64::new(seed) }
     }
 
     pub fn len(&self) -> usize {
@@ -101,21 +107,57 @@ impl Treap {
         false
     }
 
+    /// Returns the smallest key, if any.
+    pub fn min(&self) -> Option<i64> {
+        let mut cur = self.root.as_ref()?;
+        while let Some(left) = cur.left.as_ref() {
+            cur = left;
+        }
+        Some(cur.key)
+    }
+
+    /// Returns the largest key, if any.
+    pub fn max(&self) -> Option<i64> {
+        let mut cur = self.root.as_ref()?;
+        while let Some(right) = cur.right.as_ref() {
+            cur = right;
+        }
+        Some(cur.key)
+    }
+
+    /// Returns the `k`-th smallest key (0-based, duplicates counted), or None if `k >= len()`.
+    pub fn kth(&self, mut k: usize) -> Option<i64> {
+        let mut cur = self.root.as_ref();
+        while let Some(node) = cur {
+            let left_size = node.left.as_ref().map(|n| n.size).unwrap_or(0);
+            if k < left_size {
+                cur = node.left.as_ref();
+            } else if k < left_size + node.count {
+                return Some(node.key);
+            } else {
+                k -= left_size + node.count;
+                cur = node.right.as_ref();
+            }
+        }
+        None
+    }
+
     /// Insert one occurrence of `key`.
     pub fn insert(&mut self, key: i64) {
-        self.root = Self::insert_rec(self.root.take(), key);
+        let priority = self.rng.next_u64();
+        self.root = Self::insert_rec(self.root.take(), key, priority);
     }
 
-    fn insert_rec(node: Option<Box<Node>>, key: i64) -> Option<Box<Node>> {
+    fn insert_rec(node: Option<Box<Node>>, key: i64, priority: u64) -> Option<Box<Node>> {
         match node {
-            None => Some(Box::new(Node::new(key))), 
+            None => Some(Box::new(Node::new(key, priority))), 
             Some(mut boxed) => {
                 if key == boxed.key {
                     boxed.count += 1;
                     boxed.recalc();
                     Some(boxed)
                 } else if key < boxed.key {
-                    boxed.left = Self::insert_rec(boxed.left.take(), key);
+                    boxed.left = Self::insert_rec(boxed.left.take(), key, priority);
                     // heap property
                     if boxed.left.as_ref().map(|n| n.priority).unwrap_or(0) > boxed.priority {
                         boxed = Self::rotate_right(boxed);
@@ -124,7 +166,7 @@ impl Treap {
                     }
                     Some(boxed)
                 } else {
-                    boxed.right = Self::insert_rec(boxed.right.take(), key);
+                    boxed.right = Self::insert_rec(boxed.right.take(), key, priority);
                     if boxed.right.as_ref().map(|n| n.priority).unwrap_or(0) > boxed.priority {
                         boxed = Self::rotate_left(boxed);
                     } else {
@@ -203,6 +245,93 @@ impl Treap {
         y
     }
 
+    /// Removes one occurrence of the smallest key and returns it.
+    pub fn pop_min(&mut self) -> Option<i64> {
+        let key = self.min()?;
+        self.remove(key);
+        Some(key)
+    }
+
+    /// Removes one occurrence of the largest key and returns it.
+    pub fn pop_max(&mut self) -> Option<i64> {
+        let key = self.max()?;
+        self.remove(key);
+        Some(key)
+    }
+
+    /// Splits off every key `>= key` into a new treap, which draws its priorities from a generator
+    /// seeded by this one. O(log n) expected.
+    pub fn split_off(&mut self, key: i64) -> Treap {
+        let (less, rest) = Self::split(self.root.take(), key);
+        self.root = less;
+        Treap { root: rest, rng: SplitMix64::new(self.rng.next_u64()) }
+    }
+
+    /// Moves all keys of `other` into `self`, keeping duplicates.
+    /// Expected O(m log(n / m)) for treaps of sizes m <= n, which is O(log n) when the key ranges
+    /// do not interleave.
+    pub fn union(&mut self, other: Treap) {
+        self.root = Self::union_rec(self.root.take(), other.root);
+    }
+
+    /// Splits into keys `< key` and keys `>= key`.
+    fn split(node: Option<Box<Node>>, key: i64) -> (Option<Box<Node>>, Option<Box<Node>>) {
+        match node {
+            None => (None, None),
+            Some(mut boxed) => {
+                if boxed.key < key {
+                    let (l, r) = Self::split(boxed.right.take(), key);
+                    boxed.right = l;
+                    boxed.recalc();
+                    (Some(boxed), r)
+                } else {
+                    let (l, r) = Self::split(boxed.left.take(), key);
+                    boxed.left = r;
+                    boxed.recalc();
+                    (l, Some(boxed))
+                }
+            }
+        }
+    }
+
+    /// Removes the node holding `key` (all occurrences), returning the rest and the removed count.
+    fn extract(node: Option<Box<Node>>, key: i64) -> (Option<Box<Node>>, usize) {
+        match node {
+            None => (None, 0),
+            Some(mut boxed) => {
+                if key == boxed.key {
+                    (Self::merge(boxed.left.take(), boxed.right.take()), boxed.count)
+                } else {
+                    let child = if key < boxed.key { &mut boxed.left } else { &mut boxed.right };
+                    let (rest, count) = Self::extract(child.take(), key);
+                    *child = rest;
+                    boxed.recalc();
+                    (Some(boxed), count)
+                }
+            }
+        }
+    }
+
+    fn union_rec(a: Option<Box<Node>>, b: Option<Box<Node>>) -> Option<Box<Node>> {
+        match (a, b) {
+            (None, r) => r,
+            (l, None) => l,
+            (Some(mut a), Some(mut b)) => {
+                // the higher priority root stays on top
+                if a.priority < b.priority {
+                    std::mem::swap(&mut a, &mut b);
+                }
+                let (less, rest) = Self::split(Some(b), a.key);
+                let (greater, same) = Self::extract(rest, a.key);
+                a.count += same;
+                a.left = Self::union_rec(a.left.take(), less);
+                a.right = Self::union_rec(a.right.take(), greater);
+                a.recalc();
+                Some(a)
+            }
+        }
+    }
+
     pub fn inorder_vec(&self) -> Vec<i64> {
         let mut out = Vec::with_capacity(self.len());
         Self::inorder_rec(&self.root, &mut out);
@@ -222,19 +351,92 @@ impl Treap {
     pub fn get_structure(&self) -> Option<NodeInfo> {
         self.root.as_ref().map(|n| n.to_node_info())
     }
+
+    /// Keys of the nodes a search for `key` visits, from the root down to `key` itself or to the
+    /// last node before falling off the tree. Handy for `to_dot_highlighting`.
+    pub fn search_path(&self, key: i64) -> Vec<i64> {
+        let mut path = Vec::new();
+        let mut cur = self.root.as_ref();
+        while let Some(node) = cur {
+            path.push(node.key);
+            cur = match key.cmp(&node.key) {
+                std::cmp::Ordering::Less => node.left.as_ref(),
+                std::cmp::Ordering::Greater => node.right.as_ref(),
+                std::cmp::Ordering::Equal => None,
+            };
+        }
+        path
+    }
+
+    /// Graphviz DOT rendering of the tree; duplicate keys show their count.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighting(&Highlight::new())
+    }
+
+    /// Like `to_dot`, emphasising the highlighted keys and the parent-child links between them.
+    pub fn to_dot_highlighting(&self, highlight: &Highlight<i64>) -> String {
+        let mut w = DotWriter::new("Treap", true);
+        w.attr("node [shape=circle]");
+        if let Some(root) = &self.root {
+            Self::dot_rec(root, &mut w, &mut 0, highlight);
+        }
+        w.finish()
+    }
+
+    /// Writes the subtree and returns the DOT id of `node`.
+    fn dot_rec(node: &Node, w: &mut DotWriter, next_id: &mut usize, highlight: &Highlight<i64>) -> String {
+        let id = format!("n{}", *next_id);
+        *next_id += 1;
+        let label = if node.count > 1 { format!("{} (x{})", node.key, node.count) } else { node.key.to_string() };
+        w.node(&id, &label, highlight.contains(&node.key));
+        let lone_child = node.left.is_some() != node.right.is_some();
+        for child in [&node.left, &node.right] {
+            match child {
+                Some(c) => {
+                    let cid = Self::dot_rec(c, w, next_id, highlight);
+                    w.edge(&id, &cid, None, highlight.links(&node.key, &c.key, false));
+                }
+                // a placeholder keeps a lone child drawn on its own side
+                None if lone_child => {
+                    let pid = format!("n{}", *next_id);
+                    *next_id += 1;
+                    w.invisible(&pid);
+                    w.invisible_edge(&id, &pid);
+                }
+                None => {}
+            }
+        }
+        id
+    }
 }
 
 
+impl DataStructure for Treap {
+    const INFO: Info = Info {
+        name: "Treap",
+        category: "ordered sets",
+        summary: "Randomized balanced BST over i64 keys with order statistics, split and union",
+        space: "O(n)",
+        operations: &[
+            Operation::new("insert", "O(log n) expected", "O(1)"),
+            Operation::new("remove", "O(log n) expected", "O(1)"),
+            Operation::new("contains", "O(log n) expected", "O(1)"),
+            Operation::new("kth", "O(log n) expected", "O(1)"),
+            Operation::new("split_off", "O(log n) expected", "O(1)"),
+            Operation::new("union", "O(m log(n / m)) expected", "O(log n)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_insert_contains_len_inorder() {
-        // reset seed for deterministic behavior in tests
-        SPLITMIX64_SEED.store(12345, Ordering::Relaxed);
-
-        let mut t = Treap::new();
+        let mut t = Treap::with_seed(12345);
         assert!(t.is_empty());
         t.insert(5);
         t.insert(3);
@@ -246,12 +448,36 @@ mod tests {
         assert!(t.contains(7));
         assert!(!t.contains(42));
         assert_eq!(t.inorder_vec(), vec![3, 3, 5, 7]);
+        assert_eq!(t.min(), Some(3));
+        assert_eq!(t.max(), Some(7));
+        assert_eq!(Treap::new().max(), None);
+    }
+
+    #[test]
+    fn test_kth() {
+        let mut t = Treap::with_seed(77);
+        for v in [8, 2, 5, 2, 9] {
+            t.insert(v);
+        }
+        let ordered: Vec<Option<i64>> = (0..6).map(|k| t.kth(k)).collect();
+        assert_eq!(ordered, vec![Some(2), Some(2), Some(5), Some(8), Some(9), None]);
+    }
+
+    #[test]
+    fn test_unseeded_treaps_draw_distinct_priorities() {
+        let (mut a, mut b) = (Treap::new(), Treap::new());
+        a.insert(1);
+        b.insert(1);
+        assert_ne!(a.get_structure().unwrap().priority, b.get_structure().unwrap().priority);
+        let (mut c, mut d) = (Treap::with_seed(9), Treap::with_seed(9));
+        c.insert(1);
+        d.insert(1);
+        assert_eq!(c.get_structure().unwrap().priority, d.get_structure().unwrap().priority);
     }
 
     #[test]
     fn test_remove_and_duplicates() {
-        SPLITMIX64_SEED.store(999, Ordering::Relaxed);
-        let mut t = Treap::new();
+        let mut t = Treap::with_seed(999);
         t.insert(10);
         t.insert(10);
         t.insert(5);
@@ -268,10 +494,37 @@ mod tests {
         assert_eq!(t.inorder_vec(), vec![5, 15]);
     }
 
+    #[test]
+    fn test_pop_split_union() {
+        let mut t = Treap::with_seed(4242);
+        for v in [5, 1, 9, 5, 3] {
+            t.insert(v);
+        }
+        assert_eq!(t.pop_min(), Some(1));
+        assert_eq!(t.pop_max(), Some(9));
+        assert_eq!(t.inorder_vec(), vec![3, 5, 5]);
+
+        let high = t.split_off(5);
+        assert_eq!(t.inorder_vec(), vec![3]);
+        assert_eq!(high.inorder_vec(), vec![5, 5]);
+
+        let mut other = Treap::new();
+        for v in [7, 5, 2, 3] {
+            other.insert(v);
+        }
+        t.union(high);
+        t.union(other);
+        assert_eq!(t.inorder_vec(), vec![2, 3, 3, 5, 5, 5, 7]);
+        assert_eq!(t.len(), 7);
+        assert_eq!(t.kth(4), Some(5));
+        while t.pop_min().is_some() {}
+        assert!(t.is_empty());
+        assert_eq!(t.pop_max(), None);
+    }
+
     #[test]
     fn test_mass_inserts_removes_stability() {
-        SPLITMIX64_SEED.store(0xFEED, Ordering::Relaxed);
-        let mut t = Treap::new();
+        let mut t = Treap::with_seed(0xFEED);
         for v in 0..100 {
             t.insert(v);
         }
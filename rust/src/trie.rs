@@ -0,0 +1,245 @@
+// rust/src/trie.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::collections::BTreeMap;
+
+struct Node<V> {
+    children: BTreeMap<u8, usize>,
+    value: Option<V>,
+    // number of keys stored in this subtree
+    count: usize,
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Node {
+            children: BTreeMap::new(),
+            value: None,
+            count: 0,
+        }
+    }
+}
+
+/// A prefix tree mapping string keys to values, one node per byte.
+/// Children are kept in a BTreeMap, so iteration yields keys in sorted order, and every node counts
+/// the keys below it so prefix counting does not need to walk the subtree.
+/// - insert / get: O(|key| log σ)
+/// - count_prefix: O(|prefix| log σ)
+/// - longest_prefix_match: O(|query| log σ)
+/// - iter_prefix: O(|prefix| log σ) plus O(1) amortized per visited node
+///
+/// Nodes live in an arena (`Vec`) and are addressed by index.
+pub struct Trie<V> {
+    nodes: Vec<Node<V>>,
+}
+
+impl<V> Default for Trie<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> Trie<V> {
+    pub fn new() -> Self {
+        Trie { nodes: vec![Node::new()] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn find_node(&self, key: &[u8]) -> Option<usize> {
+        let mut cur = 0;
+        for b in key {
+            cur = *self.nodes[cur].children.get(b)?;
+        }
+        Some(cur)
+    }
+
+    /// Inserts `key`, returning the previous value if the key was already present.
+    pub fn insert(&mut self, key: &str, value: V) -> Option<V> {
+        if let Some(node) = self.find_node(key.as_bytes())
+            && let Some(old) = self.nodes[node].value.as_mut()
+        {
+            return Some(std::mem::replace(old, value));
+        }
+        let mut cur = 0;
+        self.nodes[0].count += 1;
+        for &b in key.as_bytes() {
+            cur = match self.nodes[cur].children.get(&b) {
+                Some(&next) => next,
+                None => {
+                    self.nodes.push(Node::new());
+                    let next = self.nodes.len() - 1;
+                    self.nodes[cur].children.insert(b, next);
+                    next
+                }
+            };
+            self.nodes[cur].count += 1;
+        }
+        self.nodes[cur].value = Some(value);
+        None
+    }
+
+    pub fn get(&self, key: &str) -> Option<&V> {
+        self.nodes[self.find_node(key.as_bytes())?].value.as_ref()
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut V> {
+        let node = self.find_node(key.as_bytes())?;
+        self.nodes[node].value.as_mut()
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Number of stored keys starting with `prefix` (including `prefix` itself).
+    pub fn count_prefix(&self, prefix: &str) -> usize {
+        self.find_node(prefix.as_bytes()).map_or(0, |node| self.nodes[node].count)
+    }
+
+    /// The longest stored key that is a prefix of `query`, returned as a slice of `query`.
+    pub fn longest_prefix_match<'q>(&self, query: &'q str) -> Option<(&'q str, &V)> {
+        let mut cur = 0;
+        let mut best = self.nodes[0].value.as_ref().map(|v| (0, v));
+        for (i, b) in query.bytes().enumerate() {
+            match self.nodes[cur].children.get(&b) {
+                Some(&next) => cur = next,
+                None => break,
+            }
+            if let Some(v) = self.nodes[cur].value.as_ref() {
+                best = Some((i + 1, v));
+            }
+        }
+        // stored keys are valid UTF-8, so a key that is a prefix of `query` ends on a char boundary
+        best.map(|(len, v)| (&query[..len], v))
+    }
+
+    /// Keys starting with `prefix` and their values, in sorted order.
+    pub fn iter_prefix(&self, prefix: &str) -> PrefixIter<'_, V> {
+        let stack = match self.find_node(prefix.as_bytes()) {
+            Some(node) => vec![(node, prefix.len(), None)],
+            None => vec![],
+        };
+        PrefixIter {
+            trie: self,
+            stack,
+            key: prefix.as_bytes().to_vec(),
+        }
+    }
+
+    /// All entries in sorted key order.
+    pub fn iter(&self) -> PrefixIter<'_, V> {
+        self.iter_prefix("")
+    }
+
+    /// Keys starting with `prefix`, in sorted order.
+    pub fn keys_with_prefix(&self, prefix: &str) -> Vec<String> {
+        self.iter_prefix(prefix).map(|(k, _)| k).collect()
+    }
+}
+
+/// Pre-order walk of a subtree, which visits keys in sorted order.
+pub struct PrefixIter<'a, V> {
+    trie: &'a Trie<V>,
+    // (node, key length above the node, byte on the edge into it)
+    stack: Vec<(usize, usize, Option<u8>)>,
+    key: Vec<u8>,
+}
+
+impl<'a, V> Iterator for PrefixIter<'a, V> {
+    type Item = (String, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, depth, byte)) = self.stack.pop() {
+            self.key.truncate(depth);
+            self.key.extend(byte);
+            let n = &self.trie.nodes[node];
+            let child_depth = self.key.len();
+            self.stack.extend(n.children.iter().rev().map(|(&b, &c)| (c, child_depth, Some(b))));
+            if let Some(v) = n.value.as_ref() {
+                let key = String::from_utf8(self.key.clone()).expect("keys are inserted as str");
+                return Some((key, v));
+            }
+        }
+        None
+    }
+}
+
+impl<V> DataStructure for Trie<V> {
+    const INFO: Info = Info {
+        name: "Trie",
+        category: "strings",
+        summary: "Prefix tree with one node per byte and per-node key counts",
+        space: "O(total key length)",
+        operations: &[
+            Operation::new("insert", "O(|key| log σ)", "O(|key|)"),
+            Operation::new("get", "O(|key| log σ)", "O(1)"),
+            Operation::new("count_prefix", "O(|prefix| log σ)", "O(1)"),
+            Operation::new("longest_prefix_match", "O(|query| log σ)", "O(1)"),
+            Operation::new("iter_prefix", "O(|prefix| log σ) + O(1) per node", "O(depth)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_get() {
+        let mut t = Trie::new();
+        assert!(t.is_empty());
+        assert_eq!(t.insert("tea", 1), None);
+        assert_eq!(t.insert("ten", 2), None);
+        assert_eq!(t.insert("te", 3), None);
+        assert_eq!(t.insert("tea", 4), Some(1));
+        assert_eq!(t.len(), 3);
+        assert_eq!(t.get("tea"), Some(&4));
+        assert_eq!(t.get("t"), None);
+        assert!(!t.contains_key("teas"));
+        *t.get_mut("ten").unwrap() += 10;
+        assert_eq!(t.get("ten"), Some(&12));
+    }
+
+    #[test]
+    fn test_prefix_queries() {
+        let mut t = Trie::new();
+        for (i, w) in ["apple", "app", "apply", "banana", "band", "ape"].iter().enumerate() {
+            t.insert(w, i);
+        }
+        assert_eq!(t.count_prefix("ap"), 4);
+        assert_eq!(t.count_prefix("app"), 3);
+        assert_eq!(t.count_prefix(""), 6);
+        assert_eq!(t.count_prefix("c"), 0);
+        assert_eq!(t.keys_with_prefix("ban"), vec!["banana", "band"]);
+        assert_eq!(t.keys_with_prefix("app"), vec!["app", "apple", "apply"]);
+        assert!(t.keys_with_prefix("x").is_empty());
+        let all: Vec<String> = t.iter().map(|(k, _)| k).collect();
+        assert_eq!(all, vec!["ape", "app", "apple", "apply", "banana", "band"]);
+    }
+
+    #[test]
+    fn test_longest_prefix_match() {
+        let mut t = Trie::new();
+        t.insert("/", "root");
+        t.insert("/usr", "usr");
+        t.insert("/usr/local", "local");
+        assert_eq!(t.longest_prefix_match("/usr/local/bin"), Some(("/usr/local", &"local")));
+        assert_eq!(t.longest_prefix_match("/usr/lib"), Some(("/usr", &"usr")));
+        assert_eq!(t.longest_prefix_match("/etc"), Some(("/", &"root")));
+        assert_eq!(t.longest_prefix_match("etc"), None);
+
+        let mut u = Trie::new();
+        u.insert("日本", ());
+        assert_eq!(u.longest_prefix_match("日本語").map(|(k, _)| k), Some("日本"));
+        assert_eq!(u.keys_with_prefix("日"), vec!["日本"]);
+    }
+}
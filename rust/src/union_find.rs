@@ -1,7 +1,10 @@
+use crate::DataStructure;
+use crate::dot::{DotWriter, Highlight};
+use crate::info::{Info, Operation};
+
 /// A Union Find data structure, also known as a Disjoint Set Union (DSU)
 /// It tracks a set of elements partitioned into a number of disjoint (non overlapping) subsets
 /// This implementation uses path compression and union by size for near consstant time complexity
-
 pub struct UnionFind {
     parent: Vec<usize>,
     size: Vec<usize>,
@@ -67,6 +70,43 @@ impl UnionFind {
     pub fn get_parents(&self) -> Vec<usize> {
         self.parent.clone()
     }
+
+    /// Graphviz DOT rendering of the parent forest as it currently is (path compression included),
+    /// with an arrow from each element to its parent and set sizes on the roots.
+    pub fn to_dot(&self) -> String {
+        self.to_dot_highlighting(&Highlight::new())
+    }
+
+    /// Like `to_dot`, emphasising the highlighted elements and the parent links between them.
+    pub fn to_dot_highlighting(&self, highlight: &Highlight) -> String {
+        let mut w = DotWriter::new("UnionFind", true);
+        w.attr("rankdir=BT");
+        w.attr("node [shape=circle]");
+        for (v, &p) in self.parent.iter().enumerate() {
+            let label = if p == v { format!("{}\nsize {}", v, self.size[v]) } else { v.to_string() };
+            w.node(&format!("e{}", v), &label, highlight.contains(&v));
+        }
+        for (v, &p) in self.parent.iter().enumerate().filter(|&(v, &p)| v != p) {
+            w.edge(&format!("e{}", v), &format!("e{}", p), None, highlight.links(&v, &p, false));
+        }
+        w.finish()
+    }
+}
+
+impl DataStructure for UnionFind {
+    const INFO: Info = Info {
+        name: "UnionFind",
+        category: "disjoint sets",
+        summary: "Disjoint set union with path compression and union by size",
+        space: "O(n)",
+        operations: &[
+            Operation::new("find", "O(α(n)) amortized", "O(1)"),
+            Operation::new("union", "O(α(n)) amortized", "O(1)"),
+            Operation::new("connected", "O(α(n)) amortized", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
 }
 
 // Unit Test 
@@ -91,7 +131,7 @@ mod tests {
         assert!(!uf.connected(1, 9));
         assert_eq!(uf.count(), 7);
         
-        assert_eq!(uf.union(1, 3), false);
+        assert!(!uf.union(1, 3));
         assert_eq!(uf.count(), 7);
     }
 }
@@ -0,0 +1,371 @@
+// rust/src/van_emde_boas.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+use std::collections::HashMap;
+
+/// Universes of at most 2^LEAF_BITS keys are a single bitmap word.
+const LEAF_BITS: u32 = 6;
+
+enum Node {
+    Leaf(u64),
+    Branch(Box<Branch>),
+}
+
+/// A non-empty vEB node over `low_bits + high_bits`-bit keys. `min` is kept out of the clusters (which is what
+/// makes insertion into an empty cluster O(1) and the recursion single-branched); `max` is also
+/// stored in its cluster.
+struct Branch {
+    min: u64,
+    max: u64,
+    low_bits: u32,
+    high_bits: u32,
+    // high halves of the keys in `clusters`, present iff some cluster is
+    summary: Option<Node>,
+    clusters: HashMap<u64, Node>,
+}
+
+impl Branch {
+    fn split(&self, x: u64) -> (u64, u64) {
+        (x >> self.low_bits, x & ((1u64 << self.low_bits) - 1))
+    }
+
+    fn join(&self, high: u64, low: u64) -> u64 {
+        high << self.low_bits | low
+    }
+
+    fn cluster(&self, high: u64) -> &Node {
+        self.clusters.get(&high).expect("summary lists only non-empty clusters")
+    }
+}
+
+impl Node {
+    /// The node holding just `x`, over `bits`-bit keys.
+    fn singleton(bits: u32, x: u64) -> Node {
+        if bits <= LEAF_BITS {
+            Node::Leaf(1u64 << x)
+        } else {
+            Node::Branch(Box::new(Branch { min: x, max: x, low_bits: bits / 2, high_bits: bits - bits / 2, summary: None, clusters: HashMap::new() }))
+        }
+    }
+
+    fn min(&self) -> u64 {
+        match self {
+            Node::Leaf(m) => m.trailing_zeros() as u64,
+            Node::Branch(b) => b.min,
+        }
+    }
+
+    fn max(&self) -> u64 {
+        match self {
+            Node::Leaf(m) => 63 - m.leading_zeros() as u64,
+            Node::Branch(b) => b.max,
+        }
+    }
+
+    fn contains(&self, x: u64) -> bool {
+        match self {
+            Node::Leaf(m) => m >> x & 1 == 1,
+            Node::Branch(b) => {
+                let (h, l) = b.split(x);
+                x == b.min || x == b.max || b.clusters.get(&h).is_some_and(|c| c.contains(l))
+            }
+        }
+    }
+
+    /// Adds `x`; returns whether it was absent.
+    fn insert(&mut self, mut x: u64) -> bool {
+        let b = match self {
+            Node::Leaf(m) => {
+                let fresh = *m >> x & 1 == 0;
+                *m |= 1u64 << x;
+                return fresh;
+            }
+            Node::Branch(b) => b,
+        };
+        if x == b.min || x == b.max {
+            return false;
+        }
+        if x < b.min {
+            // the new minimum stays outside; the old one moves down into its cluster
+            std::mem::swap(&mut x, &mut b.min);
+        }
+        b.max = b.max.max(x);
+        let (h, l) = b.split(x);
+        match b.clusters.get_mut(&h) {
+            Some(c) => c.insert(l),
+            None => {
+                b.clusters.insert(h, Node::singleton(b.low_bits, l));
+                match &mut b.summary {
+                    Some(s) => s.insert(h),
+                    None => {
+                        b.summary = Some(Node::singleton(b.high_bits, h));
+                        true
+                    }
+                }
+            }
+        }
+    }
+
+    /// Removes `x`. Returns whether it was present, and whether the node is now empty (and must
+    /// be dropped by its parent).
+    fn remove(&mut self, mut x: u64) -> (bool, bool) {
+        let b = match self {
+            Node::Leaf(m) => {
+                let present = *m >> x & 1 == 1;
+                *m &= !(1u64 << x);
+                return (present, *m == 0);
+            }
+            Node::Branch(b) => b,
+        };
+        if x < b.min || x > b.max {
+            return (false, false);
+        }
+        if x == b.min {
+            let Some(summary) = &b.summary else {
+                // min was the only key
+                return (true, true);
+            };
+            // pull the smallest clustered key up to be the new min, then remove it below
+            let h = summary.min();
+            x = b.join(h, b.cluster(h).min());
+            b.min = x;
+        }
+        let (h, l) = b.split(x);
+        let Some(cluster) = b.clusters.get_mut(&h) else { return (false, false) };
+        let (removed, emptied) = cluster.remove(l);
+        if !removed {
+            return (false, false);
+        }
+        if emptied {
+            b.clusters.remove(&h);
+            let summary = b.summary.as_mut().expect("a cluster existed");
+            if summary.remove(h).1 {
+                b.summary = None;
+            }
+        }
+        if x == b.max {
+            b.max = match &b.summary {
+                Some(s) => b.join(s.max(), b.cluster(s.max()).max()),
+                None => b.min,
+            };
+        }
+        (true, false)
+    }
+
+    /// Smallest key greater than `x`.
+    fn successor(&self, x: u64) -> Option<u64> {
+        let b = match self {
+            Node::Leaf(m) => {
+                let above = if x >= 63 { 0 } else { m >> (x + 1) << (x + 1) };
+                return (above != 0).then(|| above.trailing_zeros() as u64);
+            }
+            Node::Branch(b) => b,
+        };
+        if x < b.min {
+            return Some(b.min);
+        }
+        let (h, l) = b.split(x);
+        if let Some(c) = b.clusters.get(&h)
+            && l < c.max()
+        {
+            return c.successor(l).map(|s| b.join(h, s));
+        }
+        let next = b.summary.as_ref()?.successor(h)?;
+        Some(b.join(next, b.cluster(next).min()))
+    }
+
+    /// Largest key smaller than `x`.
+    fn predecessor(&self, x: u64) -> Option<u64> {
+        let b = match self {
+            Node::Leaf(m) => {
+                let below = m & ((1u64 << x) - 1);
+                return (below != 0).then(|| 63 - below.leading_zeros() as u64);
+            }
+            Node::Branch(b) => b,
+        };
+        if x > b.max {
+            return Some(b.max);
+        }
+        let (h, l) = b.split(x);
+        if let Some(c) = b.clusters.get(&h)
+            && l > c.min()
+        {
+            return c.predecessor(l).map(|p| b.join(h, p));
+        }
+        match b.summary.as_ref().and_then(|s| s.predecessor(h)) {
+            Some(prev) => Some(b.join(prev, b.cluster(prev).max())),
+            // the min lives outside the clusters
+            None => (x > b.min).then_some(b.min),
+        }
+    }
+}
+
+/// Van Emde Boas tree: a set of integers from the universe `0..2^bits` with O(log log U)
+/// insert, remove, predecessor and successor. A key splits into a high half choosing a cluster and
+/// a low half inside it; a summary vEB over the high halves finds the next non-empty cluster, and
+/// keeping each node's minimum out of its clusters leaves only one recursive call per level.
+/// Clusters are created lazily in hash maps, so space is O(n) rather than O(U), and universes
+/// of up to 64 keys bottom out in a bitmap word.
+/// - insert / remove / successor / predecessor: O(log log U) expected
+/// - contains: O(log log U) expected; min / max: O(1)
+pub struct VanEmdeBoas {
+    bits: u32,
+    root: Option<Node>,
+    len: usize,
+}
+
+impl VanEmdeBoas {
+    /// An empty set over `0..2^bits`, for `bits` in `1..=64`.
+    pub fn new(bits: u32) -> Self {
+        assert!((1..=64).contains(&bits), "universe must have 1 to 64 bits, got {}", bits);
+        VanEmdeBoas { bits, root: None, len: 0 }
+    }
+
+    /// Largest key the universe allows.
+    pub fn max_key(&self) -> u64 {
+        u64::MAX >> (64 - self.bits)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    fn check(&self, x: u64) {
+        assert!(x <= self.max_key(), "key {} outside the {}-bit universe", x, self.bits);
+    }
+
+    /// Adds `x`; returns false if it was already present.
+    pub fn insert(&mut self, x: u64) -> bool {
+        self.check(x);
+        let fresh = match &mut self.root {
+            Some(root) => root.insert(x),
+            None => {
+                self.root = Some(Node::singleton(self.bits, x));
+                true
+            }
+        };
+        self.len += fresh as usize;
+        fresh
+    }
+
+    /// Removes `x`; returns whether it was present.
+    pub fn remove(&mut self, x: u64) -> bool {
+        if x > self.max_key() {
+            return false;
+        }
+        let Some(root) = &mut self.root else { return false };
+        let (removed, emptied) = root.remove(x);
+        if emptied {
+            self.root = None;
+        }
+        self.len -= removed as usize;
+        removed
+    }
+
+    pub fn contains(&self, x: u64) -> bool {
+        x <= self.max_key() && self.root.as_ref().is_some_and(|r| r.contains(x))
+    }
+
+    pub fn min(&self) -> Option<u64> {
+        self.root.as_ref().map(Node::min)
+    }
+
+    pub fn max(&self) -> Option<u64> {
+        self.root.as_ref().map(Node::max)
+    }
+
+    /// Smallest key greater than `x`.
+    pub fn successor(&self, x: u64) -> Option<u64> {
+        if x >= self.max_key() {
+            return None;
+        }
+        self.root.as_ref()?.successor(x)
+    }
+
+    /// Largest key smaller than `x`.
+    pub fn predecessor(&self, x: u64) -> Option<u64> {
+        if x > self.max_key() {
+            return self.max();
+        }
+        self.root.as_ref()?.predecessor(x)
+    }
+
+    /// Keys in increasing order, by repeated successor queries.
+    pub fn iter(&self) -> impl Iterator<Item = u64> + '_ {
+        std::iter::successors(self.min(), |&x| self.successor(x))
+    }
+}
+
+impl DataStructure for VanEmdeBoas {
+    const INFO: Info = Info {
+        name: "VanEmdeBoas",
+        category: "sets",
+        summary: "Integer set over a 2^bits universe with doubly logarithmic successor queries (U = universe size)",
+        space: "O(n)",
+        operations: &[
+            Operation::new("insert", "O(log log U) expected", "O(log log U)"),
+            Operation::new("remove", "O(log log U) expected", "O(log log U)"),
+            Operation::new("successor / predecessor", "O(log log U) expected", "O(1)"),
+            Operation::new("min / max", "O(1)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_set_operations() {
+        let mut set = VanEmdeBoas::new(16);
+        for x in [3, 65535, 1000, 4, 70] {
+            assert!(set.insert(x));
+        }
+        assert!(!set.insert(70));
+        assert_eq!((set.len(), set.min(), set.max()), (5, Some(3), Some(65535)));
+        assert_eq!((set.successor(4), set.successor(70), set.successor(65535)), (Some(70), Some(1000), None));
+        assert_eq!((set.predecessor(3), set.predecessor(999), set.predecessor(65535)), (None, Some(70), Some(1000)));
+        assert!(set.remove(3) && !set.remove(3) && !set.remove(5));
+        assert_eq!(set.min(), Some(4));
+        assert!(set.remove(65535));
+        assert_eq!(set.iter().collect::<Vec<_>>(), vec![4, 70, 1000]);
+
+        let mut full = VanEmdeBoas::new(64);
+        full.insert(u64::MAX);
+        full.insert(0);
+        assert_eq!((full.successor(0), full.predecessor(u64::MAX)), (Some(u64::MAX), Some(0)));
+        assert!(full.remove(0) && full.remove(u64::MAX) && full.is_empty());
+    }
+
+    #[test]
+    fn test_against_btreeset() {
+        let mut next = test_rng(59);
+        for bits in [5, 13, 40] {
+            let mut set = VanEmdeBoas::new(bits);
+            let mut reference = BTreeSet::new();
+            // keys bunched into a small window, so clusters fill and empty again
+            let window = 1u64 << bits.min(12);
+            let base = next() & set.max_key() & !(window - 1);
+            for _ in 0..20_000 {
+                let x = base + next() % window;
+                match next() % 4 {
+                    0 => assert_eq!(set.insert(x), reference.insert(x)),
+                    1 => assert_eq!(set.remove(x), reference.remove(&x)),
+                    2 => assert_eq!(set.successor(x), reference.range(x + 1..).next().copied()),
+                    _ => assert_eq!(set.predecessor(x), reference.range(..x).next_back().copied()),
+                }
+                assert_eq!((set.len(), set.min()), (reference.len(), reference.first().copied()));
+                assert_eq!(set.max(), reference.last().copied());
+            }
+            assert!(set.iter().eq(reference.iter().copied()));
+        }
+    }
+}
@@ -0,0 +1,204 @@
+// rust/src/wavelet_tree.rs
+use crate::DataStructure;
+use crate::bit_vector::BitVector;
+use crate::info::{Info, Operation};
+use crate::range::resolve;
+use std::ops::{Bound, Range, RangeBounds};
+
+/// Wavelet tree over an `i64` sequence, stored level by level without pointers. Values are first
+/// replaced by their rank among the distinct values (symbols `0..sigma`). Level `d` holds bit `d`
+/// (from the top) of every symbol, with the sequence stably partitioned by the bits above it, so
+/// each tree node is a contiguous run: its left child is its zeros and its right child its ones,
+/// in the same order. Descending maps positions with `BitVector` ranks.
+/// - Build: O(n log sigma)
+/// - get / rank / quantile / range_count: O(log sigma)
+pub struct WaveletTree {
+    values: Vec<i64>,
+    levels: Vec<BitVector>,
+    len: usize,
+}
+
+impl WaveletTree {
+    pub fn new(seq: &[i64]) -> Self {
+        let mut values = seq.to_vec();
+        values.sort_unstable();
+        values.dedup();
+        let depth = (usize::BITS - values.len().saturating_sub(1).leading_zeros()) as usize;
+        let mut symbols: Vec<usize> = seq.iter().map(|v| values.binary_search(v).expect("value was collected")).collect();
+        let mut levels = Vec::with_capacity(depth);
+        for d in 0..depth {
+            let shift = depth - 1 - d;
+            levels.push(symbols.iter().map(|&s| s >> shift & 1 == 1).collect());
+            // stable, so every node splits into its zeros followed by its ones
+            symbols.sort_by_key(|&s| s >> shift);
+        }
+        WaveletTree { values, levels, len: seq.len() }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Moves the node `[s, e)` and the positions `ps` (inside it) of level `d` into the child
+    /// `bit` leads to.
+    fn descend<const N: usize>(&self, d: usize, node: Range<usize>, ps: [usize; N], bit: bool) -> (Range<usize>, [usize; N]) {
+        let level = &self.levels[d];
+        let zeros = level.rank0(node.end) - level.rank0(node.start);
+        if bit {
+            let start = node.start + zeros;
+            (start..node.end, ps.map(|p| start + level.rank1(p) - level.rank1(node.start)))
+        } else {
+            (node.start..node.start + zeros, ps.map(|p| node.start + level.rank0(p) - level.rank0(node.start)))
+        }
+    }
+
+    pub fn get(&self, i: usize) -> i64 {
+        assert!(i < self.len, "index {} out of bounds for length {}", i, self.len);
+        let (mut node, mut p, mut symbol) = (0..self.len, [i], 0);
+        for d in 0..self.levels.len() {
+            let bit = self.levels[d].get(p[0]);
+            symbol = symbol << 1 | bit as usize;
+            (node, p) = self.descend(d, node, p, bit);
+        }
+        self.values[symbol]
+    }
+
+    /// Occurrences of `value` among the first `i` elements.
+    pub fn rank(&self, value: i64, i: usize) -> usize {
+        assert!(i <= self.len, "rank position {} out of bounds for length {}", i, self.len);
+        let Ok(symbol) = self.values.binary_search(&value) else { return 0 };
+        let depth = self.levels.len();
+        let (mut node, mut p) = (0..self.len, [i]);
+        for d in 0..depth {
+            (node, p) = self.descend(d, node, p, symbol >> (depth - 1 - d) & 1 == 1);
+        }
+        p[0] - node.start
+    }
+
+    /// The `k`-th smallest value (0-based) in `range`, or None if the range has at most `k` elements.
+    pub fn quantile<R: RangeBounds<usize>>(&self, range: R, mut k: usize) -> Option<i64> {
+        let r = resolve(range, self.len);
+        if k >= r.len() {
+            return None;
+        }
+        let (mut node, mut window, mut symbol) = (0..self.len, [r.start, r.end], 0);
+        for d in 0..self.levels.len() {
+            let level = &self.levels[d];
+            let zeros = level.rank0(window[1]) - level.rank0(window[0]);
+            let bit = k >= zeros;
+            if bit {
+                k -= zeros;
+            }
+            symbol = symbol << 1 | bit as usize;
+            (node, window) = self.descend(d, node, window, bit);
+        }
+        Some(self.values[symbol])
+    }
+
+    /// Elements in the index range `range` whose symbol is below `bound`.
+    fn count_below(&self, range: &Range<usize>, bound: usize) -> usize {
+        if bound >= self.values.len() {
+            return range.len();
+        }
+        let depth = self.levels.len();
+        let (mut node, mut window, mut count) = (0..self.len, [range.start, range.end], 0);
+        for d in 0..depth {
+            let bit = bound >> (depth - 1 - d) & 1 == 1;
+            if bit {
+                let level = &self.levels[d];
+                count += level.rank0(window[1]) - level.rank0(window[0]);
+            }
+            (node, window) = self.descend(d, node, window, bit);
+        }
+        count
+    }
+
+    /// Number of elements in the index range `range` whose value lies in `values`.
+    pub fn range_count<R: RangeBounds<usize>, V: RangeBounds<i64>>(&self, range: R, values: V) -> usize {
+        let r = resolve(range, self.len);
+        // symbols of the values below each end of `values`
+        let lo = match values.start_bound() {
+            Bound::Included(v) => self.values.partition_point(|x| x < v),
+            Bound::Excluded(v) => self.values.partition_point(|x| x <= v),
+            Bound::Unbounded => 0,
+        };
+        let hi = match values.end_bound() {
+            Bound::Included(v) => self.values.partition_point(|x| x <= v),
+            Bound::Excluded(v) => self.values.partition_point(|x| x < v),
+            Bound::Unbounded => self.values.len(),
+        };
+        if lo >= hi {
+            return 0;
+        }
+        self.count_below(&r, hi) - self.count_below(&r, lo)
+    }
+}
+
+impl DataStructure for WaveletTree {
+    const INFO: Info = Info {
+        name: "WaveletTree",
+        category: "range queries",
+        summary: "Static sequence with rank, k-th smallest in a range and range value counting (sigma = distinct values)",
+        space: "n log sigma + o(n log sigma) bits",
+        operations: &[
+            Operation::new("build", "O(n log sigma)", "O(n)"),
+            Operation::new("get", "O(log sigma)", "O(1)"),
+            Operation::new("rank", "O(log sigma)", "O(1)"),
+            Operation::new("quantile", "O(log sigma)", "O(1)"),
+            Operation::new("range_count", "O(log sigma)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_queries() {
+        let seq = [5, 1, 4, 1, 5, 9, 2, 6, 5, 3];
+        let wt = WaveletTree::new(&seq);
+        assert_eq!((0..seq.len()).map(|i| wt.get(i)).collect::<Vec<_>>(), seq);
+        assert_eq!((wt.rank(5, 10), wt.rank(5, 4), wt.rank(1, 2), wt.rank(7, 10)), (3, 1, 1, 0));
+        // seq[3..8] sorted is [1, 2, 5, 6, 9]
+        assert_eq!(wt.quantile(3..8, 0), Some(1));
+        assert_eq!(wt.quantile(3..8, 2), Some(5));
+        assert_eq!(wt.quantile(3..=7, 4), Some(9));
+        assert_eq!(wt.quantile(3..8, 5), None);
+        assert_eq!(wt.range_count(.., 2..6), 6);
+        assert_eq!(wt.range_count(4..9, 5..), 4);
+        assert_eq!(wt.range_count(.., ..=1), 2);
+        assert_eq!(wt.range_count(0..0, ..), 0);
+
+        let single = WaveletTree::new(&[7, 7, 7]);
+        assert_eq!((single.get(1), single.rank(7, 2), single.quantile(.., 2)), (7, 2, Some(7)));
+        assert!(WaveletTree::new(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(53);
+        let n = 300;
+        let seq: Vec<i64> = (0..n).map(|_| (next() % 41) as i64 - 20).collect();
+        let wt = WaveletTree::new(&seq);
+        for _ in 0..500 {
+            let (a, b) = (next() as usize % (n + 1), next() as usize % (n + 1));
+            let (l, r) = (a.min(b), a.max(b));
+            let mut window = seq[l..r].to_vec();
+            window.sort_unstable();
+            let k = next() as usize % 10;
+            assert_eq!(wt.quantile(l..r, k), window.get(k).copied());
+            let (lo, hi) = ((next() % 50) as i64 - 25, (next() % 50) as i64 - 25);
+            assert_eq!(wt.range_count(l..r, lo..hi), window.iter().filter(|&&v| lo <= v && v < hi).count());
+            let v = (next() % 41) as i64 - 20;
+            assert_eq!(wt.rank(v, r), seq[..r].iter().filter(|&&x| x == v).count());
+        }
+    }
+}
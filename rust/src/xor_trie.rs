@@ -0,0 +1,248 @@
+// rust/src/xor_trie.rs
+use crate::DataStructure;
+use crate::info::{Info, Operation};
+
+// child index 0 means "no child": the root is node 0 and is never anyone's child
+#[derive(Clone, Copy, Default)]
+struct Node {
+    children: [usize; 2],
+    // number of stored values passing through this node
+    count: usize,
+}
+
+/// Binary trie over fixed-width integers for XOR queries: a multiset of `bits`-bit values, each
+/// stored as its root-to-leaf path of bits from the most significant down. Since the high bits of
+/// `x ^ y` decide its order, walking down while choosing, at every level, the child that makes the
+/// current bit of `x ^ y` one (or zero) greedily finds the largest (or smallest) XOR with `x`, and
+/// comparing against `k` bit by bit counts the values with `x ^ y < k` by adding up whole subtrees.
+/// Every node counts the values below it, so removal just decrements counts; emptied nodes stay in
+/// the arena and are reused when a value with the same prefix comes back.
+/// - insert / remove / count: O(bits)
+/// - max_xor_with / min_xor_with / count_less_than_xor: O(bits)
+pub struct XorTrie {
+    nodes: Vec<Node>,
+    bits: u32,
+}
+
+impl Default for XorTrie {
+    fn default() -> Self {
+        Self::new(64)
+    }
+}
+
+impl XorTrie {
+    /// An empty trie for values below `2^bits`. Panics unless `1 <= bits <= 64`.
+    pub fn new(bits: u32) -> Self {
+        assert!((1..=64).contains(&bits), "bits must be in 1..=64, got {}", bits);
+        XorTrie { nodes: vec![Node::default()], bits }
+    }
+
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Number of stored values, counting duplicates.
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn check(&self, value: u64) {
+        assert!(self.bits == 64 || value >> self.bits == 0, "{} does not fit in {} bits", value, self.bits);
+    }
+
+    fn bit(&self, value: u64, level: u32) -> usize {
+        (value >> (self.bits - 1 - level) & 1) as usize
+    }
+
+    /// Node for `value`'s full path, if every node on it exists.
+    fn leaf(&self, value: u64) -> Option<usize> {
+        let mut cur = 0;
+        for level in 0..self.bits {
+            cur = self.nodes[cur].children[self.bit(value, level)];
+            if cur == 0 {
+                return None;
+            }
+        }
+        Some(cur)
+    }
+
+    /// Adds one copy of `value`. Panics if it does not fit in `bits` bits.
+    pub fn insert(&mut self, value: u64) {
+        self.check(value);
+        let mut cur = 0;
+        self.nodes[0].count += 1;
+        for level in 0..self.bits {
+            let b = self.bit(value, level);
+            if self.nodes[cur].children[b] == 0 {
+                self.nodes[cur].children[b] = self.nodes.len();
+                self.nodes.push(Node::default());
+            }
+            cur = self.nodes[cur].children[b];
+            self.nodes[cur].count += 1;
+        }
+    }
+
+    /// Removes one copy of `value`; returns false if there was none.
+    pub fn remove(&mut self, value: u64) -> bool {
+        if self.count(value) == 0 {
+            return false;
+        }
+        let mut cur = 0;
+        self.nodes[0].count -= 1;
+        for level in 0..self.bits {
+            cur = self.nodes[cur].children[self.bit(value, level)];
+            self.nodes[cur].count -= 1;
+        }
+        true
+    }
+
+    /// Number of copies of `value`.
+    pub fn count(&self, value: u64) -> usize {
+        if self.bits < 64 && value >> self.bits != 0 {
+            return 0;
+        }
+        self.leaf(value).map_or(0, |node| self.nodes[node].count)
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        self.count(value) > 0
+    }
+
+    /// Walks down choosing, at every level, the child whose bit differs from `x`'s when `differ`
+    /// and that child is nonempty; returns the stored value reached.
+    fn greedy(&self, x: u64, differ: bool) -> Option<u64> {
+        if self.is_empty() {
+            return None;
+        }
+        let (mut cur, mut value) = (0, 0u64);
+        for level in 0..self.bits {
+            let preferred = self.bit(x, level) ^ differ as usize;
+            let child = self.nodes[cur].children[preferred];
+            let b = if child != 0 && self.nodes[child].count > 0 { preferred } else { preferred ^ 1 };
+            cur = self.nodes[cur].children[b];
+            value = value << 1 | b as u64;
+        }
+        Some(value)
+    }
+
+    /// The largest `x ^ y` over stored `y`, with that `y`; None when empty.
+    pub fn max_xor_with(&self, x: u64) -> Option<(u64, u64)> {
+        self.greedy(x, true).map(|y| (x ^ y, y))
+    }
+
+    /// The smallest `x ^ y` over stored `y`, with that `y`; None when empty.
+    pub fn min_xor_with(&self, x: u64) -> Option<(u64, u64)> {
+        self.greedy(x, false).map(|y| (x ^ y, y))
+    }
+
+    /// Number of stored values `y` (counting duplicates) with `x ^ y < k`.
+    pub fn count_less_than_xor(&self, x: u64, k: u64) -> usize {
+        // above the trie's width `x ^ y` has the bits of `x`, so those alone may settle it
+        if self.bits < 64 && x >> self.bits != k >> self.bits {
+            return if x >> self.bits < k >> self.bits { self.len() } else { 0 };
+        }
+        let (mut cur, mut total) = (0, 0);
+        for level in 0..self.bits {
+            let xb = self.bit(x, level);
+            if self.bit(k, level) == 1 {
+                // a zero here already puts `x ^ y` below `k`
+                let same = self.nodes[cur].children[xb];
+                if same != 0 {
+                    total += self.nodes[same].count;
+                }
+                cur = self.nodes[cur].children[xb ^ 1];
+            } else {
+                cur = self.nodes[cur].children[xb];
+            }
+            if cur == 0 {
+                break;
+            }
+        }
+        total
+    }
+}
+
+impl Extend<u64> for XorTrie {
+    fn extend<I: IntoIterator<Item = u64>>(&mut self, iter: I) {
+        iter.into_iter().for_each(|value| self.insert(value));
+    }
+}
+
+impl DataStructure for XorTrie {
+    const INFO: Info = Info {
+        name: "XorTrie",
+        category: "sets",
+        summary: "Binary trie over fixed-width integers for max / min XOR and XOR-below-k counting",
+        space: "O(n * bits)",
+        operations: &[
+            Operation::new("insert / remove", "O(bits)", "O(bits)"),
+            Operation::new("max_xor_with / min_xor_with", "O(bits)", "O(1)"),
+            Operation::new("count_less_than_xor", "O(bits)", "O(1)"),
+        ],
+        stable: None,
+        in_place: None,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::test_rng;
+
+    #[test]
+    fn test_xor_queries() {
+        let mut trie = XorTrie::new(5);
+        trie.extend([3, 10, 5, 25, 2, 8]);
+        // the classic maximum XOR of two numbers: 5 ^ 25 = 28
+        let best = [3, 10, 5, 25, 2, 8].iter().map(|&x| trie.max_xor_with(x).unwrap().0).max();
+        assert_eq!(best, Some(28));
+        assert_eq!(trie.max_xor_with(5), Some((28, 25)));
+        assert_eq!(trie.min_xor_with(9), Some((1, 8)));
+        assert_eq!(trie.count_less_than_xor(0, 9), 4);
+        assert_eq!(trie.count_less_than_xor(0, 1 << 20), 6);
+
+        trie.insert(25);
+        assert!(trie.remove(25) && trie.remove(25) && !trie.remove(25));
+        assert_eq!((trie.count(25), trie.count(99), trie.len()), (0, 0, 5));
+        assert_eq!(trie.max_xor_with(5), Some((15, 10)));
+        for v in [3, 10, 5, 2, 8] {
+            trie.remove(v);
+        }
+        assert_eq!((trie.max_xor_with(5), trie.count_less_than_xor(5, 31)), (None, 0));
+
+        let mut wide = XorTrie::default();
+        wide.extend([u64::MAX, 0]);
+        assert_eq!(wide.max_xor_with(1), Some((u64::MAX - 1, u64::MAX)));
+        assert_eq!(wide.count_less_than_xor(0, u64::MAX), 1);
+    }
+
+    #[test]
+    fn test_against_naive() {
+        let mut next = test_rng(139);
+        let mut trie = XorTrie::new(10);
+        let mut naive: Vec<u64> = Vec::new();
+        for _ in 0..3000 {
+            let v = next() % 1024;
+            if next().is_multiple_of(3) {
+                let expected = naive.iter().position(|&y| y == v).map(|i| naive.swap_remove(i)).is_some();
+                assert_eq!(trie.remove(v), expected);
+            } else {
+                trie.insert(v);
+                naive.push(v);
+            }
+            let (x, k) = (next() % 1024, next() % 1100);
+            assert_eq!(trie.len(), naive.len());
+            assert_eq!(trie.count(v), naive.iter().filter(|&&y| y == v).count());
+            assert_eq!(trie.max_xor_with(x).map(|r| r.0), naive.iter().map(|&y| x ^ y).max());
+            assert_eq!(trie.min_xor_with(x).map(|r| r.0), naive.iter().map(|&y| x ^ y).min());
+            if let Some((m, y)) = trie.max_xor_with(x) {
+                assert!(naive.contains(&y) && m == x ^ y);
+            }
+            assert_eq!(trie.count_less_than_xor(x, k), naive.iter().filter(|&&y| x ^ y < k).count());
+        }
+    }
+}